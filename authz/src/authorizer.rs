@@ -3,7 +3,7 @@ use std::ops::ControlFlow;
 use async_trait::async_trait;
 use backoff::{Backoff, BackoffConfig};
 
-use super::{Error, Permission};
+use super::{Action, Error, Permission, Resource};
 
 /// An authorizer is used to validate a request
 /// (+ associated permissions needed to fulfill the request)
@@ -57,6 +57,47 @@ pub trait Authorizer: std::fmt::Debug + Send + Sync {
             .await
             .expect("retry forever")
     }
+
+    /// Determine whether a request token grants `action` on every one of
+    /// the `tables` of `database`, either through a permission on the
+    /// whole database, or through a permission on each of the tables.
+    ///
+    /// Returns [`Error::Forbidden`] if the token lacks the permission on
+    /// any of the tables, and the errors of [`Authorizer::permissions`]
+    /// otherwise.
+    async fn authorize_tables(
+        &self,
+        token: Option<Vec<u8>>,
+        database: &str,
+        tables: &[&str],
+        action: Action,
+    ) -> Result<(), Error> {
+        let database_perm =
+            Permission::ResourceAction(Resource::Database(database.to_string()), action);
+        let table_perms = tables
+            .iter()
+            .map(|table| {
+                Permission::ResourceAction(
+                    Resource::Table {
+                        database: database.to_string(),
+                        table: table.to_string(),
+                    },
+                    action,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let perms = std::iter::once(database_perm.clone())
+            .chain(table_perms.iter().cloned())
+            .collect::<Vec<_>>();
+        let granted = self.permissions(token, &perms).await?;
+
+        if granted.contains(&database_perm) || table_perms.iter().all(|p| granted.contains(p)) {
+            Ok(())
+        } else {
+            Err(Error::Forbidden)
+        }
+    }
 }
 
 /// Wrapped `Option<dyn Authorizer>`
@@ -0,0 +1,323 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use data_types::{AuthToken, AuthTokenAction, AuthTokenGrant};
+use iox_catalog::interface::Catalog;
+use iox_time::Time;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+use super::{Action, Authorizer, Error, Permission, Resource};
+
+/// The prefix of the secrets of the tokens created by [`generate_token`].
+const TOKEN_PREFIX: &str = "iox_";
+
+/// How long [`CatalogAuthorizer`] caches the tokens it reads from the catalog
+/// by default, and so how long it may accept a token after it was revoked.
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(10);
+
+/// Generate the secret of a new API token.
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    format!("{TOKEN_PREFIX}{}", BASE64_URL_SAFE_NO_PAD.encode(bytes))
+}
+
+/// Hash the secret `token`, as stored in the catalog.
+pub fn hash_token(token: &[u8]) -> Vec<u8> {
+    Sha256::digest(token).to_vec()
+}
+
+/// Authorizer implementation checking tokens against the API tokens stored
+/// in the catalog.
+///
+/// A token grants the permissions of its grants: a read grant on a
+/// namespace grants the [`Action::Read`] and [`Action::ReadSchema`] actions
+/// on the namespace, and a write grant the [`Action::Write`] action. A grant
+/// restricted to a table name, or pattern, grants these actions on the
+/// matching [`Resource::Table`]s only. No token grants the creation or
+/// deletion of namespaces.
+#[derive(Debug)]
+pub struct CatalogAuthorizer {
+    catalog: Arc<dyn Catalog>,
+    cache_ttl: Duration,
+    /// Tokens read from the catalog, by the hash of their secret, with the
+    /// time they were read.
+    cache: Mutex<HashMap<Vec<u8>, (Time, AuthToken)>>,
+}
+
+impl CatalogAuthorizer {
+    /// Create an authorizer checking tokens against `catalog`, caching them
+    /// for `cache_ttl`.
+    pub fn new(catalog: Arc<dyn Catalog>, cache_ttl: Duration) -> Self {
+        Self {
+            catalog,
+            cache_ttl,
+            cache: Default::default(),
+        }
+    }
+
+    /// Return the token with the secret `token`, if it exists.
+    async fn token(&self, token: &[u8]) -> Result<Option<AuthToken>, Error> {
+        let hash = hash_token(token);
+        let now = self.catalog.time_provider().now();
+
+        if let Some((read_at, token)) = self.cache.lock().expect("not poisoned").get(&hash) {
+            if now.checked_duration_since(*read_at).unwrap_or_default() < self.cache_ttl {
+                return Ok(Some(token.clone()));
+            }
+        }
+
+        let token = self
+            .catalog
+            .repositories()
+            .await
+            .auth_tokens()
+            .get_by_hash(&hash)
+            .await
+            .map_err(|e| Error::verification("catalog lookup failed", e))?;
+
+        // Unknown tokens are not cached, so that they cannot fill the cache.
+        let mut cache = self.cache.lock().expect("not poisoned");
+        match &token {
+            Some(token) => cache.insert(hash, (now, token.clone())),
+            None => cache.remove(&hash),
+        };
+
+        Ok(token)
+    }
+}
+
+#[async_trait]
+impl Authorizer for CatalogAuthorizer {
+    async fn permissions(
+        &self,
+        token: Option<Vec<u8>>,
+        requested_perms: &[Permission],
+    ) -> Result<Vec<Permission>, Error> {
+        let token = self
+            .token(&token.ok_or(Error::NoToken)?)
+            .await?
+            .filter(|token| !token.is_revoked())
+            .ok_or(Error::InvalidToken)?;
+
+        let granted_perms: Vec<Permission> = requested_perms
+            .iter()
+            .filter(|perm| is_granted(&token, perm))
+            .cloned()
+            .collect();
+
+        if granted_perms.is_empty() {
+            return Err(Error::Forbidden);
+        }
+        Ok(granted_perms)
+    }
+}
+
+/// Return true if `token` grants `perm`.
+fn is_granted(token: &AuthToken, perm: &Permission) -> bool {
+    token
+        .grants
+        .iter()
+        .flat_map(granted_permissions)
+        .any(|granted| granted.covers(perm))
+}
+
+/// Return the permissions granted by `grant`.
+fn granted_permissions(grant: &AuthTokenGrant) -> impl Iterator<Item = Permission> + '_ {
+    let resource = match &grant.table_name {
+        Some(table) => Resource::Table {
+            database: grant.namespace_name.clone(),
+            table: table.clone(),
+        },
+        None => Resource::Database(grant.namespace_name.clone()),
+    };
+    let actions: &[Action] = match grant.action {
+        AuthTokenAction::Read => &[Action::Read, Action::ReadSchema],
+        AuthTokenAction::Write => &[Action::Write],
+    };
+    actions
+        .iter()
+        .map(move |action| Permission::ResourceAction(resource.clone(), *action))
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use data_types::AuthTokenGrant;
+    use iox_catalog::mem::MemCatalog;
+
+    use super::*;
+
+    fn perm(name: &str, action: Action) -> Permission {
+        Permission::ResourceAction(Resource::Database(name.to_string()), action)
+    }
+
+    async fn create_token(catalog: &Arc<dyn Catalog>, grants: &[&str]) -> (String, AuthToken) {
+        let secret = generate_token();
+        let grants = grants
+            .iter()
+            .map(|g| g.parse::<AuthTokenGrant>().unwrap())
+            .collect::<Vec<_>>();
+        let token = catalog
+            .repositories()
+            .await
+            .auth_tokens()
+            .create(&hash_token(secret.as_bytes()), "test", &grants)
+            .await
+            .unwrap();
+        (secret, token)
+    }
+
+    #[test]
+    fn test_generate_token() {
+        let token = generate_token();
+        assert!(token.starts_with(TOKEN_PREFIX));
+        assert_eq!(token.len(), TOKEN_PREFIX.len() + 43);
+        assert_ne!(token, generate_token());
+    }
+
+    #[tokio::test]
+    async fn test_permissions() {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+        let authz = CatalogAuthorizer::new(Arc::clone(&catalog), DEFAULT_CACHE_TTL);
+        let (secret, _) = create_token(&catalog, &["bananas:read", "platanos:write"]).await;
+        let secret = Some(secret.into_bytes());
+
+        let requested = [
+            perm("bananas", Action::Read),
+            perm("bananas", Action::ReadSchema),
+            perm("bananas", Action::Write),
+            perm("bananas", Action::Create),
+            perm("platanos", Action::Read),
+            perm("platanos", Action::Write),
+            perm("other", Action::Read),
+        ];
+        let granted = authz.permissions(secret.clone(), &requested).await.unwrap();
+        assert_eq!(
+            granted,
+            vec![
+                perm("bananas", Action::Read),
+                perm("bananas", Action::ReadSchema),
+                perm("platanos", Action::Write),
+            ]
+        );
+
+        let err = authz
+            .permissions(secret, &[perm("other", Action::Read)])
+            .await
+            .unwrap_err();
+        assert_matches!(err, Error::Forbidden);
+
+        let err = authz
+            .permissions(None, &[perm("bananas", Action::Read)])
+            .await
+            .unwrap_err();
+        assert_matches!(err, Error::NoToken);
+
+        let err = authz
+            .permissions(Some(b"nope".to_vec()), &[perm("bananas", Action::Read)])
+            .await
+            .unwrap_err();
+        assert_matches!(err, Error::InvalidToken);
+    }
+
+    #[tokio::test]
+    async fn test_table_permissions() {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+        let authz = CatalogAuthorizer::new(Arc::clone(&catalog), DEFAULT_CACHE_TTL);
+        let (secret, _) = create_token(
+            &catalog,
+            &["bananas:cpu_*:write", "bananas:mem:read", "platanos:read"],
+        )
+        .await;
+        let secret = Some(secret.into_bytes());
+
+        let table = |database: &str, table: &str, action| {
+            Permission::ResourceAction(
+                Resource::Table {
+                    database: database.to_string(),
+                    table: table.to_string(),
+                },
+                action,
+            )
+        };
+
+        let requested = [
+            perm("bananas", Action::Write),
+            table("bananas", "cpu_total", Action::Write),
+            table("bananas", "cpu_total", Action::Read),
+            table("bananas", "cpu", Action::Write),
+            table("bananas", "mem", Action::Read),
+            table("bananas", "mem", Action::ReadSchema),
+            table("platanos", "anything", Action::Read),
+        ];
+        let granted = authz.permissions(secret.clone(), &requested).await.unwrap();
+        assert_eq!(
+            granted,
+            vec![
+                table("bananas", "cpu_total", Action::Write),
+                table("bananas", "mem", Action::Read),
+                table("bananas", "mem", Action::ReadSchema),
+                table("platanos", "anything", Action::Read),
+            ]
+        );
+
+        authz
+            .authorize_tables(
+                secret.clone(),
+                "bananas",
+                &["cpu_total", "cpu_idle"],
+                Action::Write,
+            )
+            .await
+            .unwrap();
+        authz
+            .authorize_tables(secret.clone(), "platanos", &["anything"], Action::Read)
+            .await
+            .unwrap();
+        let err = authz
+            .authorize_tables(secret, "bananas", &["cpu_total", "mem"], Action::Write)
+            .await
+            .unwrap_err();
+        assert_matches!(err, Error::Forbidden);
+    }
+
+    #[tokio::test]
+    async fn test_revoked() {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+        let uncached = CatalogAuthorizer::new(Arc::clone(&catalog), Duration::ZERO);
+        let cached = CatalogAuthorizer::new(Arc::clone(&catalog), Duration::from_secs(3600));
+        let (secret, token) = create_token(&catalog, &["bananas:read"]).await;
+        let secret = Some(secret.into_bytes());
+        let perms = [perm("bananas", Action::Read)];
+
+        uncached.permissions(secret.clone(), &perms).await.unwrap();
+        cached.permissions(secret.clone(), &perms).await.unwrap();
+
+        catalog
+            .repositories()
+            .await
+            .auth_tokens()
+            .revoke(token.id)
+            .await
+            .unwrap();
+
+        let err = uncached
+            .permissions(secret.clone(), &perms)
+            .await
+            .unwrap_err();
+        assert_matches!(err, Error::InvalidToken);
+
+        // A cached token is accepted until its cache entry expires.
+        cached.permissions(secret, &perms).await.unwrap();
+    }
+}
@@ -27,6 +27,8 @@ use observability_deps::tracing::warn;
 
 mod authorizer;
 pub use authorizer::Authorizer;
+mod catalog_authorizer;
+pub use catalog_authorizer::{generate_token, hash_token, CatalogAuthorizer, DEFAULT_CACHE_TTL};
 mod iox_authorizer;
 pub use iox_authorizer::{Error, IoxAuthorizer};
 mod instrumentation;
@@ -78,6 +78,7 @@ impl TryFrom<proto::Permission> for Permission {
                     proto::resource_action_permission::ResourceType::try_from(ra.resource_type)
                         .map_err(|_| IncompatiblePermissionError {})?,
                     ra.resource_id,
+                    ra.resource_database,
                 )?;
                 let a = Action::try_from(
                     proto::resource_action_permission::Action::try_from(ra.action)
@@ -96,7 +97,7 @@ impl TryFrom<Permission> for proto::Permission {
     fn try_from(value: Permission) -> Result<Self, Self::Error> {
         match value {
             Permission::ResourceAction(r, a) => {
-                let (rt, ri) = r.try_into_proto()?;
+                let (rt, ri, rd) = r.try_into_proto()?;
                 let a: proto::resource_action_permission::Action = a.into();
                 Ok(Self {
                     permission_one_of: Some(proto::permission::PermissionOneOf::ResourceAction(
@@ -104,6 +105,7 @@ impl TryFrom<Permission> for proto::Permission {
                             resource_type: rt as i32,
                             resource_id: ri,
                             action: a as i32,
+                            resource_database: rd,
                         },
                     )),
                 })
@@ -112,22 +114,74 @@ impl TryFrom<Permission> for proto::Permission {
     }
 }
 
+impl Permission {
+    /// Returns true if `self`, a granted permission, grants the `requested`
+    /// permission.
+    ///
+    /// See [`Resource::covers`] for the resources a granted resource
+    /// covers.
+    pub fn covers(&self, requested: &Self) -> bool {
+        let (
+            Self::ResourceAction(resource, action),
+            Self::ResourceAction(req_resource, req_action),
+        ) = (self, requested);
+        action == req_action && resource.covers(req_resource)
+    }
+}
+
 /// A resource is the object that a request is trying to access.
 #[derive(Clone, Debug, PartialEq)]
 pub enum Resource {
     /// A database is a named IOx database.
     Database(String),
+    /// A table is a named table of a named IOx database.
+    ///
+    /// The table name of a granted table resource may be a pattern: `*`
+    /// matches every table of the database, and a name ending with `*`
+    /// matches every table with the preceding prefix.
+    Table {
+        /// The name of the database containing the table.
+        database: String,
+        /// The name of the table, or a pattern of table names.
+        table: String,
+    },
 }
 
 impl Resource {
+    /// Returns true if `self`, a granted resource, covers the `requested`
+    /// resource.
+    ///
+    /// A database covers itself and all of its tables, and a table covers
+    /// the tables of its database its name, or pattern, matches.
+    pub fn covers(&self, requested: &Self) -> bool {
+        match (self, requested) {
+            (Self::Database(granted), Self::Database(name)) => granted == name,
+            (Self::Database(granted), Self::Table { database, .. }) => granted == database,
+            (
+                Self::Table {
+                    database: granted_database,
+                    table: pattern,
+                },
+                Self::Table { database, table },
+            ) => granted_database == database && table_matches(pattern, table),
+            (Self::Table { .. }, Self::Database(_)) => false,
+        }
+    }
+
     fn try_from_proto(
         rt: proto::resource_action_permission::ResourceType,
         ri: Option<String>,
+        rd: Option<String>,
     ) -> Result<Self, IncompatiblePermissionError> {
-        match (rt, ri) {
-            (proto::resource_action_permission::ResourceType::Database, Some(s)) => {
+        match (rt, ri, rd) {
+            (proto::resource_action_permission::ResourceType::Database, Some(s), _) => {
                 Ok(Self::Database(s))
             }
+            (
+                proto::resource_action_permission::ResourceType::Table,
+                Some(table),
+                Some(database),
+            ) => Ok(Self::Table { database, table }),
             _ => Err(IncompatiblePermissionError {}),
         }
     }
@@ -138,6 +192,7 @@ impl Resource {
         (
             proto::resource_action_permission::ResourceType,
             Option<String>,
+            Option<String>,
         ),
         IncompatiblePermissionError,
     > {
@@ -145,11 +200,26 @@ impl Resource {
             Self::Database(s) => Ok((
                 proto::resource_action_permission::ResourceType::Database,
                 Some(s),
+                None,
+            )),
+            Self::Table { database, table } => Ok((
+                proto::resource_action_permission::ResourceType::Table,
+                Some(table),
+                Some(database),
             )),
         }
     }
 }
 
+/// Returns true if the table name `pattern` of a granted table resource
+/// matches the table `name`.
+fn table_matches(pattern: &str, name: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => pattern == name,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -212,7 +282,8 @@ mod tests {
             Resource::Database("ns1".into()),
             Resource::try_from_proto(
                 proto::resource_action_permission::ResourceType::Database,
-                Some("ns1".into())
+                Some("ns1".into()),
+                None
             )
             .unwrap()
         );
@@ -220,6 +291,7 @@ mod tests {
             IncompatiblePermissionError {},
             Resource::try_from_proto(
                 proto::resource_action_permission::ResourceType::Database,
+                None,
                 None
             )
             .unwrap_err()
@@ -228,8 +300,30 @@ mod tests {
             IncompatiblePermissionError {},
             Resource::try_from_proto(
                 proto::resource_action_permission::ResourceType::Unspecified,
+                Some("ns1".into()),
+                None
+            )
+            .unwrap_err()
+        );
+        assert_eq!(
+            Resource::Table {
+                database: "ns1".into(),
+                table: "cpu".into()
+            },
+            Resource::try_from_proto(
+                proto::resource_action_permission::ResourceType::Table,
+                Some("cpu".into()),
                 Some("ns1".into())
             )
+            .unwrap()
+        );
+        assert_eq!(
+            IncompatiblePermissionError {},
+            Resource::try_from_proto(
+                proto::resource_action_permission::ResourceType::Table,
+                Some("cpu".into()),
+                None
+            )
             .unwrap_err()
         );
     }
@@ -239,10 +333,76 @@ mod tests {
         assert_eq!(
             (
                 proto::resource_action_permission::ResourceType::Database,
-                Some("ns1".into())
+                Some("ns1".into()),
+                None
             ),
             Resource::Database("ns1".into()).try_into_proto().unwrap(),
         );
+        assert_eq!(
+            (
+                proto::resource_action_permission::ResourceType::Table,
+                Some("cpu".into()),
+                Some("ns1".into())
+            ),
+            Resource::Table {
+                database: "ns1".into(),
+                table: "cpu".into()
+            }
+            .try_into_proto()
+            .unwrap(),
+        );
+    }
+
+    #[test]
+    fn resource_covers() {
+        let database = |name: &str| Resource::Database(name.into());
+        let table = |database: &str, table: &str| Resource::Table {
+            database: database.into(),
+            table: table.into(),
+        };
+
+        assert!(database("ns1").covers(&database("ns1")));
+        assert!(!database("ns1").covers(&database("ns2")));
+        assert!(database("ns1").covers(&table("ns1", "cpu")));
+        assert!(!database("ns1").covers(&table("ns2", "cpu")));
+
+        assert!(table("ns1", "cpu").covers(&table("ns1", "cpu")));
+        assert!(!table("ns1", "cpu").covers(&table("ns1", "cpu_total")));
+        assert!(!table("ns1", "cpu").covers(&table("ns2", "cpu")));
+        assert!(!table("ns1", "*").covers(&database("ns1")));
+
+        assert!(table("ns1", "*").covers(&table("ns1", "cpu")));
+        assert!(table("ns1", "cpu_*").covers(&table("ns1", "cpu_total")));
+        assert!(table("ns1", "cpu_*").covers(&table("ns1", "cpu_")));
+        assert!(!table("ns1", "cpu_*").covers(&table("ns1", "cpu")));
+        assert!(!table("ns1", "cpu_*").covers(&table("ns2", "cpu_total")));
+    }
+
+    #[test]
+    fn permission_covers() {
+        let perm = |resource: Resource, action| Permission::ResourceAction(resource, action);
+        let granted = perm(
+            Resource::Table {
+                database: "ns1".into(),
+                table: "cpu_*".into(),
+            },
+            Action::Read,
+        );
+
+        assert!(granted.covers(&perm(
+            Resource::Table {
+                database: "ns1".into(),
+                table: "cpu_total".into(),
+            },
+            Action::Read
+        )));
+        assert!(!granted.covers(&perm(
+            Resource::Table {
+                database: "ns1".into(),
+                table: "cpu_total".into(),
+            },
+            Action::Write
+        )));
     }
 
     #[test]
@@ -255,6 +415,7 @@ mod tests {
                         resource_type: 1,
                         resource_id: Some("ns2".into()),
                         action: 4,
+                        resource_database: None,
                     }
                 ))
             })
@@ -268,6 +429,7 @@ mod tests {
                         resource_type: 0,
                         resource_id: Some("ns2".into()),
                         action: 4,
+                        resource_database: None,
                     }
                 ))
             })
@@ -281,6 +443,7 @@ mod tests {
                         resource_type: 1,
                         resource_id: Some("ns2".into()),
                         action: 0,
+                        resource_database: None,
                     }
                 ))
             })
@@ -297,6 +460,7 @@ mod tests {
                         resource_type: 1,
                         resource_id: Some("ns3".into()),
                         action: 4,
+                        resource_database: None,
                     }
                 ))
             },
@@ -1,5 +1,7 @@
 //! Compactor-Scheduler-related configs.
 
+use std::time::Duration;
+
 /// Compaction Scheduler type.
 #[derive(Debug, Default, Clone, Copy, PartialEq, clap::ValueEnum)]
 pub enum CompactorSchedulerType {
@@ -7,7 +9,8 @@ pub enum CompactorSchedulerType {
     #[default]
     Local,
 
-    /// Perform scheduling decisions remotely.
+    /// Perform scheduling decisions remotely, coordinating with other compactors through leases
+    /// in the catalog.
     Remote,
 }
 
@@ -110,6 +113,26 @@ pub struct CompactorSchedulerConfig {
     /// Shard config used by the local scheduler.
     #[clap(flatten)]
     pub shard_config: ShardConfigForLocalScheduler,
+
+    /// Lease config used by the remote scheduler.
+    #[clap(flatten)]
+    pub lease_config: LeaseConfigForRemoteScheduler,
+}
+
+/// CLI config for the compaction leases of the remote scheduler.
+#[derive(Debug, Clone, Default, clap::Parser)]
+pub struct LeaseConfigForRemoteScheduler {
+    /// How long a compactor's lease on a partition stays valid without being renewed.
+    ///
+    /// Leases are renewed while the partition is compacted, so this bounds how long the
+    /// partitions of a crashed compactor are blocked from being compacted by others.
+    #[clap(
+        long = "compaction-lease-duration",
+        env = "INFLUXDB_IOX_COMPACTION_LEASE_DURATION",
+        default_value = "5m",
+        value_parser = humantime::parse_duration,
+    )]
+    pub lease_duration: Duration,
 }
 
 #[cfg(test)]
@@ -141,6 +164,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn can_specify_remote() {
+        let config = CompactorSchedulerConfig::try_parse_from([
+            "my_binary",
+            "--compactor-scheduler",
+            "remote",
+            "--compaction-lease-duration",
+            "30s",
+        ])
+        .unwrap();
+        assert_eq!(
+            config.compactor_scheduler_type,
+            CompactorSchedulerType::Remote
+        );
+        assert_eq!(config.lease_config.lease_duration, Duration::from_secs(30));
+
+        let config = CompactorSchedulerConfig::try_parse_from(["my_binary"]).unwrap();
+        assert_eq!(
+            config.lease_config.lease_duration,
+            Duration::from_secs(5 * 60)
+        );
+    }
+
     #[test]
     fn any_other_scheduler_type_string_is_invalid() {
         let error = CompactorSchedulerConfig::try_parse_from([
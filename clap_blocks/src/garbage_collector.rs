@@ -1,10 +1,10 @@
 //! Garbage Collector configuration
 use clap::Parser;
 use humantime::parse_duration;
-use std::{fmt::Debug, time::Duration};
+use std::{fmt::Debug, path::PathBuf, time::Duration};
 
 /// Configuration specific to the object store garbage collector
-#[derive(Debug, Clone, Parser, Copy)]
+#[derive(Debug, Clone, Parser)]
 pub struct GarbageCollectorConfig {
     /// If this flag is specified, don't delete the files in object storage. Only print the files
     /// that would be deleted if this flag wasn't specified.
@@ -24,6 +24,38 @@ pub struct GarbageCollectorConfig {
     )]
     pub objectstore_cutoff: Duration,
 
+    /// If specified, orphaned items in the object store are not deleted right away but moved
+    /// under the `quarantine/` prefix, and only deleted once they have been quarantined for this
+    /// duration. This allows recovering files that were orphaned by a catalog mishap.
+    /// Parsed with <https://docs.rs/humantime/latest/humantime/fn.parse_duration.html>
+    ///
+    /// Quarantined items are kept as `quarantine/<unix seconds>/<original location>`. Moving
+    /// them back to their original location restores them.
+    ///
+    /// If not specified, orphaned items are deleted right away.
+    #[clap(
+        long,
+        value_parser = parse_duration,
+        env = "INFLUXDB_IOX_GC_OBJECTSTORE_QUARANTINE"
+    )]
+    pub objectstore_quarantine: Option<Duration>,
+
+    /// If specified, every orphaned item in the object store is appended to this file, together
+    /// with its namespace and table, the reason it was judged orphaned and what was done to it.
+    ///
+    /// This also applies to dry runs.
+    #[clap(long, env = "INFLUXDB_IOX_GC_OBJECTSTORE_REPORT")]
+    pub objectstore_report: Option<PathBuf>,
+
+    /// Format of the `--objectstore-report` file.
+    #[clap(
+        long,
+        value_enum,
+        default_value_t = OrphanReportFormat::Json,
+        env = "INFLUXDB_IOX_GC_OBJECTSTORE_REPORT_FORMAT"
+    )]
+    pub objectstore_report_format: OrphanReportFormat,
+
     /// Number of concurrent object store deletion tasks
     #[clap(
         long,
@@ -82,4 +114,40 @@ pub struct GarbageCollectorConfig {
         env = "INFLUXDB_IOX_GC_RETENTION_SLEEP_INTERVAL_MINUTES"
     )]
     pub retention_sleep_interval_minutes: u64,
+
+    /// Tombstones (recorded deletes) are removed from the catalog once they no longer apply to
+    /// any parquet file and were created longer ago than this duration.
+    /// Parsed with <https://docs.rs/humantime/latest/humantime/fn.parse_duration.html>
+    ///
+    /// This must exceed the longest time an ingester buffers data before persisting it, as
+    /// tombstones also apply to data that has not been persisted yet.
+    ///
+    /// If not specified, defaults to 1 day.
+    #[clap(
+        long,
+        default_value = "1d",
+        value_parser = parse_duration,
+        env = "INFLUXDB_IOX_GC_TOMBSTONE_CUTOFF"
+    )]
+    pub tombstone_cutoff: Duration,
+
+    /// Number of minutes to sleep between iterations of the tombstone removal loop.
+    /// Defaults to 60 minutes.
+    #[clap(
+        long,
+        default_value_t = 60,
+        env = "INFLUXDB_IOX_GC_TOMBSTONE_SLEEP_INTERVAL_MINUTES"
+    )]
+    pub tombstone_sleep_interval_minutes: u64,
+}
+
+/// Format of the orphan report written by the object store garbage collector.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OrphanReportFormat {
+    /// One JSON object per line.
+    #[default]
+    Json,
+
+    /// Comma-separated values with a header line.
+    Csv,
 }
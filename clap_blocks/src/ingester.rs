@@ -35,6 +35,21 @@ pub struct IngesterConfig {
     )]
     pub wal_rotation_period_seconds: u64,
 
+    /// Upload closed write-ahead log files to the object store under this
+    /// prefix, and restore them from there if the WAL directory is empty at
+    /// startup (i.e. because the volume was lost).
+    ///
+    /// The prefix must be unique to each ingester instance, like the WAL
+    /// directory. Writes in the currently open WAL file are not uploaded.
+    ///
+    /// This is disabled by default.
+    #[clap(
+        long = "wal-replication-prefix",
+        env = "INFLUXDB_IOX_WAL_REPLICATION_PREFIX",
+        action
+    )]
+    pub wal_replication_prefix: Option<String>,
+
     /// Sets how many queries the ingester will handle simultaneously before
     /// rejecting further incoming requests.
     #[clap(
@@ -76,6 +91,30 @@ pub struct IngesterConfig {
     )]
     pub persist_hot_partition_cost: usize,
 
+    /// Persist a partition once it has not received a write for this many
+    /// seconds, instead of waiting for the next WAL rotation.
+    ///
+    /// This is disabled by default.
+    #[clap(
+        long = "persist-idle-partition-seconds",
+        env = "INFLUXDB_IOX_PERSIST_IDLE_PARTITION_SECONDS",
+        action
+    )]
+    pub persist_idle_partition_seconds: Option<u64>,
+
+    /// The limit at which the sum of the estimated persistence cost of all
+    /// buffered partitions causes the largest partitions to be queued for
+    /// persistence, until the sum drops below this limit.
+    ///
+    /// The cost is estimated the same way as for
+    /// `--persist-hot-partition-cost`. This is disabled by default.
+    #[clap(
+        long = "persist-buffer-watermark-bytes",
+        env = "INFLUXDB_IOX_PERSIST_BUFFER_WATERMARK_BYTES",
+        action
+    )]
+    pub persist_buffer_watermark_bytes: Option<usize>,
+
     /// Limit the number of partitions that may be buffered in a single
     /// namespace (across all tables) at any one time.
     ///
@@ -3,9 +3,12 @@
 use crate::{
     ingester_address::IngesterAddress,
     memory_size::MemorySize,
-    single_tenant::{CONFIG_AUTHZ_ENV_NAME, CONFIG_AUTHZ_FLAG},
+    single_tenant::{
+        CONFIG_AUTHZ_CATALOG_ENV_NAME, CONFIG_AUTHZ_CATALOG_FLAG, CONFIG_AUTHZ_ENV_NAME,
+        CONFIG_AUTHZ_FLAG,
+    },
 };
-use std::{collections::HashMap, num::NonZeroUsize};
+use std::{collections::HashMap, num::NonZeroUsize, path::PathBuf};
 
 /// CLI config for querier configuration
 #[derive(Debug, Clone, PartialEq, Eq, clap::Parser)]
@@ -14,6 +17,18 @@ pub struct QuerierConfig {
     #[clap(long = CONFIG_AUTHZ_FLAG, env = CONFIG_AUTHZ_ENV_NAME)]
     pub authz_address: Option<String>,
 
+    /// Authorize queries with the API tokens stored in the catalog, instead
+    /// of an external authz service.
+    ///
+    /// Tokens are managed with the `influxdb_iox catalog token` commands.
+    #[clap(
+        long = CONFIG_AUTHZ_CATALOG_FLAG,
+        env = CONFIG_AUTHZ_CATALOG_ENV_NAME,
+        conflicts_with("authz_address"),
+        action
+    )]
+    pub authz_catalog: bool,
+
     /// The number of threads to use for queries.
     ///
     /// If not specified, defaults to the number of cores on the system
@@ -79,6 +94,31 @@ pub struct QuerierConfig {
     )]
     pub ram_pool_data_bytes: MemorySize,
 
+    /// Directory of the optional disk cache used to store data.
+    ///
+    /// Data evicted from the RAM cache is served from this directory instead of the object store.
+    /// The cached data survives restarts, so this should point to a local disk (e.g. an SSD) that
+    /// is not shared with any other process.
+    ///
+    /// If not specified, data is only cached in RAM.
+    #[clap(
+        long = "disk-cache-directory",
+        env = "INFLUXDB_IOX_DISK_CACHE_DIRECTORY",
+        action
+    )]
+    pub disk_cache_directory: Option<PathBuf>,
+
+    /// Size of the disk cache used to store data in bytes.
+    ///
+    /// Only used if `--disk-cache-directory` is set.
+    #[clap(
+        long = "disk-cache-bytes",
+        env = "INFLUXDB_IOX_DISK_CACHE_BYTES",
+        default_value = "10737418240",  // 10GB
+        action
+    )]
+    pub disk_cache_bytes: usize,
+
     /// Limit the number of concurrent queries.
     #[clap(
         long = "max-concurrent-queries",
@@ -128,6 +168,47 @@ pub struct QuerierConfig {
     /// See <https://github.com/influxdata/influxdb_iox/issues/8169>.
     #[clap(long = "v2-ingester-api", env = "INFLUXDB_IOX_V2_INGESTER_API", action)]
     pub v2_ingester_api: bool,
+
+    /// HTTP address of a router that completed queries are written to, e.g.
+    /// "http://127.0.0.1:8080".
+    ///
+    /// Each completed query is written as one line of the `queries` measurement into the
+    /// namespace given by `--query-log-sink-namespace`, including its statistics. This allows
+    /// analyzing the query log across restarts and queriers.
+    ///
+    /// If not specified, the query log is only kept in memory.
+    #[clap(
+        long = "query-log-sink-router-address",
+        env = "INFLUXDB_IOX_QUERY_LOG_SINK_ROUTER_ADDRESS",
+        requires = "query_log_sink_namespace",
+        action
+    )]
+    pub query_log_sink_router_address: Option<String>,
+
+    /// Namespace that completed queries are written to, in the `<org>_<bucket>` form.
+    ///
+    /// Only used if `--query-log-sink-router-address` is set.
+    #[clap(
+        long = "query-log-sink-namespace",
+        env = "INFLUXDB_IOX_QUERY_LOG_SINK_NAMESPACE",
+        requires = "query_log_sink_router_address",
+        action
+    )]
+    pub query_log_sink_namespace: Option<String>,
+
+    /// gRPC address of a router that schema changes requested through the
+    /// query APIs are forwarded to, e.g. "http://127.0.0.1:8081".
+    ///
+    /// This covers InfluxQL statements such as `CREATE DATABASE`, `DELETE`
+    /// and `DROP MEASUREMENT`. The querier never modifies the catalog itself.
+    ///
+    /// If not specified, these statements are rejected.
+    #[clap(
+        long = "router-grpc-address",
+        env = "INFLUXDB_IOX_QUERIER_ROUTER_GRPC_ADDRESS",
+        action
+    )]
+    pub router_grpc_address: Option<String>,
 }
 
 fn parse_datafusion_config(
@@ -174,6 +255,93 @@ mod tests {
         assert_eq!(actual.num_query_threads, None);
         assert!(actual.ingester_addresses.is_empty());
         assert!(actual.datafusion_config.is_empty());
+        assert_eq!(actual.disk_cache_directory, None);
+        assert_eq!(actual.query_log_sink_router_address, None);
+        assert_eq!(actual.query_log_sink_namespace, None);
+        assert_eq!(actual.router_grpc_address, None);
+    }
+
+    #[test]
+    fn test_router_grpc_address() {
+        let actual = QuerierConfig::try_parse_from([
+            "my_binary",
+            "--router-grpc-address",
+            "http://127.0.0.1:8081",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            actual.router_grpc_address.as_deref(),
+            Some("http://127.0.0.1:8081")
+        );
+    }
+
+    #[test]
+    fn test_query_log_sink() {
+        let actual = QuerierConfig::try_parse_from([
+            "my_binary",
+            "--query-log-sink-router-address",
+            "http://127.0.0.1:8080",
+            "--query-log-sink-namespace",
+            "iox_queries",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            actual.query_log_sink_router_address.as_deref(),
+            Some("http://127.0.0.1:8080")
+        );
+        assert_eq!(
+            actual.query_log_sink_namespace.as_deref(),
+            Some("iox_queries")
+        );
+
+        let actual = QuerierConfig::try_parse_from([
+            "my_binary",
+            "--query-log-sink-router-address",
+            "http://127.0.0.1:8080",
+        ])
+        .unwrap_err()
+        .to_string();
+        assert_contains!(
+            actual,
+            "--query-log-sink-namespace <QUERY_LOG_SINK_NAMESPACE>"
+        );
+    }
+
+    #[test]
+    fn test_authz_catalog() {
+        let actual = QuerierConfig::try_parse_from(["my_binary", "--authz-catalog"]).unwrap();
+        assert!(actual.authz_catalog);
+        assert_eq!(actual.authz_address, None);
+
+        let actual = QuerierConfig::try_parse_from([
+            "my_binary",
+            "--authz-catalog",
+            "--authz-addr",
+            "http://127.0.0.1:8080",
+        ])
+        .unwrap_err()
+        .to_string();
+        assert_contains!(actual, "cannot be used with");
+    }
+
+    #[test]
+    fn test_disk_cache() {
+        let actual = QuerierConfig::try_parse_from([
+            "my_binary",
+            "--disk-cache-directory",
+            "/mnt/ssd/cache",
+            "--disk-cache-bytes",
+            "1024",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            actual.disk_cache_directory,
+            Some(PathBuf::from("/mnt/ssd/cache"))
+        );
+        assert_eq!(actual.disk_cache_bytes, 1024);
     }
 
     #[test]
@@ -4,7 +4,8 @@ use crate::{
     gossip::GossipConfig,
     ingester_address::IngesterAddress,
     single_tenant::{
-        CONFIG_AUTHZ_ENV_NAME, CONFIG_AUTHZ_FLAG, CONFIG_CST_ENV_NAME, CONFIG_CST_FLAG,
+        CONFIG_AUTHZ_CATALOG_ENV_NAME, CONFIG_AUTHZ_CATALOG_FLAG, CONFIG_AUTHZ_ENV_NAME,
+        CONFIG_AUTHZ_FLAG, CONFIG_CST_ENV_NAME, CONFIG_CST_FLAG,
     },
 };
 use std::{
@@ -15,6 +16,7 @@ use std::{
 /// CLI config for the router using the RPC write path
 #[derive(Debug, Clone, clap::Parser)]
 #[allow(missing_copy_implementations)]
+#[clap(group(clap::ArgGroup::new("authz").args(["authz_address", "authz_catalog"])))]
 pub struct RouterConfig {
     /// Gossip config.
     #[clap(flatten)]
@@ -28,6 +30,18 @@ pub struct RouterConfig {
     )]
     pub authz_address: Option<String>,
 
+    /// Authorize requests with the API tokens stored in the catalog, instead
+    /// of an external authz service.
+    ///
+    /// Tokens are managed with the `influxdb_iox catalog token` commands.
+    #[clap(
+        long = CONFIG_AUTHZ_CATALOG_FLAG,
+        env = CONFIG_AUTHZ_CATALOG_ENV_NAME,
+        requires("single_tenant_deployment"),
+        action
+    )]
+    pub authz_catalog: bool,
+
     /// Differential handling based upon deployment to CST vs MT.
     ///
     /// At minimum, differs in supports of v1 endpoint. But also includes
@@ -36,7 +50,7 @@ pub struct RouterConfig {
         long = CONFIG_CST_FLAG,
         env = CONFIG_CST_ENV_NAME,
         default_value = "false",
-        requires_if("true", "authz_address")
+        requires_if("true", "authz")
     )]
     pub single_tenant_deployment: bool,
 
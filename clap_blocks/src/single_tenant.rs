@@ -9,3 +9,8 @@ pub const CONFIG_AUTHZ_FLAG: &str = "authz-addr";
 pub const CONFIG_CST_ENV_NAME: &str = "INFLUXDB_IOX_SINGLE_TENANCY";
 /// CLI flag for single tenancy deployments
 pub const CONFIG_CST_FLAG: &str = "single-tenancy";
+
+/// Env var enabling the built-in authorizer, checking tokens stored in the catalog
+pub const CONFIG_AUTHZ_CATALOG_ENV_NAME: &str = "INFLUXDB_IOX_AUTHZ_CATALOG";
+/// CLI flag enabling the built-in authorizer, checking tokens stored in the catalog
+pub const CONFIG_AUTHZ_CATALOG_FLAG: &str = "authz-catalog";
@@ -159,6 +159,18 @@ impl Builder {
         Ok(self.compose_middleware(channel, endpoint))
     }
 
+    /// Construct the [`Connection`] instance using the specified base URL, without connecting.
+    ///
+    /// The connection is established on first use, so the server does not need to be up yet.
+    pub fn build_lazy<D>(self, dst: D) -> Result<Connection>
+    where
+        D: TryInto<Uri, Error = InvalidUri> + Send,
+    {
+        let endpoint = self.create_endpoint(dst)?;
+        let channel = endpoint.connect_lazy();
+        Ok(self.compose_middleware(channel, endpoint))
+    }
+
     /// Construct the [`Connection`] instance using the specified base URL and custom connector.
     pub async fn build_with_connector<D, C>(self, dst: D, connector: C) -> Result<Connection>
     where
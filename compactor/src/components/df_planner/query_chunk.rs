@@ -1,7 +1,7 @@
 //! QueryableParquetChunk for building query plan
 use std::{any::Any, sync::Arc};
 
-use data_types::{ChunkId, ChunkOrder, TransitionPartitionId};
+use data_types::{ChunkId, ChunkOrder, DeletePredicate, TransitionPartitionId};
 use datafusion::physical_plan::Statistics;
 use iox_query::{chunk_statistics::create_chunk_statistics, QueryChunk, QueryChunkData};
 use observability_deps::tracing::debug;
@@ -20,6 +20,7 @@ pub struct QueryableParquetChunk {
     sort_key: Option<SortKey>,
     order: ChunkOrder,
     stats: Arc<Statistics>,
+    delete_predicates: Vec<Arc<DeletePredicate>>,
 }
 
 impl QueryableParquetChunk {
@@ -42,6 +43,16 @@ impl QueryableParquetChunk {
             sort_key,
             order,
             stats,
+            delete_predicates: vec![],
+        }
+    }
+
+    /// Set the delete predicates that have to be applied to the rows of this
+    /// chunk.
+    pub fn with_delete_predicates(self, delete_predicates: Vec<Arc<DeletePredicate>>) -> Self {
+        Self {
+            delete_predicates,
+            ..self
         }
     }
 
@@ -106,6 +117,10 @@ impl QueryChunk for QueryableParquetChunk {
         self.order
     }
 
+    fn delete_predicates(&self) -> &[Arc<DeletePredicate>] {
+        &self.delete_predicates
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -170,6 +185,9 @@ fn to_queryable_parquet_chunk(
         "built parquet chunk from metadata"
     );
 
+    let delete_predicates = partition_info.delete_predicates_for(&file.file);
+
     let parquet_chunk = ParquetChunk::new(Arc::new(file.file.clone()), schema, store);
     QueryableParquetChunk::new(partition_id, Arc::new(parquet_chunk), sort_key, file.order)
+        .with_delete_predicates(delete_predicates)
 }
@@ -78,6 +78,7 @@ impl ParquetFileSink for MockParquetFileSink {
             row_count: 1,
             compaction_level: level,
             created_at: Timestamp::new(1),
+            data_written_at: Timestamp::new(1),
             column_set: ColumnSet::new(vec![]),
             max_l0_created_at: max_l0_created_at.into(),
         });
@@ -174,6 +175,7 @@ mod tests {
                 row_count: 1,
                 compaction_level: CompactionLevel::FileNonOverlapped,
                 created_at: Timestamp::new(1),
+                data_written_at: Timestamp::new(1),
                 column_set: ColumnSet::new([]),
                 max_l0_created_at: max_l0_created_at.into(),
             }),
@@ -237,6 +239,7 @@ mod tests {
                 row_count: 1,
                 compaction_level: CompactionLevel::FileNonOverlapped,
                 created_at: Timestamp::new(1),
+                data_written_at: Timestamp::new(1),
                 column_set: ColumnSet::new([]),
                 max_l0_created_at: max_l0_created_at.into(),
             }),
@@ -101,6 +101,11 @@ impl ParquetFileSink for ObjectStoreParquetFileSink {
                     .id
             });
 
-        Ok(Some(parquet_file))
+        // The tombstones read before compacting have been applied, so only
+        // deletes requested since then still apply to this file.
+        Ok(Some(ParquetFileParams {
+            data_written_at: partition.tombstones_fetched_at.into(),
+            ..parquet_file
+        }))
     }
 }
@@ -0,0 +1,65 @@
+use std::{fmt::Display, sync::Arc};
+
+use async_trait::async_trait;
+use backoff::{Backoff, BackoffConfig};
+use data_types::{TableId, Tombstone};
+use iox_catalog::interface::Catalog;
+use iox_time::Time;
+
+use super::TombstonesSource;
+
+#[derive(Debug)]
+pub struct CatalogTombstonesSource {
+    backoff_config: BackoffConfig,
+    catalog: Arc<dyn Catalog>,
+}
+
+impl CatalogTombstonesSource {
+    pub fn new(backoff_config: BackoffConfig, catalog: Arc<dyn Catalog>) -> Self {
+        Self {
+            backoff_config,
+            catalog,
+        }
+    }
+}
+
+impl Display for CatalogTombstonesSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "catalog")
+    }
+}
+
+#[async_trait]
+impl TombstonesSource for CatalogTombstonesSource {
+    async fn fetch(&self, table: TableId) -> (Time, Vec<Tombstone>) {
+        // Capture the time BEFORE reading the tombstones, so that any
+        // tombstone created after this point is known to be missing from the
+        // result. This must be the catalog clock: tombstone creation times are
+        // assigned by the catalog and the local clock may be skewed.
+        let fetched_at = Backoff::new(&self.backoff_config)
+            .retry_all_errors("catalog_time", || async {
+                self.catalog
+                    .repositories()
+                    .await
+                    .tombstones()
+                    .catalog_time()
+                    .await
+            })
+            .await
+            .expect("retry forever");
+
+        let tombstones = Backoff::new(&self.backoff_config)
+            .retry_all_errors("tombstones_of_given_table_id", || async {
+                self.catalog
+                    .repositories()
+                    .await
+                    .tombstones()
+                    .list_by_table_id(table)
+                    .await
+            })
+            .await
+            .expect("retry forever");
+
+        (fetched_at.into(), tombstones)
+    }
+}
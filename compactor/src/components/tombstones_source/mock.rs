@@ -0,0 +1,77 @@
+use std::{collections::HashMap, fmt::Display};
+
+use async_trait::async_trait;
+use data_types::{TableId, Tombstone};
+use iox_time::Time;
+
+use super::TombstonesSource;
+
+#[derive(Debug)]
+pub struct MockTombstonesSource {
+    fetched_at: Time,
+    tombstones: HashMap<TableId, Vec<Tombstone>>,
+}
+
+impl MockTombstonesSource {
+    #[allow(dead_code)] // not used anywhere
+    pub fn new(fetched_at: Time, tombstones: HashMap<TableId, Vec<Tombstone>>) -> Self {
+        Self {
+            fetched_at,
+            tombstones,
+        }
+    }
+}
+
+impl Display for MockTombstonesSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "mock")
+    }
+}
+
+#[async_trait]
+impl TombstonesSource for MockTombstonesSource {
+    async fn fetch(&self, table: TableId) -> (Time, Vec<Tombstone>) {
+        (
+            self.fetched_at,
+            self.tombstones.get(&table).cloned().unwrap_or_default(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use data_types::{Timestamp, TombstoneId};
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            MockTombstonesSource::new(Time::from_timestamp_nanos(0), HashMap::default())
+                .to_string(),
+            "mock",
+        )
+    }
+
+    #[tokio::test]
+    async fn test_fetch() {
+        let t = Tombstone {
+            id: TombstoneId::new(1),
+            table_id: TableId::new(1),
+            min_time: Timestamp::new(1),
+            max_time: Timestamp::new(10),
+            serialized_predicate: String::new(),
+            created_at: Timestamp::new(5),
+        };
+        let fetched_at = Time::from_timestamp_nanos(10);
+        let source = MockTombstonesSource::new(
+            fetched_at,
+            HashMap::from([(TableId::new(1), vec![t.clone()])]),
+        );
+
+        assert_eq!(source.fetch(TableId::new(1)).await, (fetched_at, vec![t]));
+
+        // unknown table => no tombstones
+        assert_eq!(source.fetch(TableId::new(2)).await, (fetched_at, vec![]));
+    }
+}
@@ -0,0 +1,19 @@
+use std::fmt::{Debug, Display};
+
+use async_trait::async_trait;
+use data_types::{TableId, Tombstone};
+use iox_time::Time;
+
+pub mod catalog;
+pub mod mock;
+
+#[async_trait]
+pub trait TombstonesSource: Debug + Display + Send + Sync {
+    /// Get the tombstones of the given table, together with a time that is
+    /// no later than the point at which they were read.
+    ///
+    /// All tombstones created before the returned time are included.
+    ///
+    /// This method performs retries.
+    async fn fetch(&self, table: TableId) -> (Time, Vec<Tombstone>);
+}
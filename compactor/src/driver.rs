@@ -2,7 +2,7 @@ use std::{num::NonZeroUsize, sync::Arc, time::Duration};
 
 use chrono::Utc;
 use compactor_scheduler::CompactionJob;
-use data_types::{CompactionLevel, ParquetFile, ParquetFileParams, PartitionId};
+use data_types::{ChunkOrder, CompactionLevel, ParquetFile, ParquetFileParams, PartitionId};
 use futures::{stream, StreamExt, TryStreamExt};
 use gossip_compaction::tx::CompactionEventTx;
 use iox_query::exec::query_tracing::send_metrics_to_tracing;
@@ -21,8 +21,9 @@ use crate::{
         Components,
     },
     error::{DynError, ErrorKind, ErrorKindExt, SimpleError},
-    file_classification::{FileClassification, FilesForProgress},
+    file_classification::{CompactReason, FileClassification, FilesForProgress},
     partition_info::PartitionInfo,
+    plan_ir::FileIR,
     round_info::CompactType,
     PlanIR, RoundInfo,
 };
@@ -244,6 +245,19 @@ async fn try_compact_partition(
         return Ok(());
     }
 
+    // Rewrite the files affected by deletes before compacting them with anything else.
+    files = apply_deletes(
+        span.child("apply_deletes"),
+        job.clone(),
+        files,
+        Arc::clone(&df_semaphore),
+        &components,
+        Arc::clone(&scratchpad_ctx),
+        &partition_info,
+        &transmit_progress_signal,
+    )
+    .await?;
+
     // loop for each "Round".  A round is comprised of the next thing we can do, on one or more branches within
     // one or more CompactRegions.  A round does not feed back into itself.  So when split|compaction output feeds
     // into another split|compaction, that's a new round.
@@ -510,6 +524,123 @@ async fn execute_branch(
     Ok(files_next)
 }
 
+/// Rewrite each file with applicable deletes at its own compaction level, removing the deleted rows.
+///
+/// Files are rewritten individually because chunks sharing a [`ChunkOrder`] share their delete
+/// predicates during planning. The data of the rewritten files is marked as written at
+/// [`PartitionInfo::tombstones_fetched_at`], so none of the fetched tombstones apply to them anymore.
+///
+/// Returns the files that were not affected together with the rewritten ones.
+#[allow(clippy::too_many_arguments)]
+async fn apply_deletes(
+    span: SpanRecorder,
+    job: CompactionJob,
+    files: Vec<ParquetFile>,
+    df_semaphore: Arc<InstrumentedAsyncSemaphore>,
+    components: &Arc<Components>,
+    scratchpad_ctx: Arc<dyn Scratchpad>,
+    partition_info: &Arc<PartitionInfo>,
+    transmit_progress_signal: &Sender<bool>,
+) -> Result<Vec<ParquetFile>, DynError> {
+    let (affected, mut files_next): (Vec<_>, Vec<_>) = files
+        .into_iter()
+        .partition(|f| !partition_info.delete_predicates_for(f).is_empty());
+
+    if affected.is_empty() {
+        return Ok(files_next);
+    }
+
+    info!(
+        partition_id = partition_info.partition_id.get(),
+        file_count = affected.len(),
+        "applying deletes",
+    );
+
+    let saved_parquet_file_state = SavedParquetFileState::from(&affected);
+
+    for level in [
+        CompactionLevel::Initial,
+        CompactionLevel::FileNonOverlapped,
+        CompactionLevel::Final,
+    ] {
+        let level_files = affected
+            .iter()
+            .filter(|f| f.compaction_level == level)
+            .cloned()
+            .collect::<Vec<_>>();
+        if level_files.is_empty() {
+            continue;
+        }
+
+        let paths: Vec<ParquetFilePath> = level_files.iter().map(ParquetFilePath::from).collect();
+        let object_store_ids = scratchpad_ctx.uuids(&paths);
+        let plans = level_files
+            .iter()
+            .cloned()
+            .zip(object_store_ids)
+            .zip(paths)
+            .map(|((file, object_store_id), path)| {
+                let order = ChunkOrder::new(file.max_l0_created_at.get());
+                PlanIR::Compact {
+                    files: vec![FileIR {
+                        file: ParquetFile {
+                            object_store_id,
+                            ..file
+                        },
+                        path,
+                        order,
+                    }],
+                    target_level: level,
+                    reason: CompactReason::ApplyDeletes,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let created_file_params = run_plans(
+            span.child("run_plans"),
+            plans,
+            partition_info,
+            components,
+            Arc::clone(&df_semaphore),
+            Arc::<dyn Scratchpad>::clone(&scratchpad_ctx),
+        )
+        .await?;
+
+        let created_file_params = upload_files_to_object_store(
+            created_file_params,
+            Arc::<dyn Scratchpad>::clone(&scratchpad_ctx),
+        )
+        .await;
+
+        let created_file_paths: Vec<ParquetFilePath> = created_file_params
+            .iter()
+            .map(ParquetFilePath::from)
+            .collect();
+        scratchpad_ctx
+            .clean_written_from_scratchpad(&created_file_paths)
+            .await;
+
+        let (created_files, _) = update_catalog(
+            Arc::clone(components),
+            job.clone(),
+            &saved_parquet_file_state,
+            &level_files,
+            vec![],
+            created_file_params,
+            level,
+        )
+        .await?;
+
+        if let Err(e) = transmit_progress_signal.send(true) {
+            return Err(Box::new(e));
+        }
+
+        files_next.extend(created_files);
+    }
+
+    Ok(files_next)
+}
+
 /// Broadcast a compaction completion event over gossip.
 fn gossip_compaction_complete(
     gossip_handle: Option<&CompactionEventTx>,
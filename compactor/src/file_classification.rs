@@ -110,6 +110,7 @@ pub enum CompactReason {
     ManySmallFiles,
     TotalSizeLessThanMaxCompactSize,
     FoundSubsetLessThanMaxCompactSize,
+    ApplyDeletes,
 }
 
 impl FilesToSplitOrCompact {
@@ -3,9 +3,12 @@
 use std::sync::Arc;
 
 use data_types::{
-    NamespaceId, PartitionHashId, PartitionId, PartitionKey, Table, TableSchema,
-    TransitionPartitionId,
+    DeletePredicate, NamespaceId, ParquetFile, PartitionHashId, PartitionId, PartitionKey, Table,
+    TableSchema, Tombstone, TransitionPartitionId,
 };
+use iox_time::Time;
+use observability_deps::tracing::warn;
+use predicate::delete_predicate::parse_tombstone;
 use schema::sort::SortKey;
 
 /// Information about the Partition being compacted
@@ -34,6 +37,17 @@ pub struct PartitionInfo {
 
     /// partition_key
     pub partition_key: PartitionKey,
+
+    /// Tombstones of the table, as of [`Self::tombstones_fetched_at`]
+    pub tombstones: Vec<Tombstone>,
+
+    /// A catalog time no later than the point at which [`Self::tombstones`]
+    /// were read.
+    ///
+    /// Files written by the compactor are marked as holding data written at
+    /// this time, so that deletes requested while compacting still apply to
+    /// them.
+    pub tombstones_fetched_at: Time,
 }
 
 impl PartitionInfo {
@@ -50,4 +64,25 @@ impl PartitionInfo {
     pub fn partition_id(&self) -> TransitionPartitionId {
         TransitionPartitionId::from((self.partition_id, self.partition_hash_id.as_ref()))
     }
+
+    /// Returns the delete predicates of all tombstones that have to be
+    /// applied to the rows of `file`.
+    pub fn delete_predicates_for(&self, file: &ParquetFile) -> Vec<Arc<DeletePredicate>> {
+        self.tombstones
+            .iter()
+            .filter(|t| t.applies_to(file))
+            .filter_map(|t| match parse_tombstone(t) {
+                Ok(p) => Some(Arc::new(p)),
+                Err(e) => {
+                    warn!(
+                        tombstone_id = t.id.get(),
+                        partition_id = self.partition_id.get(),
+                        %e,
+                        "ignoring tombstone with invalid predicate",
+                    );
+                    None
+                }
+            })
+            .collect()
+    }
 }
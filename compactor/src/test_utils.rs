@@ -5,6 +5,8 @@ use data_types::{
     PartitionKey, Table, TableId, TableSchema,
 };
 
+use iox_time::Time;
+
 use crate::PartitionInfo;
 
 pub struct PartitionInfoBuilder {
@@ -36,6 +38,8 @@ impl PartitionInfoBuilder {
                 table_schema,
                 sort_key: None,
                 partition_key,
+                tombstones: vec![],
+                tombstones_fetched_at: Time::from_timestamp_nanos(0),
             },
         }
     }
@@ -24,6 +24,10 @@ pub enum Error {
     /// Commit failed because of an error in the throttler
     #[error("Failure in throttler: {0}")]
     ThrottlerError(#[from] crate::ThrottleError),
+
+    /// Commit was rejected because the compaction job no longer holds the lease on the partition
+    #[error("Compaction lease on partition {0} was lost")]
+    LeaseLost(PartitionId),
 }
 
 /// Ensures that the file change (i.e. deletion and creation) are committed to the catalog.
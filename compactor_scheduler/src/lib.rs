@@ -48,6 +48,10 @@ pub(crate) use local_scheduler::{
     LocalScheduler,
 };
 
+mod remote_scheduler;
+pub(crate) use remote_scheduler::RemoteScheduler;
+pub use remote_scheduler::RemoteSchedulerConfig;
+
 // partitions_source trait
 mod partitions_source;
 pub(crate) use partitions_source::*;
@@ -80,6 +84,17 @@ pub fn create_scheduler(
             );
             Arc::new(scheduler)
         }
+        SchedulerConfig::Remote(scheduler_config) => {
+            let scheduler = RemoteScheduler::new(
+                scheduler_config,
+                BackoffConfig::default(),
+                catalog,
+                time_provider,
+                metrics,
+                shadow_mode,
+            );
+            Arc::new(scheduler)
+        }
     }
 }
 
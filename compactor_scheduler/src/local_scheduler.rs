@@ -18,6 +18,7 @@ use observability_deps::tracing::{info, warn};
 
 use crate::{
     commit::{logging::LoggingCommitWrapper, metrics::MetricsCommitWrapper},
+    remote_scheduler::HeldLeases,
     Commit, CommitUpdate, CommitWrapper, CompactionJob, CompactionJobEnd, CompactionJobEndVariant,
     CompactionJobStatus, CompactionJobStatusResponse, CompactionJobStatusVariant, MockCommit,
     MockPartitionsSource, PartitionsSource, PartitionsSourceConfig, Scheduler, ShardConfig,
@@ -36,6 +37,7 @@ use self::{
     partitions_source::{
         catalog_all::CatalogAllPartitionsSource,
         catalog_to_compact::CatalogToCompactPartitionsSource,
+        catalog_tombstones::CatalogTombstonesPartitionsSource,
         filter::FilterPartitionsSourceWrapper, never_skipped::NeverSkippedPartitionsSource,
     },
     partitions_subset_source::skipped::SkippedPartitionsSource,
@@ -80,6 +82,28 @@ impl LocalScheduler {
         time_provider: Arc<dyn TimeProvider>,
         metrics: Arc<metric::Registry>,
         shadow_mode: bool,
+    ) -> Self {
+        Self::new_with_leases(
+            config,
+            backoff_config,
+            catalog,
+            time_provider,
+            metrics,
+            shadow_mode,
+            None,
+        )
+    }
+
+    /// Create a new [`LocalScheduler`] that only commits changes to partitions in `leases`,
+    /// checking the lease in the catalog as part of each commit.
+    pub(crate) fn new_with_leases(
+        config: LocalSchedulerConfig,
+        backoff_config: BackoffConfig,
+        catalog: Arc<dyn Catalog>,
+        time_provider: Arc<dyn TimeProvider>,
+        metrics: Arc<metric::Registry>,
+        shadow_mode: bool,
+        leases: Option<HeldLeases>,
     ) -> Self {
         let commit = Self::build_commit(
             config.clone(),
@@ -87,6 +111,7 @@ impl LocalScheduler {
             Arc::clone(&catalog),
             metrics,
             shadow_mode,
+            leases,
         );
 
         let partitions_source = Self::build_partitions_source(
@@ -124,12 +149,18 @@ impl LocalScheduler {
         let mut partitions_source: Arc<dyn PartitionsSource> =
             match &config.partitions_source_config {
                 PartitionsSourceConfig::CatalogRecentWrites { threshold } => {
-                    Arc::new(CatalogToCompactPartitionsSource::new(
+                    // Partitions without recent writes must still be compacted to
+                    // apply deletes against them.
+                    Arc::new(CatalogTombstonesPartitionsSource::new(
                         backoff_config.clone(),
                         Arc::clone(&catalog),
-                        *threshold,
-                        None, // Recent writes is `threshold` ago to now
-                        time_provider,
+                        CatalogToCompactPartitionsSource::new(
+                            backoff_config.clone(),
+                            Arc::clone(&catalog),
+                            *threshold,
+                            None, // Recent writes is `threshold` ago to now
+                            time_provider,
+                        ),
                     ))
                 }
                 PartitionsSourceConfig::CatalogAll => Arc::new(CatalogAllPartitionsSource::new(
@@ -212,11 +243,16 @@ impl LocalScheduler {
         catalog: Arc<dyn Catalog>,
         metrics_registry: Arc<metric::Registry>,
         shadow_mode: bool,
+        leases: Option<HeldLeases>,
     ) -> Arc<dyn Commit> {
         let commit: Arc<dyn Commit> = if shadow_mode {
             Arc::new(MockCommit::new())
         } else {
-            Arc::new(CatalogCommit::new(backoff_config, Arc::clone(&catalog)))
+            Arc::new(CatalogCommit::new(
+                backoff_config,
+                Arc::clone(&catalog),
+                leases,
+            ))
         };
 
         let commit = if let Some(commit_wrapper) = &config.commit_wrapper {
@@ -230,6 +266,19 @@ impl LocalScheduler {
             &metrics_registry,
         )))
     }
+
+    /// Hand back a job returned by [`Scheduler::get_jobs`] that will not be run, without
+    /// recording any outcome for its partition.
+    ///
+    /// The partition can be returned again by the next call to [`Scheduler::get_jobs`].
+    pub(crate) async fn release_job(
+        &self,
+        job: CompactionJob,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.partition_done_sink.release(job.partition_id).await?;
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -5,19 +5,27 @@ use backoff::{Backoff, BackoffConfig};
 use data_types::{CompactionLevel, ParquetFile, ParquetFileId, ParquetFileParams, PartitionId};
 use iox_catalog::interface::Catalog;
 
-use crate::{commit::Error, Commit};
+use crate::{commit::Error, remote_scheduler::HeldLeases, Commit};
 
 #[derive(Debug)]
 pub(crate) struct CatalogCommit {
     backoff_config: BackoffConfig,
     catalog: Arc<dyn Catalog>,
+    /// If set, only commit changes to partitions leased by this compactor, checking the lease in
+    /// the same transaction.
+    leases: Option<HeldLeases>,
 }
 
 impl CatalogCommit {
-    pub(crate) fn new(backoff_config: BackoffConfig, catalog: Arc<dyn Catalog>) -> Self {
+    pub(crate) fn new(
+        backoff_config: BackoffConfig,
+        catalog: Arc<dyn Catalog>,
+        leases: Option<HeldLeases>,
+    ) -> Self {
         Self {
             backoff_config,
             catalog,
+            leases,
         }
     }
 }
@@ -32,7 +40,7 @@ impl Display for CatalogCommit {
 impl Commit for CatalogCommit {
     async fn commit(
         &self,
-        _partition_id: PartitionId,
+        partition_id: PartitionId,
         delete: &[ParquetFile],
         upgrade: &[ParquetFile],
         create: &[ParquetFileParams],
@@ -57,18 +65,47 @@ impl Commit for CatalogCommit {
         let delete = delete.iter().map(|f| f.id).collect::<Vec<_>>();
         let upgrade = upgrade.iter().map(|f| f.id).collect::<Vec<_>>();
 
-        let result = Backoff::new(&self.backoff_config)
-            .retry_all_errors("commit parquet file changes", || async {
-                let mut repos = self.catalog.repositories().await;
-                let parquet_files = repos.parquet_files();
-                let ids = parquet_files
-                    .create_upgrade_delete(&delete, &upgrade, create, target_level)
-                    .await?;
+        let result = match &self.leases {
+            None => Backoff::new(&self.backoff_config)
+                .retry_all_errors("commit parquet file changes", || async {
+                    let mut repos = self.catalog.repositories().await;
+                    let parquet_files = repos.parquet_files();
+                    let ids = parquet_files
+                        .create_upgrade_delete(&delete, &upgrade, create, target_level)
+                        .await?;
 
-                Ok::<_, iox_catalog::interface::Error>(ids)
-            })
-            .await
-            .expect("retry forever");
+                    Ok::<_, iox_catalog::interface::Error>(ids)
+                })
+                .await
+                .expect("retry forever"),
+            Some(leases) => {
+                let job_id = leases
+                    .lock()
+                    .get(&partition_id)
+                    .copied()
+                    .ok_or(Error::LeaseLost(partition_id))?;
+
+                Backoff::new(&self.backoff_config)
+                    .retry_all_errors("commit leased parquet file changes", || async {
+                        self.catalog
+                            .repositories()
+                            .await
+                            .compaction_leases()
+                            .commit(
+                                partition_id,
+                                job_id,
+                                &delete,
+                                &upgrade,
+                                create,
+                                target_level,
+                            )
+                            .await
+                    })
+                    .await
+                    .expect("retry forever")
+                    .ok_or(Error::LeaseLost(partition_id))?
+            }
+        };
 
         if result.len() != create.len() {
             return Err(Error::InvalidCatalogResult(format!(
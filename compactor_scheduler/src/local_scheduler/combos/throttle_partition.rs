@@ -297,6 +297,17 @@ where
 
         self.inner.record(partition, res).await
     }
+
+    async fn release(&self, partition: PartitionId) -> Result<(), PartitionDoneSinkError> {
+        // a released partition was not processed, so it is not throttled
+        let known = self.state.lock().in_flight.remove(&partition).is_some();
+        // perform check when NOT holding the mutex to not poison it
+        if !known {
+            return Err(Error::Uniqueness(partition).into());
+        }
+
+        self.inner.release(partition).await
+    }
 }
 
 #[cfg(test)]
@@ -515,6 +526,36 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_release() {
+        let inner_sink = Arc::new(MockPartitionDoneSink::new());
+        let (source, _commit, sink) = throttle_partition(
+            MockPartitionsSource::new(vec![PartitionId::new(1)]),
+            MockCommit::new(),
+            Arc::clone(&inner_sink),
+            Arc::new(MockProvider::new(Time::MIN)),
+            Duration::from_secs(1),
+            1,
+        );
+        assert_eq!(source.fetch().await, vec![PartitionId::new(1)]);
+
+        // a released partition received no commits, but is not throttled
+        sink.release(PartitionId::new(1))
+            .await
+            .expect("release failed");
+        assert_eq!(inner_sink.results(), HashMap::from([]));
+        assert_eq!(source.fetch().await, vec![PartitionId::new(1)]);
+
+        sink.release(PartitionId::new(1))
+            .await
+            .expect("release failed");
+        assert_matches!(
+            sink.release(PartitionId::new(1)).await,
+            Err(PartitionDoneSinkError::Throttler(Error::Uniqueness(partition))) if partition == PartitionId::new(1),
+            "fails because partition 1 is not in flight"
+        );
+    }
+
     // TODO: remove last legacy panic from scheduler
     // Requires change of Scheduler.get_jobs() API to a Result<Vec<CompactionJob>>
     #[tokio::test]
@@ -178,6 +178,19 @@ where
         // - wrapped sink
         self.inner.record(partition, res).await
     }
+
+    async fn release(&self, partition: PartitionId) -> Result<(), crate::PartitionDoneSinkError> {
+        let existing = {
+            let mut guard = self.in_flight.lock();
+            guard.remove(&partition)
+        };
+        // perform check when NOT holding the mutex to not poison it
+        if !existing {
+            return Err(Error::Uniqueness(partition).into());
+        }
+
+        self.inner.release(partition).await
+    }
 }
 
 #[cfg(test)]
@@ -316,4 +329,31 @@ mod tests {
             "fails because partition 1 is already done"
         );
     }
+
+    #[tokio::test]
+    async fn test_release() {
+        let inner_sink = Arc::new(MockPartitionDoneSink::new());
+        let (source, sink) = unique_partitions(
+            MockPartitionsSource::new(vec![PartitionId::new(1)]),
+            Arc::clone(&inner_sink),
+            1,
+        );
+        assert_eq!(source.fetch().await, vec![PartitionId::new(1)]);
+
+        // a released partition is returned again, without recording anything
+        sink.release(PartitionId::new(1))
+            .await
+            .expect("release failed");
+        assert_eq!(inner_sink.results(), HashMap::default());
+        assert_eq!(source.fetch().await, vec![PartitionId::new(1)]);
+
+        sink.release(PartitionId::new(1))
+            .await
+            .expect("release failed");
+        assert_matches!(
+            sink.release(PartitionId::new(1)).await,
+            Err(PartitionDoneSinkError::UniquePartitions(Error::Uniqueness(partition))) if partition == PartitionId::new(1),
+            "fails because partition 1 is not in flight"
+        );
+    }
 }
@@ -35,6 +35,12 @@ pub(crate) trait PartitionDoneSink: Debug + Display + Send + Sync {
     ///
     /// This method should retry.
     async fn record(&self, partition: PartitionId, res: Result<(), DynError>) -> Result<(), Error>;
+
+    /// Stop tracking the given partition without recording any status, e.g. because it was
+    /// handed out but never processed.
+    async fn release(&self, _partition: PartitionId) -> Result<(), Error> {
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -45,4 +51,8 @@ where
     async fn record(&self, partition: PartitionId, res: Result<(), DynError>) -> Result<(), Error> {
         self.as_ref().record(partition, res).await
     }
+
+    async fn release(&self, partition: PartitionId) -> Result<(), Error> {
+        self.as_ref().release(partition).await
+    }
 }
@@ -0,0 +1,192 @@
+use std::{collections::HashSet, fmt::Display, sync::Arc};
+
+use async_trait::async_trait;
+use backoff::{Backoff, BackoffConfig};
+use data_types::PartitionId;
+use iox_catalog::interface::Catalog;
+
+use crate::PartitionsSource;
+
+/// Adds the partitions holding files that a tombstone still applies to, to
+/// those returned by the inner source.
+///
+/// Deletes are only physically applied when a partition is compacted, and a
+/// partition that receives no new writes is never returned by a source
+/// looking for recent writes. Without this source, deletes against cold
+/// partitions would never be applied, and their tombstones never removed.
+///
+/// Once the compactor has rewritten the affected files, the tombstone no
+/// longer applies to any file in the partition and it is no longer returned.
+#[derive(Debug)]
+pub(crate) struct CatalogTombstonesPartitionsSource<I>
+where
+    I: PartitionsSource,
+{
+    backoff_config: BackoffConfig,
+    catalog: Arc<dyn Catalog>,
+    inner: I,
+}
+
+impl<I> CatalogTombstonesPartitionsSource<I>
+where
+    I: PartitionsSource,
+{
+    /// Create a new [`CatalogTombstonesPartitionsSource`].
+    pub(crate) fn new(backoff_config: BackoffConfig, catalog: Arc<dyn Catalog>, inner: I) -> Self {
+        Self {
+            backoff_config,
+            catalog,
+            inner,
+        }
+    }
+}
+
+impl<I> Display for CatalogTombstonesPartitionsSource<I>
+where
+    I: PartitionsSource,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "catalog_tombstones({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl<I> PartitionsSource for CatalogTombstonesPartitionsSource<I>
+where
+    I: PartitionsSource,
+{
+    async fn fetch(&self) -> Vec<PartitionId> {
+        let mut partitions = self.inner.fetch().await;
+
+        let with_tombstones = Backoff::new(&self.backoff_config)
+            .retry_all_errors("partitions_with_unapplied_tombstones", || async {
+                self.catalog
+                    .repositories()
+                    .await
+                    .tombstones()
+                    .partitions_with_unapplied()
+                    .await
+            })
+            .await
+            .expect("retry forever");
+
+        let mut seen = partitions.iter().copied().collect::<HashSet<_>>();
+        partitions.extend(with_tombstones.into_iter().filter(|p| seen.insert(*p)));
+        partitions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use data_types::{
+        CompactionLevel, DeleteExpr, DeletePredicate, Op, ParquetFileParams, Scalar, Timestamp,
+        TimestampRange,
+    };
+    use iox_catalog::{
+        mem::MemCatalog,
+        test_helpers::{arbitrary_namespace, arbitrary_parquet_file_params, arbitrary_table},
+    };
+
+    use crate::MockPartitionsSource;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let metric_registry = Arc::new(metric::Registry::new());
+        let catalog = Arc::new(MemCatalog::new(metric_registry));
+        let source = CatalogTombstonesPartitionsSource::new(
+            BackoffConfig::default(),
+            catalog,
+            MockPartitionsSource::new(vec![]),
+        );
+        assert_eq!(source.to_string(), "catalog_tombstones(mock)");
+    }
+
+    #[tokio::test]
+    async fn test_adds_partitions_with_unapplied_tombstones() {
+        let metric_registry = Arc::new(metric::Registry::new());
+        let catalog = Arc::new(MemCatalog::new(metric_registry));
+
+        let mut repos = catalog.repositories().await;
+        let namespace = arbitrary_namespace(&mut *repos, "ns").await;
+        let table = arbitrary_table(&mut *repos, "table", &namespace).await;
+        let cold = repos
+            .partitions()
+            .create_or_get("cold".into(), table.id)
+            .await
+            .unwrap();
+        let hot = repos
+            .partitions()
+            .create_or_get("hot".into(), table.id)
+            .await
+            .unwrap();
+        let cold_file = repos
+            .parquet_files()
+            .create(arbitrary_parquet_file_params(&namespace, &table, &cold))
+            .await
+            .unwrap();
+        repos
+            .parquet_files()
+            .create(ParquetFileParams {
+                data_written_at: Timestamp::new(i64::MAX),
+                ..arbitrary_parquet_file_params(&namespace, &table, &hot)
+            })
+            .await
+            .unwrap();
+        drop(repos);
+
+        let source = CatalogTombstonesPartitionsSource::new(
+            BackoffConfig::default(),
+            Arc::clone(&catalog) as _,
+            MockPartitionsSource::new(vec![hot.id]),
+        );
+
+        // no tombstones => only the inner partitions
+        assert_eq!(source.fetch().await, vec![hot.id]);
+
+        // The tombstone applies to the file in the cold partition, but not to
+        // the one in the hot partition, which was written after it.
+        catalog
+            .repositories()
+            .await
+            .tombstones()
+            .create(
+                table.id,
+                &DeletePredicate {
+                    range: TimestampRange::new(0, 100),
+                    exprs: vec![DeleteExpr::new(
+                        "region".to_string(),
+                        Op::Eq,
+                        Scalar::String("west".to_string()),
+                    )],
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(source.fetch().await, vec![hot.id, cold.id]);
+
+        // partitions are not duplicated
+        let source = CatalogTombstonesPartitionsSource::new(
+            BackoffConfig::default(),
+            Arc::clone(&catalog) as _,
+            MockPartitionsSource::new(vec![cold.id, hot.id]),
+        );
+        assert_eq!(source.fetch().await, vec![cold.id, hot.id]);
+
+        // once the affected file is compacted away, the partition is no longer returned
+        catalog
+            .repositories()
+            .await
+            .parquet_files()
+            .create_upgrade_delete(&[cold_file.id], &[], &[], CompactionLevel::Initial)
+            .await
+            .unwrap();
+        let source = CatalogTombstonesPartitionsSource::new(
+            BackoffConfig::default(),
+            Arc::clone(&catalog) as _,
+            MockPartitionsSource::new(vec![]),
+        );
+        assert_eq!(source.fetch().await, vec![]);
+    }
+}
@@ -0,0 +1,351 @@
+//! A scheduler coordinating compactors through leases in the catalog.
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use backoff::{Backoff, BackoffConfig};
+use data_types::PartitionId;
+use iox_catalog::interface::Catalog;
+use iox_time::TimeProvider;
+use observability_deps::tracing::{debug, info, warn};
+use parking_lot::Mutex;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::{
+    CommitWrapper, CompactionJob, CompactionJobEnd, CompactionJobStatus,
+    CompactionJobStatusResponse, CompactionJobStatusVariant, LocalScheduler, LocalSchedulerConfig,
+    PartitionsSourceConfig, Scheduler,
+};
+
+/// Configuration specific to the remote scheduler.
+#[derive(Debug, Clone)]
+pub struct RemoteSchedulerConfig {
+    /// Optionally wrap the `Commit` instance
+    ///
+    /// This is mostly used for testing
+    pub commit_wrapper: Option<Arc<dyn CommitWrapper>>,
+    /// The partitions source config used to find partitions to compact.
+    pub partitions_source_config: PartitionsSourceConfig,
+    /// If skipped partitions should be removed from the partitions_source.
+    pub ignore_partition_skip_marker: bool,
+    /// Identifies this compactor in the leases it holds, e.g. its host name.
+    pub holder: String,
+    /// How long a lease stays valid without being renewed.
+    ///
+    /// Leases are renewed in the background every third of this duration, so this bounds how long
+    /// the partitions of a crashed compactor stay blocked.
+    pub lease_duration: Duration,
+}
+
+impl RemoteSchedulerConfig {
+    /// Default for [`lease_duration`](Self::lease_duration).
+    pub const DEFAULT_LEASE_DURATION: Duration = Duration::from_secs(5 * 60);
+}
+
+impl Default for RemoteSchedulerConfig {
+    fn default() -> Self {
+        Self {
+            commit_wrapper: None,
+            partitions_source_config: PartitionsSourceConfig::default(),
+            ignore_partition_skip_marker: false,
+            holder: Uuid::new_v4().to_string(),
+            lease_duration: Self::DEFAULT_LEASE_DURATION,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error("Compaction lease on partition {0} was lost")]
+    LeaseLost(PartitionId),
+}
+
+/// Leases held by this compactor, by partition.
+pub(crate) type HeldLeases = Arc<Mutex<HashMap<PartitionId, Uuid>>>;
+
+/// Implementation of the scheduler for compactors that coordinate through the catalog.
+///
+/// Each compactor considers all partitions that need compaction, but only compacts those it
+/// acquired a [lease](data_types::CompactionLease) on. Leases are renewed while the job runs,
+/// released when it ends and expire if the compactor stops renewing them, e.g. because it
+/// crashed.
+///
+/// Every commit first renews the lease of its job, and the catalog only applies the commit if the
+/// job still holds an unexpired lease, checked in the same transaction. A job that lost its lease
+/// therefore cannot change the files of a partition another compactor took over.
+///
+/// Compactors can thus be added and removed at any time, without any static sharding.
+#[derive(Debug)]
+pub(crate) struct RemoteScheduler {
+    /// Finds partitions to compact and commits changes to the catalog.
+    local: LocalScheduler,
+    catalog: Arc<dyn Catalog>,
+    backoff_config: BackoffConfig,
+    holder: String,
+    lease_duration: Duration,
+    /// Shadow mode compactors do not take any leases, so they never block other compactors.
+    shadow_mode: bool,
+    leases: HeldLeases,
+    /// Renews the held leases, started with the first call to [`Scheduler::get_jobs`].
+    heartbeat: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl RemoteScheduler {
+    /// Create a new [`RemoteScheduler`].
+    pub(crate) fn new(
+        config: RemoteSchedulerConfig,
+        backoff_config: BackoffConfig,
+        catalog: Arc<dyn Catalog>,
+        time_provider: Arc<dyn TimeProvider>,
+        metrics: Arc<metric::Registry>,
+        shadow_mode: bool,
+    ) -> Self {
+        let RemoteSchedulerConfig {
+            commit_wrapper,
+            partitions_source_config,
+            ignore_partition_skip_marker,
+            holder,
+            lease_duration,
+        } = config;
+
+        assert!(!lease_duration.is_zero(), "lease duration must not be zero");
+        info!(%holder, ?lease_duration, "starting remote compaction scheduler");
+
+        let leases = HeldLeases::default();
+        let local = LocalScheduler::new_with_leases(
+            LocalSchedulerConfig {
+                commit_wrapper,
+                partitions_source_config,
+                shard_config: None,
+                ignore_partition_skip_marker,
+            },
+            backoff_config.clone(),
+            Arc::clone(&catalog),
+            time_provider,
+            metrics,
+            shadow_mode,
+            (!shadow_mode).then(|| Arc::clone(&leases)),
+        );
+
+        Self {
+            local,
+            catalog,
+            backoff_config,
+            holder,
+            lease_duration,
+            shadow_mode,
+            leases,
+            heartbeat: Default::default(),
+        }
+    }
+
+    /// Start renewing the held leases, unless already started.
+    fn ensure_heartbeat(&self) {
+        let mut heartbeat = self.heartbeat.lock();
+        if heartbeat.is_none() {
+            *heartbeat = Some(tokio::spawn(heartbeat_leases(
+                Arc::clone(&self.catalog),
+                Arc::clone(&self.leases),
+                self.lease_duration,
+            )));
+        }
+    }
+
+    /// Try to acquire the lease for `job`, returning `false` if another job holds it.
+    async fn acquire(&self, job: &CompactionJob) -> bool {
+        let lease = Backoff::new(&self.backoff_config)
+            .retry_all_errors("acquire compaction lease", || async {
+                self.catalog
+                    .repositories()
+                    .await
+                    .compaction_leases()
+                    .acquire(
+                        job.partition_id,
+                        job.uuid(),
+                        &self.holder,
+                        self.lease_duration,
+                    )
+                    .await
+            })
+            .await
+            .expect("retry forever");
+
+        if lease.is_some() {
+            self.leases.lock().insert(job.partition_id, job.uuid());
+        }
+
+        lease.is_some()
+    }
+
+    /// Ensure `job` still holds its lease, extending it by the lease duration.
+    async fn renew(&self, job: &CompactionJob) -> Result<(), Error> {
+        let held = self.leases.lock().get(&job.partition_id) == Some(&job.uuid());
+        if !held {
+            return Err(Error::LeaseLost(job.partition_id));
+        }
+
+        let lease = Backoff::new(&self.backoff_config)
+            .retry_all_errors("renew compaction lease", || async {
+                self.catalog
+                    .repositories()
+                    .await
+                    .compaction_leases()
+                    .renew(job.partition_id, job.uuid(), self.lease_duration)
+                    .await
+            })
+            .await
+            .expect("retry forever");
+
+        if lease.is_none() {
+            forget_lease(&self.leases, job.partition_id, job.uuid());
+            return Err(Error::LeaseLost(job.partition_id));
+        }
+
+        Ok(())
+    }
+
+    /// Release the lease of `job`, if held.
+    async fn release(&self, job: &CompactionJob) {
+        if !forget_lease(&self.leases, job.partition_id, job.uuid()) {
+            return;
+        }
+
+        Backoff::new(&self.backoff_config)
+            .retry_all_errors("release compaction lease", || async {
+                self.catalog
+                    .repositories()
+                    .await
+                    .compaction_leases()
+                    .release(job.partition_id, job.uuid())
+                    .await
+            })
+            .await
+            .expect("retry forever");
+    }
+}
+
+impl Drop for RemoteScheduler {
+    fn drop(&mut self) {
+        if let Some(heartbeat) = self.heartbeat.lock().take() {
+            heartbeat.abort();
+        }
+    }
+}
+
+/// Remove the lease of `job_id` on `partition_id` from `leases`, returning `true` if it was held.
+fn forget_lease(leases: &HeldLeases, partition_id: PartitionId, job_id: Uuid) -> bool {
+    let mut leases = leases.lock();
+    if leases.get(&partition_id) == Some(&job_id) {
+        leases.remove(&partition_id);
+        true
+    } else {
+        false
+    }
+}
+
+/// Renew all `leases` every third of `lease_duration`, forgetting the ones that were lost.
+async fn heartbeat_leases(catalog: Arc<dyn Catalog>, leases: HeldLeases, lease_duration: Duration) {
+    let mut interval = tokio::time::interval(lease_duration / 3);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+
+        let held: Vec<_> = leases.lock().iter().map(|(p, j)| (*p, *j)).collect();
+        for (partition_id, job_id) in held {
+            let res = catalog
+                .repositories()
+                .await
+                .compaction_leases()
+                .renew(partition_id, job_id, lease_duration)
+                .await;
+
+            match res {
+                Ok(Some(_)) => debug!(%partition_id, %job_id, "renewed compaction lease"),
+                Ok(None) => {
+                    warn!(%partition_id, %job_id, "compaction lease lost");
+                    forget_lease(&leases, partition_id, job_id);
+                }
+                // retried with the next heartbeat
+                Err(e) => warn!(%partition_id, %job_id, %e, "failed to renew compaction lease"),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Scheduler for RemoteScheduler {
+    async fn get_jobs(&self) -> Vec<CompactionJob> {
+        let candidates = self.local.get_jobs().await;
+        if self.shadow_mode {
+            return candidates;
+        }
+        self.ensure_heartbeat();
+
+        let mut jobs = Vec::with_capacity(candidates.len());
+        for job in candidates {
+            if self.acquire(&job).await {
+                jobs.push(job);
+            } else {
+                debug!(partition_id=%job.partition_id, "partition leased by another compactor");
+
+                // hand the partition back, so it can be considered again later
+                if let Err(e) = self.local.release_job(job).await {
+                    warn!(%e, "could not release unleased compaction job");
+                }
+            }
+        }
+
+        jobs
+    }
+
+    async fn update_job_status(
+        &self,
+        job_status: CompactionJobStatus,
+    ) -> Result<CompactionJobStatusResponse, Box<dyn std::error::Error + Send + Sync>> {
+        if !self.shadow_mode && matches!(job_status.status, CompactionJobStatusVariant::Update(_)) {
+            self.renew(&job_status.job).await?;
+        }
+
+        self.local.update_job_status(job_status).await
+    }
+
+    async fn end_job(
+        &self,
+        end: CompactionJobEnd,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let job = end.job.clone();
+        let res = self.local.end_job(end).await;
+        self.release(&job).await;
+
+        res
+    }
+}
+
+impl std::fmt::Display for RemoteScheduler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "remote_compaction_scheduler")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iox_tests::TestCatalog;
+    use iox_time::{MockProvider, Time};
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let scheduler = RemoteScheduler::new(
+            RemoteSchedulerConfig::default(),
+            BackoffConfig::default(),
+            TestCatalog::new().catalog(),
+            Arc::new(MockProvider::new(Time::MIN)),
+            Arc::new(metric::Registry::default()),
+            false,
+        );
+
+        assert_eq!(scheduler.to_string(), "remote_compaction_scheduler");
+    }
+}
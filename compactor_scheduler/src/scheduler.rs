@@ -7,13 +7,18 @@ use async_trait::async_trait;
 use data_types::{CompactionLevel, ParquetFile, ParquetFileId, ParquetFileParams, PartitionId};
 use uuid::Uuid;
 
-use crate::{CommitWrapper, ErrorKind, LocalSchedulerConfig, PartitionsSourceConfig};
+use crate::{
+    CommitWrapper, ErrorKind, LocalSchedulerConfig, PartitionsSourceConfig, RemoteSchedulerConfig,
+};
 
 /// Scheduler configuration.
 #[derive(Debug, Clone)]
 pub enum SchedulerConfig {
     /// Configuration specific to the [`LocalScheduler`](crate::LocalScheduler).
     Local(LocalSchedulerConfig),
+
+    /// Configuration specific to the [`RemoteScheduler`](crate::RemoteScheduler).
+    Remote(RemoteSchedulerConfig),
 }
 
 impl SchedulerConfig {
@@ -57,6 +62,14 @@ impl std::fmt::Display for SchedulerConfig {
                     write!(f, "local_compaction_scheduler_cfg(commit_wrapper=Some)",)
                 }
             },
+            SchedulerConfig::Remote(RemoteSchedulerConfig {
+                holder,
+                lease_duration,
+                ..
+            }) => write!(
+                f,
+                "remote_compaction_scheduler_cfg(holder={holder},lease_duration={lease_duration:?})",
+            ),
         }
     }
 }
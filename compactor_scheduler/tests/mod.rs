@@ -1,2 +1,3 @@
 mod helpers;
 mod local_scheduler;
+mod remote_scheduler;
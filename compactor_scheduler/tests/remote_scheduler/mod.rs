@@ -0,0 +1,230 @@
+use std::{sync::Arc, time::Duration};
+
+use assert_matches::assert_matches;
+use compactor_scheduler::{
+    create_scheduler, CommitUpdate, CompactionJob, CompactionJobStatus, CompactionJobStatusVariant,
+    RemoteSchedulerConfig, Scheduler, SchedulerConfig,
+};
+use data_types::{ColumnType, CompactionLease, CompactionLevel, ParquetFile, PartitionId};
+use iox_tests::{TestCatalog, TestParquetFileBuilder};
+
+use super::helpers;
+
+/// Test remote schedulers sharing a catalog with one seeded partition.
+#[derive(Debug)]
+struct TestRemoteSchedulers {
+    catalog: Arc<TestCatalog>,
+    partition_id: PartitionId,
+    seeded_file: ParquetFile,
+}
+
+impl TestRemoteSchedulers {
+    async fn new() -> Self {
+        let catalog = TestCatalog::new();
+        let ns = catalog.create_namespace_with_retention("ns", None).await;
+        let table = ns.create_table("table1").await;
+        table.create_column("time", ColumnType::Time).await;
+        table.create_column("load", ColumnType::F64).await;
+        let partition = table.create_partition("k").await;
+
+        let file_builder = TestParquetFileBuilder::default().with_line_protocol("table1 load=1 11");
+        let seeded_file = partition.create_parquet_file(file_builder).await.into();
+
+        Self {
+            catalog,
+            partition_id: partition.partition.id,
+            seeded_file,
+        }
+    }
+
+    /// Create a scheduler of the compactor `holder`.
+    fn scheduler(&self, holder: &str) -> Arc<dyn Scheduler> {
+        self.scheduler_with_lease_duration(holder, RemoteSchedulerConfig::DEFAULT_LEASE_DURATION)
+    }
+
+    /// Create a scheduler of the compactor `holder`, holding leases for `lease_duration`.
+    fn scheduler_with_lease_duration(
+        &self,
+        holder: &str,
+        lease_duration: Duration,
+    ) -> Arc<dyn Scheduler> {
+        create_scheduler(
+            SchedulerConfig::Remote(RemoteSchedulerConfig {
+                holder: holder.to_string(),
+                lease_duration,
+                ..Default::default()
+            }),
+            self.catalog.catalog(),
+            self.catalog.time_provider(),
+            Arc::new(metric::Registry::default()),
+            false,
+        )
+    }
+
+    async fn leases(&self) -> Vec<CompactionLease> {
+        self.catalog
+            .catalog()
+            .repositories()
+            .await
+            .compaction_leases()
+            .list()
+            .await
+            .unwrap()
+    }
+
+    async fn lease_holders(&self) -> Vec<String> {
+        self.leases()
+            .await
+            .into_iter()
+            .map(|lease| lease.holder)
+            .collect()
+    }
+}
+
+#[tokio::test]
+async fn test_partition_is_only_scheduled_on_one_compactor() {
+    test_helpers::maybe_start_logging();
+
+    let test = TestRemoteSchedulers::new().await;
+    let scheduler_a = test.scheduler("compactor-a");
+    let scheduler_b = test.scheduler("compactor-b");
+
+    let jobs = scheduler_a.get_jobs().await;
+    assert_matches!(
+        jobs[..],
+        [CompactionJob { partition_id, .. }] if partition_id == test.partition_id
+    );
+    assert_eq!(test.lease_holders().await, ["compactor-a"]);
+
+    // the partition is leased by compactor A
+    helpers::assert_all_partitions_leased(Arc::clone(&scheduler_b)).await;
+
+    // commits of the lease holder are accepted
+    helpers::can_do_upgrade_commit(
+        Arc::clone(&scheduler_a),
+        jobs[0].clone(),
+        test.seeded_file.clone(),
+    )
+    .await;
+
+    // ending the job releases the lease
+    helpers::can_do_complete(Arc::clone(&scheduler_a), jobs[0].clone()).await;
+    assert!(test.lease_holders().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_commit_is_rejected_after_lease_was_lost() {
+    test_helpers::maybe_start_logging();
+
+    let test = TestRemoteSchedulers::new().await;
+    let scheduler = test.scheduler("compactor-a");
+
+    let jobs = scheduler.get_jobs().await;
+    assert_eq!(jobs.len(), 1);
+    let job = jobs[0].clone();
+
+    // the lease expires and is acquired by another compactor
+    let mut repos = test.catalog.catalog().repositories().await;
+    assert!(repos
+        .compaction_leases()
+        .release(job.partition_id, job.uuid())
+        .await
+        .unwrap());
+    repos
+        .compaction_leases()
+        .acquire(
+            job.partition_id,
+            uuid::Uuid::new_v4(),
+            "compactor-b",
+            RemoteSchedulerConfig::DEFAULT_LEASE_DURATION,
+        )
+        .await
+        .unwrap()
+        .expect("lease acquired");
+    drop(repos);
+
+    let res = scheduler
+        .update_job_status(CompactionJobStatus {
+            job: job.clone(),
+            status: CompactionJobStatusVariant::Update(CommitUpdate::new(
+                job.partition_id,
+                vec![],
+                vec![test.seeded_file.clone()],
+                vec![],
+                CompactionLevel::Final,
+            )),
+        })
+        .await;
+    assert_matches!(
+        res,
+        Err(e) if e.to_string().contains("was lost"),
+        "expected commit to be rejected"
+    );
+
+    // ending the job does not release the lease of the other compactor
+    helpers::can_do_complete(Arc::clone(&scheduler), job).await;
+    assert_eq!(test.lease_holders().await, ["compactor-b"]);
+}
+
+#[tokio::test]
+async fn test_contending_schedulers() {
+    test_helpers::maybe_start_logging();
+
+    let test = TestRemoteSchedulers::new().await;
+    let scheduler_a = test.scheduler("compactor-a");
+    let scheduler_b = test.scheduler("compactor-b");
+
+    // only one of the compactors acquires the lease
+    let (jobs_a, jobs_b) = tokio::join!(scheduler_a.get_jobs(), scheduler_b.get_jobs());
+    let (winner, loser, job) = match (&jobs_a[..], &jobs_b[..]) {
+        ([job], []) => (scheduler_a, scheduler_b, job.clone()),
+        ([], [job]) => (scheduler_b, scheduler_a, job.clone()),
+        _ => panic!("expected exactly one job, got {jobs_a:?} and {jobs_b:?}"),
+    };
+    assert_eq!(test.lease_holders().await.len(), 1);
+
+    // The loser handed the partition back without recording an outcome, so
+    // it is not throttled and is scheduled as soon as the lease is released.
+    helpers::assert_all_partitions_leased(Arc::clone(&loser)).await;
+    helpers::can_do_complete(winner, job).await;
+    let jobs = loser.get_jobs().await;
+    assert_matches!(
+        jobs[..],
+        [CompactionJob { partition_id, .. }] if partition_id == test.partition_id
+    );
+}
+
+#[tokio::test]
+async fn test_lease_is_renewed_until_it_is_lost() {
+    test_helpers::maybe_start_logging();
+
+    let lease_duration = Duration::from_millis(300);
+    let test = TestRemoteSchedulers::new().await;
+    let scheduler_a = test.scheduler_with_lease_duration("compactor-a", lease_duration);
+    let scheduler_b = test.scheduler_with_lease_duration("compactor-b", lease_duration);
+
+    // acquire
+    let jobs = scheduler_a.get_jobs().await;
+    assert_eq!(jobs.len(), 1);
+    let acquired = test.leases().await;
+    assert_matches!(&acquired[..], [lease] if lease.holder == "compactor-a");
+
+    // the lease is renewed in the background, and outlives its initial expiry
+    tokio::time::sleep(2 * lease_duration).await;
+    let renewed = test.leases().await;
+    assert_matches!(&renewed[..], [lease] if lease.holder == "compactor-a");
+    assert!(renewed[0].expires_at > acquired[0].expires_at);
+    helpers::assert_all_partitions_leased(Arc::clone(&scheduler_b)).await;
+
+    // compactor A stops renewing its lease, e.g. because it crashed
+    drop(scheduler_a);
+    tokio::time::sleep(2 * lease_duration).await;
+
+    // the expired lease is acquired by compactor B
+    let jobs = scheduler_b.get_jobs().await;
+    assert_matches!(
+        jobs[..],
+        [CompactionJob { partition_id, .. }] if partition_id == test.partition_id
+    );
+    assert_eq!(test.lease_holders().await, ["compactor-b"]);
+}
@@ -639,6 +639,8 @@ impl<const WITH_FILES: bool> TestSetupBuilder<WITH_FILES> {
             table_schema: Arc::new(self.table.catalog_schema().await),
             sort_key: self.partition.partition.sort_key(),
             partition_key: self.partition.partition.partition_key.clone(),
+            tombstones: vec![],
+            tombstones_fetched_at: self.config.time_provider.now(),
         });
 
         TestSetup {
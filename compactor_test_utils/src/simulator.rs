@@ -239,6 +239,7 @@ impl SimulatedFile {
             row_count,
             compaction_level,
             created_at: Timestamp::new(1),
+            data_written_at: Timestamp::new(1),
             column_set,
             max_l0_created_at: max_l0_created_at.into(),
         }
@@ -0,0 +1,189 @@
+//! Types for the API tokens stored in the catalog.
+
+use std::{fmt::Display, str::FromStr};
+
+use crate::Timestamp;
+
+/// Unique ID for an [`AuthToken`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct AuthTokenId(i64);
+
+#[allow(missing_docs)]
+impl AuthTokenId {
+    pub fn new(v: i64) -> Self {
+        Self(v)
+    }
+    pub fn get(&self) -> i64 {
+        self.0
+    }
+}
+
+impl Display for AuthTokenId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// An API token, which grants the holder of its secret access to
+/// namespaces.
+///
+/// The catalog only stores a hash of the secret, which is never returned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthToken {
+    /// the id of the token
+    pub id: AuthTokenId,
+    /// a description of the token, to identify it when listing tokens
+    pub description: String,
+    /// when the token was created
+    pub created_at: Timestamp,
+    /// when the token was revoked, after which it no longer grants access
+    pub revoked_at: Option<Timestamp>,
+    /// the access the token grants, ordered by namespace name, table name
+    /// and action
+    pub grants: Vec<AuthTokenGrant>,
+}
+
+impl AuthToken {
+    /// Return true if the token was revoked.
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+}
+
+/// Access to a namespace, or to some of its tables, granted by an
+/// [`AuthToken`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AuthTokenGrant {
+    /// the name of the namespace, which need not exist yet
+    pub namespace_name: String,
+    /// the name of the table the grant is restricted to, or `None` if it
+    /// applies to the whole namespace.
+    ///
+    /// A table name of `*` matches every table of the namespace, and a
+    /// table name ending with `*` every table with the preceding prefix.
+    pub table_name: Option<String>,
+    /// the granted action
+    pub action: AuthTokenAction,
+}
+
+impl Display for AuthTokenGrant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.table_name {
+            Some(table_name) => write!(f, "{}:{}:{}", self.namespace_name, table_name, self.action),
+            None => write!(f, "{}:{}", self.namespace_name, self.action),
+        }
+    }
+}
+
+impl FromStr for AuthTokenGrant {
+    type Err = String;
+
+    /// Parse a grant of the form `<namespace>:<action>` or
+    /// `<namespace>:<table>:<action>`.
+    ///
+    /// Namespace names cannot contain `:`, so a table name may.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (scope, action) = s.rsplit_once(':').ok_or_else(|| {
+            format!("invalid grant '{s}', expected <namespace>[:<table>]:<read|write>")
+        })?;
+        let (namespace_name, table_name) = match scope.split_once(':') {
+            Some((namespace_name, table_name)) => (namespace_name, Some(table_name)),
+            None => (scope, None),
+        };
+        if namespace_name.is_empty() {
+            return Err(format!("invalid grant '{s}', namespace name is empty"));
+        }
+        if table_name == Some("") {
+            return Err(format!("invalid grant '{s}', table name is empty"));
+        }
+        Ok(Self {
+            namespace_name: namespace_name.to_string(),
+            table_name: table_name.map(ToString::to_string),
+            action: action.parse()?,
+        })
+    }
+}
+
+/// An action an [`AuthToken`] may be granted on a namespace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, sqlx::Type)]
+#[repr(i16)]
+pub enum AuthTokenAction {
+    /// Query the data and schema of the namespace.
+    Read = 1,
+    /// Write data to the namespace.
+    Write = 2,
+}
+
+impl Display for AuthTokenAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Read => write!(f, "read"),
+            Self::Write => write!(f, "write"),
+        }
+    }
+}
+
+impl FromStr for AuthTokenAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "read" => Ok(Self::Read),
+            "write" => Ok(Self::Write),
+            _ => Err(format!("invalid action '{s}', expected read or write")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grant_round_trip() {
+        for s in [
+            "bananas:read",
+            "bananas:write",
+            "org_bucket:read",
+            "bananas:cpu:read",
+            "bananas:cpu_*:write",
+            "bananas:*:read",
+            "bananas:a:b:read",
+        ] {
+            let grant = s.parse::<AuthTokenGrant>().unwrap();
+            assert_eq!(grant.to_string(), s);
+        }
+
+        let grant = "bananas:write".parse::<AuthTokenGrant>().unwrap();
+        assert_eq!(grant.namespace_name, "bananas");
+        assert_eq!(grant.table_name, None);
+        assert_eq!(grant.action, AuthTokenAction::Write);
+
+        // table names may contain the separator, namespace names may not
+        let grant = "bananas:a:b:read".parse::<AuthTokenGrant>().unwrap();
+        assert_eq!(grant.namespace_name, "bananas");
+        assert_eq!(grant.table_name.as_deref(), Some("a:b"));
+        assert_eq!(grant.action, AuthTokenAction::Read);
+    }
+
+    #[test]
+    fn test_grant_parse_errors() {
+        assert_eq!(
+            "bananas".parse::<AuthTokenGrant>().unwrap_err(),
+            "invalid grant 'bananas', expected <namespace>[:<table>]:<read|write>"
+        );
+        assert_eq!(
+            ":read".parse::<AuthTokenGrant>().unwrap_err(),
+            "invalid grant ':read', namespace name is empty"
+        );
+        assert_eq!(
+            "bananas::read".parse::<AuthTokenGrant>().unwrap_err(),
+            "invalid grant 'bananas::read', table name is empty"
+        );
+        assert_eq!(
+            "bananas:delete".parse::<AuthTokenGrant>().unwrap_err(),
+            "invalid action 'delete', expected read or write"
+        );
+    }
+}
@@ -19,6 +19,8 @@ use thiserror::Error;
 // Workaround for "unused crate" lint false positives.
 use workspace_hack as _;
 
+mod auth_token;
+pub use auth_token::*;
 mod columns;
 pub use columns::*;
 mod namespace_name;
@@ -274,6 +276,27 @@ impl std::fmt::Display for ParquetFileId {
     }
 }
 
+/// Unique ID for a [`Tombstone`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct TombstoneId(i64);
+
+#[allow(missing_docs)]
+impl TombstoneId {
+    pub fn new(v: i64) -> Self {
+        Self(v)
+    }
+    pub fn get(&self) -> i64 {
+        self.0
+    }
+}
+
+impl std::fmt::Display for TombstoneId {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 /// Data object for a namespace
 #[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
 pub struct Namespace {
@@ -515,6 +538,24 @@ pub struct SkippedCompaction {
     pub limit_num_files_first_in_partition: i64,
 }
 
+/// A time-bounded claim of a compaction job on a partition.
+///
+/// Compactors only compact a partition while they hold an unexpired lease on it, so that
+/// several compactors can pick partitions from the catalog without compacting the same
+/// partition twice. A lease that is not renewed before it expires (e.g. because its compactor
+/// crashed) can be acquired by another job.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct CompactionLease {
+    /// the leased partition
+    pub partition_id: PartitionId,
+    /// the compaction job holding the lease
+    pub job_id: Uuid,
+    /// identifies the compactor running the job, for debugging
+    pub holder: String,
+    /// when the lease expires, unless renewed
+    pub expires_at: Timestamp,
+}
+
 use generated_types::influxdata::iox::compactor::v1 as compactor_proto;
 impl From<SkippedCompaction> for compactor_proto::SkippedCompaction {
     fn from(skipped_compaction: SkippedCompaction) -> Self {
@@ -582,6 +623,14 @@ pub struct ParquetFile {
     pub compaction_level: CompactionLevel,
     /// the creation time of the parquet file
     pub created_at: Timestamp,
+    /// A catalog time no later than the first write of any row in this file that has not been
+    /// filtered by tombstones.
+    ///
+    /// Tombstones created after this time have to be applied to the rows of the file, older ones
+    /// either predate all of its data or were applied when the file was written. Unlike
+    /// [`created_at`](Self::created_at), this may be long before the file was added to the
+    /// catalog.
+    pub data_written_at: Timestamp,
     /// Set of columns within this parquet file.
     ///
     /// # Relation to Table-wide Column Set
@@ -619,6 +668,7 @@ impl ParquetFile {
             row_count: params.row_count,
             compaction_level: params.compaction_level,
             created_at: params.created_at,
+            data_written_at: params.data_written_at,
             column_set: params.column_set,
             max_l0_created_at: params.max_l0_created_at,
         }
@@ -678,6 +728,7 @@ impl From<ParquetFile> for generated_types::influxdata::iox::catalog::v1::Parque
             row_count: v.row_count,
             compaction_level: v.compaction_level as i32,
             created_at: v.created_at.get(),
+            data_written_at: v.data_written_at.get(),
             column_set: v.column_set.iter().map(|v| v.get()).collect(),
             max_l0_created_at: v.max_l0_created_at.get(),
         }
@@ -730,6 +781,7 @@ impl TryFrom<generated_types::influxdata::iox::catalog::v1::ParquetFile> for Par
             compaction_level: CompactionLevel::try_from(v.compaction_level)
                 .map_err(ParquetFileProtoError::InvalidCompactionLevel)?,
             created_at: Timestamp::new(v.created_at),
+            data_written_at: Timestamp::new(v.data_written_at),
             column_set: ColumnSet::new(v.column_set.into_iter().map(ColumnId::new)),
             max_l0_created_at: Timestamp::new(v.max_l0_created_at),
         })
@@ -759,6 +811,9 @@ pub struct ParquetFileParams {
     pub compaction_level: CompactionLevel,
     /// the creation time of the parquet file
     pub created_at: Timestamp,
+    /// a catalog time no later than the first write of any row in this file that has not been
+    /// filtered by tombstones, see [`ParquetFile::data_written_at`]
+    pub data_written_at: Timestamp,
     /// columns in this file.
     pub column_set: ColumnSet,
     /// the max of created_at of all L0 files
@@ -778,6 +833,7 @@ impl From<ParquetFile> for ParquetFileParams {
             row_count: value.row_count,
             compaction_level: value.compaction_level,
             created_at: value.created_at,
+            data_written_at: value.data_written_at,
             column_set: value.column_set,
             max_l0_created_at: value.max_l0_created_at,
         }
@@ -918,6 +974,173 @@ impl DeletePredicate {
     }
 }
 
+use generated_types::influxdata::iox::predicate::v1 as predicate_proto;
+impl From<DeletePredicate> for predicate_proto::Predicate {
+    fn from(v: DeletePredicate) -> Self {
+        Self {
+            range: Some(predicate_proto::TimestampRange {
+                start: v.range.start(),
+                end: v.range.end(),
+            }),
+            exprs: v.exprs.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<DeleteExpr> for predicate_proto::Expr {
+    fn from(v: DeleteExpr) -> Self {
+        let op = match v.op {
+            Op::Eq => predicate_proto::Op::Eq,
+            Op::Ne => predicate_proto::Op::Ne,
+        };
+        let value = match v.scalar {
+            Scalar::Bool(v) => predicate_proto::scalar::Value::ValueBool(v),
+            Scalar::I64(v) => predicate_proto::scalar::Value::ValueI64(v),
+            Scalar::F64(v) => predicate_proto::scalar::Value::ValueF64(v.into_inner()),
+            Scalar::String(v) => predicate_proto::scalar::Value::ValueString(v),
+        };
+
+        Self {
+            column: v.column,
+            op: op as i32,
+            scalar: Some(predicate_proto::Scalar { value: Some(value) }),
+        }
+    }
+}
+
+/// Errors deserialising a protobuf serialised [`DeletePredicate`].
+#[derive(Debug, Error)]
+pub enum DeletePredicateProtoError {
+    /// The predicate does not specify a time range.
+    #[error("no time range specified for delete predicate")]
+    NoTimeRange,
+
+    /// The time range of the predicate is empty.
+    #[error("invalid time range for delete predicate: [{start}, {end})")]
+    InvalidTimeRange {
+        /// The inclusive start of the range.
+        start: i64,
+        /// The exclusive end of the range.
+        end: i64,
+    },
+
+    /// An expression does not name a column.
+    #[error("delete expression has no column")]
+    NoColumn,
+
+    /// An expression has no (or an unknown) operator.
+    #[error("invalid operator {0} in delete expression")]
+    InvalidOp(i32),
+
+    /// An expression has no scalar value.
+    #[error("delete expression for column {0} has no scalar value")]
+    NoScalar(String),
+}
+
+impl TryFrom<predicate_proto::Predicate> for DeletePredicate {
+    type Error = DeletePredicateProtoError;
+
+    fn try_from(v: predicate_proto::Predicate) -> Result<Self, Self::Error> {
+        let range = v.range.ok_or(DeletePredicateProtoError::NoTimeRange)?;
+        if range.start > range.end {
+            return Err(DeletePredicateProtoError::InvalidTimeRange {
+                start: range.start,
+                end: range.end,
+            });
+        }
+
+        let exprs = v
+            .exprs
+            .into_iter()
+            .map(DeleteExpr::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            range: TimestampRange::new(range.start, range.end),
+            exprs,
+        })
+    }
+}
+
+impl TryFrom<predicate_proto::Expr> for DeleteExpr {
+    type Error = DeletePredicateProtoError;
+
+    fn try_from(v: predicate_proto::Expr) -> Result<Self, Self::Error> {
+        if v.column.is_empty() {
+            return Err(DeletePredicateProtoError::NoColumn);
+        }
+
+        let op = match predicate_proto::Op::try_from(v.op) {
+            Ok(predicate_proto::Op::Eq) => Op::Eq,
+            Ok(predicate_proto::Op::Ne) => Op::Ne,
+            Ok(predicate_proto::Op::Unspecified) | Err(_) => {
+                return Err(DeletePredicateProtoError::InvalidOp(v.op))
+            }
+        };
+
+        let scalar = match v.scalar.and_then(|v| v.value) {
+            Some(predicate_proto::scalar::Value::ValueBool(v)) => Scalar::Bool(v),
+            Some(predicate_proto::scalar::Value::ValueI64(v)) => Scalar::I64(v),
+            Some(predicate_proto::scalar::Value::ValueF64(v)) => Scalar::F64(v.into()),
+            Some(predicate_proto::scalar::Value::ValueString(v)) => Scalar::String(v),
+            None => return Err(DeletePredicateProtoError::NoScalar(v.column)),
+        };
+
+        Ok(Self {
+            column: v.column,
+            op,
+            scalar,
+        })
+    }
+}
+
+/// A [`DeletePredicate`] recorded in the catalog against a single table.
+///
+/// A tombstone removes all rows of the table that match the predicate and
+/// were written before the tombstone was created. Rows written after the
+/// tombstone was created are unaffected.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct Tombstone {
+    /// the id of the tombstone
+    pub id: TombstoneId,
+    /// the table the delete applies to
+    pub table_id: TableId,
+    /// the inclusive lower bound of the deleted time range
+    pub min_time: Timestamp,
+    /// the exclusive upper bound of the deleted time range
+    pub max_time: Timestamp,
+    /// the conjunction of `column op scalar` expressions selecting the rows
+    /// to delete, as rendered by [`DeletePredicate::expr_sql_string()`]
+    pub serialized_predicate: String,
+    /// when the delete was requested
+    pub created_at: Timestamp,
+}
+
+impl Tombstone {
+    /// Return the time range this tombstone deletes rows from.
+    pub fn time_range(&self) -> TimestampRange {
+        TimestampRange::new(self.min_time.get(), self.max_time.get())
+    }
+
+    /// Return true if the rows of `file` have to be filtered by this
+    /// tombstone.
+    ///
+    /// A file whose data was written after the tombstone was created either
+    /// only contains data written after the delete, or was produced by a
+    /// compaction that has already removed the deleted rows.
+    pub fn applies_to(&self, file: &ParquetFile) -> bool {
+        self.table_id == file.table_id
+            && self.created_at > file.data_written_at
+            && file.min_time < self.max_time
+            && file.max_time >= self.min_time
+    }
+
+    /// Estimate the memory consumption of this object and its contents
+    pub fn size(&self) -> usize {
+        std::mem::size_of_val(self) + self.serialized_predicate.capacity()
+    }
+}
+
 /// Single expression to be used as parts of a predicate.
 ///
 /// Only very simple expression of the type `<column> <op> <scalar>` are supported.
@@ -2766,6 +2989,7 @@ mod tests {
             row_count in any::<i64>(),
             compaction_level in arbitrary_compaction_level(),
             created_at in arbitrary_timestamp(),
+            data_written_at in arbitrary_timestamp(),
             column_set in prop::collection::vec(any::<i64>(), 0..10),
             max_l0_created_at in arbitrary_timestamp(),
         ) -> ParquetFile {
@@ -2784,6 +3008,7 @@ mod tests {
                 row_count,
                 compaction_level,
                 created_at,
+                data_written_at,
                 column_set,
                 max_l0_created_at,
             }
@@ -92,6 +92,21 @@
 //! allow splitting graphemes, but by being conservative we give ourselves this
 //! option - we can't easily do the reverse!
 //!
+//! ### Tag Prefix & Bucket Parts
+//!
+//! A [`TemplatePart::TagPrefix`] renders at most the configured number of
+//! leading graphemes of the tag value. If the value was shortened, the
+//! truncation marker `#` is appended exactly as for values exceeding
+//! [`PARTITION_KEY_MAX_PART_LEN`], and reversing the key yields a
+//! [`ColumnValue::Prefix`].
+//!
+//! A [`TemplatePart::Bucket`] renders the decimal number of the bucket the tag
+//! value hashes to (see [`bucket_for_tag_value()`]), bounding the number of
+//! distinct key parts for high-cardinality tags. Reversing the key yields a
+//! [`ColumnValue::Bucket`], which cannot be matched against a string, but can
+//! be compared to the bucket of a literal value to exclude partitions from a
+//! query. A missing tag renders as `!`, as for [`TemplatePart::TagValue`].
+//!
 //! ## Part Limit & Maximum Key Size
 //!
 //! The number of parts in a partition template is limited to 8
@@ -193,6 +208,26 @@ pub enum ValidationError {
     /// [`TagValue`]: [`proto::template_part::Part::TagValue`]
     #[error("invalid tag value in partition template: {0}")]
     InvalidTagValue(String),
+
+    /// The partition template defines a [`TagBucket`] part, but the provided
+    /// number of buckets is invalid.
+    ///
+    /// [`TagBucket`]: [`proto::template_part::Part::TagBucket`]
+    #[error(
+        "invalid number of buckets in partition template: {0} \
+        (must be between 1 and {MAXIMUM_NUMBER_OF_BUCKETS})"
+    )]
+    InvalidNumberOfBuckets(u32),
+
+    /// The partition template defines a [`TagPrefix`] part, but the provided
+    /// prefix length is invalid.
+    ///
+    /// [`TagPrefix`]: [`proto::template_part::Part::TagPrefix`]
+    #[error(
+        "invalid tag prefix length in partition template: {0} \
+        (must be between 1 and {PARTITION_KEY_MAX_PART_LEN})"
+    )]
+    InvalidTagPrefixLength(u32),
 }
 
 /// The maximum number of template parts a custom partition template may specify, to limit the
@@ -200,6 +235,10 @@ pub enum ValidationError {
 /// created with it.
 pub const MAXIMUM_NUMBER_OF_TEMPLATE_PARTS: usize = 8;
 
+/// The maximum number of buckets a [`TemplatePart::Bucket`] may distribute tag
+/// values across.
+pub const MAXIMUM_NUMBER_OF_BUCKETS: u32 = 100_000;
+
 /// The sentinel character used to delimit partition key parts in the partition
 /// key string.
 pub const PARTITION_KEY_DELIMITER: char = '|';
@@ -253,6 +292,31 @@ pub const ENCODED_PARTITION_KEY_CHARS: AsciiSet = CONTROLS
 pub enum TemplatePart<'a> {
     TagValue(&'a str),
     TimeFormat(&'a str),
+    /// The tag name, and the number of buckets its values are hashed into.
+    Bucket(&'a str, u32),
+    /// The tag name, and the maximum number of characters of its value to
+    /// retain.
+    TagPrefix(&'a str, u32),
+}
+
+/// Deterministically map a tag `value` to one of `num_buckets` buckets.
+///
+/// The hash function and its keys are fixed - changing either would change the
+/// partition keys generated for existing data, and break partition pruning of
+/// [`TemplatePart::Bucket`] parts.
+///
+/// # Panics
+///
+/// Panics if `num_buckets` is 0.
+pub fn bucket_for_tag_value(value: &str, num_buckets: u32) -> u32 {
+    use siphasher::sip::SipHasher13;
+    use std::hash::Hasher;
+
+    assert!(num_buckets > 0, "number of buckets must be non-zero");
+
+    let mut hasher = SipHasher13::new_with_keys(0, 0);
+    hasher.write(value.as_bytes());
+    (hasher.finish() % num_buckets as u64) as u32
 }
 
 /// The default partitioning scheme is by each day according to the "time" column.
@@ -344,6 +408,13 @@ impl TablePartitionTemplateOverride {
             .map(|part| match part {
                 proto::template_part::Part::TagValue(value) => TemplatePart::TagValue(value),
                 proto::template_part::Part::TimeFormat(fmt) => TemplatePart::TimeFormat(fmt),
+                proto::template_part::Part::TagBucket(proto::TagBucket {
+                    tag_name,
+                    num_buckets,
+                }) => TemplatePart::Bucket(tag_name, *num_buckets),
+                proto::template_part::Part::TagPrefix(proto::TagPrefix { tag_name, length }) => {
+                    TemplatePart::TagPrefix(tag_name, *length)
+                }
             })
     }
 
@@ -370,6 +441,12 @@ impl TablePartitionTemplateOverride {
                                     .map(|part| match part {
                                         proto::template_part::Part::TagValue(s) => s.capacity(),
                                         proto::template_part::Part::TimeFormat(s) => s.capacity(),
+                                        proto::template_part::Part::TagBucket(b) => {
+                                            b.tag_name.capacity()
+                                        }
+                                        proto::template_part::Part::TagPrefix(p) => {
+                                            p.tag_name.capacity()
+                                        }
                                     })
                                     .unwrap_or_default()
                             })
@@ -389,7 +466,10 @@ impl TablePartitionTemplateOverride {
 /// `TablePartitionTemplateOverride` types. It's an internal implementation detail to minimize code
 /// duplication.
 mod serialization {
-    use super::{ValidationError, MAXIMUM_NUMBER_OF_TEMPLATE_PARTS, TAG_VALUE_KEY_TIME};
+    use super::{
+        ValidationError, MAXIMUM_NUMBER_OF_BUCKETS, MAXIMUM_NUMBER_OF_TEMPLATE_PARTS,
+        PARTITION_KEY_MAX_PART_LEN, TAG_VALUE_KEY_TIME,
+    };
     use chrono::{format::StrftimeItems, Utc};
     use generated_types::influxdata::iox::partition_template::v1 as proto;
     use std::{fmt::Write, sync::Arc};
@@ -469,15 +549,26 @@ mod serialization {
                         .map_err(|_| ValidationError::InvalidStrftime(fmt.into()))?
                     }
                     Some(proto::template_part::Part::TagValue(value)) => {
-                        // Empty is not a valid tag value
-                        if value.is_empty() {
-                            return Err(ValidationError::InvalidTagValue(value.into()));
+                        validate_tag_name(value)?;
+                    }
+                    Some(proto::template_part::Part::TagBucket(proto::TagBucket {
+                        tag_name,
+                        num_buckets,
+                    })) => {
+                        validate_tag_name(tag_name)?;
+
+                        if !(1..=MAXIMUM_NUMBER_OF_BUCKETS).contains(num_buckets) {
+                            return Err(ValidationError::InvalidNumberOfBuckets(*num_buckets));
                         }
-
-                        if value.contains(TAG_VALUE_KEY_TIME) {
-                            return Err(ValidationError::InvalidTagValue(format!(
-                                "{TAG_VALUE_KEY_TIME} cannot be used"
-                            )));
+                    }
+                    Some(proto::template_part::Part::TagPrefix(proto::TagPrefix {
+                        tag_name,
+                        length,
+                    })) => {
+                        validate_tag_name(tag_name)?;
+
+                        if *length == 0 || *length as usize > PARTITION_KEY_MAX_PART_LEN {
+                            return Err(ValidationError::InvalidTagPrefixLength(*length));
                         }
                     }
                     None => {}
@@ -488,6 +579,21 @@ mod serialization {
         }
     }
 
+    fn validate_tag_name(value: &str) -> Result<(), ValidationError> {
+        // Empty is not a valid tag value
+        if value.is_empty() {
+            return Err(ValidationError::InvalidTagValue(value.into()));
+        }
+
+        if value.contains(TAG_VALUE_KEY_TIME) {
+            return Err(ValidationError::InvalidTagValue(format!(
+                "{TAG_VALUE_KEY_TIME} cannot be used"
+            )));
+        }
+
+        Ok(())
+    }
+
     impl<DB> sqlx::Type<DB> for Wrapper
     where
         sqlx::types::Json<Self>: sqlx::Type<DB>,
@@ -550,6 +656,20 @@ pub enum ColumnValue<'a> {
     /// instead.
     Prefix(Cow<'a, str>),
 
+    /// The bucket a hashed tag value was assigned to by a
+    /// [`TemplatePart::Bucket`].
+    ///
+    /// Attempting to equality or prefix match this variant against a string
+    /// will always be false - use [`bucket_for_tag_value()`] to compute the
+    /// bucket of a value instead.
+    Bucket {
+        /// The bucket the tag value was hashed into.
+        bucket: u32,
+
+        /// The total number of buckets configured for the template part.
+        num_buckets: u32,
+    },
+
     /// Datetime.
     Datetime {
         /// Inclusive begin of the datatime partition range.
@@ -572,7 +692,7 @@ impl<'a> ColumnValue<'a> {
         let this = match self {
             ColumnValue::Identity(v) => v.as_bytes(),
             ColumnValue::Prefix(v) => v.as_bytes(),
-            ColumnValue::Datetime { .. } => {
+            ColumnValue::Bucket { .. } | ColumnValue::Datetime { .. } => {
                 return false;
             }
         };
@@ -589,6 +709,7 @@ where
         match self {
             ColumnValue::Identity(v) => other.as_ref().eq(v.as_ref()),
             ColumnValue::Prefix(_) => false,
+            ColumnValue::Bucket { .. } => false,
             ColumnValue::Datetime { .. } => false,
         }
     }
@@ -630,7 +751,12 @@ pub fn build_column_values<'a>(
     template_parts
         .zip(key_parts)
         .filter_map(|(template, value)| match template {
-            TemplatePart::TagValue(col_name) => Some((col_name, parse_part_tag_value(value)?)),
+            TemplatePart::TagValue(col_name) | TemplatePart::TagPrefix(col_name, _) => {
+                Some((col_name, parse_part_tag_value(value)?))
+            }
+            TemplatePart::Bucket(col_name, num_buckets) => {
+                Some((col_name, parse_part_bucket(value, num_buckets)?))
+            }
             TemplatePart::TimeFormat(format) => {
                 Some((TIME_COLUMN_NAME, parse_part_time_format(value, format)?))
             }
@@ -680,6 +806,26 @@ fn parse_part_tag_value(value: &str) -> Option<ColumnValue<'_>> {
     }
 }
 
+fn parse_part_bucket(value: &str, num_buckets: u32) -> Option<ColumnValue<'static>> {
+    // Skip null partition key parts, indicated by the presence of a single "!"
+    // character as the part value.
+    if value == PARTITION_KEY_VALUE_NULL_STR {
+        return None;
+    }
+
+    // A bucket outside of the configured range cannot have been generated by
+    // this template part.
+    let bucket = value
+        .parse::<u32>()
+        .ok()
+        .filter(|bucket| *bucket < num_buckets)?;
+
+    Some(ColumnValue::Bucket {
+        bucket,
+        num_buckets,
+    })
+}
+
 fn parse_part_time_format(value: &str, format: &str) -> Option<ColumnValue<'static>> {
     use chrono::format::{parse, Item, Parsed};
 
@@ -800,6 +946,18 @@ pub fn test_table_partition_override(
             let part = match part {
                 TemplatePart::TagValue(value) => proto::template_part::Part::TagValue(value.into()),
                 TemplatePart::TimeFormat(fmt) => proto::template_part::Part::TimeFormat(fmt.into()),
+                TemplatePart::Bucket(tag_name, num_buckets) => {
+                    proto::template_part::Part::TagBucket(proto::TagBucket {
+                        tag_name: tag_name.into(),
+                        num_buckets,
+                    })
+                }
+                TemplatePart::TagPrefix(tag_name, length) => {
+                    proto::template_part::Part::TagPrefix(proto::TagPrefix {
+                        tag_name: tag_name.into(),
+                        length,
+                    })
+                }
             };
 
             proto::TemplatePart { part: Some(part) }
@@ -947,6 +1105,83 @@ mod tests {
         assert_error!(err, ValidationError::InvalidTagValue(ref value) if value.is_empty());
     }
 
+    #[test]
+    fn tag_bucket_validation() {
+        let bucket = |tag_name: &str, num_buckets| {
+            serialization::Wrapper::try_from(proto::PartitionTemplate {
+                parts: vec![proto::TemplatePart {
+                    part: Some(proto::template_part::Part::TagBucket(proto::TagBucket {
+                        tag_name: tag_name.into(),
+                        num_buckets,
+                    })),
+                }],
+            })
+        };
+
+        assert!(bucket("region", 1).is_ok());
+        assert!(bucket("region", MAXIMUM_NUMBER_OF_BUCKETS).is_ok());
+
+        assert_error!(
+            bucket("region", 0),
+            ValidationError::InvalidNumberOfBuckets(0)
+        );
+        assert_error!(
+            bucket("region", MAXIMUM_NUMBER_OF_BUCKETS + 1),
+            ValidationError::InvalidNumberOfBuckets(_)
+        );
+        assert_error!(bucket("", 10), ValidationError::InvalidTagValue(_));
+        assert_error!(bucket("time", 10), ValidationError::InvalidTagValue(_));
+    }
+
+    #[test]
+    fn tag_prefix_validation() {
+        let prefix = |tag_name: &str, length| {
+            serialization::Wrapper::try_from(proto::PartitionTemplate {
+                parts: vec![proto::TemplatePart {
+                    part: Some(proto::template_part::Part::TagPrefix(proto::TagPrefix {
+                        tag_name: tag_name.into(),
+                        length,
+                    })),
+                }],
+            })
+        };
+
+        assert!(prefix("region", 1).is_ok());
+        assert!(prefix("region", PARTITION_KEY_MAX_PART_LEN as u32).is_ok());
+
+        assert_error!(
+            prefix("region", 0),
+            ValidationError::InvalidTagPrefixLength(0)
+        );
+        assert_error!(
+            prefix("region", PARTITION_KEY_MAX_PART_LEN as u32 + 1),
+            ValidationError::InvalidTagPrefixLength(_)
+        );
+        assert_error!(prefix("", 10), ValidationError::InvalidTagValue(_));
+        assert_error!(prefix("time", 10), ValidationError::InvalidTagValue(_));
+    }
+
+    /// The bucket a value is assigned to is persisted in partition keys, and
+    /// used to prune partitions at query time.
+    ///
+    /// Changing the hash function will cause data for a single tag value to be
+    /// spread across multiple partitions, and queries to incorrectly skip
+    /// partitions containing matching data.
+    ///
+    /// You shouldn't be changing this!
+    #[test]
+    fn test_bucket_for_tag_value_fixture() {
+        assert_eq!(bucket_for_tag_value("", 10), 0);
+        assert_eq!(bucket_for_tag_value("bananas", 10), 1);
+        assert_eq!(bucket_for_tag_value("cpu-01", 10), 9);
+        assert_eq!(bucket_for_tag_value("cpu-02", 10), 3);
+        assert_eq!(bucket_for_tag_value("cpu-02", 100), 33);
+        assert_eq!(bucket_for_tag_value("us-east", 100), 77);
+
+        // A single bucket always yields 0.
+        assert_eq!(bucket_for_tag_value("bananas", 1), 0);
+    }
+
     fn identity(s: &str) -> ColumnValue<'_> {
         ColumnValue::Identity(s.into())
     }
@@ -958,6 +1193,13 @@ mod tests {
         ColumnValue::Prefix(s.into())
     }
 
+    fn bucket(bucket: u32, num_buckets: u32) -> ColumnValue<'static> {
+        ColumnValue::Bucket {
+            bucket,
+            num_buckets,
+        }
+    }
+
     fn year(y: i32) -> ColumnValue<'static> {
         ColumnValue::Datetime {
             begin: Utc.with_ymd_and_hms(y, 1, 1, 0, 0, 0).unwrap(),
@@ -1255,6 +1497,28 @@ mod tests {
         want = []
     );
 
+    test_build_column_values!(
+        bucket,
+        template = [
+            TemplatePart::TimeFormat("%Y"),
+            TemplatePart::Bucket("a", 10),
+            TemplatePart::Bucket("b", 3),
+        ],
+        partition_key = "2023|7|!",
+        want = [(TIME_COLUMN_NAME, year(2023)), ("a", bucket(7, 10))]
+    );
+
+    test_build_column_values!(
+        tag_prefix,
+        template = [
+            TemplatePart::TagPrefix("a", 3),
+            TemplatePart::TagPrefix("b", 10),
+            TemplatePart::TagPrefix("c", 10),
+        ],
+        partition_key = "ban#|plátanos|!",
+        want = [("a", prefix("ban")), ("b", identity("plátanos"))]
+    );
+
     #[test]
     fn test_null_partition_key_char_str_equality() {
         assert_eq!(
@@ -1273,6 +1537,9 @@ mod tests {
         assert_ne!(prefix("bananas"), "bananas");
         assert_ne!(prefix("bananas"), "bananas2");
         assert_ne!(prefix("bananas2"), "bananas");
+
+        assert_ne!(bucket(1, 10), "1");
+        assert_ne!(bucket(1, 10), "bananas");
     }
 
     #[test]
@@ -1288,6 +1555,8 @@ mod tests {
 
         assert!(!identity("bananas2").is_prefix_match_of("bananas"));
         assert!(!prefix("bananas2").is_prefix_match_of("bananas"));
+
+        assert!(!bucket(1, 10).is_prefix_match_of("1"));
     }
 
     /// This test asserts the default derived partitioning scheme with no
@@ -1,7 +1,10 @@
-use std::{fmt::Display, sync::Arc};
+use std::{collections::HashMap, fmt::Display, sync::Arc};
 
 use datafusion::{
-    config::ConfigOptions, execution::runtime_env::RuntimeEnv, prelude::SessionConfig,
+    config::ConfigOptions,
+    error::Result,
+    execution::{object_store::ObjectStoreRegistry, runtime_env::RuntimeEnv},
+    prelude::SessionConfig,
 };
 use object_store::ObjectStore;
 use schema::TIME_DATA_TIMEZONE;
@@ -39,6 +42,65 @@ pub fn register_iox_object_store<D: Display>(
     id: D,
     object_store: Arc<dyn ObjectStore>,
 ) -> Option<Arc<dyn ObjectStore>> {
-    let url = Url::parse(&format!("iox://{id}")).unwrap();
-    runtime.as_ref().register_object_store(&url, object_store)
+    runtime
+        .as_ref()
+        .register_object_store(&iox_object_store_url(id), object_store)
+}
+
+/// URL of the "IOx" object store with the given id, see [`register_iox_object_store`].
+pub fn iox_object_store_url<D: Display>(id: D) -> Url {
+    Url::parse(&format!("iox://{id}")).unwrap()
+}
+
+/// An [`ObjectStoreRegistry`] that serves its own stores for some URLs and delegates everything else to a shared
+/// registry.
+///
+/// This allows a single query to read through other stores than the queries it shares the registry with.
+#[derive(Debug)]
+pub struct OverlayObjectStoreRegistry {
+    inner: Arc<dyn ObjectStoreRegistry>,
+    stores: HashMap<String, Arc<dyn ObjectStore>>,
+}
+
+impl OverlayObjectStoreRegistry {
+    /// Serve `stores` in front of the stores registered in `inner`.
+    pub fn new(
+        inner: Arc<dyn ObjectStoreRegistry>,
+        stores: impl IntoIterator<Item = (Url, Arc<dyn ObjectStore>)>,
+    ) -> Self {
+        Self {
+            inner,
+            stores: stores
+                .into_iter()
+                .map(|(url, store)| (store_key(&url), store))
+                .collect(),
+        }
+    }
+}
+
+impl ObjectStoreRegistry for OverlayObjectStoreRegistry {
+    /// Registers the store in the shared registry.
+    fn register_store(
+        &self,
+        url: &Url,
+        store: Arc<dyn ObjectStore>,
+    ) -> Option<Arc<dyn ObjectStore>> {
+        self.inner.register_store(url, store)
+    }
+
+    fn get_store(&self, url: &Url) -> Result<Arc<dyn ObjectStore>> {
+        match self.stores.get(&store_key(url)) {
+            Some(store) => Ok(Arc::clone(store)),
+            None => self.inner.get_store(url),
+        }
+    }
+}
+
+/// Identifies the store of `url` by its scheme and authority, ignoring the path.
+fn store_key(url: &Url) -> String {
+    format!(
+        "{}://{}",
+        url.scheme(),
+        &url[url::Position::BeforeHost..url::Position::AfterPort]
+    )
 }
@@ -24,9 +24,12 @@ use clap as _;
 use workspace_hack as _;
 
 use crate::{
-    objectstore::{checker as os_checker, deleter as os_deleter, lister as os_lister},
+    objectstore::{
+        checker as os_checker, deleter as os_deleter, lister as os_lister, report as os_report,
+    },
     parquetfile::deleter as pf_deleter,
     retention::flagger as retention_flagger,
+    tombstone::remover as tombstone_remover,
 };
 
 use clap_blocks::garbage_collector::GarbageCollectorConfig;
@@ -45,6 +48,9 @@ mod objectstore;
 mod parquetfile;
 /// Logic for flagging parquet files for deletion based on retention settings
 mod retention;
+/// Logic for removing tombstones once they have been applied
+mod tombstone;
+pub mod verify;
 
 const BUFFER_SIZE: usize = 1000;
 
@@ -61,6 +67,7 @@ pub struct GarbageCollector {
     os_deleter: tokio::task::JoinHandle<Result<(), os_deleter::Error>>,
     pf_deleter: tokio::task::JoinHandle<Result<(), pf_deleter::Error>>,
     retention_flagger: tokio::task::JoinHandle<Result<(), retention_flagger::Error>>,
+    tombstone_remover: tokio::task::JoinHandle<Result<(), tombstone_remover::Error>>,
 }
 
 impl Debug for GarbageCollector {
@@ -81,10 +88,14 @@ impl GarbageCollector {
         let dry_run = sub_config.dry_run;
         info!(
             objectstore_cutoff_days = %format_duration(sub_config.objectstore_cutoff).to_string(),
+            objectstore_quarantine = ?sub_config.objectstore_quarantine.map(|d| format_duration(d).to_string()),
+            objectstore_report = ?sub_config.objectstore_report,
             parquetfile_cutoff_days = %format_duration(sub_config.parquetfile_cutoff).to_string(),
             objectstore_sleep_interval_minutes = %sub_config.objectstore_sleep_interval_minutes,
             parquetfile_sleep_interval_minutes = %sub_config.parquetfile_sleep_interval_minutes,
             retention_sleep_interval_minutes = %sub_config.retention_sleep_interval_minutes,
+            tombstone_cutoff = %format_duration(sub_config.tombstone_cutoff).to_string(),
+            tombstone_sleep_interval_minutes = %sub_config.tombstone_sleep_interval_minutes,
             "GarbageCollector starting"
         );
 
@@ -98,20 +109,24 @@ impl GarbageCollector {
         // - checker receives from that channel and checks the catalog to see if they exist, if not
         //   it sends them on another channel
         // - deleter receives object store entries that have been checked and therefore should be
-        //   deleted.
+        //   deleted, or moved to the quarantine if quarantine mode is enabled. It also records them
+        //   in the orphan report, if configured.
         let (tx1, rx1) = mpsc::channel(BUFFER_SIZE);
         let (tx2, rx2) = mpsc::channel(BUFFER_SIZE);
 
         let sdt = shutdown.clone();
         let osa = Arc::clone(&object_store);
+        let objectstore_sleep_interval_minutes = sub_config.objectstore_sleep_interval_minutes;
+        let objectstore_sleep_interval_batch_milliseconds =
+            sub_config.objectstore_sleep_interval_batch_milliseconds;
 
         let os_lister = tokio::spawn(async move {
             select! {
                 ret = os_lister::perform(
                     osa,
                     tx1,
-                    sub_config.objectstore_sleep_interval_minutes,
-                    sub_config.objectstore_sleep_interval_batch_milliseconds,
+                    objectstore_sleep_interval_minutes,
+                    objectstore_sleep_interval_batch_milliseconds,
                 ) => {
                     ret
                 },
@@ -128,12 +143,20 @@ impl GarbageCollector {
                 message: e.to_string(),
             }
         })?;
+        let quarantine = sub_config
+            .objectstore_quarantine
+            .map(chrono::Duration::from_std)
+            .transpose()
+            .map_err(|e| Error::CutoffError {
+                message: e.to_string(),
+            })?;
 
         let os_checker = tokio::spawn(async move {
             select! {
                 ret = os_checker::perform(
                     cat,
                     cutoff,
+                    quarantine,
                     rx1,
                     tx2,
                 ) => {
@@ -145,11 +168,18 @@ impl GarbageCollector {
             }
         });
 
+        let report = sub_config
+            .objectstore_report
+            .clone()
+            .map(|path| os_report::OrphanReport::open(path, sub_config.objectstore_report_format))
+            .transpose()?;
         let os_deleter = tokio::spawn(os_deleter::perform(
             shutdown.clone(),
             object_store,
             dry_run,
+            quarantine.is_some(),
             sub_config.objectstore_concurrent_deletes,
+            report,
             rx2,
         ));
 
@@ -166,11 +196,21 @@ impl GarbageCollector {
         // flag_for_delete_by_retention() on the catalog then sleeps.
         let retention_flagger = tokio::spawn(retention_flagger::perform(
             shutdown.clone(),
-            catalog,
+            Arc::clone(&catalog),
             sub_config.retention_sleep_interval_minutes,
             sub_config.dry_run,
         ));
 
+        // Initialise the tombstone remover, which is just one thread that removes the tombstones
+        // that no longer apply to any parquet file from the catalog, then sleeps.
+        let tombstone_remover = tokio::spawn(tombstone_remover::perform(
+            shutdown.clone(),
+            catalog,
+            sub_config.tombstone_cutoff,
+            sub_config.tombstone_sleep_interval_minutes,
+            sub_config.dry_run,
+        ));
+
         Ok(Self {
             shutdown,
             os_lister,
@@ -178,6 +218,7 @@ impl GarbageCollector {
             os_deleter,
             pf_deleter,
             retention_flagger,
+            tombstone_remover,
         })
     }
 
@@ -197,17 +238,20 @@ impl GarbageCollector {
             os_deleter,
             pf_deleter,
             retention_flagger,
+            tombstone_remover,
             shutdown: _,
         } = self;
 
-        let (os_lister, os_checker, os_deleter, pf_deleter, retention_flagger) = futures::join!(
+        let (os_lister, os_checker, os_deleter, pf_deleter, retention_flagger, tombstone_remover) = futures::join!(
             os_lister,
             os_checker,
             os_deleter,
             pf_deleter,
-            retention_flagger
+            retention_flagger,
+            tombstone_remover
         );
 
+        tombstone_remover.context(TombstoneRemoverPanicSnafu)??;
         retention_flagger.context(ParquetFileDeleterPanicSnafu)??;
         pf_deleter.context(ParquetFileDeleterPanicSnafu)??;
         os_deleter.context(ObjectStoreDeleterPanicSnafu)??;
@@ -245,6 +289,10 @@ pub enum Error {
     #[snafu(display("Error converting parsed duration: {message}"))]
     CutoffError { message: String },
 
+    #[snafu(display("The object store orphan report could not be opened"))]
+    #[snafu(context(false))]
+    ObjectStoreOrphanReport { source: os_report::Error },
+
     #[snafu(display("The object store lister task failed"))]
     #[snafu(context(false))]
     ObjectStoreLister { source: os_lister::Error },
@@ -274,6 +322,12 @@ pub enum Error {
     ParquetFileRetentionFlagger { source: retention_flagger::Error },
     #[snafu(display("The parquet file retention flagger task panicked"))]
     ParquetFileRetentionFlaggerPanic { source: tokio::task::JoinError },
+
+    #[snafu(display("The tombstone remover task failed"))]
+    #[snafu(context(false))]
+    TombstoneRemover { source: tombstone_remover::Error },
+    #[snafu(display("The tombstone remover task panicked"))]
+    TombstoneRemoverPanic { source: tokio::task::JoinError },
 }
 
 #[allow(missing_docs)]
@@ -338,6 +392,51 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn quarantines_untracked_files_older_than_the_cutoff() {
+        let setup = OldFileSetup::new();
+        let report_dir = TempDir::new().unwrap();
+        let report_path = report_dir.path().join("report.csv");
+
+        #[rustfmt::skip]
+        let config = build_config(setup.data_dir_arg(), [
+            "--objectstore-sleep-interval-minutes=0",
+            "--objectstore-quarantine", "1d",
+            "--objectstore-report", report_path.to_str().unwrap(),
+            "--objectstore-report-format", "csv",
+        ]).await;
+        tokio::spawn(async {
+            main(config).await.unwrap();
+        });
+
+        // file-based objectstore only has one file, it can't take long
+        sleep(Duration::from_millis(500)).await;
+
+        assert!(
+            !setup.file_path.exists(),
+            "The path {} should have been quarantined",
+            setup.file_path.as_path().display(),
+        );
+
+        let quarantine_dir = setup.data_dir.path().join("quarantine");
+        let quarantined = fs::read_dir(&quarantine_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path().join("some-old-file"))
+            .collect::<Vec<_>>();
+        assert_eq!(quarantined.len(), 1);
+        assert!(
+            quarantined[0].exists(),
+            "The path {} should exist",
+            quarantined[0].display(),
+        );
+
+        let report = fs::read_to_string(&report_path).unwrap();
+        let lines = report.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2, "{report}");
+        assert!(lines[1].contains(",some-old-file,"), "{report}");
+        assert!(lines[1].ends_with(",not_parquet,quarantined"), "{report}");
+    }
+
     async fn build_config(data_dir: &str, args: impl IntoIterator<Item = &str> + Send) -> Config {
         let sub_config =
             GarbageCollectorConfig::parse_from(iter::once("dummy-program-name").chain(args));
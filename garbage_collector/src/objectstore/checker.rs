@@ -1,3 +1,4 @@
+use super::quarantine::parse_quarantine_location;
 use chrono::{DateTime, Duration, Utc};
 use iox_catalog::interface::{Catalog, ParquetFileRepo};
 use object_store::ObjectMeta;
@@ -31,10 +32,45 @@ pub enum Error {
 
     #[snafu(display("The deleter task exited unexpectedly"))]
     DeleterExited {
-        source: tokio::sync::mpsc::error::SendError<ObjectMeta>,
+        source: tokio::sync::mpsc::error::SendError<Orphan>,
     },
 }
 
+/// An object that is not referenced by the catalog and should be deleted (or quarantined).
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Orphan {
+    pub(crate) meta: ObjectMeta,
+    pub(crate) reason: OrphanReason,
+}
+
+/// Why an object was judged to be orphaned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OrphanReason {
+    /// The object is not a parquet file.
+    NotParquet,
+
+    /// The file name of the object is not a valid UUID.
+    InvalidUuid,
+
+    /// The catalog does not know the object.
+    NotInCatalog,
+
+    /// The object has been quarantined for longer than the quarantine period.
+    QuarantineExpired,
+}
+
+impl OrphanReason {
+    /// Get human and machine readable name.
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Self::NotParquet => "not_parquet",
+            Self::InvalidUuid => "invalid_uuid",
+            Self::NotInCatalog => "not_in_catalog",
+            Self::QuarantineExpired => "quarantine_expired",
+        }
+    }
+}
+
 /// The number of parquet files we will ask the catalog to look for at once.
 // todo(pjb): I have no idea what's a good value here to amortize the request. More than 1 is a start.
 // Here's the idea: group everything you can for 100ms `RECEIVE_TIMEOUT`, because that's not so much
@@ -45,24 +81,28 @@ const RECEIVE_TIMEOUT: core::time::Duration = core::time::Duration::from_millis(
 
 pub(crate) type Result<T, E = Error> = std::result::Result<T, E>;
 
+/// `quarantine` is the quarantine period, if quarantine mode is enabled. Quarantined objects are
+/// only passed on to the deleter once they have been quarantined for this long.
 pub(crate) async fn perform(
     catalog: Arc<dyn Catalog>,
     cutoff: Duration,
+    quarantine: Option<Duration>,
     items: mpsc::Receiver<ObjectMeta>,
-    deleter: mpsc::Sender<ObjectMeta>,
+    deleter: mpsc::Sender<Orphan>,
 ) -> Result<()> {
     let mut repositories = catalog.repositories().await;
     let parquet_files = repositories.parquet_files();
 
-    perform_inner(parquet_files, cutoff, items, deleter).await
+    perform_inner(parquet_files, cutoff, quarantine, items, deleter).await
 }
 
 /// Allows easier mocking of just `ParquetFileRepo` in tests.
 async fn perform_inner(
     parquet_files: &mut dyn ParquetFileRepo,
     cutoff: Duration,
+    quarantine: Option<Duration>,
     mut items: mpsc::Receiver<ObjectMeta>,
-    deleter: mpsc::Sender<ObjectMeta>,
+    deleter: mpsc::Sender<Orphan>,
 ) -> Result<()> {
     let mut batch = Vec::with_capacity(CATALOG_BATCH_SIZE);
     loop {
@@ -83,8 +123,10 @@ async fn perform_inner(
         };
 
         if batch.len() >= CATALOG_BATCH_SIZE || timedout {
-            let older_than = chrono::offset::Utc::now() - cutoff;
-            for item in should_delete(batch, older_than, parquet_files).await {
+            let now = chrono::offset::Utc::now();
+            let older_than = now - cutoff;
+            let quarantined_before = quarantine.map(|quarantine| now - quarantine);
+            for item in should_delete(batch, older_than, quarantined_before, parquet_files).await {
                 deleter.send(item).await.context(DeleterExitedSnafu)?;
             }
             batch = Vec::with_capacity(100);
@@ -96,7 +138,11 @@ async fn perform_inner(
 /// [ObjectMeta] can be deleted.
 /// It can be deleted if it is old enough AND there isn't a reference in the catalog for it anymore (or ever)
 /// It will also say the file can be deleted if it isn't a parquet file or the uuid isn't valid.
-/// [should_delete] returns a subset of the input, which are the items that "should" be deleted.
+/// [should_delete] returns a subset of the input, which are the items that "should" be deleted,
+/// together with the reason why.
+/// Quarantined objects are only returned if they were quarantined before `quarantined_before`,
+/// and the catalog does not reference them (again). If quarantine mode is disabled
+/// (`quarantined_before` is `None`), they are never returned.
 // It first processes the easy checks, age, uuid, file suffix, and other parse/data input errors. This
 // checking is cheap. For the files that need to be checked against the catalog, it batches them to
 // reduce the number of requests on the wire and amortize the catalog overhead. Setting the batch size
@@ -104,16 +150,63 @@ async fn perform_inner(
 async fn should_delete(
     items: Vec<ObjectMeta>,
     cutoff: DateTime<Utc>,
+    quarantined_before: Option<DateTime<Utc>>,
     parquet_files: &mut dyn ParquetFileRepo,
-) -> Vec<ObjectMeta> {
+) -> Vec<Orphan> {
     // to_delete is the vector we will return to the caller containing ObjectMeta we think should be deleted.
     // it is never longer than `items`
     let mut to_delete = Vec::with_capacity(items.len());
     // After filtering out potential errors and non-parquet files, this vector accumulates the objects
     // that need to be checked against the catalog to see if we can delete them.
     let mut to_check_in_catalog = Vec::with_capacity(items.len());
+    // Quarantined parquet files whose quarantine expired. They were judged orphaned when they
+    // were quarantined, but are checked against the catalog again before they are deleted.
+    let mut expired_to_check_in_catalog = vec![];
 
     for candidate in items {
+        // quarantined objects were already judged orphaned, only their quarantine time matters
+        if let Some(quarantined) = parse_quarantine_location(&candidate.location) {
+            match (quarantined.since, quarantined_before) {
+                (Some(since), Some(quarantined_before)) if since < quarantined_before => {
+                    match parquet_object_store_id(&quarantined.original_location) {
+                        Some(object_store_id) => {
+                            expired_to_check_in_catalog.push((object_store_id, candidate))
+                        }
+                        None => {
+                            info!(
+                                location = %candidate.location,
+                                deleting = true,
+                                reason = "quarantine expired",
+                                since = %since,
+                                "Scheduling file for deletion",
+                            );
+                            to_delete.push(Orphan {
+                                meta: candidate,
+                                reason: OrphanReason::QuarantineExpired,
+                            });
+                        }
+                    }
+                }
+                (None, _) => {
+                    warn!(
+                        location = %candidate.location,
+                        deleting = false,
+                        reason = "unknown quarantine time",
+                        "Ignoring object",
+                    );
+                }
+                _ => {
+                    debug!(
+                        location = %candidate.location,
+                        deleting = false,
+                        reason = "quarantined",
+                        "Ignoring object",
+                    );
+                }
+            }
+            continue;
+        }
+
         if cutoff < candidate.last_modified {
             // expected to be a common reason to skip a file
             debug!(
@@ -156,7 +249,10 @@ async fn should_delete(
                     reason = "not a valid UUID",
                     "Scheduling file for deletion",
                 );
-                to_delete.push(candidate)
+                to_delete.push(Orphan {
+                    meta: candidate,
+                    reason: OrphanReason::InvalidUuid,
+                })
             }
         } else {
             // expected to be a rare situation so warn.
@@ -166,14 +262,22 @@ async fn should_delete(
                 reason = "not a .parquet file",
                 "Scheduling file for deletion",
             );
-            to_delete.push(candidate)
+            to_delete.push(Orphan {
+                meta: candidate,
+                reason: OrphanReason::NotParquet,
+            })
         }
     }
 
     // do_not_delete contains the items that are present in the catalog
-    let mut do_not_delete: HashSet<Uuid> = HashSet::with_capacity(to_check_in_catalog.len());
-    for batch in to_check_in_catalog.chunks(CATALOG_BATCH_SIZE) {
-        let just_uuids: Vec<_> = batch.iter().map(|id| id.0).collect();
+    let all_uuids: Vec<_> = to_check_in_catalog
+        .iter()
+        .chain(&expired_to_check_in_catalog)
+        .map(|c| c.0)
+        .collect();
+    let mut do_not_delete: HashSet<Uuid> = HashSet::with_capacity(all_uuids.len());
+    for batch in all_uuids.chunks(CATALOG_BATCH_SIZE) {
+        let just_uuids = batch.to_vec();
         match check_ids_exists_in_catalog(just_uuids.clone(), parquet_files).await {
             Ok(present_uuids) => {
                 do_not_delete.extend(present_uuids.iter());
@@ -201,16 +305,57 @@ async fn should_delete(
         });
     }
 
+    // a quarantined file the catalog references (again) must not be deleted, but it is not in its
+    // original location either, so it needs attention.
+    for (object_store_id, candidate) in expired_to_check_in_catalog {
+        if do_not_delete.contains(&object_store_id) {
+            warn!(
+                location = %candidate.location,
+                deleting = false,
+                uuid = %object_store_id,
+                reason = "quarantined object is present in catalog",
+                "Ignoring object",
+            );
+        } else {
+            info!(
+                location = %candidate.location,
+                deleting = true,
+                reason = "quarantine expired",
+                "Scheduling file for deletion",
+            );
+            to_delete.push(Orphan {
+                meta: candidate,
+                reason: OrphanReason::QuarantineExpired,
+            });
+        }
+    }
+
     // we have a Vec of uuids for the files we _do not_ want to delete (present in the catalog)
     // remove these uuids from the Vec of all uuids we checked, adding the remainder to the delete list
     to_check_in_catalog
-        .iter()
+        .into_iter()
         .filter(|c| !do_not_delete.contains(&c.0))
-        .for_each(|c| to_delete.push(c.1.clone()));
+        .for_each(|c| {
+            to_delete.push(Orphan {
+                meta: c.1,
+                reason: OrphanReason::NotInCatalog,
+            })
+        });
 
     to_delete
 }
 
+/// Returns the object store id of the parquet file at `location`, if it is one.
+fn parquet_object_store_id(location: &object_store::path::Path) -> Option<Uuid> {
+    location
+        .parts()
+        .last()?
+        .as_ref()
+        .strip_suffix(".parquet")?
+        .parse()
+        .ok()
+}
+
 /// helper to check a batch of ids for presence in the catalog.
 /// returns a list of the ids (from the original batch) that exist (or catalog error).
 async fn check_ids_exists_in_catalog(
@@ -226,6 +371,7 @@ async fn check_ids_exists_in_catalog(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::objectstore::quarantine::quarantine_location;
     use async_trait::async_trait;
     use data_types::{
         ColumnId, ColumnSet, CompactionLevel, NamespaceId, ParquetFile, ParquetFileId,
@@ -282,6 +428,7 @@ mod tests {
             row_count: 0,
             compaction_level: CompactionLevel::Initial,
             created_at: Timestamp::new(1),
+            data_written_at: Timestamp::new(1),
             column_set: ColumnSet::new([ColumnId::new(1), ColumnId::new(2)]),
             max_l0_created_at: Timestamp::new(1),
         };
@@ -319,7 +466,7 @@ mod tests {
             e_tag: None,
         };
 
-        let results = should_delete(vec![item], cutoff, parquet_files).await;
+        let results = should_delete(vec![item], cutoff, None, parquet_files).await;
         assert_eq!(results.len(), 0);
     }
 
@@ -348,7 +495,7 @@ mod tests {
             e_tag: None,
         };
 
-        let results = should_delete(vec![item], cutoff, parquet_files).await;
+        let results = should_delete(vec![item], cutoff, None, parquet_files).await;
         assert_eq!(results.len(), 0);
     }
 
@@ -369,7 +516,7 @@ mod tests {
             e_tag: None,
         };
 
-        let results = should_delete(vec![item], cutoff, parquet_files).await;
+        let results = should_delete(vec![item], cutoff, None, parquet_files).await;
         assert_eq!(results.len(), 0);
     }
 
@@ -397,7 +544,7 @@ mod tests {
             e_tag: None,
         };
 
-        let results = should_delete(vec![item], cutoff, parquet_files).await;
+        let results = should_delete(vec![item], cutoff, None, parquet_files).await;
         assert_eq!(results.len(), 0);
     }
 
@@ -425,9 +572,15 @@ mod tests {
             size: 0,
             e_tag: None,
         };
-        let results = should_delete(vec![item.clone()], cutoff, parquet_files).await;
+        let results = should_delete(vec![item.clone()], cutoff, None, parquet_files).await;
         assert_eq!(results.len(), 1);
-        assert_eq!(results[0], item);
+        assert_eq!(
+            results[0],
+            Orphan {
+                meta: item,
+                reason: OrphanReason::NotInCatalog,
+            }
+        );
     }
 
     #[tokio::test]
@@ -447,9 +600,114 @@ mod tests {
             e_tag: None,
         };
 
-        let results = should_delete(vec![item.clone()], cutoff, parquet_files).await;
+        let results = should_delete(vec![item.clone()], cutoff, None, parquet_files).await;
         assert_eq!(results.len(), 1);
-        assert_eq!(results[0], item);
+        assert_eq!(
+            results[0],
+            Orphan {
+                meta: item,
+                reason: OrphanReason::InvalidUuid,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_old_file_that_is_not_parquet() {
+        let metric_registry = Arc::new(metric::Registry::new());
+        let catalog: Arc<dyn Catalog> = Arc::new(MemCatalog::new(Arc::clone(&metric_registry)));
+        let mut repositories = catalog.repositories().await;
+        let parquet_files = repositories.parquet_files();
+
+        let cutoff = *NEWER_TIME;
+        let last_modified = *OLDER_TIME;
+
+        let item = ObjectMeta {
+            location: Path::from("1/2/some-old-file"),
+            last_modified,
+            size: 0,
+            e_tag: None,
+        };
+
+        let results = should_delete(vec![item.clone()], cutoff, None, parquet_files).await;
+        assert_eq!(
+            results,
+            vec![Orphan {
+                meta: item,
+                reason: OrphanReason::NotParquet,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_quarantined_file_after_quarantine() {
+        let metric_registry = Arc::new(metric::Registry::new());
+        let catalog: Arc<dyn Catalog> = Arc::new(MemCatalog::new(Arc::clone(&metric_registry)));
+        let mut repositories = catalog.repositories().await;
+        let parquet_files = repositories.parquet_files();
+
+        let location = ParquetFilePath::new(
+            NamespaceId::new(1),
+            TableId::new(2),
+            &TransitionPartitionId::Deprecated(PartitionId::new(4)),
+            Uuid::new_v4(),
+        )
+        .object_store_path();
+
+        // quarantined at OLDER_TIME, but moving the object did not change its modification time
+        let item = ObjectMeta {
+            location: quarantine_location(&location, *OLDER_TIME),
+            last_modified: *OLDER_TIME - Duration::days(1),
+            size: 0,
+            e_tag: None,
+        };
+        let cutoff = *NEWER_TIME;
+
+        // quarantine disabled: quarantined files are kept
+        let results = should_delete(vec![item.clone()], cutoff, None, parquet_files).await;
+        assert_eq!(results, vec![]);
+
+        // still quarantined
+        let results =
+            should_delete(vec![item.clone()], cutoff, Some(*OLDER_TIME), parquet_files).await;
+        assert_eq!(results, vec![]);
+
+        // quarantine expired
+        let results =
+            should_delete(vec![item.clone()], cutoff, Some(*NEWER_TIME), parquet_files).await;
+        assert_eq!(
+            results,
+            vec![Orphan {
+                meta: item,
+                reason: OrphanReason::QuarantineExpired,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn dont_delete_quarantined_file_in_catalog() {
+        let (catalog, file_in_catalog) = create_catalog_and_file().await;
+        let mut repositories = catalog.repositories().await;
+        let parquet_files = repositories.parquet_files();
+
+        let location = ParquetFilePath::new(
+            file_in_catalog.namespace_id,
+            file_in_catalog.table_id,
+            &file_in_catalog.partition_id.clone(),
+            file_in_catalog.object_store_id,
+        )
+        .object_store_path();
+
+        let item = ObjectMeta {
+            location: quarantine_location(&location, *OLDER_TIME),
+            last_modified: *OLDER_TIME,
+            size: 0,
+            e_tag: None,
+        };
+
+        // the quarantine expired, but the catalog references the file
+        let results =
+            should_delete(vec![item], *NEWER_TIME, Some(*NEWER_TIME), parquet_files).await;
+        assert_eq!(results, vec![]);
     }
 
     /// The garbage collector checks the catalog for files it _should not delete_. If we can't reach
@@ -496,7 +754,8 @@ mod tests {
         assert_eq!(pf, file_in_catalog);
 
         // because of the db error, there should be no results
-        let results = should_delete(vec![item.clone()], cutoff, &mut mocked_parquet_files).await;
+        let results =
+            should_delete(vec![item.clone()], cutoff, None, &mut mocked_parquet_files).await;
         assert_eq!(results.len(), 0);
     }
 
@@ -1,38 +1,46 @@
-use futures::{StreamExt, TryStreamExt};
-use object_store::{DynObjectStore, ObjectMeta};
+use super::{
+    checker::{Orphan, OrphanReason},
+    quarantine::quarantine_location,
+    report::{self, Action, OrphanReport},
+};
+use chrono::Utc;
+use futures::{future, StreamExt, TryStreamExt};
+use object_store::DynObjectStore;
 use observability_deps::tracing::info;
 use snafu::prelude::*;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 
+/// Deletes the received orphans, or moves them to the quarantine if `quarantine` is set.
+///
+/// Every handled orphan is recorded in the `report`, if any.
 pub(crate) async fn perform(
     shutdown: CancellationToken,
     object_store: Arc<DynObjectStore>,
     dry_run: bool,
+    quarantine: bool,
     concurrent_deletes: usize,
-    items: mpsc::Receiver<ObjectMeta>,
+    mut report: Option<OrphanReport>,
+    items: mpsc::Receiver<Orphan>,
 ) -> Result<()> {
     let stream_fu = tokio_stream::wrappers::ReceiverStream::new(items)
-        .map(|item| {
+        .map(|orphan| {
             let object_store = Arc::clone(&object_store);
 
             async move {
-                let path = item.location;
-                if dry_run {
-                    info!(?path, "Not deleting due to dry run");
-                    Ok(())
-                } else {
-                    info!("Deleting {path}");
-                    object_store
-                        .delete(&path)
-                        .await
-                        .context(DeletingSnafu { path })
-                }
+                let action = handle(&object_store, &orphan, dry_run, quarantine).await?;
+                Ok((orphan, action))
             }
         })
         .buffer_unordered(concurrent_deletes)
-        .try_collect();
+        .try_for_each(|(orphan, action)| {
+            let res = match report.as_mut() {
+                Some(report) => report.write(&orphan, action).context(ReportSnafu),
+                None => Ok(()),
+            };
+            future::ready(res)
+        });
 
     tokio::select! {
         _ = shutdown.cancelled() => {
@@ -47,6 +55,36 @@ pub(crate) async fn perform(
     Ok(())
 }
 
+async fn handle(
+    object_store: &Arc<DynObjectStore>,
+    orphan: &Orphan,
+    dry_run: bool,
+    quarantine: bool,
+) -> Result<Action> {
+    let path = orphan.meta.location.clone();
+    let reason = orphan.reason.name();
+
+    if dry_run {
+        info!(?path, reason, "Not deleting due to dry run");
+        Ok(Action::DryRun)
+    } else if quarantine && orphan.reason != OrphanReason::QuarantineExpired {
+        let to = quarantine_location(&path, Utc::now());
+        info!(reason, "Quarantining {path} as {to}");
+        object_store
+            .rename(&path, &to)
+            .await
+            .context(QuarantiningSnafu { path })?;
+        Ok(Action::Quarantined)
+    } else {
+        info!(reason, "Deleting {path}");
+        object_store
+            .delete(&path)
+            .await
+            .context(DeletingSnafu { path })?;
+        Ok(Action::Deleted)
+    }
+}
+
 #[derive(Debug, Snafu)]
 #[allow(missing_docs)]
 pub enum Error {
@@ -55,6 +93,15 @@ pub enum Error {
         source: object_store::Error,
         path: object_store::path::Path,
     },
+
+    #[snafu(display("{path} could not be quarantined"))]
+    Quarantining {
+        source: object_store::Error,
+        path: object_store::path::Path,
+    },
+
+    #[snafu(display("The orphan report could not be written"))]
+    Report { source: report::Error },
 }
 
 pub(crate) type Result<T, E = Error> = std::result::Result<T, E>;
@@ -62,12 +109,12 @@ pub(crate) type Result<T, E = Error> = std::result::Result<T, E>;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::objectstore::quarantine::parse_quarantine_location;
     use bytes::Bytes;
-    use chrono::Utc;
     use data_types::{NamespaceId, PartitionId, TableId, TransitionPartitionId};
-    use object_store::path::Path;
+    use object_store::{path::Path, ObjectMeta};
     use parquet_file::ParquetFilePath;
-    use std::time::Duration;
+    use std::{collections::HashSet, time::Duration};
     use uuid::Uuid;
 
     #[tokio::test]
@@ -88,7 +135,9 @@ mod tests {
 
             async move {
                 for item in items {
-                    tx.send(item.clone()).await.unwrap();
+                    tx.send(orphan(item, OrphanReason::NotInCatalog))
+                        .await
+                        .unwrap();
                 }
 
                 // Send a shutdown signal
@@ -110,7 +159,9 @@ mod tests {
             shutdown,
             Arc::clone(&object_store),
             dry_run,
+            false,
             concurrent_deletes,
+            None,
             rx,
         );
         // Unusual test because there is no assertion but the call below should
@@ -121,6 +172,76 @@ mod tests {
             .unwrap();
     }
 
+    #[tokio::test]
+    async fn perform_quarantine() {
+        let object_store: Arc<DynObjectStore> = Arc::new(object_store::memory::InMemory::new());
+        let items = populate_os_with_items(&object_store, 2).await;
+
+        // orphans are moved to the quarantine
+        let (tx, rx) = mpsc::channel(1000);
+        for item in &items {
+            tx.send(orphan(item.clone(), OrphanReason::NotInCatalog))
+                .await
+                .unwrap();
+        }
+        drop(tx);
+        perform(
+            CancellationToken::new(),
+            Arc::clone(&object_store),
+            false,
+            true,
+            2,
+            None,
+            rx,
+        )
+        .await
+        .unwrap();
+
+        let quarantined = list_os_elements(&object_store).await;
+        assert_eq!(
+            quarantined
+                .iter()
+                .map(|item| parse_quarantine_location(&item.location)
+                    .unwrap()
+                    .original_location)
+                .collect::<HashSet<_>>(),
+            items
+                .iter()
+                .map(|item| item.location.clone())
+                .collect::<HashSet<_>>(),
+        );
+
+        // expired quarantined objects are deleted
+        let (tx, rx) = mpsc::channel(1000);
+        for item in quarantined {
+            tx.send(orphan(item, OrphanReason::QuarantineExpired))
+                .await
+                .unwrap();
+        }
+        drop(tx);
+        perform(
+            CancellationToken::new(),
+            Arc::clone(&object_store),
+            false,
+            true,
+            2,
+            None,
+            rx,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(count_os_element(&object_store).await, 0);
+    }
+
+    fn orphan(meta: ObjectMeta, reason: OrphanReason) -> Orphan {
+        Orphan { meta, reason }
+    }
+
+    async fn list_os_elements(os: &Arc<DynObjectStore>) -> Vec<ObjectMeta> {
+        os.list(None).await.unwrap().try_collect().await.unwrap()
+    }
+
     async fn count_os_element(os: &Arc<DynObjectStore>) -> usize {
         let objects = os.list(None).await.unwrap();
         objects.fold(0, |acc, _| async move { acc + 1 }).await
@@ -4,3 +4,7 @@ pub(crate) mod checker;
 pub(crate) mod deleter;
 /// Logic for listing all files in object storage.
 pub(crate) mod lister;
+/// Locations of quarantined files in object storage.
+pub(crate) mod quarantine;
+/// Machine-readable report of orphaned files in object storage.
+pub(crate) mod report;
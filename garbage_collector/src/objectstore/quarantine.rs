@@ -0,0 +1,86 @@
+use chrono::{DateTime, TimeZone, Utc};
+use object_store::path::{Path, PathPart};
+
+/// Prefix under which orphaned objects are quarantined.
+pub(crate) const QUARANTINE_PREFIX: &str = "quarantine";
+
+/// The location that the object at `location` is moved to when it is quarantined at `now`.
+///
+/// The quarantine time is part of the location (`quarantine/<unix seconds>/<location>`) because
+/// object stores do not agree on whether moving an object updates its modification time.
+pub(crate) fn quarantine_location(location: &Path, now: DateTime<Utc>) -> Path {
+    [
+        PathPart::from(QUARANTINE_PREFIX),
+        PathPart::from(now.timestamp().to_string()),
+    ]
+    .into_iter()
+    .chain(location.parts())
+    .collect()
+}
+
+/// An object under the [`QUARANTINE_PREFIX`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Quarantined {
+    /// When the object was quarantined, `None` if the location does not tell.
+    pub(crate) since: Option<DateTime<Utc>>,
+
+    /// The location the object was quarantined from.
+    pub(crate) original_location: Path,
+}
+
+/// Parse a location created by [`quarantine_location`].
+///
+/// Returns `None` if the location is not under the [`QUARANTINE_PREFIX`].
+pub(crate) fn parse_quarantine_location(location: &Path) -> Option<Quarantined> {
+    let mut parts = location.parts();
+    if parts.next()?.as_ref() != QUARANTINE_PREFIX {
+        return None;
+    }
+
+    let since = parts
+        .next()
+        .and_then(|part| part.as_ref().parse::<i64>().ok())
+        .and_then(|secs| Utc.timestamp_opt(secs, 0).single());
+
+    Some(Quarantined {
+        since,
+        original_location: parts.collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quarantine_location_roundtrip() {
+        let location = Path::from("1/2/4/00000000-0000-0000-0000-000000000000.parquet");
+        let now = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+
+        let quarantined = quarantine_location(&location, now);
+        assert_eq!(
+            quarantined.as_ref(),
+            "quarantine/1700000000/1/2/4/00000000-0000-0000-0000-000000000000.parquet"
+        );
+
+        assert_eq!(
+            parse_quarantine_location(&quarantined),
+            Some(Quarantined {
+                since: Some(now),
+                original_location: location.clone(),
+            }),
+        );
+        assert_eq!(parse_quarantine_location(&location), None);
+    }
+
+    #[test]
+    fn quarantine_location_without_time() {
+        assert_eq!(
+            parse_quarantine_location(&Path::from("quarantine/foo/1/2")),
+            Some(Quarantined {
+                since: None,
+                original_location: Path::from("1/2"),
+            }),
+        );
+    }
+}
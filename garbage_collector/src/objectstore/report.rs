@@ -0,0 +1,303 @@
+use super::{
+    checker::{Orphan, OrphanReason},
+    quarantine::parse_quarantine_location,
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use clap_blocks::garbage_collector::OrphanReportFormat;
+use object_store::path::Path;
+use serde::Serialize;
+use snafu::prelude::*;
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    path::PathBuf,
+};
+
+const CSV_HEADER: &str = "time,location,namespace_id,table_id,size,last_modified,reason,action";
+
+#[derive(Debug, Snafu)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[snafu(display("The orphan report {} could not be opened: {source}", path.display()))]
+    Open {
+        source: std::io::Error,
+        path: PathBuf,
+    },
+
+    #[snafu(display("The orphan report could not be written: {source}"))]
+    Write { source: std::io::Error },
+}
+
+pub(crate) type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// What the garbage collector did to an [`Orphan`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Action {
+    /// Nothing, because of a dry run.
+    DryRun,
+
+    /// The object was moved under the quarantine prefix.
+    Quarantined,
+
+    /// The object was deleted.
+    Deleted,
+}
+
+impl Action {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::DryRun => "dry_run",
+            Self::Quarantined => "quarantined",
+            Self::Deleted => "deleted",
+        }
+    }
+}
+
+/// One line of the report.
+#[derive(Debug, Serialize)]
+struct Record<'a> {
+    time: String,
+    location: &'a str,
+    namespace_id: Option<i64>,
+    table_id: Option<i64>,
+    size: usize,
+    last_modified: String,
+    reason: &'static str,
+    action: &'static str,
+}
+
+impl<'a> Record<'a> {
+    fn new(orphan: &'a Orphan, action: Action, now: DateTime<Utc>) -> Self {
+        let (namespace_id, table_id) = namespace_and_table(&orphan.meta.location, orphan.reason);
+
+        Self {
+            time: now.to_rfc3339_opts(SecondsFormat::Secs, true),
+            location: orphan.meta.location.as_ref(),
+            namespace_id,
+            table_id,
+            size: orphan.meta.size,
+            last_modified: orphan
+                .meta
+                .last_modified
+                .to_rfc3339_opts(SecondsFormat::Secs, true),
+            reason: orphan.reason.name(),
+            action: action.name(),
+        }
+    }
+
+    fn to_csv(&self) -> String {
+        [
+            self.time.clone(),
+            csv_escape(self.location),
+            self.namespace_id
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+            self.table_id.map(|id| id.to_string()).unwrap_or_default(),
+            self.size.to_string(),
+            self.last_modified.clone(),
+            self.reason.to_owned(),
+            self.action.to_owned(),
+        ]
+        .join(",")
+    }
+}
+
+/// Appends every [`Orphan`] that the garbage collector handled to a file, one line per orphan.
+#[derive(Debug)]
+pub(crate) struct OrphanReport {
+    file: File,
+    format: OrphanReportFormat,
+}
+
+impl OrphanReport {
+    /// Open report, appending to `path` if it exists.
+    pub(crate) fn open(path: PathBuf, format: OrphanReportFormat) -> Result<Self> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .context(OpenSnafu { path: path.clone() })?;
+
+        let is_empty = file
+            .metadata()
+            .context(OpenSnafu { path: path.clone() })?
+            .len()
+            == 0;
+        if is_empty && format == OrphanReportFormat::Csv {
+            writeln!(file, "{CSV_HEADER}").context(OpenSnafu { path })?;
+        }
+
+        Ok(Self { file, format })
+    }
+
+    /// Record what was done to an orphan.
+    pub(crate) fn write(&mut self, orphan: &Orphan, action: Action) -> Result<()> {
+        let record = Record::new(orphan, action, Utc::now());
+        let line = match self.format {
+            OrphanReportFormat::Json => {
+                serde_json::to_string(&record).expect("record is serializable")
+            }
+            OrphanReportFormat::Csv => record.to_csv(),
+        };
+
+        // written at once so that concurrent readers never see partial lines
+        self.file
+            .write_all(format!("{line}\n").as_bytes())
+            .context(WriteSnafu)
+    }
+}
+
+/// Namespace and table ID of an object, if its location is a parquet file location (or a
+/// quarantined one).
+fn namespace_and_table(location: &Path, reason: OrphanReason) -> (Option<i64>, Option<i64>) {
+    let location = match reason {
+        OrphanReason::QuarantineExpired => match parse_quarantine_location(location) {
+            Some(quarantined) => quarantined.original_location,
+            None => location.clone(),
+        },
+        _ => location.clone(),
+    };
+
+    let mut parts = location.parts();
+    let mut next_id = || {
+        parts
+            .next()
+            .and_then(|part| part.as_ref().parse::<i64>().ok())
+    };
+    let namespace_id = next_id();
+    let table_id = next_id();
+
+    match (namespace_id, table_id) {
+        (Some(namespace_id), Some(table_id)) => (Some(namespace_id), Some(table_id)),
+        _ => (None, None),
+    }
+}
+
+fn csv_escape(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objectstore::quarantine::quarantine_location;
+    use object_store::ObjectMeta;
+    use tempfile::TempDir;
+
+    #[test]
+    fn json_report() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("report.json");
+
+        let mut report = OrphanReport::open(path.clone(), OrphanReportFormat::Json).unwrap();
+        report
+            .write(
+                &orphan("1/2/4/foo.parquet", OrphanReason::NotInCatalog),
+                Action::Quarantined,
+            )
+            .unwrap();
+        report
+            .write(
+                &orphan("some-file", OrphanReason::NotParquet),
+                Action::DryRun,
+            )
+            .unwrap();
+        drop(report);
+
+        let lines = read_lines(&path)
+            .into_iter()
+            .map(|line| serde_json::from_str::<serde_json::Value>(&line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+
+        assert_eq!(lines[0]["location"], "1/2/4/foo.parquet");
+        assert_eq!(lines[0]["namespace_id"], 1);
+        assert_eq!(lines[0]["table_id"], 2);
+        assert_eq!(lines[0]["size"], 42);
+        assert_eq!(lines[0]["last_modified"], "2022-01-01T00:00:00Z");
+        assert_eq!(lines[0]["reason"], "not_in_catalog");
+        assert_eq!(lines[0]["action"], "quarantined");
+
+        assert_eq!(lines[1]["location"], "some-file");
+        assert_eq!(lines[1]["namespace_id"], serde_json::Value::Null);
+        assert_eq!(lines[1]["table_id"], serde_json::Value::Null);
+        assert_eq!(lines[1]["reason"], "not_parquet");
+        assert_eq!(lines[1]["action"], "dry_run");
+    }
+
+    #[test]
+    fn csv_report() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("report.csv");
+
+        let quarantined = quarantine_location(&Path::from("1/2/4/foo.parquet"), Utc::now());
+        let mut report = OrphanReport::open(path.clone(), OrphanReportFormat::Csv).unwrap();
+        report
+            .write(
+                &orphan(quarantined.as_ref(), OrphanReason::QuarantineExpired),
+                Action::Deleted,
+            )
+            .unwrap();
+        drop(report);
+
+        // reopening appends without repeating the header
+        let mut report = OrphanReport::open(path.clone(), OrphanReportFormat::Csv).unwrap();
+        report
+            .write(
+                &orphan("some-file", OrphanReason::NotParquet),
+                Action::Deleted,
+            )
+            .unwrap();
+        drop(report);
+
+        let lines = read_lines(&path);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], CSV_HEADER);
+
+        // strip report time
+        let lines = lines[1..]
+            .iter()
+            .map(|line| line.split_once(',').unwrap().1)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                format!("{quarantined},1,2,42,2022-01-01T00:00:00Z,quarantine_expired,deleted"),
+                String::from("some-file,,,42,2022-01-01T00:00:00Z,not_parquet,deleted"),
+            ]
+        );
+    }
+
+    #[test]
+    fn csv_escaping() {
+        assert_eq!(csv_escape("1/2/foo.parquet"), "1/2/foo.parquet");
+        assert_eq!(csv_escape("a,b"), r#""a,b""#);
+        assert_eq!(csv_escape(r#"a"b"#), r#""a""b""#);
+    }
+
+    fn orphan(location: &str, reason: OrphanReason) -> Orphan {
+        Orphan {
+            meta: ObjectMeta {
+                location: Path::from(location),
+                last_modified: DateTime::parse_from_rfc3339("2022-01-01T00:00:00Z")
+                    .unwrap()
+                    .with_timezone(&Utc),
+                size: 42,
+                e_tag: None,
+            },
+            reason,
+        }
+    }
+
+    fn read_lines(path: &std::path::Path) -> Vec<String> {
+        std::fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(ToOwned::to_owned)
+            .collect()
+    }
+}
@@ -0,0 +1,2 @@
+/// Logic for removing tombstones from the catalog once they have been applied.
+pub(crate) mod remover;
@@ -0,0 +1,172 @@
+use data_types::TombstoneId;
+use iox_catalog::interface::Catalog;
+use observability_deps::tracing::*;
+use snafu::prelude::*;
+use std::{sync::Arc, time::Duration};
+use tokio::{select, time::sleep};
+use tokio_util::sync::CancellationToken;
+
+pub(crate) async fn perform(
+    shutdown: CancellationToken,
+    catalog: Arc<dyn Catalog>,
+    cutoff: Duration,
+    sleep_interval_minutes: u64,
+    dry_run: bool,
+) -> Result<()> {
+    loop {
+        if !dry_run {
+            let removed = remove_applied(&*catalog, cutoff).await?;
+            info!(removed_count = %removed.len(), "iox_catalog::remove_created_before()");
+        } else {
+            debug!("dry run enabled for tombstone remover");
+        };
+
+        select! {
+            _ = shutdown.cancelled() => {
+                break
+            },
+            _ = sleep(Duration::from_secs(60 * sleep_interval_minutes)) => (),
+        }
+    }
+    Ok(())
+}
+
+/// Remove the tombstones created more than `cutoff` ago that no longer apply to any parquet file.
+///
+/// Tombstones are removed by creation time, so none created after the oldest tombstone that still
+/// applies to a file is removed. The cutoff is measured by the catalog clock, which assigns the
+/// tombstone creation times, and covers data affected by a tombstone that an ingester has not
+/// persisted yet.
+pub(crate) async fn remove_applied(
+    catalog: &dyn Catalog,
+    cutoff: Duration,
+) -> Result<Vec<TombstoneId>> {
+    let mut repos = catalog.repositories().await;
+
+    let now = repos
+        .tombstones()
+        .catalog_time()
+        .await
+        .context(ListingSnafu)?;
+    let mut older_than = now - cutoff.as_nanos() as i64;
+
+    let oldest_unapplied = repos
+        .tombstones()
+        .list_unapplied()
+        .await
+        .context(ListingSnafu)?
+        .into_iter()
+        .map(|t| t.created_at)
+        .min();
+    if let Some(oldest_unapplied) = oldest_unapplied {
+        older_than = older_than.min(oldest_unapplied);
+    }
+
+    repos
+        .tombstones()
+        .remove_created_before(older_than) // read/write
+        .await
+        .context(RemovingSnafu)
+}
+
+#[derive(Debug, Snafu)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[snafu(display("Failed to list unapplied tombstones in catalog"))]
+    Listing {
+        source: iox_catalog::interface::Error,
+    },
+
+    #[snafu(display("Failed to remove applied tombstones from catalog"))]
+    Removing {
+        source: iox_catalog::interface::Error,
+    },
+}
+
+pub(crate) type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_types::{
+        CompactionLevel, DeleteExpr, DeletePredicate, Op, Scalar, TableId, TimestampRange,
+        Tombstone,
+    };
+    use iox_catalog::{
+        mem::MemCatalog,
+        test_helpers::{arbitrary_namespace, arbitrary_parquet_file_params, arbitrary_table},
+    };
+
+    async fn create_tombstone(catalog: &dyn Catalog, table_id: TableId) -> Tombstone {
+        let predicate = DeletePredicate {
+            range: TimestampRange::new(0, 100),
+            exprs: vec![DeleteExpr::new(
+                "region".to_string(),
+                Op::Eq,
+                Scalar::String("west".to_string()),
+            )],
+        };
+        let tombstone = catalog
+            .repositories()
+            .await
+            .tombstones()
+            .create(table_id, &predicate)
+            .await
+            .unwrap();
+        // creation times must be distinct
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        tombstone
+    }
+
+    #[tokio::test]
+    async fn removes_applied_tombstones() {
+        let metric_registry = Arc::new(metric::Registry::new());
+        let catalog = Arc::new(MemCatalog::new(metric_registry));
+
+        let (table_1, file) = {
+            let mut repos = catalog.repositories().await;
+            let namespace = arbitrary_namespace(&mut *repos, "ns").await;
+            let table_1 = arbitrary_table(&mut *repos, "t1", &namespace).await;
+            let table_2 = arbitrary_table(&mut *repos, "t2", &namespace).await;
+            let partition = repos
+                .partitions()
+                .create_or_get("one".into(), table_2.id)
+                .await
+                .unwrap();
+            let file = repos
+                .parquet_files()
+                .create(arbitrary_parquet_file_params(
+                    &namespace, &table_2, &partition,
+                ))
+                .await
+                .unwrap();
+            (table_1, file)
+        };
+
+        let applied = create_tombstone(&*catalog, table_1.id).await;
+        let unapplied = create_tombstone(&*catalog, file.table_id).await;
+        let newer = create_tombstone(&*catalog, table_1.id).await;
+
+        // nothing is older than the cutoff
+        let removed = remove_applied(&*catalog, Duration::from_secs(86_400))
+            .await
+            .unwrap();
+        assert!(removed.is_empty());
+
+        // tombstones created after one that still applies to a file are kept
+        let removed = remove_applied(&*catalog, Duration::ZERO).await.unwrap();
+        assert_eq!(removed, vec![applied.id]);
+
+        // once the file has been rewritten, all tombstones can be removed
+        catalog
+            .repositories()
+            .await
+            .parquet_files()
+            .create_upgrade_delete(&[file.id], &[], &[], CompactionLevel::Initial)
+            .await
+            .unwrap();
+        let mut removed = remove_applied(&*catalog, Duration::ZERO).await.unwrap();
+        removed.sort();
+        assert_eq!(removed, vec![unapplied.id, newer.id]);
+    }
+}
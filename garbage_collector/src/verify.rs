@@ -0,0 +1,831 @@
+//! Checks that every parquet file in the catalog exists in object storage, i.e. the reverse of
+//! what the object store garbage collector does.
+//!
+//! The result is a [`RepairPlan`] that lists the inconsistent catalog rows and what to do about
+//! them. Nothing is changed by this module.
+
+use bytes::Bytes;
+use data_types::{
+    ParquetFile, PartitionHashId, PartitionKey, Statistics, Timestamp, TransitionPartitionId,
+};
+use futures::{StreamExt, TryStreamExt};
+use iox_catalog::interface::{Catalog, SoftDeletedRows};
+use object_store::{path::Path, DynObjectStore};
+use observability_deps::tracing::*;
+use parquet_file::{
+    metadata::{IoxMetadata, IoxParquetMetaData},
+    ParquetFilePath,
+};
+use schema::TIME_COLUMN_NAME;
+use serde::Serialize;
+use snafu::prelude::*;
+use std::{collections::HashMap, sync::Arc};
+
+/// Size of the parquet footer: the metadata length followed by the `PAR1` magic.
+const FOOTER_SIZE: usize = 8;
+
+/// Magic bytes at the end of every parquet file.
+const PARQUET_MAGIC: &[u8; 4] = b"PAR1";
+
+#[derive(Debug, Snafu)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[snafu(display("The namespaces could not be listed: {source}"))]
+    ListNamespaces {
+        source: iox_catalog::interface::Error,
+    },
+
+    #[snafu(display("Namespace {name} not found"))]
+    NamespaceNotFound { name: String },
+
+    #[snafu(display("The parquet files of namespace {namespace} could not be listed: {source}"))]
+    ListParquetFiles {
+        source: iox_catalog::interface::Error,
+        namespace: String,
+    },
+
+    #[snafu(display("The partitions of namespace {namespace} could not be listed: {source}"))]
+    ListPartitions {
+        source: iox_catalog::interface::Error,
+        namespace: String,
+    },
+
+    #[snafu(display("{path} could not be read from the object store: {source}"))]
+    ObjectStore {
+        source: object_store::Error,
+        path: Path,
+    },
+}
+
+#[allow(missing_docs)]
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Options for [`verify`].
+#[derive(Debug, Clone)]
+pub struct VerifyOptions {
+    /// Only check the parquet files of this namespace. All namespaces are checked if `None`.
+    pub namespace: Option<String>,
+
+    /// Read the footer of every parquet file and check that the IOx metadata and the statistics
+    /// embedded in it match the catalog row. This is expensive.
+    pub validate_metadata: bool,
+
+    /// Number of parquet files that are checked concurrently.
+    pub concurrency: usize,
+}
+
+impl Default for VerifyOptions {
+    fn default() -> Self {
+        Self {
+            namespace: None,
+            validate_metadata: false,
+            concurrency: 10,
+        }
+    }
+}
+
+/// Result of [`verify`].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct RepairPlan {
+    /// Number of parquet files that were checked.
+    pub files_checked: usize,
+
+    /// One step for each inconsistent parquet file, ordered by parquet file ID.
+    pub steps: Vec<RepairStep>,
+}
+
+/// What to do about one inconsistent parquet file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RepairStep {
+    /// The suggested action.
+    pub action: RepairAction,
+
+    /// Catalog ID of the parquet file.
+    pub parquet_file_id: i64,
+
+    /// Namespace of the parquet file.
+    pub namespace_id: i64,
+
+    /// Table of the parquet file.
+    pub table_id: i64,
+
+    /// Partition of the parquet file.
+    pub partition_id: String,
+
+    /// Object store location of the parquet file.
+    pub location: String,
+
+    /// What is wrong with the parquet file.
+    pub inconsistency: Inconsistency,
+}
+
+/// Suggested action for an inconsistent parquet file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RepairAction {
+    /// Soft-delete the catalog row, since the data is gone and queries touching the file fail.
+    SoftDelete,
+
+    /// The file exists but does not match the catalog row, it needs to be investigated by an
+    /// operator.
+    Flag,
+}
+
+/// What is wrong with a parquet file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Inconsistency {
+    /// The file does not exist in the object store.
+    Missing,
+
+    /// The file size in the object store does not match the catalog row.
+    SizeMismatch {
+        /// Size according to the catalog.
+        catalog: i64,
+
+        /// Size according to the object store.
+        object_store: usize,
+    },
+
+    /// The IOx metadata embedded in the file cannot be read.
+    MetadataUnreadable {
+        /// Why the metadata cannot be read.
+        error: String,
+    },
+
+    /// The IOx metadata embedded in the file does not match the catalog row.
+    MetadataMismatch {
+        /// The field that does not match.
+        field: &'static str,
+
+        /// Value according to the catalog.
+        catalog: String,
+
+        /// Value according to the file.
+        file: String,
+    },
+}
+
+impl Inconsistency {
+    /// The suggested action.
+    pub fn action(&self) -> RepairAction {
+        match self {
+            Self::Missing => RepairAction::SoftDelete,
+            Self::SizeMismatch { .. }
+            | Self::MetadataUnreadable { .. }
+            | Self::MetadataMismatch { .. } => RepairAction::Flag,
+        }
+    }
+}
+
+/// Check that every parquet file in the catalog that is not marked for deletion exists in the
+/// object store with the expected size (and, optionally, the expected IOx metadata).
+///
+/// Errors talking to the catalog or the object store abort the check, so that a transient error
+/// is never mistaken for a missing file.
+pub async fn verify(
+    catalog: Arc<dyn Catalog>,
+    object_store: Arc<DynObjectStore>,
+    options: VerifyOptions,
+) -> Result<RepairPlan> {
+    let namespaces = {
+        let mut repos = catalog.repositories().await;
+        match &options.namespace {
+            Some(name) => {
+                let namespace = repos
+                    .namespaces()
+                    .get_by_name(name, SoftDeletedRows::ExcludeDeleted)
+                    .await
+                    .context(ListNamespacesSnafu)?
+                    .context(NamespaceNotFoundSnafu { name })?;
+                vec![namespace]
+            }
+            None => repos
+                .namespaces()
+                .list(SoftDeletedRows::ExcludeDeleted)
+                .await
+                .context(ListNamespacesSnafu)?,
+        }
+    };
+
+    let validate_metadata = options.validate_metadata;
+    let mut plan = RepairPlan::default();
+    for namespace in namespaces {
+        let files = catalog
+            .repositories()
+            .await
+            .parquet_files()
+            .list_by_namespace_not_to_delete(namespace.id)
+            .await
+            .context(ListParquetFilesSnafu {
+                namespace: &namespace.name,
+            })?;
+        info!(
+            namespace = %namespace.name,
+            n_files = files.len(),
+            "Verifying parquet files"
+        );
+        plan.files_checked += files.len();
+
+        let partition_keys = if validate_metadata {
+            Some(
+                read_partition_keys(catalog.as_ref(), &files)
+                    .await
+                    .context(ListPartitionsSnafu {
+                        namespace: &namespace.name,
+                    })?,
+            )
+        } else {
+            None
+        };
+        let partition_keys = partition_keys.as_ref();
+
+        let steps: Vec<Option<RepairStep>> = futures::stream::iter(files)
+            .map(|file| {
+                let object_store = Arc::clone(&object_store);
+                async move {
+                    let inconsistency = check_file(&object_store, &file, partition_keys).await?;
+                    Ok::<_, Error>(inconsistency.map(|i| RepairStep::new(&file, i)))
+                }
+            })
+            .buffer_unordered(options.concurrency)
+            .try_collect()
+            .await?;
+        plan.steps.extend(steps.into_iter().flatten());
+    }
+
+    plan.steps.sort_by_key(|step| step.parquet_file_id);
+    Ok(plan)
+}
+
+/// Look up the partition keys of the partitions the given files belong to.
+///
+/// Partitions are keyed by both their catalog ID and, if they have one, their hash ID, since
+/// files may reference either.
+async fn read_partition_keys(
+    catalog: &dyn Catalog,
+    files: &[ParquetFile],
+) -> Result<HashMap<TransitionPartitionId, PartitionKey>, iox_catalog::interface::Error> {
+    let mut ids = vec![];
+    let mut hash_ids: Vec<&PartitionHashId> = vec![];
+    for file in files {
+        match &file.partition_id {
+            TransitionPartitionId::Deprecated(id) => ids.push(*id),
+            TransitionPartitionId::Deterministic(hash_id) => hash_ids.push(hash_id),
+        }
+    }
+    ids.sort_unstable();
+    ids.dedup();
+    hash_ids.sort_unstable();
+    hash_ids.dedup();
+
+    let mut repos = catalog.repositories().await;
+    let mut partitions = repos.partitions().get_by_id_batch(ids).await?;
+    partitions.extend(repos.partitions().get_by_hash_id_batch(&hash_ids).await?);
+
+    Ok(partitions
+        .into_iter()
+        .flat_map(|partition| {
+            [
+                (
+                    TransitionPartitionId::Deprecated(partition.id),
+                    partition.partition_key.clone(),
+                ),
+                (partition.transition_partition_id(), partition.partition_key),
+            ]
+        })
+        .collect())
+}
+
+impl RepairStep {
+    fn new(file: &ParquetFile, inconsistency: Inconsistency) -> Self {
+        let location = ParquetFilePath::from(file).object_store_path();
+        warn!(%location, ?inconsistency, "Inconsistent parquet file");
+
+        Self {
+            action: inconsistency.action(),
+            parquet_file_id: file.id.get(),
+            namespace_id: file.namespace_id.get(),
+            table_id: file.table_id.get(),
+            partition_id: file.partition_id.to_string(),
+            location: location.to_string(),
+            inconsistency,
+        }
+    }
+}
+
+/// Check a single parquet file. The embedded metadata is only validated if `partition_keys` is
+/// given.
+async fn check_file(
+    object_store: &Arc<DynObjectStore>,
+    file: &ParquetFile,
+    partition_keys: Option<&HashMap<TransitionPartitionId, PartitionKey>>,
+) -> Result<Option<Inconsistency>> {
+    let path = ParquetFilePath::from(file).object_store_path();
+
+    let meta = match object_store.head(&path).await {
+        Ok(meta) => meta,
+        Err(object_store::Error::NotFound { .. }) => return Ok(Some(Inconsistency::Missing)),
+        Err(source) => return Err(Error::ObjectStore { source, path }),
+    };
+    if meta.size as i64 != file.file_size_bytes {
+        return Ok(Some(Inconsistency::SizeMismatch {
+            catalog: file.file_size_bytes,
+            object_store: meta.size,
+        }));
+    }
+
+    let Some(partition_keys) = partition_keys else {
+        return Ok(None);
+    };
+
+    let data = match read_footer(object_store, &path, meta.size).await? {
+        Ok(data) => data,
+        Err(error) => return Ok(Some(Inconsistency::MetadataUnreadable { error })),
+    };
+
+    let partition_key = partition_keys
+        .get(&file.partition_id)
+        .map(ToString::to_string)
+        .unwrap_or_default();
+
+    Ok(check_metadata(file, &partition_key, data))
+}
+
+/// Fetch the parquet metadata and the footer at the end of the file, without downloading the
+/// data pages.
+///
+/// The outer result is an object store error, the inner one a malformed footer.
+async fn read_footer(
+    object_store: &Arc<DynObjectStore>,
+    path: &Path,
+    size: usize,
+) -> Result<Result<Bytes, String>> {
+    if size < FOOTER_SIZE {
+        return Ok(Err(format!("file of {size} bytes has no parquet footer")));
+    }
+
+    let footer = object_store
+        .get_range(path, size - FOOTER_SIZE..size)
+        .await
+        .context(ObjectStoreSnafu { path: path.clone() })?;
+    if &footer[4..] != PARQUET_MAGIC {
+        return Ok(Err(String::from("invalid parquet footer magic")));
+    }
+    let metadata_len = u32::from_le_bytes(footer[..4].try_into().expect("4 bytes")) as usize;
+    if metadata_len > size - FOOTER_SIZE {
+        return Ok(Err(format!(
+            "parquet metadata of {metadata_len} bytes does not fit into file of {size} bytes"
+        )));
+    }
+
+    // The parquet reader locates the metadata relative to the end of the data, so the tail of
+    // the file is enough to decode it.
+    let data = object_store
+        .get_range(path, size - FOOTER_SIZE - metadata_len..size)
+        .await
+        .context(ObjectStoreSnafu { path: path.clone() })?;
+    Ok(Ok(data))
+}
+
+fn check_metadata(file: &ParquetFile, partition_key: &str, data: Bytes) -> Option<Inconsistency> {
+    let FileMetadata {
+        metadata,
+        row_count,
+        min_time,
+        max_time,
+    } = match read_metadata(data) {
+        Ok(res) => res,
+        Err(error) => return Some(Inconsistency::MetadataUnreadable { error }),
+    };
+
+    let mismatch = |field: &'static str, in_catalog: String, in_file: String| {
+        (in_catalog != in_file).then_some(Inconsistency::MetadataMismatch {
+            field,
+            catalog: in_catalog,
+            file: in_file,
+        })
+    };
+
+    mismatch(
+        "object_store_id",
+        file.object_store_id.to_string(),
+        metadata.object_store_id.to_string(),
+    )
+    .or_else(|| {
+        mismatch(
+            "namespace_id",
+            file.namespace_id.to_string(),
+            metadata.namespace_id.to_string(),
+        )
+    })
+    .or_else(|| {
+        mismatch(
+            "table_id",
+            file.table_id.to_string(),
+            metadata.table_id.to_string(),
+        )
+    })
+    .or_else(|| {
+        mismatch(
+            "partition_key",
+            partition_key.to_owned(),
+            metadata.partition_key.to_string(),
+        )
+    })
+    .or_else(|| {
+        mismatch(
+            "row_count",
+            file.row_count.to_string(),
+            row_count.to_string(),
+        )
+    })
+    .or_else(|| {
+        mismatch(
+            "min_time",
+            file.min_time.get().to_string(),
+            min_time.get().to_string(),
+        )
+    })
+    .or_else(|| {
+        mismatch(
+            "max_time",
+            file.max_time.get().to_string(),
+            max_time.get().to_string(),
+        )
+    })
+}
+
+/// What [`check_metadata`] compares against the catalog row.
+#[derive(Debug)]
+struct FileMetadata {
+    metadata: IoxMetadata,
+    row_count: usize,
+    min_time: Timestamp,
+    max_time: Timestamp,
+}
+
+fn read_metadata(data: Bytes) -> Result<FileMetadata, String> {
+    let metadata = IoxParquetMetaData::from_file_bytes(data)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| String::from("no parquet metadata"))?
+        .decode()
+        .map_err(|e| e.to_string())?;
+    let iox_metadata = metadata
+        .read_iox_metadata_new()
+        .map_err(|e| e.to_string())?;
+
+    let schema = metadata.read_schema().map_err(|e| e.to_string())?;
+    let time_stats = metadata
+        .read_statistics(&schema)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|summary| summary.name == TIME_COLUMN_NAME)
+        .map(|summary| summary.stats);
+    let (min_time, max_time) = match time_stats {
+        Some(Statistics::I64(stats)) => match (stats.min, stats.max) {
+            (Some(min), Some(max)) => (Timestamp::new(min), Timestamp::new(max)),
+            _ => return Err(String::from("no time range statistics")),
+        },
+        Some(_) => return Err(String::from("unexpected type of time column statistics")),
+        None => return Err(String::from("no time column statistics")),
+    };
+
+    Ok(FileMetadata {
+        metadata: iox_metadata,
+        row_count: metadata.row_count(),
+        min_time,
+        max_time,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::{
+        array::{ArrayRef, StringArray, TimestampNanosecondArray},
+        record_batch::RecordBatch,
+    };
+    use data_types::{
+        ColumnId, ColumnSet, CompactionLevel, Namespace, ParquetFileParams, Partition, TableId,
+    };
+    use datafusion_util::{unbounded_memory_pool, MemoryStream};
+    use iox_catalog::{
+        mem::MemCatalog,
+        test_helpers::{arbitrary_namespace, arbitrary_table},
+    };
+    use iox_time::Time;
+    use object_store::memory::InMemory;
+    use parquet_file::serialize::to_parquet_bytes;
+    use schema::{builder::SchemaBuilder, InfluxFieldType, TIME_DATA_TIMEZONE};
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_verify() {
+        let setup = Setup::new().await;
+
+        // consistent
+        let id = Uuid::new_v4();
+        let ok = setup
+            .parquet_file(
+                id,
+                Some(setup.metadata(setup.iox_metadata(id), [1, 10]).await),
+            )
+            .await;
+
+        // not in the object store
+        let missing = setup.parquet_file(Uuid::new_v4(), None).await;
+
+        // wrong size
+        let wrong_size = setup
+            .parquet_file(Uuid::new_v4(), Some(Bytes::from("foo")))
+            .await;
+        setup
+            .object_store
+            .put(
+                &ParquetFilePath::from(&wrong_size).object_store_path(),
+                Bytes::from("foobar"),
+            )
+            .await
+            .unwrap();
+
+        // unreadable metadata, but the right size
+        let unreadable = setup
+            .parquet_file(Uuid::new_v4(), Some(Bytes::from("foo")))
+            .await;
+
+        // metadata of another table
+        let id = Uuid::new_v4();
+        let meta = IoxMetadata {
+            table_id: TableId::new(42),
+            ..setup.iox_metadata(id)
+        };
+        let other_table = setup
+            .parquet_file(id, Some(setup.metadata(meta, [1, 10]).await))
+            .await;
+
+        // metadata of another partition
+        let id = Uuid::new_v4();
+        let meta = IoxMetadata {
+            partition_key: "two".into(),
+            ..setup.iox_metadata(id)
+        };
+        let other_partition = setup
+            .parquet_file(id, Some(setup.metadata(meta, [1, 10]).await))
+            .await;
+
+        // data outside of the time range in the catalog
+        let id = Uuid::new_v4();
+        let other_time = setup
+            .parquet_file(
+                id,
+                Some(setup.metadata(setup.iox_metadata(id), [1, 11]).await),
+            )
+            .await;
+
+        let plan = verify(
+            Arc::clone(&setup.catalog),
+            Arc::clone(&setup.object_store),
+            VerifyOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(plan.files_checked, 7);
+        assert_eq!(
+            plan.steps
+                .iter()
+                .map(|step| (
+                    step.parquet_file_id,
+                    step.action,
+                    step.inconsistency.clone()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    missing.id.get(),
+                    RepairAction::SoftDelete,
+                    Inconsistency::Missing
+                ),
+                (
+                    wrong_size.id.get(),
+                    RepairAction::Flag,
+                    Inconsistency::SizeMismatch {
+                        catalog: 3,
+                        object_store: 6,
+                    },
+                ),
+            ],
+        );
+
+        let plan = verify(
+            Arc::clone(&setup.catalog),
+            Arc::clone(&setup.object_store),
+            VerifyOptions {
+                namespace: Some(setup.namespace.name.clone()),
+                validate_metadata: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(plan.files_checked, 7);
+        let steps = plan
+            .steps
+            .iter()
+            .map(|step| (step.parquet_file_id, step.inconsistency.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(steps.len(), 6, "{steps:?}");
+        assert!(!steps.iter().any(|(id, _)| *id == ok.id.get()));
+        assert!(matches!(
+            &steps[2],
+            (id, Inconsistency::MetadataUnreadable { .. }) if *id == unreadable.id.get()
+        ));
+        assert_eq!(
+            steps[3],
+            (
+                other_table.id.get(),
+                Inconsistency::MetadataMismatch {
+                    field: "table_id",
+                    catalog: setup.table_id.to_string(),
+                    file: String::from("42"),
+                },
+            )
+        );
+        assert_eq!(
+            steps[4],
+            (
+                other_partition.id.get(),
+                Inconsistency::MetadataMismatch {
+                    field: "partition_key",
+                    catalog: String::from("one"),
+                    file: String::from("two"),
+                },
+            )
+        );
+        assert_eq!(
+            steps[5],
+            (
+                other_time.id.get(),
+                Inconsistency::MetadataMismatch {
+                    field: "max_time",
+                    catalog: String::from("10"),
+                    file: String::from("11"),
+                },
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_unknown_namespace() {
+        let setup = Setup::new().await;
+
+        let err = verify(
+            Arc::clone(&setup.catalog),
+            Arc::clone(&setup.object_store),
+            VerifyOptions {
+                namespace: Some(String::from("does_not_exist")),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, Error::NamespaceNotFound { .. }));
+    }
+
+    #[test]
+    fn test_serialize_plan() {
+        let plan = RepairPlan {
+            files_checked: 2,
+            steps: vec![RepairStep {
+                action: RepairAction::SoftDelete,
+                parquet_file_id: 1,
+                namespace_id: 2,
+                table_id: 3,
+                partition_id: String::from("4"),
+                location: String::from("2/3/4/foo.parquet"),
+                inconsistency: Inconsistency::Missing,
+            }],
+        };
+
+        assert_eq!(
+            serde_json::to_string(&plan).unwrap(),
+            concat!(
+                r#"{"files_checked":2,"steps":[{"action":"soft_delete","parquet_file_id":1,"#,
+                r#""namespace_id":2,"table_id":3,"partition_id":"4","#,
+                r#""location":"2/3/4/foo.parquet","inconsistency":{"kind":"missing"}}]}"#,
+            ),
+        );
+    }
+
+    struct Setup {
+        catalog: Arc<dyn Catalog>,
+        object_store: Arc<DynObjectStore>,
+        namespace: Namespace,
+        table_id: TableId,
+        partition: Partition,
+    }
+
+    impl Setup {
+        async fn new() -> Self {
+            let metric_registry = Arc::new(metric::Registry::new());
+            let catalog: Arc<dyn Catalog> = Arc::new(MemCatalog::new(metric_registry));
+            let mut repos = catalog.repositories().await;
+            let namespace = arbitrary_namespace(&mut *repos, "verify_test").await;
+            let table = arbitrary_table(&mut *repos, "test_table", &namespace).await;
+            let partition = repos
+                .partitions()
+                .create_or_get("one".into(), table.id)
+                .await
+                .unwrap();
+            drop(repos);
+
+            Self {
+                catalog,
+                object_store: Arc::new(InMemory::new()),
+                namespace,
+                table_id: table.id,
+                partition,
+            }
+        }
+
+        /// IOx metadata matching the catalog rows created by [`Self::parquet_file`].
+        fn iox_metadata(&self, object_store_id: Uuid) -> IoxMetadata {
+            IoxMetadata {
+                object_store_id,
+                creation_timestamp: Time::from_timestamp_nanos(1),
+                namespace_id: self.namespace.id,
+                namespace_name: self.namespace.name.as_str().into(),
+                table_id: self.table_id,
+                table_name: "test_table".into(),
+                partition_key: "one".into(),
+                compaction_level: CompactionLevel::Initial,
+                sort_key: None,
+                max_l0_created_at: Time::from_timestamp_nanos(1),
+            }
+        }
+
+        /// Parquet file with one row per timestamp and the given embedded IOx metadata.
+        async fn metadata(&self, meta: IoxMetadata, times: [i64; 2]) -> Bytes {
+            let schema = SchemaBuilder::new()
+                .influx_field("a", InfluxFieldType::String)
+                .timestamp()
+                .build()
+                .unwrap();
+            let times = times
+                .into_iter()
+                .map(Some)
+                .collect::<TimestampNanosecondArray>()
+                .with_timezone_opt(TIME_DATA_TIMEZONE());
+            let batch = RecordBatch::try_new(
+                schema.as_arrow(),
+                vec![
+                    Arc::new(StringArray::from(vec!["value"; 2])) as ArrayRef,
+                    Arc::new(times) as ArrayRef,
+                ],
+            )
+            .unwrap();
+            let stream = Box::pin(MemoryStream::new(vec![batch]));
+            let (data, _) = to_parquet_bytes(stream, &meta, unbounded_memory_pool())
+                .await
+                .unwrap();
+            Bytes::from(data)
+        }
+
+        /// Create parquet file in the catalog and, if `data` is given, in the object store.
+        async fn parquet_file(&self, object_store_id: Uuid, data: Option<Bytes>) -> ParquetFile {
+            let params = ParquetFileParams {
+                namespace_id: self.namespace.id,
+                table_id: self.table_id,
+                partition_id: self.partition.transition_partition_id(),
+                object_store_id,
+                min_time: Timestamp::new(1),
+                max_time: Timestamp::new(10),
+                file_size_bytes: data.as_ref().map(|data| data.len() as i64).unwrap_or(1),
+                row_count: 2,
+                compaction_level: CompactionLevel::Initial,
+                created_at: Timestamp::new(1),
+                data_written_at: Timestamp::new(1),
+                column_set: ColumnSet::new([ColumnId::new(1)]),
+                max_l0_created_at: Timestamp::new(1),
+            };
+
+            let file = self
+                .catalog
+                .repositories()
+                .await
+                .parquet_files()
+                .create(params)
+                .await
+                .unwrap();
+
+            if let Some(data) = data {
+                self.object_store
+                    .put(&ParquetFilePath::from(&file).object_store_path(), data)
+                    .await
+                    .unwrap();
+            }
+
+            file
+        }
+    }
+}
@@ -32,6 +32,7 @@ fn main() -> Result<()> {
 /// - `influxdata.iox.wal.v1.rs`
 /// - `influxdata.iox.write.v1.rs`
 /// - `influxdata.platform.storage.rs`
+/// - `prometheus.rs`
 fn generate_grpc_types(root: &Path) -> Result<()> {
     let authz_path = root.join("influxdata/iox/authz/v1");
     let catalog_path = root.join("influxdata/iox/catalog/v1");
@@ -43,6 +44,7 @@ fn generate_grpc_types(root: &Path) -> Result<()> {
     let object_store_path = root.join("influxdata/iox/object_store/v1");
     let partition_template_path = root.join("influxdata/iox/partition_template/v1");
     let predicate_path = root.join("influxdata/iox/predicate/v1");
+    let prometheus_path = root.join("prometheus");
     let querier_path = root.join("influxdata/iox/querier/v1");
     let schema_path = root.join("influxdata/iox/schema/v1");
     let storage_errors_path = root.join("influxdata/platform/errors");
@@ -68,6 +70,8 @@ fn generate_grpc_types(root: &Path) -> Result<()> {
         object_store_path.join("service.proto"),
         partition_template_path.join("template.proto"),
         predicate_path.join("predicate.proto"),
+        prometheus_path.join("remote.proto"),
+        prometheus_path.join("types.proto"),
         querier_path.join("flight.proto"),
         root.join("google/longrunning/operations.proto"),
         root.join("google/rpc/error_details.proto"),
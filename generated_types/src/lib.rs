@@ -243,6 +243,11 @@ pub mod influxdata {
     }
 }
 
+/// The Prometheus remote write and remote read protocol.
+pub mod prometheus {
+    include!(concat!(env!("OUT_DIR"), "/prometheus.rs"));
+}
+
 // Needed because of https://github.com/hyperium/tonic/issues/471
 pub mod grpc {
     pub mod health {
@@ -228,6 +228,7 @@ mod tests {
             row_count: 4242111,
             compaction_level: 4200,
             created_at: 12344321,
+            data_written_at: 12344321,
             column_set: vec![1, 2, 3, 4, 5],
             max_l0_created_at: 123455555,
         };
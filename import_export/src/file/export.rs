@@ -6,13 +6,18 @@ use influxdb_iox_client::{
         generated_types::{partition_identifier, ParquetFile, PartitionIdentifier},
     },
     connection::Connection,
-    store,
+    store, table,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
 };
-use std::path::{Path, PathBuf};
 use thiserror::Error;
 use tokio::{
     fs::{self, File, OpenOptions},
-    io::{self, AsyncWriteExt},
+    io::{self, AsyncReadExt, AsyncWriteExt},
 };
 use tokio_util::compat::FuturesAsyncReadCompatExt;
 
@@ -26,28 +31,119 @@ pub enum ExportError {
 
     #[error("Writing file: {0}")]
     File(#[from] std::io::Error),
+
+    #[error("Downloaded {actual} bytes for {path:?}, expected {expected}")]
+    FileSize {
+        path: PathBuf,
+        expected: u64,
+        actual: u64,
+    },
 }
 
 type Result<T, E = ExportError> = std::result::Result<T, E>;
 
+/// The name of the manifest file written to the root of every export.
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Restricts which Parquet files are exported by a [`RemoteExporter`].
+///
+/// Files are selected as a whole: a file that overlaps the time range is
+/// exported in full, including any rows outside of it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportFilter {
+    /// Only export files containing data at or after this timestamp
+    /// (in nanoseconds since the epoch).
+    pub start: Option<i64>,
+
+    /// Only export files containing data before this timestamp (in
+    /// nanoseconds since the epoch).
+    pub end: Option<i64>,
+
+    /// Only export files of partitions with one of these keys. All
+    /// partitions are exported if empty.
+    pub partition_keys: Vec<String>,
+}
+
+impl ExportFilter {
+    /// Returns true if `parquet_file` overlaps the time range of this filter.
+    fn overlaps_time_range(&self, parquet_file: &ParquetFile) -> bool {
+        self.start
+            .map_or(true, |start| parquet_file.max_time >= start)
+            && self.end.map_or(true, |end| parquet_file.min_time < end)
+    }
+
+    /// Returns true if the partition with `partition_key` is selected by this
+    /// filter.
+    fn matches_partition_key(&self, partition_key: &str) -> bool {
+        self.partition_keys.is_empty() || self.partition_keys.iter().any(|k| k == partition_key)
+    }
+}
+
+/// Describes the contents of an export, written to [`MANIFEST_FILE_NAME`]
+/// once all files were downloaded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportManifest {
+    /// The name of the exported namespace
+    pub namespace: String,
+
+    /// The names of the exported tables
+    pub tables: Vec<String>,
+
+    /// The filter the export was created with
+    pub filter: ExportFilter,
+
+    /// The exported Parquet files
+    pub parquet_files: Vec<ManifestEntry>,
+}
+
+/// A Parquet file listed in an [`ExportManifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// The path of the file, relative to the manifest
+    pub path: PathBuf,
+
+    /// The object store id of the file
+    pub object_store_id: String,
+
+    /// The size of the file, in bytes
+    pub file_size_bytes: u64,
+
+    /// The hex encoded SHA-256 checksum of the file contents
+    pub sha256: String,
+}
+
 /// Exports data from a remote IOx instance to local files.
 ///
 /// Data is read using the clients in [`influxdb_iox_client`] (rather
 /// than the catalog) so that this can be used to debug remote systems.
+///
+/// Exports are resumable: Parquet files that were already downloaded
+/// to the output directory by a previous export are not downloaded
+/// again, if their contents match the checksum recorded in the
+/// manifest of that export.
 #[derive(Debug)]
 pub struct RemoteExporter {
     catalog_client: catalog::Client,
     store_client: store::Client,
+    table_client: table::Client,
+    filter: ExportFilter,
 }
 
 impl RemoteExporter {
     pub fn new(connection: Connection) -> Self {
         Self {
             catalog_client: catalog::Client::new(connection.clone()),
-            store_client: store::Client::new(connection),
+            store_client: store::Client::new(connection.clone()),
+            table_client: table::Client::new(connection),
+            filter: ExportFilter::default(),
         }
     }
 
+    /// Only export the Parquet files selected by `filter`.
+    pub fn with_filter(self, filter: ExportFilter) -> Self {
+        Self { filter, ..self }
+    }
+
     /// Exports all data and metadata for `table_name` in
     /// `namespace` to local files.
     ///
@@ -61,12 +157,113 @@ impl RemoteExporter {
         table_name: String,
     ) -> Result<()> {
         let output_directory = output_directory.unwrap_or_else(|| PathBuf::from(&table_name));
-        fs::create_dir_all(&output_directory).await?;
+        let previous = read_previous_manifest(&output_directory).await;
 
         let parquet_files = self
+            .export_table_files(
+                &output_directory,
+                Path::new(""),
+                &namespace_name,
+                &table_name,
+                &previous,
+            )
+            .await?;
+
+        self.write_manifest(
+            &output_directory,
+            namespace_name,
+            vec![table_name],
+            parquet_files,
+        )
+        .await?;
+        println!("Done.");
+
+        Ok(())
+    }
+
+    /// Exports all data and metadata for all tables in `namespace`
+    /// to local files.
+    ///
+    /// The files of each table are written to a sub-directory named
+    /// after the table, which can be imported individually with
+    /// [`RemoteImporter`]. If `output_directory` is specified, the
+    /// table directories are created there, otherwise in a directory
+    /// named `namespace_name`.
+    ///
+    /// [`RemoteImporter`]: crate::file::RemoteImporter
+    pub async fn export_namespace(
+        &mut self,
+        output_directory: Option<PathBuf>,
+        namespace_name: String,
+    ) -> Result<()> {
+        let output_directory = output_directory.unwrap_or_else(|| PathBuf::from(&namespace_name));
+        let previous = read_previous_manifest(&output_directory).await;
+
+        let mut table_names: Vec<_> = self
+            .table_client
+            .get_tables(&namespace_name)
+            .await?
+            .into_iter()
+            .map(|table| table.name)
+            .collect();
+        table_names.sort();
+
+        let num_tables = table_names.len();
+        println!("found {num_tables} tables in namespace {namespace_name}, exporting...");
+
+        let mut parquet_files = vec![];
+        for (index, table_name) in table_names.iter().enumerate() {
+            println!(
+                "exporting table {} of {num_tables} ({table_name})",
+                index + 1
+            );
+            let table_directory = Path::new(table_name);
+            parquet_files.extend(
+                self.export_table_files(
+                    &output_directory.join(table_directory),
+                    table_directory,
+                    &namespace_name,
+                    table_name,
+                    &previous,
+                )
+                .await?,
+            );
+        }
+
+        self.write_manifest(
+            &output_directory,
+            namespace_name,
+            table_names,
+            parquet_files,
+        )
+        .await?;
+        println!("Done.");
+
+        Ok(())
+    }
+
+    /// Exports the metadata and the Parquet files selected by the
+    /// filter of `table_name` to `output_directory`, returning the
+    /// manifest entries of the Parquet files. Entry paths are
+    /// prefixed with `manifest_prefix`.
+    ///
+    /// `previous` holds the manifest entries of a previous export to
+    /// the same directory, by object store id.
+    async fn export_table_files(
+        &mut self,
+        output_directory: &Path,
+        manifest_prefix: &Path,
+        namespace_name: &str,
+        table_name: &str,
+        previous: &HashMap<String, ManifestEntry>,
+    ) -> Result<Vec<ManifestEntry>> {
+        fs::create_dir_all(output_directory).await?;
+
+        let mut parquet_files = self
             .catalog_client
             .get_parquet_files_by_namespace_table(namespace_name, table_name)
             .await?;
+        parquet_files.retain(|parquet_file| self.filter.overlaps_time_range(parquet_file));
 
         // Export the metadata for the table. Since all
         // parquet_files are part of the same table, use the table_id
@@ -75,21 +272,53 @@ impl RemoteExporter {
             .get(0)
             .map(|parquet_file| parquet_file.table_id);
         if let Some(table_id) = table_id {
-            self.export_table_metadata(&output_directory, table_id)
+            let partition_ids = self
+                .export_table_metadata(output_directory, table_id)
                 .await?;
+            parquet_files.retain(|parquet_file| {
+                partition_ids.contains(&to_partition_id(parquet_file.partition_identifier.as_ref()))
+            });
         }
 
         let num_parquet_files = parquet_files.len();
         println!("found {num_parquet_files} Parquet files, exporting...");
         let indexed_parquet_file_metadata = parquet_files.into_iter().enumerate();
 
+        let mut entries = Vec::with_capacity(num_parquet_files);
         for (index, parquet_file) in indexed_parquet_file_metadata {
-            self.export_parquet_file(&output_directory, index, num_parquet_files, &parquet_file)
+            let previous = previous.get(&parquet_file.object_store_id);
+            let mut entry = self
+                .export_parquet_file(
+                    output_directory,
+                    index,
+                    num_parquet_files,
+                    &parquet_file,
+                    previous,
+                )
                 .await?;
+            entry.path = manifest_prefix.join(entry.path);
+            entries.push(entry);
         }
-        println!("Done.");
 
-        Ok(())
+        Ok(entries)
+    }
+
+    /// Writes the [`ExportManifest`] of an export to `output_directory`.
+    async fn write_manifest(
+        &self,
+        output_directory: &Path,
+        namespace: String,
+        tables: Vec<String>,
+        parquet_files: Vec<ManifestEntry>,
+    ) -> Result<()> {
+        let manifest = ExportManifest {
+            namespace,
+            tables,
+            filter: self.filter.clone(),
+            parquet_files,
+        };
+        let json = serde_json::to_string_pretty(&manifest)?;
+        write_string_to_file(&json, &output_directory.join(MANIFEST_FILE_NAME)).await
     }
 
     /// Exports table and partition information for the specified
@@ -100,12 +329,14 @@ impl RemoteExporter {
     /// encoded data about the table (minimal now)
     ///
     /// 2. `<output_directory>/partition.<partition_id>.json`: pbjson
-    /// encoded data for each partition
+    /// encoded data for each partition selected by the filter
+    ///
+    /// Returns the ids of the exported partitions.
     async fn export_table_metadata(
         &mut self,
         output_directory: &Path,
         table_id: i64,
-    ) -> Result<()> {
+    ) -> Result<HashSet<TransitionPartitionId>> {
         // write table metadata
         //
         // (Note that since there is way to get table metadata via
@@ -121,15 +352,21 @@ impl RemoteExporter {
             .get_partitions_by_table_id(table_id)
             .await?;
 
+        let mut partition_ids = HashSet::new();
         for partition in partitions {
+            if !self.filter.matches_partition_key(&partition.key) {
+                continue;
+            }
+
             let partition_id = to_partition_id(partition.identifier.as_ref());
             let partition_json = serde_json::to_string_pretty(&partition)?;
             let filename = format!("partition.{partition_id}.json");
             let file_path = output_directory.join(&filename);
             write_string_to_file(&partition_json, &file_path).await?;
+            partition_ids.insert(partition_id);
         }
 
-        Ok(())
+        Ok(partition_ids)
     }
 
     /// Exports a remote ParquetFile to:
@@ -137,13 +374,20 @@ impl RemoteExporter {
     /// 1. `<output_directory>/<uuid>.parquet`: The parquet bytes
     ///
     /// 2. `<output_directory>/<uuid>.parquet.json`: pbjson encoded `ParquetFile` metadata
+    ///
+    /// The Parquet bytes are downloaded to a temporary file that is
+    /// renamed once complete, so files left behind by an interrupted
+    /// export are never mistaken for complete ones. The download is
+    /// skipped if the file already exists and matches its `previous`
+    /// manifest entry.
     async fn export_parquet_file(
         &mut self,
         output_directory: &Path,
         index: usize,
         num_parquet_files: usize,
         parquet_file: &ParquetFile,
-    ) -> Result<()> {
+        previous: Option<&ManifestEntry>,
+    ) -> Result<ManifestEntry> {
         let uuid = &parquet_file.object_store_id;
         let file_size_bytes = parquet_file.file_size_bytes as u64;
 
@@ -161,38 +405,105 @@ impl RemoteExporter {
         let filename = format!("{uuid}.{partition_id}.parquet");
         let file_path = output_directory.join(&filename);
 
-        if fs::metadata(&file_path)
-            .await
-            .map_or(false, |metadata| metadata.len() == file_size_bytes)
+        if let Some(previous) = verified_download(&file_path, file_size_bytes, previous).await? {
+            println!(
+                "skipping file {} of {num_parquet_files} ({filename} already exists with expected checksum)",
+                index + 1
+            );
+            return Ok(ManifestEntry {
+                path: PathBuf::from(filename),
+                object_store_id: uuid.clone(),
+                file_size_bytes,
+                sha256: previous.sha256.clone(),
+            });
+        }
+
+        // scope to close files
         {
             println!(
-                "skipping file {} of {num_parquet_files} ({filename} already exists with expected file size)",
+                "downloading file {} of {num_parquet_files} ({filename})...",
                 index + 1
             );
-        } else {
-            // scope to close files
-            {
-                println!(
-                    "downloading file {} of {num_parquet_files} ({filename})...",
-                    index + 1
-                );
-                let mut response = self
-                    .store_client
-                    .get_parquet_file_by_object_store_id(uuid.clone())
-                    .await?
-                    .map_ok(|res| res.data)
-                    .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
-                    .into_async_read()
-                    .compat();
-                let mut file = File::create(&file_path).await?;
-                io::copy(&mut response, &mut file).await?;
+            let mut response = self
+                .store_client
+                .get_parquet_file_by_object_store_id(uuid.clone())
+                .await?
+                .map_ok(|res| res.data)
+                .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+                .into_async_read()
+                .compat();
+            let partial_path = output_directory.join(format!("{filename}.partial"));
+            let mut file = File::create(&partial_path).await?;
+            let actual = io::copy(&mut response, &mut file).await?;
+            if actual != file_size_bytes {
+                fs::remove_file(&partial_path).await?;
+                return Err(ExportError::FileSize {
+                    path: file_path,
+                    expected: file_size_bytes,
+                    actual,
+                });
             }
+            file.sync_all().await?;
+            fs::rename(&partial_path, &file_path).await?;
         }
 
-        Ok(())
+        Ok(ManifestEntry {
+            path: PathBuf::from(filename),
+            object_store_id: uuid.clone(),
+            file_size_bytes,
+            sha256: sha256_file(&file_path).await?,
+        })
     }
 }
 
+/// Returns the entries of the manifest in `output_directory`, written by a
+/// previous export, by object store id.
+///
+/// Returns no entries if there is no manifest, or it cannot be read, in which
+/// case all files are downloaded again.
+async fn read_previous_manifest(output_directory: &Path) -> HashMap<String, ManifestEntry> {
+    let path = output_directory.join(MANIFEST_FILE_NAME);
+    let manifest = match fs::read_to_string(&path).await {
+        Ok(json) => serde_json::from_str::<ExportManifest>(&json).map_err(ExportError::from),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return HashMap::new(),
+        Err(e) => Err(e.into()),
+    };
+
+    match manifest {
+        Ok(manifest) => manifest
+            .parquet_files
+            .into_iter()
+            .map(|entry| (entry.object_store_id.clone(), entry))
+            .collect(),
+        Err(e) => {
+            println!("ignoring manifest of previous export {path:?}: {e}");
+            HashMap::new()
+        }
+    }
+}
+
+/// Returns the `previous` manifest entry of the Parquet file at `file_path`
+/// if the file exists and its contents match the entry and
+/// `file_size_bytes`, i.e. the file does not need to be downloaded again.
+async fn verified_download<'a>(
+    file_path: &Path,
+    file_size_bytes: u64,
+    previous: Option<&'a ManifestEntry>,
+) -> Result<Option<&'a ManifestEntry>> {
+    let Some(previous) = previous.filter(|p| p.file_size_bytes == file_size_bytes) else {
+        return Ok(None);
+    };
+
+    match fs::metadata(file_path).await {
+        Ok(metadata) if metadata.len() == file_size_bytes => {}
+        Ok(_) => return Ok(None),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok((sha256_file(file_path).await? == previous.sha256).then_some(previous))
+}
+
 fn to_partition_id(partition_identifier: Option<&PartitionIdentifier>) -> TransitionPartitionId {
     match partition_identifier
         .and_then(|pi| pi.id.as_ref())
@@ -208,6 +519,22 @@ fn to_partition_id(partition_identifier: Option<&PartitionIdentifier>) -> Transi
     }
 }
 
+/// Returns the hex encoded SHA-256 checksum of the contents of the file at `path`
+async fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = File::open(path).await?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
 /// writes the contents of a string to a file, overwriting the previous contents, if any
 async fn write_string_to_file(contents: &str, path: &Path) -> Result<()> {
     let mut file = OpenOptions::new()
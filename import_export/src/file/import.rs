@@ -19,14 +19,18 @@ use parquet_file::{
     metadata::{DecodedIoxParquetMetaData, IoxMetadata, IoxParquetMetaData},
     ParquetFilePath,
 };
+use sha2::{Digest, Sha256};
 use std::{
     borrow::Cow,
+    collections::HashMap,
     io::Read,
     path::{Path, PathBuf},
     sync::Arc,
 };
 use thiserror::Error;
 
+use super::{ExportManifest, ManifestEntry, MANIFEST_FILE_NAME};
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Reading {path:?}: {e}")]
@@ -73,6 +77,12 @@ pub enum Error {
     #[error("Unknown compaction level in encoded metadata: {0}")]
     UnknownCompactionLevel(Box<dyn std::error::Error + std::marker::Send + Sync>),
 
+    #[error("File {path:?} listed in the export manifest not found")]
+    MissingFile { path: PathBuf },
+
+    #[error("File {path:?} does not match the export manifest: {message}")]
+    ManifestMismatch { path: PathBuf, message: String },
+
     #[error("Catalog error: {0}")]
     Catalog(#[from] iox_catalog::interface::Error),
 
@@ -118,6 +128,10 @@ pub struct ExportedContents {
 impl ExportedContents {
     /// Read the contents of the directory in `dir_path`, categorizing
     /// files in that directory.
+    ///
+    /// If the export has a manifest, in `dir_path` for a table export or in
+    /// its parent directory for a namespace export, only the Parquet files it
+    /// lists are included, and their contents are verified against it.
     pub fn try_new(dir_path: &Path) -> Result<Self> {
         info!(?dir_path, "Reading exported catalog contents");
 
@@ -165,11 +179,53 @@ impl ExportedContents {
             }
         }
 
+        new_self.try_verify_manifest(dir_path)?;
         new_self.try_decode_files()?;
 
         Ok(new_self)
     }
 
+    /// Removes the Parquet files that are not listed in the manifest of the
+    /// export, if any, and verifies that the listed files are complete.
+    fn try_verify_manifest(&mut self, dir_path: &Path) -> Result<()> {
+        let Some((manifest, prefix)) = read_manifest(dir_path)? else {
+            warn!(
+                ?dir_path,
+                "No export manifest found, importing all Parquet files without verifying them"
+            );
+            return Ok(());
+        };
+
+        // The entries of the files in `dir_path`, by file name
+        let mut entries: HashMap<_, _> = manifest
+            .parquet_files
+            .iter()
+            .filter(|entry| entry.path.parent() == Some(prefix.as_path()))
+            .filter_map(|entry| Some((entry.path.file_name()?.to_os_string(), entry)))
+            .collect();
+
+        let mut parquet_files = Vec::with_capacity(entries.len());
+        for path in std::mem::take(&mut self.parquet_files) {
+            match path.file_name().and_then(|name| entries.remove(name)) {
+                Some(entry) => {
+                    verify_file(&path, entry)?;
+                    parquet_files.push(path);
+                }
+                None => warn!(?path, "IGNORING Parquet file not listed in export manifest"),
+            }
+        }
+
+        if let Some(entry) = entries.into_values().next() {
+            return Err(Error::MissingFile {
+                path: dir_path.join(file_name(&entry.path).as_ref()),
+            });
+        }
+
+        parquet_files.sort();
+        self.parquet_files = parquet_files;
+        Ok(())
+    }
+
     /// tries to decode all the metadata files found in the export
     fn try_decode_files(&mut self) -> Result<()> {
         debug!("Decoding partition files");
@@ -253,6 +309,70 @@ impl ExportedContents {
     }
 }
 
+/// Reads the [`ExportManifest`] of the export in `dir_path`, from `dir_path`
+/// or its parent directory, returning it with the path of `dir_path` relative
+/// to the manifest.
+fn read_manifest(dir_path: &Path) -> Result<Option<(ExportManifest, PathBuf)>> {
+    let candidates = [
+        Some((dir_path.to_path_buf(), PathBuf::new())),
+        dir_path
+            .file_name()
+            .zip(dir_path.parent())
+            .map(|(name, parent)| (parent.to_path_buf(), PathBuf::from(name))),
+    ];
+
+    for (manifest_dir, prefix) in candidates.into_iter().flatten() {
+        let path = manifest_dir.join(MANIFEST_FILE_NAME);
+        let json = match std::fs::read_to_string(&path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(Error::reading(path, e)),
+        };
+        debug!(?path, "Reading export manifest");
+        let manifest: ExportManifest =
+            serde_json::from_str(&json).map_err(|e| Error::Json { path, e })?;
+
+        // The manifest of a namespace export lists the table directories
+        if !prefix.as_os_str().is_empty() && !manifest.tables.iter().any(|t| prefix == Path::new(t))
+        {
+            continue;
+        }
+        return Ok(Some((manifest, prefix)));
+    }
+
+    Ok(None)
+}
+
+/// Verifies that the size and checksum of the file at `path` match `entry`.
+fn verify_file(path: &Path, entry: &ManifestEntry) -> Result<()> {
+    let mismatch = |message: String| Error::ManifestMismatch {
+        path: path.into(),
+        message,
+    };
+
+    let mut file = std::fs::File::open(path).map_err(|e| Error::reading(path, e))?;
+    let mut hasher = Sha256::new();
+    let file_size_bytes =
+        std::io::copy(&mut file, &mut hasher).map_err(|e| Error::reading(path, e))?;
+
+    if file_size_bytes != entry.file_size_bytes {
+        return Err(mismatch(format!(
+            "size is {file_size_bytes} bytes, expected {}",
+            entry.file_size_bytes
+        )));
+    }
+
+    let sha256 = format!("{:x}", hasher.finalize());
+    if sha256 != entry.sha256 {
+        return Err(mismatch(format!(
+            "SHA-256 checksum is {sha256}, expected {}",
+            entry.sha256
+        )));
+    }
+
+    Ok(())
+}
+
 /// Returns the name of the file
 fn file_name(p: &Path) -> Cow<'_, str> {
     p.file_name()
@@ -645,6 +765,7 @@ impl RemoteImporter {
                 row_count: proto_parquet_file.row_count,
                 compaction_level,
                 created_at: Timestamp::new(proto_parquet_file.created_at),
+                data_written_at: Timestamp::new(proto_parquet_file.data_written_at),
                 column_set,
                 max_l0_created_at: Timestamp::new(proto_parquet_file.max_l0_created_at),
             }
@@ -668,6 +789,7 @@ impl RemoteImporter {
                 //compaction_level: CompactionLevel::Final,
                 compaction_level: CompactionLevel::Initial,
                 created_at,
+                data_written_at: created_at,
                 column_set,
                 max_l0_created_at: created_at,
             }
@@ -2,5 +2,7 @@
 mod export;
 mod import;
 
-pub use export::{ExportError, RemoteExporter};
+pub use export::{
+    ExportError, ExportFilter, ExportManifest, ManifestEntry, RemoteExporter, MANIFEST_FILE_NAME,
+};
 pub use import::{Error, ExportedContents, RemoteImporter};
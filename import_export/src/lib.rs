@@ -15,3 +15,5 @@ use workspace_hack as _;
 
 /// Import/Export data to files
 pub mod file;
+/// Import data from TSM files
+pub mod tsm;
@@ -0,0 +1,636 @@
+//! Utilities for importing the data of TSM files, written by InfluxDB 1.x and
+//! 2.x, into IOx
+
+use arrow_util::bitset::BitSet;
+use influxdb_iox_client::write;
+use influxdb_tsm::{
+    mapper::{ColumnData, MeasurementTable, TableSection},
+    reader::{BlockDecoder, TsmBlockReader, TsmIndexReader},
+    InfluxId, TsmError,
+};
+use mutable_batch::{writer::Writer, MutableBatch};
+use observability_deps::tracing::{debug, info, warn};
+use schema::{Projection, TIME_COLUMN_NAME};
+use std::{
+    fmt::Display,
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// The maximum number of rows converted into a single [`MutableBatch`].
+const MAX_BATCH_ROWS: usize = 100_000;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Reading {path:?}: {e}")]
+    Reading { path: PathBuf, e: std::io::Error },
+
+    #[error("Invalid TSM file {path:?}: {e}")]
+    Tsm { path: PathBuf, e: TsmError },
+
+    #[error("Deletes recorded in tombstone files are not applied, found {files:?}")]
+    Tombstones { files: Vec<PathBuf> },
+
+    #[error("Cannot import data of {first} and {other} together, found in {path:?}")]
+    MixedSources {
+        path: PathBuf,
+        first: TsmSource,
+        other: TsmSource,
+    },
+
+    #[error("Error converting measurement {measurement:?}: {e}")]
+    Conversion {
+        measurement: String,
+        e: mutable_batch::Error,
+    },
+
+    #[error("Error converting measurement {measurement:?} to line protocol: {message}")]
+    LineProtocol {
+        measurement: String,
+        message: String,
+    },
+
+    #[error("Error writing data: {0}")]
+    Write(#[from] influxdb_iox_client::error::Error),
+
+    #[error("TSM conversion task failed: {0}")]
+    Task(#[from] tokio::task::JoinError),
+
+    #[error("Import cancelled")]
+    Cancelled,
+}
+
+impl Error {
+    fn reading(path: impl Into<PathBuf>, e: std::io::Error) -> Self {
+        let path = path.into();
+        Self::Reading { path, e }
+    }
+
+    fn tsm(path: impl Into<PathBuf>, e: TsmError) -> Self {
+        let path = path.into();
+        Self::Tsm { path, e }
+    }
+}
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Returns the paths of the TSM files (files with a `.tsm` extension) in
+/// `path`, in order.
+///
+/// `path` is either a single TSM file, or a directory that is searched
+/// recursively, such as the data directory of an InfluxDB 1.x or 2.x server.
+pub fn find_tsm_files(path: &Path) -> Result<Vec<PathBuf>> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let mut files = vec![];
+    let mut dirs = vec![path.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in dir.read_dir().map_err(|e| Error::reading(&dir, e))? {
+            let path = entry.map_err(|e| Error::reading(&dir, e))?.path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else if path.extension().is_some_and(|ext| ext == "tsm") {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+
+    debug!(?files, "Found TSM files");
+    Ok(files)
+}
+
+/// Returns the paths of the non-empty tombstone files of the TSM `files`, in
+/// order.
+///
+/// A tombstone file records the series and time ranges deleted from the TSM
+/// file with the same name and a `.tombstone` extension, such as
+/// `000000001-000000001.tombstone`.
+pub fn find_tombstones(files: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut tombstones = vec![];
+    for file in files {
+        let path = file.with_extension("tombstone");
+        match std::fs::metadata(&path) {
+            Ok(metadata) if metadata.len() > 0 => tombstones.push(path),
+            Ok(_) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(Error::reading(path, e)),
+        }
+    }
+
+    debug!(?tombstones, "Found tombstone files");
+    Ok(tombstones)
+}
+
+/// The InfluxDB database that the data of a TSM file belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TsmSource {
+    /// An InfluxDB 2.x bucket, identified by the org and bucket IDs in the
+    /// series keys.
+    Bucket {
+        org_id: InfluxId,
+        bucket_id: InfluxId,
+    },
+
+    /// An InfluxDB 1.x database and retention policy, identified by the
+    /// location of the file in the data directory
+    /// (`<db>/<rp>/<shard id>/<file>.tsm`).
+    Database { db: String, rp: String },
+}
+
+impl Display for TsmSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bucket { org_id, bucket_id } => write!(f, "org {org_id} bucket {bucket_id}"),
+            Self::Database { db, rp } => write!(f, "database {db:?} retention policy {rp:?}"),
+        }
+    }
+}
+
+/// Returns the InfluxDB database that the data of the TSM file at `path`
+/// belongs to.
+///
+/// Returns `None` for an InfluxDB 1.x file outside of a data directory, or a
+/// file without series. Returns an error if the series of the file belong to
+/// more than one InfluxDB 2.x bucket.
+pub fn tsm_file_source(path: &Path) -> Result<Option<TsmSource>> {
+    let mut source = None;
+    for entry in open_index(path)? {
+        let entry = entry.map_err(|e| Error::tsm(path, e))?;
+        if !entry.is_v2_key() {
+            return Ok(database_source(path));
+        }
+
+        let other = TsmSource::Bucket {
+            org_id: entry.org_id(),
+            bucket_id: entry.bucket_id(),
+        };
+        match source {
+            None => source = Some(other),
+            Some(ref first) if *first != other => {
+                return Err(Error::MixedSources {
+                    path: path.into(),
+                    first: first.clone(),
+                    other,
+                })
+            }
+            Some(_) => {}
+        }
+    }
+
+    Ok(source)
+}
+
+/// Returns the InfluxDB database that the data of all TSM `files` belongs
+/// to, or an error if they belong to more than one database.
+///
+/// Files whose database cannot be determined are ignored, see
+/// [`tsm_file_source`].
+pub fn tsm_files_source(files: &[PathBuf]) -> Result<Option<TsmSource>> {
+    let mut source: Option<TsmSource> = None;
+    for file in files {
+        let Some(other) = tsm_file_source(file)? else {
+            continue;
+        };
+        match source {
+            None => source = Some(other),
+            Some(ref first) if *first != other => {
+                return Err(Error::MixedSources {
+                    path: file.clone(),
+                    first: first.clone(),
+                    other,
+                })
+            }
+            Some(_) => {}
+        }
+    }
+
+    Ok(source)
+}
+
+/// Returns the database and retention policy of an InfluxDB 1.x TSM file from
+/// its location in the data directory, `<db>/<rp>/<shard id>/<file>.tsm`.
+fn database_source(path: &Path) -> Option<TsmSource> {
+    let shard = path.parent()?;
+    shard.file_name()?.to_str()?.parse::<u64>().ok()?;
+    let rp = shard.parent()?;
+    let db = rp.parent()?;
+
+    Some(TsmSource::Database {
+        db: db.file_name()?.to_str()?.to_owned(),
+        rp: rp.file_name()?.to_str()?.to_owned(),
+    })
+}
+
+fn open_file(path: &Path) -> Result<BufReader<File>> {
+    File::open(path)
+        .map(BufReader::new)
+        .map_err(|e| Error::reading(path, e))
+}
+
+fn open_index(path: &Path) -> Result<TsmIndexReader<BufReader<File>>> {
+    let len = std::fs::metadata(path)
+        .map_err(|e| Error::reading(path, e))?
+        .len();
+    TsmIndexReader::try_new(open_file(path)?, len as usize).map_err(|e| Error::tsm(path, e))
+}
+
+/// Converts the data of the TSM file at `path` into [`MutableBatch`]es,
+/// calling `f` with each batch and the name of the measurement it belongs to.
+///
+/// Both InfluxDB 1.x and 2.x TSM files are supported. All series are
+/// converted, whichever database they belong to; use [`tsm_file_source`] to
+/// check it. Deletes recorded in tombstone files are not applied, use
+/// [`find_tombstones`] to check for them.
+pub fn convert_tsm_file<F>(path: &Path, mut f: F) -> Result<()>
+where
+    F: FnMut(String, MutableBatch) -> Result<()>,
+{
+    let tsm_error = |e: TsmError| Error::tsm(path, e);
+
+    let index_reader = open_index(path)?;
+    let mut block_reader = TsmBlockReader::new(open_file(path)?);
+
+    let mut table: Option<MeasurementTable> = None;
+    for entry in index_reader {
+        let entry = entry.map_err(tsm_error)?;
+        let (measurement, tagset, field_key) = if entry.is_v2_key() {
+            let key = entry.parse_key().map_err(tsm_error)?;
+            (key.measurement, key.tagset, key.field_key)
+        } else {
+            let key = entry.parse_v1_key().map_err(tsm_error)?;
+            (key.measurement, key.tagset, key.field_key)
+        };
+
+        // The index is sorted by key, so all the series of a measurement are
+        // adjacent.
+        match table.take() {
+            Some(previous) if previous.name != measurement => {
+                convert_measurement(previous, &mut block_reader, path, &mut f)?
+            }
+            previous => table = previous,
+        }
+
+        table
+            .get_or_insert_with(|| MeasurementTable::new(measurement, 0))
+            .add_series_data(tagset, field_key, entry.block)
+            .map_err(tsm_error)?;
+    }
+
+    if let Some(table) = table {
+        convert_measurement(table, &mut block_reader, path, &mut f)?;
+    }
+
+    Ok(())
+}
+
+/// Converts all the series of `table` into batches of at most
+/// [`MAX_BATCH_ROWS`] rows, passing each to `f`.
+fn convert_measurement<F>(
+    mut table: MeasurementTable,
+    block_reader: impl BlockDecoder,
+    path: &Path,
+    f: &mut F,
+) -> Result<()>
+where
+    F: FnMut(String, MutableBatch) -> Result<()>,
+{
+    let measurement = table.name.clone();
+    debug!(%measurement, "Converting measurement");
+
+    let mut batch = MutableBatch::new();
+    // `process` only propagates `TsmError`s, so stash any other error and
+    // abort processing.
+    let mut error = None;
+    let res = table.process(block_reader, |section| {
+        write_section(&mut batch, section)
+            .map_err(|e| Error::Conversion {
+                measurement: measurement.clone(),
+                e,
+            })
+            .and_then(|_| {
+                if batch.rows() >= MAX_BATCH_ROWS {
+                    f(measurement.clone(), std::mem::take(&mut batch))
+                } else {
+                    Ok(())
+                }
+            })
+            .map_err(|e| {
+                let description = e.to_string();
+                error = Some(e);
+                TsmError { description }
+            })
+    });
+
+    if let Some(e) = error {
+        return Err(e);
+    }
+    res.map_err(|e| Error::tsm(path, e))?;
+
+    if batch.rows() > 0 {
+        f(measurement, batch)?;
+    }
+
+    Ok(())
+}
+
+/// Appends the rows of a single series in `section` to `batch`.
+fn write_section(batch: &mut MutableBatch, section: TableSection) -> mutable_batch::Result<()> {
+    let rows = section.len();
+    let mut writer = Writer::new(batch, rows);
+
+    for (key, value) in &section.tag_cols {
+        writer.write_tag(key, None, std::iter::repeat(value.as_str()).take(rows))?;
+    }
+
+    for (key, values) in &section.field_cols {
+        match values {
+            ColumnData::Float(v) => writer.write_f64(
+                key,
+                Some(valid_mask(v).bytes()),
+                v.iter().flatten().copied(),
+            )?,
+            ColumnData::Integer(v) => writer.write_i64(
+                key,
+                Some(valid_mask(v).bytes()),
+                v.iter().flatten().copied(),
+            )?,
+            ColumnData::Unsigned(v) => writer.write_u64(
+                key,
+                Some(valid_mask(v).bytes()),
+                v.iter().flatten().copied(),
+            )?,
+            ColumnData::Bool(v) => writer.write_bool(
+                key,
+                Some(valid_mask(v).bytes()),
+                v.iter().flatten().copied(),
+            )?,
+            ColumnData::Str(v) => {
+                let strings: Vec<_> = v
+                    .iter()
+                    .flatten()
+                    .map(|s| String::from_utf8_lossy(s))
+                    .collect();
+                writer.write_string(
+                    key,
+                    Some(valid_mask(v).bytes()),
+                    strings.iter().map(|s| s.as_ref()),
+                )?
+            }
+        }
+    }
+
+    writer.write_time(TIME_COLUMN_NAME, section.ts.iter().copied())?;
+    writer.commit();
+
+    Ok(())
+}
+
+/// Returns a [`BitSet`] in which the bits of the non-null `values` are set.
+fn valid_mask<T>(values: &[Option<T>]) -> BitSet {
+    let mut mask = BitSet::with_size(values.len());
+    for (idx, value) in values.iter().enumerate() {
+        if value.is_some() {
+            mask.set(idx);
+        }
+    }
+    mask
+}
+
+/// Imports the data of TSM files into a namespace, by writing it as line
+/// protocol through the IOx write API (i.e. the router).
+#[derive(Debug)]
+pub struct TsmImporter {
+    client: write::Client,
+    namespace: String,
+    ignore_tombstones: bool,
+}
+
+impl TsmImporter {
+    /// Create a new importer, writing to `namespace` with `client`.
+    pub fn new(client: write::Client, namespace: impl Into<String>) -> Self {
+        Self {
+            client,
+            namespace: namespace.into(),
+            ignore_tombstones: false,
+        }
+    }
+
+    /// Import TSM files that have tombstone files, ignoring the deletes they
+    /// record, rather than failing.
+    pub fn with_ignore_tombstones(self, ignore_tombstones: bool) -> Self {
+        Self {
+            ignore_tombstones,
+            ..self
+        }
+    }
+
+    /// Imports the TSM files in `files`, in order, stopping at the first
+    /// error.
+    ///
+    /// All files must hold the data of the same InfluxDB 2.x bucket, or 1.x
+    /// database and retention policy, otherwise nothing is imported. The same
+    /// applies if any file has a tombstone file, unless tombstones are
+    /// ignored, as the deletes they record are not applied.
+    pub async fn import(&mut self, files: &[PathBuf]) -> Result<()> {
+        let total_files = files.len();
+        let source = tsm_files_source(files)?;
+
+        let tombstones = find_tombstones(files)?;
+        if !tombstones.is_empty() {
+            if !self.ignore_tombstones {
+                return Err(Error::Tombstones { files: tombstones });
+            }
+            warn!(
+                ?tombstones,
+                "Ignoring tombstone files, deleted data will be imported"
+            );
+        }
+        info!(
+            %total_files,
+            namespace=%self.namespace,
+            ?source,
+            "Begin importing TSM files"
+        );
+
+        for (files_done, file) in files.iter().enumerate() {
+            self.import_file(file).await?;
+
+            let files_done = files_done + 1;
+            let pct = (files_done as f64 / total_files as f64) * 100.0;
+            info!(%files_done, %total_files, %pct, "Import running");
+        }
+
+        info!(%total_files, "Completed importing TSM files");
+        Ok(())
+    }
+
+    async fn import_file(&mut self, path: &Path) -> Result<()> {
+        info!(?path, "Importing TSM file");
+
+        // The conversion does blocking IO, so run it on a separate thread and
+        // write the batches as they are produced.
+        let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+        let task_path = path.to_path_buf();
+        let task = tokio::task::spawn_blocking(move || {
+            convert_tsm_file(&task_path, |measurement, batch| {
+                tx.blocking_send((measurement, batch))
+                    .map_err(|_| Error::Cancelled)
+            })
+        });
+
+        let mut rows = 0;
+        while let Some((measurement, batch)) = rx.recv().await {
+            rows += batch.rows();
+            let lp = to_line_protocol(&measurement, &batch)?;
+            self.client.write_lp(&self.namespace, lp).await?;
+        }
+        task.await??;
+
+        info!(?path, %rows, "Imported TSM file");
+        Ok(())
+    }
+}
+
+/// Converts `batch` into line protocol for `measurement`.
+fn to_line_protocol(measurement: &str, batch: &MutableBatch) -> Result<String> {
+    let error = |message: String| Error::LineProtocol {
+        measurement: measurement.into(),
+        message,
+    };
+
+    let schema = batch
+        .schema(Projection::All)
+        .map_err(|e| error(e.to_string()))?;
+    let record_batch = batch
+        .to_arrow(Projection::All)
+        .map_err(|e| error(e.to_string()))?;
+    let lp = parquet_to_line_protocol::convert_to_lines(measurement, &schema, &record_batch)
+        .map_err(error)?;
+
+    String::from_utf8(lp).map_err(|e| error(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::io::copy;
+    use tempfile::TempDir;
+
+    /// Decompresses the TSM file fixture `name` into `dir`.
+    fn tsm_fixture(dir: &Path, name: &str) -> PathBuf {
+        let fixture = File::open(format!("../test_fixtures/{name}.tsm.gz")).unwrap();
+        std::fs::create_dir_all(dir).unwrap();
+        let path = dir.join(format!("{name}.tsm"));
+        copy(
+            &mut GzDecoder::new(fixture),
+            &mut File::create(&path).unwrap(),
+        )
+        .unwrap();
+        path
+    }
+
+    fn bucket(source: Option<TsmSource>) -> (String, String) {
+        match source {
+            Some(TsmSource::Bucket { org_id, bucket_id }) => {
+                (org_id.to_string(), bucket_id.to_string())
+            }
+            other => panic!("unexpected source: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_tsm_file_source() {
+        let dir = TempDir::new().unwrap();
+        let path = tsm_fixture(dir.path(), "000000000000462-000000002");
+
+        assert_eq!(
+            bucket(tsm_file_source(&path).unwrap()),
+            ("05e5b1cc26d7e000".to_owned(), "05e5b1cc26d7e001".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_tsm_files_source() {
+        let dir = TempDir::new().unwrap();
+        let a = tsm_fixture(&dir.path().join("a"), "000000000000462-000000002");
+        let b = tsm_fixture(&dir.path().join("b"), "000000000000462-000000002");
+        let other = tsm_fixture(dir.path(), "000000000000005-000000002");
+
+        assert_eq!(tsm_files_source(&[]).unwrap(), None);
+        assert_eq!(
+            bucket(tsm_files_source(&[a.clone(), b.clone()]).unwrap()),
+            ("05e5b1cc26d7e000".to_owned(), "05e5b1cc26d7e001".to_owned())
+        );
+
+        let err = tsm_files_source(&[a, other.clone(), b]).unwrap_err();
+        match err {
+            Error::MixedSources {
+                path,
+                first,
+                other: o,
+            } => {
+                assert_eq!(path, other);
+                assert_eq!(
+                    bucket(Some(first)),
+                    ("05e5b1cc26d7e000".to_owned(), "05e5b1cc26d7e001".to_owned())
+                );
+                assert_eq!(
+                    bucket(Some(o)),
+                    ("05c19117091a1000".to_owned(), "05c19117091a1001".to_owned())
+                );
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn test_find_tombstones() {
+        let dir = TempDir::new().unwrap();
+        let a = tsm_fixture(&dir.path().join("a"), "000000000000462-000000002");
+        let b = tsm_fixture(&dir.path().join("b"), "000000000000462-000000002");
+        let c = tsm_fixture(&dir.path().join("c"), "000000000000462-000000002");
+
+        assert!(find_tombstones(&[a.clone(), b.clone(), c.clone()])
+            .unwrap()
+            .is_empty());
+
+        // empty tombstone files record no deletes
+        let b_tombstone = b.with_extension("tombstone");
+        File::create(&b_tombstone).unwrap();
+        let c_tombstone = c.with_extension("tombstone");
+        std::fs::write(&c_tombstone, b"deleted").unwrap();
+        // tombstones of files not being imported are ignored
+        std::fs::write(dir.path().join("other.tombstone"), b"deleted").unwrap();
+
+        assert_eq!(find_tombstones(&[a, b, c]).unwrap(), vec![c_tombstone]);
+    }
+
+    #[test]
+    fn test_database_source() {
+        assert_eq!(
+            database_source(Path::new(
+                "/var/lib/influxdb/data/telegraf/autogen/12/000000001-000000001.tsm"
+            )),
+            Some(TsmSource::Database {
+                db: "telegraf".to_owned(),
+                rp: "autogen".to_owned(),
+            })
+        );
+
+        // not in a shard directory
+        assert_eq!(
+            database_source(Path::new("/tmp/telegraf/autogen/000000001-000000001.tsm")),
+            None
+        );
+        assert_eq!(
+            database_source(Path::new("12/000000001-000000001.tsm")),
+            None
+        );
+    }
+}
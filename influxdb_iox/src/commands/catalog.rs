@@ -5,6 +5,8 @@ use thiserror::Error;
 
 use crate::process_info::setup_metric_registry;
 
+mod token;
+
 #[allow(clippy::enum_variant_names)]
 #[derive(Debug, Error)]
 pub enum Error {
@@ -13,6 +15,9 @@ pub enum Error {
 
     #[error("Catalog DSN error: {0}")]
     CatalogDsn(#[from] clap_blocks::catalog_dsn::Error),
+
+    #[error("{0}")]
+    Token(#[from] token::Error),
 }
 
 /// Various commands for catalog manipulation
@@ -34,6 +39,9 @@ struct Setup {
 enum Command {
     /// Run database migrations
     Setup(Setup),
+
+    /// Manage the API tokens checked by the built-in authorizer
+    Token(token::Config),
 }
 
 pub async fn command(config: Config) -> Result<(), Error> {
@@ -44,6 +52,7 @@ pub async fn command(config: Config) -> Result<(), Error> {
             catalog.setup().await?;
             println!("OK");
         }
+        Command::Token(config) => token::command(config).await?,
     }
 
     Ok(())
@@ -0,0 +1,144 @@
+//! This module implements the `catalog token` CLI command
+
+use authz::{generate_token, hash_token};
+use clap_blocks::catalog_dsn::CatalogDsnConfig;
+use comfy_table::{Cell, Table};
+use data_types::{AuthToken, AuthTokenGrant, AuthTokenId};
+use iox_time::Time;
+use thiserror::Error;
+
+use crate::process_info::setup_metric_registry;
+
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Catalog error: {0}")]
+    Catalog(#[from] iox_catalog::interface::Error),
+
+    #[error("Catalog DSN error: {0}")]
+    CatalogDsn(#[from] clap_blocks::catalog_dsn::Error),
+
+    #[error("Token {0} not found")]
+    NotFound(AuthTokenId),
+}
+
+/// Manage the API tokens checked by the built-in authorizer
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    #[clap(flatten)]
+    catalog_dsn: CatalogDsnConfig,
+
+    #[clap(subcommand)]
+    command: Command,
+}
+
+/// Create a new token, and print its secret
+#[derive(Debug, clap::Parser)]
+struct Create {
+    /// A description of the token, to identify it when listing tokens
+    #[clap(long, action)]
+    description: String,
+
+    /// The access granted by the token, as `<namespace>:<read|write>`, or
+    /// `<namespace>:<table>:<read|write>` to grant access to the tables of
+    /// the namespace matching `<table>`, which may end with a `*` wildcard.
+    ///
+    /// May be repeated to grant access to several namespaces or tables, or
+    /// both read and write access to them.
+    #[clap(long = "grant", action)]
+    grants: Vec<AuthTokenGrant>,
+}
+
+/// Revoke a token, which no longer grants access once the authorizers drop
+/// it from their cache
+#[derive(Debug, clap::Parser)]
+struct Revoke {
+    /// The ID of the token to revoke
+    id: i64,
+}
+
+/// All possible subcommands for token
+#[derive(Debug, clap::Parser)]
+enum Command {
+    /// Create a new token, and print its secret
+    Create(Create),
+
+    /// List all tokens, including revoked ones
+    List,
+
+    /// Revoke a token
+    Revoke(Revoke),
+}
+
+pub async fn command(config: Config) -> Result<(), Error> {
+    let metrics = setup_metric_registry();
+    let catalog = config.catalog_dsn.get_catalog("cli", metrics).await?;
+    let mut repos = catalog.repositories().await;
+
+    match config.command {
+        Command::Create(Create {
+            description,
+            grants,
+        }) => {
+            // The secret is only ever printed here, the catalog only stores
+            // its hash.
+            let secret = generate_token();
+            let token = repos
+                .auth_tokens()
+                .create(&hash_token(secret.as_bytes()), &description, &grants)
+                .await?;
+            println!("{}", create_table(std::slice::from_ref(&token)));
+            println!("Token (it cannot be displayed again): {secret}");
+        }
+        Command::List => {
+            let tokens = repos.auth_tokens().list().await?;
+            println!("{}", create_table(&tokens));
+        }
+        Command::Revoke(Revoke { id }) => {
+            let id = AuthTokenId::new(id);
+            let token = repos
+                .auth_tokens()
+                .revoke(id)
+                .await?
+                .ok_or(Error::NotFound(id))?;
+            println!("{}", create_table(std::slice::from_ref(&token)));
+        }
+    }
+
+    Ok(())
+}
+
+/// Turn tokens into a table
+fn create_table(tokens: &[AuthToken]) -> Table {
+    let mut table = Table::new();
+    table.load_preset("||--+-++|    ++++++");
+
+    let headers: Vec<_> = ["id", "description", "grants", "created_at", "revoked_at"]
+        .into_iter()
+        .map(Cell::new)
+        .collect();
+    table.set_header(headers);
+
+    for token in tokens {
+        let grants = token
+            .grants
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let revoked_at = token
+            .revoked_at
+            .map(|t| Time::from(t).to_rfc3339())
+            .unwrap_or_default();
+
+        table.add_row(vec![
+            Cell::new(token.id.to_string()),
+            Cell::new(&token.description),
+            Cell::new(grants),
+            Cell::new(Time::from(token.created_at).to_rfc3339()),
+            Cell::new(revoked_at),
+        ]);
+    }
+
+    table
+}
@@ -41,7 +41,9 @@ pub type Result<T, E = Error> = std::result::Result<T, E>;
 
 #[derive(Debug, clap::Parser)]
 pub struct Config {
-    /// Directory containing the output of running `influxdb_iox remote store get-table`
+    /// Directory containing the output of running `influxdb_iox remote store get-table`, or one
+    /// of the table directories written by `influxdb_iox remote store get-namespace`. Only the
+    /// Parquet files listed in the manifest of the export, if any, are imported
     #[clap(value_parser)]
     input_dir: PathBuf,
 
@@ -7,6 +7,7 @@ mod parquet_to_lp;
 mod print_cpu;
 mod schema;
 mod skipped_compactions;
+mod verify;
 mod wal;
 
 #[derive(Debug, Snafu)]
@@ -27,6 +28,10 @@ pub enum Error {
     #[snafu(display("Error in skipped-compactions subcommand: {}", source))]
     SkippedCompactions { source: skipped_compactions::Error },
 
+    #[snafu(context(false))]
+    #[snafu(display("Error in verify subcommand: {}", source))]
+    Verify { source: verify::Error },
+
     #[snafu(context(false))]
     #[snafu(display("Error in wal subcommand: {}", source))]
     Wal { source: wal::Error },
@@ -72,6 +77,10 @@ enum Command {
     /// Interrogate skipped compactions
     SkippedCompactions(skipped_compactions::Config),
 
+    /// Check that every parquet file in the catalog exists in the object store and print a
+    /// repair plan
+    Verify(verify::Config),
+
     /// Subcommands for debugging the WAL
     Wal(wal::Config),
 }
@@ -93,6 +102,7 @@ where
             let connection = connection().await;
             skipped_compactions::command(connection, config).await?
         }
+        Command::Verify(config) => verify::command(config).await?,
         Command::Wal(config) => wal::command(connection, config).await?,
     }
 
@@ -0,0 +1,121 @@
+//! This module implements the `verify` CLI command
+use clap_blocks::{
+    catalog_dsn::CatalogDsnConfig,
+    object_store::{make_object_store, ObjectStoreConfig},
+};
+use garbage_collector::verify::{verify, RepairAction, VerifyOptions};
+use snafu::{ResultExt, Snafu};
+use std::{num::NonZeroUsize, path::PathBuf};
+
+use crate::process_info::setup_metric_registry;
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Catalog DSN error: {}", source))]
+    #[snafu(context(false))]
+    CatalogDsn {
+        source: clap_blocks::catalog_dsn::Error,
+    },
+
+    #[snafu(display("Cannot parse object store config: {}", source))]
+    #[snafu(context(false))]
+    ObjectStoreParsing {
+        source: clap_blocks::object_store::ParseError,
+    },
+
+    #[snafu(display("Verification failed: {}", source))]
+    #[snafu(context(false))]
+    Verify {
+        source: garbage_collector::verify::Error,
+    },
+
+    #[snafu(display("Cannot serialize repair plan: {}", source))]
+    #[snafu(context(false))]
+    Serde { source: serde_json::Error },
+
+    #[snafu(display("Cannot write repair plan to '{:?}': {}", path, source))]
+    File {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Check that every parquet file in the catalog exists in the object store.
+///
+/// Prints a repair plan as JSON that lists every inconsistent parquet file together with the
+/// suggested action: `soft_delete` for files that are missing from the object store, `flag` for
+/// files that exist but do not match their catalog row. The catalog is NOT modified.
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    #[clap(flatten)]
+    catalog_dsn: CatalogDsnConfig,
+
+    #[clap(flatten)]
+    object_store: ObjectStoreConfig,
+
+    /// Only check the parquet files of this namespace.
+    #[clap(long, action)]
+    namespace: Option<String>,
+
+    /// Read the footer of every parquet file and check that its embedded IOx metadata and
+    /// statistics match the catalog.
+    #[clap(long, action)]
+    validate_metadata: bool,
+
+    /// Number of parquet files that are checked concurrently.
+    #[clap(long, default_value = "10", action)]
+    concurrency: NonZeroUsize,
+
+    /// Write the repair plan to this file instead of stdout.
+    #[clap(long, short, action)]
+    output: Option<PathBuf>,
+}
+
+pub async fn command(config: Config) -> Result<()> {
+    let Config {
+        catalog_dsn,
+        object_store,
+        namespace,
+        validate_metadata,
+        concurrency,
+        output,
+    } = config;
+
+    let metrics = setup_metric_registry();
+    let catalog = catalog_dsn.get_catalog("cli", metrics).await?;
+    let object_store = make_object_store(&object_store)?;
+
+    let plan = verify(
+        catalog,
+        object_store,
+        VerifyOptions {
+            namespace,
+            validate_metadata,
+            concurrency: concurrency.get(),
+        },
+    )
+    .await?;
+
+    let count = |action| {
+        plan.steps
+            .iter()
+            .filter(|step| step.action == action)
+            .count()
+    };
+    eprintln!(
+        "checked {} parquet files: {} to soft-delete, {} flagged",
+        plan.files_checked,
+        count(RepairAction::SoftDelete),
+        count(RepairAction::Flag),
+    );
+
+    let json = serde_json::to_string_pretty(&plan)?;
+    match output {
+        Some(path) => std::fs::write(&path, json).context(FileSnafu { path })?,
+        None => println!("{json}"),
+    }
+
+    Ok(())
+}
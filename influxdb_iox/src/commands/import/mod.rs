@@ -0,0 +1,35 @@
+//! This module implements the `import` CLI command
+
+use influxdb_iox_client::connection::Connection;
+use thiserror::Error;
+
+mod tsm;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Error in tsm subcommand: {0}")]
+    Tsm(#[from] tsm::Error),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Import data from other systems
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+/// All possible subcommands for import
+#[derive(Debug, clap::Parser)]
+enum Command {
+    /// Import the data of TSM files written by InfluxDB 1.x or 2.x
+    Tsm(tsm::Config),
+}
+
+pub async fn command(connection: Connection, config: Config) -> Result<()> {
+    match config.command {
+        Command::Tsm(config) => tsm::command(connection, config).await?,
+    }
+    Ok(())
+}
@@ -0,0 +1,85 @@
+//! This module implements the `import tsm` CLI command
+
+use std::{num::NonZeroUsize, path::PathBuf, time::Instant};
+
+use import_export::tsm::{find_tsm_files, TsmImporter};
+use influxdb_iox_client::{connection::Connection, write};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Import error: {0}")]
+    Import(#[from] import_export::tsm::Error),
+
+    #[error("No TSM files found in {0:?}")]
+    NoFiles(PathBuf),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Import the data of TSM files written by InfluxDB 1.x or 2.x, writing it
+/// into a namespace through the router.
+///
+/// All files must hold the data of the same InfluxDB 2.x bucket, or 1.x
+/// database and retention policy. Deletes recorded in `.tombstone` files are
+/// not applied, so files with tombstones are only imported with
+/// `--ignore-tombstones`.
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    /// If specified, restricts the maximum amount of line protocol
+    /// sent per request to this many bytes. Defaults to 1MB
+    #[clap(action, long, short = 'b', default_value = "1048576")]
+    max_request_payload_size_bytes: usize,
+
+    /// Uploads up to this many http requests at a time. Defaults to 10
+    #[clap(action, long, short = 'c', default_value = "10")]
+    max_concurrent_uploads: NonZeroUsize,
+
+    /// Import TSM files that have `.tombstone` files, ignoring the deletes
+    /// they record, instead of failing
+    #[clap(action, long)]
+    ignore_tombstones: bool,
+
+    /// The namespace into which to write, in the form <org_id>_<bucket_id>
+    #[clap(action)]
+    namespace: String,
+
+    /// A TSM file, or a directory (such as the data directory of an
+    /// InfluxDB server) that is searched recursively for `.tsm` files
+    #[clap(action)]
+    path: PathBuf,
+}
+
+pub async fn command(connection: Connection, config: Config) -> Result<()> {
+    let start = Instant::now();
+
+    let Config {
+        max_request_payload_size_bytes,
+        max_concurrent_uploads,
+        ignore_tombstones,
+        namespace,
+        path,
+    } = config;
+
+    let files = find_tsm_files(&path)?;
+    if files.is_empty() {
+        return Err(Error::NoFiles(path));
+    }
+
+    let client = write::Client::new(connection)
+        .with_max_concurrent_uploads(max_concurrent_uploads)
+        .with_max_request_payload_size_bytes(Some(max_request_payload_size_bytes));
+
+    TsmImporter::new(client, namespace)
+        .with_ignore_tombstones(ignore_tombstones)
+        .import(&files)
+        .await?;
+
+    println!(
+        "Imported {} TSM files in {:?}",
+        files.len(),
+        start.elapsed()
+    );
+
+    Ok(())
+}
@@ -19,6 +19,11 @@ pub struct PartitionTemplateConfig {
     ///
     ///  - timeFormat and tagValue can be in any order
     ///
+    ///  - tagBucket hashes the value of a tag into a fixed number of buckets, and tagPrefix
+    ///    retains at most the given number of leading characters of a tag value, e.g.
+    ///    {"tagBucket": {"tagName": "col1", "numBuckets": 10}} or
+    ///    {"tagPrefix": {"tagName": "col2", "length": 3}}
+    ///
     ///  - The value of timeFormat and tagValue are string and can be whatever at parsing time.
     ///    If they are not in the right format the server expcected, the server will return error.
     ///    Note that "time" is a reserved word and cannot be used in timeFormat.
@@ -53,7 +58,9 @@ mod tests {
     use test_helpers::assert_contains;
 
     use crate::commands::partition_template::PartitionTemplateConfig;
-    use generated_types::influxdata::iox::partition_template::v1::template_part::Part;
+    use generated_types::influxdata::iox::partition_template::v1::{
+        template_part::Part, TagBucket, TagPrefix,
+    };
 
     // ===================================================
     // Negative tests for parsing invalid partition template
@@ -102,7 +109,7 @@ mod tests {
         .unwrap_err()
         .to_string();
 
-        assert_contains!(partition_template, "Client Error: Invalid partition template format : unknown field `time Format`, expected one of `tag_value`, `tagValue`, `time_format`, `timeFormat`");
+        assert_contains!(partition_template, "Client Error: Invalid partition template format : unknown field `time Format`, expected one of `tag_value`, `tagValue`, `time_format`, `timeFormat`, `tag_bucket`, `tagBucket`, `tag_prefix`, `tagPrefix`");
     }
 
     #[test]
@@ -115,7 +122,7 @@ mod tests {
         .unwrap_err()
         .to_string();
 
-        assert_contains!(partition_template, "Client Error: Invalid partition template format : unknown field `wrong format`, expected one of `tag_value`, `tagValue`, `time_format`, `timeFormat`");
+        assert_contains!(partition_template, "Client Error: Invalid partition template format : unknown field `wrong format`, expected one of `tag_value`, `tagValue`, `time_format`, `timeFormat`, `tag_bucket`, `tagBucket`, `tag_prefix`, `tagPrefix`");
     }
 
     #[test]
@@ -168,6 +175,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn valid_tag_bucket_and_prefix_format() {
+        let actual = PartitionTemplateConfig::try_parse_from([
+            "server",
+            "--partition-template",
+            "{\"parts\": [{\"tagBucket\": {\"tagName\": \"col1\", \"numBuckets\": 10}}, {\"tagPrefix\": {\"tagName\": \"col2\", \"length\": 3}}] }",
+        ])
+        .unwrap();
+
+        let part_template = actual.partition_template.unwrap();
+        assert_eq!(part_template.parts.len(), 2);
+        assert_eq!(
+            part_template.parts[0].part,
+            Some(Part::TagBucket(TagBucket {
+                tag_name: "col1".to_string(),
+                num_buckets: 10,
+            }))
+        );
+        assert_eq!(
+            part_template.parts[1].part,
+            Some(Part::TagPrefix(TagPrefix {
+                tag_name: "col2".to_string(),
+                length: 3,
+            }))
+        );
+    }
+
     #[test]
     fn valid_partition_template_time_first() {
         let actual = PartitionTemplateConfig::try_parse_from([
@@ -1,7 +1,7 @@
 //! This module implements the `remote store` CLI subcommand
 
 use futures::StreamExt;
-use import_export::file::RemoteExporter;
+use import_export::file::{ExportFilter, RemoteExporter};
 use influxdb_iox_client::{connection::Connection, store};
 use std::path::PathBuf;
 use thiserror::Error;
@@ -59,6 +59,68 @@ struct GetTable {
     /// after the table in the current working directory.
     #[clap(action, short)]
     output_directory: Option<PathBuf>,
+
+    #[clap(flatten)]
+    filter: FilterArgs,
+}
+
+/// Get data for all tables of a namespace into a local directory, with one
+/// sub-directory per table.
+///
+/// A `manifest.json` listing the checksums of all exported Parquet files is
+/// written to the output directory. Re-running an interrupted export resumes
+/// it, skipping the Parquet files that were already downloaded.
+#[derive(Debug, clap::Parser)]
+struct GetNamespace {
+    /// The namespace to get the Parquet files for
+    #[clap(action)]
+    namespace: String,
+
+    /// The output directory to use. If not specified, files will be placed in a directory named
+    /// after the namespace in the current working directory.
+    #[clap(action, short)]
+    output_directory: Option<PathBuf>,
+
+    #[clap(flatten)]
+    filter: FilterArgs,
+}
+
+/// Arguments restricting which Parquet files are exported
+#[derive(Debug, clap::Parser)]
+struct FilterArgs {
+    /// Only export Parquet files containing data at or after this time (nanoseconds since the
+    /// epoch, also accepts RFC3339 format)
+    #[clap(long, value_parser = parse_timestamp)]
+    start: Option<i64>,
+
+    /// Only export Parquet files containing data before this time (nanoseconds since the epoch,
+    /// also accepts RFC3339 format)
+    #[clap(long, value_parser = parse_timestamp)]
+    end: Option<i64>,
+
+    /// Only export Parquet files of the partitions with these keys. May be specified multiple
+    /// times
+    #[clap(long = "partition-key", action)]
+    partition_keys: Vec<String>,
+}
+
+impl From<FilterArgs> for ExportFilter {
+    fn from(args: FilterArgs) -> Self {
+        Self {
+            start: args.start,
+            end: args.end,
+            partition_keys: args.partition_keys,
+        }
+    }
+}
+
+fn parse_timestamp(s: &str) -> Result<i64, String> {
+    match s.parse::<i64>() {
+        Ok(v) => Ok(v),
+        Err(_) => iox_time::Time::from_rfc3339(s)
+            .map(|t| t.timestamp_nanos())
+            .map_err(|e| format!("invalid timestamp '{s}': {e}")),
+    }
 }
 
 /// All possible subcommands for store
@@ -67,6 +129,8 @@ enum Command {
     Get(Get),
 
     GetTable(GetTable),
+
+    GetNamespace(GetNamespace),
 }
 
 pub async fn command(connection: Connection, config: Config) -> Result<()> {
@@ -88,11 +152,22 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
             namespace,
             table,
             output_directory,
+            filter,
         }) => {
-            let mut exporter = RemoteExporter::new(connection);
+            let mut exporter = RemoteExporter::new(connection).with_filter(filter.into());
             Ok(exporter
                 .export_table(output_directory, namespace, table)
                 .await?)
         }
+        Command::GetNamespace(GetNamespace {
+            namespace,
+            output_directory,
+            filter,
+        }) => {
+            let mut exporter = RemoteExporter::new(connection).with_filter(filter.into());
+            Ok(exporter
+                .export_namespace(output_directory, namespace)
+                .await?)
+        }
     }
 }
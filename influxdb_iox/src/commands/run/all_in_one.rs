@@ -16,7 +16,8 @@ use clap_blocks::{
     router::RouterConfig,
     run_config::RunConfig,
     single_tenant::{
-        CONFIG_AUTHZ_ENV_NAME, CONFIG_AUTHZ_FLAG, CONFIG_CST_ENV_NAME, CONFIG_CST_FLAG,
+        CONFIG_AUTHZ_CATALOG_ENV_NAME, CONFIG_AUTHZ_CATALOG_FLAG, CONFIG_AUTHZ_ENV_NAME,
+        CONFIG_AUTHZ_FLAG, CONFIG_CST_ENV_NAME, CONFIG_CST_FLAG,
     },
     socket_addr::SocketAddr,
 };
@@ -169,6 +170,7 @@ pub type Result<T, E = Error> = std::result::Result<T, E>;
     about = "Runs in IOx All in One mode, containing router, ingester, compactor and querier."
 )]
 #[group(skip)]
+#[clap(group(clap::ArgGroup::new("authz").args(["authz_address", "authz_catalog"])))]
 pub struct Config {
     #[clap(
         long = CONFIG_AUTHZ_FLAG,
@@ -177,11 +179,19 @@ pub struct Config {
     )]
     pub(crate) authz_address: Option<String>,
 
+    #[clap(
+        long = CONFIG_AUTHZ_CATALOG_FLAG,
+        env = CONFIG_AUTHZ_CATALOG_ENV_NAME,
+        requires("single_tenant_deployment"),
+        action
+    )]
+    pub(crate) authz_catalog: bool,
+
     #[clap(
         long = CONFIG_CST_FLAG,
         env = CONFIG_CST_ENV_NAME,
         default_value = "false",
-        requires_if("true", "authz_address")
+        requires_if("true", "authz")
     )]
     pub(crate) single_tenant_deployment: bool,
 
@@ -368,6 +378,7 @@ impl Config {
     fn specialize(self) -> SpecializedConfig {
         let Self {
             authz_address,
+            authz_catalog,
             logging_config,
             tracing_config,
             max_http_request_size,
@@ -479,10 +490,13 @@ impl Config {
         let ingester_config = IngesterConfig {
             wal_directory,
             wal_rotation_period_seconds,
+            wal_replication_prefix: None,
             concurrent_query_limit,
             persist_max_parallelism,
             persist_queue_depth,
             persist_hot_partition_cost,
+            persist_idle_partition_seconds: None,
+            persist_buffer_watermark_bytes: None,
             rpc_write_max_incoming_bytes: 1024 * 1024 * 1024, // 1GiB
             gossip_config: GossipConfig::disabled(),
             max_partitions_per_namespace: None,
@@ -490,6 +504,7 @@ impl Config {
 
         let router_config = RouterConfig {
             authz_address: authz_address.clone(),
+            authz_catalog,
             single_tenant_deployment,
             http_request_limit: 1_000,
             ingester_addresses: ingester_addresses.clone(),
@@ -529,15 +544,21 @@ impl Config {
 
         let querier_config = QuerierConfig {
             authz_address,
+            authz_catalog,
             num_query_threads: None, // will be ignored
             ingester_addresses,
             ram_pool_metadata_bytes: querier_ram_pool_metadata_bytes,
             ram_pool_data_bytes: querier_ram_pool_data_bytes,
+            disk_cache_directory: None,
+            disk_cache_bytes: 0,
             max_concurrent_queries: querier_max_concurrent_queries,
             exec_mem_pool_bytes,
             ingester_circuit_breaker_threshold: u64::MAX, // never for all-in-one-mode
             datafusion_config: Default::default(),
             v2_ingester_api: false,
+            query_log_sink_router_address: None,
+            query_log_sink_namespace: None,
+            router_grpc_address: Some(format!("http://{router_grpc_bind_address}")),
         };
 
         SpecializedConfig {
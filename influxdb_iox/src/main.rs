@@ -39,6 +39,7 @@ use trace_exporters::{
 mod commands {
     pub mod catalog;
     pub mod debug;
+    pub mod import;
     pub mod namespace;
     pub mod partition_template;
     pub mod query;
@@ -216,6 +217,9 @@ enum Command {
     /// Write data into the specified namespace
     Write(commands::write::Config),
 
+    /// Import data from other systems into the specified namespace
+    Import(commands::import::Config),
+
     /// Query the data with SQL
     Query(commands::query::Config),
 
@@ -359,6 +363,14 @@ fn main() -> Result<(), std::io::Error> {
                     std::process::exit(ReturnCode::Failure as _)
                 }
             }
+            Some(Command::Import(config)) => {
+                let _tracing_guard = handle_init_logs(init_simple_logs(log_verbose_count));
+                let connection = connection(http_host).await;
+                if let Err(e) = commands::import::command(connection, config).await {
+                    eprintln!("{e}");
+                    std::process::exit(ReturnCode::Failure as _)
+                }
+            }
             Some(Command::Query(config)) => {
                 let _tracing_guard = handle_init_logs(init_simple_logs(log_verbose_count));
                 let connection = connection(grpc_host).await;
@@ -323,6 +323,65 @@ async fn write_and_query() {
     .await
 }
 
+/// Test the import tsm CLI command
+#[tokio::test]
+async fn import_tsm() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::Custom(Box::new(|state: &mut StepTestState| {
+                async {
+                    let router_addr = state.cluster().router().router_http_base().to_string();
+                    let namespace = state.cluster().namespace();
+
+                    // decompress the (InfluxDB 2.x) TSM fixture into a data directory
+                    let dir = tempdir().unwrap();
+                    let mut decoder = flate2::read::GzDecoder::new(
+                        std::fs::File::open("../test_fixtures/000000000000005-000000002.tsm.gz")
+                            .unwrap(),
+                    );
+                    let mut file =
+                        std::fs::File::create(dir.path().join("000000000000005-000000002.tsm"))
+                            .unwrap();
+                    std::io::copy(&mut decoder, &mut file).unwrap();
+
+                    Command::cargo_bin("influxdb_iox")
+                        .unwrap()
+                        .arg("-h")
+                        .arg(&router_addr)
+                        .arg("import")
+                        .arg("tsm")
+                        .arg(namespace)
+                        .arg(dir.path())
+                        .assert()
+                        .success()
+                        .stdout(predicate::str::contains("Imported 1 TSM files"));
+                }
+                .boxed()
+            })),
+            Step::Custom(Box::new(|state: &mut StepTestState| {
+                async {
+                    wait_for_query_result(
+                        state,
+                        "SELECT cpu, host from cpu order by time desc limit 1",
+                        None,
+                        "| cpu-total | Edwards-MBP |",
+                    )
+                    .await;
+                }
+                .boxed()
+            })),
+        ],
+    )
+    .run()
+    .await
+}
+
 /// Test error handling for the query CLI command
 #[tokio::test]
 async fn query_error_handling() {
@@ -359,6 +418,71 @@ async fn query_error_handling() {
     .await
 }
 
+/// Test InfluxQL statements that change the schema, which the querier forwards to the router
+#[tokio::test]
+async fn influxql_schema_changes() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let ingester_config = TestConfig::new_ingester(&database_url);
+    let router_config = TestConfig::new_router(&ingester_config);
+    let querier_config =
+        TestConfig::new_querier(&ingester_config).with_querier_router(&router_config);
+    let mut cluster = MiniCluster::new()
+        .with_ingester(ingester_config)
+        .await
+        .with_router(router_config)
+        .await
+        .with_querier(querier_config)
+        .await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol("cpu,region=west usage=1 1\nmem,region=west used=2i 1".into()),
+            Step::Custom(Box::new(|state: &mut StepTestState| {
+                async {
+                    let querier_addr = state.cluster().querier().querier_grpc_base().to_string();
+                    let namespace = state.cluster().namespace();
+                    let new_namespace = format!("{namespace}_new");
+
+                    let influxql = |query: &str| {
+                        let mut cmd = Command::cargo_bin("influxdb_iox").unwrap();
+                        cmd.arg("-h")
+                            .arg(&querier_addr)
+                            .arg("query")
+                            .arg("--lang")
+                            .arg("influxql")
+                            .arg(namespace)
+                            .arg(query);
+                        cmd
+                    };
+
+                    influxql(&format!("CREATE DATABASE {new_namespace}"))
+                        .assert()
+                        .success();
+                    // creating an existing database is not an error
+                    influxql(&format!("CREATE DATABASE {new_namespace}"))
+                        .assert()
+                        .success();
+                    influxql("SHOW DATABASES")
+                        .assert()
+                        .success()
+                        .stdout(predicate::str::contains(new_namespace.as_str()));
+
+                    influxql("DELETE FROM cpu WHERE region = 'west'")
+                        .assert()
+                        .success();
+                    influxql("DROP MEASUREMENT mem").assert().success();
+                }
+                .boxed()
+            })),
+        ],
+    )
+    .run()
+    .await
+}
+
 /// Test error handling for the query CLI command for InfluxQL queries
 #[tokio::test]
 async fn influxql_error_handling() {
@@ -388,7 +512,7 @@ async fn influxql_error_handling() {
                         .assert()
                         .failure()
                         .stderr(predicate::str::contains(
-                            "Error while planning query: This feature is not implemented: CREATE DATABASE",
+                            "Error while executing statement: This feature is not implemented: schema changes require the querier to be configured with a router address",
                         ));
                 }
                     .boxed()
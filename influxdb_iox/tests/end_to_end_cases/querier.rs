@@ -802,11 +802,16 @@ async fn iox_debug_header() {
                     "SELECT * from information_schema.tables where table_schema = 'system'",
                 ),
                 expected: vec![
-                    "+---------------+--------------+------------+------------+",
-                    "| table_catalog | table_schema | table_name | table_type |",
-                    "+---------------+--------------+------------+------------+",
-                    "| public        | system       | queries    | BASE TABLE |",
-                    "+---------------+--------------+------------+------------+",
+                    "+---------------+--------------+-------------------+------------+",
+                    "| table_catalog | table_schema | table_name        | table_type |",
+                    "+---------------+--------------+-------------------+------------+",
+                    "| public        | system       | caches            | BASE TABLE |",
+                    "| public        | system       | compactor_skipped | BASE TABLE |",
+                    "| public        | system       | parquet_files     | BASE TABLE |",
+                    "| public        | system       | partitions        | BASE TABLE |",
+                    "| public        | system       | queries           | BASE TABLE |",
+                    "| public        | system       | tables            | BASE TABLE |",
+                    "+---------------+--------------+-------------------+------------+",
                 ],
             },
             Step::Query {
@@ -826,16 +831,21 @@ async fn iox_debug_header() {
             Step::QueryWithDebug {
                 sql: String::from("SHOW TABLES"),
                 expected: vec![
-                    "+---------------+--------------------+-------------+------------+",
-                    "| table_catalog | table_schema       | table_name  | table_type |",
-                    "+---------------+--------------------+-------------+------------+",
-                    "| public        | information_schema | columns     | VIEW       |",
-                    "| public        | information_schema | df_settings | VIEW       |",
-                    "| public        | information_schema | tables      | VIEW       |",
-                    "| public        | information_schema | views       | VIEW       |",
-                    "| public        | iox                | the_table   | BASE TABLE |",
-                    "| public        | system             | queries     | BASE TABLE |",
-                    "+---------------+--------------------+-------------+------------+",
+                    "+---------------+--------------------+-------------------+------------+",
+                    "| table_catalog | table_schema       | table_name        | table_type |",
+                    "+---------------+--------------------+-------------------+------------+",
+                    "| public        | information_schema | columns           | VIEW       |",
+                    "| public        | information_schema | df_settings       | VIEW       |",
+                    "| public        | information_schema | tables            | VIEW       |",
+                    "| public        | information_schema | views             | VIEW       |",
+                    "| public        | iox                | the_table         | BASE TABLE |",
+                    "| public        | system             | caches            | BASE TABLE |",
+                    "| public        | system             | compactor_skipped | BASE TABLE |",
+                    "| public        | system             | parquet_files     | BASE TABLE |",
+                    "| public        | system             | partitions        | BASE TABLE |",
+                    "| public        | system             | queries           | BASE TABLE |",
+                    "| public        | system             | tables            | BASE TABLE |",
+                    "+---------------+--------------------+-------------------+------------+",
                 ],
             },
             Step::QueryExpectingError {
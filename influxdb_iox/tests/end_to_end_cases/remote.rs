@@ -3,8 +3,9 @@ use std::path::Path;
 
 use super::get_object_store_id;
 use assert_cmd::Command;
+use assert_matches::assert_matches;
 use futures::FutureExt;
-use import_export::file::ExportedContents;
+use import_export::file::{ExportManifest, ExportedContents, MANIFEST_FILE_NAME};
 use predicates::prelude::*;
 use tempfile::tempdir;
 use test_helpers_end_to_end::{maybe_skip_integration, MiniCluster, Step, StepTest, StepTestState};
@@ -137,7 +138,7 @@ async fn remote_store_get_table() {
                         .assert()
                         .success()
                         .stdout(predicate::str::contains(format!(
-                            "skipping file 1 of 1 ({} already exists with expected file size)",
+                            "skipping file 1 of 1 ({} already exists with expected checksum)",
                             contents.parquet_file_name(0).unwrap(),
                         )));
 
@@ -163,6 +164,37 @@ async fn remote_store_get_table() {
                             "downloading file 1 of 1 ({})...",
                             contents.parquet_file_name(0).unwrap(),
                         )));
+
+                    // If the contents don't match the checksum of the
+                    // manifest, the file is neither imported nor skipped
+                    let parquet_file = &contents.parquet_files()[0];
+                    let len = fs::metadata(parquet_file).await.unwrap().len();
+                    fs::write(parquet_file, vec![0; len as usize])
+                        .await
+                        .unwrap();
+                    assert_matches!(
+                        ExportedContents::try_new(&custom_output_dir),
+                        Err(import_export::file::Error::ManifestMismatch { .. })
+                    );
+
+                    Command::cargo_bin("influxdb_iox")
+                        .unwrap()
+                        .arg("-h")
+                        .arg(&router_addr)
+                        .arg("remote")
+                        .arg("store")
+                        .arg("get-table")
+                        .arg("-o")
+                        .arg(&custom_output_dir)
+                        .arg(&namespace)
+                        .arg(other_table_name)
+                        .assert()
+                        .success()
+                        .stdout(predicate::str::contains(format!(
+                            "downloading file 1 of 1 ({})...",
+                            contents.parquet_file_name(0).unwrap(),
+                        )));
+                    assert_one_parquet_file_and_meta(&custom_output_dir);
                 }
                 .boxed()
             })),
@@ -202,6 +234,125 @@ async fn remote_store_get_table() {
     .await
 }
 
+/// Get the Parquet files of all tables in a namespace, using the command
+/// `remote store get-namespace`
+#[tokio::test]
+async fn remote_store_get_namespace() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+    let table_name = "my_awesome_table";
+    let other_table_name = "my_ordinary_table";
+
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::RecordNumParquetFiles,
+            Step::WriteLineProtocol(format!("{table_name},tag1=A,tag2=B val=42i 123456")),
+            Step::WaitForPersisted {
+                expected_increase: 1,
+            },
+            Step::RecordNumParquetFiles,
+            Step::WriteLineProtocol(format!("{table_name},tag1=C,tag2=B val=9000i 789000")),
+            Step::WaitForPersisted {
+                expected_increase: 1,
+            },
+            Step::RecordNumParquetFiles,
+            Step::WriteLineProtocol(format!("{other_table_name},tag1=A,tag2=B val=42i 123456")),
+            Step::WaitForPersisted {
+                expected_increase: 1,
+            },
+            Step::Custom(Box::new(move |state: &mut StepTestState| {
+                async move {
+                    let router_addr = state.cluster().router().router_grpc_base().to_string();
+                    let namespace = state.cluster().namespace().to_string();
+
+                    let dir = tempfile::tempdir().expect("could not get temporary directory");
+
+                    Command::cargo_bin("influxdb_iox")
+                        .unwrap()
+                        .current_dir(&dir)
+                        .arg("-h")
+                        .arg(&router_addr)
+                        .arg("remote")
+                        .arg("store")
+                        .arg("get-namespace")
+                        .arg(&namespace)
+                        .assert()
+                        .success();
+
+                    // There should be a directory named after the
+                    // namespace, with one directory per table
+                    let namespace_dir = dir.as_ref().join(&namespace);
+                    assert_two_parquet_files_and_meta(&namespace_dir.join(table_name));
+                    assert_one_parquet_file_and_meta(&namespace_dir.join(other_table_name));
+
+                    let manifest = read_manifest(&namespace_dir).await;
+                    assert_eq!(manifest.namespace, namespace);
+                    assert_eq!(manifest.tables, [table_name, other_table_name]);
+                    assert_eq!(manifest.parquet_files.len(), 3);
+                    for entry in &manifest.parquet_files {
+                        let data = fs::read(namespace_dir.join(&entry.path)).await.unwrap();
+                        assert_eq!(data.len() as u64, entry.file_size_bytes);
+                        assert_eq!(entry.sha256.len(), 64);
+                    }
+
+                    // Running the same command again shouldn't download any new files
+                    Command::cargo_bin("influxdb_iox")
+                        .unwrap()
+                        .current_dir(&dir)
+                        .arg("-h")
+                        .arg(&router_addr)
+                        .arg("remote")
+                        .arg("store")
+                        .arg("get-namespace")
+                        .arg(&namespace)
+                        .assert()
+                        .success()
+                        .stdout(predicate::str::contains("downloading").not());
+                    assert_eq!(read_manifest(&namespace_dir).await, manifest);
+
+                    // Only export the files containing data in the time range
+                    let filtered_dir = dir.as_ref().join("filtered");
+                    Command::cargo_bin("influxdb_iox")
+                        .unwrap()
+                        .arg("-h")
+                        .arg(&router_addr)
+                        .arg("remote")
+                        .arg("store")
+                        .arg("get-namespace")
+                        .arg("-o")
+                        .arg(&filtered_dir)
+                        .arg("--start")
+                        .arg("500000")
+                        .arg("--end")
+                        .arg("1970-01-01T00:00:01Z")
+                        .arg(&namespace)
+                        .assert()
+                        .success();
+
+                    let manifest = read_manifest(&filtered_dir).await;
+                    assert_eq!(manifest.filter.start, Some(500000));
+                    assert_eq!(manifest.filter.end, Some(1_000_000_000));
+                    assert_eq!(manifest.parquet_files.len(), 1);
+                    assert!(manifest.parquet_files[0].path.starts_with(table_name));
+                }
+                .boxed()
+            })),
+        ],
+    )
+    .run()
+    .await
+}
+
+async fn read_manifest(dir: &Path) -> ExportManifest {
+    let json = fs::read_to_string(dir.join(MANIFEST_FILE_NAME))
+        .await
+        .unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
 /// Asserts that the directory contains metadata and parquet files for
 /// 1 partition that has 1 table with 1 parquet files
 fn assert_one_parquet_file_and_meta(table_dir: &Path) -> ExportedContents {
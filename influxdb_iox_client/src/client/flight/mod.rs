@@ -1,8 +1,10 @@
 //! Client for InfluxDB IOx Flight API
 
-use std::{pin::Pin, task::Poll};
+use std::{collections::HashMap, pin::Pin, task::Poll};
 
-use ::generated_types::influxdata::iox::querier::v1::{read_info::QueryType, ReadInfo};
+use ::generated_types::influxdata::iox::querier::v1::{
+    read_info::QueryType, QueryParamValue, ReadInfo,
+};
 use futures_util::{Stream, StreamExt};
 use prost::Message;
 use thiserror::Error;
@@ -198,6 +200,7 @@ impl Client {
             query_type: QueryType::Sql.into(),
             flightsql_command: vec![],
             is_debug: false,
+            params: HashMap::new(),
         };
 
         self.do_get_with_read_info(request).await
@@ -209,6 +212,20 @@ impl Client {
         &mut self,
         database: impl Into<String> + Send,
         influxql_query: impl Into<String> + Send,
+    ) -> Result<IOxRecordBatchStream, Error> {
+        self.influxql_with_params(database, influxql_query, HashMap::new())
+            .await
+    }
+
+    /// Query the given database with the given InfluxQL query, replacing
+    /// the bind parameters it references (such as `$host`) with the values
+    /// in `params`, returning a struct that can stream Arrow
+    /// [`RecordBatch`] results.
+    pub async fn influxql_with_params(
+        &mut self,
+        database: impl Into<String> + Send,
+        influxql_query: impl Into<String> + Send,
+        params: HashMap<String, QueryParamValue>,
     ) -> Result<IOxRecordBatchStream, Error> {
         let request = ReadInfo {
             database: database.into(),
@@ -216,6 +233,7 @@ impl Client {
             query_type: QueryType::InfluxQl.into(),
             flightsql_command: vec![],
             is_debug: false,
+            params,
         };
 
         self.do_get_with_read_info(request).await
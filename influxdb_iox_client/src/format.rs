@@ -1,12 +1,19 @@
 //! Output formatting utilities for Arrow record batches
 
-use std::{fmt::Display, str::FromStr};
+use std::{fmt::Display, io::Write, str::FromStr};
 
 use thiserror::Error;
 
 use arrow::{
-    self, csv::WriterBuilder, error::ArrowError, json::ArrayWriter, record_batch::RecordBatch,
+    self,
+    csv::WriterBuilder,
+    datatypes::SchemaRef,
+    error::ArrowError,
+    ipc::writer::FileWriter,
+    json::{ArrayWriter, LineDelimitedWriter},
+    record_batch::RecordBatch,
 };
+use parquet::{arrow::ArrowWriter, errors::ParquetError};
 
 /// Output formatting for InfluxQL.
 pub mod influxql;
@@ -15,9 +22,16 @@ pub mod influxql;
 #[derive(Debug, Error)]
 pub enum Error {
     /// Unknown formatting type
-    #[error("Unknown format type: {}. Expected one of 'pretty', 'csv' or 'json'", .0)]
+    #[error(
+        "Unknown format type: {}. Expected one of 'pretty', 'csv', 'json', 'jsonl', 'parquet' or 'arrow'",
+        .0
+    )]
     Invalid(String),
 
+    /// The format is binary, and cannot be formatted as a string
+    #[error("Cannot format {} output as text", .0)]
+    Binary(QueryOutputFormat),
+
     /// Error pretty printing
     #[error("Arrow pretty printing error: {}", .0)]
     PrettyArrow(ArrowError),
@@ -37,6 +51,18 @@ pub enum Error {
     /// Error converting JSON output to utf-8
     #[error("Error converting JSON output to UTF-8: {}", .0)]
     JsonUtf8(std::string::FromUtf8Error),
+
+    /// Error during Parquet conversion
+    #[error("Parquet writing error: {}", .0)]
+    Parquet(ParquetError),
+
+    /// Error during Arrow IPC conversion
+    #[error("Arrow IPC writing error: {}", .0)]
+    ArrowIpc(ArrowError),
+
+    /// Error writing the output
+    #[error("Error writing output: {}", .0)]
+    Write(std::io::Error),
 }
 type Result<T, E = Error> = std::result::Result<T, E>;
 
@@ -49,6 +75,12 @@ pub enum QueryOutputFormat {
     Csv,
     /// Arrow JSON format
     Json,
+    /// Arrow newline delimited JSON format, with one object per row
+    JsonLines,
+    /// Parquet file
+    Parquet,
+    /// Arrow IPC file
+    ArrowIpc,
 }
 
 impl Display for QueryOutputFormat {
@@ -57,6 +89,9 @@ impl Display for QueryOutputFormat {
             QueryOutputFormat::Pretty => write!(f, "pretty"),
             QueryOutputFormat::Csv => write!(f, "csv"),
             QueryOutputFormat::Json => write!(f, "json"),
+            QueryOutputFormat::JsonLines => write!(f, "jsonl"),
+            QueryOutputFormat::Parquet => write!(f, "parquet"),
+            QueryOutputFormat::ArrowIpc => write!(f, "arrow"),
         }
     }
 }
@@ -75,6 +110,9 @@ impl FromStr for QueryOutputFormat {
             "pretty" => Ok(Self::Pretty),
             "csv" => Ok(Self::Csv),
             "json" => Ok(Self::Json),
+            "jsonl" | "ndjson" => Ok(Self::JsonLines),
+            "parquet" => Ok(Self::Parquet),
+            "arrow" => Ok(Self::ArrowIpc),
             _ => Err(Error::Invalid(s.to_string())),
         }
     }
@@ -87,6 +125,9 @@ impl QueryOutputFormat {
             Self::Pretty => "text/plain",
             Self::Csv => "text/csv",
             Self::Json => "application/json",
+            Self::JsonLines => "application/x-ndjson",
+            Self::Parquet => "application/vnd.apache.parquet",
+            Self::ArrowIpc => "application/vnd.apache.arrow.file",
         }
     }
 }
@@ -119,11 +160,117 @@ impl QueryOutputFormat {
     ///  {"location":"Boston","state":"MA","surface_degrees":50.2,"time":1568756160}
     /// ]
     /// ```
+    ///
+    /// JSON lines:
+    /// ```text
+    /// {"bottom_degrees":50.4,"location":"santa_monica","state":"CA","surface_degrees":65.2,"time":1568756160}
+    /// {"location":"Boston","state":"MA","surface_degrees":50.2,"time":1568756160}
+    /// ```
+    ///
+    /// The binary Parquet and Arrow IPC formats cannot be formatted as a
+    /// String, use [`writer`](Self::writer) instead.
     pub fn format(&self, batches: &[RecordBatch]) -> Result<String> {
         match self {
             Self::Pretty => batches_to_pretty(batches),
             Self::Csv => batches_to_csv(batches),
             Self::Json => batches_to_json(batches),
+            Self::JsonLines => batches_to_json_lines(batches),
+            Self::Parquet | Self::ArrowIpc => Err(Error::Binary(*self)),
+        }
+    }
+
+    /// Return a [`BatchWriter`] that writes record batches with the schema
+    /// `schema` to `w` in this format, as they are produced.
+    ///
+    /// The pretty format is aligned across all the record batches, so it
+    /// is only written when the writer is finished.
+    pub fn writer<W: Write + Send>(&self, w: W, schema: SchemaRef) -> Result<BatchWriter<W>> {
+        let inner = match self {
+            Self::Pretty => WriterInner::Pretty(w, vec![RecordBatch::new_empty(schema)]),
+            Self::Csv => WriterInner::Csv(WriterBuilder::new().has_headers(true).build(w)),
+            Self::Json => WriterInner::Json(ArrayWriter::new(w)),
+            Self::JsonLines => WriterInner::JsonLines(LineDelimitedWriter::new(w)),
+            Self::Parquet => {
+                WriterInner::Parquet(ArrowWriter::try_new(w, schema, None).map_err(Error::Parquet)?)
+            }
+            Self::ArrowIpc => {
+                WriterInner::ArrowIpc(FileWriter::try_new(w, &schema).map_err(Error::ArrowIpc)?)
+            }
+        };
+        Ok(BatchWriter { inner })
+    }
+}
+
+/// Writes record batches in a [`QueryOutputFormat`], created by
+/// [`QueryOutputFormat::writer`].
+pub struct BatchWriter<W: Write + Send> {
+    inner: WriterInner<W>,
+}
+
+enum WriterInner<W: Write + Send> {
+    Pretty(W, Vec<RecordBatch>),
+    Csv(arrow::csv::Writer<W>),
+    Json(ArrayWriter<W>),
+    JsonLines(LineDelimitedWriter<W>),
+    Parquet(ArrowWriter<W>),
+    ArrowIpc(FileWriter<W>),
+}
+
+impl<W: Write + Send> std::fmt::Debug for BatchWriter<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let format = match &self.inner {
+            WriterInner::Pretty(..) => QueryOutputFormat::Pretty,
+            WriterInner::Csv(_) => QueryOutputFormat::Csv,
+            WriterInner::Json(_) => QueryOutputFormat::Json,
+            WriterInner::JsonLines(_) => QueryOutputFormat::JsonLines,
+            WriterInner::Parquet(_) => QueryOutputFormat::Parquet,
+            WriterInner::ArrowIpc(_) => QueryOutputFormat::ArrowIpc,
+        };
+        f.debug_struct("BatchWriter")
+            .field("format", &format)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<W: Write + Send> BatchWriter<W> {
+    /// Write `batch`.
+    pub fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        match &mut self.inner {
+            WriterInner::Pretty(_, batches) => {
+                batches.push(batch.clone());
+                Ok(())
+            }
+            WriterInner::Csv(w) => w.write(batch).map_err(Error::CsvArrow),
+            WriterInner::Json(w) => w.write(batch).map_err(Error::JsonArrow),
+            WriterInner::JsonLines(w) => w.write(batch).map_err(Error::JsonArrow),
+            WriterInner::Parquet(w) => w.write(batch).map_err(Error::Parquet),
+            WriterInner::ArrowIpc(w) => w.write(batch).map_err(Error::ArrowIpc),
+        }
+    }
+
+    /// Write any trailing output of the format, such as the footer of a
+    /// Parquet file, and return the underlying writer.
+    pub fn finish(self) -> Result<W> {
+        match self.inner {
+            WriterInner::Pretty(mut w, batches) => {
+                let pretty = batches_to_pretty(&batches)?;
+                writeln!(w, "{pretty}").map_err(Error::Write)?;
+                Ok(w)
+            }
+            WriterInner::Csv(w) => Ok(w.into_inner()),
+            WriterInner::Json(mut w) => {
+                w.finish().map_err(Error::JsonArrow)?;
+                Ok(w.into_inner())
+            }
+            WriterInner::JsonLines(mut w) => {
+                w.finish().map_err(Error::JsonArrow)?;
+                Ok(w.into_inner())
+            }
+            WriterInner::Parquet(w) => w.into_inner().map_err(Error::Parquet),
+            WriterInner::ArrowIpc(mut w) => {
+                w.finish().map_err(Error::ArrowIpc)?;
+                w.into_inner().map_err(Error::ArrowIpc)
+            }
         }
     }
 }
@@ -163,8 +310,33 @@ fn batches_to_json(batches: &[RecordBatch]) -> Result<String> {
     Ok(json)
 }
 
+fn batches_to_json_lines(batches: &[RecordBatch]) -> Result<String> {
+    let mut bytes = vec![];
+
+    // json writer wants &[&RecordBatch]
+    let batches: Vec<_> = batches.iter().collect();
+    {
+        let mut writer = LineDelimitedWriter::new(&mut bytes);
+        writer.write_batches(&batches).map_err(Error::JsonArrow)?;
+
+        writer.finish().map_err(Error::JsonArrow)?;
+    }
+
+    let json = String::from_utf8(bytes).map_err(Error::JsonUtf8)?;
+
+    Ok(json)
+}
+
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
+    use arrow::{
+        array::{ArrayRef, Int64Array, StringArray},
+        ipc::reader::FileReader,
+    };
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
     use super::*;
 
     #[test]
@@ -196,9 +368,28 @@ mod tests {
             QueryOutputFormat::Json
         );
 
+        assert_eq!(
+            QueryOutputFormat::from_str("jsonl").unwrap(),
+            QueryOutputFormat::JsonLines
+        );
+        assert_eq!(
+            QueryOutputFormat::from_str("NDJSON").unwrap(),
+            QueryOutputFormat::JsonLines
+        );
+
+        assert_eq!(
+            QueryOutputFormat::from_str("parquet").unwrap(),
+            QueryOutputFormat::Parquet
+        );
+
+        assert_eq!(
+            QueryOutputFormat::from_str("arrow").unwrap(),
+            QueryOutputFormat::ArrowIpc
+        );
+
         assert_eq!(
             QueryOutputFormat::from_str("un").unwrap_err().to_string(),
-            "Unknown format type: un. Expected one of 'pretty', 'csv' or 'json'"
+            "Unknown format type: un. Expected one of 'pretty', 'csv', 'json', 'jsonl', 'parquet' or 'arrow'"
         );
     }
 
@@ -218,5 +409,87 @@ mod tests {
             QueryOutputFormat::from_str(&QueryOutputFormat::Json.to_string()).unwrap(),
             QueryOutputFormat::Json
         );
+
+        assert_eq!(
+            QueryOutputFormat::from_str(&QueryOutputFormat::JsonLines.to_string()).unwrap(),
+            QueryOutputFormat::JsonLines
+        );
+
+        assert_eq!(
+            QueryOutputFormat::from_str(&QueryOutputFormat::Parquet.to_string()).unwrap(),
+            QueryOutputFormat::Parquet
+        );
+
+        assert_eq!(
+            QueryOutputFormat::from_str(&QueryOutputFormat::ArrowIpc.to_string()).unwrap(),
+            QueryOutputFormat::ArrowIpc
+        );
+    }
+
+    fn batches() -> Vec<RecordBatch> {
+        let a: ArrayRef = Arc::new(StringArray::from(vec!["foo", "bar"]));
+        let b: ArrayRef = Arc::new(Int64Array::from(vec![1, 2]));
+        let batch = RecordBatch::try_from_iter(vec![("a", a), ("b", b)]).unwrap();
+        vec![batch.slice(0, 1), batch.slice(1, 1)]
+    }
+
+    fn write(format: QueryOutputFormat, batches: &[RecordBatch]) -> Vec<u8> {
+        let mut writer = format.writer(vec![], batches[0].schema()).unwrap();
+        for batch in batches {
+            writer.write(batch).unwrap();
+        }
+        writer.finish().unwrap()
+    }
+
+    #[test]
+    fn test_writer_text() {
+        let batches = batches();
+        for format in [
+            QueryOutputFormat::Pretty,
+            QueryOutputFormat::Csv,
+            QueryOutputFormat::Json,
+            QueryOutputFormat::JsonLines,
+        ] {
+            let written = String::from_utf8(write(format, &batches)).unwrap();
+            let formatted = format.format(&batches).unwrap();
+            assert_eq!(written.trim_end(), formatted.trim_end(), "{format}");
+        }
+
+        assert_eq!(
+            QueryOutputFormat::JsonLines.format(&batches).unwrap(),
+            "{\"a\":\"foo\",\"b\":1}\n{\"a\":\"bar\",\"b\":2}\n"
+        );
+    }
+
+    #[test]
+    fn test_writer_binary() {
+        let batches = batches();
+
+        assert_eq!(
+            QueryOutputFormat::Parquet
+                .format(&batches)
+                .unwrap_err()
+                .to_string(),
+            "Cannot format parquet output as text"
+        );
+
+        let parquet = write(QueryOutputFormat::Parquet, &batches);
+        let read: Vec<_> = ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(parquet))
+            .unwrap()
+            .build()
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            batches_to_pretty(&read).unwrap(),
+            batches_to_pretty(&batches).unwrap()
+        );
+
+        let ipc = write(QueryOutputFormat::ArrowIpc, &batches);
+        let read: Vec<_> = FileReader::try_new(std::io::Cursor::new(ipc), None)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(read, batches);
     }
 }
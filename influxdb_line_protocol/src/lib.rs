@@ -69,6 +69,7 @@
 
 pub mod builder;
 pub use builder::LineProtocolBuilder;
+pub mod stream;
 
 use fmt::Display;
 use log::debug;
@@ -143,6 +144,9 @@ pub enum Error {
     #[snafu(display(r#"Tag Set Malformed"#))]
     TagSetMalformed,
 
+    #[snafu(display(r#"Line is not valid UTF-8: {}"#, source))]
+    InvalidUtf8 { source: std::str::Utf8Error },
+
     // TODO: Replace this with specific failures.
     #[snafu(display(r#"A generic parsing error occurred: {:?}"#, kind))]
     GenericParsingError {
@@ -521,36 +525,102 @@ impl PartialEq<String> for EscapedStr<'_> {
 /// [`ParsedLine`]. See the [crate-level documentation](self) for more
 /// information and examples.
 pub fn parse_lines(input: &str) -> impl Iterator<Item = Result<ParsedLine<'_>>> {
-    split_lines(input).filter_map(|line| {
-        let i = trim_leading(line);
+    split_lines(input).filter_map(parse_single_line)
+}
 
-        if i.is_empty() {
-            return None;
+/// Parses a single `line` (as yielded by [`split_lines`]), returning [`None`]
+/// if the line is empty or a comment.
+fn parse_single_line(line: &str) -> Option<Result<ParsedLine<'_>>> {
+    let i = trim_leading(line);
+
+    if i.is_empty() {
+        return None;
+    }
+
+    let res = match parse_line(i) {
+        Ok((remaining, line)) => {
+            // should have parsed the whole input line; if any
+            // data remains it is a parse error for this line.
+            // Corresponding Go logic:
+            // https://github.com/influxdata/influxdb/blob/217eddc87e14a79b01d0c22994fc139f530094a2/models/points_parser.go#L259-L266
+            if !remaining.is_empty() {
+                Some(Err(Error::CannotParseEntireLine {
+                    trailing_content: String::from(remaining),
+                }))
+            } else {
+                Some(Ok(line))
+            }
         }
+        Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => Some(Err(e)),
+        Err(nom::Err::Incomplete(_)) => unreachable!("Cannot have incomplete data"), // Only streaming parsers have this
+    };
 
-        let res = match parse_line(i) {
-            Ok((remaining, line)) => {
-                // should have parsed the whole input line; if any
-                // data remains it is a parse error for this line.
-                // Corresponding Go logic:
-                // https://github.com/influxdata/influxdb/blob/217eddc87e14a79b01d0c22994fc139f530094a2/models/points_parser.go#L259-L266
-                if !remaining.is_empty() {
-                    Some(Err(Error::CannotParseEntireLine {
-                        trailing_content: String::from(remaining),
-                    }))
-                } else {
-                    Some(Ok(line))
-                }
-            }
-            Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => Some(Err(e)),
-            Err(nom::Err::Incomplete(_)) => unreachable!("Cannot have incomplete data"), // Only streaming parsers have this
+    if let Some(Err(r)) = &res {
+        debug!("Error parsing line: '{}'. Error was {:?}", line, r);
+    }
+    res
+}
+
+/// Locates the parse error within an invalid `line`, returning the byte index
+/// at which the offending content starts and, if the error is in a field, the
+/// key of that field.
+///
+/// This walks the line element by element using the same parsers as
+/// [`parse_line`], and is only intended to be called once parsing failed.
+fn locate_error(line: &str) -> (usize, Option<String>) {
+    let pos = |rest: &str| line.len() - rest.len();
+
+    let i = trim_leading(line);
+    let rest = match series(i) {
+        Ok((rest, _)) => rest,
+        Err(_) => {
+            // Point at the tag set if the measurement itself is valid.
+            return match measurement(i) {
+                Ok((rest, _)) if rest.starts_with(',') => (pos(rest) + 1, None),
+                _ => (pos(i), None),
+            };
+        }
+    };
+
+    let mut rest = match whitespace(rest) {
+        Ok((rest, _)) => rest,
+        Err(_) => return (pos(rest), None),
+    };
+
+    loop {
+        let (after_key, key) = match field_key(rest) {
+            Ok(v) => v,
+            Err(_) => return (pos(rest), None),
+        };
+        let key = String::from(key);
+
+        let after_eq = match tag::<_, _, Error>("=")(after_key) {
+            Ok((after_eq, _)) => after_eq,
+            Err(_) => return (pos(after_key), Some(key)),
+        };
+        rest = match field_value(after_eq) {
+            // A value must be followed by another field or the timestamp.
+            Ok((rest, _)) if rest.is_empty() || rest.starts_with([',', ' ']) => rest,
+            _ => return (pos(after_eq), Some(key)),
         };
 
-        if let Some(Err(r)) = &res {
-            debug!("Error parsing line: '{}'. Error was {:?}", line, r);
+        match tag::<_, _, Error>(",")(rest) {
+            Ok((after_comma, _)) => rest = after_comma,
+            Err(_) => break,
         }
-        res
-    })
+    }
+
+    let ts = match whitespace(rest) {
+        Ok((ts, _)) => ts,
+        Err(_) => return (pos(rest), None),
+    };
+    match timestamp(ts) {
+        Ok((rest, _)) => {
+            let rest = whitespace(rest).map(|(rest, _)| rest).unwrap_or(rest);
+            (pos(rest), None)
+        }
+        Err(_) => (pos(ts), None),
+    }
 }
 
 /// Split `input` into individual lines to be parsed, based on the
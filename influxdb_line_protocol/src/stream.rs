@@ -0,0 +1,464 @@
+//! Incremental splitting and parsing of line protocol that arrives in chunks.
+//!
+//! [`parse_lines`](crate::parse_lines) requires the entire input to be in
+//! memory as a `&str`. A [`LineSplitter`] instead accepts the input as a
+//! sequence of [`Buf`] chunks (such as the frames of a HTTP request body) and
+//! yields each [`Line`] as soon as it is complete, without copying it.
+//!
+//! Errors returned by [`Line::parse()`] carry the line number, column and
+//! byte offset of the offending content within the stream, and the field it
+//! belongs to (if any).
+//!
+//! ```
+//! use influxdb_line_protocol::stream::LineSplitter;
+//!
+//! let mut splitter = LineSplitter::default();
+//! splitter.push(&b"cpu,host=a usage=0.5 1\ncpu,host=b us"[..]);
+//!
+//! // Only the first line is complete
+//! let line = splitter.next_line().unwrap();
+//! assert_eq!(line.number(), 1);
+//! let parsed = line.parse().unwrap().unwrap();
+//! assert_eq!(parsed.series.measurement, "cpu");
+//! assert!(splitter.next_line().is_none());
+//!
+//! splitter.push(&b"age=bananas 2"[..]);
+//! splitter.finish();
+//!
+//! let line = splitter.next_line().unwrap();
+//! let err = line.parse().unwrap().unwrap_err();
+//! assert_eq!(err.line(), 2);
+//! assert_eq!(err.column(), 18);
+//! assert_eq!(err.field(), Some("usage"));
+//! assert!(splitter.next_line().is_none());
+//! ```
+
+use std::fmt;
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use crate::{locate_error, parse_single_line, Error, ParsedLine};
+
+/// The scanner state of a partially read line, mirroring the state kept by
+/// [`split_lines`](crate::split_lines).
+#[derive(Debug, Default, Clone, Copy)]
+struct ScanState {
+    quoted: bool,
+    fields: bool,
+
+    // tracks how many '=' and commas we've seen
+    equals: usize,
+    commas: usize,
+
+    in_escape: bool,
+
+    /// The number of (quoted) newlines seen so far within the line.
+    newlines: usize,
+}
+
+impl ScanState {
+    /// Advance the state by the byte `c`, returning true if `c` terminates
+    /// the line.
+    ///
+    /// All the bytes of interest are ASCII, and never occur within a multi
+    /// byte UTF-8 sequence, so this is equivalent to the `char` based
+    /// [`split_lines`](crate::split_lines).
+    fn advance(&mut self, c: u8) -> bool {
+        // skip past escaped characters
+        if self.in_escape {
+            self.in_escape = false;
+            if c == b'\n' {
+                self.newlines += 1;
+            }
+            return false;
+        }
+
+        if c == b'\\' {
+            self.in_escape = true;
+            return false;
+        }
+
+        if c == b' ' {
+            self.fields = true;
+            return false;
+        }
+
+        if self.fields {
+            if !self.quoted && c == b'=' {
+                self.equals += 1;
+                return false;
+            } else if !self.quoted && c == b',' {
+                self.commas += 1;
+                return false;
+            } else if c == b'"' && self.equals > self.commas {
+                self.quoted = !self.quoted;
+                return false;
+            }
+        }
+
+        if c == b'\n' {
+            if !self.quoted {
+                return true;
+            }
+            self.newlines += 1;
+        }
+
+        false
+    }
+}
+
+/// Splits a stream of line protocol chunks into individual [`Line`]s.
+///
+/// Input is added with [`LineSplitter::push()`], and complete lines are
+/// removed with [`LineSplitter::next_line()`]. Once all the input has been
+/// pushed, [`LineSplitter::finish()`] must be called to obtain the last line
+/// if the input does not end with a newline.
+///
+/// Lines are split exactly as [`split_lines`](crate::split_lines) does.
+#[derive(Debug)]
+pub struct LineSplitter {
+    /// The unconsumed input, starting at the beginning of a line.
+    buf: BytesMut,
+
+    /// The number of bytes in `buf` already scanned.
+    scanned: usize,
+    state: ScanState,
+
+    /// The byte offset of `buf` within the stream.
+    offset: usize,
+    /// The (1-based) physical line number at the start of `buf`.
+    line_number: usize,
+
+    finished: bool,
+}
+
+impl Default for LineSplitter {
+    fn default() -> Self {
+        Self {
+            buf: BytesMut::new(),
+            scanned: 0,
+            state: ScanState::default(),
+            offset: 0,
+            line_number: 1,
+            finished: false,
+        }
+    }
+}
+
+impl LineSplitter {
+    /// Append `chunk` to the input.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after [`LineSplitter::finish()`].
+    pub fn push(&mut self, chunk: impl Buf) {
+        assert!(!self.finished, "push after finish");
+        self.buf.put(chunk);
+    }
+
+    /// Mark the end of the input, causing any trailing data not terminated
+    /// by a newline to be returned by [`LineSplitter::next_line()`].
+    pub fn finish(&mut self) {
+        self.finished = true;
+    }
+
+    /// The number of buffered bytes that do not yet form a complete line.
+    pub fn buffered(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Return the next complete line, if any.
+    ///
+    /// Empty lines are returned, and parse to [`None`].
+    pub fn next_line(&mut self) -> Option<Line> {
+        let end = self.buf[self.scanned..]
+            .iter()
+            .position(|&c| self.state.advance(c))
+            .map(|i| self.scanned + i);
+
+        let (data, consumed) = match end {
+            Some(end) => {
+                let data = self.buf.split_to(end).freeze();
+                self.buf.advance(1);
+                (data, end + 1)
+            }
+            None if self.finished && !self.buf.is_empty() => {
+                let len = self.buf.len();
+                (self.buf.split().freeze(), len)
+            }
+            None => {
+                self.scanned = self.buf.len();
+                return None;
+            }
+        };
+
+        let line = Line {
+            data,
+            number: self.line_number,
+            offset: self.offset,
+        };
+
+        self.line_number += self.state.newlines + 1;
+        self.offset += consumed;
+        self.scanned = 0;
+        self.state = ScanState::default();
+
+        Some(line)
+    }
+}
+
+/// A single line of line protocol, split from a stream by a [`LineSplitter`].
+#[derive(Debug, Clone)]
+pub struct Line {
+    data: Bytes,
+    number: usize,
+    offset: usize,
+}
+
+impl Line {
+    /// The raw content of the line, excluding the terminating newline.
+    pub fn data(&self) -> &Bytes {
+        &self.data
+    }
+
+    /// The (1-based) physical line number at which this line starts.
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    /// The byte offset of the start of this line within the stream.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Parse the line, returning [`None`] if it is empty or a comment.
+    pub fn parse(&self) -> Option<Result<ParsedLine<'_>, LineParseError>> {
+        let s = match std::str::from_utf8(&self.data) {
+            Ok(s) => s,
+            Err(source) => {
+                return Some(Err(self.error(
+                    source.valid_up_to(),
+                    None,
+                    Error::InvalidUtf8 { source },
+                )))
+            }
+        };
+
+        parse_single_line(s).map(|res| {
+            res.map_err(|source| {
+                let (index, field) = locate_error(s);
+                self.error(index, field, source)
+            })
+        })
+    }
+
+    fn error(&self, index: usize, field: Option<String>, source: Error) -> LineParseError {
+        LineParseError {
+            line: self.number,
+            column: index + 1,
+            offset: self.offset + index,
+            field,
+            source,
+        }
+    }
+}
+
+/// An error parsing a [`Line`], locating the offending content within the
+/// stream.
+#[derive(Debug)]
+pub struct LineParseError {
+    line: usize,
+    column: usize,
+    offset: usize,
+    field: Option<String>,
+    source: Error,
+}
+
+impl LineParseError {
+    /// The (1-based) physical line number of the invalid line.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The (1-based) byte column within the line at which the offending
+    /// content starts.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// The byte offset of the offending content within the stream.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The key of the field containing the error, if the error is within a
+    /// field.
+    pub fn field(&self) -> Option<&str> {
+        self.field.as_deref()
+    }
+
+    /// The underlying parse error.
+    pub fn error(&self) -> &Error {
+        &self.source
+    }
+
+    /// Consume `self`, returning the underlying parse error.
+    pub fn into_error(self) -> Error {
+        self.source
+    }
+}
+
+impl fmt::Display for LineParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "error parsing line {}, column {} (byte offset {})",
+            self.line, self.column, self.offset
+        )?;
+        if let Some(field) = &self.field {
+            write!(f, ", field \"{field}\"")?;
+        }
+        write!(f, ": {}", self.source)
+    }
+}
+
+impl std::error::Error for LineParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_lines, split_lines};
+
+    /// Push `input` in chunks of `chunk_size` bytes, draining complete lines
+    /// after every chunk.
+    fn split_chunked(input: &str, chunk_size: usize) -> Vec<Line> {
+        let mut splitter = LineSplitter::default();
+        let mut lines = vec![];
+        for chunk in input.as_bytes().chunks(chunk_size) {
+            splitter.push(chunk);
+            lines.extend(std::iter::from_fn(|| splitter.next_line()));
+        }
+        splitter.finish();
+        lines.extend(std::iter::from_fn(|| splitter.next_line()));
+        assert_eq!(splitter.buffered(), 0);
+        lines
+    }
+
+    #[test]
+    fn splits_like_split_lines() {
+        let input = "\
+cpu,host=a usage=1 1
+# comment
+
+str v=\"quoted\nnewline\",x=1 2
+esc\\\n,t=1 v=\"\\\"\" 3
+unterminated f=1";
+
+        let want = split_lines(input)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+
+        for chunk_size in [1, 2, 3, 7, input.len()] {
+            let got = split_chunked(input, chunk_size);
+            let got = got
+                .iter()
+                .filter(|line| !line.data().is_empty())
+                .map(|line| std::str::from_utf8(line.data()).unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(got, want, "chunk size {chunk_size}");
+        }
+    }
+
+    #[test]
+    fn line_numbers_and_offsets() {
+        let input = "a v=1\nb v=\"x\ny\"\n\nc v=2\n";
+        let lines = split_chunked(input, 4);
+
+        let got = lines
+            .iter()
+            .map(|line| (line.number(), line.offset()))
+            .collect::<Vec<_>>();
+        assert_eq!(got, [(1, 0), (2, 6), (4, 16), (5, 17)]);
+
+        for line in &lines {
+            let start = line.offset();
+            assert_eq!(
+                &input.as_bytes()[start..start + line.data().len()],
+                &line.data()[..]
+            );
+        }
+    }
+
+    #[test]
+    fn parses_like_parse_lines() {
+        let input = "cpu,host=a usage=1i,idle=2 1\n\nmem free=3u 2\ndisk used=4,ok=t";
+        let want = parse_lines(input)
+            .map(|line| line.unwrap().to_string())
+            .collect::<Vec<_>>();
+
+        let lines = split_chunked(input, 5);
+        let got = lines
+            .iter()
+            .filter_map(|line| line.parse())
+            .map(|line| line.unwrap().to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(got, want);
+    }
+
+    #[test]
+    fn error_spans() {
+        let cases = [
+            // (input, Some((column, field)) if invalid)
+            ("cpu,host=a usage=1 1", None),
+            ("cpu, usage=1", Some((5, None))),
+            ("cpu,host=a usage=1.2.3 1", Some((18, Some("usage")))),
+            (
+                "cpu,host=a a=1,b=99999999999999999999i",
+                Some((18, Some("b"))),
+            ),
+            ("cpu,host=a a=1,b 1", Some((17, Some("b")))),
+            ("cpu,host=a a=1 1 trailing", Some((18, None))),
+            ("cpu,host=a a=1 nope", Some((16, None))),
+            ("cpu", Some((4, None))),
+        ];
+
+        for (input, want) in cases {
+            let mut splitter = LineSplitter::default();
+            splitter.push(&b"m v=1\n"[..]);
+            splitter.push(input.as_bytes());
+            splitter.finish();
+
+            splitter.next_line().unwrap().parse().unwrap().unwrap();
+            let line = splitter.next_line().unwrap();
+            let got = line.parse().unwrap().err();
+
+            match (got, want) {
+                (None, None) => {}
+                (Some(err), Some((column, field))) => {
+                    assert_eq!(err.line(), 2, "{input}");
+                    assert_eq!(err.column(), column, "{input}: {err}");
+                    assert_eq!(err.offset(), 6 + column - 1, "{input}");
+                    assert_eq!(err.field(), field, "{input}");
+                }
+                (got, want) => panic!("{input}: got {got:?}, want {want:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let mut splitter = LineSplitter::default();
+        splitter.push(&b"cpu v=\"\xff\" 1"[..]);
+        splitter.finish();
+
+        let err = splitter.next_line().unwrap().parse().unwrap().unwrap_err();
+        assert_eq!(err.line(), 1);
+        assert_eq!(err.column(), 8);
+        assert!(matches!(err.error(), Error::InvalidUtf8 { .. }));
+    }
+}
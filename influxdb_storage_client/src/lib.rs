@@ -305,6 +305,25 @@ impl Client {
         Ok(Self::collect_data(responses))
     }
 
+    /// Make a request to query::read_series_cardinality and do the
+    /// required async dance to flatten the resulting stream
+    pub async fn read_series_cardinality(
+        &mut self,
+        request: ReadSeriesCardinalityRequest,
+    ) -> Result<Vec<i64>, tonic::Status> {
+        let request = request.log_trace("read_series_cardinality request");
+        let responses: Vec<_> = self
+            .inner
+            .read_series_cardinality(request)
+            .await
+            .log_trace("read_series_cardinality response")?
+            .into_inner()
+            .try_collect()
+            .await?;
+
+        Ok(responses.into_iter().flat_map(|r| r.values).collect())
+    }
+
     /// Make a request to query::measurement_fields and do the
     /// required async dance to flatten the resulting stream to Strings
     pub async fn measurement_fields(
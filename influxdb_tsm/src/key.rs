@@ -10,6 +10,19 @@ pub struct ParsedTsmKey {
     pub field_key: String,
 }
 
+/// The measurement, tag set and field key of a TSM index key written by
+/// InfluxDB 1.x.
+///
+/// Unlike the keys written by InfluxDB 2.x (see [`ParsedTsmKey`]), these keys
+/// have no org and bucket ID prefix, and store the measurement name and
+/// tag set in the same form as line protocol.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParsedV1TsmKey {
+    pub measurement: String,
+    pub tagset: Vec<(String, String)>,
+    pub field_key: String,
+}
+
 /// Public error type that wraps the underlying data parsing error
 /// with the actual key value being parsed.
 #[derive(Debug, Snafu, PartialEq, Eq)]
@@ -171,6 +184,121 @@ fn parse_tsm_key_internal(key: &[u8]) -> Result<ParsedTsmKey, DataError> {
     })
 }
 
+/// The delimiter between the series key and the field key of a TSM key.
+const FIELD_KEY_DELIMITER: &[u8] = b"#!~#";
+
+/// Returns true if `key` was written by InfluxDB 2.x, in which case it should
+/// be parsed with [`parse_tsm_key`], and false if it was written by InfluxDB
+/// 1.x, in which case it should be parsed with [`parse_v1_tsm_key`].
+///
+/// InfluxDB 2.x keys store the measurement name as the value of the special
+/// tag key `\x00`, which cannot appear in an InfluxDB 1.x key.
+pub fn is_v2_tsm_key(key: &[u8]) -> bool {
+    key.windows(3).any(|w| w == b",\x00=")
+}
+
+/// Parses the measurement, field key and tag set from a TSM index key written
+/// by InfluxDB 1.x.
+///
+/// The format looks like:
+///
+/// ```text
+/// <measurement>,<tag_key>=<tag_value>,...#!~#<field_key>
+/// ```
+///
+/// For example:
+///
+/// ```text
+/// cpu,host=a,region=west#!~#usage_idle
+///
+///    measurement = "cpu"
+///    tags = [("host", "a"), ("region", "west")]
+///    field = "usage_idle"
+/// ```
+pub fn parse_v1_tsm_key(key: &[u8]) -> Result<ParsedV1TsmKey, Error> {
+    // Wrap in an internal function to translate error types and add key context
+    parse_v1_tsm_key_internal(key).context(ParsingTsmKeySnafu {
+        key: String::from_utf8_lossy(key),
+    })
+}
+
+fn parse_v1_tsm_key_internal(key: &[u8]) -> Result<ParsedV1TsmKey, DataError> {
+    let delimiter = key
+        .windows(FIELD_KEY_DELIMITER.len())
+        .position(|w| w == FIELD_KEY_DELIMITER)
+        .context(NoFieldKeySnafu)?;
+
+    // The field key following the delimiter is not escaped.
+    let field_key = &key[delimiter + FIELD_KEY_DELIMITER.len()..];
+    if field_key.is_empty() {
+        return ParsingFieldKeySnafu {
+            details: "field key too short",
+        }
+        .fail();
+    }
+
+    let mut parts = split_unescaped(&key[..delimiter], b',').into_iter();
+    let measurement = unescape(parts.next().unwrap_or_default());
+    if measurement.is_empty() {
+        return NoMeasurementSnafu.fail();
+    }
+
+    let tagset = parts
+        .map(|tag| match split_unescaped(tag, b'=').as_slice() {
+            [tag_key, tag_value] if !tag_key.is_empty() => {
+                Ok((unescape(tag_key), unescape(tag_value)))
+            }
+            _ => ParsingTsmTagKeySnafu {
+                description: format!("invalid tag '{}'", String::from_utf8_lossy(tag)),
+            }
+            .fail(),
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(ParsedV1TsmKey {
+        measurement,
+        tagset,
+        field_key: String::from_utf8_lossy(field_key).into_owned(),
+    })
+}
+
+/// Splits `data` on each occurrence of `delimiter` that is not escaped by a
+/// backslash.
+fn split_unescaped(data: &[u8], delimiter: u8) -> Vec<&[u8]> {
+    let mut parts = vec![];
+    let mut start = 0;
+    let mut escaped = false;
+
+    for (i, byte) in data.iter().enumerate() {
+        if escaped {
+            escaped = false;
+        } else if *byte == b'\\' {
+            escaped = true;
+        } else if *byte == delimiter {
+            parts.push(&data[start..i]);
+            start = i + 1;
+        }
+    }
+    parts.push(&data[start..]);
+
+    parts
+}
+
+/// Removes the backslashes escaping commas, spaces and equals signs in `data`.
+fn unescape(data: &[u8]) -> String {
+    let mut unescaped = Vec::with_capacity(data.len());
+    let mut iter = data.iter().copied().peekable();
+
+    while let Some(byte) = iter.next() {
+        if byte == b'\\' && matches!(iter.peek(), Some(b',' | b' ' | b'=')) {
+            continue;
+        }
+        unescaped.push(byte);
+    }
+
+    String::from_utf8_lossy(&unescaped).into_owned()
+}
+
 // Parses an influx id from the byte sequence. IDs are generally just 8 bytes, but we escape
 // certain characters ('\', ' ' and '='), so we unescape them as part of this process.
 // The iterator will consume all bytes that are part of the id.
@@ -885,6 +1013,61 @@ mod tests {
         assert_eq!(parsed_key.field_key, String::from("responseSize"));
     }
 
+    #[test]
+    fn test_is_v2_tsm_key() {
+        assert!(is_v2_tsm_key(
+            b"\x00\x00\x00\x00\x00\x00\x00\x01\x00\x00\x00\x00\x00\x00\x00\x02,\x00=cpu,\xff=usage#!~#usage"
+        ));
+        assert!(!is_v2_tsm_key(b"cpu,host=a#!~#usage"));
+    }
+
+    #[test]
+    fn test_parse_v1_tsm_key() {
+        let parsed_key = parse_v1_tsm_key(b"cpu,host=a,region=west#!~#usage_idle").unwrap();
+        assert_eq!(
+            parsed_key,
+            ParsedV1TsmKey {
+                measurement: "cpu".into(),
+                tagset: vec![
+                    ("host".into(), "a".into()),
+                    ("region".into(), "west".into())
+                ],
+                field_key: "usage_idle".into(),
+            }
+        );
+
+        // no tags
+        let parsed_key = parse_v1_tsm_key(b"cpu#!~#usage_idle").unwrap();
+        assert_eq!(parsed_key.measurement, "cpu");
+        assert!(parsed_key.tagset.is_empty());
+        assert_eq!(parsed_key.field_key, "usage_idle");
+
+        // escaped measurement and tags, and an unescaped field key
+        let parsed_key =
+            parse_v1_tsm_key(br"my\ cpu\,1,host\=name=a\ b\,c#!~#usage idle,total").unwrap();
+        assert_eq!(parsed_key.measurement, "my cpu,1");
+        assert_eq!(
+            parsed_key.tagset,
+            vec![("host=name".to_string(), "a b,c".to_string())]
+        );
+        assert_eq!(parsed_key.field_key, "usage idle,total");
+    }
+
+    #[test]
+    fn test_parse_v1_tsm_key_error() {
+        let err = parse_v1_tsm_key(b"cpu,host=a").unwrap_err();
+        assert!(err.to_string().contains("No field key"), "{err}");
+
+        let err = parse_v1_tsm_key(b"cpu,host=a#!~#").unwrap_err();
+        assert!(err.to_string().contains("field key too short"), "{err}");
+
+        let err = parse_v1_tsm_key(b",host=a#!~#usage").unwrap_err();
+        assert!(err.to_string().contains("No measurement"), "{err}");
+
+        let err = parse_v1_tsm_key(b"cpu,host#!~#usage").unwrap_err();
+        assert!(err.to_string().contains("invalid tag 'host'"), "{err}");
+    }
+
     fn do_test_parse_tsm_field_key_value_good(input: &str, expected_field_key: &str) {
         let mut iter = input.bytes();
         let result = parse_tsm_field_key_value(&mut iter);
@@ -23,7 +23,7 @@ use std::error;
 use std::fmt;
 use std::io;
 
-pub use key::ParsedTsmKey;
+pub use key::{ParsedTsmKey, ParsedV1TsmKey};
 
 #[derive(Clone, Debug, Copy, PartialEq, Eq)]
 pub enum BlockType {
@@ -225,6 +225,22 @@ impl IndexEntry {
             description: e.to_string(),
         })
     }
+
+    /// Returns true if this entry was written by InfluxDB 2.x, and its key
+    /// should be parsed with [`parse_key`](Self::parse_key), or false if it
+    /// was written by InfluxDB 1.x, and its key should be parsed with
+    /// [`parse_v1_key`](Self::parse_v1_key).
+    pub fn is_v2_key(&self) -> bool {
+        key::is_v2_tsm_key(&self.key)
+    }
+
+    /// Parse the key of an entry written by InfluxDB 1.x, which has no org
+    /// and bucket ID.
+    pub fn parse_v1_key(&self) -> Result<ParsedV1TsmKey, TsmError> {
+        key::parse_v1_tsm_key(&self.key).map_err(|e| TsmError {
+            description: e.to_string(),
+        })
+    }
 }
 
 /// A BlockDecoder is capable of decoding a block definition into block data
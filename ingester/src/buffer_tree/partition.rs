@@ -6,6 +6,7 @@ use data_types::{
     sequence_number_set::SequenceNumberSet, NamespaceId, PartitionKey, SequenceNumber,
     SortedColumnSet, TableId, TimestampMinMax, TransitionPartitionId,
 };
+use iox_time::{SystemProvider, Time, TimeProvider};
 use mutable_batch::MutableBatch;
 use observability_deps::tracing::*;
 use schema::{merge::SchemaMerger, sort::SortKey, Schema};
@@ -113,6 +114,16 @@ pub struct PartitionData {
     /// persisting with a unique, opaque identifier.
     persisting: PersistingList,
 
+    /// The wall-clock time of the first write buffered in `buffer`, if any.
+    buffer_first_write_at: Option<Time>,
+
+    /// The wall-clock time of the most recent write buffered in `buffer`, if
+    /// any.
+    buffer_last_write_at: Option<Time>,
+
+    /// The wall-clock time of the first write of each batch in `persisting`.
+    persisting_first_write_at: Vec<(BatchIdent, Time)>,
+
     /// The number of persist operations started over the lifetime of this
     /// [`PartitionData`].
     started_persistence_count: BatchIdent,
@@ -154,6 +165,9 @@ impl PartitionData {
             table,
             buffer: DataBuffer::default(),
             persisting: PersistingList::default(),
+            buffer_first_write_at: None,
+            buffer_last_write_at: None,
+            persisting_first_write_at: Vec::new(),
             started_persistence_count: BatchIdent::default(),
             completed_persistence_count: 0,
             partition_counter,
@@ -183,6 +197,9 @@ impl PartitionData {
 
         // Buffer the write.
         self.buffer.buffer_write(mb, sequence_number)?;
+        let now = SystemProvider::new().now();
+        self.buffer_first_write_at.get_or_insert(now);
+        self.buffer_last_write_at = Some(now);
 
         // Invariant: if the partition contains a buffered write, it must report
         // non-empty.
@@ -262,6 +279,28 @@ impl PartitionData {
             })
     }
 
+    /// Return the wall-clock time of the oldest write currently buffered or
+    /// persisting in this [`PartitionData`].
+    ///
+    /// Deletes requested after this point in time may apply to (some of) the
+    /// data in this partition.
+    pub(crate) fn oldest_write_at(&self) -> Option<Time> {
+        self.persisting_first_write_at
+            .iter()
+            .map(|(_, t)| *t)
+            .chain(self.buffer_first_write_at)
+            .min()
+    }
+
+    /// Return the wall-clock time of the most recent write buffered in this
+    /// [`PartitionData`] that is not yet persisting.
+    ///
+    /// Returns [`None`] if there is no buffered data that can be marked as
+    /// persisting.
+    pub(crate) fn last_write_at(&self) -> Option<Time> {
+        self.buffer_last_write_at
+    }
+
     /// Return the schema of the data currently buffered within this
     /// [`PartitionData`].
     ///
@@ -376,6 +415,13 @@ impl PartitionData {
         // From this point on, all code MUST be infallible or the buffered data
         // contained within persisting may be dropped.
 
+        // A non-empty buffer always has a first write time.
+        let first_write_at = self
+            .buffer_first_write_at
+            .take()
+            .expect("non-empty buffer must have a first write time");
+        self.buffer_last_write_at = None;
+
         // Invariant: the non-empty partition counter is always >0 at this
         // point because this partition is non-empty.
         assert!(self.partition_counter.read() > 0);
@@ -403,11 +449,14 @@ impl PartitionData {
                 fsm.get_query_data(&OwnedProjection::default()),
             ),
             batch_ident,
+            first_write_at,
         );
 
         // Push the buffer into the persisting list (which maintains batch
         // order).
         self.persisting.push(batch_ident, fsm);
+        self.persisting_first_write_at
+            .push((batch_ident, first_write_at));
 
         // Invariant: the partition must not be marked as empty when there's an
         // entry in the persisting list.
@@ -434,6 +483,8 @@ impl PartitionData {
         debug_assert!(!self.is_empty());
 
         let fsm = self.persisting.remove(batch.batch_ident());
+        self.persisting_first_write_at
+            .retain(|(ident, _)| *ident != batch.batch_ident());
 
         self.completed_persistence_count += 1;
 
@@ -1251,6 +1302,30 @@ mod tests {
         assert!(p.mark_persisting().is_none());
     }
 
+    #[tokio::test]
+    async fn test_last_write_at() {
+        let mut p = PartitionDataBuilder::new().build();
+        assert!(p.last_write_at().is_none());
+
+        let mb = lp_to_mutable_batch(r#"bananas,city=London people=2,pigeons="millions" 10"#).1;
+        p.buffer_write(mb, SequenceNumber::new(1))
+            .expect("write should succeed");
+        let first = p.last_write_at().expect("write must be recorded");
+        assert_eq!(p.oldest_write_at(), Some(first));
+
+        let mb = lp_to_mutable_batch(r#"bananas,city=Madrid people=4,pigeons="none" 20"#).1;
+        p.buffer_write(mb, SequenceNumber::new(2))
+            .expect("write should succeed");
+        let second = p.last_write_at().expect("write must be recorded");
+        assert!(second >= first);
+        assert_eq!(p.oldest_write_at(), Some(first));
+
+        // Persisting data is not considered for idleness.
+        let _data = p.mark_persisting().expect("must contain data");
+        assert!(p.last_write_at().is_none());
+        assert_eq!(p.oldest_write_at(), Some(first));
+    }
+
     // Ensure an empty PartitionData does not panic due to constructing an empty
     // QueryAdaptor.
     #[tokio::test]
@@ -1,5 +1,7 @@
 use std::fmt::Display;
 
+use iox_time::Time;
+
 use crate::query_adaptor::QueryAdaptor;
 
 /// An opaque, monotonic generational identifier of a buffer in a
@@ -45,17 +47,28 @@ impl Display for BatchIdent {
 pub struct PersistingData {
     data: QueryAdaptor,
     batch_ident: BatchIdent,
+    first_write_at: Time,
 }
 
 impl PersistingData {
-    pub(super) fn new(data: QueryAdaptor, batch_ident: BatchIdent) -> Self {
-        Self { data, batch_ident }
+    pub(super) fn new(data: QueryAdaptor, batch_ident: BatchIdent, first_write_at: Time) -> Self {
+        Self {
+            data,
+            batch_ident,
+            first_write_at,
+        }
     }
 
     pub(super) fn batch_ident(&self) -> BatchIdent {
         self.batch_ident
     }
 
+    /// The wall-clock time at which the first write in this batch was
+    /// buffered.
+    pub(crate) fn first_write_at(&self) -> Time {
+        self.first_write_at
+    }
+
     pub(crate) fn query_adaptor(&self) -> QueryAdaptor {
         self.data.clone()
     }
@@ -215,13 +215,14 @@ where
         let partitions = self.partitions().into_iter().filter_map(move |p| {
             let mut span = span.child("partition read");
 
-            let (id, completed_persistence_count, data, partition_key) = {
+            let (id, completed_persistence_count, data, partition_key, oldest_write_at) = {
                 let mut p = p.lock();
                 (
                     p.partition_id().clone(),
                     p.completed_persistence_count(),
                     p.get_query_data(&projection),
                     p.partition_key().clone(),
+                    p.oldest_write_at(),
                 )
             };
 
@@ -261,6 +262,7 @@ where
                         id,
                         completed_persistence_count,
                     )
+                    .with_oldest_write_at(oldest_write_at)
                 }
                 None => PartitionResponse::new(vec![], id, completed_persistence_count),
             };
@@ -341,6 +343,10 @@ fn keep_after_pruning_partition_key(
                             max_value,
                         }
                     }
+                    ColumnValue::Bucket { .. } => {
+                        // A bucket cannot be expressed as a value range.
+                        return None;
+                    }
                     ColumnValue::Datetime { .. } => {
                         // not yet supported
                         return None;
@@ -0,0 +1,148 @@
+//! Conversion of local wall clock times to the catalog clock.
+
+use std::{sync::Arc, time::Duration};
+
+use backoff::{Backoff, BackoffConfig};
+use iox_catalog::interface::Catalog;
+use iox_time::{SystemProvider, Time, TimeProvider};
+use parking_lot::Mutex;
+use tokio::time::Instant;
+
+/// The duration for which a measured [`CatalogClockOffset`] is reused before
+/// it is measured again.
+///
+/// This bounds the time until a step of either clock is taken into account.
+const OFFSET_TTL: Duration = Duration::from_secs(60);
+
+/// The measured difference between the catalog clock and the local wall
+/// clock.
+///
+/// Tombstone creation times are assigned by the catalog, so any local time
+/// compared against them (such as the time of the first write buffered for a
+/// partition) must first be converted to the catalog clock - the local clock
+/// of the ingester may be skewed relative to the catalog.
+///
+/// The offset is a lower bound: the network round trip to read the catalog
+/// clock causes converted times to be early, never late. Data is therefore
+/// never considered to be written after a delete that was requested after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CatalogClockOffset(i64);
+
+impl CatalogClockOffset {
+    /// Measure the offset by reading the catalog clock.
+    ///
+    /// This call retries until it completes.
+    pub(crate) async fn fetch(catalog: &dyn Catalog, backoff_config: &BackoffConfig) -> Self {
+        let catalog_now = Backoff::new(backoff_config)
+            .retry_all_errors("read catalog time", || async {
+                catalog
+                    .repositories()
+                    .await
+                    .tombstones()
+                    .catalog_time()
+                    .await
+            })
+            .await
+            .expect("retry forever");
+
+        // Read the local clock AFTER the catalog clock, so that the offset
+        // never overestimates the catalog time of a local time.
+        let local_now = SystemProvider::new().now();
+
+        Self(catalog_now.get() - local_now.timestamp_nanos())
+    }
+
+    /// Convert the local time `t` to the catalog clock.
+    pub(crate) fn to_catalog_time(&self, t: Time) -> Time {
+        Time::from_timestamp_nanos(t.timestamp_nanos() + self.0)
+    }
+}
+
+/// A [`CatalogClockOffset`] that is measured at most once per [`OFFSET_TTL`],
+/// so that converting local times does not cost a catalog round trip each
+/// time.
+#[derive(Debug)]
+pub(crate) struct CatalogClock {
+    catalog: Arc<dyn Catalog>,
+    backoff_config: BackoffConfig,
+    offset: Mutex<Option<(Instant, CatalogClockOffset)>>,
+}
+
+impl CatalogClock {
+    pub(crate) fn new(catalog: Arc<dyn Catalog>) -> Self {
+        Self {
+            catalog,
+            backoff_config: BackoffConfig::default(),
+            offset: Default::default(),
+        }
+    }
+
+    /// Return the (possibly cached) offset of the catalog clock.
+    ///
+    /// This call retries until it completes.
+    pub(crate) async fn offset(&self) -> CatalogClockOffset {
+        if let Some((measured_at, offset)) = *self.offset.lock() {
+            if measured_at.elapsed() < OFFSET_TTL {
+                return offset;
+            }
+        }
+
+        let measured_at = Instant::now();
+        let offset = CatalogClockOffset::fetch(&*self.catalog, &self.backoff_config).await;
+        *self.offset.lock() = Some((measured_at, offset));
+
+        offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iox_catalog::mem::MemCatalog;
+    use metric::{Attributes, DurationHistogram, Metric};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_offset() {
+        let metrics = Arc::new(metric::Registry::default());
+        let catalog = MemCatalog::new(metrics);
+
+        let before = SystemProvider::new().now();
+        let offset = CatalogClockOffset::fetch(&catalog, &Default::default()).await;
+
+        // The in-memory catalog shares the local clock, and the catalog clock
+        // is read before the local clock, so the offset is never positive.
+        assert!(offset.0 <= 0);
+        let converted = offset.to_catalog_time(before);
+        assert!(converted <= before);
+        assert!(converted > before - Duration::from_secs(60));
+    }
+    #[tokio::test(start_paused = true)]
+    async fn test_clock_reuses_offset() {
+        let metrics = Arc::new(metric::Registry::default());
+        let catalog = Arc::new(MemCatalog::new(Arc::clone(&metrics)));
+        let clock = CatalogClock::new(catalog);
+
+        clock.offset().await;
+        clock.offset().await;
+        assert_catalog_time_reads(&metrics, 1);
+
+        tokio::time::advance(OFFSET_TTL).await;
+        clock.offset().await;
+        assert_catalog_time_reads(&metrics, 2);
+    }
+
+    #[track_caller]
+    fn assert_catalog_time_reads(metrics: &metric::Registry, n: u64) {
+        let histogram = metrics
+            .get_instrument::<Metric<DurationHistogram>>("catalog_op_duration")
+            .expect("failed to read metric")
+            .get_observer(&Attributes::from(&[
+                ("op", "tombstone_catalog_time"),
+                ("result", "success"),
+            ]))
+            .expect("failed to get observer")
+            .fetch();
+        assert_eq!(histogram.sample_count(), n);
+    }
+}
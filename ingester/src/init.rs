@@ -28,7 +28,7 @@ use thiserror::Error;
 use tokio::sync::oneshot;
 use tokio_util::sync::CancellationToken;
 use tracker::DiskSpaceMetrics;
-use wal::Wal;
+use wal::{SegmentId, Wal};
 
 use crate::{
     buffer_tree::{
@@ -45,19 +45,21 @@ use crate::{
     ingest_state::IngestState,
     ingester_id::IngesterId,
     persist::{
-        column_map_resolver::CatalogColumnMapResolver, completion_observer::MaybeLayer,
-        file_metrics::ParquetFileInstrumentation, handle::PersistHandle,
-        hot_partitions::HotPartitionPersister,
+        cold_partitions::ColdPartitionPersister, column_map_resolver::CatalogColumnMapResolver,
+        completion_observer::MaybeLayer, file_metrics::ParquetFileInstrumentation,
+        handle::PersistHandle, hot_partitions::HotPartitionPersister,
     },
     query::{
         exec_instrumentation::QueryExecInstrumentation,
-        result_instrumentation::QueryResultInstrumentation, tracing::QueryExecTracing,
+        result_instrumentation::QueryResultInstrumentation, tombstone_filter::QueryTombstoneFilter,
+        tracing::QueryExecTracing,
     },
     server::grpc::GrpcDelegate,
     timestamp_oracle::TimestampOracle,
     wal::{
         disk_full_protection::{self, guard_disk_capacity},
         reference_tracker::WalReferenceHandle,
+        replication::{ReplicatedWal, WalReplicator},
         rotate_task::periodic_rotation,
         wal_sink::WalSink,
     },
@@ -118,6 +120,11 @@ pub struct IngesterGuard<T> {
     /// Aborted on drop.
     disk_metric_task: tokio::task::JoinHandle<()>,
 
+    /// The handle of the periodic cold partition persistence task, if enabled.
+    ///
+    /// Aborted on drop.
+    cold_partition_task: Option<tokio::task::JoinHandle<()>>,
+
     /// The task handle executing the graceful shutdown once triggered.
     graceful_shutdown_handler: tokio::task::JoinHandle<()>,
     shutdown_complete: Shared<oneshot::Receiver<()>>,
@@ -145,6 +152,9 @@ impl<T> Drop for IngesterGuard<T> {
     fn drop(&mut self) {
         self.rotation_task.abort();
         self.disk_metric_task.abort();
+        if let Some(task) = &self.cold_partition_task {
+            task.abort();
+        }
         self.graceful_shutdown_handler.abort();
     }
 }
@@ -210,6 +220,12 @@ pub enum InitError {
 ///
 /// Any error during replay is fatal.
 ///
+/// If `wal_replication_prefix` is set, closed WAL segment files are uploaded to
+/// the object store under this prefix, and deleted once all the data within
+/// them has been persisted. If `wal_directory` is empty at startup, the
+/// replicated segment files are downloaded and replayed. The prefix MUST be
+/// unique to each ingester instance.
+///
 /// ## Graceful Shutdown
 ///
 /// When `shutdown` completes, the ingester blocks ingest (returning an error to
@@ -266,6 +282,20 @@ pub enum InitError {
 /// Decreasing this value increases the frequency of persist operations, and
 /// usually decreases the size of the resulting parquet files.
 ///
+/// ## Cold Persistence
+///
+/// Partitions that never become "hot" are persisted at the latest when the WAL
+/// is rotated. Two optional mechanisms persist them earlier:
+///
+/// If `persist_idle_partition_threshold` is set, partitions that have not
+/// received a write for at least that duration are enqueued for persistence.
+///
+/// If `persist_buffer_watermark` is set and the sum of the persist cost
+/// estimates of all buffered partitions exceeds it, the partitions with the
+/// largest cost estimate are enqueued for persistence until the total drops
+/// below the watermark. This bounds the memory used by workloads that write to
+/// many small partitions, none of which ever become "hot".
+///
 /// [`MutableBatch::size_data()`]: mutable_batch::MutableBatch::size_data
 #[allow(clippy::too_many_arguments)]
 pub async fn new<F>(
@@ -274,10 +304,13 @@ pub async fn new<F>(
     persist_background_fetch_time: Duration,
     wal_directory: PathBuf,
     wal_rotation_period: Duration,
+    wal_replication_prefix: Option<String>,
     persist_executor: Arc<Executor>,
     persist_workers: usize,
     persist_queue_depth: usize,
     persist_hot_partition_cost: usize,
+    persist_idle_partition_threshold: Option<Duration>,
+    persist_buffer_watermark: Option<usize>,
     object_store: ParquetStorage,
     gossip: GossipConfig,
     max_partitions_per_namespace: NonZeroUsize,
@@ -356,17 +389,37 @@ where
     // write path.
     let ingest_state = Arc::new(IngestState::default());
 
+    // Optionally replicate closed WAL segments to object storage, restoring
+    // them if the WAL directory was lost.
+    let wal_replicator = wal_replication_prefix.map(|prefix| {
+        WalReplicator::new(Arc::clone(object_store.object_store()), &prefix, &metrics)
+    });
+    let mut min_segment_id = SegmentId::new(0);
+    if let Some(replicator) = &wal_replicator {
+        wal_replay::restore(&wal_directory, replicator)
+            .await
+            .map_err(|e| InitError::WalReplay(e.into()))?;
+
+        // Number new segment files after all replicated ones, so that their
+        // upload never replaces a replicated segment.
+        min_segment_id = replicator
+            .next_segment_id()
+            .await
+            .map_err(|e| InitError::WalReplay(e.into()))?;
+    }
+
     // Initialise the WAL
-    let wal = Wal::new(wal_directory.clone())
+    let wal = Wal::new_with_min_segment_id(wal_directory.clone(), min_segment_id)
         .await
         .map_err(InitError::WalInit)?;
+    let replicated_wal = ReplicatedWal::new(Arc::clone(&wal), wal_replicator.clone());
 
     // Start defining the chain of persist completion observers so it can be
     // layered in gossip handlers if needed.
     //
     // Prepare the WAL segment reference tracker
     let (wal_reference_handle, wal_reference_actor) =
-        WalReferenceHandle::new(Arc::clone(&wal), &metrics);
+        WalReferenceHandle::new(replicated_wal.clone(), &metrics);
     // Add file metric instrumentation.
     let persist_observer = ParquetFileInstrumentation::new(wal_reference_handle.clone(), &metrics);
 
@@ -466,7 +519,7 @@ where
 
     // Replay the WAL log files, if any.
     let max_sequence_number = wal_replay::replay(
-        &wal,
+        &replicated_wal,
         &buffer,
         Arc::clone(&persist_handle),
         Arc::clone(&ingest_state),
@@ -497,7 +550,8 @@ where
     );
 
     // And the chain of QueryExec that forms the read path.
-    let read_path = QueryResultInstrumentation::new(Arc::clone(&buffer), &metrics);
+    let read_path = QueryTombstoneFilter::new(Arc::clone(&buffer), Arc::clone(&catalog));
+    let read_path = QueryResultInstrumentation::new(read_path, &metrics);
     let read_path = QueryExecInstrumentation::new(
         "buffer",
         QueryExecTracing::new(read_path, "buffer"),
@@ -511,8 +565,23 @@ where
         wal_reference_handle.clone(),
         Arc::clone(&buffer),
         Arc::clone(&persist_handle),
+        wal_replicator,
     ));
 
+    // Spawn a background task to periodically persist idle partitions, and
+    // partitions that push the buffer over the watermark.
+    let cold_partition_task = (persist_idle_partition_threshold.is_some()
+        || persist_buffer_watermark.is_some())
+    .then(|| {
+        let cold_partition_persister = ColdPartitionPersister::new(
+            Arc::clone(&persist_handle),
+            persist_idle_partition_threshold,
+            persist_buffer_watermark,
+            &metrics,
+        );
+        tokio::spawn(cold_partition_persister.run(Arc::clone(&buffer)))
+    });
+
     // Restore the highest sequence number from the WAL files, and default to 0
     // if there were no files to replay.
     //
@@ -548,6 +617,7 @@ where
         ),
         rotation_task,
         disk_metric_task,
+        cold_partition_task,
         graceful_shutdown_handler: shutdown_task,
         shutdown_complete: shutdown_rx.shared(),
     })
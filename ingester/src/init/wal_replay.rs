@@ -1,4 +1,5 @@
 use std::fmt::Debug;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -18,6 +19,7 @@ use crate::{
     ingest_state::{IngestState, IngestStateError},
     partition_iter::PartitionIter,
     persist::{drain_buffer::persist_partitions, queue::PersistQueue},
+    wal::replication::{ReplicatedWal, WalReplicationError, WalReplicator},
 };
 
 /// This duration controls how long to wait between reads of the ingest state
@@ -46,6 +48,10 @@ pub enum WalReplayError {
     /// [`BufferTree`]: crate::buffer_tree::BufferTree
     #[error("failed to apply op: {0}")]
     Apply(#[from] DmlError),
+
+    /// An error restoring replicated WAL segment files from object storage.
+    #[error("failed to restore replicated wal segments: {0}")]
+    Restore(#[from] WalReplicationError),
 }
 
 /// A type that can list, read & delete closed WAL segment files. This abstracts
@@ -85,6 +91,47 @@ impl WalReader for Arc<wal::Wal> {
     }
 }
 
+#[async_trait]
+impl<W> WalReader for ReplicatedWal<W>
+where
+    W: WalReader,
+{
+    type SegmentReader = W::SegmentReader;
+
+    fn reader_for_closed_segment(&self, id: SegmentId) -> Result<Self::SegmentReader, wal::Error> {
+        self.inner().reader_for_closed_segment(id)
+    }
+
+    fn closed_segments(&self) -> Vec<(SegmentId, u64)> {
+        self.inner().closed_segments()
+    }
+
+    async fn delete(&self, id: SegmentId) -> Result<(), wal::Error> {
+        self.inner().delete(id).await?;
+        if let Some(replicator) = self.replicator() {
+            replicator.delete(id).await;
+        }
+        Ok(())
+    }
+}
+
+/// Download the replicated WAL segment files into `wal_directory` if it
+/// contains no segment files (i.e. because the local volume was lost),
+/// returning the number of segment files restored.
+///
+/// The restored files are replayed by [`replay()`] as if they had never left
+/// the local disk.
+pub(crate) async fn restore(
+    wal_directory: &Path,
+    replicator: &WalReplicator,
+) -> Result<usize, WalReplayError> {
+    let n = replicator.restore(wal_directory).await?;
+    if n > 0 {
+        info!(n_files = n, "restored replicated wal segments");
+    }
+    Ok(n)
+}
+
 /// A trait to associate a [`SegmentId`] with a WAL op batch reader
 pub trait SegmentedWalOpBatchReader:
     Iterator<Item = Result<Vec<SequencedWalOp>, wal::Error>> + Send
@@ -1002,4 +1049,89 @@ mod tests {
         );
         assert_eq!(mock_sink.get_calls().len(), 2);
     }
+
+    #[tokio::test]
+    async fn test_restore_only_without_segment_files() {
+        let metrics = metric::Registry::default();
+        let store: Arc<object_store::DynObjectStore> =
+            Arc::new(object_store::memory::InMemory::new());
+        let replicator = WalReplicator::new(Arc::clone(&store), "wal", &metrics);
+
+        // Replicate a closed segment.
+        let dir = test_helpers::tmp_dir().unwrap();
+        let wal = Wal::new(dir.path()).await.unwrap();
+        let (segment, _) = wal.rotate().unwrap();
+        replicator.upload(&segment).await;
+
+        // The original WAL directory contains segment files, so nothing is
+        // restored.
+        assert_eq!(restore(dir.path(), &replicator).await.unwrap(), 0);
+
+        // A missing WAL directory is restored.
+        let empty = test_helpers::tmp_dir().unwrap();
+        let missing = empty.path().join("wal");
+        assert_eq!(restore(&missing, &replicator).await.unwrap(), 1);
+
+        let restored = Wal::new(&missing).await.unwrap();
+        assert_eq!(
+            WalReader::closed_segments(&restored),
+            [(segment.id(), segment.size())]
+        );
+
+        // Other files and directories in the WAL directory do not prevent a
+        // restore.
+        let other = test_helpers::tmp_dir().unwrap();
+        std::fs::create_dir(other.path().join("lost+found")).unwrap();
+        std::fs::write(other.path().join("README"), "bananas").unwrap();
+        assert_eq!(restore(other.path(), &replicator).await.unwrap(), 1);
+        assert!(other.path().join(format!("{}.dat", segment.id())).exists());
+    }
+
+    #[tokio::test]
+    async fn test_restore_interrupted() {
+        let metrics = metric::Registry::default();
+        let store: Arc<object_store::DynObjectStore> =
+            Arc::new(object_store::memory::InMemory::new());
+        let replicator = WalReplicator::new(Arc::clone(&store), "wal", &metrics);
+
+        let dir = test_helpers::tmp_dir().unwrap();
+        let wal = Wal::new(dir.path()).await.unwrap();
+        let (first, _) = wal.rotate().unwrap();
+        let (second, _) = wal.rotate().unwrap();
+        replicator.upload(&first).await;
+        replicator.upload(&second).await;
+
+        // A restore interrupted while downloading leaves a truncated file in
+        // the partial download directory, which is discarded.
+        let target = test_helpers::tmp_dir().unwrap();
+        let partial = target.path().join("restore.partial");
+        std::fs::create_dir(&partial).unwrap();
+        std::fs::write(partial.join(format!("{}.dat", first.id())), "trunc").unwrap();
+        assert_eq!(restore(target.path(), &replicator).await.unwrap(), 2);
+        assert!(!partial.exists());
+        assert_eq!(
+            std::fs::read(target.path().join(format!("{}.dat", first.id()))).unwrap(),
+            std::fs::read(first.path()).unwrap(),
+        );
+
+        // A restore interrupted while moving the downloaded files is
+        // completed, even though segment files are present.
+        let target = test_helpers::tmp_dir().unwrap();
+        let staged = target.path().join("restore.staged");
+        std::fs::create_dir(&staged).unwrap();
+        std::fs::copy(
+            first.path(),
+            target.path().join(format!("{}.dat", first.id())),
+        )
+        .unwrap();
+        std::fs::copy(second.path(), staged.join(format!("{}.dat", second.id()))).unwrap();
+        assert_eq!(restore(target.path(), &replicator).await.unwrap(), 1);
+        assert!(!staged.exists());
+
+        let restored = Wal::new(target.path()).await.unwrap();
+        assert_eq!(
+            WalReader::closed_segments(&restored),
+            [(first.id(), first.size()), (second.id(), second.size())]
+        );
+    }
 }
@@ -246,6 +246,7 @@ pub mod internal_implementation_details {
 mod arcmap;
 mod buffer_tree;
 mod cancellation_safe;
+mod catalog_clock;
 mod deferred_load;
 mod dml_payload;
 mod dml_sink;
@@ -0,0 +1,389 @@
+use std::{cmp::Reverse, fmt::Debug, sync::Arc, time::Duration};
+
+use iox_time::{SystemProvider, Time, TimeProvider};
+use observability_deps::tracing::{debug, info};
+use parking_lot::Mutex;
+
+use crate::{buffer_tree::partition::PartitionData, partition_iter::PartitionIter};
+
+use super::queue::PersistQueue;
+
+/// The interval at which the buffered partitions are evaluated by
+/// [`ColdPartitionPersister::run()`].
+pub(crate) const COLD_PARTITION_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The reason a partition was enqueued for persistence by the
+/// [`ColdPartitionPersister`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Reason {
+    /// The partition has not been written to for at least the configured idle
+    /// duration.
+    Idle,
+    /// The total buffered data exceeds the configured watermark, and this
+    /// partition is one of the largest.
+    Watermark,
+}
+
+/// Periodically persists partitions that are not "hot" enough to be persisted
+/// by the [`HotPartitionPersister`], but that should not wait for the next WAL
+/// rotation either.
+///
+/// Two (independently optional) conditions cause a partition to be enqueued for
+/// persistence:
+///
+///   * The partition has not received a write for at least
+///     `idle_threshold`.
+///
+///   * The sum of the persist cost estimates of all partitions in the buffer
+///     exceeds `buffer_watermark`. The largest partitions are persisted first,
+///     until the (estimated) buffered total drops back below the watermark.
+///
+/// Idle partitions are persisted first and count towards reducing the buffered
+/// total.
+///
+/// [`HotPartitionPersister`]: super::hot_partitions::HotPartitionPersister
+#[derive(Debug)]
+pub(crate) struct ColdPartitionPersister<P> {
+    persist_handle: P,
+    idle_threshold: Option<Duration>,
+    buffer_watermark: Option<usize>,
+
+    /// The sum of the persist cost estimates of all partitions, as observed
+    /// during the most recent evaluation.
+    buffered_cost: metric::U64Gauge,
+
+    /// Metrics tracking the number of partitions persisted because they were
+    /// idle, or to reduce the buffered data below the watermark.
+    idle_count: metric::U64Counter,
+    watermark_count: metric::U64Counter,
+}
+
+impl<P> ColdPartitionPersister<P>
+where
+    P: PersistQueue + Clone + Sync + 'static,
+{
+    pub(crate) fn new(
+        persist_handle: P,
+        idle_threshold: Option<Duration>,
+        buffer_watermark: Option<usize>,
+        metrics: &metric::Registry,
+    ) -> Self {
+        let buffered_cost = metrics
+            .register_metric::<metric::U64Gauge>(
+                "ingester_buffered_persist_cost",
+                "sum of the estimated persist cost of all data buffered in the \
+                ingester that is not yet persisting",
+            )
+            .recorder(&[]);
+        let enqueue_count = metrics.register_metric::<metric::U64Counter>(
+            "ingester_persist_cold_partition_enqueue_count",
+            "number of times persistence of a partition has been triggered \
+            because it was idle, or to keep the buffered data below the \
+            pre-configured watermark",
+        );
+        Self {
+            persist_handle,
+            idle_threshold,
+            buffer_watermark,
+            buffered_cost,
+            idle_count: enqueue_count.recorder(&[("reason", "idle")]),
+            watermark_count: enqueue_count.recorder(&[("reason", "watermark")]),
+        }
+    }
+
+    /// Evaluate the partitions in `buffer` every
+    /// [`COLD_PARTITION_CHECK_INTERVAL`], enqueuing cold partitions for
+    /// persistence.
+    pub(crate) async fn run<T>(self, buffer: T)
+    where
+        T: PartitionIter + Sync + 'static,
+    {
+        let time_provider = SystemProvider::new();
+        let mut interval = tokio::time::interval(COLD_PARTITION_CHECK_INTERVAL);
+
+        loop {
+            interval.tick().await;
+            self.persist_cold(buffer.partition_iter(), time_provider.now());
+        }
+    }
+
+    /// Enqueue all partitions in `partitions` for persistence that are idle at
+    /// `now`, and as many of the largest partitions as necessary to bring the
+    /// buffered total below the watermark.
+    ///
+    /// This call does not wait for the persist jobs to be enqueued or
+    /// completed.
+    fn persist_cold<T>(&self, partitions: T, now: Time)
+    where
+        T: Iterator<Item = Arc<Mutex<PartitionData>>>,
+    {
+        let mut total_cost = 0_usize;
+        let mut idle = vec![];
+        let mut active = vec![];
+
+        for p in partitions {
+            let (last_write_at, cost) = {
+                let guard = p.lock();
+                (guard.last_write_at(), guard.persist_cost_estimate())
+            };
+
+            // Skip partitions that have no data that can be persisted.
+            let Some(last_write_at) = last_write_at else {
+                continue;
+            };
+            total_cost += cost;
+
+            let is_idle = self.idle_threshold.is_some_and(|threshold| {
+                now.checked_duration_since(last_write_at)
+                    .is_some_and(|d| d >= threshold)
+            });
+            if is_idle {
+                idle.push((p, cost));
+            } else {
+                active.push((p, cost));
+            }
+        }
+
+        self.buffered_cost.set(total_cost as u64);
+
+        for (p, cost) in idle {
+            if self.persist(p, cost, Reason::Idle) {
+                total_cost = total_cost.saturating_sub(cost);
+            }
+        }
+
+        let Some(watermark) = self.buffer_watermark else {
+            return;
+        };
+        if total_cost <= watermark {
+            return;
+        }
+
+        debug!(
+            total_cost,
+            watermark, "buffered data exceeds watermark, persisting largest partitions"
+        );
+
+        active.sort_unstable_by_key(|(_, cost)| Reverse(*cost));
+        for (p, cost) in active {
+            if total_cost <= watermark {
+                break;
+            }
+            if self.persist(p, cost, Reason::Watermark) {
+                total_cost = total_cost.saturating_sub(cost);
+            }
+        }
+    }
+
+    /// Mark `partition` as persisting and enqueue it for persistence, returning
+    /// false if it no longer contains data that can be persisted.
+    fn persist(&self, partition: Arc<Mutex<PartitionData>>, cost: usize, reason: Reason) -> bool {
+        // The partition may have been persisted concurrently since it was
+        // evaluated.
+        let Some(data) = partition.lock().mark_persisting() else {
+            return false;
+        };
+
+        info!(
+            partition_id = %data.partition_id(),
+            cost_estimate = cost,
+            ?reason,
+            "marking cold partition for persistence"
+        );
+
+        // Perform the enqueue in a separate task, to avoid blocking the
+        // evaluation of the remaining partitions if the persist system is
+        // saturated.
+        let persist_handle = self.persist_handle.clone();
+        tokio::spawn(async move {
+            // There is no need to await on the completion handle.
+            persist_handle.enqueue(partition, data).await;
+        });
+
+        match reason {
+            Reason::Idle => self.idle_count.inc(1),
+            Reason::Watermark => self.watermark_count.inc(1),
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use data_types::SequenceNumber;
+    use metric::Attributes;
+    use mutable_batch_lp::test_helpers::lp_to_mutable_batch;
+    use parking_lot::Mutex;
+
+    use crate::{
+        persist::queue::mock::MockPersistQueue,
+        test_util::{PartitionDataBuilder, ARBITRARY_TABLE_NAME},
+    };
+
+    use super::*;
+
+    const IDLE: Duration = Duration::from_secs(60);
+
+    /// Build a partition containing `n` rows.
+    fn partition_with_rows(n: usize) -> Arc<Mutex<PartitionData>> {
+        let mut p = PartitionDataBuilder::new().build();
+        for i in 0..n {
+            let mb = lp_to_mutable_batch(&format!(
+                r#"{},city=Hereford people={i},crisps="good" {i}"#,
+                &*ARBITRARY_TABLE_NAME
+            ))
+            .1;
+            p.buffer_write(mb, SequenceNumber::new(i as _))
+                .expect("write should succeed");
+        }
+        Arc::new(Mutex::new(p))
+    }
+
+    macro_rules! assert_enqueue_count {
+        ($metrics:ident, $reason:literal, $value:expr) => {
+            metric::assert_counter!(
+                $metrics,
+                metric::U64Counter,
+                "ingester_persist_cold_partition_enqueue_count",
+                labels = Attributes::from(&[("reason", $reason)]),
+                value = $value,
+            );
+        };
+    }
+
+    #[tokio::test]
+    async fn test_idle_partition_persist() {
+        let p = partition_with_rows(1);
+        let last_write_at = p.lock().last_write_at().unwrap();
+
+        let metrics = metric::Registry::default();
+        let persist_handle = Arc::new(MockPersistQueue::default());
+        let persister =
+            ColdPartitionPersister::new(Arc::clone(&persist_handle), Some(IDLE), None, &metrics);
+
+        // Not yet idle.
+        persister.persist_cold(
+            vec![Arc::clone(&p)].into_iter(),
+            last_write_at + Duration::from_secs(59),
+        );
+        tokio::task::yield_now().await;
+        assert_eq!(persist_handle.calls().len(), 0);
+        assert_enqueue_count!(metrics, "idle", 0);
+
+        // Idle.
+        persister.persist_cold(vec![Arc::clone(&p)].into_iter(), last_write_at + IDLE);
+        tokio::task::yield_now().await;
+        assert_eq!(persist_handle.calls().len(), 1);
+        assert_enqueue_count!(metrics, "idle", 1);
+
+        // The partition contains no more data to persist, so it is not
+        // enqueued a second time.
+        persister.persist_cold(
+            vec![Arc::clone(&p)].into_iter(),
+            last_write_at + IDLE + IDLE,
+        );
+        tokio::task::yield_now().await;
+        assert_eq!(persist_handle.calls().len(), 1);
+        assert_enqueue_count!(metrics, "idle", 1);
+        assert_enqueue_count!(metrics, "watermark", 0);
+
+        // Check persist completion.
+        drop(persister);
+        Arc::try_unwrap(persist_handle)
+            .expect("should be no more refs")
+            .join()
+            .await;
+        assert_eq!(p.lock().completed_persistence_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_watermark_persists_largest_first() {
+        let small = partition_with_rows(1);
+        let medium = partition_with_rows(10);
+        let large = partition_with_rows(100);
+
+        let cost = |p: &Arc<Mutex<PartitionData>>| p.lock().persist_cost_estimate();
+        let (small_cost, medium_cost, large_cost) = (cost(&small), cost(&medium), cost(&large));
+        assert!(small_cost < medium_cost && medium_cost < large_cost);
+
+        // Persisting the largest partition is not enough to get below the
+        // watermark, but also persisting the medium one is.
+        let watermark = small_cost + 1;
+        assert!(small_cost + medium_cost > watermark);
+
+        let metrics = metric::Registry::default();
+        let persist_handle = Arc::new(MockPersistQueue::default());
+        let persister = ColdPartitionPersister::new(
+            Arc::clone(&persist_handle),
+            None,
+            Some(watermark),
+            &metrics,
+        );
+
+        let now = small.lock().last_write_at().unwrap();
+        persister.persist_cold(
+            vec![Arc::clone(&small), Arc::clone(&medium), Arc::clone(&large)].into_iter(),
+            now,
+        );
+        tokio::task::yield_now().await;
+
+        metric::assert_counter!(
+            metrics,
+            metric::U64Gauge,
+            "ingester_buffered_persist_cost",
+            value = (small_cost + medium_cost + large_cost) as u64,
+        );
+        assert_enqueue_count!(metrics, "watermark", 2);
+        assert_enqueue_count!(metrics, "idle", 0);
+
+        let calls = persist_handle.calls();
+        assert_eq!(calls.len(), 2);
+        assert!(calls.iter().any(|c| Arc::ptr_eq(c, &large)));
+        assert!(calls.iter().any(|c| Arc::ptr_eq(c, &medium)));
+
+        // The small partition is left buffered.
+        assert!(small.lock().last_write_at().is_some());
+        assert!(medium.lock().last_write_at().is_none());
+        assert!(large.lock().last_write_at().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_idle_partitions_count_towards_watermark() {
+        let idle = partition_with_rows(10);
+        // Ensure the writes to the two partitions have distinct timestamps.
+        std::thread::sleep(Duration::from_millis(10));
+        let active = partition_with_rows(1);
+
+        let idle_cost = idle.lock().persist_cost_estimate();
+        let active_cost = active.lock().persist_cost_estimate();
+
+        let metrics = metric::Registry::default();
+        let persist_handle = Arc::new(MockPersistQueue::default());
+        let persister = ColdPartitionPersister::new(
+            Arc::clone(&persist_handle),
+            Some(IDLE),
+            // Persisting the idle partition alone brings the total below the
+            // watermark.
+            Some(idle_cost + active_cost - 1),
+            &metrics,
+        );
+
+        // Only the first partition is idle.
+        let now = idle.lock().last_write_at().unwrap() + IDLE;
+        assert!(active.lock().last_write_at().unwrap() + IDLE > now);
+
+        persister.persist_cold(
+            vec![Arc::clone(&idle), Arc::clone(&active)].into_iter(),
+            now,
+        );
+        tokio::task::yield_now().await;
+
+        assert_enqueue_count!(metrics, "idle", 1);
+        assert_enqueue_count!(metrics, "watermark", 0);
+        assert!(idle.lock().last_write_at().is_none());
+        assert!(active.lock().last_write_at().is_some());
+    }
+}
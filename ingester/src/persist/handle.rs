@@ -21,6 +21,7 @@ use super::{
 };
 use crate::{
     buffer_tree::partition::{persisting::PersistingData, PartitionData, SortKeyState},
+    catalog_clock::CatalogClock,
     ingest_state::IngestState,
     persist::worker,
 };
@@ -197,6 +198,7 @@ impl PersistHandle {
         let worker_state = Arc::new(SharedWorkerState {
             exec,
             store,
+            catalog_clock: CatalogClock::new(Arc::clone(&catalog)),
             catalog,
             column_map_resolver,
             completion_observer,
@@ -1,6 +1,7 @@
 //! The persistence subsystem; abstractions, types, and implementation.
 
 pub(crate) mod backpressure;
+pub(crate) mod cold_partitions;
 pub(crate) mod column_map_resolver;
 pub(super) mod compact;
 pub(crate) mod completion_observer;
@@ -289,11 +290,13 @@ mod tests {
                 compaction_level,
                 file_size_bytes,
                 created_at,
+                data_written_at,
                 max_l0_created_at,
                 ..
             }] =>
             {
                 assert_eq!(created_at.get(), max_l0_created_at.get());
+                assert!(data_written_at.get() <= created_at.get());
 
                 assert_eq!(got_namespace_id, &namespace_id);
                 assert_eq!(got_table_id, &table_id);
@@ -476,11 +479,13 @@ mod tests {
                 compaction_level,
                 file_size_bytes,
                 created_at,
+                data_written_at,
                 max_l0_created_at,
                 ..
             }] =>
             {
                 assert_eq!(created_at.get(), max_l0_created_at.get());
+                assert!(data_written_at.get() <= created_at.get());
 
                 assert_eq!(got_namespace_id, &namespace_id);
                 assert_eq!(got_table_id, &table_id);
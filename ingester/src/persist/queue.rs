@@ -175,6 +175,7 @@ pub(crate) mod mock {
                             row_count: 24,
                             compaction_level: data_types::CompactionLevel::Initial,
                             created_at: Timestamp::new(1234),
+                            data_written_at: Timestamp::new(1234),
                             column_set: ColumnSet::new([1, 2, 3, 4].into_iter().map(ColumnId::new)),
                             max_l0_created_at: Timestamp::new(42),
                         },
@@ -13,7 +13,7 @@ use schema::sort::SortKey;
 use tokio::{sync::mpsc, time::Instant};
 use uuid::Uuid;
 
-use crate::persist::compact::compact_persisting_batch;
+use crate::{catalog_clock::CatalogClock, persist::compact::compact_persisting_batch};
 
 use super::{
     column_map_resolver::ColumnMapResolver,
@@ -28,6 +28,7 @@ pub(super) struct SharedWorkerState<O, C> {
     pub(super) exec: Arc<Executor>,
     pub(super) store: ParquetStorage,
     pub(super) catalog: Arc<dyn Catalog>,
+    pub(super) catalog_clock: CatalogClock,
     pub(super) completion_observer: O,
     pub(super) column_map_resolver: C,
 }
@@ -318,6 +319,17 @@ where
         "partition parquet uploaded"
     );
 
+    // The data of the file is marked as written at the time of the first
+    // write it contains, so that any delete requested after that write is
+    // applied to the file by queriers and the compactor - deletes are not
+    // applied at persist time. Tombstones are timestamped by the catalog, so
+    // the local time of the first write is converted to the catalog clock.
+    let data_written_at = worker_state
+        .catalog_clock
+        .offset()
+        .await
+        .to_catalog_time(ctx.data().first_write_at());
+
     // Build the data that must be inserted into the parquet_files catalog
     // table in order to make the file visible to queriers.
     let parquet_table_data =
@@ -332,6 +344,10 @@ where
                 })
                 .id
         });
+    let parquet_table_data = ParquetFileParams {
+        data_written_at: data_written_at.into(),
+        ..parquet_table_data
+    };
 
     (catalog_sort_key_update, parquet_table_data)
 }
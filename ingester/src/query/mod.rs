@@ -14,5 +14,8 @@ pub(crate) mod exec_instrumentation;
 pub(crate) mod result_instrumentation;
 pub(crate) mod tracing;
 
+// Deletes
+pub(crate) mod tombstone_filter;
+
 #[cfg(test)]
 pub(crate) mod mock_query_exec;
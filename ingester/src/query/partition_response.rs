@@ -4,6 +4,7 @@
 
 use arrow::record_batch::RecordBatch;
 use data_types::TransitionPartitionId;
+use iox_time::Time;
 
 /// Response data for a single partition.
 #[derive(Debug)]
@@ -16,6 +17,10 @@ pub(crate) struct PartitionResponse {
 
     /// Count of persisted Parquet files for this partition by this ingester instance.
     completed_persistence_count: u64,
+
+    /// The wall-clock time of the oldest write contained in `batches`, if
+    /// known.
+    oldest_write_at: Option<Time>,
 }
 
 impl PartitionResponse {
@@ -28,6 +33,16 @@ impl PartitionResponse {
             batches: data,
             id,
             completed_persistence_count,
+            oldest_write_at: None,
+        }
+    }
+
+    /// Set the wall-clock time of the oldest write contained in this
+    /// response.
+    pub(crate) fn with_oldest_write_at(self, oldest_write_at: Option<Time>) -> Self {
+        Self {
+            oldest_write_at,
+            ..self
         }
     }
 
@@ -39,6 +54,10 @@ impl PartitionResponse {
         self.completed_persistence_count
     }
 
+    pub(crate) fn oldest_write_at(&self) -> Option<Time> {
+        self.oldest_write_at
+    }
+
     pub(crate) fn into_record_batches(self) -> Vec<RecordBatch> {
         self.batches
     }
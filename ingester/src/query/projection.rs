@@ -146,4 +146,22 @@ impl OwnedProjection {
             Projection::Project(v) => Some(v.as_ref()),
         }
     }
+
+    /// Return a new projection that additionally includes `columns`.
+    ///
+    /// Columns already part of this projection are not duplicated.
+    pub(crate) fn with_columns<'a>(&self, columns: impl IntoIterator<Item = &'a str>) -> Self {
+        match &self.0 {
+            Projection::All => Self(Projection::All),
+            Projection::Project(v) => {
+                let mut v = v.clone();
+                for c in columns {
+                    if !v.iter().any(|existing| existing == c) {
+                        v.push(c.to_string());
+                    }
+                }
+                Self(Projection::Project(v))
+            }
+        }
+    }
 }
@@ -0,0 +1,291 @@
+//! A [`QueryExec`] decorator removing deleted rows from query responses.
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use backoff::{Backoff, BackoffConfig};
+use data_types::{DeletePredicate, NamespaceId, TableId, Timestamp, Tombstone};
+use futures::StreamExt;
+use iox_catalog::interface::Catalog;
+use observability_deps::tracing::warn;
+use parking_lot::Mutex;
+use predicate::{
+    delete_predicate::{filter_batch_by_delete_predicates, parse_tombstone},
+    Predicate,
+};
+use schema::TIME_COLUMN_NAME;
+use tokio::time::Instant;
+use trace::span::Span;
+
+use crate::catalog_clock::{CatalogClock, CatalogClockOffset};
+
+use super::{
+    partition_response::PartitionResponse,
+    projection::OwnedProjection,
+    response::{PartitionStream, QueryResponse},
+    QueryError, QueryExec,
+};
+
+/// The duration for which the tombstones of a table are cached before being
+/// re-fetched from the catalog.
+///
+/// This bounds the time until a delete becomes visible in query responses.
+const TOMBSTONE_TTL: Duration = Duration::from_secs(10);
+
+type Tombstones = Arc<[(Tombstone, Arc<DeletePredicate>)]>;
+
+/// A [`QueryExec`] decorator that applies the deletes recorded in the catalog
+/// to the buffered data returned by the inner implementation.
+///
+/// The ingester does not know when each individual row was written, only the
+/// time of the oldest write buffered for a partition. All tombstones created
+/// after that write are applied to the partition's data, which may also
+/// remove rows that were written after the delete was requested until the
+/// partition is persisted. The data of the persisted files is marked as
+/// written at the time of their first write, causing queriers to apply the
+/// same tombstones.
+///
+/// Tombstone creation times are assigned by the catalog clock, so the local
+/// time of the oldest write is converted using a [`CatalogClock`].
+#[derive(Debug)]
+pub(crate) struct QueryTombstoneFilter<T> {
+    inner: T,
+    catalog: Arc<dyn Catalog>,
+    clock: CatalogClock,
+    backoff_config: BackoffConfig,
+    cache: Mutex<HashMap<TableId, (Instant, Tombstones)>>,
+}
+
+impl<T> QueryTombstoneFilter<T> {
+    pub(crate) fn new(inner: T, catalog: Arc<dyn Catalog>) -> Self {
+        Self {
+            inner,
+            clock: CatalogClock::new(Arc::clone(&catalog)),
+            catalog,
+            backoff_config: BackoffConfig::default(),
+            cache: Default::default(),
+        }
+    }
+
+    /// Return the (possibly cached) tombstones of `table_id`.
+    async fn tombstones(&self, table_id: TableId) -> Tombstones {
+        if let Some((fetched_at, tombstones)) = self.cache.lock().get(&table_id) {
+            if fetched_at.elapsed() < TOMBSTONE_TTL {
+                return Arc::clone(tombstones);
+            }
+        }
+
+        let fetched_at = Instant::now();
+        let tombstones = Backoff::new(&self.backoff_config)
+            .retry_all_errors("fetch tombstones", || async {
+                self.catalog
+                    .repositories()
+                    .await
+                    .tombstones()
+                    .list_by_table_id(table_id)
+                    .await
+            })
+            .await
+            .expect("retry forever");
+
+        let tombstones = tombstones
+            .into_iter()
+            .filter_map(|t| match parse_tombstone(&t) {
+                Ok(p) => Some((t, Arc::new(p))),
+                Err(e) => {
+                    warn!(
+                        tombstone_id=%t.id,
+                        %table_id,
+                        error=%e,
+                        "ignoring tombstone with invalid predicate"
+                    );
+                    None
+                }
+            })
+            .collect::<Tombstones>();
+
+        self.cache
+            .lock()
+            .insert(table_id, (fetched_at, Arc::clone(&tombstones)));
+
+        tombstones
+    }
+}
+
+#[async_trait]
+impl<T> QueryExec for QueryTombstoneFilter<T>
+where
+    T: QueryExec<Response = QueryResponse>,
+{
+    type Response = QueryResponse;
+
+    async fn query_exec(
+        &self,
+        namespace_id: NamespaceId,
+        table_id: TableId,
+        projection: OwnedProjection,
+        span: Option<Span>,
+        predicate: Option<Predicate>,
+    ) -> Result<Self::Response, QueryError> {
+        let tombstones = self.tombstones(table_id).await;
+        if tombstones.is_empty() {
+            return self
+                .inner
+                .query_exec(namespace_id, table_id, projection, span, predicate)
+                .await;
+        }
+        let offset = self.clock.offset().await;
+
+        // The delete predicates must be evaluated against the columns they
+        // reference, which may not be part of the requested projection.
+        let extended = projection.with_columns(
+            tombstones
+                .iter()
+                .flat_map(|(_, p)| p.exprs.iter().map(|e| e.column.as_str()))
+                .chain([TIME_COLUMN_NAME]),
+        );
+
+        let response = self
+            .inner
+            .query_exec(namespace_id, table_id, extended, span, predicate)
+            .await?;
+
+        let stream = response
+            .into_partition_stream()
+            .map(move |p| apply_tombstones(p, offset, &tombstones, &projection));
+
+        Ok(QueryResponse::new(PartitionStream::new(stream)))
+    }
+}
+
+/// Remove the rows in `partition` deleted by any of the `tombstones` created
+/// after its oldest write, and apply `projection` to the result.
+fn apply_tombstones(
+    partition: PartitionResponse,
+    offset: CatalogClockOffset,
+    tombstones: &[(Tombstone, Arc<DeletePredicate>)],
+    projection: &OwnedProjection,
+) -> PartitionResponse {
+    let Some(oldest_write_at) = partition.oldest_write_at() else {
+        return partition;
+    };
+    let cutoff = Timestamp::from(offset.to_catalog_time(oldest_write_at));
+
+    let predicates = tombstones
+        .iter()
+        .filter(|(t, _)| t.created_at > cutoff)
+        .map(|(_, p)| Arc::clone(p))
+        .collect::<Vec<_>>();
+
+    let id = partition.id().clone();
+    let completed_persistence_count = partition.completed_persistence_count();
+
+    let batches = partition
+        .into_record_batches()
+        .into_iter()
+        .map(|batch| {
+            filter_batch_by_delete_predicates(batch, &predicates)
+                .expect("failed to apply delete predicates")
+        })
+        .filter(|batch| batch.num_rows() > 0)
+        .collect::<Vec<_>>();
+
+    PartitionResponse::new(
+        projection.project_record_batch(&batches),
+        id,
+        completed_persistence_count,
+    )
+    .with_oldest_write_at(Some(oldest_write_at))
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_util::assert_batches_sorted_eq;
+    use data_types::{DeleteExpr, Op, Scalar, TimestampRange};
+    use futures::TryStreamExt;
+    use iox_catalog::{
+        mem::MemCatalog,
+        test_helpers::{arbitrary_namespace, arbitrary_table},
+    };
+    use iox_time::{SystemProvider, TimeProvider};
+
+    use super::*;
+    use crate::{
+        query::mock_query_exec::MockQueryExec, test_util::ARBITRARY_TRANSITION_PARTITION_ID,
+    };
+
+    #[tokio::test]
+    async fn test_apply_tombstones() {
+        let metrics = Arc::new(metric::Registry::default());
+        let catalog: Arc<dyn Catalog> = Arc::new(MemCatalog::new(Arc::clone(&metrics)));
+
+        let table_id = {
+            let mut repos = catalog.repositories().await;
+            let ns = arbitrary_namespace(&mut *repos, "bananas").await;
+            arbitrary_table(&mut *repos, "platanos", &ns).await.id
+        };
+
+        let batch = mutable_batch_lp::lines_to_batches(
+            "platanos,region=west v=1 10\nplatanos,region=east v=2 20\nplatanos,region=west v=3 200",
+            0,
+        )
+        .unwrap()
+        .remove("platanos")
+        .unwrap()
+        .to_arrow(schema::Projection::All)
+        .unwrap();
+
+        let written_at = SystemProvider::new().now();
+        tokio::time::sleep(Duration::from_millis(1)).await;
+
+        catalog
+            .repositories()
+            .await
+            .tombstones()
+            .create(
+                table_id,
+                &DeletePredicate {
+                    range: TimestampRange::new(0, 100),
+                    exprs: vec![DeleteExpr::new(
+                        "region".to_string(),
+                        Op::Eq,
+                        Scalar::String("west".to_string()),
+                    )],
+                },
+            )
+            .await
+            .unwrap();
+
+        let response = QueryResponse::new(PartitionStream::new(futures::stream::iter([
+            PartitionResponse::new(vec![batch], ARBITRARY_TRANSITION_PARTITION_ID.clone(), 0)
+                .with_oldest_write_at(Some(written_at)),
+        ])));
+        let inner = MockQueryExec::default().with_result(Ok(response));
+        let filter = QueryTombstoneFilter::new(inner, catalog);
+
+        let got = filter
+            .query_exec(
+                NamespaceId::new(1),
+                table_id,
+                OwnedProjection::from(vec!["v"]),
+                None,
+                None,
+            )
+            .await
+            .unwrap()
+            .into_partition_stream()
+            .flat_map(|p| futures::stream::iter(p.into_record_batches()))
+            .map(Ok::<_, QueryError>)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert_batches_sorted_eq!(
+            [
+                "+-----+", //
+                "| v   |", "+-----+", "| 2.0 |", "| 3.0 |", "+-----+",
+            ],
+            &got
+        );
+    }
+}
@@ -6,6 +6,7 @@
 
 pub(crate) mod disk_full_protection;
 pub(crate) mod reference_tracker;
+pub(crate) mod replication;
 pub(crate) mod rotate_task;
 mod traits;
 pub(crate) mod wal_sink;
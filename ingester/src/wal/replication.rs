@@ -0,0 +1,502 @@
+//! Replication of closed WAL segment files to object storage.
+//!
+//! The WAL only protects acknowledged writes against an ingester crash if the
+//! local disk survives. When replication is enabled, each segment file is
+//! uploaded to object storage once it is rotated out by the periodic rotation
+//! task, and the replicated copy
+//! is deleted alongside the local file once all the data within it has been
+//! persisted.
+//!
+//! If an ingester starts with no segment files in its WAL directory (i.e.
+//! because the volume was lost), the replicated segments are downloaded and
+//! replayed. New segment files are numbered after the highest replicated
+//! segment ID, and a replicated segment is never overwritten.
+//!
+//! Writes in the currently open segment are not replicated - the window of
+//! data that can be lost is bounded by the WAL rotation period.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use backoff::{Backoff, BackoffConfig};
+use futures::TryStreamExt;
+use object_store::{path::Path as ObjectPath, DynObjectStore};
+use observability_deps::tracing::*;
+use thiserror::Error;
+use wal::{ClosedSegment, SegmentId, SEGMENT_FILE_EXTENSION};
+
+use super::reference_tracker::WalFileDeleter;
+
+/// The maximum duration of time spent retrying the upload of a single segment
+/// file before giving up.
+const UPLOAD_DEADLINE: Duration = Duration::from_secs(60);
+
+/// Errors that occur when replicating WAL segment files.
+#[derive(Debug, Error)]
+pub enum WalReplicationError {
+    /// An error communicating with the object store.
+    #[error("object store error: {0}")]
+    ObjectStore(#[from] object_store::Error),
+
+    /// An error reading or writing a local segment file.
+    #[error("failed to access local wal segment {path}: {source}")]
+    Io {
+        /// The local segment file path.
+        path: PathBuf,
+        /// The underlying error.
+        source: std::io::Error,
+    },
+}
+
+/// Uploads, deletes and restores WAL segment files in object storage.
+///
+/// Segment files are stored as `<prefix>/<segment id>.dat`.
+#[derive(Debug, Clone)]
+pub(crate) struct WalReplicator {
+    object_store: Arc<DynObjectStore>,
+    prefix: ObjectPath,
+
+    /// Metrics tracking the outcome of segment file uploads.
+    upload_ok: metric::U64Counter,
+    upload_error: metric::U64Counter,
+}
+
+impl WalReplicator {
+    /// Replicate segment files to `object_store`, under `prefix`.
+    ///
+    /// The `prefix` MUST be unique to this ingester.
+    pub(crate) fn new(
+        object_store: Arc<DynObjectStore>,
+        prefix: &str,
+        metrics: &metric::Registry,
+    ) -> Self {
+        let uploads = metrics.register_metric::<metric::U64Counter>(
+            "ingester_wal_segment_uploads",
+            "number of closed wal segment files uploaded to object storage",
+        );
+        Self {
+            object_store,
+            prefix: ObjectPath::from(prefix),
+            upload_ok: uploads.recorder(&[("result", "success")]),
+            upload_error: uploads.recorder(&[("result", "error")]),
+        }
+    }
+
+    fn segment_path(&self, id: SegmentId) -> ObjectPath {
+        self.prefix.child(segment_filename(id).as_str())
+    }
+
+    /// Upload the closed `segment` file, retrying for at most
+    /// [`UPLOAD_DEADLINE`].
+    ///
+    /// A failure to upload is logged, but otherwise ignored - the data remains
+    /// durable in the local segment file.
+    pub(crate) async fn upload(&self, segment: &ClosedSegment) {
+        let id = segment.id();
+        let location = self.segment_path(id);
+
+        let res = Backoff::new(&BackoffConfig {
+            deadline: Some(UPLOAD_DEADLINE),
+            ..Default::default()
+        })
+        .retry_all_errors("upload wal segment", || async {
+            // Never overwrite a replicated segment - an existing object with
+            // the same ID holds different data that may still be needed.
+            match self.object_store.head(&location).await {
+                Ok(_) => return Ok(false),
+                Err(object_store::Error::NotFound { .. }) => {}
+                Err(e) => return Err(e.into()),
+            }
+            let data = tokio::fs::read(segment.path())
+                .await
+                .map_err(|source| io_error(segment.path(), source))?;
+            self.object_store.put(&location, data.into()).await?;
+            Ok::<_, WalReplicationError>(true)
+        })
+        .await;
+
+        match res {
+            Ok(true) => {
+                debug!(segment_id = %id, %location, "uploaded wal segment");
+                self.upload_ok.inc(1);
+            }
+            Ok(false) => {
+                error!(segment_id = %id, %location, "not uploading wal segment, a replicated segment with the same id exists");
+                self.upload_error.inc(1);
+            }
+            Err(error) => {
+                error!(segment_id = %id, %location, %error, "failed to upload wal segment");
+                self.upload_error.inc(1);
+            }
+        }
+    }
+
+    /// Delete the replicated copy of the segment with the specified `id`, if
+    /// any.
+    pub(crate) async fn delete(&self, id: SegmentId) {
+        let location = self.segment_path(id);
+        match self.object_store.delete(&location).await {
+            Ok(()) | Err(object_store::Error::NotFound { .. }) => {
+                debug!(segment_id = %id, %location, "deleted replicated wal segment");
+            }
+            Err(error) => {
+                // The replicated copy lingers, and is replayed (duplicating
+                // already persisted data) only if the local WAL is lost.
+                warn!(segment_id = %id, %location, %error, "failed to delete replicated wal segment");
+            }
+        }
+    }
+
+    /// List the IDs and locations of all replicated segment files, ordered by
+    /// ID.
+    async fn list(&self) -> Result<Vec<(SegmentId, ObjectPath)>, WalReplicationError> {
+        let mut segments = self
+            .object_store
+            .list(Some(&self.prefix))
+            .await?
+            .try_filter_map(|meta| async move {
+                // Only consider objects named like segment files.
+                let id = meta
+                    .location
+                    .filename()
+                    .and_then(|f| f.strip_suffix(SEGMENT_FILE_EXTENSION))
+                    .and_then(|v| v.strip_suffix('.'))
+                    .and_then(|id| id.parse::<u64>().ok());
+                match id {
+                    Some(id) => Ok(Some((SegmentId::new(id), meta.location))),
+                    None => {
+                        warn!(location = %meta.location, "ignoring unexpected object in wal replication prefix");
+                        Ok(None)
+                    }
+                }
+            })
+            .try_collect::<Vec<_>>()
+            .await?;
+        segments.sort_by_key(|(id, _)| *id);
+        Ok(segments)
+    }
+
+    /// Return an ID greater than that of any replicated segment file.
+    ///
+    /// New segment files must be numbered starting from this ID so they never
+    /// replace a replicated segment.
+    pub(crate) async fn next_segment_id(&self) -> Result<SegmentId, WalReplicationError> {
+        Ok(self
+            .list()
+            .await?
+            .last()
+            .map(|(id, _)| SegmentId::new(id.get() + 1))
+            .unwrap_or(SegmentId::new(0)))
+    }
+
+    /// Download all replicated segment files into `dir` unless it already
+    /// contains segment files, returning the number of segments restored.
+    ///
+    /// Segment files are first downloaded into a staging directory within
+    /// `dir`, and only moved into `dir` once all of them have been written
+    /// and synced to disk. A restore interrupted while moving the files is
+    /// completed by the next call, while one interrupted before is restarted.
+    pub(crate) async fn restore(&self, dir: &Path) -> Result<usize, WalReplicationError> {
+        let partial = dir.join(RESTORE_PARTIAL_DIR);
+        let staged = dir.join(RESTORE_STAGED_DIR);
+
+        create_dir(dir).await?;
+
+        // Complete an interrupted restore, if all of its segments were
+        // downloaded.
+        if tokio::fs::try_exists(&staged)
+            .await
+            .map_err(|source| io_error(&staged, source))?
+        {
+            info!("completing interrupted restore of replicated wal segments");
+            return move_staged(&staged, dir).await;
+        }
+
+        if has_segment_files(dir).await? {
+            debug!("wal directory contains segment files, skipping restore of replicated segments");
+            return Ok(0);
+        }
+
+        // Discard the downloads of a previously interrupted restore.
+        match tokio::fs::remove_dir_all(&partial).await {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(source) => return Err(io_error(&partial, source)),
+        }
+        create_dir(&partial).await?;
+
+        let segments = self.list().await?;
+        for (id, location) in &segments {
+            let data = self.object_store.get(location).await?.bytes().await?;
+
+            let path = partial.join(segment_filename(*id));
+            tokio::fs::write(&path, &data)
+                .await
+                .map_err(|source| io_error(&path, source))?;
+            sync_file(&path).await?;
+
+            info!(%location, size = data.len(), "downloaded replicated wal segment");
+        }
+        sync_file(&partial).await?;
+
+        // Atomically mark the download as complete.
+        tokio::fs::rename(&partial, &staged)
+            .await
+            .map_err(|source| io_error(&staged, source))?;
+        sync_file(dir).await?;
+
+        move_staged(&staged, dir).await
+    }
+}
+
+/// The directory within the WAL directory that replicated segments are
+/// downloaded into.
+///
+/// The WAL ignores directories within its root directory.
+const RESTORE_PARTIAL_DIR: &str = "restore.partial";
+
+/// The name [`RESTORE_PARTIAL_DIR`] is renamed to once all segments have been
+/// downloaded.
+const RESTORE_STAGED_DIR: &str = "restore.staged";
+
+fn io_error(path: &Path, source: std::io::Error) -> WalReplicationError {
+    WalReplicationError::Io {
+        path: path.to_owned(),
+        source,
+    }
+}
+
+fn segment_filename(id: SegmentId) -> String {
+    format!("{id}.{SEGMENT_FILE_EXTENSION}")
+}
+
+async fn create_dir(dir: &Path) -> Result<(), WalReplicationError> {
+    tokio::fs::create_dir_all(dir)
+        .await
+        .map_err(|source| io_error(dir, source))
+}
+
+/// Sync the file or directory at `path` to disk.
+///
+/// Syncing a directory makes the files created or renamed within it durable.
+async fn sync_file(path: &Path) -> Result<(), WalReplicationError> {
+    tokio::fs::File::open(path)
+        .await
+        .map_err(|source| io_error(path, source))?
+        .sync_all()
+        .await
+        .map_err(|source| io_error(path, source))
+}
+
+/// Returns true if `dir` contains any segment file.
+async fn has_segment_files(dir: &Path) -> Result<bool, WalReplicationError> {
+    let mut entries = tokio::fs::read_dir(dir)
+        .await
+        .map_err(|source| io_error(dir, source))?;
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .map_err(|source| io_error(dir, source))?
+    {
+        let path = entry.path();
+        let is_file = entry
+            .file_type()
+            .await
+            .map_err(|source| io_error(&path, source))?
+            .is_file();
+        if is_file
+            && path
+                .extension()
+                .is_some_and(|e| e == SEGMENT_FILE_EXTENSION)
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Move the fully downloaded segment files in `staged` into `dir`, and remove
+/// `staged`, returning the number of segment files moved.
+async fn move_staged(staged: &Path, dir: &Path) -> Result<usize, WalReplicationError> {
+    let mut n = 0;
+    let mut entries = tokio::fs::read_dir(staged)
+        .await
+        .map_err(|source| io_error(staged, source))?;
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .map_err(|source| io_error(staged, source))?
+    {
+        let to = dir.join(entry.file_name());
+        tokio::fs::rename(entry.path(), &to)
+            .await
+            .map_err(|source| io_error(&to, source))?;
+        n += 1;
+    }
+    sync_file(dir).await?;
+
+    tokio::fs::remove_dir(staged)
+        .await
+        .map_err(|source| io_error(staged, source))?;
+
+    Ok(n)
+}
+
+/// A WAL decorator that deletes the replicated copy of a segment file whenever
+/// the local file is deleted.
+#[derive(Debug, Clone)]
+pub(crate) struct ReplicatedWal<T> {
+    inner: T,
+    replicator: Option<WalReplicator>,
+}
+
+impl<T> ReplicatedWal<T> {
+    /// Decorate `inner`, deleting replicated segments through `replicator` if
+    /// replication is enabled.
+    pub(crate) fn new(inner: T, replicator: Option<WalReplicator>) -> Self {
+        Self { inner, replicator }
+    }
+
+    /// The decorated WAL.
+    pub(crate) fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// The replicator, if replication is enabled.
+    pub(crate) fn replicator(&self) -> Option<&WalReplicator> {
+        self.replicator.as_ref()
+    }
+}
+
+#[async_trait]
+impl<T> WalFileDeleter for ReplicatedWal<T>
+where
+    T: WalFileDeleter,
+{
+    async fn delete_file(&self, id: SegmentId) {
+        self.inner.delete_file(id).await;
+        if let Some(replicator) = &self.replicator {
+            replicator.delete(id).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use object_store::memory::InMemory;
+
+    use super::*;
+
+    async fn list(store: &DynObjectStore) -> Vec<String> {
+        let mut paths = store
+            .list(None)
+            .await
+            .unwrap()
+            .map_ok(|meta| meta.location.to_string())
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        paths.sort();
+        paths
+    }
+
+    #[tokio::test]
+    async fn test_upload_delete_restore() {
+        let metrics = metric::Registry::default();
+        let store: Arc<DynObjectStore> = Arc::new(InMemory::new());
+        let replicator = WalReplicator::new(Arc::clone(&store), "wal/ingester-0", &metrics);
+
+        // Rotate out two (empty) segment files and upload them.
+        let dir = test_helpers::tmp_dir().unwrap();
+        let wal = wal::Wal::new(dir.path()).await.unwrap();
+        let (first, _) = wal.rotate().unwrap();
+        let (second, _) = wal.rotate().unwrap();
+        replicator.upload(&first).await;
+        replicator.upload(&second).await;
+
+        metric::assert_counter!(
+            metrics,
+            metric::U64Counter,
+            "ingester_wal_segment_uploads",
+            labels = metric::Attributes::from(&[("result", "success")]),
+            value = 2,
+        );
+        assert_eq!(
+            list(&*store).await,
+            [
+                format!("wal/ingester-0/{}.dat", first.id()),
+                format!("wal/ingester-0/{}.dat", second.id()),
+            ]
+        );
+
+        // Deleting a segment through the decorated WAL deletes both copies.
+        let replicated = ReplicatedWal::new(Arc::clone(&wal), Some(replicator.clone()));
+        replicated.delete_file(first.id()).await;
+        assert!(!first.path().exists());
+        assert_eq!(
+            list(&*store).await,
+            [format!("wal/ingester-0/{}.dat", second.id())]
+        );
+
+        // Deleting a segment that was never uploaded is not an error.
+        replicator.delete(SegmentId::new(42)).await;
+
+        // Restore into an empty directory.
+        let restore_dir = test_helpers::tmp_dir().unwrap();
+        let n = replicator.restore(restore_dir.path()).await.unwrap();
+        assert_eq!(n, 1);
+
+        let restored = wal::Wal::new(restore_dir.path()).await.unwrap();
+        let ids = restored
+            .closed_segments()
+            .iter()
+            .map(|s| s.id())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [second.id()]);
+        assert_eq!(
+            std::fs::read(build_path(restore_dir.path(), second.id())).unwrap(),
+            std::fs::read(second.path()).unwrap(),
+        );
+
+        // New segments are numbered after the highest replicated segment.
+        assert_eq!(
+            replicator.next_segment_id().await.unwrap(),
+            SegmentId::new(second.id().get() + 1)
+        );
+
+        // A segment is never uploaded over a replicated segment with the same
+        // ID.
+        let other_dir = test_helpers::tmp_dir().unwrap();
+        let other_wal = wal::Wal::new(other_dir.path()).await.unwrap();
+        let (other_first, _) = other_wal.rotate().unwrap();
+        let (other_second, _) = other_wal.rotate().unwrap();
+        assert_eq!(other_first.id(), first.id());
+        assert_eq!(other_second.id(), second.id());
+        replicator.upload(&other_second).await;
+        metric::assert_counter!(
+            metrics,
+            metric::U64Counter,
+            "ingester_wal_segment_uploads",
+            labels = metric::Attributes::from(&[("result", "error")]),
+            value = 1,
+        );
+
+        // The ID of a deleted replicated segment may be reused.
+        replicator.upload(&other_first).await;
+        metric::assert_counter!(
+            metrics,
+            metric::U64Counter,
+            "ingester_wal_segment_uploads",
+            labels = metric::Attributes::from(&[("result", "success")]),
+            value = 3,
+        );
+    }
+
+    fn build_path(dir: &Path, id: SegmentId) -> PathBuf {
+        dir.join(format!("{id}.{SEGMENT_FILE_EXTENSION}"))
+    }
+}
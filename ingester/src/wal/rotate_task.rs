@@ -4,17 +4,21 @@ use std::{sync::Arc, time::Duration};
 use crate::{
     partition_iter::PartitionIter,
     persist::{drain_buffer::persist_partitions, queue::PersistQueue},
-    wal::reference_tracker::WalReferenceHandle,
+    wal::{reference_tracker::WalReferenceHandle, replication::WalReplicator},
 };
 
 /// Rotate the `wal` segment file every `period` duration of time, notifying
 /// the [`WalReferenceHandle`].
+///
+/// If a [`WalReplicator`] is provided, each closed segment file is uploaded to
+/// object storage.
 pub(crate) async fn periodic_rotation<T, P>(
     wal: Arc<wal::Wal>,
     period: Duration,
     wal_reference_handle: WalReferenceHandle,
     buffer: T,
     persist: P,
+    replicator: Option<WalReplicator>,
 ) where
     T: PartitionIter + Sync + 'static,
     P: PersistQueue + Clone + 'static,
@@ -36,9 +40,32 @@ pub(crate) async fn periodic_rotation<T, P>(
             n_ops = ids.len(),
             "rotated wal"
         );
-        wal_reference_handle
-            .enqueue_rotated_file(stats.id(), ids)
-            .await;
+        match &replicator {
+            Some(replicator) => {
+                // Upload the closed segment before the reference tracker is
+                // notified of it, ensuring the segment cannot be deleted (and
+                // the replicated copy removed) before the upload completes.
+                //
+                // The upload is performed in a separate task, to avoid an
+                // unavailable object store stalling this ticker.
+                tokio::spawn({
+                    let replicator = replicator.clone();
+                    let wal_reference_handle = wal_reference_handle.clone();
+                    let stats = stats.clone();
+                    async move {
+                        replicator.upload(&stats).await;
+                        wal_reference_handle
+                            .enqueue_rotated_file(stats.id(), ids)
+                            .await;
+                    }
+                });
+            }
+            None => {
+                wal_reference_handle
+                    .enqueue_rotated_file(stats.id(), ids)
+                    .await;
+            }
+        }
 
         // Do not block the ticker while partitions are persisted to ensure
         // timely ticking.
@@ -184,6 +211,7 @@ mod tests {
             wal_reference_handle.clone(),
             vec![Arc::clone(&p)],
             Arc::clone(&persist_handle),
+            None,
         ));
 
         tokio::time::pause();
@@ -337,6 +365,7 @@ mod tests {
             wal_reference_handle,
             vec![Arc::clone(&p)],
             Arc::clone(&persist_handle),
+            None,
         ));
 
         tokio::time::pause();
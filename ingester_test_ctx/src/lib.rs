@@ -163,10 +163,13 @@ impl TestContextBuilder {
             persist_background_fetch_time,
             dir.path().to_owned(),
             wal_rotation_period,
+            None,
             persist_executor,
             persist_workers,
             max_persist_queue_depth,
             persist_hot_partition_cost,
+            None,
+            None,
             storage.clone(),
             GossipConfig::default(),
             NonZeroUsize::new(usize::MAX).unwrap(),
@@ -0,0 +1,18 @@
+-- The legacy tombstone table was keyed on (shard, sequence number) and is no
+-- longer written to. Replace it with a table recording deletes by wall-clock
+-- creation time.
+DROP TABLE IF EXISTS tombstone;
+
+CREATE TABLE IF NOT EXISTS tombstone
+(
+    id                   BIGINT GENERATED ALWAYS AS IDENTITY
+        CONSTRAINT tombstone_pkey PRIMARY KEY,
+    table_id             BIGINT NOT NULL
+        REFERENCES table_name (id) ON DELETE CASCADE,
+    min_time             BIGINT NOT NULL,
+    max_time             BIGINT NOT NULL,
+    serialized_predicate TEXT   NOT NULL,
+    created_at           BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS tombstone_table_id_idx ON tombstone (table_id);
@@ -0,0 +1,11 @@
+-- Time-bounded claims of compaction jobs on partitions, allowing several
+-- compactors to schedule compaction jobs without static sharding.
+CREATE TABLE IF NOT EXISTS compaction_lease
+(
+    partition_id BIGINT NOT NULL
+        CONSTRAINT compaction_lease_pkey PRIMARY KEY
+        REFERENCES partition (id) ON DELETE CASCADE,
+    job_id       UUID   NOT NULL,
+    holder       TEXT   NOT NULL,
+    expires_at   BIGINT NOT NULL
+);
@@ -0,0 +1,23 @@
+-- API tokens of the built-in authorizer. Only a hash of the secret of a
+-- token is stored.
+CREATE TABLE IF NOT EXISTS auth_token
+(
+    id          BIGINT GENERATED ALWAYS AS IDENTITY
+        CONSTRAINT auth_token_pkey PRIMARY KEY,
+    token_hash  BYTEA  NOT NULL
+        CONSTRAINT auth_token_hash_unique UNIQUE,
+    description TEXT   NOT NULL,
+    created_at  BIGINT NOT NULL,
+    revoked_at  BIGINT
+);
+
+-- The actions a token grants on namespaces, by name, as a token may be
+-- created before the namespaces it grants access to.
+CREATE TABLE IF NOT EXISTS auth_token_grant
+(
+    token_id       BIGINT   NOT NULL
+        REFERENCES auth_token (id) ON DELETE CASCADE,
+    namespace_name TEXT     NOT NULL,
+    action         SMALLINT NOT NULL,
+    CONSTRAINT auth_token_grant_pkey PRIMARY KEY (token_id, namespace_name, action)
+);
@@ -0,0 +1,9 @@
+-- Grants of API tokens may be scoped to the tables of a namespace matching a
+-- table name or pattern. An empty table name grants access to the whole
+-- namespace, as part of the primary key it cannot be NULL.
+ALTER TABLE auth_token_grant
+    ADD COLUMN IF NOT EXISTS table_name TEXT NOT NULL DEFAULT '';
+
+ALTER TABLE auth_token_grant
+    DROP CONSTRAINT auth_token_grant_pkey,
+    ADD CONSTRAINT auth_token_grant_pkey PRIMARY KEY (token_id, namespace_name, table_name, action);
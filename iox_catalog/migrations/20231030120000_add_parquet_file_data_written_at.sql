@@ -0,0 +1,15 @@
+-- Tombstones are matched against the time the data of a parquet file was
+-- written, which may be long before the file was added to the catalog.
+-- `created_at` stays the time the file was added, as the partition
+-- new_file_at trigger and the compaction scheduler rely on it.
+--
+-- Existing files use their creation time.
+ALTER TABLE IF EXISTS parquet_file
+    ADD COLUMN IF NOT EXISTS data_written_at BIGINT;
+
+UPDATE parquet_file
+SET data_written_at = created_at
+WHERE data_written_at IS NULL;
+
+ALTER TABLE IF EXISTS parquet_file
+    ALTER COLUMN data_written_at SET NOT NULL;
@@ -0,0 +1,21 @@
+-- The legacy tombstone table was keyed on (shard, sequence number) and is no
+-- longer written to. Replace it with a table recording deletes by wall-clock
+-- creation time.
+DROP TABLE IF EXISTS processed_tombstone;
+DROP TABLE IF EXISTS tombstone;
+
+CREATE TABLE IF NOT EXISTS tombstone
+(
+    id                   INTEGER
+        CONSTRAINT tombstone_pkey
+            PRIMARY KEY AUTOINCREMENT,
+    table_id             numeric NOT NULL
+        REFERENCES table_name
+            ON DELETE CASCADE,
+    min_time             numeric NOT NULL,
+    max_time             numeric NOT NULL,
+    serialized_predicate text    NOT NULL,
+    created_at           numeric NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS tombstone_table_id_idx ON tombstone (table_id);
@@ -0,0 +1,13 @@
+-- Time-bounded claims of compaction jobs on partitions, allowing several
+-- compactors to schedule compaction jobs without static sharding.
+CREATE TABLE IF NOT EXISTS compaction_lease
+(
+    partition_id numeric NOT NULL
+        CONSTRAINT compaction_lease_pkey
+            PRIMARY KEY
+        REFERENCES partition
+            ON DELETE CASCADE,
+    job_id       uuid    NOT NULL,
+    holder       text    NOT NULL,
+    expires_at   numeric NOT NULL
+);
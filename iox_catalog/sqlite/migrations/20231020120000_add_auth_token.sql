@@ -0,0 +1,27 @@
+-- API tokens of the built-in authorizer. Only a hash of the secret of a
+-- token is stored.
+CREATE TABLE IF NOT EXISTS auth_token
+(
+    id          INTEGER
+        CONSTRAINT auth_token_pkey
+            PRIMARY KEY AUTOINCREMENT,
+    token_hash  blob    NOT NULL
+        CONSTRAINT auth_token_hash_unique
+            UNIQUE,
+    description text    NOT NULL,
+    created_at  numeric NOT NULL,
+    revoked_at  numeric
+);
+
+-- The actions a token grants on namespaces, by name, as a token may be
+-- created before the namespaces it grants access to.
+CREATE TABLE IF NOT EXISTS auth_token_grant
+(
+    token_id       numeric NOT NULL
+        REFERENCES auth_token
+            ON DELETE CASCADE,
+    namespace_name text    NOT NULL,
+    action         smallint NOT NULL,
+    CONSTRAINT auth_token_grant_pkey
+        PRIMARY KEY (token_id, namespace_name, action)
+);
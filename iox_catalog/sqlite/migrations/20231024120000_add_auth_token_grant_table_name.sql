@@ -0,0 +1,24 @@
+-- Grants of API tokens may be scoped to the tables of a namespace matching a
+-- table name or pattern. An empty table name grants access to the whole
+-- namespace, as part of the primary key it cannot be NULL.
+--
+-- SQLite cannot change the primary key of a table, so the table is recreated.
+CREATE TABLE IF NOT EXISTS auth_token_grant_new
+(
+    token_id       numeric  NOT NULL
+        REFERENCES auth_token
+            ON DELETE CASCADE,
+    namespace_name text     NOT NULL,
+    table_name     text     NOT NULL DEFAULT '',
+    action         smallint NOT NULL,
+    CONSTRAINT auth_token_grant_pkey
+        PRIMARY KEY (token_id, namespace_name, table_name, action)
+);
+
+INSERT INTO auth_token_grant_new ( token_id, namespace_name, action )
+SELECT token_id, namespace_name, action
+FROM auth_token_grant;
+
+DROP TABLE auth_token_grant;
+
+ALTER TABLE auth_token_grant_new RENAME TO auth_token_grant;
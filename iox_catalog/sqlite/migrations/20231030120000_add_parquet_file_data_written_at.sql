@@ -0,0 +1,11 @@
+-- Tombstones are matched against the time the data of a parquet file was
+-- written, which may be long before the file was added to the catalog.
+-- `created_at` stays the time the file was added, as the partition
+-- new_file_at trigger and the compaction scheduler rely on it.
+--
+-- Existing files use their creation time.
+ALTER TABLE parquet_file
+    ADD COLUMN data_written_at numeric default 0 not null;
+
+UPDATE parquet_file
+SET data_written_at = COALESCE(created_at, 0);
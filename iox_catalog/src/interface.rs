@@ -3,11 +3,12 @@
 use async_trait::async_trait;
 use data_types::{
     partition_template::{NamespacePartitionTemplateOverride, TablePartitionTemplateOverride},
-    Column, ColumnType, ColumnsByName, CompactionLevel, MaxColumnsPerTable, MaxTables, Namespace,
-    NamespaceId, NamespaceName, NamespaceSchema, NamespaceServiceProtectionLimitsOverride,
-    ParquetFile, ParquetFileId, ParquetFileParams, Partition, PartitionHashId, PartitionId,
-    PartitionKey, SkippedCompaction, SortedColumnSet, Table, TableId, TableSchema, Timestamp,
-    TransitionPartitionId,
+    AuthToken, AuthTokenGrant, AuthTokenId, Column, ColumnType, ColumnsByName, CompactionLease,
+    CompactionLevel, DeletePredicate, MaxColumnsPerTable, MaxTables, Namespace, NamespaceId,
+    NamespaceName, NamespaceSchema, NamespaceServiceProtectionLimitsOverride, ParquetFile,
+    ParquetFileId, ParquetFileParams, Partition, PartitionHashId, PartitionId, PartitionKey,
+    SkippedCompaction, SortedColumnSet, Table, TableId, TableSchema, Timestamp, Tombstone,
+    TombstoneId, TransitionPartitionId,
 };
 use iox_time::TimeProvider;
 use snafu::{OptionExt, Snafu};
@@ -15,6 +16,7 @@ use std::{
     collections::{BTreeMap, HashMap, HashSet},
     fmt::{Debug, Display},
     sync::Arc,
+    time::Duration,
 };
 use uuid::Uuid;
 
@@ -152,6 +154,12 @@ pub enum Error {
 
     #[snafu(display("could not delete namespace: {source}"))]
     CouldNotDeleteNamespace { source: sqlx::Error },
+
+    #[snafu(display("tombstone {} not found", id))]
+    TombstoneNotFound { id: TombstoneId },
+
+    #[snafu(display("an API token with the same hash already exists"))]
+    AuthTokenExists,
 }
 
 /// A specialized `Error` for Catalog errors
@@ -249,6 +257,15 @@ pub trait RepoCollection: Send + Sync + Debug {
 
     /// Repository for [Parquet files](data_types::ParquetFile).
     fn parquet_files(&mut self) -> &mut dyn ParquetFileRepo;
+
+    /// Repository for [tombstones](data_types::Tombstone).
+    fn tombstones(&mut self) -> &mut dyn TombstoneRepo;
+
+    /// Repository for [compaction leases](data_types::CompactionLease).
+    fn compaction_leases(&mut self) -> &mut dyn CompactionLeaseRepo;
+
+    /// Repository for [API tokens](data_types::AuthToken).
+    fn auth_tokens(&mut self) -> &mut dyn AuthTokenRepo;
 }
 
 /// Functions for working with namespaces in the catalog
@@ -452,6 +469,13 @@ pub trait PartitionRepo: Send + Sync {
     /// List the records of compacting a partition being skipped. This is mostly useful for testing.
     async fn list_skipped_compactions(&mut self) -> Result<Vec<SkippedCompaction>>;
 
+    /// List the records of compacting a partition being skipped, for the partitions of the given
+    /// namespace.
+    async fn list_skipped_compactions_by_namespace_id(
+        &mut self,
+        namespace_id: NamespaceId,
+    ) -> Result<Vec<SkippedCompaction>>;
+
     /// Delete the records of skipping a partition being compacted.
     async fn delete_skipped_compactions(
         &mut self,
@@ -544,6 +568,119 @@ pub trait ParquetFileRepo: Send + Sync {
     ) -> Result<Vec<ParquetFileId>>;
 }
 
+/// Functions for working with tombstones (recorded deletes) in the catalog
+#[async_trait]
+pub trait TombstoneRepo: Send + Sync {
+    /// Record a delete of all rows of the given table matching `predicate`.
+    ///
+    /// The tombstone's creation time is set by the catalog; only data written before that time is
+    /// affected by the delete.
+    async fn create(&mut self, table_id: TableId, predicate: &DeletePredicate)
+        -> Result<Tombstone>;
+
+    /// The current time of the catalog clock.
+    ///
+    /// [`Tombstone::created_at`] is assigned from this clock, so any timestamp compared against it
+    /// (such as a parquet file's `data_written_at`) must be derived from this clock too, never from the
+    /// local clock of the calling host.
+    async fn catalog_time(&mut self) -> Result<Timestamp>;
+
+    /// List all tombstones that still apply to at least one parquet file not marked for deletion,
+    /// ordered by ID.
+    async fn list_unapplied(&mut self) -> Result<Vec<Tombstone>>;
+
+    /// List the partitions holding parquet files, not marked for deletion, that a tombstone still
+    /// applies to.
+    async fn partitions_with_unapplied(&mut self) -> Result<Vec<PartitionId>>;
+
+    /// List all tombstones for the given table, ordered by ID.
+    async fn list_by_table_id(&mut self, table_id: TableId) -> Result<Vec<Tombstone>>;
+
+    /// List all tombstones for the given namespace, ordered by ID.
+    async fn list_by_namespace_id(&mut self, namespace_id: NamespaceId) -> Result<Vec<Tombstone>>;
+
+    /// Remove tombstones that were created before `older_than`.
+    ///
+    /// This must only be called once all data affected by those tombstones has been physically
+    /// removed. Returns the IDs of the removed tombstones.
+    async fn remove_created_before(&mut self, older_than: Timestamp) -> Result<Vec<TombstoneId>>;
+}
+
+/// Functions for working with compaction leases in the catalog
+#[async_trait]
+pub trait CompactionLeaseRepo: Send + Sync {
+    /// Acquire a lease on the given partition for the compaction job `job_id`, run by `holder`.
+    ///
+    /// The lease expires `duration` from now, as measured by the catalog. Acquiring a lease the job
+    /// already holds renews it. Returns `None` if another job holds an unexpired lease on the
+    /// partition.
+    async fn acquire(
+        &mut self,
+        partition_id: PartitionId,
+        job_id: Uuid,
+        holder: &str,
+        duration: Duration,
+    ) -> Result<Option<CompactionLease>>;
+
+    /// Extend the lease of the job `job_id` on the given partition to expire `duration` from now.
+    ///
+    /// Returns `None` if the job does not hold the lease, e.g. because it expired and was acquired
+    /// by another job in the meantime.
+    async fn renew(
+        &mut self,
+        partition_id: PartitionId,
+        job_id: Uuid,
+        duration: Duration,
+    ) -> Result<Option<CompactionLease>>;
+
+    /// Release the lease of the job `job_id` on the given partition, if it holds it. Returns `true`
+    /// if a lease was released.
+    async fn release(&mut self, partition_id: PartitionId, job_id: Uuid) -> Result<bool>;
+
+    /// List all leases, including expired ones, ordered by partition ID.
+    async fn list(&mut self) -> Result<Vec<CompactionLease>>;
+
+    /// Apply the changes of [`ParquetFileRepo::create_upgrade_delete`] for the compaction job
+    /// `job_id` on the given partition, if the job holds an unexpired lease on it.
+    ///
+    /// The lease is checked in the same transaction as the changes are made, so a job that lost its
+    /// lease cannot commit. Returns `None`, without changing any file, if the job does not hold the
+    /// lease.
+    async fn commit(
+        &mut self,
+        partition_id: PartitionId,
+        job_id: Uuid,
+        delete: &[ParquetFileId],
+        upgrade: &[ParquetFileId],
+        create: &[ParquetFileParams],
+        target_level: CompactionLevel,
+    ) -> Result<Option<Vec<ParquetFileId>>>;
+}
+
+/// Functions for working with API tokens in the catalog
+#[async_trait]
+pub trait AuthTokenRepo: Send + Sync {
+    /// Create a token granting `grants`, identified by the hash `token_hash` of its secret.
+    async fn create(
+        &mut self,
+        token_hash: &[u8],
+        description: &str,
+        grants: &[AuthTokenGrant],
+    ) -> Result<AuthToken>;
+
+    /// Get the token identified by the hash `token_hash` of its secret, whether or not it was
+    /// revoked.
+    async fn get_by_hash(&mut self, token_hash: &[u8]) -> Result<Option<AuthToken>>;
+
+    /// List all tokens, including revoked ones, ordered by ID.
+    async fn list(&mut self) -> Result<Vec<AuthToken>>;
+
+    /// Revoke the token with the given ID, or return `None` if it does not exist.
+    ///
+    /// Revoking a revoked token leaves its revocation time unchanged.
+    async fn revoke(&mut self, id: AuthTokenId) -> Result<Option<AuthToken>>;
+}
+
 /// Gets the namespace schema including all tables and columns.
 pub async fn get_schema_by_id<R>(
     id: NamespaceId,
@@ -656,6 +793,39 @@ where
     Ok(ColumnsByName::new(columns))
 }
 
+/// Record a delete of all rows matching `predicate` in the given namespace.
+///
+/// The delete is limited to the table named `table_name`, if specified, and applies to all tables
+/// of the namespace otherwise. Deleting from a table that does not exist is a no-op.
+///
+/// Returns the created [`Tombstone`]s.
+pub async fn delete_by_predicate<R>(
+    namespace_id: NamespaceId,
+    table_name: Option<&str>,
+    predicate: &DeletePredicate,
+    repos: &mut R,
+) -> Result<Vec<Tombstone>>
+where
+    R: RepoCollection + ?Sized,
+{
+    let tables = match table_name {
+        Some(name) => repos
+            .tables()
+            .get_by_namespace_and_name(namespace_id, name)
+            .await?
+            .into_iter()
+            .collect(),
+        None => repos.tables().list_by_namespace_id(namespace_id).await?,
+    };
+
+    let mut tombstones = Vec::with_capacity(tables.len());
+    for table in tables {
+        tombstones.push(repos.tombstones().create(table.id, predicate).await?);
+    }
+
+    Ok(tombstones)
+}
+
 /// Fetch all [`NamespaceSchema`] in the catalog.
 ///
 /// This method performs the minimal number of queries needed to build the
@@ -775,7 +945,9 @@ pub(crate) mod test_helpers {
     use super::*;
     use ::test_helpers::assert_error;
     use assert_matches::assert_matches;
-    use data_types::{ColumnId, CompactionLevel, MaxColumnsPerTable, MaxTables};
+    use data_types::{
+        ColumnId, CompactionLevel, DeleteExpr, MaxColumnsPerTable, MaxTables, TimestampRange,
+    };
     use futures::Future;
     use generated_types::influxdata::iox::partition_template::v1 as proto;
     use metric::{Attributes, DurationHistogram, Metric};
@@ -798,6 +970,9 @@ pub(crate) mod test_helpers {
         test_list_schemas(clean_state().await).await;
         test_list_schemas_soft_deleted_rows(clean_state().await).await;
         test_delete_namespace(clean_state().await).await;
+        test_tombstone(clean_state().await).await;
+        test_compaction_lease(clean_state().await).await;
+        test_auth_token(clean_state().await).await;
 
         let catalog = clean_state().await;
         test_namespace(Arc::clone(&catalog)).await;
@@ -818,6 +993,18 @@ pub(crate) mod test_helpers {
         let catalog = clean_state().await;
         test_parquet_file(Arc::clone(&catalog)).await;
         assert_metric_hit(&catalog.metrics(), "parquet_create");
+
+        let catalog = clean_state().await;
+        test_tombstone(Arc::clone(&catalog)).await;
+        assert_metric_hit(&catalog.metrics(), "tombstone_create");
+
+        let catalog = clean_state().await;
+        test_compaction_lease(Arc::clone(&catalog)).await;
+        assert_metric_hit(&catalog.metrics(), "compaction_lease_acquire");
+
+        let catalog = clean_state().await;
+        test_auth_token(Arc::clone(&catalog)).await;
+        assert_metric_hit(&catalog.metrics(), "auth_token_create");
     }
 
     async fn test_setup(catalog: Arc<dyn Catalog>) {
@@ -1931,6 +2118,22 @@ pub(crate) mod test_helpers {
         assert_eq!(skipped_compactions[0].limit_num_files, 2);
         assert_eq!(skipped_compactions[0].estimated_bytes, 10);
         assert_eq!(skipped_compactions[0].limit_bytes, 20);
+        // skipped compactions can be listed by namespace
+        let skipped_compactions = repos
+            .partitions()
+            .list_skipped_compactions_by_namespace_id(namespace.id)
+            .await
+            .unwrap();
+        assert_eq!(skipped_compactions.len(), 1);
+        assert_eq!(skipped_compactions[0].partition_id, to_skip_partition.id);
+        let other_namespace =
+            arbitrary_namespace(&mut *repos, "namespace_partition_test_other").await;
+        let skipped_compactions = repos
+            .partitions()
+            .list_skipped_compactions_by_namespace_id(other_namespace.id)
+            .await
+            .unwrap();
+        assert!(skipped_compactions.is_empty());
         //
         let skipped_partition_records = repos
             .partitions()
@@ -3375,6 +3578,516 @@ pub(crate) mod test_helpers {
         (namespace, schema)
     }
 
+    async fn test_tombstone(catalog: Arc<dyn Catalog>) {
+        let mut repos = catalog.repositories().await;
+        let namespace_1 = arbitrary_namespace(&mut *repos, "namespace_test_tombstone_1").await;
+        let table_1 = arbitrary_table(&mut *repos, "test_table_1", &namespace_1).await;
+        let table_2 = arbitrary_table(&mut *repos, "test_table_2", &namespace_1).await;
+        let namespace_2 = arbitrary_namespace(&mut *repos, "namespace_test_tombstone_2").await;
+        let table_3 = arbitrary_table(&mut *repos, "test_table_3", &namespace_2).await;
+
+        let predicate = DeletePredicate {
+            range: TimestampRange::new(10, 100),
+            exprs: vec![DeleteExpr::new(
+                "region".to_string(),
+                data_types::Op::Eq,
+                data_types::Scalar::String("west".to_string()),
+            )],
+        };
+
+        let t1 = repos
+            .tombstones()
+            .create(table_1.id, &predicate)
+            .await
+            .unwrap();
+        assert_eq!(t1.table_id, table_1.id);
+        assert_eq!(t1.min_time, Timestamp::new(10));
+        assert_eq!(t1.max_time, Timestamp::new(100));
+        assert_eq!(t1.serialized_predicate, r#""region"='west'"#);
+
+        let t2 = repos
+            .tombstones()
+            .create(table_2.id, &predicate)
+            .await
+            .unwrap();
+        let t3 = repos
+            .tombstones()
+            .create(table_3.id, &predicate)
+            .await
+            .unwrap();
+        assert!(t1.id < t2.id);
+        assert!(t2.id < t3.id);
+        assert!(t1.created_at <= t2.created_at);
+
+        let listed = repos
+            .tombstones()
+            .list_by_table_id(table_1.id)
+            .await
+            .unwrap();
+        assert_eq!(listed, vec![t1.clone()]);
+
+        let listed = repos
+            .tombstones()
+            .list_by_namespace_id(namespace_1.id)
+            .await
+            .unwrap();
+        assert_eq!(listed, vec![t1.clone(), t2.clone()]);
+
+        // creating a tombstone for an unknown table fails
+        let err = repos
+            .tombstones()
+            .create(TableId::new(i64::MAX), &predicate)
+            .await
+            .unwrap_err();
+        assert_matches!(
+            err,
+            Error::ForeignKeyViolation { .. } | Error::TableNotFound { .. }
+        );
+
+        // remove everything created before the last tombstone
+        let removed = repos
+            .tombstones()
+            .remove_created_before(t3.created_at)
+            .await
+            .unwrap();
+        assert!(!removed.contains(&t3.id));
+        assert!(repos
+            .tombstones()
+            .list_by_table_id(table_3.id)
+            .await
+            .unwrap()
+            .contains(&t3));
+
+        let removed = repos
+            .tombstones()
+            .remove_created_before(Timestamp::new(t3.created_at.get() + 1))
+            .await
+            .unwrap();
+        assert!(removed.contains(&t3.id));
+        assert!(repos
+            .tombstones()
+            .list_by_namespace_id(namespace_2.id)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(repos
+            .tombstones()
+            .list_by_namespace_id(namespace_1.id)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // the catalog clock assigns creation times
+        let now = repos.tombstones().catalog_time().await.unwrap();
+        assert!(now >= t3.created_at);
+
+        // a tombstone is unapplied while it covers a file that is not marked for deletion
+        let partition_1 = repos
+            .partitions()
+            .create_or_get("one".into(), table_1.id)
+            .await
+            .unwrap();
+        let partition_2 = repos
+            .partitions()
+            .create_or_get("one".into(), table_2.id)
+            .await
+            .unwrap();
+        let covered = repos
+            .parquet_files()
+            .create(arbitrary_parquet_file_params(
+                &namespace_1,
+                &table_1,
+                &partition_1,
+            ))
+            .await
+            .unwrap();
+        let outside_time_range = repos
+            .parquet_files()
+            .create(ParquetFileParams {
+                min_time: Timestamp::new(200),
+                max_time: Timestamp::new(300),
+                ..arbitrary_parquet_file_params(&namespace_1, &table_2, &partition_2)
+            })
+            .await
+            .unwrap();
+        assert!(repos
+            .tombstones()
+            .list_unapplied()
+            .await
+            .unwrap()
+            .is_empty());
+
+        let t4 = repos
+            .tombstones()
+            .create(table_1.id, &predicate)
+            .await
+            .unwrap();
+        let t5 = repos
+            .tombstones()
+            .create(table_2.id, &predicate)
+            .await
+            .unwrap();
+        assert!(t4.created_at >= now);
+        let unapplied = repos.tombstones().list_unapplied().await.unwrap();
+        assert_eq!(unapplied, vec![t4.clone()]);
+        let partitions = repos
+            .tombstones()
+            .partitions_with_unapplied()
+            .await
+            .unwrap();
+        assert_eq!(partitions, vec![partition_1.id]);
+
+        // once the covered file is replaced, the tombstone no longer applies
+        repos
+            .parquet_files()
+            .create_upgrade_delete(&[covered.id], &[], &[], CompactionLevel::Initial)
+            .await
+            .unwrap();
+        assert!(repos
+            .tombstones()
+            .list_unapplied()
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(repos
+            .tombstones()
+            .partitions_with_unapplied()
+            .await
+            .unwrap()
+            .is_empty());
+
+        let mut removed = repos
+            .tombstones()
+            .remove_created_before(Timestamp::new(t5.created_at.get() + 1))
+            .await
+            .unwrap();
+        removed.sort();
+        assert_eq!(removed, vec![t4.id, t5.id]);
+        repos
+            .parquet_files()
+            .create_upgrade_delete(&[outside_time_range.id], &[], &[], CompactionLevel::Initial)
+            .await
+            .unwrap();
+
+        // deletes for a whole namespace create a tombstone per table
+        let created = delete_by_predicate(namespace_1.id, None, &predicate, &mut *repos)
+            .await
+            .unwrap();
+        let mut table_ids = created.iter().map(|t| t.table_id).collect::<Vec<_>>();
+        table_ids.sort();
+        assert_eq!(table_ids, vec![table_1.id, table_2.id]);
+
+        let created = delete_by_predicate(
+            namespace_1.id,
+            Some("test_table_2"),
+            &predicate,
+            &mut *repos,
+        )
+        .await
+        .unwrap();
+        assert_matches!(created.as_slice(), [t] if t.table_id == table_2.id);
+
+        // deleting from an unknown table is a no-op
+        let created = delete_by_predicate(namespace_1.id, Some("missing"), &predicate, &mut *repos)
+            .await
+            .unwrap();
+        assert!(created.is_empty());
+    }
+
+    async fn test_compaction_lease(catalog: Arc<dyn Catalog>) {
+        let mut repos = catalog.repositories().await;
+        let namespace = arbitrary_namespace(&mut *repos, "namespace_compaction_lease_test").await;
+        let table = arbitrary_table(&mut *repos, "test_table", &namespace).await;
+        let partition_1 = repos
+            .partitions()
+            .create_or_get("one".into(), table.id)
+            .await
+            .unwrap();
+        let partition_2 = repos
+            .partitions()
+            .create_or_get("two".into(), table.id)
+            .await
+            .unwrap();
+
+        let job_1 = Uuid::new_v4();
+        let job_2 = Uuid::new_v4();
+        let hour = Duration::from_secs(3600);
+
+        assert!(repos.compaction_leases().list().await.unwrap().is_empty());
+
+        // acquire a lease
+        let before = Timestamp::from(catalog.time_provider().now());
+        let lease = repos
+            .compaction_leases()
+            .acquire(partition_1.id, job_1, "compactor-1", hour)
+            .await
+            .unwrap()
+            .expect("lease acquired");
+        assert_eq!(lease.partition_id, partition_1.id);
+        assert_eq!(lease.job_id, job_1);
+        assert_eq!(lease.holder, "compactor-1");
+        assert!(lease.expires_at.get() >= before.get() + hour.as_nanos() as i64);
+
+        // another job cannot acquire it while it is valid
+        let acquired = repos
+            .compaction_leases()
+            .acquire(partition_1.id, job_2, "compactor-2", hour)
+            .await
+            .unwrap();
+        assert_eq!(acquired, None);
+
+        // ... but can acquire leases on other partitions
+        let lease_2 = repos
+            .compaction_leases()
+            .acquire(partition_2.id, job_2, "compactor-2", hour)
+            .await
+            .unwrap()
+            .expect("lease acquired");
+
+        // the same job can acquire its lease again
+        let reacquired = repos
+            .compaction_leases()
+            .acquire(partition_1.id, job_1, "compactor-1", hour)
+            .await
+            .unwrap()
+            .expect("lease acquired");
+        assert_eq!(reacquired.job_id, job_1);
+
+        // only the holder can renew and release a lease
+        let renewed = repos
+            .compaction_leases()
+            .renew(partition_1.id, job_2, hour)
+            .await
+            .unwrap();
+        assert_eq!(renewed, None);
+        let released = repos
+            .compaction_leases()
+            .release(partition_1.id, job_2)
+            .await
+            .unwrap();
+        assert!(!released);
+
+        let renewed = repos
+            .compaction_leases()
+            .renew(partition_1.id, job_1, hour)
+            .await
+            .unwrap()
+            .expect("lease renewed");
+        assert!(renewed.expires_at >= reacquired.expires_at);
+
+        let leases = repos.compaction_leases().list().await.unwrap();
+        assert_eq!(leases, vec![renewed, lease_2.clone()]);
+
+        // released leases can be acquired by other jobs
+        let released = repos
+            .compaction_leases()
+            .release(partition_1.id, job_1)
+            .await
+            .unwrap();
+        assert!(released);
+        let renewed = repos
+            .compaction_leases()
+            .renew(partition_1.id, job_1, hour)
+            .await
+            .unwrap();
+        assert_eq!(renewed, None);
+        repos
+            .compaction_leases()
+            .acquire(partition_1.id, job_2, "compactor-2", Duration::ZERO)
+            .await
+            .unwrap()
+            .expect("lease acquired");
+
+        // expired leases can be acquired by other jobs
+        let lease = repos
+            .compaction_leases()
+            .acquire(partition_1.id, job_1, "compactor-1", hour)
+            .await
+            .unwrap()
+            .expect("lease acquired");
+        assert_eq!(lease.job_id, job_1);
+
+        let leases = repos.compaction_leases().list().await.unwrap();
+        assert_eq!(leases, vec![lease, lease_2]);
+
+        // leasing an unknown partition fails
+        let err = repos
+            .compaction_leases()
+            .acquire(PartitionId::new(i64::MAX), job_1, "compactor-1", hour)
+            .await
+            .unwrap_err();
+        assert_matches!(
+            err,
+            Error::ForeignKeyViolation { .. } | Error::PartitionNotFound { .. }
+        );
+
+        // only the job holding the lease can commit file changes
+        let file = arbitrary_parquet_file_params(&namespace, &table, &partition_1);
+        let ids = repos
+            .compaction_leases()
+            .commit(
+                partition_1.id,
+                job_2,
+                &[],
+                &[],
+                &[file.clone()],
+                CompactionLevel::FileNonOverlapped,
+            )
+            .await
+            .unwrap();
+        assert_eq!(ids, None);
+        let files = repos
+            .parquet_files()
+            .list_by_partition_not_to_delete(&partition_1.transition_partition_id())
+            .await
+            .unwrap();
+        assert!(files.is_empty());
+
+        let ids = repos
+            .compaction_leases()
+            .commit(
+                partition_1.id,
+                job_1,
+                &[],
+                &[],
+                &[file],
+                CompactionLevel::FileNonOverlapped,
+            )
+            .await
+            .unwrap()
+            .expect("lease held");
+        let files = repos
+            .parquet_files()
+            .list_by_partition_not_to_delete(&partition_1.transition_partition_id())
+            .await
+            .unwrap();
+        assert_eq!(files.iter().map(|f| f.id).collect::<Vec<_>>(), ids);
+
+        // expired leases do not allow commits
+        repos
+            .compaction_leases()
+            .acquire(partition_2.id, job_2, "compactor-2", Duration::ZERO)
+            .await
+            .unwrap()
+            .expect("lease acquired");
+        let ids = repos
+            .compaction_leases()
+            .commit(
+                partition_2.id,
+                job_2,
+                &[],
+                &[],
+                &[arbitrary_parquet_file_params(
+                    &namespace,
+                    &table,
+                    &partition_2,
+                )],
+                CompactionLevel::FileNonOverlapped,
+            )
+            .await
+            .unwrap();
+        assert_eq!(ids, None);
+    }
+
+    async fn test_auth_token(catalog: Arc<dyn Catalog>) {
+        let mut repos = catalog.repositories().await;
+
+        assert!(repos.auth_tokens().list().await.unwrap().is_empty());
+
+        let grant = |s: &str| s.parse::<AuthTokenGrant>().unwrap();
+
+        // grants are deduplicated and ordered
+        let before = Timestamp::from(catalog.time_provider().now());
+        let token_1 = repos
+            .auth_tokens()
+            .create(
+                b"hash-1",
+                "reader",
+                &[grant("b:read"), grant("a:read"), grant("b:read")],
+            )
+            .await
+            .unwrap();
+        assert_eq!(token_1.description, "reader");
+        assert!(token_1.created_at >= before);
+        assert_eq!(token_1.revoked_at, None);
+        assert_eq!(token_1.grants, vec![grant("a:read"), grant("b:read")]);
+
+        // a token may grant nothing
+        let token_2 = repos
+            .auth_tokens()
+            .create(b"hash-2", "nothing", &[])
+            .await
+            .unwrap();
+        assert!(token_2.grants.is_empty());
+        assert_ne!(token_1.id, token_2.id);
+
+        let token_3 = repos
+            .auth_tokens()
+            .create(
+                b"hash-3",
+                "writer",
+                &[grant("a:cpu_*:read"), grant("a:write"), grant("a:read")],
+            )
+            .await
+            .unwrap();
+        // grants of a whole namespace are ordered before grants of its tables
+        assert_eq!(
+            token_3.grants,
+            vec![grant("a:read"), grant("a:write"), grant("a:cpu_*:read")]
+        );
+
+        let err = repos
+            .auth_tokens()
+            .create(b"hash-1", "duplicate", &[])
+            .await
+            .unwrap_err();
+        assert_matches!(err, Error::AuthTokenExists);
+
+        let got = repos.auth_tokens().get_by_hash(b"hash-1").await.unwrap();
+        assert_eq!(got.as_ref(), Some(&token_1));
+        let got = repos.auth_tokens().get_by_hash(b"hash-3").await.unwrap();
+        assert_eq!(got.as_ref(), Some(&token_3));
+        let got = repos.auth_tokens().get_by_hash(b"nope").await.unwrap();
+        assert_eq!(got, None);
+
+        let tokens = repos.auth_tokens().list().await.unwrap();
+        assert_eq!(
+            tokens,
+            vec![token_1.clone(), token_2.clone(), token_3.clone()]
+        );
+
+        // revoked tokens are still returned, with their revocation time
+        let revoked = repos
+            .auth_tokens()
+            .revoke(token_1.id)
+            .await
+            .unwrap()
+            .expect("token exists");
+        assert!(revoked.is_revoked());
+        assert_eq!(revoked.grants, token_1.grants);
+        let got = repos.auth_tokens().get_by_hash(b"hash-1").await.unwrap();
+        assert_eq!(got.as_ref(), Some(&revoked));
+
+        // revoking a token again leaves it unchanged
+        let revoked_again = repos
+            .auth_tokens()
+            .revoke(token_1.id)
+            .await
+            .unwrap()
+            .expect("token exists");
+        assert_eq!(revoked_again, revoked);
+
+        let missing = repos
+            .auth_tokens()
+            .revoke(AuthTokenId::new(i64::MAX))
+            .await
+            .unwrap();
+        assert_eq!(missing, None);
+
+        let tokens = repos.auth_tokens().list().await.unwrap();
+        assert_eq!(tokens, vec![revoked, token_2, token_3]);
+    }
+
     async fn test_list_schemas(catalog: Arc<dyn Catalog>) {
         let mut repos = catalog.repositories().await;
 
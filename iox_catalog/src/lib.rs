@@ -421,6 +421,7 @@ pub mod test_helpers {
             row_count: 0,
             compaction_level: CompactionLevel::Initial,
             created_at: Timestamp::new(1),
+            data_written_at: Timestamp::new(1),
             column_set: ColumnSet::new([ColumnId::new(1), ColumnId::new(2)]),
             max_l0_created_at: Timestamp::new(1),
         }
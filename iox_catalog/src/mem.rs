@@ -3,8 +3,9 @@
 
 use crate::{
     interface::{
-        CasFailure, Catalog, ColumnRepo, ColumnTypeMismatchSnafu, Error, NamespaceRepo,
-        ParquetFileRepo, PartitionRepo, RepoCollection, Result, SoftDeletedRows, TableRepo,
+        AuthTokenRepo, CasFailure, Catalog, ColumnRepo, ColumnTypeMismatchSnafu,
+        CompactionLeaseRepo, Error, NamespaceRepo, ParquetFileRepo, PartitionRepo, RepoCollection,
+        Result, SoftDeletedRows, TableRepo, TombstoneRepo,
         MAX_PARQUET_FILES_SELECTED_ONCE_FOR_DELETE, MAX_PARQUET_FILES_SELECTED_ONCE_FOR_RETENTION,
     },
     metrics::MetricDecorator,
@@ -14,18 +15,20 @@ use data_types::{
     partition_template::{
         NamespacePartitionTemplateOverride, TablePartitionTemplateOverride, TemplatePart,
     },
-    Column, ColumnId, ColumnType, CompactionLevel, MaxColumnsPerTable, MaxTables, Namespace,
-    NamespaceId, NamespaceName, NamespaceServiceProtectionLimitsOverride, ParquetFile,
-    ParquetFileId, ParquetFileParams, Partition, PartitionHashId, PartitionId, PartitionKey,
-    SkippedCompaction, SortedColumnSet, Table, TableId, Timestamp, TransitionPartitionId,
+    AuthToken, AuthTokenGrant, AuthTokenId, Column, ColumnId, ColumnType, CompactionLease,
+    CompactionLevel, DeletePredicate, MaxColumnsPerTable, MaxTables, Namespace, NamespaceId,
+    NamespaceName, NamespaceServiceProtectionLimitsOverride, ParquetFile, ParquetFileId,
+    ParquetFileParams, Partition, PartitionHashId, PartitionId, PartitionKey, SkippedCompaction,
+    SortedColumnSet, Table, TableId, Timestamp, Tombstone, TombstoneId, TransitionPartitionId,
 };
 use iox_time::{SystemProvider, TimeProvider};
 use snafu::ensure;
 use sqlx::types::Uuid;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeSet, HashMap, HashSet},
     fmt::{Display, Formatter},
     sync::Arc,
+    time::Duration,
 };
 use tokio::sync::{Mutex, OwnedMutexGuard};
 
@@ -69,6 +72,11 @@ struct MemCollections {
     partitions: Vec<Partition>,
     skipped_compactions: Vec<SkippedCompaction>,
     parquet_files: Vec<ParquetFile>,
+    tombstones: Vec<Tombstone>,
+    next_tombstone_id: i64,
+    compaction_leases: Vec<CompactionLease>,
+    /// API tokens, with the hashes of their secrets
+    auth_tokens: Vec<(Vec<u8>, AuthToken)>,
 }
 
 /// transaction bound to an in-memory catalog.
@@ -138,6 +146,18 @@ impl RepoCollection for MemTxn {
     fn parquet_files(&mut self) -> &mut dyn ParquetFileRepo {
         self
     }
+
+    fn tombstones(&mut self) -> &mut dyn TombstoneRepo {
+        self
+    }
+
+    fn compaction_leases(&mut self) -> &mut dyn CompactionLeaseRepo {
+        self
+    }
+
+    fn auth_tokens(&mut self) -> &mut dyn AuthTokenRepo {
+        self
+    }
 }
 
 #[async_trait]
@@ -339,12 +359,15 @@ impl TableRepo for MemTxn {
 
         let table = table.clone();
 
-        // Partitioning is only supported for tags, so create tag columns for all `TagValue`
-        // partition template parts. It's important this happens within the table creation
-        // transaction so that there isn't a possibility of a concurrent write creating these
-        // columns with an unsupported type.
+        // Partitioning is only supported for tags, so create tag columns for all `TagValue`,
+        // `TagPrefix` and `Bucket` partition template parts. It's important this happens within
+        // the table creation transaction so that there isn't a possibility of a concurrent write
+        // creating these columns with an unsupported type.
         for template_part in table.partition_template.parts() {
-            if let TemplatePart::TagValue(tag_name) = template_part {
+            if let TemplatePart::TagValue(tag_name)
+            | TemplatePart::TagPrefix(tag_name, _)
+            | TemplatePart::Bucket(tag_name, _) = template_part
+            {
                 self.columns()
                     .create_or_get(tag_name, table.id, ColumnType::Tag)
                     .await?;
@@ -769,6 +792,31 @@ impl PartitionRepo for MemTxn {
         Ok(stage.skipped_compactions.clone())
     }
 
+    async fn list_skipped_compactions_by_namespace_id(
+        &mut self,
+        namespace_id: NamespaceId,
+    ) -> Result<Vec<SkippedCompaction>> {
+        let stage = self.stage();
+
+        let table_ids = stage
+            .tables
+            .iter()
+            .filter_map(|t| (t.namespace_id == namespace_id).then_some(t.id))
+            .collect::<HashSet<_>>();
+        let partition_ids = stage
+            .partitions
+            .iter()
+            .filter_map(|p| table_ids.contains(&p.table_id).then_some(p.id))
+            .collect::<HashSet<_>>();
+
+        Ok(stage
+            .skipped_compactions
+            .iter()
+            .filter(|s| partition_ids.contains(&s.partition_id))
+            .cloned()
+            .collect())
+    }
+
     async fn delete_skipped_compactions(
         &mut self,
         partition_id: PartitionId,
@@ -990,6 +1038,26 @@ impl ParquetFileRepo for MemTxn {
         create: &[ParquetFileParams],
         target_level: CompactionLevel,
     ) -> Result<Vec<ParquetFileId>> {
+        let ids = self
+            .create_upgrade_delete_leased(None, delete, upgrade, create, target_level)
+            .await?;
+        Ok(ids.expect("no lease to check"))
+    }
+}
+
+impl MemTxn {
+    /// Apply the changes of [`ParquetFileRepo::create_upgrade_delete`] at once.
+    ///
+    /// If `lease` is given, the changes are only applied if the given job holds an unexpired
+    /// lease on the given partition, returning `None` otherwise.
+    async fn create_upgrade_delete_leased(
+        &mut self,
+        lease: Option<(PartitionId, Uuid)>,
+        delete: &[ParquetFileId],
+        upgrade: &[ParquetFileId],
+        create: &[ParquetFileParams],
+        target_level: CompactionLevel,
+    ) -> Result<Option<Vec<ParquetFileId>>> {
         let delete_set = delete.iter().copied().collect::<HashSet<_>>();
         let upgrade_set = upgrade.iter().copied().collect::<HashSet<_>>();
 
@@ -1000,6 +1068,16 @@ impl ParquetFileRepo for MemTxn {
 
         let mut stage = self.inner.clone();
 
+        if let Some((partition_id, job_id)) = lease {
+            let now = Timestamp::from(self.time_provider.now());
+            let held = stage.compaction_leases.iter().any(|l| {
+                l.partition_id == partition_id && l.job_id == job_id && l.expires_at > now
+            });
+            if !held {
+                return Ok(None);
+            }
+        }
+
         for id in delete {
             let marked_at = Timestamp::from(self.time_provider.now());
             flag_for_delete(&mut stage, *id, marked_at).await?;
@@ -1015,7 +1093,288 @@ impl ParquetFileRepo for MemTxn {
 
         *self.inner = stage;
 
-        Ok(ids)
+        Ok(Some(ids))
+    }
+}
+
+#[async_trait]
+impl TombstoneRepo for MemTxn {
+    async fn create(
+        &mut self,
+        table_id: TableId,
+        predicate: &DeletePredicate,
+    ) -> Result<Tombstone> {
+        let created_at = Timestamp::from(self.time_provider.now());
+        let stage = self.stage();
+
+        if !stage.tables.iter().any(|t| t.id == table_id) {
+            return Err(Error::TableNotFound { id: table_id });
+        }
+
+        // IDs are never reused, even after tombstones have been removed
+        stage.next_tombstone_id += 1;
+        let tombstone = Tombstone {
+            id: TombstoneId::new(stage.next_tombstone_id),
+            table_id,
+            min_time: Timestamp::new(predicate.range.start()),
+            max_time: Timestamp::new(predicate.range.end()),
+            serialized_predicate: predicate.expr_sql_string(),
+            created_at,
+        };
+        stage.tombstones.push(tombstone.clone());
+
+        Ok(tombstone)
+    }
+
+    async fn catalog_time(&mut self) -> Result<Timestamp> {
+        Ok(Timestamp::from(self.time_provider.now()))
+    }
+
+    async fn list_unapplied(&mut self) -> Result<Vec<Tombstone>> {
+        let stage = self.stage();
+
+        Ok(stage
+            .tombstones
+            .iter()
+            .filter(|t| {
+                stage
+                    .parquet_files
+                    .iter()
+                    .any(|f| f.to_delete.is_none() && t.applies_to(f))
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn partitions_with_unapplied(&mut self) -> Result<Vec<PartitionId>> {
+        let stage = self.stage();
+
+        let partition_ids: BTreeSet<_> = stage
+            .parquet_files
+            .iter()
+            .filter(|f| f.to_delete.is_none() && stage.tombstones.iter().any(|t| t.applies_to(f)))
+            .filter_map(|f| {
+                stage
+                    .partitions
+                    .iter()
+                    .find(|p| p.transition_partition_id() == f.partition_id)
+                    .map(|p| p.id)
+            })
+            .collect();
+
+        Ok(partition_ids.into_iter().collect())
+    }
+
+    async fn list_by_table_id(&mut self, table_id: TableId) -> Result<Vec<Tombstone>> {
+        let stage = self.stage();
+
+        Ok(stage
+            .tombstones
+            .iter()
+            .filter(|t| t.table_id == table_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn list_by_namespace_id(&mut self, namespace_id: NamespaceId) -> Result<Vec<Tombstone>> {
+        let stage = self.stage();
+
+        let table_ids: HashSet<_> = stage
+            .tables
+            .iter()
+            .filter(|t| t.namespace_id == namespace_id)
+            .map(|t| t.id)
+            .collect();
+
+        Ok(stage
+            .tombstones
+            .iter()
+            .filter(|t| table_ids.contains(&t.table_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn remove_created_before(&mut self, older_than: Timestamp) -> Result<Vec<TombstoneId>> {
+        let stage = self.stage();
+
+        let (removed, remaining): (Vec<_>, Vec<_>) = std::mem::take(&mut stage.tombstones)
+            .into_iter()
+            .partition(|t| t.created_at < older_than);
+        stage.tombstones = remaining;
+
+        Ok(removed.into_iter().map(|t| t.id).collect())
+    }
+}
+
+#[async_trait]
+impl CompactionLeaseRepo for MemTxn {
+    async fn acquire(
+        &mut self,
+        partition_id: PartitionId,
+        job_id: Uuid,
+        holder: &str,
+        duration: Duration,
+    ) -> Result<Option<CompactionLease>> {
+        let now = self.time_provider.now();
+        let stage = self.stage();
+
+        if !stage.partitions.iter().any(|p| p.id == partition_id) {
+            return Err(Error::PartitionNotFound {
+                id: TransitionPartitionId::Deprecated(partition_id),
+            });
+        }
+
+        let lease = CompactionLease {
+            partition_id,
+            job_id,
+            holder: holder.to_string(),
+            expires_at: Timestamp::from(now + duration),
+        };
+
+        match stage
+            .compaction_leases
+            .iter_mut()
+            .find(|l| l.partition_id == partition_id)
+        {
+            Some(existing)
+                if existing.job_id != job_id && existing.expires_at > Timestamp::from(now) =>
+            {
+                Ok(None)
+            }
+            Some(existing) => {
+                *existing = lease.clone();
+                Ok(Some(lease))
+            }
+            None => {
+                stage.compaction_leases.push(lease.clone());
+                Ok(Some(lease))
+            }
+        }
+    }
+
+    async fn renew(
+        &mut self,
+        partition_id: PartitionId,
+        job_id: Uuid,
+        duration: Duration,
+    ) -> Result<Option<CompactionLease>> {
+        let expires_at = Timestamp::from(self.time_provider.now() + duration);
+        let stage = self.stage();
+
+        Ok(stage
+            .compaction_leases
+            .iter_mut()
+            .find(|l| l.partition_id == partition_id && l.job_id == job_id)
+            .map(|lease| {
+                lease.expires_at = expires_at;
+                lease.clone()
+            }))
+    }
+
+    async fn release(&mut self, partition_id: PartitionId, job_id: Uuid) -> Result<bool> {
+        let stage = self.stage();
+
+        let len = stage.compaction_leases.len();
+        stage
+            .compaction_leases
+            .retain(|l| l.partition_id != partition_id || l.job_id != job_id);
+
+        Ok(stage.compaction_leases.len() != len)
+    }
+
+    async fn list(&mut self) -> Result<Vec<CompactionLease>> {
+        let stage = self.stage();
+
+        let mut leases = stage.compaction_leases.clone();
+        leases.sort_by_key(|l| l.partition_id);
+
+        Ok(leases)
+    }
+
+    async fn commit(
+        &mut self,
+        partition_id: PartitionId,
+        job_id: Uuid,
+        delete: &[ParquetFileId],
+        upgrade: &[ParquetFileId],
+        create: &[ParquetFileParams],
+        target_level: CompactionLevel,
+    ) -> Result<Option<Vec<ParquetFileId>>> {
+        self.create_upgrade_delete_leased(
+            Some((partition_id, job_id)),
+            delete,
+            upgrade,
+            create,
+            target_level,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl AuthTokenRepo for MemTxn {
+    async fn create(
+        &mut self,
+        token_hash: &[u8],
+        description: &str,
+        grants: &[AuthTokenGrant],
+    ) -> Result<AuthToken> {
+        let created_at = Timestamp::from(self.time_provider.now());
+        let stage = self.stage();
+
+        if stage.auth_tokens.iter().any(|(hash, _)| hash == token_hash) {
+            return Err(Error::AuthTokenExists);
+        }
+
+        let mut grants = grants.to_vec();
+        grants.sort();
+        grants.dedup();
+
+        // Tokens are never deleted, only revoked
+        let token = AuthToken {
+            id: AuthTokenId::new(stage.auth_tokens.len() as i64 + 1),
+            description: description.to_string(),
+            created_at,
+            revoked_at: None,
+            grants,
+        };
+        stage.auth_tokens.push((token_hash.to_vec(), token.clone()));
+
+        Ok(token)
+    }
+
+    async fn get_by_hash(&mut self, token_hash: &[u8]) -> Result<Option<AuthToken>> {
+        let stage = self.stage();
+
+        Ok(stage
+            .auth_tokens
+            .iter()
+            .find(|(hash, _)| hash == token_hash)
+            .map(|(_, token)| token.clone()))
+    }
+
+    async fn list(&mut self) -> Result<Vec<AuthToken>> {
+        let stage = self.stage();
+
+        Ok(stage
+            .auth_tokens
+            .iter()
+            .map(|(_, token)| token.clone())
+            .collect())
+    }
+
+    async fn revoke(&mut self, id: AuthTokenId) -> Result<Option<AuthToken>> {
+        let now = Timestamp::from(self.time_provider.now());
+        let stage = self.stage();
+
+        Ok(stage
+            .auth_tokens
+            .iter_mut()
+            .find(|(_, token)| token.id == id)
+            .map(|(_, token)| {
+                token.revoked_at.get_or_insert(now);
+                token.clone()
+            }))
     }
 }
 
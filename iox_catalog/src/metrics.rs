@@ -1,20 +1,21 @@
 //! Metric instrumentation for catalog implementations.
 
 use crate::interface::{
-    CasFailure, ColumnRepo, NamespaceRepo, ParquetFileRepo, PartitionRepo, RepoCollection, Result,
-    SoftDeletedRows, TableRepo,
+    AuthTokenRepo, CasFailure, ColumnRepo, CompactionLeaseRepo, NamespaceRepo, ParquetFileRepo,
+    PartitionRepo, RepoCollection, Result, SoftDeletedRows, TableRepo, TombstoneRepo,
 };
 use async_trait::async_trait;
 use data_types::{
     partition_template::{NamespacePartitionTemplateOverride, TablePartitionTemplateOverride},
-    Column, ColumnType, CompactionLevel, MaxColumnsPerTable, MaxTables, Namespace, NamespaceId,
-    NamespaceName, NamespaceServiceProtectionLimitsOverride, ParquetFile, ParquetFileId,
-    ParquetFileParams, Partition, PartitionHashId, PartitionId, PartitionKey, SkippedCompaction,
-    SortedColumnSet, Table, TableId, Timestamp, TransitionPartitionId,
+    AuthToken, AuthTokenGrant, AuthTokenId, Column, ColumnType, CompactionLease, CompactionLevel,
+    DeletePredicate, MaxColumnsPerTable, MaxTables, Namespace, NamespaceId, NamespaceName,
+    NamespaceServiceProtectionLimitsOverride, ParquetFile, ParquetFileId, ParquetFileParams,
+    Partition, PartitionHashId, PartitionId, PartitionKey, SkippedCompaction, SortedColumnSet,
+    Table, TableId, Timestamp, Tombstone, TombstoneId, TransitionPartitionId,
 };
 use iox_time::{SystemProvider, TimeProvider};
 use metric::{DurationHistogram, Metric};
-use std::{collections::HashMap, fmt::Debug, sync::Arc};
+use std::{collections::HashMap, fmt::Debug, sync::Arc, time::Duration};
 use uuid::Uuid;
 
 /// Decorates a implementation of the catalog's [`RepoCollection`] (and the
@@ -43,7 +44,15 @@ impl<T> MetricDecorator<T> {
 
 impl<T, P> RepoCollection for MetricDecorator<T, P>
 where
-    T: NamespaceRepo + TableRepo + ColumnRepo + PartitionRepo + ParquetFileRepo + Debug,
+    T: NamespaceRepo
+        + TableRepo
+        + ColumnRepo
+        + PartitionRepo
+        + ParquetFileRepo
+        + TombstoneRepo
+        + CompactionLeaseRepo
+        + AuthTokenRepo
+        + Debug,
     P: TimeProvider,
 {
     fn namespaces(&mut self) -> &mut dyn NamespaceRepo {
@@ -65,6 +74,18 @@ where
     fn parquet_files(&mut self) -> &mut dyn ParquetFileRepo {
         self
     }
+
+    fn tombstones(&mut self) -> &mut dyn TombstoneRepo {
+        self
+    }
+
+    fn compaction_leases(&mut self) -> &mut dyn CompactionLeaseRepo {
+        self
+    }
+
+    fn auth_tokens(&mut self) -> &mut dyn AuthTokenRepo {
+        self
+    }
 }
 
 /// Emit a trait impl for `impl_trait` that delegates calls to the inner
@@ -179,6 +200,7 @@ decorate!(
         "partition_update_sort_key" = cas_sort_key(&mut self, partition_id: &TransitionPartitionId, old_sort_key: Option<Vec<String>>, old_sort_key_ids: Option<SortedColumnSet>, new_sort_key: &[&str], new_sort_key_ids: &SortedColumnSet) -> Result<Partition, CasFailure<(Option<Vec<String>>, SortedColumnSet)>>;
         "partition_record_skipped_compaction" = record_skipped_compaction(&mut self, partition_id: PartitionId, reason: &str, num_files: usize, limit_num_files: usize, limit_num_files_first_in_partition: usize, estimated_bytes: u64, limit_bytes: u64) -> Result<()>;
         "partition_list_skipped_compactions" = list_skipped_compactions(&mut self) -> Result<Vec<SkippedCompaction>>;
+        "partition_list_skipped_compactions_by_namespace_id" = list_skipped_compactions_by_namespace_id(&mut self, namespace_id: NamespaceId) -> Result<Vec<SkippedCompaction>>;
         "partition_delete_skipped_compactions" = delete_skipped_compactions(&mut self, partition_id: PartitionId) -> Result<Option<SkippedCompaction>>;
         "partition_most_recent_n" = most_recent_n(&mut self, n: usize) -> Result<Vec<Partition>>;
         "partition_partitions_new_file_between" = partitions_new_file_between(&mut self, minimum_time: Timestamp, maximum_time: Option<Timestamp>) -> Result<Vec<PartitionId>>;
@@ -202,3 +224,37 @@ decorate!(
         "parquet_create_upgrade_delete" = create_upgrade_delete(&mut self, delete: &[ParquetFileId], upgrade: &[ParquetFileId], create: &[ParquetFileParams], target_level: CompactionLevel) -> Result<Vec<ParquetFileId>>;
     ]
 );
+
+decorate!(
+    impl_trait = TombstoneRepo,
+    methods = [
+        "tombstone_create" = create(&mut self, table_id: TableId, predicate: &DeletePredicate) -> Result<Tombstone>;
+        "tombstone_catalog_time" = catalog_time(&mut self) -> Result<Timestamp>;
+        "tombstone_list_unapplied" = list_unapplied(&mut self) -> Result<Vec<Tombstone>>;
+        "tombstone_partitions_with_unapplied" = partitions_with_unapplied(&mut self) -> Result<Vec<PartitionId>>;
+        "tombstone_list_by_table_id" = list_by_table_id(&mut self, table_id: TableId) -> Result<Vec<Tombstone>>;
+        "tombstone_list_by_namespace_id" = list_by_namespace_id(&mut self, namespace_id: NamespaceId) -> Result<Vec<Tombstone>>;
+        "tombstone_remove_created_before" = remove_created_before(&mut self, older_than: Timestamp) -> Result<Vec<TombstoneId>>;
+    ]
+);
+
+decorate!(
+    impl_trait = CompactionLeaseRepo,
+    methods = [
+        "compaction_lease_acquire" = acquire(&mut self, partition_id: PartitionId, job_id: Uuid, holder: &str, duration: Duration) -> Result<Option<CompactionLease>>;
+        "compaction_lease_renew" = renew(&mut self, partition_id: PartitionId, job_id: Uuid, duration: Duration) -> Result<Option<CompactionLease>>;
+        "compaction_lease_release" = release(&mut self, partition_id: PartitionId, job_id: Uuid) -> Result<bool>;
+        "compaction_lease_list" = list(&mut self) -> Result<Vec<CompactionLease>>;
+        "compaction_lease_commit" = commit(&mut self, partition_id: PartitionId, job_id: Uuid, delete: &[ParquetFileId], upgrade: &[ParquetFileId], create: &[ParquetFileParams], target_level: CompactionLevel) -> Result<Option<Vec<ParquetFileId>>>;
+    ]
+);
+
+decorate!(
+    impl_trait = AuthTokenRepo,
+    methods = [
+        "auth_token_create" = create(&mut self, token_hash: &[u8], description: &str, grants: &[AuthTokenGrant]) -> Result<AuthToken>;
+        "auth_token_get_by_hash" = get_by_hash(&mut self, token_hash: &[u8]) -> Result<Option<AuthToken>>;
+        "auth_token_list" = list(&mut self) -> Result<Vec<AuthToken>>;
+        "auth_token_revoke" = revoke(&mut self, id: AuthTokenId) -> Result<Option<AuthToken>>;
+    ]
+);
@@ -2,8 +2,9 @@
 
 use crate::{
     interface::{
-        self, CasFailure, Catalog, ColumnRepo, ColumnTypeMismatchSnafu, Error, NamespaceRepo,
-        ParquetFileRepo, PartitionRepo, RepoCollection, Result, SoftDeletedRows, TableRepo,
+        self, AuthTokenRepo, CasFailure, Catalog, ColumnRepo, ColumnTypeMismatchSnafu,
+        CompactionLeaseRepo, Error, NamespaceRepo, ParquetFileRepo, PartitionRepo, RepoCollection,
+        Result, SoftDeletedRows, TableRepo, TombstoneRepo,
         MAX_PARQUET_FILES_SELECTED_ONCE_FOR_DELETE, MAX_PARQUET_FILES_SELECTED_ONCE_FOR_RETENTION,
     },
     kafkaless_transition::{
@@ -17,10 +18,12 @@ use data_types::{
     partition_template::{
         NamespacePartitionTemplateOverride, TablePartitionTemplateOverride, TemplatePart,
     },
-    Column, ColumnId, ColumnSet, ColumnType, CompactionLevel, MaxColumnsPerTable, MaxTables,
+    AuthToken, AuthTokenAction, AuthTokenGrant, AuthTokenId, Column, ColumnId, ColumnSet,
+    ColumnType, CompactionLease, CompactionLevel, DeletePredicate, MaxColumnsPerTable, MaxTables,
     Namespace, NamespaceId, NamespaceName, NamespaceServiceProtectionLimitsOverride, ParquetFile,
     ParquetFileId, ParquetFileParams, Partition, PartitionHashId, PartitionId, PartitionKey,
-    SkippedCompaction, SortedColumnSet, Table, TableId, Timestamp, TransitionPartitionId,
+    SkippedCompaction, SortedColumnSet, Table, TableId, Timestamp, Tombstone, TombstoneId,
+    TransitionPartitionId,
 };
 use iox_time::{SystemProvider, TimeProvider};
 use metric::Registry;
@@ -39,6 +42,7 @@ use std::{
     fmt::{Display, Write},
     str::FromStr,
     sync::Arc,
+    time::Duration,
 };
 
 static MIGRATOR: Migrator = sqlx::migrate!("sqlite/migrations");
@@ -259,6 +263,17 @@ impl RepoCollection for SqliteTxn {
     fn parquet_files(&mut self) -> &mut dyn ParquetFileRepo {
         self
     }
+    fn tombstones(&mut self) -> &mut dyn TombstoneRepo {
+        self
+    }
+
+    fn compaction_leases(&mut self) -> &mut dyn CompactionLeaseRepo {
+        self
+    }
+
+    fn auth_tokens(&mut self) -> &mut dyn AuthTokenRepo {
+        self
+    }
 }
 
 #[async_trait]
@@ -605,12 +620,15 @@ RETURNING *;
             }
         })?;
 
-        // Partitioning is only supported for tags, so create tag columns for all `TagValue`
-        // partition template parts. It's important this happens within the table creation
-        // transaction so that there isn't a possibility of a concurrent write creating these
-        // columns with an unsupported type.
+        // Partitioning is only supported for tags, so create tag columns for all `TagValue`,
+        // `TagPrefix` and `Bucket` partition template parts. It's important this happens within
+        // the table creation transaction so that there isn't a possibility of a concurrent write
+        // creating these columns with an unsupported type.
         for template_part in table.partition_template.parts() {
-            if let TemplatePart::TagValue(tag_name) = template_part {
+            if let TemplatePart::TagValue(tag_name)
+            | TemplatePart::TagPrefix(tag_name, _)
+            | TemplatePart::Bucket(tag_name, _) = template_part
+            {
                 insert_column_with_connection(&mut *tx, tag_name, table.id, ColumnType::Tag)
                     .await?;
             }
@@ -1177,6 +1195,25 @@ SELECT * FROM skipped_compactions
         .context(interface::CouldNotListSkippedCompactionsSnafu)
     }
 
+    async fn list_skipped_compactions_by_namespace_id(
+        &mut self,
+        namespace_id: NamespaceId,
+    ) -> Result<Vec<SkippedCompaction>> {
+        sqlx::query_as::<_, SkippedCompaction>(
+            r#"
+SELECT skipped_compactions.*
+FROM skipped_compactions
+INNER JOIN partition ON partition.id = skipped_compactions.partition_id
+INNER JOIN table_name ON table_name.id = partition.table_id
+WHERE table_name.namespace_id = $1;
+        "#,
+        )
+        .bind(namespace_id) // $1
+        .fetch_all(self.inner.get_mut())
+        .await
+        .context(interface::CouldNotListSkippedCompactionsSnafu)
+    }
+
     async fn delete_skipped_compactions(
         &mut self,
         partition_id: PartitionId,
@@ -1278,6 +1315,7 @@ struct ParquetFilePod {
     row_count: i64,
     compaction_level: CompactionLevel,
     created_at: Timestamp,
+    data_written_at: Timestamp,
     column_set: Json<Vec<i64>>,
     max_l0_created_at: Timestamp,
 }
@@ -1297,6 +1335,7 @@ impl From<ParquetFilePod> for ParquetFile {
             row_count: value.row_count,
             compaction_level: value.compaction_level,
             created_at: value.created_at,
+            data_written_at: value.data_written_at,
             column_set: to_column_set(&value.column_set),
             max_l0_created_at: value.max_l0_created_at,
         }
@@ -1319,7 +1358,8 @@ SELECT parquet_file.id, parquet_file.namespace_id, parquet_file.table_id,
        parquet_file.partition_id, parquet_file.partition_hash_id, parquet_file.object_store_id,
        parquet_file.min_time, parquet_file.max_time, parquet_file.to_delete,
        parquet_file.file_size_bytes, parquet_file.row_count, parquet_file.compaction_level,
-       parquet_file.created_at, parquet_file.column_set, parquet_file.max_l0_created_at
+       parquet_file.created_at, parquet_file.data_written_at, parquet_file.column_set,
+       parquet_file.max_l0_created_at
 FROM parquet_file;
              "#,
         )
@@ -1373,7 +1413,8 @@ SELECT parquet_file.id, parquet_file.namespace_id, parquet_file.table_id,
        parquet_file.partition_id, parquet_file.partition_hash_id, parquet_file.object_store_id,
        parquet_file.min_time, parquet_file.max_time, parquet_file.to_delete,
        parquet_file.file_size_bytes, parquet_file.row_count, parquet_file.compaction_level,
-       parquet_file.created_at, parquet_file.column_set, parquet_file.max_l0_created_at
+       parquet_file.created_at, parquet_file.data_written_at, parquet_file.column_set,
+       parquet_file.max_l0_created_at
 FROM parquet_file
 INNER JOIN table_name on table_name.id = parquet_file.table_id
 WHERE table_name.namespace_id = $1
@@ -1394,7 +1435,7 @@ WHERE table_name.namespace_id = $1
             r#"
 SELECT id, namespace_id, table_id, partition_id, partition_hash_id, object_store_id,
        min_time, max_time, to_delete, file_size_bytes,
-       row_count, compaction_level, created_at, column_set, max_l0_created_at
+       row_count, compaction_level, created_at, data_written_at, column_set, max_l0_created_at
 FROM parquet_file
 WHERE table_id = $1 AND to_delete IS NULL;
              "#,
@@ -1443,7 +1484,7 @@ RETURNING id;
                 r#"
 SELECT parquet_file.id, namespace_id, parquet_file.table_id, partition_id, partition_hash_id,
        object_store_id, min_time, max_time, parquet_file.to_delete, file_size_bytes, row_count,
-       compaction_level, created_at, column_set, max_l0_created_at
+       compaction_level, created_at, data_written_at, column_set, max_l0_created_at
 FROM parquet_file
 INNER JOIN partition
 ON partition.id = parquet_file.partition_id OR partition.hash_id = parquet_file.partition_hash_id
@@ -1456,7 +1497,7 @@ WHERE partition.hash_id = $1
                 r#"
 SELECT parquet_file.id, namespace_id, parquet_file.table_id, partition_id, partition_hash_id,
        object_store_id, min_time, max_time, parquet_file.to_delete, file_size_bytes, row_count,
-       compaction_level, created_at, column_set, max_l0_created_at
+       compaction_level, created_at, data_written_at, column_set, max_l0_created_at
 FROM parquet_file
 INNER JOIN partition
 ON partition.id = parquet_file.partition_id OR partition.hash_id = parquet_file.partition_hash_id
@@ -1483,8 +1524,8 @@ WHERE partition.id = $1
         let rec = sqlx::query_as::<_, ParquetFilePod>(
             r#"
 SELECT id, namespace_id, table_id, partition_id, partition_hash_id, object_store_id, min_time,
-       max_time, to_delete, file_size_bytes, row_count, compaction_level, created_at, column_set,
-       max_l0_created_at
+       max_time, to_delete, file_size_bytes, row_count, compaction_level, created_at,
+       data_written_at, column_set, max_l0_created_at
 FROM parquet_file
 WHERE object_store_id = $1;
              "#,
@@ -1535,6 +1576,26 @@ WHERE object_store_id IN ({v});",
         create: &[ParquetFileParams],
         target_level: CompactionLevel,
     ) -> Result<Vec<ParquetFileId>> {
+        let ids = self
+            .create_upgrade_delete_leased(None, delete, upgrade, create, target_level)
+            .await?;
+        Ok(ids.expect("no lease to check"))
+    }
+}
+
+impl SqliteTxn {
+    /// Apply the changes of [`ParquetFileRepo::create_upgrade_delete`] in one transaction.
+    ///
+    /// If `lease` is given, the changes are only applied if the given job holds an unexpired
+    /// lease on the given partition, returning `None` otherwise.
+    async fn create_upgrade_delete_leased(
+        &mut self,
+        lease: Option<(PartitionId, Uuid)>,
+        delete: &[ParquetFileId],
+        upgrade: &[ParquetFileId],
+        create: &[ParquetFileParams],
+        target_level: CompactionLevel,
+    ) -> Result<Option<Vec<ParquetFileId>>> {
         let delete_set = delete.iter().copied().collect::<HashSet<_>>();
         let upgrade_set = upgrade.iter().copied().collect::<HashSet<_>>();
 
@@ -1550,6 +1611,27 @@ WHERE object_store_id IN ({v});",
             .await
             .map_err(|e| Error::StartTransaction { source: e })?;
 
+        if let Some((partition_id, job_id)) = lease {
+            let held = sqlx::query(
+                r#"
+SELECT 1
+FROM compaction_lease
+WHERE partition_id = $1 AND job_id = $2 AND expires_at > $3;
+                "#,
+            )
+            .bind(partition_id) // $1
+            .bind(job_id) // $2
+            .bind(Timestamp::from(self.time_provider.now())) // $3
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| Error::SqlxError { source: e })?;
+
+            if held.is_none() {
+                // dropping the transaction rolls it back
+                return Ok(None);
+            }
+        }
+
         for id in delete {
             let marked_at = Timestamp::from(self.time_provider.now());
             flag_for_delete(&mut *tx, *id, marked_at).await?;
@@ -1566,12 +1648,451 @@ WHERE object_store_id IN ({v});",
             .await
             .map_err(|e| Error::FailedToCommit { source: e })?;
 
-        Ok(ids)
+        Ok(Some(ids))
     }
 }
 
 // The following three functions are helpers to the create_upgrade_delete method.
 // They are also used by the respective create/flag_for_delete/update_compaction_level methods.
+#[async_trait]
+impl TombstoneRepo for SqliteTxn {
+    async fn create(
+        &mut self,
+        table_id: TableId,
+        predicate: &DeletePredicate,
+    ) -> Result<Tombstone> {
+        let created_at = Timestamp::from(self.time_provider.now());
+
+        sqlx::query_as::<_, Tombstone>(
+            r#"
+INSERT INTO tombstone ( table_id, min_time, max_time, serialized_predicate, created_at )
+VALUES ( $1, $2, $3, $4, $5 )
+RETURNING id, table_id, min_time, max_time, serialized_predicate, created_at;
+            "#,
+        )
+        .bind(table_id) // $1
+        .bind(predicate.range.start()) // $2
+        .bind(predicate.range.end()) // $3
+        .bind(predicate.expr_sql_string()) // $4
+        .bind(created_at) // $5
+        .fetch_one(self.inner.get_mut())
+        .await
+        .map_err(|e| {
+            if is_fk_violation(&e) {
+                Error::ForeignKeyViolation { source: e }
+            } else {
+                Error::SqlxError { source: e }
+            }
+        })
+    }
+
+    async fn catalog_time(&mut self) -> Result<Timestamp> {
+        Ok(Timestamp::from(self.time_provider.now()))
+    }
+
+    async fn list_unapplied(&mut self) -> Result<Vec<Tombstone>> {
+        sqlx::query_as::<_, Tombstone>(
+            r#"
+SELECT tombstone.id, tombstone.table_id, tombstone.min_time, tombstone.max_time,
+       tombstone.serialized_predicate, tombstone.created_at
+FROM tombstone
+WHERE EXISTS (
+    SELECT 1
+    FROM parquet_file
+    WHERE parquet_file.table_id = tombstone.table_id
+      AND parquet_file.to_delete IS NULL
+      AND parquet_file.data_written_at < tombstone.created_at
+      AND parquet_file.min_time < tombstone.max_time
+      AND parquet_file.max_time >= tombstone.min_time
+)
+ORDER BY tombstone.id;
+            "#,
+        )
+        .fetch_all(self.inner.get_mut())
+        .await
+        .map_err(|e| Error::SqlxError { source: e })
+    }
+
+    async fn partitions_with_unapplied(&mut self) -> Result<Vec<PartitionId>> {
+        sqlx::query_as(
+            r#"
+SELECT DISTINCT partition.id AS partition_id
+FROM tombstone
+INNER JOIN parquet_file ON parquet_file.table_id = tombstone.table_id
+INNER JOIN partition
+ON partition.id = parquet_file.partition_id OR partition.hash_id = parquet_file.partition_hash_id
+WHERE parquet_file.to_delete IS NULL
+  AND parquet_file.data_written_at < tombstone.created_at
+  AND parquet_file.min_time < tombstone.max_time
+  AND parquet_file.max_time >= tombstone.min_time
+ORDER BY partition.id;
+            "#,
+        )
+        .fetch_all(self.inner.get_mut())
+        .await
+        .map_err(|e| Error::SqlxError { source: e })
+    }
+
+    async fn list_by_table_id(&mut self, table_id: TableId) -> Result<Vec<Tombstone>> {
+        sqlx::query_as::<_, Tombstone>(
+            r#"
+SELECT id, table_id, min_time, max_time, serialized_predicate, created_at
+FROM tombstone
+WHERE table_id = $1
+ORDER BY id;
+            "#,
+        )
+        .bind(table_id) // $1
+        .fetch_all(self.inner.get_mut())
+        .await
+        .map_err(|e| Error::SqlxError { source: e })
+    }
+
+    async fn list_by_namespace_id(&mut self, namespace_id: NamespaceId) -> Result<Vec<Tombstone>> {
+        sqlx::query_as::<_, Tombstone>(
+            r#"
+SELECT tombstone.id, tombstone.table_id, tombstone.min_time, tombstone.max_time,
+       tombstone.serialized_predicate, tombstone.created_at
+FROM tombstone
+INNER JOIN table_name on table_name.id = tombstone.table_id
+WHERE table_name.namespace_id = $1
+ORDER BY tombstone.id;
+            "#,
+        )
+        .bind(namespace_id) // $1
+        .fetch_all(self.inner.get_mut())
+        .await
+        .map_err(|e| Error::SqlxError { source: e })
+    }
+
+    async fn remove_created_before(&mut self, older_than: Timestamp) -> Result<Vec<TombstoneId>> {
+        sqlx::query(
+            r#"
+DELETE FROM tombstone
+WHERE created_at < $1
+RETURNING id;
+            "#,
+        )
+        .bind(older_than) // $1
+        .fetch_all(self.inner.get_mut())
+        .await
+        .map_err(|e| Error::SqlxError { source: e })
+        .map(|rows| rows.into_iter().map(|row| row.get("id")).collect())
+    }
+}
+
+#[async_trait]
+impl CompactionLeaseRepo for SqliteTxn {
+    async fn acquire(
+        &mut self,
+        partition_id: PartitionId,
+        job_id: Uuid,
+        holder: &str,
+        duration: Duration,
+    ) -> Result<Option<CompactionLease>> {
+        let now = self.time_provider.now();
+        let expires_at = Timestamp::from(now + duration);
+
+        // Take over the partition's lease only if it expired or is held by the same job.
+        sqlx::query_as::<_, CompactionLease>(
+            r#"
+INSERT INTO compaction_lease ( partition_id, job_id, holder, expires_at )
+VALUES ( $1, $2, $3, $4 )
+ON CONFLICT (partition_id) DO UPDATE
+SET job_id = excluded.job_id, holder = excluded.holder, expires_at = excluded.expires_at
+WHERE compaction_lease.expires_at <= $5 OR compaction_lease.job_id = excluded.job_id
+RETURNING partition_id, job_id, holder, expires_at;
+            "#,
+        )
+        .bind(partition_id) // $1
+        .bind(job_id) // $2
+        .bind(holder) // $3
+        .bind(expires_at) // $4
+        .bind(Timestamp::from(now)) // $5
+        .fetch_optional(self.inner.get_mut())
+        .await
+        .map_err(|e| {
+            if is_fk_violation(&e) {
+                Error::ForeignKeyViolation { source: e }
+            } else {
+                Error::SqlxError { source: e }
+            }
+        })
+    }
+
+    async fn renew(
+        &mut self,
+        partition_id: PartitionId,
+        job_id: Uuid,
+        duration: Duration,
+    ) -> Result<Option<CompactionLease>> {
+        let expires_at = Timestamp::from(self.time_provider.now() + duration);
+
+        sqlx::query_as::<_, CompactionLease>(
+            r#"
+UPDATE compaction_lease
+SET expires_at = $3
+WHERE partition_id = $1 AND job_id = $2
+RETURNING partition_id, job_id, holder, expires_at;
+            "#,
+        )
+        .bind(partition_id) // $1
+        .bind(job_id) // $2
+        .bind(expires_at) // $3
+        .fetch_optional(self.inner.get_mut())
+        .await
+        .map_err(|e| Error::SqlxError { source: e })
+    }
+
+    async fn release(&mut self, partition_id: PartitionId, job_id: Uuid) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+DELETE FROM compaction_lease
+WHERE partition_id = $1 AND job_id = $2;
+            "#,
+        )
+        .bind(partition_id) // $1
+        .bind(job_id) // $2
+        .execute(self.inner.get_mut())
+        .await
+        .map_err(|e| Error::SqlxError { source: e })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list(&mut self) -> Result<Vec<CompactionLease>> {
+        sqlx::query_as::<_, CompactionLease>(
+            r#"
+SELECT partition_id, job_id, holder, expires_at
+FROM compaction_lease
+ORDER BY partition_id;
+            "#,
+        )
+        .fetch_all(self.inner.get_mut())
+        .await
+        .map_err(|e| Error::SqlxError { source: e })
+    }
+
+    async fn commit(
+        &mut self,
+        partition_id: PartitionId,
+        job_id: Uuid,
+        delete: &[ParquetFileId],
+        upgrade: &[ParquetFileId],
+        create: &[ParquetFileParams],
+        target_level: CompactionLevel,
+    ) -> Result<Option<Vec<ParquetFileId>>> {
+        self.create_upgrade_delete_leased(
+            Some((partition_id, job_id)),
+            delete,
+            upgrade,
+            create,
+            target_level,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl AuthTokenRepo for SqliteTxn {
+    async fn create(
+        &mut self,
+        token_hash: &[u8],
+        description: &str,
+        grants: &[AuthTokenGrant],
+    ) -> Result<AuthToken> {
+        let created_at = Timestamp::from(self.time_provider.now());
+
+        let row = sqlx::query_as::<_, AuthTokenRow>(
+            r#"
+INSERT INTO auth_token ( token_hash, description, created_at )
+VALUES ( $1, $2, $3 )
+RETURNING id, description, created_at, revoked_at;
+            "#,
+        )
+        .bind(token_hash) // $1
+        .bind(description) // $2
+        .bind(created_at) // $3
+        .fetch_one(self.inner.get_mut())
+        .await
+        .map_err(|e| {
+            if is_unique_violation(&e) {
+                Error::AuthTokenExists
+            } else {
+                Error::SqlxError { source: e }
+            }
+        })?;
+
+        let mut grants = grants.to_vec();
+        grants.sort();
+        grants.dedup();
+        for grant in &grants {
+            sqlx::query(
+                r#"
+INSERT INTO auth_token_grant ( token_id, namespace_name, table_name, action )
+VALUES ( $1, $2, $3, $4 );
+                "#,
+            )
+            .bind(row.id) // $1
+            .bind(&grant.namespace_name) // $2
+            .bind(grant.table_name.as_deref().unwrap_or_default()) // $3
+            .bind(grant.action) // $4
+            .execute(self.inner.get_mut())
+            .await
+            .map_err(|e| Error::SqlxError { source: e })?;
+        }
+
+        Ok(row.into_token(grants))
+    }
+
+    async fn get_by_hash(&mut self, token_hash: &[u8]) -> Result<Option<AuthToken>> {
+        let row = sqlx::query_as::<_, AuthTokenRow>(
+            r#"
+SELECT id, description, created_at, revoked_at
+FROM auth_token
+WHERE token_hash = $1;
+            "#,
+        )
+        .bind(token_hash) // $1
+        .fetch_optional(self.inner.get_mut())
+        .await
+        .map_err(|e| Error::SqlxError { source: e })?;
+
+        match row {
+            Some(row) => self.token_with_grants(row).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    async fn list(&mut self) -> Result<Vec<AuthToken>> {
+        let rows = sqlx::query_as::<_, AuthTokenRow>(
+            r#"
+SELECT id, description, created_at, revoked_at
+FROM auth_token
+ORDER BY id;
+            "#,
+        )
+        .fetch_all(self.inner.get_mut())
+        .await
+        .map_err(|e| Error::SqlxError { source: e })?;
+
+        let grants = sqlx::query_as::<_, AuthTokenGrantRow>(
+            r#"
+SELECT token_id, namespace_name, table_name, action
+FROM auth_token_grant;
+            "#,
+        )
+        .fetch_all(self.inner.get_mut())
+        .await
+        .map_err(|e| Error::SqlxError { source: e })?;
+
+        let mut grants_by_token: HashMap<AuthTokenId, Vec<AuthTokenGrant>> = HashMap::new();
+        for row in grants {
+            grants_by_token
+                .entry(row.token_id)
+                .or_default()
+                .push(row.grant());
+        }
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let mut grants = grants_by_token.remove(&row.id).unwrap_or_default();
+                grants.sort();
+                row.into_token(grants)
+            })
+            .collect())
+    }
+
+    async fn revoke(&mut self, id: AuthTokenId) -> Result<Option<AuthToken>> {
+        let revoked_at = Timestamp::from(self.time_provider.now());
+
+        let row = sqlx::query_as::<_, AuthTokenRow>(
+            r#"
+UPDATE auth_token
+SET revoked_at = COALESCE(revoked_at, $2)
+WHERE id = $1
+RETURNING id, description, created_at, revoked_at;
+            "#,
+        )
+        .bind(id) // $1
+        .bind(revoked_at) // $2
+        .fetch_optional(self.inner.get_mut())
+        .await
+        .map_err(|e| Error::SqlxError { source: e })?;
+
+        match row {
+            Some(row) => self.token_with_grants(row).await.map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+impl SqliteTxn {
+    /// Return the token of `row`, with its grants.
+    async fn token_with_grants(&mut self, row: AuthTokenRow) -> Result<AuthToken> {
+        let mut grants = sqlx::query_as::<_, AuthTokenGrantRow>(
+            r#"
+SELECT token_id, namespace_name, table_name, action
+FROM auth_token_grant
+WHERE token_id = $1;
+            "#,
+        )
+        .bind(row.id) // $1
+        .fetch_all(self.inner.get_mut())
+        .await
+        .map_err(|e| Error::SqlxError { source: e })?
+        .into_iter()
+        .map(|row| row.grant())
+        .collect::<Vec<_>>();
+        grants.sort();
+
+        Ok(row.into_token(grants))
+    }
+}
+
+/// A row of the `auth_token` table.
+#[derive(Debug, sqlx::FromRow)]
+struct AuthTokenRow {
+    id: AuthTokenId,
+    description: String,
+    created_at: Timestamp,
+    revoked_at: Option<Timestamp>,
+}
+
+impl AuthTokenRow {
+    fn into_token(self, grants: Vec<AuthTokenGrant>) -> AuthToken {
+        AuthToken {
+            id: self.id,
+            description: self.description,
+            created_at: self.created_at,
+            revoked_at: self.revoked_at,
+            grants,
+        }
+    }
+}
+
+/// A row of the `auth_token_grant` table.
+#[derive(Debug, sqlx::FromRow)]
+struct AuthTokenGrantRow {
+    token_id: AuthTokenId,
+    namespace_name: String,
+    /// The table name the grant is restricted to, or empty for the whole
+    /// namespace.
+    table_name: String,
+    action: AuthTokenAction,
+}
+
+impl AuthTokenGrantRow {
+    fn grant(self) -> AuthTokenGrant {
+        AuthTokenGrant {
+            namespace_name: self.namespace_name,
+            table_name: Some(self.table_name).filter(|t| !t.is_empty()),
+            action: self.action,
+        }
+    }
+}
+
 async fn create_parquet_file<'q, E>(
     executor: E,
     parquet_file_params: ParquetFileParams,
@@ -1590,6 +2111,7 @@ where
         row_count,
         compaction_level,
         created_at,
+        data_written_at,
         column_set,
         max_l0_created_at,
     } = parquet_file_params;
@@ -1603,12 +2125,13 @@ where
 INSERT INTO parquet_file (
     shard_id, table_id, partition_id, partition_hash_id, object_store_id,
     min_time, max_time, file_size_bytes,
-    row_count, compaction_level, created_at, namespace_id, column_set, max_l0_created_at )
-VALUES ( $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14 )
+    row_count, compaction_level, created_at, namespace_id, column_set, max_l0_created_at,
+    data_written_at )
+VALUES ( $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15 )
 RETURNING
     id, table_id, partition_id, partition_hash_id, object_store_id, min_time, max_time, to_delete,
-    file_size_bytes, row_count, compaction_level, created_at, namespace_id, column_set,
-    max_l0_created_at;
+    file_size_bytes, row_count, compaction_level, created_at, data_written_at, namespace_id,
+    column_set, max_l0_created_at;
         "#,
     )
     .bind(TRANSITION_SHARD_ID) // $1
@@ -1625,6 +2148,7 @@ RETURNING
     .bind(namespace_id) // $12
     .bind(from_column_set(&column_set)) // $13
     .bind(max_l0_created_at) // $14
+    .bind(data_written_at) // $15
     .fetch_one(executor)
     .await;
 
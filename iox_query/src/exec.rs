@@ -7,6 +7,7 @@ pub mod fieldlist;
 pub mod gapfill;
 mod metrics;
 mod non_null_checker;
+pub mod query_stats;
 pub mod query_tracing;
 mod schema_pivot;
 pub mod seriesset;
@@ -13,6 +13,7 @@ use crate::{
     exec::{
         fieldlist::{FieldList, IntoFieldList},
         non_null_checker::NonNullCheckerExec,
+        query_stats::{QueryMemoryPool, QueryStats},
         query_tracing::TracedStream,
         schema_pivot::{SchemaPivotExec, SchemaPivotNode},
         seriesset::{
@@ -25,19 +26,21 @@ use crate::{
     logical_optimizer::register_iox_logical_optimizers,
     physical_optimizer::register_iox_physical_optimizers,
     plan::{
+        cardinality::{SeriesCardinalityPlan, CARDINALITY_COLUMN_NAME},
         fieldlist::FieldListPlan,
         seriesset::{SeriesSetPlan, SeriesSetPlans},
         stringset::StringSetPlan,
     },
 };
-use arrow::record_batch::RecordBatch;
+use arrow::{array::Int64Array, record_batch::RecordBatch};
 use async_trait::async_trait;
 use datafusion::{
     catalog::CatalogProvider,
     execution::{
         context::{QueryPlanner, SessionState, TaskContext},
+        disk_manager::DiskManagerConfig,
         memory_pool::MemoryPool,
-        runtime_env::RuntimeEnv,
+        runtime_env::{RuntimeConfig, RuntimeEnv},
     },
     logical_expr::{LogicalPlan, UserDefinedLogicalNode},
     physical_plan::{
@@ -47,12 +50,16 @@ use datafusion::{
     physical_planner::{DefaultPhysicalPlanner, ExtensionPlanner, PhysicalPlanner},
     prelude::*,
 };
-use datafusion_util::config::{iox_session_config, DEFAULT_CATALOG};
+use datafusion_util::config::{
+    iox_object_store_url, iox_session_config, OverlayObjectStoreRegistry, DEFAULT_CATALOG,
+};
 use executor::DedicatedExecutor;
 use futures::{Stream, StreamExt, TryStreamExt};
+use object_store::DynObjectStore;
 use observability_deps::tracing::{debug, warn};
+use parquet_file::storage::StorageId;
 use query_functions::{register_scalar_functions, selectors::register_selector_aggregates};
-use std::{fmt, num::NonZeroUsize, sync::Arc};
+use std::{collections::HashMap, fmt, num::NonZeroUsize, sync::Arc};
 use trace::{
     ctx::SpanContext,
     span::{MetaValue, Span, SpanEvent, SpanExt, SpanRecorder},
@@ -176,6 +183,12 @@ pub struct IOxSessionConfig {
 
     /// Span context from which to create spans for this query
     span_ctx: Option<SpanContext>,
+
+    /// Statistics of the query executed in the built context
+    query_stats: Arc<QueryStats>,
+
+    /// Object stores used by this query instead of those registered in the shared runtime
+    object_stores: HashMap<StorageId, Arc<DynObjectStore>>,
 }
 
 impl fmt::Debug for IOxSessionConfig {
@@ -198,6 +211,8 @@ impl IOxSessionConfig {
             runtime,
             default_catalog: None,
             span_ctx: None,
+            query_stats: Default::default(),
+            object_stores: HashMap::default(),
         }
     }
 
@@ -222,6 +237,18 @@ impl IOxSessionConfig {
         Self { span_ctx, ..self }
     }
 
+    /// Read the object store `id` through `store` in this query, instead of through the store registered in the
+    /// shared runtime.
+    pub fn with_object_store(mut self, id: StorageId, store: Arc<DynObjectStore>) -> Self {
+        self.object_stores.insert(id, store);
+        self
+    }
+
+    /// Statistics of the query that will be executed in the built context.
+    pub fn query_stats(&self) -> Arc<QueryStats> {
+        Arc::clone(&self.query_stats)
+    }
+
     /// Set DataFusion [config option].
     ///
     /// May be used to set [IOx-specific] option as well.
@@ -247,12 +274,14 @@ impl IOxSessionConfig {
         let maybe_span = self.span_ctx.child_span("Query Execution");
         let recorder = SpanRecorder::new(maybe_span);
 
-        // attach span to DataFusion session
+        // attach span and query stats to DataFusion session
         let session_config = self
             .session_config
-            .with_extension(Arc::new(recorder.span().cloned()));
+            .with_extension(Arc::new(recorder.span().cloned()))
+            .with_extension(Arc::clone(&self.query_stats));
 
-        let state = SessionState::with_config_rt(session_config, self.runtime)
+        let runtime = query_runtime(&self.runtime, &self.query_stats, self.object_stores);
+        let state = SessionState::with_config_rt(session_config, runtime)
             .with_query_planner(Arc::new(IOxQueryPlanner {}));
         let state = register_iox_physical_optimizers(state);
         let state = register_iox_logical_optimizers(state);
@@ -268,6 +297,33 @@ impl IOxSessionConfig {
     }
 }
 
+/// Create the runtime of a single query.
+///
+/// The query shares the memory pool, disk manager and object stores with the `shared` runtime, but reservations are
+/// also tracked per query to record its peak memory usage in `query_stats`.
+fn query_runtime(
+    shared: &RuntimeEnv,
+    query_stats: &Arc<QueryStats>,
+    object_stores: HashMap<StorageId, Arc<DynObjectStore>>,
+) -> Arc<RuntimeEnv> {
+    let memory_pool =
+        QueryMemoryPool::new(Arc::clone(&shared.memory_pool), Arc::clone(query_stats));
+    let object_store_registry = OverlayObjectStoreRegistry::new(
+        Arc::clone(&shared.object_store_registry),
+        object_stores
+            .into_iter()
+            .map(|(id, store)| (iox_object_store_url(id), store)),
+    );
+
+    let config = RuntimeConfig::new()
+        .with_memory_pool(Arc::new(memory_pool))
+        .with_disk_manager(DiskManagerConfig::Existing(Arc::clone(
+            &shared.disk_manager,
+        )))
+        .with_object_store_registry(Arc::new(object_store_registry));
+    Arc::new(RuntimeEnv::new(config).expect("creating query runtime"))
+}
+
 /// This is an execution context for planning in IOx.  It wraps a
 /// DataFusion execution context with the information needed for planning.
 ///
@@ -613,6 +669,31 @@ impl IOxSessionContext {
         }
     }
 
+    /// Executes this plan on the query pool, and returns the total number
+    /// of series counted by its plans
+    pub async fn to_series_cardinality(&self, plan: SeriesCardinalityPlan) -> Result<u64> {
+        let ctx = self.child_ctx("to_series_cardinality");
+        let batches = ctx.run_logical_plans(plan.plans).await?;
+
+        let mut cardinality = 0_u64;
+        for batch in batches {
+            let column = batch
+                .column_by_name(CARDINALITY_COLUMN_NAME)
+                .and_then(|c| c.as_any().downcast_ref::<Int64Array>())
+                .ok_or_else(|| {
+                    DataFusionError::Internal(format!(
+                        "series cardinality plan must produce an Int64 column \
+                         '{CARDINALITY_COLUMN_NAME}', got schema {:?}",
+                        batch.schema()
+                    ))
+                })?;
+
+            cardinality += column.iter().flatten().map(|v| v as u64).sum::<u64>();
+        }
+
+        Ok(cardinality)
+    }
+
     /// plans and runs the plans in parallel and collects the results
     /// run each plan in parallel and collect the results
     async fn run_logical_plans(&self, plans: Vec<LogicalPlan>) -> Result<Vec<RecordBatch>> {
@@ -693,6 +774,13 @@ impl IOxSessionContext {
     pub fn tasks(&self) -> usize {
         self.exec.tasks()
     }
+
+    /// Statistics about the data touched by the query that is planned with this context.
+    ///
+    /// Contexts that were not created via [`IOxSessionConfig::build`] get fresh, unshared statistics.
+    pub fn query_stats(&self) -> Arc<QueryStats> {
+        self.inner.state().query_stats().unwrap_or_default()
+    }
 }
 
 /// Extension trait to pull IOx spans out of DataFusion contexts.
@@ -702,6 +790,9 @@ pub trait SessionContextIOxExt {
 
     /// Get span context
     fn span_ctx(&self) -> Option<SpanContext>;
+
+    /// Get statistics of the query that is planned in this context.
+    fn query_stats(&self) -> Option<Arc<QueryStats>>;
 }
 
 impl SessionContextIOxExt for SessionState {
@@ -716,4 +807,8 @@ impl SessionContextIOxExt for SessionState {
             .get_extension::<Option<Span>>()
             .and_then(|span| span.as_ref().as_ref().map(|span| span.ctx.clone()))
     }
+
+    fn query_stats(&self) -> Option<Arc<QueryStats>> {
+        self.config().get_extension::<QueryStats>()
+    }
 }
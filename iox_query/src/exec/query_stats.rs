@@ -0,0 +1,248 @@
+//! Per-query statistics.
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use datafusion::{
+    error::Result,
+    execution::memory_pool::{MemoryConsumer, MemoryPool, MemoryReservation},
+    physical_plan::ExecutionPlan,
+};
+
+/// Statistics about the data that a single query touched.
+///
+/// A fresh instance is attached to every [`IOxSessionContext`](super::IOxSessionContext) and can be retrieved during
+/// planning via [`SessionContextIOxExt::query_stats`](super::SessionContextIOxExt::query_stats), so table providers can
+/// record what they scanned and pruned. The memory the query reserves is recorded during execution, see
+/// [`QueryMemoryPool`].
+#[derive(Debug, Default)]
+pub struct QueryStats {
+    partitions_scanned: AtomicU64,
+    partitions_pruned: AtomicU64,
+    parquet_files_scanned: AtomicU64,
+    parquet_files_pruned: AtomicU64,
+    bytes_from_cache: AtomicU64,
+    bytes_from_object_store: AtomicU64,
+    ingester_latency_nanos: AtomicU64,
+    ingester_queried: AtomicBool,
+    peak_memory: AtomicU64,
+}
+
+impl QueryStats {
+    /// Record partitions that are scanned by the query and that were pruned during planning.
+    pub fn record_partitions(&self, scanned: u64, pruned: u64) {
+        self.partitions_scanned
+            .fetch_add(scanned, Ordering::Relaxed);
+        self.partitions_pruned.fetch_add(pruned, Ordering::Relaxed);
+    }
+
+    /// Record parquet files that are scanned by the query and that were pruned during planning.
+    pub fn record_parquet_files(&self, scanned: u64, pruned: u64) {
+        self.parquet_files_scanned
+            .fetch_add(scanned, Ordering::Relaxed);
+        self.parquet_files_pruned
+            .fetch_add(pruned, Ordering::Relaxed);
+    }
+
+    /// Record bytes that the query reads from a cache and from the object store.
+    ///
+    /// This is called by the object store the query reads from while it executes.
+    pub fn record_bytes(&self, from_cache: u64, from_object_store: u64) {
+        self.bytes_from_cache
+            .fetch_add(from_cache, Ordering::Relaxed);
+        self.bytes_from_object_store
+            .fetch_add(from_object_store, Ordering::Relaxed);
+    }
+
+    /// Record the latency of a request to the ingesters.
+    ///
+    /// A query may issue multiple requests (e.g. one per table), only the slowest one is kept.
+    pub fn record_ingester_latency(&self, latency: Duration) {
+        self.ingester_latency_nanos
+            .fetch_max(latency.as_nanos() as u64, Ordering::Relaxed);
+        self.ingester_queried.store(true, Ordering::Relaxed);
+    }
+
+    /// Record the memory currently reserved by the query, keeping the peak.
+    fn record_memory(&self, reserved: u64) {
+        self.peak_memory.fetch_max(reserved, Ordering::Relaxed);
+    }
+
+    /// Number of partitions scanned.
+    pub fn partitions_scanned(&self) -> u64 {
+        self.partitions_scanned.load(Ordering::Relaxed)
+    }
+
+    /// Number of partitions pruned during planning.
+    pub fn partitions_pruned(&self) -> u64 {
+        self.partitions_pruned.load(Ordering::Relaxed)
+    }
+
+    /// Number of parquet files scanned.
+    pub fn parquet_files_scanned(&self) -> u64 {
+        self.parquet_files_scanned.load(Ordering::Relaxed)
+    }
+
+    /// Number of parquet files pruned during planning.
+    pub fn parquet_files_pruned(&self) -> u64 {
+        self.parquet_files_pruned.load(Ordering::Relaxed)
+    }
+
+    /// Bytes read from a cache.
+    pub fn bytes_from_cache(&self) -> u64 {
+        self.bytes_from_cache.load(Ordering::Relaxed)
+    }
+
+    /// Bytes read from the object store.
+    pub fn bytes_from_object_store(&self) -> u64 {
+        self.bytes_from_object_store.load(Ordering::Relaxed)
+    }
+
+    /// Latency of the slowest ingester request, if the ingesters were queried at all.
+    pub fn ingester_latency(&self) -> Option<Duration> {
+        self.ingester_queried
+            .load(Ordering::Relaxed)
+            .then(|| Duration::from_nanos(self.ingester_latency_nanos.load(Ordering::Relaxed)))
+    }
+
+    /// Peak of the memory reserved by the query in the memory pool, in bytes.
+    pub fn peak_memory(&self) -> u64 {
+        self.peak_memory.load(Ordering::Relaxed)
+    }
+}
+
+/// A [`MemoryPool`] for a single query, tracking the memory the query reserves in the shared pool in [`QueryStats`].
+#[derive(Debug)]
+pub(crate) struct QueryMemoryPool {
+    inner: Arc<dyn MemoryPool>,
+    reserved: AtomicUsize,
+    query_stats: Arc<QueryStats>,
+}
+
+impl QueryMemoryPool {
+    pub(crate) fn new(inner: Arc<dyn MemoryPool>, query_stats: Arc<QueryStats>) -> Self {
+        Self {
+            inner,
+            reserved: AtomicUsize::new(0),
+            query_stats,
+        }
+    }
+
+    fn record_grow(&self, additional: usize) {
+        let reserved = self.reserved.fetch_add(additional, Ordering::Relaxed) + additional;
+        self.query_stats.record_memory(reserved as u64);
+    }
+}
+
+impl MemoryPool for QueryMemoryPool {
+    fn register(&self, consumer: &MemoryConsumer) {
+        self.inner.register(consumer)
+    }
+
+    fn unregister(&self, consumer: &MemoryConsumer) {
+        self.inner.unregister(consumer)
+    }
+
+    fn grow(&self, reservation: &MemoryReservation, additional: usize) {
+        self.inner.grow(reservation, additional);
+        self.record_grow(additional);
+    }
+
+    fn shrink(&self, reservation: &MemoryReservation, shrink: usize) {
+        self.inner.shrink(reservation, shrink);
+        self.reserved.fetch_sub(shrink, Ordering::Relaxed);
+    }
+
+    fn try_grow(&self, reservation: &MemoryReservation, additional: usize) -> Result<()> {
+        self.inner.try_grow(reservation, additional)?;
+        self.record_grow(additional);
+        Ok(())
+    }
+
+    /// Memory reserved in the shared pool, by all queries.
+    fn reserved(&self) -> usize {
+        self.inner.reserved()
+    }
+}
+
+/// Number of rows produced by the leaves of the plan, i.e. the rows that were read from the underlying data sources.
+///
+/// This should only be called *after* the plan was executed.
+pub fn rows_scanned(plan: &dyn ExecutionPlan) -> u64 {
+    let children = plan.children();
+    if children.is_empty() {
+        plan.metrics()
+            .and_then(|metrics| metrics.output_rows())
+            .unwrap_or_default() as u64
+    } else {
+        children
+            .iter()
+            .map(|child| rows_scanned(child.as_ref()))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::execution::memory_pool::GreedyMemoryPool;
+
+    use super::*;
+
+    #[test]
+    fn test_ingester_latency() {
+        let stats = QueryStats::default();
+        assert_eq!(stats.ingester_latency(), None);
+
+        stats.record_ingester_latency(Duration::from_millis(3));
+        stats.record_ingester_latency(Duration::from_millis(1));
+        assert_eq!(stats.ingester_latency(), Some(Duration::from_millis(3)));
+    }
+
+    #[test]
+    fn test_counters() {
+        let stats = QueryStats::default();
+        stats.record_partitions(1, 2);
+        stats.record_partitions(3, 4);
+        stats.record_parquet_files(5, 6);
+        stats.record_bytes(7, 8);
+
+        assert_eq!(stats.partitions_scanned(), 4);
+        assert_eq!(stats.partitions_pruned(), 6);
+        assert_eq!(stats.parquet_files_scanned(), 5);
+        assert_eq!(stats.parquet_files_pruned(), 6);
+        assert_eq!(stats.bytes_from_cache(), 7);
+        assert_eq!(stats.bytes_from_object_store(), 8);
+    }
+
+    #[test]
+    fn test_peak_memory() {
+        let stats = Arc::new(QueryStats::default());
+        let shared: Arc<dyn MemoryPool> = Arc::new(GreedyMemoryPool::new(100));
+        let mut other = MemoryConsumer::new("other").register(&shared);
+        other.grow(10);
+
+        let pool: Arc<dyn MemoryPool> = Arc::new(QueryMemoryPool::new(
+            Arc::clone(&shared),
+            Arc::clone(&stats),
+        ));
+        let mut r1 = MemoryConsumer::new("r1").register(&pool);
+        let mut r2 = MemoryConsumer::new("r2").register(&pool);
+        r1.grow(20);
+        r2.try_grow(30).unwrap();
+        r1.shrink(15);
+        r2.try_grow(10).unwrap();
+        assert_eq!(shared.reserved(), 55);
+        // reservations exceeding the shared pool are not recorded
+        r1.try_grow(50).unwrap_err();
+        drop(r1);
+        drop(r2);
+
+        assert_eq!(stats.peak_memory(), 50);
+        assert_eq!(shared.reserved(), 10);
+    }
+}
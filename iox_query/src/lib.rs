@@ -24,13 +24,13 @@ use arrow::{
     record_batch::RecordBatch,
 };
 use async_trait::async_trait;
-use data_types::{ChunkId, ChunkOrder, TransitionPartitionId};
+use data_types::{ChunkId, ChunkOrder, DeletePredicate, TransitionPartitionId};
 use datafusion::{
     error::DataFusionError,
-    physical_plan::{SendableRecordBatchStream, Statistics},
+    physical_plan::{ExecutionPlan, SendableRecordBatchStream, Statistics},
     prelude::{Expr, SessionContext},
 };
-use exec::IOxSessionContext;
+use exec::{query_stats::QueryStats, IOxSessionContext};
 use once_cell::sync::Lazy;
 use parquet_file::storage::ParquetExecInput;
 use schema::{sort::SortKey, Projection, Schema};
@@ -94,6 +94,14 @@ pub trait QueryChunk: Debug + Send + Sync + 'static {
     /// Order of this chunk relative to other overlapping chunks.
     fn order(&self) -> ChunkOrder;
 
+    /// Delete predicates that apply to the data of this chunk.
+    ///
+    /// Rows matching any of these predicates are removed before the chunk is
+    /// deduplicated against other chunks.
+    fn delete_predicates(&self) -> &[Arc<DeletePredicate>] {
+        &[]
+    }
+
     /// Return backend as [`Any`] which can be used to downcast to a specific implementation.
     fn as_any(&self) -> &dyn Any;
 }
@@ -103,44 +111,77 @@ pub trait QueryChunk: Debug + Send + Sync + 'static {
 /// on query completion.
 ///
 pub struct QueryCompletedToken {
-    /// If this query completed successfully
-    success: bool,
+    /// Outcome of the query, handed to `f`
+    completion: QueryCompletion,
 
     /// Function invoked when the token is dropped. It is passed the
-    /// vaue of `self.success`
-    f: Option<Box<dyn FnOnce(bool) + Send>>,
+    /// value of `self.completion`
+    f: Option<Box<dyn FnOnce(QueryCompletion) + Send>>,
 }
 
 impl Debug for QueryCompletedToken {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("QueryCompletedToken")
-            .field("success", &self.success)
+            .field("success", &self.completion.success)
             .finish()
     }
 }
 
 impl QueryCompletedToken {
-    pub fn new(f: impl FnOnce(bool) + Send + 'static) -> Self {
+    pub fn new(f: impl FnOnce(QueryCompletion) + Send + 'static) -> Self {
         Self {
-            success: false,
+            completion: QueryCompletion::default(),
             f: Some(Box::new(f)),
         }
     }
 
     /// Record that this query completed successfully
     pub fn set_success(&mut self) {
-        self.success = true;
+        self.completion.success = true;
+    }
+
+    /// Record the error that made this query fail
+    pub fn set_error(&mut self, e: &dyn std::fmt::Display) {
+        self.completion.error = Some(e.to_string());
+    }
+
+    /// Record the physical plan of this query and the [`QueryStats`]
+    /// of the context that it was planned with.
+    ///
+    /// The plan is kept until the token is dropped, so that its
+    /// metrics can be inspected once the query completed.
+    pub fn set_plan(&mut self, ctx: &IOxSessionContext, plan: Arc<dyn ExecutionPlan>) {
+        self.completion.plan = Some(plan);
+        self.completion.stats = Some(ctx.query_stats());
     }
 }
 
 impl Drop for QueryCompletedToken {
     fn drop(&mut self) {
         if let Some(f) = self.f.take() {
-            (f)(self.success)
+            (f)(std::mem::take(&mut self.completion))
         }
     }
 }
 
+/// Outcome of a query, passed to the callback of a [`QueryCompletedToken`].
+#[derive(Debug, Default)]
+pub struct QueryCompletion {
+    /// If the query completed successfully.
+    pub success: bool,
+
+    /// The error that made the query fail, if known.
+    pub error: Option<String>,
+
+    /// The physical plan, if the query was planned.
+    ///
+    /// The metrics of the plan describe its execution.
+    pub plan: Option<Arc<dyn ExecutionPlan>>,
+
+    /// Statistics recorded while the query was planned.
+    pub stats: Option<Arc<QueryStats>>,
+}
+
 /// Boxed description of a query that knows how to render to a string
 ///
 /// This avoids storing potentially large strings
@@ -276,6 +317,10 @@ where
         self.as_ref().order()
     }
 
+    fn delete_predicates(&self) -> &[Arc<DeletePredicate>] {
+        self.as_ref().delete_predicates()
+    }
+
     fn as_any(&self) -> &dyn Any {
         // present the underlying implementation, not the wrapper
         self.as_ref().as_any()
@@ -319,6 +364,10 @@ impl QueryChunk for Arc<dyn QueryChunk> {
         self.as_ref().order()
     }
 
+    fn delete_predicates(&self) -> &[Arc<DeletePredicate>] {
+        self.as_ref().delete_predicates()
+    }
+
     fn as_any(&self) -> &dyn Any {
         // present the underlying implementation, not the wrapper
         self.as_ref().as_any()
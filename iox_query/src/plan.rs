@@ -1,3 +1,4 @@
+pub mod cardinality;
 pub mod fieldlist;
 pub mod seriesset;
 pub mod stringset;
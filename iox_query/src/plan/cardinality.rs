@@ -0,0 +1,35 @@
+use datafusion::logical_expr::LogicalPlan;
+
+/// The name of the column containing the series cardinality of a single
+/// plan of a [`SeriesCardinalityPlan`].
+pub const CARDINALITY_COLUMN_NAME: &str = "cardinality";
+
+/// How the number of distinct series is computed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SeriesCardinalityMode {
+    /// Count the distinct series exactly.
+    #[default]
+    Exact,
+
+    /// Estimate the number of distinct series using HyperLogLog sketches,
+    /// trading accuracy for bounded memory usage.
+    Approximate,
+}
+
+/// A plan which produces the number of distinct series.
+///
+/// Each plan must produce a single row with a single non-null `Int64`
+/// column named [`CARDINALITY_COLUMN_NAME`]. The plans must count disjoint
+/// sets of series (e.g. one plan per table), such that the total
+/// cardinality is the sum of their results.
+#[derive(Debug, Default)]
+pub struct SeriesCardinalityPlan {
+    /// The plans to execute.
+    pub plans: Vec<LogicalPlan>,
+}
+
+impl From<Vec<LogicalPlan>> for SeriesCardinalityPlan {
+    fn from(plans: Vec<LogicalPlan>) -> Self {
+        Self { plans }
+    }
+}
@@ -1,7 +1,10 @@
 //! Implementation of a DataFusion `TableProvider` in terms of `QueryChunk`s
 
 use async_trait::async_trait;
-use std::{collections::HashSet, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::Arc,
+};
 
 use arrow::{
     datatypes::{Fields, Schema as ArrowSchema, SchemaRef as ArrowSchemaRef},
@@ -17,10 +20,11 @@ use datafusion::{
         expressions::col as physical_col, filter::FilterExec, projection::ProjectionExec,
         ExecutionPlan,
     },
-    prelude::Expr,
+    prelude::{col, lit, Expr},
     sql::TableReference,
 };
 use observability_deps::tracing::trace;
+use predicate::delete_predicate::delete_predicates_keep_expr;
 use schema::{sort::SortKey, Schema};
 
 use crate::{
@@ -225,6 +229,18 @@ impl TableProvider for ChunkTableProvider {
             ctx.config().target_partitions(),
         );
 
+        // Remove deleted rows BEFORE de-dup: a delete only affects the chunks it applies to, and a newer version
+        // of a deleted row in another chunk must win the de-duplication.
+        let plan = if let Some(expr) = delete_predicates_filter_expr(&self.chunks, &self.iox_schema)
+        {
+            Arc::new(FilterExec::try_new(
+                df_physical_expr(plan.as_ref(), expr)?,
+                plan,
+            )?)
+        } else {
+            plan
+        };
+
         // De-dup before doing anything else, because all logical expressions act on de-duplicated data.
         let plan = if self.deduplication {
             let sort_exprs = arrow_sort_key_exprs(&dedup_sort_key, &plan.schema());
@@ -308,6 +324,45 @@ impl TableProvider for ChunkTableProvider {
     }
 }
 
+/// Build a filter expression that removes the rows deleted by the
+/// [delete predicates](QueryChunk::delete_predicates) of the given chunks.
+///
+/// The predicates of a chunk are scoped to that chunk using the
+/// [chunk order column](CHUNK_ORDER_COLUMN_NAME). Chunks sharing the same
+/// order cannot be told apart, so only the predicates common to all of them
+/// are applied.
+fn delete_predicates_filter_expr(chunks: &[Arc<dyn QueryChunk>], schema: &Schema) -> Option<Expr> {
+    if chunks.iter().all(|c| c.delete_predicates().is_empty()) {
+        return None;
+    }
+
+    let mut by_order: BTreeMap<i64, Vec<&Arc<dyn QueryChunk>>> = BTreeMap::new();
+    for chunk in chunks {
+        by_order.entry(chunk.order().get()).or_default().push(chunk);
+    }
+
+    let column_names = schema
+        .as_arrow()
+        .fields()
+        .iter()
+        .map(|f| f.name().as_str())
+        .collect::<Vec<_>>();
+
+    by_order
+        .into_iter()
+        .filter_map(|(order, chunks)| {
+            let (first, rest) = chunks.split_first().expect("at least one chunk");
+            let predicates = first.delete_predicates().iter().filter(|p| {
+                rest.iter()
+                    .all(|c| c.delete_predicates().iter().any(|other| other == *p))
+            });
+            let keep = delete_predicates_keep_expr(predicates.map(|p| p.as_ref()), &column_names)?;
+
+            Some(col(CHUNK_ORDER_COLUMN_NAME).not_eq(lit(order)).or(keep))
+        })
+        .reduce(|a, b| a.and(b))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -316,6 +371,8 @@ mod test {
         pruning::retention_expr,
         test::{format_execution_plan, TestChunk},
     };
+    use arrow_util::assert_batches_sorted_eq;
+    use data_types::{DeleteExpr, DeletePredicate, Op, Scalar, TimestampRange};
     use datafusion::prelude::{col, lit};
 
     #[tokio::test]
@@ -520,6 +577,90 @@ mod test {
         );
     }
 
+    #[tokio::test]
+    async fn provider_scan_delete_predicates() {
+        let table_name = "t";
+        let delete = DeletePredicate {
+            range: TimestampRange::new(0, 15_000),
+            exprs: vec![DeleteExpr::new(
+                "tag1".to_string(),
+                Op::Eq,
+                Scalar::String("VT".to_string()),
+            )],
+        };
+        let chunk1 = Arc::new(
+            TestChunk::new(table_name)
+                .with_id(1)
+                .with_order(1)
+                .with_tag_column("tag1")
+                .with_tag_column("tag2")
+                .with_f64_field_column("field")
+                .with_time_column()
+                .with_three_rows_of_data()
+                .with_delete_predicate(delete.clone()),
+        ) as Arc<dyn QueryChunk>;
+        let chunk2 = Arc::new(
+            TestChunk::new(table_name)
+                .with_id(2)
+                .with_order(2)
+                .with_tag_column("tag1")
+                .with_tag_column("tag2")
+                .with_f64_field_column("field")
+                .with_time_column()
+                .with_three_rows_of_data(),
+        ) as Arc<dyn QueryChunk>;
+        let schema = chunk1.schema().clone();
+
+        let ctx = IOxSessionContext::with_testing();
+        let state = ctx.inner().state();
+
+        // rows deleted from the only chunk containing them are gone
+        let provider = ProviderBuilder::new(Arc::from(table_name), schema.clone())
+            .add_chunk(Arc::clone(&chunk1))
+            .build()
+            .unwrap();
+        let plan = provider
+            .scan(&state, Some(&vec![1, 3]), &[], None)
+            .await
+            .unwrap();
+        let batches = ctx.collect(plan).await.unwrap();
+        assert_batches_sorted_eq!(
+            [
+                "+------+-----------------------------+",
+                "| tag1 | time                        |",
+                "+------+-----------------------------+",
+                "| UT   | 1970-01-01T00:00:00.000020Z |",
+                "| WA   | 1970-01-01T00:00:00.000008Z |",
+                "+------+-----------------------------+",
+            ],
+            &batches
+        );
+
+        // the delete does not affect other chunks, even if they contain the same primary key
+        let provider = ProviderBuilder::new(Arc::from(table_name), schema)
+            .add_chunk(Arc::clone(&chunk1))
+            .add_chunk(Arc::clone(&chunk2))
+            .build()
+            .unwrap();
+        let plan = provider
+            .scan(&state, Some(&vec![1, 3]), &[], None)
+            .await
+            .unwrap();
+        let batches = ctx.collect(plan).await.unwrap();
+        assert_batches_sorted_eq!(
+            [
+                "+------+-----------------------------+",
+                "| tag1 | time                        |",
+                "+------+-----------------------------+",
+                "| UT   | 1970-01-01T00:00:00.000020Z |",
+                "| VT   | 1970-01-01T00:00:00.000010Z |",
+                "| WA   | 1970-01-01T00:00:00.000008Z |",
+                "+------+-----------------------------+",
+            ],
+            &batches
+        );
+    }
+
     #[tokio::test]
     async fn provider_scan_retention() {
         let table_name = "t";
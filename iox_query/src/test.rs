@@ -20,7 +20,9 @@ use arrow::{
     record_batch::RecordBatch,
 };
 use async_trait::async_trait;
-use data_types::{ChunkId, ChunkOrder, PartitionKey, TableId, TransitionPartitionId};
+use data_types::{
+    ChunkId, ChunkOrder, DeletePredicate, PartitionKey, TableId, TransitionPartitionId,
+};
 use datafusion::error::DataFusionError;
 use datafusion::execution::context::SessionState;
 use datafusion::logical_expr::Expr;
@@ -322,6 +324,9 @@ pub struct TestChunk {
 
     /// Suppress output
     quiet: bool,
+
+    /// Delete predicates applying to this chunk
+    delete_predicates: Vec<Arc<DeletePredicate>>,
 }
 
 /// Implements a method for adding a column with default stats
@@ -385,6 +390,7 @@ impl TestChunk {
             sort_key: None,
             partition_id: TransitionPartitionId::arbitrary_for_testing(),
             quiet: false,
+            delete_predicates: vec![],
         }
     }
 
@@ -404,6 +410,11 @@ impl TestChunk {
         }
     }
 
+    pub fn with_delete_predicate(mut self, predicate: DeletePredicate) -> Self {
+        self.delete_predicates.push(Arc::new(predicate));
+        self
+    }
+
     pub fn with_dummy_parquet_file(self) -> Self {
         self.with_dummy_parquet_file_and_store("iox://store")
     }
@@ -1108,6 +1119,10 @@ impl QueryChunk for TestChunk {
         self.order
     }
 
+    fn delete_predicates(&self) -> &[Arc<DeletePredicate>] {
+        &self.delete_predicates
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -0,0 +1,185 @@
+use crate::error;
+use arrow::array::{
+    as_list_array, Array, ArrayRef, Float64Array, Int64Array, IntervalMonthDayNanoArray,
+    TimestampNanosecondArray,
+};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field, IntervalUnit, TimeUnit};
+use datafusion::common::{downcast_value, DataFusionError, Result, ScalarValue};
+use datafusion::logical_expr::{Accumulator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+/// The name of the integral aggregate function.
+pub(super) const NAME: &str = "integral";
+
+/// Valid signatures for the integral aggregate function.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        crate::NUMERICS
+            .iter()
+            .map(|dt| {
+                TypeSignature::Exact(vec![
+                    dt.clone(),
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                    DataType::Interval(IntervalUnit::MonthDayNano),
+                ])
+            })
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// Calculate the return type given the function signature. Integral
+/// always returns a Float64.
+pub(super) fn return_type(_: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(DataType::Float64))
+}
+
+/// Create a new accumulator for the data type.
+pub(super) fn accumulator(_: &DataType) -> Result<Box<dyn Accumulator>> {
+    Ok(Box::new(IntegralAccumulator::new()))
+}
+
+/// Calculate the intermediate merge state for the aggregator, which
+/// are the timestamps and values of all points and the unit.
+pub(super) fn state_type(_: &DataType) -> Result<Arc<Vec<DataType>>> {
+    Ok(Arc::new(vec![
+        DataType::List(Arc::new(Field::new(
+            "item",
+            DataType::Timestamp(TimeUnit::Nanosecond, None),
+            true,
+        ))),
+        DataType::List(Arc::new(Field::new("item", DataType::Float64, true))),
+        DataType::Int64,
+    ]))
+}
+
+#[derive(Debug)]
+struct IntegralAccumulator {
+    /// The timestamps and values of the points accumulated so far.
+    points: Vec<(i64, f64)>,
+    /// The unit of time, in nanoseconds, the result is computed for.
+    unit: Option<i64>,
+}
+
+impl IntegralAccumulator {
+    fn new() -> Self {
+        Self {
+            points: vec![],
+            unit: None,
+        }
+    }
+
+    fn update(&mut self, times: &ArrayRef, values: &ArrayRef) -> Result<()> {
+        let times = downcast_value!(times, TimestampNanosecondArray);
+        let values = cast(values, &DataType::Float64)?;
+        let values = downcast_value!(values, Float64Array);
+
+        self.points.reserve(values.len() - values.null_count());
+        self.points.extend(
+            times
+                .iter()
+                .zip(values.iter())
+                .filter_map(|(t, v)| t.zip(v)),
+        );
+        Ok(())
+    }
+
+    fn set_unit(&mut self, unit: i64) -> Result<()> {
+        if unit <= 0 {
+            return error::internal(format!("invalid unit ({unit}) for integral"));
+        }
+        self.unit.get_or_insert(unit);
+        Ok(())
+    }
+}
+
+impl Accumulator for IntegralAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        assert_eq!(values.len(), 3);
+
+        // INVARIANT:
+        // The planner guarantees that the third argument is always a duration
+        // literal.
+        let units = downcast_value!(&values[2], IntervalMonthDayNanoArray);
+        if self.unit.is_none() && !units.is_empty() && units.is_valid(0) {
+            self.set_unit(crate::interval_nanos(units.value(0))?)?;
+        }
+
+        self.update(&values[1], &values[0])
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        let Some(unit) = self.unit else {
+            return Ok(ScalarValue::Float64(None));
+        };
+        Ok(ScalarValue::Float64(integral(self.points.clone(), unit)))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self) + self.points.capacity() * std::mem::size_of::<(i64, f64)>()
+    }
+
+    fn state(&self) -> Result<Vec<ScalarValue>> {
+        let (times, values): (Vec<_>, Vec<_>) = self
+            .points
+            .iter()
+            .map(|(t, v)| {
+                (
+                    ScalarValue::TimestampNanosecond(Some(*t), None),
+                    ScalarValue::Float64(Some(*v)),
+                )
+            })
+            .unzip();
+        Ok(vec![
+            ScalarValue::new_list(Some(times), DataType::Timestamp(TimeUnit::Nanosecond, None)),
+            ScalarValue::new_list(Some(values), DataType::Float64),
+            ScalarValue::Int64(self.unit),
+        ])
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        assert_eq!(states.len(), 3);
+
+        let units = downcast_value!(&states[2], Int64Array);
+        if let Some(unit) = units.iter().flatten().next() {
+            self.set_unit(unit)?;
+        }
+
+        let times = as_list_array(&states[0]);
+        let values = as_list_array(&states[1]);
+        for idx in 0..times.len() {
+            self.update(&times.value(idx), &values.value(idx))?;
+        }
+        Ok(())
+    }
+}
+
+/// Calculate the area under the curve of the `points`, using the trapezoidal
+/// rule, expressed in multiples of `unit` nanoseconds. Returns `None` if
+/// there are no points.
+///
+/// When multiple points share the same timestamp, only the last is used,
+/// following the behaviour of the original influxdb implementation.
+///
+/// This accumulator is only used without a `GROUP BY time(..)` clause. With
+/// one, the area must be interpolated to the boundaries of each interval,
+/// which requires the points of the neighbouring intervals, so the planner
+/// sums the `integral_window` window function instead.
+fn integral(mut points: Vec<(i64, f64)>, unit: i64) -> Option<f64> {
+    points.sort_by_key(|(t, _)| *t);
+
+    let mut iter = points.into_iter();
+    let (mut prev_time, mut prev_value) = iter.next()?;
+    let mut sum = 0.0;
+    for (time, value) in iter {
+        if time != prev_time {
+            let elapsed = (time - prev_time) as f64 / unit as f64;
+            sum += 0.5 * (value + prev_value) * elapsed;
+        }
+        prev_time = time;
+        prev_value = value;
+    }
+    Some(sum)
+}
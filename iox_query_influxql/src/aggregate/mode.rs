@@ -0,0 +1,189 @@
+use arrow::array::{as_list_array, Array, ArrayRef, TimestampNanosecondArray};
+use arrow::datatypes::{DataType, Field, TimeUnit};
+use datafusion::common::{downcast_value, DataFusionError, Result, ScalarValue};
+use datafusion::logical_expr::{Accumulator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+/// The name of the mode aggregate function.
+pub(super) const NAME: &str = "mode";
+
+/// Valid signatures for the mode aggregate function. The arguments are
+/// the input values and their timestamps.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        crate::NUMERICS
+            .iter()
+            .chain(&[DataType::Utf8, DataType::Boolean])
+            .map(|dt| {
+                TypeSignature::Exact(vec![
+                    dt.clone(),
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                ])
+            })
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// Calculate the return type given the function signature. Mode
+/// always returns the same type as the input column.
+pub(super) fn return_type(signature: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(signature[0].clone()))
+}
+
+/// Create a new accumulator for the data type.
+pub(super) fn accumulator(dt: &DataType) -> Result<Box<dyn Accumulator>> {
+    Ok(Box::new(ModeAccumulator::new(dt.clone())))
+}
+
+/// Calculate the intermediate merge state for the aggregator, which are
+/// the values and timestamps of all points.
+pub(super) fn state_type(dt: &DataType) -> Result<Arc<Vec<DataType>>> {
+    Ok(Arc::new(vec![
+        DataType::List(Arc::new(Field::new("item", dt.clone(), true))),
+        DataType::List(Arc::new(Field::new(
+            "item",
+            DataType::Timestamp(TimeUnit::Nanosecond, None),
+            true,
+        ))),
+    ]))
+}
+
+#[derive(Debug)]
+struct ModeAccumulator {
+    data_type: DataType,
+    /// The values and timestamps of the points accumulated so far.
+    data: Vec<(ScalarValue, i64)>,
+}
+
+impl ModeAccumulator {
+    fn new(data_type: DataType) -> Self {
+        Self {
+            data_type,
+            data: vec![],
+        }
+    }
+
+    fn update(&mut self, values: &ArrayRef, times: &ArrayRef) -> Result<()> {
+        assert_eq!(values.data_type(), &self.data_type);
+        let times = downcast_value!(times, TimestampNanosecondArray);
+
+        self.data.reserve(values.len() - values.null_count());
+        for idx in 0..values.len() {
+            if values.is_valid(idx) && times.is_valid(idx) {
+                self.data
+                    .push((ScalarValue::try_from_array(values, idx)?, times.value(idx)))
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Accumulator for ModeAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        assert_eq!(values.len(), 2);
+
+        self.update(&values[0], &values[1])
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        Ok(mode(self.data.clone()).unwrap_or(ScalarValue::try_from(&self.data_type)?))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + self.data.capacity() * std::mem::size_of::<(ScalarValue, i64)>()
+            + self
+                .data
+                .iter()
+                .map(|(v, _)| v.size() - std::mem::size_of_val(v))
+                .sum::<usize>()
+    }
+
+    fn state(&self) -> Result<Vec<ScalarValue>> {
+        let (values, times): (Vec<_>, Vec<_>) = self
+            .data
+            .iter()
+            .map(|(v, t)| (v.clone(), ScalarValue::TimestampNanosecond(Some(*t), None)))
+            .unzip();
+        Ok(vec![
+            ScalarValue::new_list(Some(values), self.data_type.clone()),
+            ScalarValue::new_list(Some(times), DataType::Timestamp(TimeUnit::Nanosecond, None)),
+        ])
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        assert_eq!(states.len(), 2);
+
+        let values = as_list_array(&states[0]);
+        let times = as_list_array(&states[1]);
+        for idx in 0..values.len() {
+            self.update(&values.value(idx), &times.value(idx))?;
+        }
+        Ok(())
+    }
+}
+
+/// Return the most frequent value of the `data`. When there are multiple
+/// values with the same frequency, the value with the earliest timestamp is
+/// returned.
+///
+/// This follows the behaviour of the `mode` reducers of the original
+/// influxdb implementation.
+fn mode(mut data: Vec<(ScalarValue, i64)>) -> Option<ScalarValue> {
+    data.sort_by(|(a, a_time), (b, b_time)| {
+        a.partial_cmp(b)
+            .unwrap_or(Ordering::Equal)
+            .then(a_time.cmp(b_time))
+    });
+
+    // The most frequent value, its frequency and its earliest timestamp.
+    let mut most: Option<(ScalarValue, usize, i64)> = None;
+    let mut iter = data.into_iter().peekable();
+    while let Some((value, time)) = iter.next() {
+        let mut count = 1;
+        while iter.next_if(|(v, _)| v == &value).is_some() {
+            count += 1;
+        }
+        if most
+            .as_ref()
+            .map_or(true, |(_, n, t)| count > *n || (count == *n && time < *t))
+        {
+            most = Some((value, count, time));
+        }
+    }
+    most.map(|(value, _, _)| value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mode_ties() {
+        let data = |points: &[(i64, i64)]| {
+            points
+                .iter()
+                .map(|(v, t)| (ScalarValue::Int64(Some(*v)), *t))
+                .collect::<Vec<_>>()
+        };
+
+        // the most frequent value
+        assert_eq!(
+            mode(data(&[(1, 10), (2, 20), (2, 30)])),
+            Some(ScalarValue::Int64(Some(2)))
+        );
+        // on a tie, the value with the earliest timestamp
+        assert_eq!(
+            mode(data(&[(1, 40), (2, 20), (1, 10), (2, 30)])),
+            Some(ScalarValue::Int64(Some(1)))
+        );
+        assert_eq!(
+            mode(data(&[(1, 40), (2, 20), (1, 50), (2, 30)])),
+            Some(ScalarValue::Int64(Some(2)))
+        );
+        assert_eq!(mode(vec![]), None);
+    }
+}
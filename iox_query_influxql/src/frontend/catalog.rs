@@ -0,0 +1,371 @@
+//! InfluxQL statements which operate on the catalog, rather than query the
+//! data of a single namespace.
+use crate::error;
+use arrow::array::{DictionaryArray, StringArray};
+use arrow::datatypes::{DataType, Field, Int32Type, Schema as ArrowSchema};
+use arrow::record_batch::RecordBatch;
+use data_types::{DeleteExpr, DeletePredicate, Op, Scalar, TimestampRange};
+use datafusion::common::Result;
+use generated_types::influxdata::iox::querier::v1::InfluxQlMetadata;
+use influxdb_influxql_parser::common::MeasurementName;
+use influxdb_influxql_parser::delete::DeleteStatement;
+use influxdb_influxql_parser::expression::{
+    ConditionalBinary, ConditionalExpression, ConditionalOperator, Expr,
+};
+use influxdb_influxql_parser::literal::Literal;
+use influxdb_influxql_parser::statement::Statement;
+use influxdb_influxql_parser::time_range::{split_cond, ReduceContext};
+use influxdb_influxql_parser::timestamp::Timestamp;
+use schema::{INFLUXQL_MEASUREMENT_COLUMN_NAME, INFLUXQL_METADATA_KEY};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// An InfluxQL statement that creates, lists or removes namespaces, tables
+/// or rows. These statements are not planned as queries, and must be
+/// executed against the catalog by the caller.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CatalogStatement {
+    /// `CREATE DATABASE`, which creates a namespace.
+    ///
+    /// The replication factor, shard duration and name of the retention
+    /// policy have no equivalent in IOx and are ignored.
+    CreateDatabase {
+        /// The name of the namespace to create.
+        name: String,
+        /// The retention period of the namespace, where `None` is infinite.
+        retention_period_ns: Option<i64>,
+    },
+
+    /// `DELETE`, which deletes the rows matching a predicate from the
+    /// current namespace.
+    Delete {
+        /// The tables to delete from, or all tables of the namespace when
+        /// `None`.
+        table_names: Option<Vec<String>>,
+        /// The rows to delete.
+        predicate: DeletePredicate,
+    },
+
+    /// `DROP MEASUREMENT`, which deletes all the rows of a table of the
+    /// current namespace.
+    DropMeasurement {
+        /// The table to delete the rows of.
+        table_name: String,
+    },
+
+    /// `SHOW DATABASES`, which lists the namespaces of the catalog.
+    ShowDatabases,
+}
+
+impl CatalogStatement {
+    /// Returns the [`CatalogStatement`] for `statement`, or `None` if it is
+    /// a query that should be planned by the
+    /// [`InfluxQLQueryPlanner`](super::planner::InfluxQLQueryPlanner).
+    ///
+    /// The `now` value is used to evaluate `now()` in the time range of a
+    /// `DELETE` statement.
+    pub(crate) fn try_new(statement: &Statement, now: Timestamp) -> Result<Option<Self>> {
+        Ok(Some(match statement {
+            Statement::CreateDatabase(create) => Self::CreateDatabase {
+                name: create.name.to_string(),
+                retention_period_ns: create.duration.map(|d| *d).filter(|d| *d > 0),
+            },
+            Statement::Delete(delete) => {
+                let (from, condition) = match delete.as_ref() {
+                    DeleteStatement::FromWhere { from, condition } => {
+                        (Some(from), condition.as_ref())
+                    }
+                    DeleteStatement::Where(condition) => (None, Some(condition)),
+                };
+
+                let table_names = from
+                    .map(|from| {
+                        from.iter()
+                            .map(|name| match name {
+                                MeasurementName::Name(name) => Ok(name.to_string()),
+                                MeasurementName::Regex(_) => {
+                                    error::not_implemented("DELETE FROM with a regular expression")
+                                }
+                            })
+                            .collect::<Result<Vec<_>>>()
+                    })
+                    .transpose()?;
+
+                let predicate = match condition {
+                    Some(condition) => delete_predicate(condition, now)?,
+                    None => Self::drop_measurement_predicate(),
+                };
+
+                Self::Delete {
+                    table_names,
+                    predicate,
+                }
+            }
+            Statement::DropMeasurement(drop) => Self::DropMeasurement {
+                table_name: drop.name.to_string(),
+            },
+            Statement::ShowDatabases(_) => Self::ShowDatabases,
+            _ => return Ok(None),
+        }))
+    }
+
+    /// Returns the [`DeletePredicate`] that matches all the rows of a table,
+    /// which is used to implement `DROP MEASUREMENT`.
+    pub fn drop_measurement_predicate() -> DeletePredicate {
+        DeletePredicate {
+            range: TimestampRange::new(i64::MIN, i64::MAX),
+            exprs: vec![],
+        }
+    }
+}
+
+/// Convert the `condition` of a `DELETE` statement to a [`DeletePredicate`].
+///
+/// As with InfluxDB 1.x, the condition may only restrict the time range and
+/// compare tags to string literals, and only the `AND` operator is supported.
+fn delete_predicate(condition: &ConditionalExpression, now: Timestamp) -> Result<DeletePredicate> {
+    let rc = ReduceContext {
+        now: Some(now),
+        tz: None,
+    };
+    let (cond, time_range) = split_cond(&rc, condition).map_err(error::map::expr_error)?;
+
+    let mut exprs = vec![];
+    if let Some(cond) = cond {
+        delete_exprs(&cond, &mut exprs)?;
+    }
+
+    // The upper bound of the time range is inclusive, whereas the end of
+    // a TimestampRange is exclusive.
+    let range = TimestampRange::new(
+        time_range.lower.unwrap_or(i64::MIN),
+        time_range
+            .upper
+            .map_or(i64::MAX, |upper| upper.saturating_add(1)),
+    );
+
+    Ok(DeletePredicate { range, exprs })
+}
+
+/// Append the tag comparisons of `cond` to `exprs`.
+fn delete_exprs(cond: &ConditionalExpression, exprs: &mut Vec<DeleteExpr>) -> Result<()> {
+    match cond {
+        ConditionalExpression::Grouped(cond) => delete_exprs(cond, exprs),
+        ConditionalExpression::Binary(ConditionalBinary {
+            lhs,
+            op: ConditionalOperator::And,
+            rhs,
+        }) => {
+            delete_exprs(lhs, exprs)?;
+            delete_exprs(rhs, exprs)
+        }
+        ConditionalExpression::Binary(ConditionalBinary {
+            lhs,
+            op: op @ (ConditionalOperator::Eq | ConditionalOperator::NotEq),
+            rhs,
+        }) => match (lhs.expr(), rhs.expr()) {
+            (Some(Expr::VarRef(var_ref)), Some(Expr::Literal(Literal::String(value))))
+            | (Some(Expr::Literal(Literal::String(value))), Some(Expr::VarRef(var_ref))) => {
+                exprs.push(DeleteExpr::new(
+                    var_ref.name.to_string(),
+                    if matches!(op, ConditionalOperator::Eq) {
+                        Op::Eq
+                    } else {
+                        Op::Ne
+                    },
+                    Scalar::String(value.clone()),
+                ));
+                Ok(())
+            }
+            _ => error::query(format!(
+                "unsupported DELETE condition: {cond}, expected a tag compared to a string"
+            )),
+        },
+        _ => error::query(format!(
+            "unsupported DELETE condition: {cond}, expected a tag comparison or time range"
+        )),
+    }
+}
+
+/// The name of the measurement returned by `SHOW DATABASES`.
+const DATABASES_MEASUREMENT_NAME: &str = "databases";
+
+/// Returns the results of `SHOW DATABASES`, which lists the namespace
+/// `names`.
+pub fn show_databases_record_batch(names: Vec<String>) -> Result<RecordBatch> {
+    let schema = output_schema(vec![Field::new("name", DataType::Utf8, false)])?;
+    let keys = vec![0; names.len()];
+    Ok(RecordBatch::try_new(
+        schema,
+        vec![
+            Arc::new(DictionaryArray::<Int32Type>::try_new(
+                keys.into(),
+                Arc::new(StringArray::from(vec![DATABASES_MEASUREMENT_NAME])),
+            )?),
+            Arc::new(StringArray::from(names)),
+        ],
+    )?)
+}
+
+/// Returns the results of the `CREATE DATABASE`, `DELETE` and
+/// `DROP MEASUREMENT` statements, which have no rows.
+pub fn empty_record_batch() -> Result<RecordBatch> {
+    Ok(RecordBatch::new_empty(output_schema(vec![])?))
+}
+
+/// Returns the schema of the results of a [`CatalogStatement`], which has
+/// the measurement column followed by `fields`.
+fn output_schema(fields: Vec<Field>) -> Result<Arc<ArrowSchema>> {
+    let md = serde_json::to_string(&InfluxQlMetadata {
+        measurement_column_index: 0,
+        tag_key_columns: vec![],
+    })
+    .map_err(|err| error::map::internal(format!("error serializing InfluxQL metadata: {err}")))?;
+
+    Ok(Arc::new(ArrowSchema::new_with_metadata(
+        std::iter::once(Field::new(
+            INFLUXQL_MEASUREMENT_COLUMN_NAME,
+            DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+            false,
+        ))
+        .chain(fields)
+        .collect::<Vec<_>>(),
+        HashMap::from([(INFLUXQL_METADATA_KEY.to_owned(), md)]),
+    )))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use influxdb_influxql_parser::parse_statements;
+
+    fn catalog_statement(q: &str) -> Result<Option<CatalogStatement>> {
+        let statement = parse_statements(q).unwrap().pop().unwrap();
+        let now = Utc.timestamp_opt(1_000, 0).unwrap();
+        CatalogStatement::try_new(&statement, now.into())
+    }
+
+    #[test]
+    fn test_catalog_statement() {
+        assert_eq!(catalog_statement("SELECT foo FROM bar").unwrap(), None);
+        assert_eq!(catalog_statement("SHOW MEASUREMENTS").unwrap(), None);
+
+        assert_eq!(
+            catalog_statement("SHOW DATABASES").unwrap(),
+            Some(CatalogStatement::ShowDatabases)
+        );
+
+        assert_eq!(
+            catalog_statement("CREATE DATABASE foo").unwrap(),
+            Some(CatalogStatement::CreateDatabase {
+                name: "foo".into(),
+                retention_period_ns: None,
+            })
+        );
+        assert_eq!(
+            catalog_statement("CREATE DATABASE foo WITH DURATION 1h REPLICATION 1").unwrap(),
+            Some(CatalogStatement::CreateDatabase {
+                name: "foo".into(),
+                retention_period_ns: Some(3_600_000_000_000),
+            })
+        );
+        // A duration of 0 is an infinite retention period
+        assert_eq!(
+            catalog_statement("CREATE DATABASE foo WITH DURATION 0s").unwrap(),
+            Some(CatalogStatement::CreateDatabase {
+                name: "foo".into(),
+                retention_period_ns: None,
+            })
+        );
+
+        assert_eq!(
+            catalog_statement("DROP MEASUREMENT cpu").unwrap(),
+            Some(CatalogStatement::DropMeasurement {
+                table_name: "cpu".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_delete() {
+        assert_eq!(
+            catalog_statement("DELETE FROM cpu").unwrap(),
+            Some(CatalogStatement::Delete {
+                table_names: Some(vec!["cpu".into()]),
+                predicate: CatalogStatement::drop_measurement_predicate(),
+            })
+        );
+
+        assert_eq!(
+            catalog_statement("DELETE FROM cpu, mem WHERE host = 'a' AND (region != 'b')").unwrap(),
+            Some(CatalogStatement::Delete {
+                table_names: Some(vec!["cpu".into(), "mem".into()]),
+                predicate: DeletePredicate {
+                    range: TimestampRange::new(i64::MIN, i64::MAX),
+                    exprs: vec![
+                        DeleteExpr::new("host".into(), Op::Eq, Scalar::String("a".into())),
+                        DeleteExpr::new("region".into(), Op::Ne, Scalar::String("b".into())),
+                    ],
+                },
+            })
+        );
+
+        assert_eq!(
+            catalog_statement("DELETE WHERE time >= 10 AND time <= now() AND 'a' = host").unwrap(),
+            Some(CatalogStatement::Delete {
+                table_names: None,
+                predicate: DeletePredicate {
+                    range: TimestampRange::new(10, 1_000_000_000_001),
+                    exprs: vec![DeleteExpr::new(
+                        "host".into(),
+                        Op::Eq,
+                        Scalar::String("a".into())
+                    )],
+                },
+            })
+        );
+
+        // Fallible
+
+        assert_eq!(
+            catalog_statement("DELETE FROM /cpu/")
+                .unwrap_err()
+                .to_string(),
+            "This feature is not implemented: DELETE FROM with a regular expression"
+        );
+        assert_eq!(
+            catalog_statement("DELETE WHERE host = 'a' OR host = 'b'")
+                .unwrap_err()
+                .to_string(),
+            "Error during planning: unsupported DELETE condition: host = 'a' OR host = 'b', expected a tag comparison or time range"
+        );
+        assert_eq!(
+            catalog_statement("DELETE WHERE host =~ /a/")
+                .unwrap_err()
+                .to_string(),
+            "Error during planning: unsupported DELETE condition: host =~ /a/, expected a tag comparison or time range"
+        );
+        assert_eq!(
+            catalog_statement("DELETE WHERE value = 1")
+                .unwrap_err()
+                .to_string(),
+            "Error during planning: unsupported DELETE condition: value = 1, expected a tag compared to a string"
+        );
+    }
+
+    #[test]
+    fn test_show_databases_record_batch() {
+        let batch = show_databases_record_batch(vec!["foo".into(), "bar".into()]).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.schema().field(1).name(), "name");
+        assert!(batch
+            .schema()
+            .metadata()
+            .contains_key(INFLUXQL_METADATA_KEY));
+
+        let batch = empty_record_batch().unwrap();
+        assert_eq!(batch.num_rows(), 0);
+        assert_eq!(batch.num_columns(), 1);
+    }
+}
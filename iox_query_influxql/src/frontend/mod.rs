@@ -1 +1,3 @@
+pub mod catalog;
+pub mod params;
 pub mod planner;
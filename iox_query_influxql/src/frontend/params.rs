@@ -0,0 +1,182 @@
+//! Bind parameters of InfluxQL queries.
+//!
+//! Bind parameters are referenced in a query as `$name`, and are substituted
+//! with the literal value provided for `name` prior to planning, as described
+//! in the [InfluxDB 1.x documentation].
+//!
+//! [InfluxDB 1.x documentation]: https://docs.influxdata.com/influxdb/v1.8/tools/api/#bind-parameters
+
+use std::collections::HashMap;
+
+use datafusion::common::Result;
+use influxdb_influxql_parser::{
+    expression::Expr,
+    literal::Literal,
+    statement::Statement,
+    visit_mut::{VisitableMut, VisitorMut},
+};
+
+use crate::error;
+
+/// The value of a bind parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatementParam {
+    /// A boolean value.
+    Boolean(bool),
+    /// A signed integer value.
+    Integer(i64),
+    /// An unsigned integer value.
+    Unsigned(u64),
+    /// A floating point value.
+    Float(f64),
+    /// A string value.
+    String(String),
+}
+
+impl From<StatementParam> for Literal {
+    fn from(value: StatementParam) -> Self {
+        match value {
+            StatementParam::Boolean(v) => Self::Boolean(v),
+            StatementParam::Integer(v) => Self::Integer(v),
+            StatementParam::Unsigned(v) => Self::Unsigned(v),
+            StatementParam::Float(v) => Self::Float(v),
+            StatementParam::String(v) => Self::String(v),
+        }
+    }
+}
+
+/// Convert a value of a JSON object of parameters, as accepted by the `params`
+/// argument of the InfluxDB 1.x HTTP query API.
+///
+/// Integral numbers are converted to [`StatementParam::Integer`], or
+/// [`StatementParam::Unsigned`] if they exceed [`i64::MAX`].
+impl TryFrom<serde_json::Value> for StatementParam {
+    type Error = datafusion::error::DataFusionError;
+
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        use serde_json::Value;
+
+        match value {
+            Value::Bool(v) => Ok(Self::Boolean(v)),
+            Value::Number(v) => match (v.as_i64(), v.as_u64(), v.as_f64()) {
+                (Some(v), _, _) => Ok(Self::Integer(v)),
+                (None, Some(v), _) => Ok(Self::Unsigned(v)),
+                (None, None, Some(v)) => Ok(Self::Float(v)),
+                (None, None, None) => error::query(format!("invalid parameter value: {v}")),
+            },
+            Value::String(v) => Ok(Self::String(v)),
+            Value::Null | Value::Array(_) | Value::Object(_) => error::query(format!(
+                "unsupported parameter value: {value}, expected a boolean, number or string"
+            )),
+        }
+    }
+}
+
+/// A map of bind parameter names to their values.
+pub type StatementParams = HashMap<String, StatementParam>;
+
+/// Replace every bind parameter referenced by `statement` with the literal
+/// value provided in `params`.
+///
+/// Parameters that are provided but not referenced by `statement` are ignored.
+///
+/// # Errors
+///
+/// Returns an error if `statement` references a parameter that is not present
+/// in `params`.
+pub(super) fn replace_bind_parameters(
+    statement: &mut Statement,
+    params: &StatementParams,
+) -> Result<()> {
+    struct Replacer<'a>(&'a StatementParams);
+
+    impl VisitorMut for Replacer<'_> {
+        type Error = datafusion::error::DataFusionError;
+
+        fn post_visit_expr(&mut self, n: &mut Expr) -> Result<(), Self::Error> {
+            if let Expr::BindParameter(name) = n {
+                let Some(value) = self.0.get(name.as_str()) else {
+                    return error::query(format!("missing parameter: {}", name.as_str()));
+                };
+                *n = Expr::Literal(value.clone().into());
+            }
+            Ok(())
+        }
+    }
+
+    statement.accept(&mut Replacer(params))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use assert_matches::assert_matches;
+    use datafusion::error::DataFusionError;
+    use influxdb_influxql_parser::parse_statements;
+
+    fn replace(query: &str, params: &StatementParams) -> Result<String> {
+        let mut statement = parse_statements(query).unwrap().pop().unwrap();
+        replace_bind_parameters(&mut statement, params)?;
+        Ok(statement.to_string())
+    }
+
+    #[test]
+    fn test_replace_bind_parameters() {
+        let params = StatementParams::from([
+            ("host".to_string(), StatementParam::String("west".into())),
+            ("n".to_string(), StatementParam::Integer(-3)),
+            ("u".to_string(), StatementParam::Unsigned(u64::MAX)),
+            ("f".to_string(), StatementParam::Float(1.5)),
+            ("b".to_string(), StatementParam::Boolean(true)),
+            ("unused".to_string(), StatementParam::Boolean(false)),
+        ]);
+
+        assert_eq!(
+            replace(
+                "SELECT usage_idle + $n FROM cpu WHERE host = $host AND usage_idle > $f",
+                &params
+            )
+            .unwrap(),
+            "SELECT usage_idle + -3 FROM cpu WHERE host = 'west' AND usage_idle > 1.5"
+        );
+        assert_eq!(
+            replace("SELECT value FROM cpu WHERE value = $u OR up = $b", &params).unwrap(),
+            "SELECT value FROM cpu WHERE value = 18446744073709551615 OR up = true"
+        );
+        assert_eq!(
+            replace("DELETE FROM cpu WHERE host = $\"host\"", &params).unwrap(),
+            "DELETE FROM cpu WHERE host = 'west'"
+        );
+
+        // queries without parameters are unchanged
+        assert_eq!(
+            replace("SELECT usage_idle FROM cpu", &StatementParams::new()).unwrap(),
+            "SELECT usage_idle FROM cpu"
+        );
+
+        let err = replace("SELECT usage_idle FROM cpu WHERE host = $region", &params).unwrap_err();
+        assert_matches!(err, DataFusionError::Plan(msg) => {
+            assert_eq!(msg, "missing parameter: region");
+        });
+    }
+
+    #[test]
+    fn test_statement_param_from_json() {
+        use serde_json::json;
+
+        let param = |v| StatementParam::try_from(v);
+
+        assert_matches!(param(json!(true)), Ok(StatementParam::Boolean(true)));
+        assert_matches!(param(json!(-1)), Ok(StatementParam::Integer(-1)));
+        assert_matches!(
+            param(json!(u64::MAX)),
+            Ok(StatementParam::Unsigned(u64::MAX))
+        );
+        assert_matches!(param(json!(1.0)), Ok(StatementParam::Float(v)) if v == 1.0);
+        assert_matches!(param(json!("west")), Ok(StatementParam::String(v)) if v == "west");
+
+        assert_matches!(param(json!(null)), Err(DataFusionError::Plan(_)));
+        assert_matches!(param(json!([1])), Err(DataFusionError::Plan(_)));
+        assert_matches!(param(json!({"a": 1})), Err(DataFusionError::Plan(_)));
+    }
+}
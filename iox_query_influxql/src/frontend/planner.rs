@@ -1,7 +1,8 @@
 use arrow::datatypes::SchemaRef;
 use datafusion::physical_expr::execution_props::ExecutionProps;
+use influxdb_influxql_parser::show::OnClause;
 use influxdb_influxql_parser::show_field_keys::ShowFieldKeysStatement;
-use influxdb_influxql_parser::show_measurements::ShowMeasurementsStatement;
+use influxdb_influxql_parser::show_measurements::{ExtendedOnClause, ShowMeasurementsStatement};
 use influxdb_influxql_parser::show_tag_keys::ShowTagKeysStatement;
 use influxdb_influxql_parser::show_tag_values::ShowTagValuesStatement;
 use std::any::Any;
@@ -11,7 +12,10 @@ use std::fmt::Debug;
 use std::ops::Deref;
 use std::sync::Arc;
 
+use super::catalog::CatalogStatement;
+use super::params::{replace_bind_parameters, StatementParams};
 use crate::plan::{parse_regex, InfluxQLToLogicalPlan, SchemaProvider};
+use chrono::Utc;
 use datafusion::common::Statistics;
 use datafusion::datasource::provider_as_source;
 use datafusion::execution::context::{SessionState, TaskContext};
@@ -24,7 +28,7 @@ use datafusion::{
     error::{DataFusionError, Result},
     physical_plan::ExecutionPlan,
 };
-use influxdb_influxql_parser::common::MeasurementName;
+use influxdb_influxql_parser::common::{MeasurementName, QualifiedMeasurementName};
 use influxdb_influxql_parser::parse_statements;
 use influxdb_influxql_parser::statement::Statement;
 use influxdb_influxql_parser::visit::{Visitable, Visitor};
@@ -146,14 +150,19 @@ impl InfluxQLQueryPlanner {
 
     /// Plan an InfluxQL query against the catalogs registered with `ctx`, and return a
     /// DataFusion physical execution plan that runs on the query executor.
+    ///
+    /// Any bind parameters referenced by `query` are replaced with the
+    /// corresponding values of `params`.
     pub async fn query(
         &self,
         query: &str,
+        params: &StatementParams,
         ctx: &IOxSessionContext,
     ) -> Result<Arc<dyn ExecutionPlan>> {
         debug!(text=%query, "planning InfluxQL query");
 
-        let statement = self.query_to_statement(query)?;
+        let mut statement = self.query_to_statement(query)?;
+        replace_bind_parameters(&mut statement, params)?;
         let logical_plan = self.statement_to_plan(statement, ctx).await?;
 
         let input = ctx.create_physical_plan(&logical_plan).await?;
@@ -172,6 +181,34 @@ impl InfluxQLQueryPlanner {
         Ok(Arc::new(SchemaExec { input, schema }))
     }
 
+    /// Parse `query` and return the [`CatalogStatement`] it represents, or
+    /// `None` if it is a query that should be planned with
+    /// [`query`](Self::query).
+    ///
+    /// Any bind parameters referenced by `query` are replaced with the
+    /// corresponding values of `params`.
+    pub fn catalog_statement(
+        &self,
+        query: &str,
+        params: &StatementParams,
+    ) -> Result<Option<CatalogStatement>> {
+        let mut statement = self.query_to_statement(query)?;
+        replace_bind_parameters(&mut statement, params)?;
+        CatalogStatement::try_new(&statement, Utc::now().into())
+    }
+
+    /// Parse `query` and return the name of the namespace it references,
+    /// through an `ON` clause or the database and retention policy of its
+    /// measurements, or `None` if it applies to the namespace of the session.
+    ///
+    /// The caller must register the catalog of the namespace with the
+    /// [`IOxSessionContext`] passed to [`query`](Self::query), under the
+    /// name of the namespace.
+    pub fn namespace(&self, query: &str) -> Result<Option<String>> {
+        let statement = self.query_to_statement(query)?;
+        find_namespace(&statement)
+    }
+
     async fn statement_to_plan(
         &self,
         statement: Statement,
@@ -181,22 +218,26 @@ impl InfluxQLQueryPlanner {
 
         let session_cfg = ctx.inner().copied_config();
         let cfg = session_cfg.options();
-        let schema = ctx
-            .inner()
-            .catalog(&cfg.catalog.default_catalog)
-            .ok_or_else(|| {
-                DataFusionError::Plan(format!(
-                    "failed to resolve catalog: {}",
-                    cfg.catalog.default_catalog
-                ))
-            })?
-            .schema(&cfg.catalog.default_schema)
-            .ok_or_else(|| {
-                DataFusionError::Plan(format!(
-                    "failed to resolve schema: {}",
-                    cfg.catalog.default_schema
-                ))
-            })?;
+        let catalog = match find_namespace(&statement)? {
+            Some(namespace_name) => ctx.inner().catalog(&namespace_name).ok_or_else(|| {
+                DataFusionError::Plan(format!("database not found: {namespace_name}"))
+            })?,
+            None => ctx
+                .inner()
+                .catalog(&cfg.catalog.default_catalog)
+                .ok_or_else(|| {
+                    DataFusionError::Plan(format!(
+                        "failed to resolve catalog: {}",
+                        cfg.catalog.default_catalog
+                    ))
+                })?,
+        };
+        let schema = catalog.schema(&cfg.catalog.default_schema).ok_or_else(|| {
+            DataFusionError::Plan(format!(
+                "failed to resolve schema: {}",
+                cfg.catalog.default_schema
+            ))
+        })?;
         let names = schema.table_names();
         let query_tables = find_all_measurements(&statement, &names)?;
 
@@ -237,6 +278,87 @@ impl InfluxQLQueryPlanner {
     }
 }
 
+/// Returns the name of the namespace for the database `database` and the
+/// retention policy `retention_policy`, following the naming used by the
+/// InfluxDB 1.x write API.
+pub fn namespace_name(database: &str, retention_policy: Option<&str>) -> String {
+    match retention_policy {
+        Some(rp) if !is_default_retention_policy(rp) => format!("{database}/{rp}"),
+        _ => database.to_string(),
+    }
+}
+
+/// Returns `true` if `rp` names the default retention policy of a database.
+fn is_default_retention_policy(rp: &str) -> bool {
+    rp.eq_ignore_ascii_case("autogen") || rp.eq_ignore_ascii_case("default")
+}
+
+/// Find the namespace referenced by the `ON` clause or the qualified
+/// measurement names of `stmt`, including those of any subqueries.
+///
+/// A statement may only reference a single namespace.
+fn find_namespace(stmt: &Statement) -> Result<Option<String>> {
+    struct Matcher<'a>(&'a mut Option<String>);
+    impl<'a> Matcher<'a> {
+        fn reference(self, namespace_name: String) -> Result<Self> {
+            match self.0.as_deref() {
+                Some(existing) if existing != namespace_name => {
+                    Err(DataFusionError::NotImplemented(format!(
+                        "statements referencing multiple databases: {existing}, {namespace_name}"
+                    )))
+                }
+                Some(_) => Ok(self),
+                None => {
+                    *self.0 = Some(namespace_name);
+                    Ok(self)
+                }
+            }
+        }
+    }
+
+    impl<'a> Visitor for Matcher<'a> {
+        type Error = DataFusionError;
+
+        fn post_visit_qualified_measurement_name(
+            self,
+            n: &QualifiedMeasurementName,
+        ) -> Result<Self, Self::Error> {
+            match (&n.database, &n.retention_policy) {
+                (Some(db), rp) => {
+                    self.reference(namespace_name(db, rp.as_deref().map(|rp| rp.as_str())))
+                }
+                (None, Some(rp)) if !is_default_retention_policy(rp) => {
+                    Err(DataFusionError::NotImplemented(format!(
+                        "retention policy {rp} without a database in FROM clause"
+                    )))
+                }
+                (None, _) => Ok(self),
+            }
+        }
+
+        fn post_visit_on_clause(self, n: &OnClause) -> Result<Self, Self::Error> {
+            self.reference(namespace_name(n, None))
+        }
+
+        fn post_visit_extended_on_clause(self, n: &ExtendedOnClause) -> Result<Self, Self::Error> {
+            match n {
+                ExtendedOnClause::Database(db) => self.reference(namespace_name(db, None)),
+                ExtendedOnClause::DatabaseRetentionPolicy(db, rp) => {
+                    self.reference(namespace_name(db, Some(rp)))
+                }
+                ExtendedOnClause::AllDatabases
+                | ExtendedOnClause::AllDatabasesAndRetentionPolicies => Err(
+                    DataFusionError::NotImplemented(format!("SHOW MEASUREMENTS {n}")),
+                ),
+            }
+        }
+    }
+
+    let mut namespace_name = None;
+    stmt.accept(Matcher(&mut namespace_name))?;
+    Ok(namespace_name)
+}
+
 fn find_all_measurements(stmt: &Statement, tables: &[String]) -> Result<HashSet<String>> {
     struct Matcher<'a>(&'a mut HashSet<String>, &'a [String]);
     impl<'a> Visitor for Matcher<'a> {
@@ -404,4 +526,63 @@ mod test {
         assert!(find("SELECT * FROM /^l/").is_empty());
         assert!(find("SELECT * FROM (SELECT * FROM /^l/)").is_empty());
     }
+
+    #[test]
+    fn test_find_namespace() {
+        fn find(q: &str) -> Result<Option<String>> {
+            let p = InfluxQLQueryPlanner::new();
+            let s = p.query_to_statement(q).unwrap();
+            find_namespace(&s)
+        }
+
+        assert_eq!(find("SELECT * FROM foo").unwrap(), None);
+        assert_eq!(find("SELECT * FROM autogen.foo").unwrap(), None);
+        assert_eq!(find("SELECT * FROM db..foo").unwrap(), Some("db".into()));
+        assert_eq!(
+            find("SELECT * FROM db.autogen.foo, db..bar").unwrap(),
+            Some("db".into())
+        );
+        assert_eq!(
+            find("SELECT * FROM db.one_week./^foo/").unwrap(),
+            Some("db/one_week".into())
+        );
+        assert_eq!(
+            find("SELECT * FROM (SELECT * FROM db..foo)").unwrap(),
+            Some("db".into())
+        );
+        assert_eq!(find("SHOW TAG KEYS ON db").unwrap(), Some("db".into()));
+        assert_eq!(find("SHOW FIELD KEYS ON db").unwrap(), Some("db".into()));
+        assert_eq!(
+            find("SHOW TAG VALUES ON db WITH KEY = k").unwrap(),
+            Some("db".into())
+        );
+        assert_eq!(find("SHOW MEASUREMENTS ON db").unwrap(), Some("db".into()));
+        assert_eq!(
+            find("SHOW MEASUREMENTS ON db.one_week").unwrap(),
+            Some("db/one_week".into())
+        );
+        assert_eq!(
+            find("SHOW RETENTION POLICIES ON db").unwrap(),
+            Some("db".into())
+        );
+
+        // Fallible
+
+        assert_error!(
+            find("SELECT * FROM db..foo, other..bar"),
+            DataFusionError::NotImplemented(ref s) if s == "statements referencing multiple databases: db, other"
+        );
+        assert_error!(
+            find("SHOW TAG KEYS ON db FROM other..foo"),
+            DataFusionError::NotImplemented(ref s) if s == "statements referencing multiple databases: db, other"
+        );
+        assert_error!(
+            find("SELECT * FROM one_week.foo"),
+            DataFusionError::NotImplemented(ref s) if s == "retention policy one_week without a database in FROM clause"
+        );
+        assert_error!(
+            find("SHOW MEASUREMENTS ON *"),
+            DataFusionError::NotImplemented(ref s) if s == "SHOW MEASUREMENTS ON *"
+        );
+    }
 }
@@ -12,7 +12,7 @@
     unused_crate_dependencies
 )]
 
-use arrow::datatypes::DataType;
+use arrow::datatypes::{DataType, IntervalMonthDayNanoType};
 
 // Workaround for "unused crate" lint false positives.
 use workspace_hack as _;
@@ -26,3 +26,16 @@ mod window;
 /// A list of the numeric types supported by InfluxQL that can be be used
 /// as input to user-defined functions.
 static NUMERICS: &[DataType] = &[DataType::Int64, DataType::UInt64, DataType::Float64];
+
+/// Convert the `MonthDayNano` interval `interval` to a duration in nanoseconds.
+///
+/// Months and days do not have a fixed number of nanoseconds, so intervals
+/// with those components are rejected. InfluxQL durations never have them.
+fn interval_nanos(interval: i128) -> datafusion::common::Result<i64> {
+    match IntervalMonthDayNanoType::to_parts(interval) {
+        (0, 0, nanos) => Ok(nanos),
+        (months, days, _) => error::internal(format!(
+            "expected a duration, got an interval of {months} months and {days} days"
+        )),
+    }
+}
@@ -11,7 +11,7 @@ use influxdb_influxql_parser::common::{
 use influxdb_influxql_parser::expression::{ConditionalExpression, Expr};
 use influxdb_influxql_parser::select::{
     FieldList, FillClause, FromMeasurementClause, GroupByClause, MeasurementSelection,
-    SelectStatement, TimeZoneClause,
+    SLimitClause, SOffsetClause, SelectStatement, TimeZoneClause,
 };
 use influxdb_influxql_parser::time_range::TimeRange;
 use schema::{InfluxColumnType, Schema};
@@ -81,6 +81,12 @@ pub(super) struct Select {
     /// A value to specify an offset to start retrieving rows.
     pub(super) offset: Option<OffsetClause>,
 
+    /// A value to restrict the number of series returned.
+    pub(super) series_limit: Option<SLimitClause>,
+
+    /// A value to specify the number of series to skip.
+    pub(super) series_offset: Option<SOffsetClause>,
+
     /// The timezone for the query, specified as [`tz('<time zone>')`][time_zone_clause].
     ///
     /// [time_zone_clause]: https://docs.influxdata.com/influxdb/v1.8/query_language/explore-data/#the-time-zone-clause
@@ -124,8 +130,8 @@ impl From<Select> for SelectStatement {
             order_by: value.order_by,
             limit: value.limit,
             offset: value.offset,
-            series_limit: None,
-            series_offset: None,
+            series_limit: value.series_limit,
+            series_offset: value.series_offset,
             timezone: value.timezone.map(TimeZoneClause::new),
         }
     }
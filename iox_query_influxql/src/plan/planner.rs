@@ -1,6 +1,6 @@
 mod select;
 
-use crate::aggregate::PERCENTILE;
+use crate::aggregate::{INTEGRAL, MODE, PERCENTILE, SPREAD};
 use crate::error;
 use crate::plan::ir::{DataSource, Field, Interval, Select, SelectQuery};
 use crate::plan::planner::select::{
@@ -10,21 +10,30 @@ use crate::plan::planner::select::{
 use crate::plan::planner_time_range_expression::time_range_to_df_expr;
 use crate::plan::rewriter::{find_table_names, rewrite_statement, ProjectionType};
 use crate::plan::udf::{
-    cumulative_sum, derivative, difference, find_window_udfs, moving_average,
-    non_negative_derivative, non_negative_difference,
+    chande_momentum_oscillator, cumulative_sum, derivative, difference,
+    double_exponential_moving_average, elapsed, exponential_moving_average, find_window_udfs,
+    holt_winters, holt_winters_with_fit, kaufmans_adaptive_moving_average,
+    kaufmans_efficiency_ratio, moving_average, non_negative_derivative, non_negative_difference,
+    relative_strength_index, triple_exponential_derivative, triple_exponential_moving_average,
 };
 use crate::plan::util::{binary_operator_to_df_operator, rebase_expr, IQLSchema};
 use crate::plan::var_ref::var_ref_data_type_to_data_type;
 use crate::plan::{planner_rewrite_expression, udf};
 use crate::window::{
-    CUMULATIVE_SUM, DERIVATIVE, DIFFERENCE, MOVING_AVERAGE, NON_NEGATIVE_DERIVATIVE,
-    NON_NEGATIVE_DIFFERENCE, PERCENT_ROW_NUMBER,
+    CHANDE_MOMENTUM_OSCILLATOR, CUMULATIVE_SUM, DERIVATIVE, DIFFERENCE,
+    DOUBLE_EXPONENTIAL_MOVING_AVERAGE, ELAPSED, EXPONENTIAL_MOVING_AVERAGE, HOLT_WINTERS,
+    HOLT_WINTERS_WITH_FIT, INTEGRAL_WINDOW, KAUFMANS_ADAPTIVE_MOVING_AVERAGE,
+    KAUFMANS_EFFICIENCY_RATIO, MOVING_AVERAGE, NON_NEGATIVE_DERIVATIVE, NON_NEGATIVE_DIFFERENCE,
+    PERCENT_ROW_NUMBER, RELATIVE_STRENGTH_INDEX, TRIPLE_EXPONENTIAL_DERIVATIVE,
+    TRIPLE_EXPONENTIAL_MOVING_AVERAGE,
 };
 use arrow::array::{
     BooleanArray, DictionaryArray, Int32Array, Int64Array, StringArray, StringBuilder,
     StringDictionaryBuilder,
 };
-use arrow::datatypes::{DataType, Field as ArrowField, Int32Type, Schema as ArrowSchema};
+use arrow::datatypes::{
+    DataType, Field as ArrowField, Int32Type, IntervalMonthDayNanoType, Schema as ArrowSchema,
+};
 use arrow::record_batch::RecordBatch;
 use chrono_tz::Tz;
 use datafusion::catalog::TableReference;
@@ -37,8 +46,8 @@ use datafusion::logical_expr::logical_plan::builder::project;
 use datafusion::logical_expr::logical_plan::Analyze;
 use datafusion::logical_expr::utils::{expr_as_column_expr, find_aggregate_exprs};
 use datafusion::logical_expr::{
-    binary_expr, col, date_bin, expr, expr::WindowFunction, lit, lit_timestamp_nano, now, union,
-    window_function, AggregateFunction, AggregateUDF, Between, BuiltInWindowFunction,
+    binary_expr, col, date_bin, expr, expr::WindowFunction, lit, lit_timestamp_nano, now, random,
+    union, window_function, AggregateFunction, AggregateUDF, Between, BuiltInWindowFunction,
     BuiltinScalarFunction, EmptyRelation, Explain, Expr, ExprSchemable, Extension, LogicalPlan,
     LogicalPlanBuilder, Operator, PlanType, Projection, ScalarUDF, TableSource, ToStringifiedPlan,
     WindowFrame, WindowFrameBound, WindowFrameUnits,
@@ -58,10 +67,10 @@ use influxdb_influxql_parser::expression::{
 use influxdb_influxql_parser::functions::{
     is_aggregate_function, is_now_function, is_scalar_math_function,
 };
-use influxdb_influxql_parser::select::{FillClause, GroupByClause};
+use influxdb_influxql_parser::select::{FillClause, GroupByClause, SLimitClause, SOffsetClause};
 use influxdb_influxql_parser::show_field_keys::ShowFieldKeysStatement;
 use influxdb_influxql_parser::show_measurements::{
-    ShowMeasurementsStatement, WithMeasurementClause,
+    ExtendedOnClause, ShowMeasurementsStatement, WithMeasurementClause,
 };
 use influxdb_influxql_parser::show_retention_policies::ShowRetentionPoliciesStatement;
 use influxdb_influxql_parser::show_tag_keys::ShowTagKeysStatement;
@@ -431,6 +440,10 @@ impl<'a> Context<'a> {
 
 #[allow(missing_debug_implementations)]
 /// InfluxQL query planner
+///
+/// The database named by the `ON` clause or the qualified measurement names
+/// of a statement is not resolved by the planner; the caller is expected to
+/// provide the [`SchemaProvider`] of that database.
 pub struct InfluxQLToLogicalPlan<'a> {
     s: &'a dyn SchemaProvider,
     iox_ctx: &'a IOxSessionContext,
@@ -443,14 +456,18 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
     pub fn statement_to_plan(&self, statement: Statement) -> Result<LogicalPlan> {
         match statement {
-            Statement::CreateDatabase(_) => error::not_implemented("CREATE DATABASE"),
-            Statement::Delete(_) => error::not_implemented("DELETE"),
-            Statement::DropMeasurement(_) => error::not_implemented("DROP MEASUREMENT"),
+            // Statements which operate on the catalog are not planned, and
+            // must be executed by the caller.
+            //
+            // See: CatalogStatement
+            Statement::CreateDatabase(_) => catalog_statement_error("CREATE DATABASE"),
+            Statement::Delete(_) => catalog_statement_error("DELETE"),
+            Statement::DropMeasurement(_) => catalog_statement_error("DROP MEASUREMENT"),
             Statement::Explain(explain) => self.explain_statement_to_plan(*explain),
             Statement::Select(select) => {
                 self.select_query_to_plan(&self.rewrite_select_statement(*select)?)
             }
-            Statement::ShowDatabases(_) => error::not_implemented("SHOW DATABASES"),
+            Statement::ShowDatabases(_) => catalog_statement_error("SHOW DATABASES"),
             Statement::ShowMeasurements(show_measurements) => {
                 self.show_measurements_to_plan(*show_measurements)
             }
@@ -646,10 +663,20 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             &projection_tag_set,
         )?;
 
-        self.limit(
+        let plan = self.limit(
             plan,
             select.offset,
             select.limit,
+            vec![time_sort_expr.clone()],
+            sort_by_measurement,
+            &group_by_tag_set,
+            &projection_tag_set,
+        )?;
+
+        self.series_limit(
+            plan,
+            select.series_offset,
+            select.series_limit,
             vec![time_sort_expr],
             sort_by_measurement,
             &group_by_tag_set,
@@ -694,10 +721,20 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             &projection_tag_set,
         )?;
 
-        Ok(Some(self.limit(
+        let plan = self.limit(
             plan,
             select.offset,
             select.limit,
+            vec![time_sort_expr.clone()],
+            false,
+            &group_by_tag_set,
+            &projection_tag_set,
+        )?;
+
+        Ok(Some(self.series_limit(
+            plan,
+            select.series_offset,
+            select.series_limit,
             vec![time_sort_expr],
             false,
             &group_by_tag_set,
@@ -972,8 +1009,6 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
     /// type. These a queries that include a single FIRST, LAST, MAX, MIN,
     /// PERCENTILE, or SAMPLE function call, possibly requesting additional
     /// tags or fields.
-    ///
-    /// N.B SAMPLE is not yet implemented.
     fn project_select_selector(
         &self,
         ctx: &Context<'_>,
@@ -1037,8 +1072,32 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
                 (idx, field_key, plan)
             }
-            (_, Selector::Sample { field_key: _, n: _ }) => {
-                return error::not_implemented("sample selector function")
+            (idx, Selector::Sample { field_key, n }) => {
+                // Number the rows of each partition in a random order, and
+                // select the first n rows.
+                let window_row = Expr::WindowFunction(WindowFunction::new(
+                    window_function::WindowFunction::BuiltInWindowFunction(
+                        window_function::BuiltInWindowFunction::RowNumber,
+                    ),
+                    vec![],
+                    window_partition_by(ctx, input.schema(), group_by_tag_set),
+                    vec![random().sort(true, false)],
+                    WindowFrame {
+                        units: WindowFrameUnits::Rows,
+                        start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                        end_bound: WindowFrameBound::CurrentRow,
+                    },
+                ));
+                let row_column_name = window_row.display_name()?;
+
+                let filter_expr = binary_expr(col(row_column_name.clone()), Operator::LtEq, lit(n));
+                let plan = LogicalPlanBuilder::from(input)
+                    .filter(field_key.as_expr().is_not_null())?
+                    .window(vec![window_row.alias(row_column_name)])?
+                    .filter(filter_expr)?
+                    .build()?;
+
+                (idx, field_key, plan)
             }
 
             (_, s) => {
@@ -1235,6 +1294,60 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             }
         }
 
+        // With a GROUP BY time(..) clause, INTEGRAL interpolates the area at the
+        // boundaries of each interval, which requires the points of the neighbouring
+        // intervals. The area attributed to each interval is calculated by a window
+        // function over each series, and summed by the aggregate.
+        let mut integral_window_exprs = vec![];
+        if let Some(i) = ctx.interval {
+            let partition_by =
+                fields_to_exprs_no_nulls(input.schema(), group_by_tag_set).collect::<Vec<_>>();
+
+            for aggr_expr in aggr_exprs.iter_mut() {
+                let Expr::AggregateUDF(udf) = &*aggr_expr else {
+                    continue;
+                };
+                if udf.fun.name != INTEGRAL.name {
+                    continue;
+                }
+
+                let mut args = udf.args.clone();
+                args.push(lit(ScalarValue::new_interval_mdn(0, 0, i.duration)));
+                args.push(lit(ScalarValue::TimestampNanosecond(
+                    Some(i.offset.unwrap_or_default()),
+                    None,
+                )));
+                let window_expr = Expr::WindowFunction(WindowFunction::new(
+                    INTEGRAL_WINDOW.clone(),
+                    args,
+                    partition_by.clone(),
+                    vec!["time".as_expr().sort(true, false)],
+                    WindowFrame {
+                        units: WindowFrameUnits::Rows,
+                        start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                        end_bound: WindowFrameBound::Following(ScalarValue::Null),
+                    },
+                ));
+                let column_name = window_expr.display_name()?;
+                integral_window_exprs.push(window_expr.alias(column_name.clone()));
+
+                let sum_expr = sum(col(column_name));
+                select_exprs = select_exprs
+                    .into_iter()
+                    .map(|expr| {
+                        expr.transform_up(&|expr| {
+                            Ok(if expr == *aggr_expr {
+                                Transformed::Yes(sum_expr.clone())
+                            } else {
+                                Transformed::No(expr)
+                            })
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                *aggr_expr = sum_expr;
+            }
+        }
+
         // This block identifies the time column index and updates the time expression
         // based on the semantics of the projection.
         let time_column = {
@@ -1311,12 +1424,24 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             return Ok((LogicalPlanBuilder::empty(true).build()?, select_exprs));
         }
 
-        let plan = LogicalPlanBuilder::from(input)
-            .aggregate(aggr_group_by_exprs.clone(), aggr_exprs.clone())?
-            .build()?;
+        let plan = if integral_window_exprs.is_empty() {
+            LogicalPlanBuilder::from(input)
+        } else {
+            LogicalPlanBuilder::from(input).window(integral_window_exprs)?
+        }
+        .aggregate(aggr_group_by_exprs.clone(), aggr_exprs.clone())?
+        .build()?;
 
         let fill_option = ctx.fill();
 
+        // The HOLT_WINTERS functions forecast values for the N intervals following
+        // the last interval of the query, so the gap-filled intervals must be
+        // extended to produce rows for those values.
+        let forecast_duration = match ctx.interval {
+            Some(interval) => max_holt_winters_forecast(&select_exprs) * interval.duration,
+            None => 0,
+        };
+
         // Wrap the plan in a GapFill operator if the statement specifies a `GROUP BY TIME` clause and
         // the FILL option is one of
         //
@@ -1325,17 +1450,17 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         // * `literal` value
         // * `linear`
         //
+        // or the projection includes a HOLT_WINTERS function.
         let plan = if ctx.group_by.and_then(|gb| gb.time_dimension()).is_some()
-            && fill_option != FillClause::None
+            && (fill_option != FillClause::None || forecast_duration > 0)
         {
             let fill_strategy = match fill_option {
-                FillClause::Null | FillClause::Value(_) => FillStrategy::Null,
+                FillClause::Null | FillClause::Value(_) | FillClause::None => FillStrategy::Null,
                 FillClause::Previous => FillStrategy::PrevNullAsMissing,
                 FillClause::Linear => FillStrategy::LinearInterpolate,
-                FillClause::None => unreachable!(),
             };
 
-            build_gap_fill_node(plan, time_column, fill_strategy)?
+            build_gap_fill_node(plan, time_column, fill_strategy, forecast_duration)?
         } else {
             plan
         };
@@ -1496,6 +1621,14 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             }
         }
 
+        fn elapsed_unit(args: &[Expr]) -> Result<ScalarValue> {
+            match args.get(1) {
+                Some(Expr::Literal(v)) => Ok(v.clone()),
+                Some(e) => error::internal(format!("udf_to_expr: unexpected expression: {e}")),
+                None => Ok(ScalarValue::new_interval_mdn(0, 0, 1)), // 1ns
+            }
+        }
+
         match udf::WindowFunction::try_from_scalar_udf(Arc::clone(&fun)) {
             Some(udf::WindowFunction::MovingAverage) => Ok(Expr::WindowFunction(WindowFunction {
                 fun: MOVING_AVERAGE.clone(),
@@ -1581,6 +1714,166 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                 },
             })
             .alias(alias)),
+            Some(udf::WindowFunction::Elapsed) => Ok(Expr::WindowFunction(WindowFunction {
+                fun: ELAPSED.clone(),
+                args: vec![args[0].clone(), lit(elapsed_unit(&args)?), "time".as_expr()],
+                partition_by,
+                order_by,
+                window_frame: WindowFrame {
+                    units: WindowFrameUnits::Rows,
+                    start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                    end_bound: WindowFrameBound::Following(ScalarValue::Null),
+                },
+            })
+            .alias(alias)),
+            Some(udf::WindowFunction::ExponentialMovingAverage) => {
+                Ok(Expr::WindowFunction(WindowFunction {
+                    fun: EXPONENTIAL_MOVING_AVERAGE.clone(),
+                    args: args,
+                    partition_by,
+                    order_by,
+                    window_frame: WindowFrame {
+                        units: WindowFrameUnits::Rows,
+                        start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                        end_bound: WindowFrameBound::Following(ScalarValue::Null),
+                    },
+                })
+                .alias(alias))
+            }
+            Some(udf::WindowFunction::DoubleExponentialMovingAverage) => {
+                Ok(Expr::WindowFunction(WindowFunction {
+                    fun: DOUBLE_EXPONENTIAL_MOVING_AVERAGE.clone(),
+                    args: args,
+                    partition_by,
+                    order_by,
+                    window_frame: WindowFrame {
+                        units: WindowFrameUnits::Rows,
+                        start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                        end_bound: WindowFrameBound::Following(ScalarValue::Null),
+                    },
+                })
+                .alias(alias))
+            }
+            Some(udf::WindowFunction::TripleExponentialMovingAverage) => {
+                Ok(Expr::WindowFunction(WindowFunction {
+                    fun: TRIPLE_EXPONENTIAL_MOVING_AVERAGE.clone(),
+                    args: args,
+                    partition_by,
+                    order_by,
+                    window_frame: WindowFrame {
+                        units: WindowFrameUnits::Rows,
+                        start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                        end_bound: WindowFrameBound::Following(ScalarValue::Null),
+                    },
+                })
+                .alias(alias))
+            }
+            Some(udf::WindowFunction::KaufmansEfficiencyRatio) => {
+                Ok(Expr::WindowFunction(WindowFunction {
+                    fun: KAUFMANS_EFFICIENCY_RATIO.clone(),
+                    args: args,
+                    partition_by,
+                    order_by,
+                    window_frame: WindowFrame {
+                        units: WindowFrameUnits::Rows,
+                        start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                        end_bound: WindowFrameBound::Following(ScalarValue::Null),
+                    },
+                })
+                .alias(alias))
+            }
+            Some(udf::WindowFunction::KaufmansAdaptiveMovingAverage) => {
+                Ok(Expr::WindowFunction(WindowFunction {
+                    fun: KAUFMANS_ADAPTIVE_MOVING_AVERAGE.clone(),
+                    args: args,
+                    partition_by,
+                    order_by,
+                    window_frame: WindowFrame {
+                        units: WindowFrameUnits::Rows,
+                        start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                        end_bound: WindowFrameBound::Following(ScalarValue::Null),
+                    },
+                })
+                .alias(alias))
+            }
+            Some(udf::WindowFunction::RelativeStrengthIndex) => {
+                Ok(Expr::WindowFunction(WindowFunction {
+                    fun: RELATIVE_STRENGTH_INDEX.clone(),
+                    args: args,
+                    partition_by,
+                    order_by,
+                    window_frame: WindowFrame {
+                        units: WindowFrameUnits::Rows,
+                        start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                        end_bound: WindowFrameBound::Following(ScalarValue::Null),
+                    },
+                })
+                .alias(alias))
+            }
+            Some(udf::WindowFunction::TripleExponentialDerivative) => {
+                Ok(Expr::WindowFunction(WindowFunction {
+                    fun: TRIPLE_EXPONENTIAL_DERIVATIVE.clone(),
+                    args: args,
+                    partition_by,
+                    order_by,
+                    window_frame: WindowFrame {
+                        units: WindowFrameUnits::Rows,
+                        start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                        end_bound: WindowFrameBound::Following(ScalarValue::Null),
+                    },
+                })
+                .alias(alias))
+            }
+            Some(udf::WindowFunction::ChandeMomentumOscillator) => {
+                Ok(Expr::WindowFunction(WindowFunction {
+                    fun: CHANDE_MOMENTUM_OSCILLATOR.clone(),
+                    args: args,
+                    partition_by,
+                    order_by,
+                    window_frame: WindowFrame {
+                        units: WindowFrameUnits::Rows,
+                        start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                        end_bound: WindowFrameBound::Following(ScalarValue::Null),
+                    },
+                })
+                .alias(alias))
+            }
+            Some(
+                wf @ (udf::WindowFunction::HoltWinters | udf::WindowFunction::HoltWintersWithFit),
+            ) => {
+                let Some(interval) = ctx.interval else {
+                    return error::internal(format!("{} requires a GROUP BY interval", fun.name));
+                };
+                // Only the rows up to the end of the time range of the query
+                // are input data, any later rows are for the forecast values.
+                let upper = match ctx.time_range.upper {
+                    Some(upper) => lit(ScalarValue::TimestampNanosecond(Some(upper), None)),
+                    None => now(),
+                };
+                Ok(Expr::WindowFunction(WindowFunction {
+                    fun: if matches!(wf, udf::WindowFunction::HoltWinters) {
+                        HOLT_WINTERS.clone()
+                    } else {
+                        HOLT_WINTERS_WITH_FIT.clone()
+                    },
+                    args: vec![
+                        args[0].clone(),
+                        "time".as_expr(),
+                        args[1].clone(),
+                        args[2].clone(),
+                        lit(ScalarValue::new_interval_mdn(0, 0, interval.duration)),
+                        upper,
+                    ],
+                    partition_by,
+                    order_by,
+                    window_frame: WindowFrame {
+                        units: WindowFrameUnits::Rows,
+                        start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                        end_bound: WindowFrameBound::Following(ScalarValue::Null),
+                    },
+                })
+                .alias(alias))
+            }
             None => error::internal(format!(
                 "unexpected user-defined window function: {}",
                 fun.name
@@ -1729,6 +2022,146 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         }
     }
 
+    /// Generate a plan that restricts the series of the input, first omitting a specified
+    /// number of series, followed by restricting the quantity of series returned.
+    ///
+    /// Following InfluxQL OG, a series is identified by the tag set from the `GROUP BY`
+    /// clause, and the series are limited independently for each measurement, in the order of
+    /// their tag values. A limit of `0` does not restrict the number of series.
+    ///
+    /// ## Arguments
+    ///
+    /// - `input`: The plan to apply the series limit to.
+    /// - `soffset`: The number of series to skip.
+    /// - `slimit`: The maximum number of series to return for each measurement.
+    /// - `sort_exprs`: An `Expr::Sort` referring to the `time` column of the input.
+    /// - `sort_by_measurement`: `true` if the `input` must be sorted by the measurement column.
+    /// - `group_by_tag_set`: Tag columns from the `input` plan that identify a series.
+    /// - `projection_tag_set`: Additional tag columns that should be used to sort the `output`
+    ///   plan.
+    #[allow(clippy::too_many_arguments)]
+    fn series_limit(
+        &self,
+        input: LogicalPlan,
+        soffset: Option<SOffsetClause>,
+        slimit: Option<SLimitClause>,
+        sort_exprs: Vec<Expr>,
+        sort_by_measurement: bool,
+        group_by_tag_set: &[&str],
+        projection_tag_set: &[&str],
+    ) -> Result<LogicalPlan> {
+        let slimit = slimit
+            .map(|v| <u64 as TryInto<i64>>::try_into(*v))
+            .transpose()
+            .map_err(|_| error::map::query("slimit out of range"))?
+            .filter(|v| *v > 0);
+        let soffset = soffset
+            .map(|v| <u64 as TryInto<i64>>::try_into(*v))
+            .transpose()
+            .map_err(|_| error::map::query("soffset out of range"))?
+            .filter(|v| *v > 0);
+
+        let series_exprs =
+            fields_to_exprs_no_nulls(input.schema(), group_by_tag_set).collect::<Vec<_>>();
+
+        match (slimit, soffset) {
+            (None, None) => return Ok(input),
+            // There is at most a single series per measurement, which is either
+            // skipped or returned.
+            (_, Some(_)) if series_exprs.is_empty() => {
+                return LogicalPlanBuilder::from(input).limit(0, Some(0))?.build()
+            }
+            (_, None) if series_exprs.is_empty() => return Ok(input),
+            _ => {}
+        }
+
+        // The name of the DENSE_RANK window expression
+        const IOX_SERIES_ALIAS: &str = "iox::series";
+
+        // Construct a DENSE_RANK window expression, numbering the series of each
+        // measurement:
+        //
+        // DENSE_RANK() OVER (
+        //   PARTITION BY [iox::measurement]
+        //   ORDER BY [group_by_tag_set] ASC NULLS FIRST
+        //   ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW
+        // ) AS iox::series
+        let partition_by = if sort_by_measurement {
+            vec![INFLUXQL_MEASUREMENT_COLUMN_NAME.as_expr()]
+        } else {
+            vec![]
+        };
+
+        // A missing tag value is an empty string in InfluxQL, which sorts
+        // before all other values.
+        let order_by = series_exprs
+            .into_iter()
+            .map(|e| Expr::sort(e, true, true))
+            .collect::<Vec<_>>();
+
+        let window_func_exprs = vec![Expr::WindowFunction(WindowFunction {
+            fun: window_function::WindowFunction::BuiltInWindowFunction(
+                BuiltInWindowFunction::DenseRank,
+            ),
+            args: vec![],
+            partition_by,
+            order_by,
+            window_frame: WindowFrame {
+                units: WindowFrameUnits::Rows,
+                start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                end_bound: WindowFrameBound::CurrentRow,
+            },
+        })
+        .alias(IOX_SERIES_ALIAS)];
+
+        // Prepare new projection.
+        let proj_exprs = input
+            .schema()
+            .fields()
+            .iter()
+            .map(|expr| Expr::Column(expr.unqualified_column()))
+            .collect::<Vec<_>>();
+
+        let plan = LogicalPlanBuilder::from(input)
+            .window(window_func_exprs)?
+            .build()?;
+
+        // a reference to the DENSE_RANK column.
+        let series_alias = IOX_SERIES_ALIAS.as_expr();
+
+        let series_filter_expr = match (slimit, soffset) {
+            // WHERE "iox::series" BETWEEN SOFFSET + 1 AND SOFFSET + SLIMIT
+            (Some(slimit), Some(soffset)) => Expr::Between(Between {
+                expr: Box::new(series_alias),
+                negated: false,
+                low: Box::new(lit(soffset.saturating_add(1))),
+                high: Box::new(lit(soffset.saturating_add(slimit))),
+            }),
+
+            // WHERE "iox::series" <= SLIMIT
+            (Some(slimit), None) => series_alias.lt_eq(lit(slimit)),
+
+            // WHERE "iox::series" > SOFFSET
+            (None, Some(soffset)) => series_alias.gt(lit(soffset)),
+            (None, None) => unreachable!("slimit and soffset cannot not be None"),
+        };
+
+        let plan = LogicalPlanBuilder::from(plan)
+            // Filter by the SLIMIT and SOFFSET clause
+            .filter(series_filter_expr)?
+            // Project the output without the IOX_SERIES_ALIAS column
+            .project(proj_exprs)?
+            .build()?;
+
+        plan_with_sort(
+            plan,
+            sort_exprs,
+            sort_by_measurement,
+            group_by_tag_set,
+            projection_tag_set,
+        )
+    }
+
     /// Map the InfluxQL `SELECT` projection list into a list of DataFusion expressions.
     fn field_list_to_exprs(
         &self,
@@ -1863,7 +2296,8 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                     },
                 })
             }
-            IQLExpr::BindParameter(_) => error::not_implemented("parameter"),
+            // BindParameter should be substituted prior to planning.
+            IQLExpr::BindParameter(_) => error::internal("unexpected bind parameter"),
             IQLExpr::Literal(val) => match val {
                 Literal::Integer(v) => Ok(lit(*v)),
                 Literal::Unsigned(v) => Ok(lit(*v)),
@@ -1874,9 +2308,9 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                     .timestamp_nanos_opt()
                     .ok_or_else(|| error::map::query("timestamp out of range"))
                     .map(|ts| lit(ScalarValue::TimestampNanosecond(Some(ts), None))),
-                Literal::Duration(v) => {
-                    Ok(lit(ScalarValue::IntervalMonthDayNano(Some((**v).into()))))
-                }
+                Literal::Duration(v) => Ok(lit(ScalarValue::IntervalMonthDayNano(Some(
+                    IntervalMonthDayNanoType::make_value(0, 0, **v),
+                )))),
                 Literal::Regex(re) => match scope {
                     // a regular expression in a projection list is unexpected,
                     // as it should have been expanded by the rewriter.
@@ -1966,6 +2400,17 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             }
         }
 
+        /// Return the value of the integer literal argument `expr`.
+        fn integer_arg(name: &str, expr: &IQLExpr) -> Result<i64> {
+            match expr {
+                IQLExpr::Literal(Literal::Integer(v)) => Ok(*v),
+                IQLExpr::Literal(Literal::Unsigned(v)) => i64::try_from(*v).map_err(|_| {
+                    error::map::query(format!("integer argument out of range in {name}()"))
+                }),
+                _ => error::query(format!("expected integer argument in {name}()")),
+            }
+        }
+
         let Call { name, args } = call;
 
         match name.as_str() {
@@ -2023,6 +2468,48 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                     None,
                 )))
             }
+            "mode" | "spread" => {
+                let expr = self.expr_to_df_expr(scope, &args[0], schema)?;
+                if let Expr::Literal(ScalarValue::Null) = expr {
+                    return Ok(expr);
+                }
+
+                check_arg_count(name, args, 1)?;
+                // MODE breaks ties using the timestamps of the values.
+                let (fun, eargs) = if name == "mode" {
+                    (MODE.clone(), vec![expr, "time".as_expr()])
+                } else {
+                    (SPREAD.clone(), vec![expr])
+                };
+                Ok(Expr::AggregateUDF(expr::AggregateUDF::new(
+                    fun, eargs, None, None,
+                )))
+            }
+            "integral" => {
+                check_arg_count_range(name, args, 1, 2)?;
+
+                let expr = self.expr_to_df_expr(scope, &args[0], schema)?;
+                if let Expr::Literal(ScalarValue::Null) = expr {
+                    return Ok(expr);
+                }
+
+                // The unit defaults to 1s, when not specified.
+                let unit = match args.get(1) {
+                    Some(e) => self.expr_to_df_expr(scope, e, schema)?,
+                    None => lit(ScalarValue::new_interval_mdn(0, 0, 1_000_000_000)),
+                };
+                Ok(Expr::AggregateUDF(expr::AggregateUDF::new(
+                    INTEGRAL.clone(),
+                    vec![expr, "time".as_expr(), unit],
+                    None,
+                    None,
+                )))
+            }
+            // The SAMPLE function is handled as a `ProjectionType::Selector`
+            // query, with or without a `GROUP BY time(..)` clause. It is not
+            // supported in combination with other aggregates or selectors,
+            // as it produces multiple rows for each group.
+            "sample" => error::not_implemented("sample() combined with other functions"),
             name @ ("first" | "last" | "min" | "max") => {
                 let expr = self.expr_to_df_expr(scope, &args[0], schema)?;
                 if let Expr::Literal(ScalarValue::Null) = expr {
@@ -2129,6 +2616,97 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
                 Ok(cumulative_sum(vec![arg0]))
             }
+            "elapsed" => {
+                check_arg_count_range(name, args, 1, 2)?;
+
+                // arg0 should be a column or function
+                let arg0 = self.expr_to_df_expr(scope, &args[0], schema)?;
+                if let Expr::Literal(ScalarValue::Null) = arg0 {
+                    return Ok(arg0);
+                }
+                let mut eargs = vec![arg0];
+                if args.len() > 1 {
+                    let arg1 = self.expr_to_df_expr(scope, &args[1], schema)?;
+                    eargs.push(arg1);
+                }
+
+                Ok(elapsed(eargs))
+            }
+            "exponential_moving_average"
+            | "double_exponential_moving_average"
+            | "triple_exponential_moving_average"
+            | "relative_strength_index"
+            | "triple_exponential_derivative"
+            | "chande_momentum_oscillator" => {
+                check_arg_count_range(name, args, 2, 4)?;
+
+                // arg0 should be a column or function
+                let arg0 = self.expr_to_df_expr(scope, &args[0], schema)?;
+                if let Expr::Literal(ScalarValue::Null) = arg0 {
+                    return Ok(arg0);
+                }
+
+                // The optional hold period and warmup type arguments are
+                // replaced by their defaults, when not specified.
+                let period = integer_arg(name, &args[1])?;
+                let hold = args.get(2).map_or(Ok(-1), |e| integer_arg(name, e))?;
+                let warmup = match args.get(3) {
+                    Some(IQLExpr::Literal(Literal::String(s))) => s.as_str(),
+                    Some(_) => {
+                        return error::query(format!("expected string argument in {name}()"))
+                    }
+                    None if name == "chande_momentum_oscillator" => "none",
+                    None => "exponential",
+                };
+                let eargs = vec![arg0, lit(period), lit(hold), lit(warmup)];
+
+                Ok(match name {
+                    "exponential_moving_average" => exponential_moving_average(eargs),
+                    "double_exponential_moving_average" => double_exponential_moving_average(eargs),
+                    "triple_exponential_moving_average" => triple_exponential_moving_average(eargs),
+                    "relative_strength_index" => relative_strength_index(eargs),
+                    "triple_exponential_derivative" => triple_exponential_derivative(eargs),
+                    _ => chande_momentum_oscillator(eargs),
+                })
+            }
+            "kaufmans_efficiency_ratio" | "kaufmans_adaptive_moving_average" => {
+                check_arg_count_range(name, args, 2, 3)?;
+
+                // arg0 should be a column or function
+                let arg0 = self.expr_to_df_expr(scope, &args[0], schema)?;
+                if let Expr::Literal(ScalarValue::Null) = arg0 {
+                    return Ok(arg0);
+                }
+
+                let period = integer_arg(name, &args[1])?;
+                let hold = args.get(2).map_or(Ok(-1), |e| integer_arg(name, e))?;
+                let eargs = vec![arg0, lit(period), lit(hold)];
+
+                Ok(if name == "kaufmans_efficiency_ratio" {
+                    kaufmans_efficiency_ratio(eargs)
+                } else {
+                    kaufmans_adaptive_moving_average(eargs)
+                })
+            }
+            "holt_winters" | "holt_winters_with_fit" => {
+                check_arg_count(name, args, 3)?;
+
+                // arg0 should be a function
+                let arg0 = self.expr_to_df_expr(scope, &args[0], schema)?;
+                if let Expr::Literal(ScalarValue::Null) = arg0 {
+                    return Ok(arg0);
+                }
+
+                let n = integer_arg(name, &args[1])?;
+                let s = integer_arg(name, &args[2])?;
+                let eargs = vec![arg0, lit(n), lit(s)];
+
+                Ok(if name == "holt_winters" {
+                    holt_winters(eargs)
+                } else {
+                    holt_winters_with_fit(eargs)
+                })
+            }
             // The TOP/BOTTOM function is handled as a `ProjectionType::TopBottomSelector`
             // query, so the planner only needs to project the single column
             // argument.
@@ -2309,12 +2887,6 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                 let all_tables = self.s.table_names().into_iter().collect::<HashSet<_>>();
                 let mut out = HashSet::new();
                 for qualified_name in &*from {
-                    if qualified_name.database.is_some() {
-                        return error::not_implemented("database name in from clause");
-                    }
-                    if qualified_name.retention_policy.is_some() {
-                        return error::not_implemented("retention policy in from clause");
-                    }
                     match &qualified_name.name {
                         MeasurementName::Name(name) => {
                             let name = name.as_str();
@@ -2345,18 +2917,6 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         with_measurement: Option<WithMeasurementClause>,
     ) -> Result<Vec<String>> {
         match with_measurement {
-            Some(
-                WithMeasurementClause::Equals(qualified_name)
-                | WithMeasurementClause::Regex(qualified_name),
-            ) if qualified_name.database.is_some() => {
-                error::not_implemented("database name in from clause")
-            }
-            Some(
-                WithMeasurementClause::Equals(qualified_name)
-                | WithMeasurementClause::Regex(qualified_name),
-            ) if qualified_name.retention_policy.is_some() => {
-                error::not_implemented("retention policy in from clause")
-            }
             Some(WithMeasurementClause::Equals(qualified_name)) => match qualified_name.name {
                 MeasurementName::Name(n) => {
                     let names = self.s.table_names();
@@ -2398,11 +2958,6 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
     }
 
     fn show_tag_keys_to_plan(&self, show_tag_keys: ShowTagKeysStatement) -> Result<LogicalPlan> {
-        if show_tag_keys.database.is_some() {
-            // How do we handle this? Do we need to perform cross-namespace queries here?
-            return error::not_implemented("SHOW TAG KEYS ON <database>");
-        }
-
         let tag_key_col = "tagKey";
         let output_schema = Arc::new(ArrowSchema::new(vec![
             ArrowField::new(
@@ -2571,11 +3126,6 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         &self,
         show_field_keys: ShowFieldKeysStatement,
     ) -> Result<LogicalPlan> {
-        if show_field_keys.database.is_some() {
-            // How do we handle this? Do we need to perform cross-namespace queries here?
-            return error::not_implemented("SHOW FIELD KEYS ON <database>");
-        }
-
         let field_key_col = "fieldKey";
         let output_schema = Arc::new(ArrowSchema::new(vec![
             ArrowField::new(INFLUXQL_MEASUREMENT_COLUMN_NAME, DataType::Utf8, false),
@@ -2651,11 +3201,6 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         &self,
         show_tag_values: ShowTagValuesStatement,
     ) -> Result<LogicalPlan> {
-        if show_tag_values.database.is_some() {
-            // How do we handle this? Do we need to perform cross-namespace queries here?
-            return error::not_implemented("SHOW TAG VALUES ON <database>");
-        }
-
         let key_col = "key";
         let value_col = "value";
         let output_schema = Arc::new(ArrowSchema::new(vec![
@@ -2757,9 +3302,12 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         &self,
         show_measurements: ShowMeasurementsStatement,
     ) -> Result<LogicalPlan> {
-        if show_measurements.on.is_some() {
-            // How do we handle this? Do we need to perform cross-namespace queries here?
-            return error::not_implemented("SHOW MEASUREMENTS ON <database>");
+        if let Some(
+            on @ (ExtendedOnClause::AllDatabases
+            | ExtendedOnClause::AllDatabasesAndRetentionPolicies),
+        ) = &show_measurements.on
+        {
+            return error::not_implemented(format!("SHOW MEASUREMENTS {on}"));
         }
 
         let tables = self.expand_with_measurement_clause(show_measurements.with_measurement)?;
@@ -2879,11 +3427,6 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         &self,
         show_retention_policies: ShowRetentionPoliciesStatement,
     ) -> Result<LogicalPlan> {
-        if show_retention_policies.database.is_some() {
-            // This syntax is not yet handled.
-            return error::not_implemented("SHOW RETENTION POLICIES ON <database>");
-        }
-
         let output_schema = Arc::new(ArrowSchema::new(vec![
             ArrowField::new(
                 INFLUXQL_MEASUREMENT_COLUMN_NAME,
@@ -2964,10 +3507,13 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 /// * `input` - An aggregate plan which requires gap-filling.
 /// * `time_column` - The `date_bin` expression.
 /// * `fill_strategy` - The strategy used to fill gaps in the data.
+/// * `extend_nanos` - The duration, in nanoseconds, the end of the time range
+///   is extended by, to produce rows after the time range of the query.
 fn build_gap_fill_node(
     input: LogicalPlan,
     time_column: &Expr,
     fill_strategy: FillStrategy,
+    extend_nanos: i64,
 ) -> Result<LogicalPlan> {
     let (expr, alias) = match time_column {
         Expr::Alias(Alias { expr, name: alias }) => (expr.as_ref(), alias),
@@ -3027,6 +3573,20 @@ fn build_gap_fill_node(
                     .ok_or_else(|| error::map::internal("expected to find a Filter or TableScan"))
             }?;
 
+            let time_range = if extend_nanos > 0 {
+                let extend = lit(ScalarValue::new_interval_mdn(0, 0, extend_nanos));
+                Range {
+                    start: time_range.start,
+                    end: match time_range.end {
+                        Bound::Included(end) => Bound::Included(end + extend),
+                        Bound::Excluded(end) => Bound::Excluded(end + extend),
+                        Bound::Unbounded => Bound::Unbounded,
+                    },
+                }
+            } else {
+                time_range
+            };
+
             let origin = (nargs == 3).then_some(date_bin_args[2].clone());
 
             (date_bin_args[0].clone(), time_range, origin)
@@ -3080,6 +3640,37 @@ fn build_gap_fill_node(
     }))
 }
 
+/// Returns the largest number of values forecast by the HOLT_WINTERS functions
+/// of `exprs`, or `0` if there are none.
+fn max_holt_winters_forecast(exprs: &[Expr]) -> i64 {
+    find_window_udfs(exprs)
+        .iter()
+        .filter_map(|e| match e {
+            Expr::ScalarUDF(expr::ScalarUDF { fun, args }) => {
+                match udf::WindowFunction::try_from_scalar_udf(Arc::clone(fun)) {
+                    Some(
+                        udf::WindowFunction::HoltWinters | udf::WindowFunction::HoltWintersWithFit,
+                    ) => match args.get(1) {
+                        Some(Expr::Literal(ScalarValue::Int64(Some(n)))) => Some(*n),
+                        _ => None,
+                    },
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Returns an error for a `statement` which operates on the catalog, and
+/// therefore cannot be planned as a query.
+fn catalog_statement_error<T>(statement: &str) -> Result<T> {
+    error::query(format!(
+        "{statement} operates on the catalog and cannot be planned as a query"
+    ))
+}
+
 /// Adds [`InfluxQlMetadata`] to the `plan`.
 fn plan_with_metadata(plan: LogicalPlan, metadata: &InfluxQlMetadata) -> Result<LogicalPlan> {
     fn make_schema(schema: DFSchemaRef, metadata: &InfluxQlMetadata) -> Result<DFSchemaRef> {
@@ -3482,15 +4073,14 @@ mod test {
         );
     }
 
-    /// Verify the list of unsupported statements.
-    ///
-    /// It is expected certain statements will be unsupported, indefinitely.
+    /// Verify the list of statements which operate on the catalog, and are
+    /// executed by the caller rather than planned.
     #[test]
-    fn test_unsupported_statements() {
-        assert_snapshot!(plan("CREATE DATABASE foo"), @"This feature is not implemented: CREATE DATABASE");
-        assert_snapshot!(plan("DELETE FROM foo"), @"This feature is not implemented: DELETE");
-        assert_snapshot!(plan("DROP MEASUREMENT foo"), @"This feature is not implemented: DROP MEASUREMENT");
-        assert_snapshot!(plan("SHOW DATABASES"), @"This feature is not implemented: SHOW DATABASES");
+    fn test_catalog_statements() {
+        assert_snapshot!(plan("CREATE DATABASE foo"), @"Error during planning: CREATE DATABASE operates on the catalog and cannot be planned as a query");
+        assert_snapshot!(plan("DELETE FROM foo"), @"Error during planning: DELETE operates on the catalog and cannot be planned as a query");
+        assert_snapshot!(plan("DROP MEASUREMENT foo"), @"Error during planning: DROP MEASUREMENT operates on the catalog and cannot be planned as a query");
+        assert_snapshot!(plan("SHOW DATABASES"), @"Error during planning: SHOW DATABASES operates on the catalog and cannot be planned as a query");
     }
 
     mod metadata_queries {
@@ -3856,7 +4446,7 @@ mod test {
             TableScan: retention policies [iox::measurement:Dictionary(Int32, Utf8), name:Utf8, duration:Utf8, shardGroupDuration:Utf8, replicaN:Int64, default:Boolean]
             "###);
             assert_snapshot!(plan("SHOW RETENTION POLICIES ON my_db"), @r###"
-            This feature is not implemented: SHOW RETENTION POLICIES ON <database>
+            TableScan: retention policies [iox::measurement:Dictionary(Int32, Utf8), name:Utf8, duration:Utf8, shardGroupDuration:Utf8, replicaN:Int64, default:Boolean]
             "###);
         }
     }
@@ -4465,6 +5055,40 @@ mod test {
             "###);
         }
 
+        #[test]
+        fn test_mode_spread() {
+            assert_snapshot!(plan("SELECT mode(usage_idle), spread(usage_idle) FROM cpu"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), mode:Float64;N, spread:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, TimestampNanosecond(0, None) AS time, mode(cpu.usage_idle,cpu.time) AS mode, spread(cpu.usage_idle) AS spread [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), mode:Float64;N, spread:Float64;N]
+                Aggregate: groupBy=[[]], aggr=[[mode(cpu.usage_idle, cpu.time), spread(cpu.usage_idle)]] [mode(cpu.usage_idle,cpu.time):Float64;N, spread(cpu.usage_idle):Float64;N]
+                  TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            assert_snapshot!(plan("SELECT spread(usage_idle) FROM cpu GROUP BY cpu"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, spread:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, TimestampNanosecond(0, None) AS time, cpu.cpu AS cpu, spread(cpu.usage_idle) AS spread [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, spread:Float64;N]
+                Aggregate: groupBy=[[cpu.cpu]], aggr=[[spread(cpu.usage_idle)]] [cpu:Dictionary(Int32, Utf8);N, spread(cpu.usage_idle):Float64;N]
+                  TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+        }
+
+        #[test]
+        fn test_integral() {
+            assert_snapshot!(plan("SELECT integral(usage_idle) FROM cpu"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), integral:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, TimestampNanosecond(0, None) AS time, integral(cpu.usage_idle,cpu.time,IntervalMonthDayNano("1000000000")) AS integral [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), integral:Float64;N]
+                Aggregate: groupBy=[[]], aggr=[[integral(cpu.usage_idle, cpu.time, IntervalMonthDayNano("1000000000"))]] [integral(cpu.usage_idle,cpu.time,IntervalMonthDayNano("1000000000")):Float64;N]
+                  TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            assert_snapshot!(plan("SELECT integral(usage_idle, 1m) FROM cpu"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), integral:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, TimestampNanosecond(0, None) AS time, integral(cpu.usage_idle,cpu.time,IntervalMonthDayNano("60000000000")) AS integral [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), integral:Float64;N]
+                Aggregate: groupBy=[[]], aggr=[[integral(cpu.usage_idle, cpu.time, IntervalMonthDayNano("60000000000"))]] [integral(cpu.usage_idle,cpu.time,IntervalMonthDayNano("60000000000")):Float64;N]
+                  TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+        }
+
         #[test]
         fn test_top() {
             assert_snapshot!(plan("SELECT top(usage_idle,10) FROM cpu"), @r###"
@@ -5550,6 +6174,70 @@ mod test {
             "###);
         }
 
+        #[test]
+        fn test_select_group_by_slimit_soffset() {
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu GROUP BY cpu SLIMIT 1"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+              Projection: iox::measurement, time, cpu, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                Filter: iox::series <= Int64(1) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                  WindowAggr: windowExpr=[[DENSE_RANK() ORDER BY [cpu ASC NULLS FIRST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::series]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                    Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                      Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                        TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu GROUP BY cpu SOFFSET 1"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+              Projection: iox::measurement, time, cpu, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                Filter: iox::series > Int64(1) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                  WindowAggr: windowExpr=[[DENSE_RANK() ORDER BY [cpu ASC NULLS FIRST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::series]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                    Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                      Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                        TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu GROUP BY cpu SLIMIT 2 SOFFSET 1"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+              Projection: iox::measurement, time, cpu, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                Filter: iox::series BETWEEN Int64(2) AND Int64(3) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                  WindowAggr: windowExpr=[[DENSE_RANK() ORDER BY [cpu ASC NULLS FIRST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::series]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                    Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                      Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                        TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // LIMIT is applied to each of the remaining series
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu GROUP BY cpu LIMIT 1 SLIMIT 1"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+              Projection: iox::measurement, time, cpu, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                Filter: iox::series <= Int64(1) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                  WindowAggr: windowExpr=[[DENSE_RANK() ORDER BY [cpu ASC NULLS FIRST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::series]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                    Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                      Projection: iox::measurement, time, cpu, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                        Filter: iox::row <= Int64(1) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::row:UInt64;N]
+                          WindowAggr: windowExpr=[[ROW_NUMBER() PARTITION BY [cpu] ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::row]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::row:UInt64;N]
+                            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                                TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // Without a GROUP BY, there is a single series
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu SLIMIT 1"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu SOFFSET 1"), @r###"
+            Limit: skip=0, fetch=0 [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+              Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                  TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // returns an error if SLIMIT or SOFFSET values exceed i64::MAX
+            let max = (i64::MAX as u64) + 1;
+            assert_snapshot!(plan(format!("SELECT usage_idle FROM cpu GROUP BY cpu SLIMIT {max}")), @"Error during planning: slimit out of range");
+            assert_snapshot!(plan(format!("SELECT usage_idle FROM cpu GROUP BY cpu SOFFSET {max}")), @"Error during planning: soffset out of range");
+        }
+
         #[test]
         fn test_select_function_tag_column() {
             assert_snapshot!(plan("SELECT last(foo) as foo, first(usage_idle) from cpu group by foo"), @r###"
@@ -68,24 +68,6 @@ fn rewrite_select(s: &dyn SchemaProvider, stmt: &SelectStatement) -> Result<Sele
     rw.rewrite(s, stmt)
 }
 
-/// Asserts that the `SELECT` statement does not use any unimplemented features.
-///
-/// The list of unimplemented or unsupported features are listed below.
-///
-/// # `SLIMIT` and `SOFFSET`
-///
-/// * `SLIMIT` and `SOFFSET` don't work as expected per issue [#7571]
-/// * This issue [is noted](https://docs.influxdata.com/influxdb/v1.8/query_language/explore-data/#the-slimit-clause) in our official documentation
-///
-/// [#7571]: https://github.com/influxdata/influxdb/issues/7571
-fn check_features(stmt: &SelectStatement) -> Result<()> {
-    if stmt.series_limit.is_some() || stmt.series_offset.is_some() {
-        return error::not_implemented("SLIMIT or SOFFSET");
-    }
-
-    Ok(())
-}
-
 #[derive(Default)]
 struct RewriteSelect {
     /// The depth of the `SELECT` statement currently processed by the rewriter.
@@ -96,8 +78,6 @@ impl RewriteSelect {
     /// Transform a `SelectStatement` to a `Select`, which is an intermediate representation used by
     /// the InfluxQL planner. Transformations include expanding wildcards.
     fn rewrite(&self, s: &dyn SchemaProvider, stmt: &SelectStatement) -> Result<Select> {
-        check_features(stmt)?;
-
         let from = self.expand_from(s, stmt)?;
         let tag_set = from_tag_set(s, &from);
         let (fields, group_by) = self.expand_projection(s, stmt, &from, &tag_set)?;
@@ -160,6 +140,8 @@ impl RewriteSelect {
             order_by: stmt.order_by,
             limit: stmt.limit,
             offset: stmt.offset,
+            series_limit: stmt.series_limit,
+            series_offset: stmt.series_offset,
             timezone: stmt.timezone.map(|v| *v),
         })
     }
@@ -947,6 +929,9 @@ struct FieldChecker {
     /// `true` if the projection contains an invocation of the `TOP` or `BOTTOM` function.
     has_top_bottom: bool,
 
+    /// `true` if the projection contains an invocation of the `SAMPLE` function.
+    has_sample: bool,
+
     /// `true` when one or more projections do not contain an aggregate expression.
     has_non_aggregate_fields: bool,
 
@@ -1044,6 +1029,12 @@ impl FieldChecker {
                 }
             } else if self.has_distinct {
                 ProjectionType::RawDistinct
+            } else if self.has_sample && self.selector_count == 1 && self.aggregate_count == 0 {
+                // SAMPLE returns the raw points it selects from each
+                // interval, rather than a single point per interval.
+                ProjectionType::Selector {
+                    has_fields: self.has_non_aggregate_fields,
+                }
             } else {
                 ProjectionType::Aggregate
             }
@@ -1214,6 +1205,7 @@ impl FieldChecker {
 
     fn check_sample(&mut self, args: &[Expr]) -> Result<()> {
         self.inc_selector_count();
+        self.has_sample = true;
 
         check_exp_args!("sample", 2, args);
         let v = lit_integer!("sample", args, 1);
@@ -1494,7 +1486,7 @@ impl FieldChecker {
     }
 
     fn check_holt_winters(&mut self, name: &str, args: &[Expr]) -> Result<()> {
-        self.inc_aggregate_count();
+        self.inc_window_count();
         check_exp_args!(name, 3, args);
 
         let v = lit_integer!(name, args, 1);
@@ -1749,6 +1741,15 @@ mod test {
         .unwrap();
         assert_matches!(info.projection_type, ProjectionType::Aggregate);
 
+        let info = select_statement_info(&parse_select(
+            "SELECT sample(foo, 2) FROM cpu GROUP BY TIME(10s)",
+        ))
+        .unwrap();
+        assert_matches!(
+            info.projection_type,
+            ProjectionType::Selector { has_fields: false }
+        );
+
         let info =
             select_statement_info(&parse_select("SELECT last(foo), first(foo) FROM cpu")).unwrap();
         assert_matches!(info.projection_type, ProjectionType::Aggregate);
@@ -1768,6 +1769,12 @@ mod test {
         .unwrap();
         assert_matches!(info.projection_type, ProjectionType::WindowAggregateMixed);
 
+        let info = select_statement_info(&parse_select(
+            "SELECT holt_winters(mean(foo), 3, 0) FROM cpu GROUP BY TIME(10s)",
+        ))
+        .unwrap();
+        assert_matches!(info.projection_type, ProjectionType::WindowAggregate);
+
         let info = select_statement_info(&parse_select("SELECT top(foo, 3) FROM cpu")).unwrap();
         assert_matches!(info.projection_type, ProjectionType::TopBottomSelector);
     }
@@ -2362,6 +2369,14 @@ mod test {
                 "SELECT time::timestamp AS time, usage_idle::float AS usage_idle FROM cpu GROUP BY host::tag, non_existent"
             );
 
+            // retains the series limit and offset
+            let stmt = parse_select("SELECT usage_idle FROM cpu GROUP BY host SLIMIT 1 SOFFSET 2");
+            let stmt = rewrite_select_statement(&namespace, &stmt).unwrap();
+            assert_eq!(
+                stmt.to_string(),
+                "SELECT time::timestamp AS time, usage_idle::float AS usage_idle FROM cpu GROUP BY host::tag SLIMIT 1 SOFFSET 2"
+            );
+
             let stmt = parse_select("SELECT usage_idle FROM cpu GROUP BY *");
             let stmt = rewrite_select_statement(&namespace, &stmt).unwrap();
             assert_eq!(
@@ -2452,20 +2467,6 @@ mod test {
                 err.to_string(),
                 "Error during planning: unable to use tag as wildcard in count()"
             );
-
-            let stmt = parse_select("SELECT usage_idle FROM cpu SLIMIT 1");
-            let err = rewrite_select_statement(&namespace, &stmt).unwrap_err();
-            assert_eq!(
-                err.to_string(),
-                "This feature is not implemented: SLIMIT or SOFFSET"
-            );
-
-            let stmt = parse_select("SELECT usage_idle FROM cpu SOFFSET 1");
-            let err = rewrite_select_statement(&namespace, &stmt).unwrap_err();
-            assert_eq!(
-                err.to_string(),
-                "This feature is not implemented: SLIMIT or SOFFSET"
-            );
         }
 
         /// Verify subqueries
@@ -0,0 +1,155 @@
+use super::exponential_moving_average::{Ema, WarmupType};
+use crate::{error, NUMERICS};
+use arrow::array::{Array, ArrayRef, Float64Array, Int64Array, StringArray};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
+use datafusion::common::{downcast_value, DataFusionError, Result};
+use datafusion::logical_expr::{PartitionEvaluator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// The name of the chande_momentum_oscillator window function.
+pub(super) const NAME: &str = "chande_momentum_oscillator";
+
+/// Valid signatures for the chande_momentum_oscillator window function.
+///
+/// The arguments are the input values, the period, the hold period and the
+/// warmup type.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        NUMERICS
+            .iter()
+            .map(|dt| {
+                TypeSignature::Exact(vec![
+                    dt.clone(),
+                    DataType::Int64,
+                    DataType::Int64,
+                    DataType::Utf8,
+                ])
+            })
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// Calculate the return type given the function signature.
+pub(super) fn return_type(_: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(DataType::Float64))
+}
+
+/// Create a new partition_evaluator_factory.
+pub(super) fn partition_evaluator_factory() -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(CmoPartitionEvaluator {}))
+}
+
+/// Accumulates the upward and downward changes of the input values.
+#[derive(Debug)]
+enum Momentum {
+    /// Sum the changes of the last period.
+    Sum {
+        period: usize,
+        diffs: VecDeque<f64>,
+        up: f64,
+        down: f64,
+    },
+    /// Smooth the changes with an exponential moving average.
+    Smoothed { up: Ema, down: Ema },
+}
+
+impl Momentum {
+    /// Add the change `diff` and return the upward and downward momentum.
+    fn add(&mut self, diff: f64) -> (f64, f64) {
+        match self {
+            Self::Sum {
+                period,
+                diffs,
+                up,
+                down,
+            } => {
+                diffs.push_back(diff);
+                if diffs.len() > *period {
+                    let oldest = diffs.pop_front().expect("not empty");
+                    *up -= oldest.max(0.0);
+                    *down -= (-oldest).max(0.0);
+                }
+                *up += diff.max(0.0);
+                *down += (-diff).max(0.0);
+                (*up, *down)
+            }
+            Self::Smoothed { up, down } => (up.add(diff.max(0.0)), down.add((-diff).max(0.0))),
+        }
+    }
+}
+
+/// PartitionEvaluator which returns the Chande momentum oscillator of
+/// the input data.
+#[derive(Debug)]
+struct CmoPartitionEvaluator {}
+
+impl PartitionEvaluator for CmoPartitionEvaluator {
+    fn evaluate_all(&mut self, values: &[ArrayRef], _num_rows: usize) -> Result<Arc<dyn Array>> {
+        assert_eq!(values.len(), 4);
+
+        // INVARIANT:
+        // The planner and rewriter guarantee that the period, hold period and
+        // warmup arguments are always constants.
+        //
+        // See: FieldChecker::check_chande_momentum_oscillator
+        let period = downcast_value!(&values[1], Int64Array).value(0);
+        let hold = downcast_value!(&values[2], Int64Array).value(0);
+        let warmup = downcast_value!(&values[3], StringArray).value(0);
+
+        if period < 1 {
+            return error::internal(format!("invalid period ({period}) for {NAME}"));
+        }
+        let period = period as usize;
+        let hold = usize::try_from(hold).unwrap_or(period);
+
+        let mut momentum = match warmup {
+            "none" => Momentum::Sum {
+                period,
+                diffs: VecDeque::with_capacity(period + 1),
+                up: 0.0,
+                down: 0.0,
+            },
+            warmup => {
+                let ema = Ema::new(period, WarmupType::try_from_str(NAME, warmup)?);
+                Momentum::Smoothed {
+                    up: ema.clone(),
+                    down: ema,
+                }
+            }
+        };
+
+        let array = cast(&values[0], &DataType::Float64)?;
+        let array = downcast_value!(array, Float64Array);
+
+        let mut last: Option<f64> = None;
+        let mut count = 0;
+        Ok(Arc::new(
+            array
+                .iter()
+                .map(|v| {
+                    let v = v?;
+                    count += 1;
+                    let (up, down) = momentum.add(v - last.replace(v)?);
+                    let cmo = if up + down == 0.0 {
+                        0.0
+                    } else {
+                        100.0 * (up - down) / (up + down)
+                    };
+                    (count > hold).then_some(cmo)
+                })
+                .collect::<Float64Array>(),
+        ))
+    }
+
+    fn uses_window_frame(&self) -> bool {
+        false
+    }
+
+    fn include_rank(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{error, interval_nanos};
+use arrow::array::{
+    Array, ArrayRef, Int64Array, IntervalMonthDayNanoArray, TimestampNanosecondArray,
+};
+use arrow::datatypes::DataType;
+use datafusion::common::{downcast_value, DataFusionError, Result};
+use datafusion::logical_expr::{PartitionEvaluator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+/// The name of the elapsed window function.
+pub(super) const NAME: &str = "elapsed";
+
+/// Valid signatures for the elapsed window function. The first argument may
+/// be of any type, as only its nullness is considered.
+pub(super) static SIGNATURE: Lazy<Signature> =
+    Lazy::new(|| Signature::new(TypeSignature::Any(3), Volatility::Immutable));
+
+/// Calculate the return type given the function signature.
+pub(super) fn return_type(_: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(DataType::Int64))
+}
+
+/// Create a new partition_evaluator_factory.
+pub(super) fn partition_evaluator_factory() -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(ElapsedPartitionEvaluator {}))
+}
+
+/// PartitionEvaluator which returns the time elapsed between subsequent
+/// non-null input values, in the provided units.
+#[derive(Debug)]
+struct ElapsedPartitionEvaluator {}
+
+impl PartitionEvaluator for ElapsedPartitionEvaluator {
+    fn evaluate_all(&mut self, values: &[ArrayRef], _num_rows: usize) -> Result<Arc<dyn Array>> {
+        assert_eq!(values.len(), 3);
+
+        let array = Arc::clone(&values[0]);
+        let times = downcast_value!(&values[2], TimestampNanosecondArray);
+
+        // The second element of the values array is the second argument to
+        // the 'elapsed' function. This specifies the unit duration for the
+        // result.
+        //
+        // INVARIANT:
+        // The planner guarantees that the second argument is always a duration
+        // literal.
+        let unit = interval_nanos(downcast_value!(&values[1], IntervalMonthDayNanoArray).value(0))?;
+        if unit <= 0 {
+            return error::internal(format!("invalid unit ({unit}) for elapsed"));
+        }
+
+        let mut last_time: Option<i64> = None;
+        let mut builder = Int64Array::builder(array.len());
+        for idx in 0..array.len() {
+            if array.is_null(idx) || times.is_null(idx) {
+                builder.append_null();
+                continue;
+            }
+            let time = times.value(idx);
+            match last_time.replace(time) {
+                Some(last) => builder.append_value((time - last) / unit),
+                None => builder.append_null(),
+            }
+        }
+        Ok(Arc::new(builder.finish()))
+    }
+
+    fn uses_window_frame(&self) -> bool {
+        false
+    }
+
+    fn include_rank(&self) -> bool {
+        false
+    }
+}
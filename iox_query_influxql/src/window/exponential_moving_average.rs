@@ -0,0 +1,244 @@
+use crate::{error, NUMERICS};
+use arrow::array::{Array, ArrayRef, Float64Array, Int64Array, StringArray};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
+use datafusion::common::{downcast_value, DataFusionError, Result};
+use datafusion::logical_expr::{PartitionEvaluator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+/// The name of the exponential_moving_average window function.
+pub(super) const EXPONENTIAL_MOVING_AVERAGE_NAME: &str = "exponential_moving_average";
+
+/// The name of the double_exponential_moving_average window function.
+pub(super) const DOUBLE_EXPONENTIAL_MOVING_AVERAGE_NAME: &str = "double_exponential_moving_average";
+
+/// The name of the triple_exponential_moving_average window function.
+pub(super) const TRIPLE_EXPONENTIAL_MOVING_AVERAGE_NAME: &str = "triple_exponential_moving_average";
+
+/// Valid signatures for the exponential moving average window functions.
+///
+/// The arguments are the input values, the period, the hold period and the
+/// warmup type.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        NUMERICS
+            .iter()
+            .map(|dt| {
+                TypeSignature::Exact(vec![
+                    dt.clone(),
+                    DataType::Int64,
+                    DataType::Int64,
+                    DataType::Utf8,
+                ])
+            })
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// Calculate the return type given the function signature.
+pub(super) fn return_type(_: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(DataType::Float64))
+}
+
+/// Create a new partition_evaluator_factory for an exponential moving
+/// average of the given `order`, where an order of 2 is a double and 3 is
+/// a triple exponential moving average.
+pub(super) fn partition_evaluator_factory(order: usize) -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(EmaPartitionEvaluator { order }))
+}
+
+/// The algorithm used to compute the average of the first values, until
+/// a full period has been observed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum WarmupType {
+    /// Use an exponential moving average with a period of the number of
+    /// values observed so far.
+    Exponential,
+    /// Use a simple average of the values observed so far.
+    Simple,
+}
+
+impl WarmupType {
+    pub(super) fn try_from_str(name: &str, warmup: &str) -> Result<Self> {
+        match warmup {
+            "exponential" => Ok(Self::Exponential),
+            "simple" => Ok(Self::Simple),
+            _ => error::internal(format!("invalid warmup type ({warmup}) for {name}")),
+        }
+    }
+}
+
+/// An exponential moving average, as computed by InfluxQL.
+#[derive(Debug, Clone)]
+pub(super) struct Ema {
+    period: usize,
+    alpha: f64,
+    warmup: WarmupType,
+    count: usize,
+    last: f64,
+}
+
+impl Ema {
+    pub(super) fn new(period: usize, warmup: WarmupType) -> Self {
+        Self {
+            period,
+            alpha: 2.0 / (period as f64 + 1.0),
+            warmup,
+            count: 0,
+            last: 0.0,
+        }
+    }
+
+    /// Use the smoothing factor `alpha`, rather than the one derived from
+    /// the period.
+    pub(super) fn with_alpha(self, alpha: f64) -> Self {
+        Self { alpha, ..self }
+    }
+
+    /// The number of values that must be added before the average is
+    /// considered to be fully warmed.
+    pub(super) fn warm_count(&self) -> usize {
+        self.period - 1
+    }
+
+    /// Returns true if a full period of values have been added.
+    pub(super) fn warmed(&self) -> bool {
+        self.count == self.period
+    }
+
+    /// Add `v` and return the updated average.
+    pub(super) fn add(&mut self, v: f64) -> f64 {
+        let avg = if self.count == 0 {
+            v
+        } else if self.warmed() {
+            (v - self.last) * self.alpha + self.last
+        } else {
+            match self.warmup {
+                WarmupType::Simple => {
+                    (self.last * self.count as f64 + v) / (self.count as f64 + 1.0)
+                }
+                WarmupType::Exponential => {
+                    let alpha = 2.0 / (self.count as f64 + 2.0);
+                    (v - self.last) * alpha + self.last
+                }
+            }
+        };
+        self.last = avg;
+        if self.count < self.period {
+            self.count += 1;
+        }
+        avg
+    }
+}
+
+/// A chain of `order` exponential moving averages, where each average is
+/// computed from the output of the previous.
+#[derive(Debug)]
+pub(super) struct MultiEma {
+    emas: Vec<Ema>,
+}
+
+impl MultiEma {
+    pub(super) fn new(order: usize, period: usize, warmup: WarmupType) -> Self {
+        Self {
+            emas: vec![Ema::new(period, warmup); order],
+        }
+    }
+
+    pub(super) fn warm_count(&self) -> usize {
+        let warm_count = self.emas[0].warm_count();
+        match self.emas[0].warmup {
+            WarmupType::Exponential => warm_count,
+            WarmupType::Simple => warm_count * self.emas.len(),
+        }
+    }
+
+    /// Add `v` and return the combined average of the chain.
+    fn add(&mut self, v: f64) -> f64 {
+        match self.add_chained(v).as_slice() {
+            [ema] => *ema,
+            [ema1, ema2] => 2.0 * ema1 - ema2,
+            [ema1, ema2, ema3] => 3.0 * ema1 - 3.0 * ema2 + ema3,
+            _ => unreachable!("unsupported exponential moving average order"),
+        }
+    }
+
+    /// Add `v` and return the updated average of each level of the chain.
+    pub(super) fn add_chained(&mut self, v: f64) -> Vec<f64> {
+        let mut avgs = Vec::with_capacity(self.emas.len());
+        let mut v = v;
+        for idx in 0..self.emas.len() {
+            // With a simple warmup, the next average is only computed once
+            // the previous average is warmed.
+            if idx == 0
+                || self.emas[idx - 1].warmed()
+                || self.emas[idx - 1].warmup == WarmupType::Exponential
+            {
+                v = self.emas[idx].add(v);
+            }
+            avgs.push(v);
+        }
+        avgs
+    }
+}
+
+/// PartitionEvaluator which returns the exponential moving average of
+/// the input data.
+#[derive(Debug)]
+struct EmaPartitionEvaluator {
+    order: usize,
+}
+
+impl PartitionEvaluator for EmaPartitionEvaluator {
+    fn evaluate_all(&mut self, values: &[ArrayRef], _num_rows: usize) -> Result<Arc<dyn Array>> {
+        assert_eq!(values.len(), 4);
+
+        // INVARIANT:
+        // The planner and rewriter guarantee that the period, hold period and
+        // warmup arguments are always constants.
+        //
+        // See: FieldChecker::check_exponential_moving_average
+        let period = downcast_value!(&values[1], Int64Array).value(0);
+        let hold = downcast_value!(&values[2], Int64Array).value(0);
+        let warmup = downcast_value!(&values[3], StringArray).value(0);
+
+        if period < 1 {
+            return error::internal(format!(
+                "invalid period ({period}) for exponential moving average"
+            ));
+        }
+        let mut ema = MultiEma::new(
+            self.order,
+            period as usize,
+            WarmupType::try_from_str("exponential moving average", warmup)?,
+        );
+        let hold = usize::try_from(hold).unwrap_or_else(|_| ema.warm_count());
+
+        let array = cast(&values[0], &DataType::Float64)?;
+        let array = downcast_value!(array, Float64Array);
+
+        let mut count = 0;
+        Ok(Arc::new(
+            array
+                .iter()
+                .map(|v| {
+                    v.and_then(|v| {
+                        let avg = ema.add(v);
+                        count += 1;
+                        (count > hold).then_some(avg)
+                    })
+                })
+                .collect::<Float64Array>(),
+        ))
+    }
+
+    fn uses_window_frame(&self) -> bool {
+        false
+    }
+
+    fn include_rank(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,594 @@
+use crate::{error, interval_nanos, NUMERICS};
+use arrow::array::{
+    Array, ArrayRef, Float64Array, Int64Array, IntervalMonthDayNanoArray, TimestampNanosecondArray,
+};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, IntervalUnit, TimeUnit};
+use datafusion::common::{downcast_value, DataFusionError, Result};
+use datafusion::logical_expr::{PartitionEvaluator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The name of the holt_winters window function.
+pub(super) const HOLT_WINTERS_NAME: &str = "holt_winters";
+
+/// The name of the holt_winters_with_fit window function.
+pub(super) const HOLT_WINTERS_WITH_FIT_NAME: &str = "holt_winters_with_fit";
+
+/// Valid signatures for the holt_winters window functions.
+///
+/// The arguments are the input values, their timestamps, the number of
+/// values to forecast, the seasonal pattern length, the `GROUP BY time(..)`
+/// interval and the upper time bound of the input data.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        NUMERICS
+            .iter()
+            .map(|dt| {
+                TypeSignature::Exact(vec![
+                    dt.clone(),
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                    DataType::Int64,
+                    DataType::Int64,
+                    DataType::Interval(IntervalUnit::MonthDayNano),
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                ])
+            })
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// Calculate the return type given the function signature.
+pub(super) fn return_type(_: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(DataType::Float64))
+}
+
+/// Create a new partition_evaluator_factory, which also returns the values
+/// fitted to the input data if `include_fit` is `true`.
+pub(super) fn partition_evaluator_factory(
+    include_fit: bool,
+) -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(HoltWintersPartitionEvaluator { include_fit }))
+}
+
+/// PartitionEvaluator which returns, for each row, the value forecast by the
+/// Holt-Winters method for the time of the row, or `NULL` if no value was
+/// forecast for that time.
+///
+/// The rows after the upper time bound of the input data only exist to hold
+/// the forecast values, so their input values are ignored. The planner
+/// extends the `GROUP BY time(..)` intervals to include those rows.
+#[derive(Debug)]
+struct HoltWintersPartitionEvaluator {
+    include_fit: bool,
+}
+
+impl PartitionEvaluator for HoltWintersPartitionEvaluator {
+    fn evaluate_all(&mut self, values: &[ArrayRef], _num_rows: usize) -> Result<Arc<dyn Array>> {
+        assert_eq!(values.len(), 6);
+
+        let times = downcast_value!(&values[1], TimestampNanosecondArray);
+        if times.is_empty() {
+            return Ok(Arc::new(Float64Array::from(Vec::<Option<f64>>::new())));
+        }
+
+        // INVARIANT:
+        // The planner guarantees that the number of values, season, interval
+        // and upper time bound arguments are always constants.
+        let h = downcast_value!(&values[2], Int64Array).value(0);
+        let m = downcast_value!(&values[3], Int64Array).value(0);
+        let interval =
+            interval_nanos(downcast_value!(&values[4], IntervalMonthDayNanoArray).value(0))?;
+        let upper = downcast_value!(&values[5], TimestampNanosecondArray).value(0);
+
+        if interval <= 0 {
+            return error::internal(format!("invalid interval ({interval}) for holt_winters"));
+        }
+
+        let array = cast(&values[0], &DataType::Float64)?;
+        let array = downcast_value!(array, Float64Array);
+
+        let mut points = times
+            .iter()
+            .zip(array.iter())
+            .filter_map(|(t, v)| t.zip(v))
+            .filter(|(t, _)| *t <= upper)
+            .collect::<Vec<_>>();
+        points.sort_by_key(|(t, _)| *t);
+
+        let forecast = HoltWinters::new(h, m, interval).forecast(&points, self.include_fit);
+        let forecast: HashMap<_, _> = forecast.into_iter().collect();
+
+        Ok(Arc::new(
+            times
+                .iter()
+                .map(|t| t.and_then(|t| forecast.get(&t).copied()))
+                .collect::<Float64Array>(),
+        ))
+    }
+
+    fn uses_window_frame(&self) -> bool {
+        false
+    }
+
+    fn include_rank(&self) -> bool {
+        false
+    }
+}
+
+/// Arbitrary weight for initializing some initial guesses.
+const WEIGHT: f64 = 0.5;
+
+/// Epsilon value for the minimization process.
+const EPSILON: f64 = 1.0e-4;
+
+/// The lower bound, upper bound and step of the grid of initial guesses for
+/// the alpha, beta, gamma and phi parameters. The grid has N^4 points, so N
+/// is kept small.
+const GUESS_LOWER: f64 = 0.3;
+const GUESS_UPPER: f64 = 1.0;
+const GUESS_STEP: f64 = 0.4;
+
+/// Forecasts values using the Holt-Winters damped trend method, following
+/// the behaviour of the original influxdb implementation.
+///
+/// See: <https://github.com/influxdata/influxdb/blob/f365bb7e3a9c5e227dbf66d84adf674d3d127176/query/functions.go#L198-L491>
+#[derive(Debug)]
+struct HoltWinters {
+    /// The number of values to forecast.
+    h: i64,
+    /// The length of the seasonal pattern.
+    m: usize,
+    /// Whether the data is seasonal, which is the case if `m` is at least 2.
+    seasonal: bool,
+    /// The interval between values, in nanoseconds.
+    interval: i64,
+    /// The input values, with `NaN` for missing intervals.
+    y: Vec<f64>,
+}
+
+impl HoltWinters {
+    fn new(h: i64, m: i64, interval: i64) -> Self {
+        let m = m.max(0) as usize;
+        Self {
+            h,
+            m,
+            seasonal: m >= 2,
+            interval,
+            y: vec![],
+        }
+    }
+
+    /// Round `t` to the nearest multiple of the interval.
+    fn round_time(&self, t: i64) -> i64 {
+        let remainder = t % self.interval;
+        if remainder > self.interval / 2 {
+            (t / self.interval + 1) * self.interval
+        } else {
+            t - remainder
+        }
+    }
+
+    /// Return the forecast `(time, value)` pairs for the time ordered
+    /// `points`. The fitted values of the points are included if
+    /// `include_fit` is `true`.
+    fn forecast(&mut self, points: &[(i64, f64)], include_fit: bool) -> Vec<(i64, f64)> {
+        let l = points.len();
+        if l < 2 || (self.seasonal && l < self.m) || self.h <= 0 {
+            return vec![];
+        }
+
+        // Fill in y with the values, and NaN for missing values
+        let start = self.round_time(points[0].0);
+        let stop = self.round_time(points[l - 1].0);
+        if (stop - start) / self.interval <= 0 {
+            return vec![];
+        }
+        self.y = vec![points[0].1];
+        let mut t = start;
+        for &(time, value) in &points[1..] {
+            let rt = self.round_time(time);
+            if rt <= t {
+                // Drop this point
+                continue;
+            }
+            // Add any missing values before the next point
+            while rt - t > self.interval {
+                self.y.push(f64::NAN);
+                t += self.interval;
+            }
+            self.y.push(value);
+            t = rt;
+        }
+        if self.seasonal && self.y.len() < self.m {
+            return vec![];
+        }
+
+        let m = self.m;
+        let y = &self.y;
+
+        // Starting guesses, skipping missing values
+        let l0 = if self.seasonal {
+            y[..m]
+                .iter()
+                .filter(|v| !v.is_nan())
+                .map(|v| v / m as f64)
+                .sum()
+        } else {
+            WEIGHT * y[0]
+        };
+
+        let b0 = if self.seasonal {
+            (0..m)
+                .take_while(|i| m + i < y.len())
+                .filter(|&i| !y[i].is_nan() && !y[m + i].is_nan())
+                .map(|i| (y[m + i] - y[i]) / (m * m) as f64)
+                .sum()
+        } else if !y[1].is_nan() {
+            WEIGHT * (y[1] - y[0])
+        } else {
+            0.0
+        };
+
+        let seasonals = if self.seasonal {
+            y[..m]
+                .iter()
+                .map(|v| if v.is_nan() { 0.0 } else { v / l0 })
+                .collect()
+        } else {
+            vec![]
+        };
+
+        let mut parameters = vec![0.0, 0.0, 0.0, 0.0, l0, b0];
+        parameters.extend(seasonals);
+
+        // Determine the best fit for the alpha, beta, gamma and phi parameters
+        let mut min_sse = f64::INFINITY;
+        let mut best_params: Option<Vec<f64>> = None;
+        let mut alpha = GUESS_LOWER;
+        while alpha < GUESS_UPPER {
+            let mut beta = GUESS_LOWER;
+            while beta < GUESS_UPPER {
+                let mut gamma = GUESS_LOWER;
+                while gamma < GUESS_UPPER {
+                    let mut phi = GUESS_LOWER;
+                    while phi < GUESS_UPPER {
+                        parameters[..4].copy_from_slice(&[alpha, beta, gamma, phi]);
+                        let (sse, params) =
+                            nelder_mead(|params| self.sse(params), &parameters, EPSILON, 1.0);
+                        if sse < min_sse || best_params.is_none() {
+                            min_sse = sse;
+                            best_params = Some(params);
+                        }
+                        phi += GUESS_STEP;
+                    }
+                    gamma += GUESS_STEP;
+                }
+                beta += GUESS_STEP;
+            }
+            alpha += GUESS_STEP;
+        }
+        let mut params = best_params.expect("at least one guess");
+
+        let forecasted = self.forecast_values(self.h as usize, &mut params);
+        if include_fit {
+            let start = points[0].0;
+            forecasted
+                .into_iter()
+                .enumerate()
+                .filter(|(_, v)| !v.is_nan())
+                .map(|(i, v)| (start + self.interval * i as i64, v))
+                .collect()
+        } else {
+            let stop = points[l - 1].0;
+            forecasted[self.y.len()..]
+                .iter()
+                .enumerate()
+                .filter(|(_, v)| !v.is_nan())
+                .map(|(i, v)| (stop + self.interval * (i as i64 + 1), *v))
+                .collect()
+        }
+    }
+
+    /// Compute the fitted values of `y`, followed by `h` forecast values,
+    /// using the alpha, beta, gamma, phi, initial level, initial trend and
+    /// initial seasonal `params`.
+    ///
+    /// The parameters are constrained to their valid range, and the seasonal
+    /// parameters are used as a ring buffer of past seasonal components, so
+    /// `params` is modified.
+    fn forecast_values(&self, h: usize, params: &mut [f64]) -> Vec<f64> {
+        // Constrain alpha, beta, gamma and phi to [0, 1]
+        for p in &mut params[..4] {
+            *p = p.clamp(0.0, 1.0);
+        }
+        let (alpha, beta, gamma, phi) = (params[0], params[1], params[2], params[3]);
+        let mut phi_h = phi;
+
+        let mut y_t = self.y[0];
+        let mut l_t = params[4];
+        let mut b_t = params[5];
+
+        let seasonals = &mut params[6..];
+        let m = seasonals.len();
+        // Season index offset
+        let mut so = m.saturating_sub(1);
+
+        let len = self.y.len() + h;
+        let mut forecasted = Vec::with_capacity(len);
+        forecasted.push(y_t);
+        for t in 1..len {
+            let (s_tm, s_tmh) = if self.seasonal {
+                let hm = t % m;
+                (
+                    seasonals[(t + so - m) % m],
+                    seasonals[(t + so + hm - m) % m],
+                )
+            } else {
+                (1.0, 1.0)
+            };
+
+            // Using the recursive relations compute the next values
+            let l_tp = l_t;
+            let b_tp = b_t;
+            l_t = alpha * (y_t / s_tm) + (1.0 - alpha) * (l_tp + phi * b_tp);
+            b_t = beta * (l_t - l_tp) + (1.0 - beta) * phi * b_tp;
+            let s_t = gamma * (y_t / (l_tp + phi * b_tp)) + (1.0 - gamma) * s_tm;
+            y_t = (l_t + phi_h * b_t) * s_tmh;
+
+            phi_h += phi.powi(t as i32);
+
+            if self.seasonal {
+                seasonals[(t + so) % m] = s_t;
+                so += 1;
+            }
+
+            forecasted.push(y_t);
+        }
+        forecasted
+    }
+
+    /// Compute the sum of squared errors of the values fitted with `params`.
+    fn sse(&self, params: &mut [f64]) -> f64 {
+        let forecasted = self.forecast_values(0, params);
+        let mut sse = 0.0;
+        for (y, f) in self.y.iter().zip(forecasted) {
+            // Skip missing values since they cannot be used to compute an error.
+            if y.is_nan() {
+                continue;
+            }
+            // Penalize forecast NaNs
+            if f.is_nan() {
+                return f64::INFINITY;
+            }
+            sse += (f - y) * (f - y);
+        }
+        sse
+    }
+}
+
+/// The maximum number of iterations of the Nelder-Mead method.
+const NELDER_MEAD_MAX_ITERATIONS: usize = 1000;
+/// The reflection coefficient of the Nelder-Mead method.
+const NELDER_MEAD_ALPHA: f64 = 1.0;
+/// The contraction coefficient of the Nelder-Mead method.
+const NELDER_MEAD_BETA: f64 = 0.5;
+/// The expansion coefficient of the Nelder-Mead method.
+const NELDER_MEAD_GAMMA: f64 = 2.0;
+
+/// Minimize `objective` with the Nelder-Mead simplex method, starting from
+/// the simplex of size `scale` around `start`. Returns the minimum found and
+/// the parameters for it.
+///
+/// `objective` may modify the vertices of the simplex it is called for,
+/// following the behaviour of the original influxdb implementation.
+///
+/// See: <https://github.com/influxdata/influxdb/blob/f365bb7e3a9c5e227dbf66d84adf674d3d127176/query/neldermead/neldermead.go>
+fn nelder_mead(
+    mut objective: impl FnMut(&mut [f64]) -> f64,
+    start: &[f64],
+    epsilon: f64,
+    scale: f64,
+) -> (f64, Vec<f64>) {
+    let n = start.len();
+    let nf = n as f64;
+
+    // Create the initial simplex, with start as one of the vertices
+    let pn = scale * ((nf + 1.0).sqrt() - 1.0 + nf) / (nf * 2f64.sqrt());
+    let qn = scale * ((nf + 1.0).sqrt() - 1.0) / (nf * 2f64.sqrt());
+    let mut v = vec![start.to_vec()];
+    for i in 0..n {
+        v.push(
+            start
+                .iter()
+                .enumerate()
+                .map(|(j, s)| if i == j { pn + s } else { qn + s })
+                .collect(),
+        );
+    }
+
+    // The values of the objective function at each vertex
+    let mut f: Vec<f64> = v.iter_mut().map(|v| objective(v)).collect();
+
+    let mut vr = vec![0.0; n];
+    let mut ve = vec![0.0; n];
+    let mut vc = vec![0.0; n];
+    let mut vm = vec![0.0; n];
+
+    for _ in 0..NELDER_MEAD_MAX_ITERATIONS {
+        // Find the indexes of the largest and smallest values
+        let mut vg = 0;
+        let mut vs = 0;
+        for (i, fi) in f.iter().enumerate() {
+            if *fi > f[vg] {
+                vg = i;
+            }
+            if *fi < f[vs] {
+                vs = i;
+            }
+        }
+        // Find the index of the second largest value
+        let mut vh = vs;
+        for (i, fi) in f.iter().enumerate() {
+            if *fi > f[vh] && *fi < f[vg] {
+                vh = i;
+            }
+        }
+
+        // Calculate the centroid
+        for (i, m) in vm.iter_mut().enumerate() {
+            let cent: f64 = v
+                .iter()
+                .enumerate()
+                .filter(|(row, _)| *row != vg)
+                .map(|(_, vertex)| vertex[i])
+                .sum();
+            *m = cent / nf;
+        }
+
+        // Reflect vg to the new vertex vr
+        for ((r, m), g) in vr.iter_mut().zip(&vm).zip(&v[vg]) {
+            *r = m + NELDER_MEAD_ALPHA * (m - g);
+        }
+        let fr = objective(&mut vr);
+
+        if fr < f[vh] && fr >= f[vs] {
+            v[vg].copy_from_slice(&vr);
+            f[vg] = fr;
+        }
+
+        // Investigate a step further in this direction
+        if fr < f[vs] {
+            for ((e, m), r) in ve.iter_mut().zip(&vm).zip(&vr) {
+                *e = m + NELDER_MEAD_GAMMA * (r - m);
+            }
+            let fe = objective(&mut ve);
+
+            if fe < fr {
+                v[vg].copy_from_slice(&ve);
+                f[vg] = fe;
+            } else {
+                v[vg].copy_from_slice(&vr);
+                f[vg] = fr;
+            }
+        }
+
+        // Check to see if a contraction is necessary
+        if fr >= f[vh] {
+            if fr < f[vg] && fr >= f[vh] {
+                // Perform an outside contraction
+                for ((c, m), r) in vc.iter_mut().zip(&vm).zip(&vr) {
+                    *c = m + NELDER_MEAD_BETA * (r - m);
+                }
+            } else {
+                // Perform an inside contraction
+                for ((c, m), g) in vc.iter_mut().zip(&vm).zip(&v[vg]) {
+                    *c = m - NELDER_MEAD_BETA * (m - g);
+                }
+            }
+            let fc = objective(&mut vc);
+
+            if fc < f[vg] {
+                v[vg].copy_from_slice(&vc);
+                f[vg] = fc;
+            } else {
+                // The contraction was not successful, so halve the distance
+                // from vs to all other vertices of the simplex.
+                let best = v[vs].clone();
+                for (row, vertex) in v.iter_mut().enumerate() {
+                    if row != vs {
+                        for (x, b) in vertex.iter_mut().zip(&best) {
+                            *x = b + (*x - b) / 2.0;
+                        }
+                    }
+                }
+                f[vg] = objective(&mut v[vg]);
+                f[vh] = objective(&mut v[vh]);
+            }
+        }
+
+        // Test for convergence
+        let favg = f.iter().sum::<f64>() / (nf + 1.0);
+        let s = f
+            .iter()
+            .map(|f| (f - favg).powi(2) / nf)
+            .sum::<f64>()
+            .sqrt();
+        if s < epsilon {
+            break;
+        }
+    }
+
+    // Find the index of the smallest value
+    let vs = f
+        .iter()
+        .enumerate()
+        .fold(0, |vs, (i, fi)| if *fi < f[vs] { i } else { vs });
+
+    let mut parameters = v.swap_remove(vs);
+    let min = objective(&mut parameters);
+    (min, parameters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nelder_mead() {
+        let (min, params) = nelder_mead(
+            |x| (x[0] - 3.0).powi(2) + (x[1] + 1.0).powi(2) + 2.0,
+            &[0.0, 0.0],
+            1e-10,
+            1.0,
+        );
+        assert!((min - 2.0).abs() < 1e-4, "min: {min}");
+        assert!((params[0] - 3.0).abs() < 1e-2, "params: {params:?}");
+        assert!((params[1] + 1.0).abs() < 1e-2, "params: {params:?}");
+    }
+
+    #[test]
+    fn test_holt_winters_too_few_points() {
+        let mut hw = HoltWinters::new(3, 0, 10);
+        assert_eq!(hw.forecast(&[(0, 1.0)], false), vec![]);
+
+        // seasonal forecasts require a full season
+        let mut hw = HoltWinters::new(3, 4, 10);
+        assert_eq!(
+            hw.forecast(&[(0, 1.0), (10, 2.0), (20, 3.0)], false),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_holt_winters_forecast_times() {
+        let points = [(5, 1.0), (15, 2.0), (25, 3.0), (45, 5.0)];
+
+        // forecasts follow the last point
+        let mut hw = HoltWinters::new(3, 0, 10);
+        let forecast = hw.forecast(&points, false);
+        let times: Vec<_> = forecast.iter().map(|p| p.0).collect();
+        assert_eq!(times, vec![55, 65, 75]);
+        assert!(forecast.iter().all(|p| p.1.is_finite()), "{forecast:?}");
+
+        // fitted values start at the first point, including missing intervals
+        let mut hw = HoltWinters::new(3, 0, 10);
+        let times: Vec<_> = hw.forecast(&points, true).iter().map(|p| p.0).collect();
+        assert_eq!(times, vec![5, 15, 25, 35, 45, 55, 65, 75]);
+    }
+
+    #[test]
+    fn test_holt_winters_seasonal() {
+        let points: Vec<_> = (0..12)
+            .map(|i| (i * 10, [1.0, 3.0, 2.0, 4.0][i as usize % 4] + i as f64))
+            .collect();
+
+        let mut hw = HoltWinters::new(4, 4, 10);
+        let forecast = hw.forecast(&points, false);
+        let times: Vec<_> = forecast.iter().map(|p| p.0).collect();
+        assert_eq!(times, vec![120, 130, 140, 150]);
+        assert!(forecast.iter().all(|p| p.1.is_finite()), "{forecast:?}");
+    }
+}
@@ -0,0 +1,202 @@
+use crate::{error, interval_nanos, NUMERICS};
+use arrow::array::{
+    Array, ArrayRef, Float64Array, IntervalMonthDayNanoArray, TimestampNanosecondArray,
+};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, IntervalUnit, TimeUnit};
+use datafusion::common::{downcast_value, DataFusionError, Result};
+use datafusion::logical_expr::{PartitionEvaluator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+
+/// The name of the integral_window window function.
+pub(super) const NAME: &str = "integral_window";
+
+/// Valid signatures for the integral_window window function.
+///
+/// The arguments are the input values, their timestamps, the unit of the
+/// result, and the stride and origin of the `GROUP BY time(..)` intervals.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        NUMERICS
+            .iter()
+            .map(|dt| {
+                TypeSignature::Exact(vec![
+                    dt.clone(),
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                    DataType::Interval(IntervalUnit::MonthDayNano),
+                    DataType::Interval(IntervalUnit::MonthDayNano),
+                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                ])
+            })
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// Calculate the return type given the function signature. Integral_window
+/// always returns a Float64.
+pub(super) fn return_type(_: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(DataType::Float64))
+}
+
+/// Create a new partition_evaluator_factory.
+pub(super) fn partition_evaluator_factory() -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(IntegralWindowPartitionEvaluator {}))
+}
+
+/// PartitionEvaluator which returns, for each point, the area under the
+/// curve of the input data that is attributed to the `GROUP BY time(..)`
+/// interval of the point. Summing the result for each interval produces the
+/// integral of the interval.
+///
+/// The partition must be ordered by time in ascending order.
+#[derive(Debug)]
+struct IntegralWindowPartitionEvaluator {}
+
+impl PartitionEvaluator for IntegralWindowPartitionEvaluator {
+    fn evaluate_all(&mut self, values: &[ArrayRef], _num_rows: usize) -> Result<Arc<dyn Array>> {
+        assert_eq!(values.len(), 5);
+
+        // INVARIANT:
+        // The planner guarantees that the unit, stride and origin arguments
+        // are always constants.
+        let unit = interval_nanos(downcast_value!(&values[2], IntervalMonthDayNanoArray).value(0))?;
+        let stride =
+            interval_nanos(downcast_value!(&values[3], IntervalMonthDayNanoArray).value(0))?;
+        let origin = downcast_value!(&values[4], TimestampNanosecondArray).value(0);
+
+        if unit <= 0 {
+            return error::internal(format!("invalid unit ({unit}) for {NAME}"));
+        }
+        if stride <= 0 {
+            return error::internal(format!("invalid stride ({stride}) for {NAME}"));
+        }
+
+        let times = downcast_value!(&values[1], TimestampNanosecondArray);
+        let array = cast(&values[0], &DataType::Float64)?;
+        let array = downcast_value!(array, Float64Array);
+
+        let points = times.iter().zip(array.iter()).map(|(t, v)| t.zip(v));
+        Ok(Arc::new(
+            interval_areas(points, unit, stride, origin)
+                .into_iter()
+                .collect::<Float64Array>(),
+        ))
+    }
+
+    fn uses_window_frame(&self) -> bool {
+        false
+    }
+
+    fn include_rank(&self) -> bool {
+        false
+    }
+}
+
+/// Calculate the area under the curve between each of the time ordered
+/// `points` and the previous point, using the trapezoidal rule, expressed in
+/// multiples of `unit` nanoseconds.
+///
+/// When consecutive points are in different intervals of `stride`
+/// nanoseconds from `origin`, the value at the end of the interval of the
+/// previous point is linearly interpolated. The area up to the end of the
+/// interval is attributed to the previous point, and the remainder to the
+/// current point. When multiple points share the same timestamp, only the
+/// last is used. This follows the behaviour of the original influxdb
+/// implementation.
+fn interval_areas(
+    points: impl IntoIterator<Item = Option<(i64, f64)>>,
+    unit: i64,
+    stride: i64,
+    origin: i64,
+) -> Vec<Option<f64>> {
+    let interval_end = |t: i64| t - (t - origin).rem_euclid(stride) + stride;
+    let area = |(t1, v1): (i64, f64), (t2, v2): (i64, f64)| {
+        0.5 * (v1 + v2) * ((t2 - t1) as f64 / unit as f64)
+    };
+
+    let mut areas = vec![];
+    // The index and point of the previous point.
+    let mut prev: Option<(usize, (i64, f64))> = None;
+    for (idx, point) in points.into_iter().enumerate() {
+        let Some((time, value)) = point else {
+            areas.push(None);
+            continue;
+        };
+
+        let (prev_idx, (prev_time, prev_value)) = match prev.replace((idx, (time, value))) {
+            Some(prev) => prev,
+            None => {
+                areas.push(Some(0.0));
+                continue;
+            }
+        };
+
+        let end = interval_end(prev_time);
+        if time >= end {
+            let end_value = prev_value
+                + (value - prev_value) * ((end - prev_time) as f64 / (time - prev_time) as f64);
+            let prev_area = areas[prev_idx].unwrap_or_default();
+            areas[prev_idx] = Some(prev_area + area((prev_time, prev_value), (end, end_value)));
+            areas.push(Some(area((end, end_value), (time, value))));
+        } else {
+            areas.push(Some(area((prev_time, prev_value), (time, value))));
+        }
+    }
+    areas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval_areas() {
+        let points = vec![
+            Some((0, 0.0)),
+            Some((10, 10.0)),
+            None,
+            Some((20, 20.0)),
+            Some((20, 20.0)),
+            Some((40, 0.0)),
+        ];
+
+        // The value at the end of the [0, 15) interval is interpolated
+        // as 15.0, and at the end of the [15, 30) interval as 10.0.
+        assert_eq!(
+            interval_areas(points, 1, 15, 0),
+            vec![
+                Some(0.0),
+                Some(50.0 + 62.5),
+                None,
+                Some(87.5),
+                Some(150.0),
+                Some(50.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_interval_areas_unit_and_origin() {
+        let points = vec![Some((-5, 2.0)), Some((5, 2.0))];
+
+        // The intervals are [-8, 2) and [2, 12).
+        assert_eq!(
+            interval_areas(points.clone(), 2, 10, 2),
+            vec![Some(7.0), Some(3.0)]
+        );
+
+        // Both points are in the interval [-10, 10).
+        assert_eq!(
+            interval_areas(points, 2, 20, 10),
+            vec![Some(0.0), Some(10.0)]
+        );
+    }
+
+    #[test]
+    fn test_interval_areas_empty() {
+        assert_eq!(interval_areas(vec![None, None], 1, 10, 0), vec![None, None]);
+        assert_eq!(interval_areas(vec![], 1, 10, 0), vec![]);
+    }
+}
@@ -0,0 +1,170 @@
+use crate::{error, NUMERICS};
+use arrow::array::{Array, ArrayRef, Float64Array, Int64Array};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
+use datafusion::common::{downcast_value, DataFusionError, Result};
+use datafusion::logical_expr::{PartitionEvaluator, Signature, TypeSignature, Volatility};
+use once_cell::sync::Lazy;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// The name of the kaufmans_efficiency_ratio window function.
+pub(super) const EFFICIENCY_RATIO_NAME: &str = "kaufmans_efficiency_ratio";
+
+/// The name of the kaufmans_adaptive_moving_average window function.
+pub(super) const ADAPTIVE_MOVING_AVERAGE_NAME: &str = "kaufmans_adaptive_moving_average";
+
+/// Valid signatures for the Kaufman's window functions.
+///
+/// The arguments are the input values, the period and the hold period.
+pub(super) static SIGNATURE: Lazy<Signature> = Lazy::new(|| {
+    Signature::one_of(
+        NUMERICS
+            .iter()
+            .map(|dt| TypeSignature::Exact(vec![dt.clone(), DataType::Int64, DataType::Int64]))
+            .collect(),
+        Volatility::Immutable,
+    )
+});
+
+/// Calculate the return type given the function signature.
+pub(super) fn return_type(_: &[DataType]) -> Result<Arc<DataType>> {
+    Ok(Arc::new(DataType::Float64))
+}
+
+/// Create a new partition_evaluator_factory for the efficiency ratio.
+pub(super) fn efficiency_ratio_partition_evaluator_factory() -> Result<Box<dyn PartitionEvaluator>>
+{
+    Ok(Box::new(KaufmansPartitionEvaluator {
+        adaptive_moving_average: false,
+    }))
+}
+
+/// Create a new partition_evaluator_factory for the adaptive moving average.
+pub(super) fn adaptive_moving_average_partition_evaluator_factory(
+) -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(KaufmansPartitionEvaluator {
+        adaptive_moving_average: true,
+    }))
+}
+
+/// Kaufman's efficiency ratio, which is the ratio of the absolute change
+/// over the period to the sum of the absolute changes between each value
+/// of the period.
+#[derive(Debug)]
+struct EfficiencyRatio {
+    period: usize,
+    values: VecDeque<f64>,
+    noise: f64,
+}
+
+impl EfficiencyRatio {
+    fn new(period: usize) -> Self {
+        Self {
+            period,
+            values: VecDeque::with_capacity(period + 1),
+            noise: 0.0,
+        }
+    }
+
+    /// Returns true once a full period of changes has been observed.
+    fn warmed(&self) -> bool {
+        self.values.len() > self.period
+    }
+
+    /// Add `v` and return the updated efficiency ratio.
+    fn add(&mut self, v: f64) -> f64 {
+        if let Some(last) = self.values.back() {
+            self.noise += (v - last).abs();
+        }
+        self.values.push_back(v);
+        if self.values.len() > self.period + 1 {
+            let oldest = self.values.pop_front().expect("not empty");
+            self.noise -= (self.values[0] - oldest).abs();
+        }
+
+        let signal = (v - self.values[0]).abs();
+        if signal == 0.0 || self.noise == 0.0 {
+            0.0
+        } else {
+            signal / self.noise
+        }
+    }
+}
+
+/// PartitionEvaluator which returns either Kaufman's efficiency ratio or
+/// Kaufman's adaptive moving average of the input data.
+#[derive(Debug)]
+struct KaufmansPartitionEvaluator {
+    adaptive_moving_average: bool,
+}
+
+impl PartitionEvaluator for KaufmansPartitionEvaluator {
+    fn evaluate_all(&mut self, values: &[ArrayRef], _num_rows: usize) -> Result<Arc<dyn Array>> {
+        assert_eq!(values.len(), 3);
+
+        // INVARIANT:
+        // The planner and rewriter guarantee that the period and hold period
+        // arguments are always constants.
+        //
+        // See: FieldChecker::check_kaufmans
+        let period = downcast_value!(&values[1], Int64Array).value(0);
+        let hold = downcast_value!(&values[2], Int64Array).value(0);
+
+        if period < 1 {
+            return error::internal(format!("invalid period ({period}) for kaufmans function"));
+        }
+        let period = period as usize;
+        let hold = usize::try_from(hold).unwrap_or(period);
+
+        let array = cast(&values[0], &DataType::Float64)?;
+        let array = downcast_value!(array, Float64Array);
+
+        // The smoothing constants for the fastest (2 periods) and slowest
+        // (30 periods) exponential moving averages.
+        let fast = 2.0 / (2.0 + 1.0);
+        let slow = 2.0 / (30.0 + 1.0);
+
+        let mut er = EfficiencyRatio::new(period);
+        let mut kama: Option<f64> = None;
+        let mut count = 0;
+        Ok(Arc::new(
+            array
+                .iter()
+                .map(|v| {
+                    v.and_then(|v| {
+                        let warmed = er.warmed();
+                        let ratio = er.add(v);
+                        count += 1;
+
+                        let value = if self.adaptive_moving_average {
+                            // The adaptive moving average is seeded with
+                            // the last value before a full period of changes
+                            // is available.
+                            let last = match kama {
+                                Some(last) if warmed => last,
+                                _ => v,
+                            };
+                            let sc = (ratio * (fast - slow) + slow).powi(2);
+                            let next = last + sc * (v - last);
+                            kama = Some(next);
+                            next
+                        } else {
+                            ratio
+                        };
+
+                        (count > hold).then_some(value)
+                    })
+                })
+                .collect::<Float64Array>(),
+        ))
+    }
+
+    fn uses_window_frame(&self) -> bool {
+        false
+    }
+
+    fn include_rank(&self) -> bool {
+        false
+    }
+}
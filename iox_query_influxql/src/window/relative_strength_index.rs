@@ -0,0 +1,78 @@
+use super::exponential_moving_average::{Ema, WarmupType};
+use crate::error;
+use arrow::array::{Array, ArrayRef, Float64Array, Int64Array, StringArray};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
+use datafusion::common::{downcast_value, DataFusionError, Result};
+use datafusion::logical_expr::PartitionEvaluator;
+use std::sync::Arc;
+
+/// The name of the relative_strength_index window function.
+pub(super) const NAME: &str = "relative_strength_index";
+
+/// Create a new partition_evaluator_factory.
+pub(super) fn partition_evaluator_factory() -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(RsiPartitionEvaluator {}))
+}
+
+/// PartitionEvaluator which returns the relative strength index of the
+/// input data.
+///
+/// The upward and downward changes are smoothed with an exponential moving
+/// average with a smoothing factor of `1 / period`, and the change of the
+/// first value is measured from zero, following the behaviour of the
+/// original influxdb implementation.
+#[derive(Debug)]
+struct RsiPartitionEvaluator {}
+
+impl PartitionEvaluator for RsiPartitionEvaluator {
+    fn evaluate_all(&mut self, values: &[ArrayRef], _num_rows: usize) -> Result<Arc<dyn Array>> {
+        assert_eq!(values.len(), 4);
+
+        // INVARIANT:
+        // The planner and rewriter guarantee that the period, hold period and
+        // warmup arguments are always constants.
+        //
+        // See: FieldChecker::check_exponential_moving_average
+        let period = downcast_value!(&values[1], Int64Array).value(0);
+        let hold = downcast_value!(&values[2], Int64Array).value(0);
+        let warmup = downcast_value!(&values[3], StringArray).value(0);
+
+        if period < 1 {
+            return error::internal(format!("invalid period ({period}) for {NAME}"));
+        }
+        let ema = Ema::new(period as usize + 1, WarmupType::try_from_str(NAME, warmup)?)
+            .with_alpha(1.0 / period as f64);
+        let hold = usize::try_from(hold).unwrap_or_else(|_| ema.warm_count());
+        let (mut up, mut down) = (ema.clone(), ema);
+
+        let array = cast(&values[0], &DataType::Float64)?;
+        let array = downcast_value!(array, Float64Array);
+
+        let mut last = 0.0;
+        let mut count = 0;
+        Ok(Arc::new(
+            array
+                .iter()
+                .map(|v| {
+                    let v = v?;
+                    count += 1;
+                    let diff = v - last;
+                    last = v;
+                    let up = up.add(diff.max(0.0));
+                    let down = down.add((-diff).max(0.0));
+                    let rsi = 100.0 - (100.0 / (1.0 + up / down));
+                    (count > hold && !rsi.is_nan()).then_some(rsi)
+                })
+                .collect::<Float64Array>(),
+        ))
+    }
+
+    fn uses_window_frame(&self) -> bool {
+        false
+    }
+
+    fn include_rank(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,70 @@
+use super::exponential_moving_average::{MultiEma, WarmupType};
+use crate::error;
+use arrow::array::{Array, ArrayRef, Float64Array, Int64Array, StringArray};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
+use datafusion::common::{downcast_value, DataFusionError, Result};
+use datafusion::logical_expr::PartitionEvaluator;
+use std::sync::Arc;
+
+/// The name of the triple_exponential_derivative window function.
+pub(super) const NAME: &str = "triple_exponential_derivative";
+
+/// Create a new partition_evaluator_factory.
+pub(super) fn partition_evaluator_factory() -> Result<Box<dyn PartitionEvaluator>> {
+    Ok(Box::new(TrixPartitionEvaluator {}))
+}
+
+/// PartitionEvaluator which returns the percentage rate of change of a
+/// triple exponential moving average of the input data.
+#[derive(Debug)]
+struct TrixPartitionEvaluator {}
+
+impl PartitionEvaluator for TrixPartitionEvaluator {
+    fn evaluate_all(&mut self, values: &[ArrayRef], _num_rows: usize) -> Result<Arc<dyn Array>> {
+        assert_eq!(values.len(), 4);
+
+        // INVARIANT:
+        // The planner and rewriter guarantee that the period, hold period and
+        // warmup arguments are always constants.
+        //
+        // See: FieldChecker::check_exponential_moving_average
+        let period = downcast_value!(&values[1], Int64Array).value(0);
+        let hold = downcast_value!(&values[2], Int64Array).value(0);
+        let warmup = downcast_value!(&values[3], StringArray).value(0);
+
+        if period < 1 {
+            return error::internal(format!("invalid period ({period}) for {NAME}"));
+        }
+        let mut ema = MultiEma::new(3, period as usize, WarmupType::try_from_str(NAME, warmup)?);
+        // The rate of change requires one more value than the average.
+        let hold = usize::try_from(hold).unwrap_or_else(|_| ema.warm_count() + 1);
+
+        let array = cast(&values[0], &DataType::Float64)?;
+        let array = downcast_value!(array, Float64Array);
+
+        let mut last = 0.0;
+        let mut count = 0;
+        Ok(Arc::new(
+            array
+                .iter()
+                .map(|v| {
+                    let v = v?;
+                    count += 1;
+                    let avg = *ema.add_chained(v).last().expect("three averages");
+                    let rate = ((avg / last) - 1.0) * 100.0;
+                    last = avg;
+                    (count > hold && rate.is_finite()).then_some(rate)
+                })
+                .collect::<Float64Array>(),
+        ))
+    }
+
+    fn uses_window_frame(&self) -> bool {
+        false
+    }
+
+    fn include_rank(&self) -> bool {
+        false
+    }
+}
@@ -22,8 +22,12 @@ use data_types::ChunkId;
 use datafusion::{
     common::DFSchemaRef,
     error::DataFusionError,
-    logical_expr::{utils::exprlist_to_columns, ExprSchemable, LogicalPlan, LogicalPlanBuilder},
-    prelude::{when, Column, Expr},
+    logical_expr::{
+        expr_fn::{approx_distinct, count_distinct},
+        utils::exprlist_to_columns,
+        ExprSchemable, LogicalPlan, LogicalPlanBuilder,
+    },
+    prelude::{cast, concat, lit, replace, when, Column, Expr},
 };
 use datafusion_util::{
     config::{DEFAULT_CATALOG, DEFAULT_SCHEMA},
@@ -37,6 +41,7 @@ use iox_query::{
         stringset::StringSet, IOxSessionContext,
     },
     plan::{
+        cardinality::{SeriesCardinalityMode, SeriesCardinalityPlan, CARDINALITY_COLUMN_NAME},
         fieldlist::FieldListPlan,
         seriesset::{SeriesSetPlan, SeriesSetPlans},
         stringset::{Error as StringSetError, StringSetPlan, StringSetPlanBuilder},
@@ -836,6 +841,38 @@ impl InfluxRpcPlanner {
         Ok(SeriesSetPlans::new(plans))
     }
 
+    /// Returns a plan that counts the distinct series which have at least one
+    /// row passing the conditions specified by `predicate`.
+    ///
+    /// As for [`read_filter`](Self::read_filter), a series is identified by
+    /// the measurement, its tag set and a field with non-null values.
+    pub async fn series_cardinality(
+        &self,
+        namespace: Arc<dyn QueryNamespace>,
+        rpc_predicate: InfluxRpcPredicate,
+        mode: SeriesCardinalityMode,
+    ) -> Result<SeriesCardinalityPlan> {
+        let ctx = self.ctx.child_ctx("series_cardinality planning");
+        debug!(?rpc_predicate, ?mode, "planning series_cardinality");
+
+        let table_predicates = rpc_predicate
+            .table_predicates(self.meta.as_ref())
+            .context(CreatingPredicatesSnafu)?;
+
+        let plans = create_plans(
+            namespace,
+            &table_predicates,
+            ctx,
+            Arc::clone(&self.meta),
+            |table_name, predicate, chunks, schema| {
+                Self::series_cardinality_plan(table_name, schema, predicate, mode, chunks)
+            },
+        )
+        .await?;
+
+        Ok(plans.into_iter().flatten().collect::<Vec<_>>().into())
+    }
+
     /// Creates a DataFusion LogicalPlan that returns column *names* as a
     /// single column of Strings for a specific table
     ///
@@ -1265,6 +1302,101 @@ impl InfluxRpcPlanner {
             field_columns,
         ))
     }
+
+    /// Creates a DataFusion LogicalPlan that returns the number of series
+    /// in a specific table as a single row, or `None` if the predicate
+    /// selects none of the table's fields.
+    ///
+    /// Each series is identified by a key built from the tag values of a row.
+    /// The keys are counted separately for the rows of each field that has
+    /// a value:
+    ///
+    /// ```text
+    /// SELECT
+    ///   count(DISTINCT CASE WHEN field1 IS NOT NULL THEN key END)
+    ///   + ...
+    ///   + count(DISTINCT CASE WHEN fieldN IS NOT NULL THEN key END) AS cardinality
+    /// ```
+    ///
+    /// In [`SeriesCardinalityMode::Approximate`] mode, `approx_distinct` is
+    /// used instead of `count(DISTINCT ..)`.
+    ///
+    /// The created plan looks like:
+    ///
+    ///    Projection (sum of the per-field counts)
+    ///      Aggregate(agg: count distinct key per field)
+    ///        Filter(predicate)
+    ///          Scan
+    fn series_cardinality_plan(
+        table_name: &str,
+        schema: &Schema,
+        predicate: &Predicate,
+        mode: SeriesCardinalityMode,
+        chunks: Vec<Arc<dyn QueryChunk>>,
+    ) -> Result<Option<LogicalPlan>> {
+        let scan_and_filter = ScanPlanBuilder::new(Arc::from(table_name), schema)
+            .with_predicate(predicate)
+            .with_chunks(chunks)
+            .build()?;
+
+        let schema = scan_and_filter.provider.iox_schema();
+        let key = series_key(schema);
+
+        let agg_exprs = filtered_fields_iter(schema, predicate)
+            .enumerate()
+            .map(|(i, field)| {
+                let value = field.expr.unalias().is_not_null();
+                let key = when(value, key.clone()).end()?;
+                let agg = match mode {
+                    SeriesCardinalityMode::Exact => count_distinct(key),
+                    SeriesCardinalityMode::Approximate => approx_distinct(key),
+                };
+                Ok(agg.alias(format!("series_{i}")))
+            })
+            .collect::<Result<Vec<_>, DataFusionError>>()
+            .context(BuildingPlanSnafu)?;
+
+        let Some(cardinality) = (0..agg_exprs.len())
+            .map(|i| cast(format!("series_{i}").as_expr(), DataType::Int64))
+            .reduce(|a, b| a + b)
+        else {
+            return Ok(None);
+        };
+
+        let plan = scan_and_filter
+            .plan_builder
+            .aggregate(Vec::<Expr>::new(), agg_exprs)?
+            .project(vec![cardinality.alias(CARDINALITY_COLUMN_NAME)])?
+            .build()?;
+
+        Ok(Some(plan))
+    }
+}
+
+/// Returns an expression that builds a string identifying the series of a
+/// row from its tag values, like `tag1=a,tag2=b`.
+///
+/// Backslashes and commas in tag values are escaped, so that a value cannot
+/// be mistaken for the separator of the next tag. Tag values are never
+/// empty, so a "tag=" pair unambiguously marks a NULL value (`concat`
+/// ignores NULL arguments).
+fn series_key(schema: &Schema) -> Expr {
+    let key_parts = schema
+        .tags_iter()
+        .enumerate()
+        .flat_map(|(i, field)| {
+            let sep = if i == 0 { "" } else { "," };
+            let value = cast(field.name().as_expr(), DataType::Utf8);
+            let value = replace(replace(value, lit("\\"), lit("\\\\")), lit(","), lit("\\,"));
+            [lit(format!("{sep}{}=", field.name())), value]
+        })
+        .collect::<Vec<_>>();
+
+    if key_parts.is_empty() {
+        lit("")
+    } else {
+        concat(&key_parts)
+    }
 }
 
 /// Stream of chunks for table predicates.
@@ -1888,9 +2020,15 @@ impl QueryNamespaceMeta for NamespaceMeta {
 
 #[cfg(test)]
 mod tests {
+    use arrow::{
+        array::{DictionaryArray, TimestampNanosecondArray},
+        datatypes::Int32Type,
+        record_batch::RecordBatch,
+    };
+    use arrow_util::assert_batches_eq;
     use datafusion::{
         common::ScalarValue,
-        prelude::{col, lit},
+        prelude::{col, lit, SessionContext},
     };
     use datafusion_util::lit_dict;
     use futures::{future::BoxFuture, FutureExt};
@@ -1900,6 +2038,7 @@ mod tests {
         exec::Executor,
         test::{TestChunk, TestDatabase},
     };
+    use schema::builder::SchemaBuilder;
     use test_helpers::maybe_start_logging;
 
     use super::*;
@@ -2461,6 +2600,48 @@ mod tests {
         .await
     }
 
+    #[tokio::test]
+    async fn test_series_key_escaping() {
+        let schema = SchemaBuilder::new()
+            .tag("t1")
+            .tag("t2")
+            .timestamp()
+            .build()
+            .unwrap();
+
+        // Without escaping, both rows have the key "t1=a,t2=b,t2=".
+        let t1: DictionaryArray<Int32Type> = vec![Some("a,t2=b"), Some("a")].into_iter().collect();
+        let t2: DictionaryArray<Int32Type> = vec![None, Some("b,t2=")].into_iter().collect();
+        let time = TimestampNanosecondArray::from(vec![1, 2]);
+        let batch = RecordBatch::try_new(
+            schema.as_arrow(),
+            vec![Arc::new(t1), Arc::new(t2), Arc::new(time)],
+        )
+        .unwrap();
+
+        let ctx = SessionContext::new();
+        let batches = ctx
+            .read_batch(batch)
+            .unwrap()
+            .select(vec![series_key(&schema).alias("key")])
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+
+        assert_batches_eq!(
+            [
+                "+----------------+",
+                "| key            |",
+                "+----------------+",
+                "| t1=a\\,t2=b,t2= |",
+                "| t1=a,t2=b\\,t2= |",
+                "+----------------+",
+            ],
+            &batches
+        );
+    }
+
     #[tokio::test]
     async fn test_issue_7848() {
         maybe_start_logging();
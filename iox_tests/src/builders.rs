@@ -33,6 +33,7 @@ impl ParquetFileBuilder {
                 row_count: 1,
                 compaction_level: CompactionLevel::FileNonOverlapped,
                 created_at: Timestamp::new(0),
+                data_written_at: Timestamp::new(0),
                 column_set: ColumnSet::new(vec![]),
                 max_l0_created_at: Timestamp::new(0),
             },
@@ -632,6 +632,7 @@ impl TestPartition {
             file_size_bytes: file_size_bytes.unwrap_or(0) as i64,
             row_count: row_count as i64,
             created_at: Timestamp::new(creation_time),
+            data_written_at: Timestamp::new(creation_time),
             compaction_level,
             column_set,
             max_l0_created_at: Timestamp::new(max_l0_created_at),
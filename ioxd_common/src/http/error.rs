@@ -113,6 +113,17 @@ pub struct HttpApiError {
     /// Optional error line (for line protocol errors).
     #[serde(skip_serializing_if = "Option::is_none")]
     line: Option<usize>,
+
+    /// Lines rejected by a partial write (for line protocol errors).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    rejected_lines: Vec<RejectedLine>,
+}
+
+/// A line rejected by a partial write, as reported in a [`HttpApiError`].
+#[derive(Debug, Serialize)]
+struct RejectedLine {
+    line: usize,
+    reason: String,
 }
 
 impl HttpApiError {
@@ -122,6 +133,7 @@ impl HttpApiError {
             code: code.into(),
             msg: msg.into(),
             line: None,
+            rejected_lines: vec![],
         }
     }
 
@@ -130,6 +142,18 @@ impl HttpApiError {
         Self { line, ..self }
     }
 
+    /// Add the `(line number, reason)` of each line rejected by a partial
+    /// write to the error.
+    pub fn with_rejected_lines(self, lines: impl IntoIterator<Item = (usize, String)>) -> Self {
+        Self {
+            rejected_lines: lines
+                .into_iter()
+                .map(|(line, reason)| RejectedLine { line, reason })
+                .collect(),
+            ..self
+        }
+    }
+
     /// Generate response body for this error.
     fn body(&self) -> Body {
         Body::from(serde_json::to_string(&self).expect("must serialise to json"))
@@ -5,7 +5,8 @@ use clap_blocks::compactor_scheduler::{
     ShardConfigForLocalScheduler,
 };
 use compactor_scheduler::{
-    LocalSchedulerConfig, PartitionsSourceConfig, SchedulerConfig, ShardConfig,
+    LocalSchedulerConfig, PartitionsSourceConfig, RemoteSchedulerConfig, SchedulerConfig,
+    ShardConfig,
 };
 use data_types::PartitionId;
 
@@ -76,7 +77,21 @@ pub(crate) fn convert_scheduler_config(config: CompactorSchedulerConfig) -> Sche
                 .partition_source_config
                 .ignore_partition_skip_marker,
         }),
-        CompactorSchedulerType::Remote => unimplemented!("Remote scheduler not implemented"),
+        CompactorSchedulerType::Remote => SchedulerConfig::Remote(RemoteSchedulerConfig {
+            commit_wrapper: None,
+            partitions_source_config: convert_partitions_source_config(
+                config.partition_source_config.clone(),
+            ),
+            ignore_partition_skip_marker: config
+                .partition_source_config
+                .ignore_partition_skip_marker,
+            // identify the compactor by its host name, if known
+            holder: config
+                .shard_config
+                .hostname
+                .unwrap_or_else(|| RemoteSchedulerConfig::default().holder),
+            lease_duration: config.lease_config.lease_duration,
+        }),
     }
 }
 
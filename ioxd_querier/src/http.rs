@@ -0,0 +1,175 @@
+//! HTTP API of the querier.
+
+mod prom;
+mod v1;
+mod v3;
+
+use std::sync::Arc;
+
+use authz::Authorizer;
+use datafusion::error::DataFusionError;
+use hyper::{body::HttpBody, Body, Method, Request, Response};
+use ioxd_common::http::error::{HttpApiError, HttpApiErrorCode, HttpApiErrorSource};
+use service_common::QueryNamespaceProvider;
+use thiserror::Error;
+
+/// Errors returned by the querier HTTP request handler.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// The requested path has no registered handler.
+    #[error("not found")]
+    NoHandler,
+
+    /// The request body could not be read.
+    #[error(transparent)]
+    Body(#[from] BodyError),
+
+    /// The request body is not a valid query request.
+    #[error("invalid request: {0}")]
+    InvalidRequest(serde_json::Error),
+
+    /// The requested output format is not supported.
+    #[error("{0}")]
+    InvalidFormat(influxdb_iox_client::format::Error),
+
+    /// The request has no authorization token.
+    #[error("authentication required")]
+    Unauthenticated,
+
+    /// The authorization token does not grant the required permissions.
+    #[error("access denied")]
+    Forbidden,
+
+    /// The permissions of the request could not be checked.
+    #[error("authorization failed: {0}")]
+    Authz(authz::Error),
+
+    /// The queried namespace does not exist.
+    #[error("namespace not found: {0}")]
+    NamespaceNotFound(String),
+
+    /// The query could not be planned.
+    #[error("error planning query: {0}")]
+    Planning(DataFusionError),
+
+    /// The execution of the query could not be started.
+    #[error("error executing query: {0}")]
+    Execution(DataFusionError),
+
+    /// The results of the query could not be written in the requested
+    /// format.
+    #[error("error formatting results: {0}")]
+    Format(influxdb_iox_client::format::Error),
+
+    /// The Prometheus remote read request is invalid.
+    #[error(transparent)]
+    PromRead(#[from] prom::ReadRequestError),
+}
+
+impl Error {
+    fn status_code(&self) -> HttpApiErrorCode {
+        match self {
+            Self::NoHandler | Self::NamespaceNotFound(_) => HttpApiErrorCode::NotFound,
+            Self::Body(BodyError::Read(_))
+            | Self::InvalidRequest(_)
+            | Self::InvalidFormat(_)
+            | Self::PromRead(_)
+            | Self::Planning(_) => HttpApiErrorCode::Invalid,
+            Self::Body(BodyError::TooLarge(_)) => HttpApiErrorCode::RequestTooLarge,
+            Self::Unauthenticated => HttpApiErrorCode::Unauthorized,
+            Self::Forbidden => HttpApiErrorCode::Forbidden,
+            Self::Authz(_) | Self::Execution(_) | Self::Format(_) => {
+                HttpApiErrorCode::InternalError
+            }
+        }
+    }
+}
+
+impl HttpApiErrorSource for Error {
+    fn to_http_api_error(&self) -> HttpApiError {
+        HttpApiError::new(self.status_code(), self.to_string())
+    }
+}
+
+impl From<authz::Error> for Error {
+    fn from(err: authz::Error) -> Self {
+        match err {
+            authz::Error::NoToken => Self::Unauthenticated,
+            authz::Error::Forbidden | authz::Error::InvalidToken => Self::Forbidden,
+            err => Self::Authz(err),
+        }
+    }
+}
+
+/// Errors reading a request body.
+#[derive(Debug, Error)]
+pub enum BodyError {
+    /// The body could not be read from the client.
+    #[error("error reading request body: {0}")]
+    Read(hyper::Error),
+
+    /// The body is larger than the maximum allowed size.
+    #[error("max request size ({0} bytes) exceeded")]
+    TooLarge(usize),
+}
+
+/// Read `body`, which must not be larger than `max_bytes`.
+async fn read_body(mut body: Body, max_bytes: usize) -> Result<Vec<u8>, BodyError> {
+    let mut buf = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(BodyError::Read)?;
+        if buf.len() + chunk.len() > max_bytes {
+            return Err(BodyError::TooLarge(max_bytes));
+        }
+        buf.extend_from_slice(&chunk);
+    }
+    Ok(buf)
+}
+
+/// Routes HTTP requests to the query APIs of the querier.
+#[derive(Debug)]
+pub(crate) struct HttpApi<S> {
+    server: Arc<S>,
+    authz: Option<Arc<dyn Authorizer>>,
+}
+
+impl<S> HttpApi<S>
+where
+    S: QueryNamespaceProvider,
+{
+    pub(crate) fn new(server: Arc<S>, authz: Option<Arc<dyn Authorizer>>) -> Self {
+        Self { server, authz }
+    }
+
+    /// Handle `req`.
+    ///
+    /// Errors of the InfluxDB 1.x query API are returned in the response
+    /// body, in the format expected by its clients.
+    pub(crate) async fn route(&self, req: Request<Body>) -> Result<Response<Body>, Error> {
+        match (req.method(), req.uri().path()) {
+            (&Method::GET | &Method::POST, "/query") => Ok(v1::query(
+                Arc::clone(&self.server),
+                self.authz.as_ref().map(Arc::clone),
+                req,
+            )
+            .await),
+            (&Method::POST, "/api/v3/query_sql") => {
+                v3::query_sql(
+                    Arc::clone(&self.server),
+                    self.authz.as_ref().map(Arc::clone),
+                    req,
+                )
+                .await
+            }
+            (&Method::POST, "/api/v1/prom/read") => {
+                prom::read(
+                    Arc::clone(&self.server),
+                    self.authz.as_ref().map(Arc::clone),
+                    req,
+                )
+                .await
+            }
+            _ => Err(Error::NoHandler),
+        }
+    }
+}
@@ -0,0 +1,717 @@
+//! The Prometheus remote read `/api/v1/prom/read` API, which serves the
+//! samples written by the remote write API of the router, for IOx to be used
+//! as the long-term storage of Prometheus.
+//!
+//! The samples of a metric are read from the table named after it, with its
+//! other labels as tags and its values as the `value` field. Only the
+//! `SAMPLES` response type is supported, so all the samples of a request are
+//! returned at once.
+
+use std::{collections::BTreeSet, fmt::Write, sync::Arc};
+
+use authz::{extract_token, http::AuthorizationHeaderExtension, Authorizer};
+use datafusion::{
+    common::Column,
+    error::DataFusionError,
+    prelude::{lit, Expr},
+    scalar::ScalarValue,
+};
+use futures::TryStreamExt;
+use generated_types::{
+    prometheus::{
+        label_matcher, read_request::ResponseType, Label, LabelMatcher, Query, QueryResult,
+        ReadRequest, ReadResponse, Sample, TimeSeries,
+    },
+    prost::Message,
+};
+use hyper::{
+    header::{CONTENT_ENCODING, CONTENT_TYPE},
+    Body, Request, Response, StatusCode,
+};
+use iox_query::{
+    exec::{
+        seriesset::series::{Data, Either, Series},
+        IOxSessionContext,
+    },
+    plan::seriesset::SeriesSetPlans,
+};
+use observability_deps::tracing::info;
+use predicate::{
+    rpc_predicate::{InfluxRpcPredicate, FIELD_COLUMN_NAME, MEASUREMENT_COLUMN_NAME},
+    Predicate,
+};
+use serde::Deserialize;
+use service_common::{planner::Planner, table_access::granted_tables, QueryNamespaceProvider};
+use thiserror::Error;
+use trace::{ctx::SpanContext, span::SpanExt};
+use trace_http::ctx::{RequestLogContext, RequestLogContextExt};
+
+use super::{read_body, BodyError, Error};
+
+/// The maximum size of a request body, both compressed and decompressed.
+const MAX_REQUEST_BYTES: usize = 10 * 1024 * 1024;
+
+/// The maximum number of points of a series read at once.
+const POINTS_PER_BATCH: usize = 1000;
+
+/// The label holding the metric name of a time series, which is the name of
+/// the table its samples are read from.
+const METRIC_NAME_LABEL: &str = "__name__";
+
+/// The field column the values of the samples are read from.
+const VALUE_FIELD_NAME: &str = "value";
+
+/// The names of the columns the samples are read from, which are not labels.
+const RESERVED_COLUMN_NAMES: [&str; 2] = [VALUE_FIELD_NAME, "time"];
+
+/// Errors returned when parsing a remote read request.
+#[derive(Debug, Error)]
+pub enum ReadRequestError {
+    /// The query parameters are invalid.
+    #[error("invalid query parameters: {0}")]
+    Params(serde_urlencoded::de::Error),
+
+    /// The body is not valid snappy-compressed data.
+    #[error("error decoding snappy-compressed body: {0}")]
+    Snappy(snap::Error),
+
+    /// The decompressed body is not a protobuf `ReadRequest`.
+    #[error("invalid remote read request: {0}")]
+    Decode(generated_types::prost::DecodeError),
+
+    /// None of the response types accepted by the client is supported.
+    #[error("unsupported response types, only SAMPLES is supported")]
+    UnsupportedResponseType,
+
+    /// A label matcher has an unknown type.
+    #[error("unknown type {0} of label matcher")]
+    MatcherType(i32),
+
+    /// The regex of a label matcher is invalid.
+    #[error("invalid regex {pattern:?} of label matcher: {source}")]
+    Regex {
+        /// The invalid regex.
+        pattern: String,
+        /// The underlying error.
+        source: regex::Error,
+    },
+}
+
+/// The query parameters of a `/api/v1/prom/read` request.
+#[derive(Debug, Deserialize)]
+struct ReadParams {
+    /// The namespace to read from.
+    db: String,
+}
+
+/// Handle a `/api/v1/prom/read` request.
+pub(super) async fn read<S>(
+    server: Arc<S>,
+    authz: Option<Arc<dyn Authorizer>>,
+    req: Request<Body>,
+) -> Result<Response<Body>, Error>
+where
+    S: QueryNamespaceProvider,
+{
+    let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
+    let external_span_ctx: Option<RequestLogContext> = req.extensions().get().cloned();
+    let authz_token = extract_token(
+        req.extensions()
+            .get::<AuthorizationHeaderExtension>()
+            .and_then(|v| v.as_ref()),
+    );
+
+    let ReadParams { db } = serde_urlencoded::from_str(req.uri().query().unwrap_or_default())
+        .map_err(ReadRequestError::Params)?;
+    let body = read_body(req.into_body(), MAX_REQUEST_BYTES).await?;
+    let request = decode_request(&body)?;
+    let predicates = request
+        .queries
+        .iter()
+        .map(query_predicate)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let perms = [authz::Permission::ResourceAction(
+        authz::Resource::Database(db.clone()),
+        authz::Action::Read,
+    )];
+    // A token which is not authorized to read the whole namespace may still
+    // be granted access to some of its tables, which are the only ones read.
+    let restrict_tables = match authz.permissions(authz_token.clone(), &perms).await {
+        Ok(_) => false,
+        Err(authz::Error::Forbidden) => true,
+        Err(e) => return Err(e.into()),
+    };
+
+    // A token restricted to some tables is not told whether the namespace
+    // exists.
+    let namespace = server
+        .db(&db, span_ctx.child_span("get namespace"), false)
+        .await
+        .ok_or_else(|| {
+            if restrict_tables {
+                Error::Forbidden
+            } else {
+                Error::NamespaceNotFound(db.clone())
+            }
+        })?;
+
+    let _permit = server
+        .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+        .await;
+
+    let selectors = request
+        .queries
+        .iter()
+        .map(selector)
+        .collect::<Vec<_>>()
+        .join(", ");
+    info!(
+        namespace_name=%db,
+        %selectors,
+        trace=external_span_ctx.format_jaeger().as_str(),
+        "Prometheus /api/v1/prom/read request",
+    );
+
+    let ctx = namespace.new_query_context(span_ctx);
+    let table_names = if restrict_tables {
+        let tables = granted_tables(&authz, &ctx, &db, authz_token).await?;
+        Some(tables.into_iter().collect::<BTreeSet<_>>())
+    } else {
+        None
+    };
+    let mut token = namespace.record_query(
+        external_span_ctx.as_ref().map(RequestLogContext::ctx),
+        "prom_read",
+        Box::new(selectors),
+    );
+
+    let mut results = Vec::with_capacity(request.queries.len());
+    for predicate in predicates {
+        let predicate = InfluxRpcPredicate::new(table_names.clone(), predicate);
+        let plans = match Planner::new(&ctx)
+            .read_filter(Arc::clone(&namespace), predicate)
+            .await
+        {
+            Ok(plans) => plans,
+            Err(e) => {
+                token.set_error(&e);
+                return Err(Error::Planning(e));
+            }
+        };
+        match read_series(&ctx, plans).await {
+            Ok(timeseries) => results.push(QueryResult { timeseries }),
+            Err(e) => {
+                token.set_error(&e);
+                return Err(Error::Execution(e));
+            }
+        }
+    }
+    token.set_success();
+
+    let response = ReadResponse { results }.encode_to_vec();
+    let body = snap::raw::Encoder::new()
+        .compress_vec(&response)
+        .map_err(|e| Error::Execution(DataFusionError::External(Box::new(e))))?;
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(CONTENT_TYPE, "application/x-protobuf")
+        .header(CONTENT_ENCODING, "snappy")
+        .body(Body::from(body))
+        .expect("valid response"))
+}
+
+/// Decode the snappy-compressed protobuf `ReadRequest` in `body`.
+fn decode_request(body: &[u8]) -> Result<ReadRequest, Error> {
+    // Check the size before decompressing, to prevent a decompression bomb
+    // based DoS.
+    let len = snap::raw::decompress_len(body).map_err(ReadRequestError::Snappy)?;
+    if len > MAX_REQUEST_BYTES {
+        return Err(BodyError::TooLarge(MAX_REQUEST_BYTES).into());
+    }
+    let body = snap::raw::Decoder::new()
+        .decompress_vec(body)
+        .map_err(ReadRequestError::Snappy)?;
+    let request = ReadRequest::decode(body.as_slice()).map_err(ReadRequestError::Decode)?;
+
+    // Clients which do not list the response types they accept accept
+    // SAMPLES only.
+    let samples = ResponseType::Samples as i32;
+    if !request.accepted_response_types.is_empty()
+        && !request.accepted_response_types.contains(&samples)
+    {
+        return Err(ReadRequestError::UnsupportedResponseType.into());
+    }
+
+    Ok(request)
+}
+
+/// The selector of `query` in PromQL syntax, to describe it in logs and the
+/// query log.
+fn selector(query: &Query) -> String {
+    let mut selector = String::from("{");
+    for (i, matcher) in query.matchers.iter().enumerate() {
+        let op = match label_matcher::Type::try_from(matcher.r#type) {
+            Ok(label_matcher::Type::Eq) => "=",
+            Ok(label_matcher::Type::Neq) => "!=",
+            Ok(label_matcher::Type::Re) => "=~",
+            Ok(label_matcher::Type::Nre) => "!~",
+            Err(_) => "?",
+        };
+        if i > 0 {
+            selector.push(',');
+        }
+        write!(selector, "{}{op}{:?}", matcher.name, matcher.value).expect("infallible");
+    }
+    write!(
+        selector,
+        "}}[{}ms, {}ms]",
+        query.start_timestamp_ms, query.end_timestamp_ms
+    )
+    .expect("infallible");
+    selector
+}
+
+/// The predicate selecting the samples matched by `query`.
+///
+/// The time range of a query includes both its start and end.
+fn query_predicate(query: &Query) -> Result<Predicate, Error> {
+    let start = query.start_timestamp_ms.saturating_mul(1_000_000);
+    let end = query
+        .end_timestamp_ms
+        .saturating_mul(1_000_000)
+        .saturating_add(1);
+
+    let field = Expr::Column(Column::from_name(FIELD_COLUMN_NAME)).eq(lit(VALUE_FIELD_NAME));
+    query.matchers.iter().try_fold(
+        Predicate::new().with_range(start, end).with_expr(field),
+        |predicate, matcher| Ok(predicate.with_expr(matcher_expr(matcher)?)),
+    )
+}
+
+/// The expression selecting the series matched by `matcher`.
+///
+/// A label with an empty value is the same as no label in Prometheus, so a
+/// matcher matching the empty string also matches the series without the
+/// label, which have a null tag.
+fn matcher_expr(matcher: &LabelMatcher) -> Result<Expr, Error> {
+    let LabelMatcher { name, value, .. } = matcher;
+    let column = match name.as_str() {
+        METRIC_NAME_LABEL => Expr::Column(Column::from_name(MEASUREMENT_COLUMN_NAME)),
+        // No series has these labels, as they are the names of the columns
+        // the samples are read from.
+        name if RESERVED_COLUMN_NAMES.contains(&name) => lit(ScalarValue::Utf8(None)),
+        name => Expr::Column(Column::from_name(name)),
+    };
+
+    let matcher_type = label_matcher::Type::try_from(matcher.r#type)
+        .map_err(|_| ReadRequestError::MatcherType(matcher.r#type))?;
+    let expr = match matcher_type {
+        label_matcher::Type::Eq if value.is_empty() => column.is_null(),
+        label_matcher::Type::Eq => column.eq(lit(value.as_str())),
+        label_matcher::Type::Neq if value.is_empty() => column.is_not_null(),
+        label_matcher::Type::Neq => column
+            .clone()
+            .is_null()
+            .or(column.not_eq(lit(value.as_str()))),
+        label_matcher::Type::Re | label_matcher::Type::Nre => {
+            // Prometheus regexes match whole label values.
+            let pattern = format!("^(?:{value})$");
+            let matches_empty = regex::Regex::new(&pattern)
+                .map_err(|source| ReadRequestError::Regex {
+                    pattern: value.clone(),
+                    source,
+                })?
+                .is_match("");
+
+            match (matcher_type, matches_empty) {
+                (label_matcher::Type::Re, false) => {
+                    query_functions::regex_match_expr(column, pattern)
+                }
+                (label_matcher::Type::Re, true) => column
+                    .clone()
+                    .is_null()
+                    .or(query_functions::regex_match_expr(column, pattern)),
+                (_, false) => column
+                    .clone()
+                    .is_null()
+                    .or(query_functions::regex_not_match_expr(column, pattern)),
+                (_, true) => column
+                    .clone()
+                    .is_not_null()
+                    .and(query_functions::regex_not_match_expr(column, pattern)),
+            }
+        }
+    };
+
+    Ok(expr)
+}
+
+/// Execute the read filter `plans`, and return the time series of the
+/// float `value` field of the series they select.
+async fn read_series(
+    ctx: &IOxSessionContext,
+    plans: SeriesSetPlans,
+) -> Result<Vec<TimeSeries>, DataFusionError> {
+    let series = ctx
+        .to_series_and_groups(
+            plans,
+            Arc::clone(&ctx.inner().runtime_env().memory_pool),
+            POINTS_PER_BATCH,
+        )
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let mut timeseries: Vec<TimeSeries> = Vec::with_capacity(series.len());
+    for item in series {
+        let Either::Series(Series { tags, data }) = item else {
+            continue;
+        };
+        // Values which are not floats were not written by Prometheus.
+        let Data::FloatPoints(batches) = data else {
+            continue;
+        };
+
+        let mut labels = tags
+            .into_iter()
+            .filter(|tag| tag.key.as_ref() != FIELD_COLUMN_NAME)
+            .map(|tag| Label {
+                name: match tag.key.as_ref() {
+                    MEASUREMENT_COLUMN_NAME => METRIC_NAME_LABEL.to_string(),
+                    key => key.to_string(),
+                },
+                value: tag.value.to_string(),
+            })
+            .collect::<Vec<_>>();
+        labels.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+
+        let samples = batches.into_iter().flat_map(|batch| {
+            batch
+                .timestamps
+                .into_iter()
+                .zip(batch.values)
+                .map(|(timestamp, value)| Sample {
+                    value,
+                    timestamp: timestamp.div_euclid(1_000_000),
+                })
+        });
+
+        // The points of a series may be split across several consecutive
+        // series.
+        match timeseries.last_mut() {
+            Some(last) if last.labels == labels => last.samples.extend(samples),
+            _ => timeseries.push(TimeSeries {
+                labels,
+                samples: samples.collect(),
+            }),
+        }
+    }
+
+    Ok(timeseries)
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use authz::Permission;
+    use iox_query::test::TestChunk;
+    use ioxd_common::http::error::HttpApiErrorCode;
+    use service_common::test_util::TestDatabaseStore;
+
+    use super::*;
+    use label_matcher::Type;
+
+    #[derive(Debug)]
+    struct MockAuthorizer {}
+
+    #[async_trait]
+    impl Authorizer for MockAuthorizer {
+        async fn permissions(
+            &self,
+            token: Option<Vec<u8>>,
+            perms: &[Permission],
+        ) -> Result<Vec<Permission>, authz::Error> {
+            match token.as_deref() {
+                Some(b"GOOD") => Ok(perms.to_vec()),
+                // Grants access to the "up" table only.
+                Some(b"UP") => {
+                    let granted = perms
+                        .iter()
+                        .filter(|perm| {
+                            matches!(
+                                perm,
+                                Permission::ResourceAction(authz::Resource::Table { table, .. }, _)
+                                    if table == "up"
+                            )
+                        })
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    if granted.is_empty() {
+                        return Err(authz::Error::Forbidden);
+                    }
+                    Ok(granted)
+                }
+                Some(_) => Err(authz::Error::Forbidden),
+                None => Err(authz::Error::NoToken),
+            }
+        }
+    }
+
+    async fn test_store() -> Arc<TestDatabaseStore> {
+        let store = TestDatabaseStore::default();
+        let db = store.db_or_create("bananas").await;
+        // One sample of 99.5 at 1µs, with the "MA" value for each tag.
+        db.add_chunk(
+            "partition",
+            Arc::new(
+                TestChunk::new("up")
+                    .with_time_column()
+                    .with_tag_column("job")
+                    .with_f64_field_column("value")
+                    .with_one_row_of_data(),
+            ),
+        );
+        db.add_chunk(
+            "partition",
+            Arc::new(
+                TestChunk::new("go_goroutines")
+                    .with_time_column()
+                    .with_tag_column("instance")
+                    .with_f64_field_column("value")
+                    .with_one_row_of_data(),
+            ),
+        );
+        Arc::new(store)
+    }
+
+    fn matcher(r#type: Type, name: &str, value: &str) -> LabelMatcher {
+        LabelMatcher {
+            r#type: r#type as i32,
+            name: name.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    fn query(matchers: Vec<LabelMatcher>) -> Query {
+        Query {
+            start_timestamp_ms: 0,
+            end_timestamp_ms: 1000,
+            matchers,
+            hints: None,
+        }
+    }
+
+    fn post(request: &ReadRequest, token: Option<&'static str>) -> Request<Body> {
+        let body = snap::raw::Encoder::new()
+            .compress_vec(&request.encode_to_vec())
+            .unwrap();
+        let mut req = Request::post("/api/v1/prom/read?db=bananas")
+            .body(Body::from(body))
+            .unwrap();
+        req.extensions_mut()
+            .insert(AuthorizationHeaderExtension::new(
+                token.map(|t| format!("Token {t}").parse().unwrap()),
+            ));
+        req
+    }
+
+    async fn read_request(
+        store: &Arc<TestDatabaseStore>,
+        authz: Option<Arc<dyn Authorizer>>,
+        req: Request<Body>,
+    ) -> Result<ReadResponse, Error> {
+        let response = read(Arc::clone(store), authz, req).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_ENCODING], "snappy");
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body = snap::raw::Decoder::new().decompress_vec(&body).unwrap();
+        Ok(ReadResponse::decode(body.as_slice()).unwrap())
+    }
+
+    /// The metric names of the series of each query result.
+    fn metrics(response: &ReadResponse) -> Vec<Vec<String>> {
+        response
+            .results
+            .iter()
+            .map(|result| {
+                result
+                    .timeseries
+                    .iter()
+                    .flat_map(|ts| &ts.labels)
+                    .filter(|l| l.name == METRIC_NAME_LABEL)
+                    .map(|l| l.value.clone())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_read() {
+        let store = test_store().await;
+        let request = ReadRequest {
+            queries: vec![
+                query(vec![matcher(Type::Eq, "__name__", "up")]),
+                query(vec![matcher(Type::Re, "__name__", "go_.*|up")]),
+                // The regex must match the whole metric name.
+                query(vec![matcher(Type::Re, "__name__", "go")]),
+                query(vec![matcher(Type::Neq, "job", "MA")]),
+                query(vec![matcher(Type::Eq, "job", "")]),
+                query(vec![matcher(Type::Nre, "instance", "M.*")]),
+                query(vec![
+                    matcher(Type::Re, "__name__", ".+"),
+                    matcher(Type::Eq, "value", ""),
+                ]),
+            ],
+            accepted_response_types: vec![],
+        };
+
+        let response = read_request(&store, None, post(&request, None))
+            .await
+            .unwrap();
+        assert_eq!(
+            metrics(&response),
+            [
+                vec!["up"],
+                vec!["go_goroutines", "up"],
+                vec![],
+                vec!["go_goroutines"],
+                vec!["go_goroutines"],
+                vec!["up"],
+                vec!["go_goroutines", "up"],
+            ]
+        );
+
+        assert_eq!(
+            response.results[0].timeseries,
+            [TimeSeries {
+                labels: vec![
+                    Label {
+                        name: "__name__".to_string(),
+                        value: "up".to_string(),
+                    },
+                    Label {
+                        name: "job".to_string(),
+                        value: "MA".to_string(),
+                    },
+                ],
+                samples: vec![Sample {
+                    value: 99.5,
+                    timestamp: 0,
+                }],
+            }]
+        );
+
+        // The sample is outside of the time range.
+        let request = ReadRequest {
+            queries: vec![Query {
+                start_timestamp_ms: 1,
+                ..query(vec![matcher(Type::Eq, "__name__", "up")])
+            }],
+            accepted_response_types: vec![],
+        };
+        let response = read_request(&store, None, post(&request, None))
+            .await
+            .unwrap();
+        assert_eq!(metrics(&response), [Vec::<String>::new()]);
+    }
+
+    #[tokio::test]
+    async fn test_read_errors() {
+        let store = test_store().await;
+
+        let request = ReadRequest {
+            queries: vec![query(vec![matcher(Type::Re, "__name__", "(")])],
+            accepted_response_types: vec![],
+        };
+        let err = read_request(&store, None, post(&request, None))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::PromRead(ReadRequestError::Regex { .. })),
+            "{err}"
+        );
+
+        let request = ReadRequest {
+            queries: vec![],
+            accepted_response_types: vec![ResponseType::StreamedXorChunks as i32],
+        };
+        let err = read_request(&store, None, post(&request, None))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                Error::PromRead(ReadRequestError::UnsupportedResponseType)
+            ),
+            "{err}"
+        );
+
+        let req = Request::post("/api/v1/prom/read?db=bananas")
+            .body(Body::from("not snappy"))
+            .unwrap();
+        let err = read_request(&store, None, req).await.unwrap_err();
+        assert!(
+            matches!(err, Error::PromRead(ReadRequestError::Snappy(_))),
+            "{err}"
+        );
+
+        let req = Request::post("/api/v1/prom/read")
+            .body(Body::empty())
+            .unwrap();
+        let err = read_request(&store, None, req).await.unwrap_err();
+        assert_eq!(err.status_code(), HttpApiErrorCode::Invalid);
+        assert!(
+            matches!(err, Error::PromRead(ReadRequestError::Params(_))),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_authz() {
+        let store = test_store().await;
+        let authz: Option<Arc<dyn Authorizer>> = Some(Arc::new(MockAuthorizer {}));
+        let request = ReadRequest {
+            queries: vec![query(vec![matcher(Type::Re, "__name__", ".+")])],
+            accepted_response_types: vec![],
+        };
+
+        let err = read_request(&store, authz.clone(), post(&request, None))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code(), HttpApiErrorCode::Unauthorized);
+
+        let err = read_request(&store, authz.clone(), post(&request, Some("BAD")))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code(), HttpApiErrorCode::Forbidden);
+
+        let response = read_request(&store, authz.clone(), post(&request, Some("GOOD")))
+            .await
+            .unwrap();
+        assert_eq!(metrics(&response), [vec!["go_goroutines", "up"]]);
+
+        // Only the series of the granted tables are read.
+        let response = read_request(&store, authz.clone(), post(&request, Some("UP")))
+            .await
+            .unwrap();
+        assert_eq!(metrics(&response), [vec!["up"]]);
+
+        // A token granted access to some tables only is not told whether
+        // the namespace exists.
+        let missing = |token| {
+            let mut req = post(&request, Some(token));
+            *req.uri_mut() = "/api/v1/prom/read?db=nope".parse().unwrap();
+            req
+        };
+        let err = read_request(&store, authz.clone(), missing("GOOD"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code(), HttpApiErrorCode::NotFound);
+        let err = read_request(&store, authz, missing("UP"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code(), HttpApiErrorCode::Forbidden);
+    }
+}
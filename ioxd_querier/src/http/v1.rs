@@ -0,0 +1,1008 @@
+//! The InfluxDB 1.x compatible `/query` API, which runs InfluxQL queries.
+//!
+//! The statements of a query are executed in order, and their results are
+//! returned as the series of the 1.x JSON or CSV result formats, either in
+//! a single response, or streamed in chunks when `chunked=true`.
+
+mod series;
+
+use std::{collections::HashMap, collections::VecDeque, convert::Infallible, sync::Arc};
+
+use arrow::record_batch::RecordBatch;
+use authz::{extract_token, http::AuthorizationHeaderExtension, Authorizer};
+use datafusion::error::DataFusionError;
+use futures::{stream::BoxStream, StreamExt};
+use hyper::{
+    header::{ACCEPT, CONTENT_TYPE},
+    Body, Method, Request, Response, StatusCode,
+};
+use influxdb_influxql_parser::parse_statements;
+use iox_query::{QueryCompletedToken, QueryNamespace};
+use iox_query_influxql::frontend::{
+    catalog::CatalogStatement,
+    params::{StatementParam, StatementParams},
+    planner::{namespace_name, InfluxQLQueryPlanner},
+};
+use observability_deps::tracing::info;
+use serde::Deserialize;
+use service_common::{
+    planner::Planner,
+    table_access::{granted_tables, TableAccess},
+    QueryNamespaceProvider,
+};
+use service_grpc_flight::{
+    catalog_statement_permissions, execute_catalog_statement, register_namespace,
+};
+use thiserror::Error;
+use trace::{ctx::SpanContext, span::SpanExt};
+use trace_http::ctx::{RequestLogContext, RequestLogContextExt};
+use tracker::InstrumentedAsyncOwnedSemaphorePermit;
+
+use super::{read_body, BodyError};
+
+use self::series::{
+    CsvEncoder, Precision, QueryResponse, SeriesBuilder, StatementResult, TimeFormat,
+};
+
+/// The number of rows of a chunk of a chunked response, unless set by the
+/// `chunk_size` parameter.
+const DEFAULT_CHUNK_SIZE: usize = 10_000;
+
+/// The maximum size of a form encoded request body.
+const MAX_REQUEST_BYTES: usize = 10 * 1024 * 1024;
+
+/// Errors of a `/query` request that prevent any of its statements from
+/// being executed.
+///
+/// Errors of an individual statement are returned in its result instead.
+#[derive(Debug, Error)]
+enum Error {
+    #[error("missing required parameter \"q\"")]
+    MissingQuery,
+
+    #[error("invalid query parameters: {0}")]
+    InvalidParams(#[from] serde_urlencoded::de::Error),
+
+    #[error("invalid epoch: {0}")]
+    InvalidEpoch(String),
+
+    #[error("error parsing query parameters: {0}")]
+    InvalidBindParams(String),
+
+    #[error("error parsing query: {0}")]
+    ParseQuery(String),
+
+    #[error(transparent)]
+    Body(#[from] BodyError),
+
+    #[error("authentication required")]
+    Unauthenticated,
+
+    #[error("access denied")]
+    Forbidden,
+
+    #[error("authorization failed: {0}")]
+    Authz(authz::Error),
+}
+
+impl Error {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::MissingQuery
+            | Self::InvalidParams(_)
+            | Self::InvalidEpoch(_)
+            | Self::InvalidBindParams(_)
+            | Self::ParseQuery(_)
+            | Self::Body(BodyError::Read(_)) => StatusCode::BAD_REQUEST,
+            Self::Body(BodyError::TooLarge(_)) => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::Unauthenticated => StatusCode::UNAUTHORIZED,
+            Self::Forbidden => StatusCode::FORBIDDEN,
+            Self::Authz(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// Return the error as a response with a body of the form
+    /// `{"error":"..."}`, as returned by InfluxDB 1.x.
+    fn into_response(self) -> Response<Body> {
+        let body = serde_json::json!({ "error": self.to_string() }).to_string();
+        Response::builder()
+            .status(self.status_code())
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .expect("valid response")
+    }
+}
+
+impl From<authz::Error> for Error {
+    fn from(err: authz::Error) -> Self {
+        match err {
+            authz::Error::NoToken => Self::Unauthenticated,
+            authz::Error::Forbidden | authz::Error::InvalidToken => Self::Forbidden,
+            err => Self::Authz(err),
+        }
+    }
+}
+
+/// The parameters of a `/query` request, from the URL query string or a
+/// form encoded request body.
+#[derive(Debug, Default, Deserialize)]
+struct QueryParams {
+    /// The database of the queried namespace.
+    db: Option<String>,
+    /// The retention policy of the queried namespace.
+    rp: Option<String>,
+    /// The InfluxQL statements to execute.
+    q: Option<String>,
+    /// The precision of integer timestamps, or RFC 3339 timestamps if unset.
+    epoch: Option<String>,
+    /// Stream the results in chunks.
+    chunked: Option<bool>,
+    /// The maximum number of rows of a chunk.
+    chunk_size: Option<usize>,
+    /// A JSON object of the values of the bind parameters of `q`.
+    params: Option<String>,
+    /// The authorization token, if not set in the `Authorization` header.
+    p: Option<String>,
+}
+
+impl QueryParams {
+    /// Return these parameters, overridden by those set in `other`.
+    fn merge(self, other: Self) -> Self {
+        Self {
+            db: other.db.or(self.db),
+            rp: other.rp.or(self.rp),
+            q: other.q.or(self.q),
+            epoch: other.epoch.or(self.epoch),
+            chunked: other.chunked.or(self.chunked),
+            chunk_size: other.chunk_size.or(self.chunk_size),
+            params: other.params.or(self.params),
+            p: other.p.or(self.p),
+        }
+    }
+}
+
+/// Handle a `/query` request.
+pub(super) async fn query<S>(
+    server: Arc<S>,
+    authz: Option<Arc<dyn Authorizer>>,
+    req: Request<Body>,
+) -> Response<Body>
+where
+    S: QueryNamespaceProvider,
+{
+    match try_query(server, authz, req).await {
+        Ok(response) => response,
+        Err(e) => {
+            info!(%e, "error handling /query request");
+            e.into_response()
+        }
+    }
+}
+
+async fn try_query<S>(
+    server: Arc<S>,
+    authz: Option<Arc<dyn Authorizer>>,
+    req: Request<Body>,
+) -> Result<Response<Body>, Error>
+where
+    S: QueryNamespaceProvider,
+{
+    let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
+    let external_span_ctx: Option<RequestLogContext> = req.extensions().get().cloned();
+    let header_token = extract_token(
+        req.extensions()
+            .get::<AuthorizationHeaderExtension>()
+            .and_then(|v| v.as_ref()),
+    );
+    let csv = req
+        .headers()
+        .get(ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.contains("application/csv") || v.contains("text/csv"))
+        .unwrap_or_default();
+
+    let params = read_params(req).await?;
+    let authz_token = header_token.or_else(|| params.p.clone().map(String::into_bytes));
+    let query = params.q.ok_or(Error::MissingQuery)?;
+    let time_format = match params.epoch.as_deref() {
+        Some(epoch) => TimeFormat::Epoch(
+            Precision::parse(epoch).ok_or_else(|| Error::InvalidEpoch(epoch.to_string()))?,
+        ),
+        // Timestamps are always integers in CSV.
+        None if csv => TimeFormat::Epoch(Precision::Nanoseconds),
+        None => TimeFormat::Rfc3339,
+    };
+    let chunked = params.chunked.unwrap_or_default();
+    let chunk_size = match chunked {
+        true => params
+            .chunk_size
+            .filter(|n| *n > 0)
+            .unwrap_or(DEFAULT_CHUNK_SIZE),
+        false => usize::MAX,
+    };
+    let bind_params = params
+        .params
+        .as_deref()
+        .map(parse_bind_params)
+        .transpose()?
+        .unwrap_or_default();
+    let namespace = params
+        .db
+        .filter(|db| !db.is_empty())
+        .map(|db| namespace_name(&db, params.rp.as_deref()));
+
+    let statements = parse_statements(&query)
+        .map_err(|e| Error::ParseQuery(e.to_string()))?
+        .into_iter()
+        .enumerate()
+        .map(|(id, statement)| Statement::new(id, statement.to_string(), &bind_params))
+        .collect::<VecDeque<_>>();
+
+    // All permissions required by the statements are checked up front, so
+    // that no statement is executed if any of them is denied.
+    let perms = statements
+        .iter()
+        .flat_map(|s| s.permissions(namespace.as_deref()))
+        .collect::<Vec<_>>();
+    // Queries of the namespace of the request only, which are not
+    // authorized to read the whole namespace, may still be granted access
+    // to the tables they read, which are only known once they are planned.
+    let table_scoped = namespace.is_some()
+        && statements
+            .iter()
+            .all(|s| s.is_table_scoped(namespace.as_deref()));
+    let mut restrict_tables = false;
+    if !perms.is_empty() {
+        let granted = match authz.permissions(authz_token.clone(), &perms).await {
+            Ok(granted) => granted,
+            Err(authz::Error::Forbidden) if table_scoped => vec![],
+            Err(e) => return Err(e.into()),
+        };
+        if perms.iter().any(|perm| !granted.contains(perm)) {
+            if !table_scoped {
+                return Err(Error::Forbidden);
+            }
+            restrict_tables = true;
+        }
+    }
+
+    let permit = server
+        .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+        .await;
+
+    info!(
+        namespace_name=namespace.as_deref().unwrap_or_default(),
+        %query,
+        trace=external_span_ctx.format_jaeger().as_str(),
+        "InfluxQL /query request",
+    );
+
+    let runner = Runner {
+        ctx: QueryContext {
+            server,
+            authz,
+            authz_token,
+            namespace,
+            restrict_tables,
+            params: bind_params,
+            chunk_size,
+            time_format,
+            span_ctx,
+            external_span_ctx,
+        },
+        statements,
+        running: None,
+        _permit: permit,
+    };
+    let results = futures::stream::unfold(runner, |mut runner| async move {
+        let results = runner.next_results().await?;
+        Some((futures::stream::iter(results), runner))
+    })
+    .flatten();
+
+    let content_type = if csv {
+        "application/csv"
+    } else {
+        "application/json"
+    };
+    let body = match (chunked, csv) {
+        (false, _) => {
+            let results = results.collect::<Vec<_>>().await;
+            if csv {
+                Body::from(CsvEncoder::default().encode(&results))
+            } else {
+                Body::from(json(&QueryResponse { results }))
+            }
+        }
+        // Each chunk is sent as soon as it is complete. The execution of the
+        // query stops if the client disconnects, as the body is dropped.
+        (true, true) => {
+            let mut encoder = CsvEncoder::default();
+            Body::wrap_stream(results.map(move |r| Ok::<_, Infallible>(encoder.encode(&[r]))))
+        }
+        (true, false) => Body::wrap_stream(
+            results.map(|r| Ok::<_, Infallible>(json(&QueryResponse { results: vec![r] }))),
+        ),
+    };
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(CONTENT_TYPE, content_type)
+        .body(body)
+        .expect("valid response"))
+}
+
+/// Read the parameters of `req` from its URL, and its body if it is a form
+/// encoded POST request.
+async fn read_params(req: Request<Body>) -> Result<QueryParams, Error> {
+    let params: QueryParams = serde_urlencoded::from_str(req.uri().query().unwrap_or_default())?;
+
+    let is_form = req
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.starts_with("application/x-www-form-urlencoded"))
+        .unwrap_or_default();
+    if req.method() != Method::POST || !is_form {
+        return Ok(params);
+    }
+
+    let form = read_body(req.into_body(), MAX_REQUEST_BYTES).await?;
+    Ok(params.merge(serde_urlencoded::from_bytes(&form)?))
+}
+
+/// Parse the JSON object of bind parameter values of the `params` parameter.
+fn parse_bind_params(params: &str) -> Result<StatementParams, Error> {
+    serde_json::from_str::<HashMap<String, serde_json::Value>>(params)
+        .map_err(|e| Error::InvalidBindParams(e.to_string()))?
+        .into_iter()
+        .map(|(name, value)| {
+            StatementParam::try_from(value)
+                .map(|value| (name, value))
+                .map_err(|e| Error::InvalidBindParams(e.to_string()))
+        })
+        .collect()
+}
+
+/// Serialize `response` as a line of JSON.
+fn json(response: &QueryResponse) -> String {
+    let mut s = serde_json::to_string(response).expect("results serialize to JSON");
+    s.push('\n');
+    s
+}
+
+/// A statement of a query.
+#[derive(Debug)]
+struct Statement {
+    id: usize,
+    text: String,
+    kind: Result<StatementKind, DataFusionError>,
+}
+
+#[derive(Debug)]
+enum StatementKind {
+    /// A statement which operates on the catalog.
+    Catalog(CatalogStatement),
+    /// A query, and the namespace it references through an `ON` clause or
+    /// qualified measurement names, if any.
+    Query(Option<String>),
+}
+
+impl Statement {
+    fn new(id: usize, text: String, params: &StatementParams) -> Self {
+        let planner = InfluxQLQueryPlanner::new();
+        let kind = planner
+            .catalog_statement(&text, params)
+            .and_then(|statement| match statement {
+                Some(statement) => Ok(StatementKind::Catalog(statement)),
+                None => planner.namespace(&text).map(StatementKind::Query),
+            });
+        Self { id, text, kind }
+    }
+
+    /// Returns true if the statement is a query which reads the namespace of
+    /// the request only, or is invalid.
+    fn is_table_scoped(&self, namespace: Option<&str>) -> bool {
+        match &self.kind {
+            Ok(StatementKind::Query(other)) => other.is_none() || other.as_deref() == namespace,
+            Ok(StatementKind::Catalog(_)) => false,
+            Err(_) => true,
+        }
+    }
+
+    /// Returns the permissions required to execute the statement against
+    /// the namespace of the request, which are those of the Flight API.
+    ///
+    /// A statement that is invalid, or has no namespace, requires none, as
+    /// it fails when it is executed.
+    fn permissions(&self, namespace: Option<&str>) -> Vec<authz::Permission> {
+        let read = |name: &str| {
+            authz::Permission::ResourceAction(
+                authz::Resource::Database(name.to_string()),
+                authz::Action::Read,
+            )
+        };
+        match (&self.kind, namespace) {
+            (
+                Ok(StatementKind::Catalog(
+                    CatalogStatement::Delete { .. } | CatalogStatement::DropMeasurement { .. },
+                )),
+                None,
+            ) => vec![],
+            (Ok(StatementKind::Catalog(statement)), namespace) => {
+                catalog_statement_permissions(namespace.unwrap_or_default(), statement)
+            }
+            (Ok(StatementKind::Query(other)), namespace) => namespace
+                .into_iter()
+                .chain(other.as_deref().filter(|other| Some(*other) != namespace))
+                .map(read)
+                .collect(),
+            (Err(_), _) => vec![],
+        }
+    }
+}
+
+/// The results of the statement currently being executed.
+struct Running {
+    statement_id: usize,
+    builder: SeriesBuilder,
+    stream: BoxStream<'static, Result<RecordBatch, DataFusionError>>,
+    token: Option<QueryCompletedToken>,
+}
+
+/// Executes the statements of a query in order, stopping at the first
+/// statement that fails.
+struct Runner<S> {
+    ctx: QueryContext<S>,
+    statements: VecDeque<Statement>,
+    running: Option<Running>,
+    _permit: InstrumentedAsyncOwnedSemaphorePermit,
+}
+
+impl<S> Runner<S>
+where
+    S: QueryNamespaceProvider,
+{
+    /// Return the next results, or `None` once all statements have been
+    /// executed.
+    async fn next_results(&mut self) -> Option<Vec<StatementResult>> {
+        loop {
+            if let Some(running) = &mut self.running {
+                match running.stream.next().await {
+                    Some(Ok(batch)) => match running.builder.push(&batch) {
+                        Ok(results) if results.is_empty() => continue,
+                        Ok(results) => return Some(results),
+                        Err(e) => return Some(vec![self.fail(e)]),
+                    },
+                    Some(Err(e)) => return Some(vec![self.fail(e)]),
+                    None => {
+                        let running = self.running.take().expect("running statement");
+                        if let Some(mut token) = running.token {
+                            token.set_success();
+                        }
+                        return Some(vec![running.builder.finish()]);
+                    }
+                }
+            }
+
+            let statement = self.statements.pop_front()?;
+            let statement_id = statement.id;
+            match self.ctx.start(statement).await {
+                Ok(running) => self.running = Some(running),
+                Err(e) => {
+                    self.statements.clear();
+                    self.ctx.log_error(&e);
+                    return Some(vec![StatementResult::error(statement_id, e)]);
+                }
+            }
+        }
+    }
+
+    /// Stop the execution of the query, as the running statement failed
+    /// with `e`, and return its result.
+    fn fail(&mut self, e: impl std::fmt::Display) -> StatementResult {
+        let running = self.running.take().expect("running statement");
+        if let Some(mut token) = running.token {
+            token.set_error(&e);
+        }
+        self.statements.clear();
+        self.ctx.log_error(&e);
+        StatementResult::error(running.statement_id, e)
+    }
+}
+
+/// The state of a query request needed to execute its statements.
+struct QueryContext<S> {
+    server: Arc<S>,
+    authz: Option<Arc<dyn Authorizer>>,
+    authz_token: Option<Vec<u8>>,
+    namespace: Option<String>,
+    /// The token is not authorized to read the whole namespace, and queries
+    /// may only read the tables it is granted access to.
+    restrict_tables: bool,
+    params: StatementParams,
+    chunk_size: usize,
+    time_format: TimeFormat,
+    span_ctx: Option<SpanContext>,
+    external_span_ctx: Option<RequestLogContext>,
+}
+
+impl<S> QueryContext<S>
+where
+    S: QueryNamespaceProvider,
+{
+    fn log_error(&self, e: &dyn std::fmt::Display) {
+        info!(
+            namespace_name=self.namespace.as_deref().unwrap_or_default(),
+            %e,
+            trace=self.external_span_ctx.format_jaeger().as_str(),
+            "Error executing InfluxQL statement via /query",
+        );
+    }
+
+    async fn start(&self, statement: Statement) -> Result<Running, DataFusionError> {
+        let builder = SeriesBuilder::new(statement.id, self.chunk_size, self.time_format);
+        let (stream, token) = match statement.kind? {
+            StatementKind::Catalog(catalog_statement) => {
+                let batch = self
+                    .run_catalog_statement(&statement.text, catalog_statement)
+                    .await?;
+                (futures::stream::iter([Ok(batch)]).boxed(), None)
+            }
+            StatementKind::Query(other) => {
+                let (stream, token) = self.run_query(statement.text, other).await?;
+                (stream, Some(token))
+            }
+        };
+        Ok(Running {
+            statement_id: statement.id,
+            builder,
+            stream,
+            token,
+        })
+    }
+
+    /// Plan and execute the InfluxQL query `query`, which may reference the
+    /// namespace `other`.
+    async fn run_query(
+        &self,
+        query: String,
+        other: Option<String>,
+    ) -> Result<
+        (
+            BoxStream<'static, Result<RecordBatch, DataFusionError>>,
+            QueryCompletedToken,
+        ),
+        DataFusionError,
+    > {
+        let namespace_name = self
+            .namespace
+            .as_ref()
+            .or(other.as_ref())
+            .ok_or_else(|| DataFusionError::Plan("database name required".to_string()))?;
+        let db = self
+            .server
+            .db(
+                namespace_name,
+                self.span_ctx.child_span("get namespace"),
+                false,
+            )
+            .await
+            // A token restricted to some tables is not told whether the
+            // namespace exists.
+            .ok_or_else(|| {
+                if self.restrict_tables {
+                    access_denied()
+                } else {
+                    database_not_found(namespace_name)
+                }
+            })?;
+
+        let ctx = db.new_query_context(self.span_ctx.clone());
+        let table_access = if self.restrict_tables {
+            let tables =
+                granted_tables(&self.authz, &ctx, namespace_name, self.authz_token.clone())
+                    .await
+                    .map_err(|e| match e {
+                        authz::Error::Forbidden => access_denied(),
+                        e => DataFusionError::External(Box::new(e)),
+                    })?;
+            Some(TableAccess::restrict(&ctx, tables))
+        } else {
+            None
+        };
+        let mut token = db.record_query(
+            self.external_span_ctx.as_ref().map(RequestLogContext::ctx),
+            "influxql",
+            Box::new(query.clone()),
+        );
+
+        let stream = async {
+            if let Some(other) = other.as_ref().filter(|other| *other != namespace_name) {
+                register_namespace(self.server.as_ref(), &ctx, other, false)
+                    .await
+                    .map_err(statement_error)?;
+            }
+            let plan = Planner::new(&ctx)
+                .influxql(query, self.params.clone())
+                .await;
+            // The tables are checked before any planning error is returned,
+            // as it may describe the tables which are not granted.
+            if table_access
+                .as_ref()
+                .is_some_and(|access| !access.denied_tables().is_empty())
+            {
+                return Err(access_denied());
+            }
+            let plan = plan?;
+            token.set_plan(&ctx, Arc::clone(&plan));
+            ctx.execute_stream(plan).await
+        }
+        .await;
+
+        match stream {
+            Ok(stream) => Ok((stream.boxed(), token)),
+            Err(e) => {
+                token.set_error(&e);
+                Err(e)
+            }
+        }
+    }
+
+    /// Execute a statement which operates on the catalog, and return its
+    /// result as a single record batch.
+    async fn run_catalog_statement(
+        &self,
+        query: &str,
+        statement: CatalogStatement,
+    ) -> Result<RecordBatch, DataFusionError> {
+        let namespace_name = match (&statement, self.namespace.as_deref()) {
+            (CatalogStatement::Delete { .. } | CatalogStatement::DropMeasurement { .. }, None) => {
+                return Err(DataFusionError::Plan("database name required".to_string()))
+            }
+            (_, namespace_name) => namespace_name.unwrap_or_default(),
+        };
+
+        execute_catalog_statement(
+            self.server.as_ref(),
+            &self.authz,
+            self.authz_token.clone(),
+            namespace_name,
+            query,
+            statement,
+        )
+        .await
+        .map_err(statement_error)
+    }
+}
+
+fn database_not_found(namespace_name: &str) -> DataFusionError {
+    DataFusionError::Plan(format!("database not found: {namespace_name}"))
+}
+
+/// Convert an error of the helpers shared with the Flight API into the error
+/// of a statement.
+fn statement_error(e: service_grpc_flight::Error) -> DataFusionError {
+    match e {
+        service_grpc_flight::Error::CatalogStatement { source, .. } => source,
+        service_grpc_flight::Error::DatabaseNotFound { namespace_name } => {
+            database_not_found(&namespace_name)
+        }
+        e => DataFusionError::External(Box::new(e)),
+    }
+}
+
+fn access_denied() -> DataFusionError {
+    DataFusionError::Plan("access denied".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use authz::Permission;
+    use iox_query::test::TestChunk;
+    use service_common::test_util::TestDatabaseStore;
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct MockAuthorizer {}
+
+    #[async_trait]
+    impl Authorizer for MockAuthorizer {
+        async fn permissions(
+            &self,
+            token: Option<Vec<u8>>,
+            perms: &[Permission],
+        ) -> Result<Vec<Permission>, authz::Error> {
+            match token.as_deref() {
+                Some(b"GOOD") => Ok(perms.to_vec()),
+                // Read access to the "visible" namespace only.
+                Some(b"VISIBLE") => Ok(perms
+                    .iter()
+                    .filter(|p| {
+                        matches!(p, Permission::ResourceAction(
+                            authz::Resource::Database(name), _) if name == "visible")
+                    })
+                    .cloned()
+                    .collect()),
+                Some(b"BAD") => Err(authz::Error::Forbidden),
+                // Grants access to the "cpu" table only.
+                Some(b"CPU") => {
+                    let granted = perms
+                        .iter()
+                        .filter(|perm| {
+                            matches!(
+                                perm,
+                                Permission::ResourceAction(authz::Resource::Table { table, .. }, _)
+                                    if table == "cpu"
+                            )
+                        })
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    if granted.is_empty() {
+                        return Err(authz::Error::Forbidden);
+                    }
+                    Ok(granted)
+                }
+                Some(_) => Err(authz::Error::InvalidToken),
+                None => Err(authz::Error::NoToken),
+            }
+        }
+    }
+
+    async fn test_store() -> Arc<TestDatabaseStore> {
+        let store = Arc::new(TestDatabaseStore::default());
+        store.db_or_create("bananas").await.add_chunk(
+            "partition",
+            Arc::new(
+                TestChunk::new("cpu")
+                    .with_time_column()
+                    .with_tag_column("host")
+                    .with_f64_field_column("usage")
+                    .with_one_row_of_data(),
+            ),
+        );
+        store.db_or_create("bananas").await.add_chunk(
+            "partition",
+            Arc::new(
+                TestChunk::new("mem")
+                    .with_time_column()
+                    .with_f64_field_column("used")
+                    .with_one_row_of_data(),
+            ),
+        );
+        store.db_or_create("visible").await;
+        store
+    }
+
+    async fn request(
+        store: &Arc<TestDatabaseStore>,
+        authz: Option<Arc<dyn Authorizer>>,
+        req: Request<Body>,
+    ) -> (StatusCode, String) {
+        let response = query(Arc::clone(store), authz, req).await;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    fn get(uri: &str) -> Request<Body> {
+        Request::get(uri).body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_select() {
+        let store = test_store().await;
+
+        let (status, body) = request(
+            &store,
+            None,
+            get("/query?db=bananas&q=SELECT+*+FROM+cpu&epoch=ns"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body,
+            "{\"results\":[{\"statement_id\":0,\"series\":[{\"name\":\"cpu\",\"columns\":[\"time\",\"host\",\"usage\"],\"values\":[[1000,\"MA\",99.5]]}]}]}\n"
+        );
+
+        let (status, body) = request(
+            &store,
+            None,
+            get("/query?db=bananas&q=SELECT+usage+FROM+cpu+GROUP+BY+host"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body,
+            "{\"results\":[{\"statement_id\":0,\"series\":[{\"name\":\"cpu\",\"tags\":{\"host\":\"MA\"},\"columns\":[\"time\",\"usage\"],\"values\":[[\"1970-01-01T00:00:00.000001Z\",99.5]]}]}]}\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_statements() {
+        let store = test_store().await;
+
+        // Statements after a failing statement are not executed.
+        let (status, body) = request(
+            &store,
+            None,
+            get("/query?db=bananas&q=SHOW+DATABASES;SELECT+*+FROM+nope.autogen.cpu;SHOW+DATABASES"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body,
+            "{\"results\":[\
+             {\"statement_id\":0,\"series\":[{\"name\":\"databases\",\"columns\":[\"name\"],\"values\":[[\"bananas\"],[\"visible\"]]}]},\
+             {\"statement_id\":1,\"error\":\"Error during planning: database not found: nope\"}\
+             ]}\n"
+        );
+
+        // Queries need a namespace.
+        let (status, body) = request(&store, None, get("/query?q=SELECT+*+FROM+cpu")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body,
+            "{\"results\":[{\"statement_id\":0,\"error\":\"Error during planning: database name required\"}]}\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_chunked() {
+        let store = test_store().await;
+
+        let (status, body) = request(
+            &store,
+            None,
+            get("/query?q=SHOW+DATABASES&chunked=true&chunk_size=1"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body,
+            "{\"results\":[{\"statement_id\":0,\"series\":[{\"name\":\"databases\",\"columns\":[\"name\"],\"values\":[[\"bananas\"]],\"partial\":true}],\"partial\":true}]}\n\
+             {\"results\":[{\"statement_id\":0,\"series\":[{\"name\":\"databases\",\"columns\":[\"name\"],\"values\":[[\"visible\"]]}]}]}\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_csv_form_post() {
+        let store = test_store().await;
+
+        let req = Request::post("/query?q=SELECT+*+FROM+cpu")
+            .header(ACCEPT, "application/csv")
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from("q=SHOW+DATABASES"))
+            .unwrap();
+        let (status, body) = request(&store, None, req).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body,
+            "name,tags,name\ndatabases,,bananas\ndatabases,,visible\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_errors() {
+        let store = test_store().await;
+
+        let (status, body) = request(&store, None, get("/query?db=bananas")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body, r#"{"error":"missing required parameter \"q\""}"#);
+
+        let (status, _) = request(&store, None, get("/query?q=SELEC")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = request(&store, None, get("/query?q=SHOW+DATABASES&epoch=y")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = request(&store, None, get("/query?q=SHOW+DATABASES&params=%5B%5D")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_authz() {
+        let store = test_store().await;
+        let authz = Some(Arc::new(MockAuthorizer {}) as Arc<dyn Authorizer>);
+
+        async fn code(
+            store: &Arc<TestDatabaseStore>,
+            authz: &Option<Arc<dyn Authorizer>>,
+            req: Request<Body>,
+        ) -> StatusCode {
+            request(store, authz.as_ref().map(Arc::clone), req).await.0
+        }
+
+        let select = "/query?db=bananas&q=SELECT+*+FROM+cpu";
+        assert_eq!(
+            code(&store, &authz, get(select)).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            code(&store, &authz, get(&format!("{select}&p=GOOD"))).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            code(&store, &authz, get(&format!("{select}&p=BAD"))).await,
+            StatusCode::FORBIDDEN
+        );
+
+        let req = Request::get(select)
+            .extension(AuthorizationHeaderExtension::new(Some(
+                "Token GOOD".parse().unwrap(),
+            )))
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(code(&store, &authz, req).await, StatusCode::OK);
+
+        // Every namespace referenced by the query must be readable.
+        assert_eq!(
+            code(
+                &store,
+                &authz,
+                get("/query?db=visible&q=SELECT+*+FROM+bananas..cpu&p=VISIBLE")
+            )
+            .await,
+            StatusCode::FORBIDDEN
+        );
+
+        // A token granted access to some tables only may query them.
+        let (status, body) = request(
+            &store,
+            authz.as_ref().map(Arc::clone),
+            get("/query?db=bananas&q=SELECT+*+FROM+cpu&epoch=ns&p=CPU"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body,
+            "{\"results\":[{\"statement_id\":0,\"series\":[{\"name\":\"cpu\",\"columns\":[\"time\",\"host\",\"usage\"],\"values\":[[1000,\"MA\",99.5]]}]}]}\n"
+        );
+        // Tables which are not granted are not visible.
+        let (status, body) = request(
+            &store,
+            authz.as_ref().map(Arc::clone),
+            get("/query?db=bananas&q=SELECT+*+FROM+mem&p=CPU"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "{\"results\":[{\"statement_id\":0}]}\n");
+        // The existence of the namespace is not revealed.
+        let (status, body) = request(
+            &store,
+            authz.as_ref().map(Arc::clone),
+            get("/query?db=nope&q=SELECT+*+FROM+cpu&p=CPU"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body,
+            "{\"results\":[{\"statement_id\":0,\"error\":\"Error during planning: access denied\"}]}\n"
+        );
+        // Other statements still need access to the whole namespace.
+        assert_eq!(
+            code(
+                &store,
+                &authz,
+                get("/query?db=bananas&q=DELETE+FROM+cpu&p=CPU")
+            )
+            .await,
+            StatusCode::FORBIDDEN
+        );
+
+        // Only readable namespaces are listed.
+        let (status, body) = request(
+            &store,
+            authz.as_ref().map(Arc::clone),
+            get("/query?q=SHOW+DATABASES&p=VISIBLE"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body,
+            "{\"results\":[{\"statement_id\":0,\"series\":[{\"name\":\"databases\",\"columns\":[\"name\"],\"values\":[[\"visible\"]]}]}]}\n"
+        );
+    }
+}
@@ -0,0 +1,620 @@
+//! Conversion of InfluxQL query results to the series of the InfluxDB 1.x
+//! HTTP query API, and their JSON and CSV encodings.
+
+use std::{collections::BTreeMap, ops::Not};
+
+use arrow::{
+    array::{
+        as_boolean_array, as_primitive_array, as_string_array, Array, ArrayRef, ArrowPrimitiveType,
+    },
+    compute::cast,
+    datatypes::{
+        DataType, Float64Type, Int64Type, SchemaRef, TimeUnit, TimestampNanosecondType, UInt64Type,
+    },
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use chrono::SecondsFormat;
+use generated_types::influxdata::iox::querier::v1::InfluxQlMetadata;
+use iox_time::Time;
+use serde::Serialize;
+use serde_json::Value;
+use thiserror::Error;
+
+/// Errors converting record batches to series.
+#[derive(Debug, Error)]
+pub(crate) enum Error {
+    #[error("missing InfluxQL metadata")]
+    MissingMetadata,
+
+    #[error("invalid InfluxQL metadata: {0}")]
+    InvalidMetadata(#[from] serde_json::Error),
+
+    #[error("error converting query results: {0}")]
+    Arrow(#[from] ArrowError),
+
+    #[error("unsupported data type of column {name}: {data_type}")]
+    UnsupportedDataType { name: String, data_type: DataType },
+}
+
+/// The precision of timestamps, selected with the `epoch` parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Precision {
+    Hours,
+    Minutes,
+    Seconds,
+    Milliseconds,
+    Microseconds,
+    Nanoseconds,
+}
+
+impl Precision {
+    /// Parse the value of the `epoch` parameter.
+    pub(crate) fn parse(epoch: &str) -> Option<Self> {
+        Some(match epoch {
+            "h" => Self::Hours,
+            "m" => Self::Minutes,
+            "s" => Self::Seconds,
+            "ms" => Self::Milliseconds,
+            "u" | "µ" => Self::Microseconds,
+            "ns" => Self::Nanoseconds,
+            _ => return None,
+        })
+    }
+
+    fn nanos(&self) -> i64 {
+        match self {
+            Self::Hours => 3_600_000_000_000,
+            Self::Minutes => 60_000_000_000,
+            Self::Seconds => 1_000_000_000,
+            Self::Milliseconds => 1_000_000,
+            Self::Microseconds => 1_000,
+            Self::Nanoseconds => 1,
+        }
+    }
+}
+
+/// How timestamps are rendered in the results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TimeFormat {
+    /// An RFC 3339 string with as many fractional digits as necessary,
+    /// which is the default of the JSON encoding.
+    Rfc3339,
+    /// An integer in the given precision since the Unix epoch.
+    Epoch(Precision),
+}
+
+impl TimeFormat {
+    fn format(&self, nanos: i64) -> Value {
+        match self {
+            Self::Rfc3339 => {
+                let s = Time::from_timestamp_nanos(nanos)
+                    .date_time()
+                    .to_rfc3339_opts(SecondsFormat::Nanos, true);
+                // Trim the trailing zeros of the fractional seconds, like
+                // the RFC3339Nano layout of InfluxDB 1.x.
+                let (datetime, fraction) = s.split_once('.').expect("fractional seconds");
+                let fraction = fraction.trim_end_matches(['0', 'Z']);
+                Value::String(if fraction.is_empty() {
+                    format!("{datetime}Z")
+                } else {
+                    format!("{datetime}.{fraction}Z")
+                })
+            }
+            Self::Epoch(precision) => Value::from(nanos.div_euclid(precision.nanos())),
+        }
+    }
+}
+
+/// The response of a query request that is not chunked.
+#[derive(Debug, Serialize)]
+pub(crate) struct QueryResponse {
+    pub(crate) results: Vec<StatementResult>,
+}
+
+/// The result of a statement, or part of it when the response is chunked.
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub(crate) struct StatementResult {
+    pub(crate) statement_id: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) series: Vec<Series>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) error: Option<String>,
+    /// Set when more results of the statement follow in later chunks.
+    #[serde(skip_serializing_if = "Not::not")]
+    pub(crate) partial: bool,
+}
+
+impl StatementResult {
+    /// The result of a statement that failed with `error`.
+    pub(crate) fn error(statement_id: usize, error: impl ToString) -> Self {
+        Self {
+            statement_id,
+            error: Some(error.to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+/// The rows of a statement result sharing a measurement and group by tag
+/// values.
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct Series {
+    pub(crate) name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) tags: Option<BTreeMap<String, String>>,
+    pub(crate) columns: Vec<String>,
+    pub(crate) values: Vec<Vec<Value>>,
+    /// Set when more rows of the series follow in the next chunk.
+    #[serde(skip_serializing_if = "Not::not")]
+    pub(crate) partial: bool,
+}
+
+/// The position of the columns of the record batches of a statement.
+#[derive(Debug)]
+struct Layout {
+    measurement: usize,
+    tag_keys: Vec<(String, usize)>,
+    columns: Vec<usize>,
+    column_names: Vec<String>,
+}
+
+impl Layout {
+    fn try_new(schema: &SchemaRef) -> Result<Self, Error> {
+        let md = schema
+            .metadata()
+            .get(schema::INFLUXQL_METADATA_KEY)
+            .ok_or(Error::MissingMetadata)?;
+        let md: InfluxQlMetadata = serde_json::from_str(md)?;
+
+        let measurement = md.measurement_column_index as usize;
+        let columns = (0..schema.fields().len())
+            .filter(|i| {
+                *i != measurement
+                    && !md
+                        .tag_key_columns
+                        .iter()
+                        .any(|tk| tk.column_index as usize == *i && !tk.is_projected)
+            })
+            .collect::<Vec<_>>();
+        let column_names = columns
+            .iter()
+            .map(|i| schema.field(*i).name().clone())
+            .collect();
+        let tag_keys = md
+            .tag_key_columns
+            .into_iter()
+            .map(|tk| (tk.tag_key, tk.column_index as usize))
+            .collect();
+
+        Ok(Self {
+            measurement,
+            tag_keys,
+            columns,
+            column_names,
+        })
+    }
+}
+
+/// Groups the rows of the record batches of a statement into series, and
+/// splits them into results of at most `chunk_size` rows.
+#[derive(Debug)]
+pub(crate) struct SeriesBuilder {
+    statement_id: usize,
+    chunk_size: usize,
+    time_format: TimeFormat,
+    layout: Option<Layout>,
+    /// The measurement and tag values of the last row.
+    key: Option<(String, Vec<String>)>,
+    series: Vec<Series>,
+    rows: usize,
+}
+
+impl SeriesBuilder {
+    pub(crate) fn new(statement_id: usize, chunk_size: usize, time_format: TimeFormat) -> Self {
+        Self {
+            statement_id,
+            chunk_size,
+            time_format,
+            layout: None,
+            key: None,
+            series: vec![],
+            rows: 0,
+        }
+    }
+
+    /// Add the rows of `batch`, and return the results that are complete.
+    ///
+    /// The last result is held back until the next row is known, so that
+    /// it can be marked as partial.
+    pub(crate) fn push(&mut self, batch: &RecordBatch) -> Result<Vec<StatementResult>, Error> {
+        let layout = match self.layout.take() {
+            Some(layout) => layout,
+            None => Layout::try_new(&batch.schema())?,
+        };
+        let columns = batch
+            .columns()
+            .iter()
+            .map(|array| match array.data_type() {
+                DataType::Dictionary(_, _) => cast(array, &DataType::Utf8),
+                _ => Ok(ArrayRef::clone(array)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let strings = |i: usize| as_string_array(&columns[i]);
+        let mut results = vec![];
+        for row in 0..batch.num_rows() {
+            let name = strings(layout.measurement).value(row);
+            let tag_values = layout
+                .tag_keys
+                .iter()
+                .map(|(_, i)| {
+                    let array = strings(*i);
+                    if array.is_null(row) {
+                        ""
+                    } else {
+                        array.value(row)
+                    }
+                })
+                .collect::<Vec<_>>();
+            let same_series = self
+                .key
+                .as_ref()
+                .map(|(n, t)| n == name && t.iter().eq(tag_values.iter().copied()))
+                .unwrap_or_default();
+
+            if self.rows == self.chunk_size {
+                if let Some(series) = self.series.last_mut() {
+                    series.partial = same_series;
+                }
+                results.push(StatementResult {
+                    statement_id: self.statement_id,
+                    series: std::mem::take(&mut self.series),
+                    error: None,
+                    partial: true,
+                });
+                self.rows = 0;
+            }
+
+            if !same_series || self.series.is_empty() {
+                let tags = (!layout.tag_keys.is_empty()).then(|| {
+                    layout
+                        .tag_keys
+                        .iter()
+                        .zip(&tag_values)
+                        .map(|((key, _), value)| (key.clone(), value.to_string()))
+                        .collect()
+                });
+                self.series.push(Series {
+                    name: name.to_string(),
+                    tags,
+                    columns: layout.column_names.clone(),
+                    values: vec![],
+                    partial: false,
+                });
+                self.key = Some((
+                    name.to_string(),
+                    tag_values.iter().map(|v| v.to_string()).collect(),
+                ));
+            }
+
+            let values = layout
+                .columns
+                .iter()
+                .zip(&layout.column_names)
+                .map(|(i, name)| value(name, &columns[*i], row, self.time_format))
+                .collect::<Result<Vec<_>, _>>()?;
+            self.series
+                .last_mut()
+                .expect("series of row")
+                .values
+                .push(values);
+            self.rows += 1;
+        }
+
+        self.layout = Some(layout);
+        Ok(results)
+    }
+
+    /// Return the last result of the statement.
+    pub(crate) fn finish(self) -> StatementResult {
+        StatementResult {
+            statement_id: self.statement_id,
+            series: self.series,
+            error: None,
+            partial: false,
+        }
+    }
+}
+
+/// Returns the value of `array` at `row`.
+fn value(
+    name: &str,
+    array: &ArrayRef,
+    row: usize,
+    time_format: TimeFormat,
+) -> Result<Value, Error> {
+    fn primitive<T: ArrowPrimitiveType>(array: &ArrayRef, row: usize) -> T::Native {
+        as_primitive_array::<T>(array).value(row)
+    }
+
+    if array.is_null(row) {
+        return Ok(Value::Null);
+    }
+    Ok(match array.data_type() {
+        DataType::Float64 => serde_json::Number::from_f64(primitive::<Float64Type>(array, row))
+            .map(Value::Number)
+            .unwrap_or(Value::Null),
+        DataType::Int64 => Value::from(primitive::<Int64Type>(array, row)),
+        DataType::UInt64 => Value::from(primitive::<UInt64Type>(array, row)),
+        DataType::Boolean => Value::Bool(as_boolean_array(array).value(row)),
+        DataType::Utf8 => Value::String(as_string_array(array).value(row).to_string()),
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+            time_format.format(primitive::<TimestampNanosecondType>(array, row))
+        }
+        data_type => {
+            return Err(Error::UnsupportedDataType {
+                name: name.to_string(),
+                data_type: data_type.clone(),
+            })
+        }
+    })
+}
+
+/// Encodes statement results in CSV, with a header for each change of the
+/// columns of the series.
+#[derive(Debug, Default)]
+pub(crate) struct CsvEncoder {
+    columns: Option<Vec<String>>,
+}
+
+impl CsvEncoder {
+    pub(crate) fn encode(&mut self, results: &[StatementResult]) -> String {
+        let mut out = String::new();
+        for result in results {
+            if let Some(error) = &result.error {
+                self.columns = None;
+                write_record(&mut out, ["error"]);
+                write_record(&mut out, [error.as_str()]);
+                continue;
+            }
+
+            for series in &result.series {
+                if self.columns.as_ref() != Some(&series.columns) {
+                    if self.columns.is_some() {
+                        out.push('\n');
+                    }
+                    write_record(
+                        &mut out,
+                        ["name", "tags"]
+                            .into_iter()
+                            .chain(series.columns.iter().map(String::as_str)),
+                    );
+                    self.columns = Some(series.columns.clone());
+                }
+
+                let tags = series
+                    .tags
+                    .iter()
+                    .flatten()
+                    .map(|(k, v)| format!("{k}={v}"))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                for values in &series.values {
+                    let values = values
+                        .iter()
+                        .map(|v| match v {
+                            Value::Null => String::new(),
+                            Value::String(s) => s.clone(),
+                            v => v.to_string(),
+                        })
+                        .collect::<Vec<_>>();
+                    write_record(
+                        &mut out,
+                        [series.name.as_str(), tags.as_str()]
+                            .into_iter()
+                            .chain(values.iter().map(String::as_str)),
+                    );
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Append a CSV record of `fields` to `out`, quoting fields as necessary.
+fn write_record<'a>(out: &mut String, fields: impl IntoIterator<Item = &'a str>) {
+    for (i, field) in fields.into_iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        if field.contains([',', '"', '\n', '\r']) {
+            out.push('"');
+            out.push_str(&field.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(field);
+        }
+    }
+    out.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use arrow::{
+        array::{DictionaryArray, Float64Array, StringArray, TimestampNanosecondArray},
+        datatypes::{Field, Int32Type, Schema},
+    };
+    use generated_types::influxdata::iox::querier::v1::influx_ql_metadata::TagKeyColumn;
+    use schema::TIME_DATA_TIMEZONE;
+
+    use super::*;
+
+    /// Returns a batch of `cpu` rows grouped by the `host` tag, which is
+    /// not projected.
+    fn batch(hosts: &[Option<&str>], times: &[i64], values: &[Option<f64>]) -> RecordBatch {
+        let md = InfluxQlMetadata {
+            measurement_column_index: 0,
+            tag_key_columns: vec![TagKeyColumn {
+                tag_key: "host".to_string(),
+                column_index: 1,
+                is_projected: false,
+            }],
+        };
+        let schema = Arc::new(Schema::new_with_metadata(
+            vec![
+                Field::new(
+                    "iox::measurement",
+                    DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+                    false,
+                ),
+                Field::new("host", DataType::Utf8, true),
+                Field::new(
+                    "time",
+                    DataType::Timestamp(TimeUnit::Nanosecond, TIME_DATA_TIMEZONE()),
+                    false,
+                ),
+                Field::new("usage", DataType::Float64, true),
+            ],
+            HashMap::from([(
+                schema::INFLUXQL_METADATA_KEY.to_owned(),
+                serde_json::to_string(&md).unwrap(),
+            )]),
+        ));
+        RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(
+                    DictionaryArray::<Int32Type>::try_new(
+                        vec![0; hosts.len()].into(),
+                        Arc::new(StringArray::from(vec!["cpu"])),
+                    )
+                    .unwrap(),
+                ),
+                Arc::new(StringArray::from(hosts.to_vec())),
+                Arc::new(
+                    TimestampNanosecondArray::from(times.to_vec())
+                        .with_timezone_opt(TIME_DATA_TIMEZONE()),
+                ),
+                Arc::new(Float64Array::from(values.to_vec())),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn encode(results: Vec<StatementResult>) -> String {
+        serde_json::to_string(&QueryResponse { results }).unwrap()
+    }
+
+    #[test]
+    fn test_series() {
+        let mut builder = SeriesBuilder::new(0, usize::MAX, TimeFormat::Rfc3339);
+        let batch = batch(
+            &[Some("a"), Some("a"), None],
+            &[0, 1_500_000_000, 60_000_000_000],
+            &[Some(1.5), None, Some(f64::NAN)],
+        );
+        assert!(builder.push(&batch).unwrap().is_empty());
+        let result = builder.finish();
+
+        assert_eq!(
+            encode(vec![result]),
+            r#"{"results":[{"statement_id":0,"series":[{"name":"cpu","tags":{"host":"a"},"columns":["time","usage"],"values":[["1970-01-01T00:00:00Z",1.5],["1970-01-01T00:00:01.5Z",null]]},{"name":"cpu","tags":{"host":""},"columns":["time","usage"],"values":[["1970-01-01T00:01:00Z",null]]}]}]}"#
+        );
+    }
+
+    #[test]
+    fn test_series_chunks() {
+        let mut builder =
+            SeriesBuilder::new(1, 2, TimeFormat::Epoch(Precision::parse("s").unwrap()));
+        let first = batch(
+            &[Some("a"), Some("a")],
+            &[1_000_000_000, 2_000_000_000],
+            &[Some(1.0), Some(2.0)],
+        );
+        let second = batch(
+            &[Some("a"), Some("b")],
+            &[3_000_000_000, 4_000_000_000],
+            &[Some(3.0), Some(4.0)],
+        );
+
+        // The first chunk is complete, but held back until the next row.
+        assert!(builder.push(&first).unwrap().is_empty());
+        let chunks = builder.push(&second).unwrap();
+        assert_eq!(
+            encode(chunks),
+            r#"{"results":[{"statement_id":1,"series":[{"name":"cpu","tags":{"host":"a"},"columns":["time","usage"],"values":[[1,1.0],[2,2.0]],"partial":true}],"partial":true}]}"#
+        );
+        assert_eq!(
+            encode(vec![builder.finish()]),
+            r#"{"results":[{"statement_id":1,"series":[{"name":"cpu","tags":{"host":"a"},"columns":["time","usage"],"values":[[3,3.0]]},{"name":"cpu","tags":{"host":"b"},"columns":["time","usage"],"values":[[4,4.0]]}]}]}"#
+        );
+    }
+
+    #[test]
+    fn test_empty_and_error_results() {
+        let builder = SeriesBuilder::new(0, 10, TimeFormat::Rfc3339);
+        assert_eq!(
+            encode(vec![
+                builder.finish(),
+                StatementResult::error(1, "database not found: foo")
+            ]),
+            r#"{"results":[{"statement_id":0},{"statement_id":1,"error":"database not found: foo"}]}"#
+        );
+    }
+
+    #[test]
+    fn test_missing_metadata() {
+        let batch = batch(&[Some("a")], &[0], &[Some(1.0)]);
+        let batch = RecordBatch::try_new(
+            Arc::new(Schema::new(batch.schema().fields().clone())),
+            batch.columns().to_vec(),
+        )
+        .unwrap();
+        let mut builder = SeriesBuilder::new(0, 10, TimeFormat::Rfc3339);
+        assert!(matches!(builder.push(&batch), Err(Error::MissingMetadata)));
+    }
+
+    #[test]
+    fn test_epoch() {
+        assert_eq!(Precision::parse("x"), None);
+        assert_eq!(
+            TimeFormat::Epoch(Precision::Milliseconds).format(1_500_999_999),
+            Value::from(1500)
+        );
+        assert_eq!(
+            TimeFormat::Epoch(Precision::Hours).format(-1),
+            Value::from(-1)
+        );
+        assert_eq!(
+            TimeFormat::Rfc3339.format(1_000),
+            Value::from("1970-01-01T00:00:00.000001Z")
+        );
+    }
+
+    #[test]
+    fn test_csv() {
+        let mut builder =
+            SeriesBuilder::new(0, usize::MAX, TimeFormat::Epoch(Precision::Nanoseconds));
+        builder
+            .push(&batch(
+                &[Some("a,b"), Some("c")],
+                &[1, 2],
+                &[Some(1.5), None],
+            ))
+            .unwrap();
+        let results = [builder.finish(), StatementResult::error(1, "boom")];
+
+        let mut encoder = CsvEncoder::default();
+        assert_eq!(
+            encoder.encode(&results),
+            "name,tags,time,usage\n\
+             cpu,\"host=a,b\",1,1.5\n\
+             cpu,host=c,2,\n\
+             error\n\
+             boom\n"
+        );
+    }
+}
@@ -0,0 +1,512 @@
+//! The `/api/v3/query_sql` API, which runs a SQL query against a namespace.
+//!
+//! The results are streamed to the client in one of the formats of
+//! [`QueryOutputFormat`] as the query executes. The execution of the query
+//! stops if the client disconnects, as the response body is dropped.
+
+use std::{
+    io::Write,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+use authz::{extract_token, http::AuthorizationHeaderExtension, Authorizer};
+use datafusion::{error::DataFusionError, physical_plan::SendableRecordBatchStream};
+use futures::StreamExt;
+use hyper::{header::CONTENT_TYPE, Body, Request, Response, StatusCode};
+use influxdb_iox_client::format::{BatchWriter, QueryOutputFormat};
+use iox_query::QueryCompletedToken;
+use observability_deps::tracing::info;
+use serde::Deserialize;
+use service_common::{
+    planner::Planner,
+    table_access::{granted_tables, TableAccess},
+    QueryNamespaceProvider,
+};
+use trace::{ctx::SpanContext, span::SpanExt};
+use trace_http::ctx::{RequestLogContext, RequestLogContextExt};
+use tracker::InstrumentedAsyncOwnedSemaphorePermit;
+
+use super::{read_body, Error};
+
+/// The maximum size of a request body.
+const MAX_REQUEST_BYTES: usize = 10 * 1024 * 1024;
+
+/// The JSON body of a `/api/v3/query_sql` request.
+#[derive(Debug, Deserialize)]
+struct QueryRequest {
+    /// The namespace to query.
+    db: String,
+    /// The SQL query.
+    query: String,
+    /// The output format of the results, JSON if unset.
+    format: Option<String>,
+}
+
+/// Handle a `/api/v3/query_sql` request.
+pub(super) async fn query_sql<S>(
+    server: Arc<S>,
+    authz: Option<Arc<dyn Authorizer>>,
+    req: Request<Body>,
+) -> Result<Response<Body>, Error>
+where
+    S: QueryNamespaceProvider,
+{
+    let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
+    let external_span_ctx: Option<RequestLogContext> = req.extensions().get().cloned();
+    let authz_token = extract_token(
+        req.extensions()
+            .get::<AuthorizationHeaderExtension>()
+            .and_then(|v| v.as_ref()),
+    );
+
+    let body = read_body(req.into_body(), MAX_REQUEST_BYTES).await?;
+    let QueryRequest { db, query, format } =
+        serde_json::from_slice(&body).map_err(Error::InvalidRequest)?;
+    let format = format
+        .as_deref()
+        .map(QueryOutputFormat::from_str)
+        .transpose()
+        .map_err(Error::InvalidFormat)?
+        .unwrap_or(QueryOutputFormat::Json);
+
+    let perms = [authz::Permission::ResourceAction(
+        authz::Resource::Database(db.clone()),
+        authz::Action::Read,
+    )];
+    // A token which is not authorized to read the whole namespace may still
+    // be granted access to the tables the query reads, which are only known
+    // once it is planned.
+    let restrict_tables = match authz.permissions(authz_token.clone(), &perms).await {
+        Ok(_) => false,
+        Err(authz::Error::Forbidden) => true,
+        Err(e) => return Err(e.into()),
+    };
+
+    // A token restricted to some tables is not told whether the namespace
+    // exists.
+    let namespace = server
+        .db(&db, span_ctx.child_span("get namespace"), false)
+        .await
+        .ok_or_else(|| {
+            if restrict_tables {
+                Error::Forbidden
+            } else {
+                Error::NamespaceNotFound(db.clone())
+            }
+        })?;
+
+    let permit = server
+        .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+        .await;
+
+    info!(
+        namespace_name=%db,
+        %query,
+        %format,
+        trace=external_span_ctx.format_jaeger().as_str(),
+        "SQL /api/v3/query_sql request",
+    );
+
+    let ctx = namespace.new_query_context(span_ctx);
+    let table_access = if restrict_tables {
+        let tables = granted_tables(&authz, &ctx, &db, authz_token).await?;
+        Some(TableAccess::restrict(&ctx, tables))
+    } else {
+        None
+    };
+    let mut token = namespace.record_query(
+        external_span_ctx.as_ref().map(RequestLogContext::ctx),
+        "sql",
+        Box::new(query.clone()),
+    );
+
+    let plan = Planner::new(&ctx).sql(query).await;
+    // The tables are checked before any planning error is returned, as it
+    // may describe the tables which are not granted.
+    if table_access.is_some_and(|access| !access.denied_tables().is_empty()) {
+        return Err(Error::Forbidden);
+    }
+    let plan = match plan {
+        Ok(plan) => plan,
+        Err(e) => {
+            token.set_error(&e);
+            return Err(Error::Planning(e));
+        }
+    };
+    token.set_plan(&ctx, Arc::clone(&plan));
+    let stream = match ctx.execute_stream(plan).await {
+        Ok(stream) => stream,
+        Err(e) => {
+            token.set_error(&e);
+            return Err(Error::Execution(e));
+        }
+    };
+
+    let buffer = SharedBuffer::default();
+    let writer = match format.writer(buffer.clone(), stream.schema()) {
+        Ok(writer) => writer,
+        Err(e) => {
+            token.set_error(&e);
+            return Err(Error::Format(e));
+        }
+    };
+
+    let results = ResultStream {
+        stream,
+        writer: Some(writer),
+        buffer,
+        token,
+        _permit: permit,
+    };
+    let body = futures::stream::unfold(results, |mut results| async move {
+        let bytes = results.next_bytes().await?;
+        Some((bytes, results))
+    });
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(CONTENT_TYPE, format.content_type())
+        .body(Body::wrap_stream(body))
+        .expect("valid response"))
+}
+
+/// A buffer the [`BatchWriter`] of a response writes to, which is drained
+/// into the response body as the results are written.
+#[derive(Debug, Default, Clone)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    fn take(&self) -> Vec<u8> {
+        std::mem::take(&mut *self.0.lock().expect("not poisoned"))
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().expect("not poisoned").extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The results of a query, written in the output format of the request.
+///
+/// The query holds its permit of the query semaphore until the results are
+/// dropped. If they are dropped before the last record batch is written,
+/// the query is recorded as failed.
+struct ResultStream {
+    stream: SendableRecordBatchStream,
+    writer: Option<BatchWriter<SharedBuffer>>,
+    buffer: SharedBuffer,
+    token: QueryCompletedToken,
+    _permit: InstrumentedAsyncOwnedSemaphorePermit,
+}
+
+impl ResultStream {
+    /// Return the next part of the response body, or `None` once all the
+    /// results have been written.
+    async fn next_bytes(&mut self) -> Option<Result<Vec<u8>, DataFusionError>> {
+        loop {
+            let writer = self.writer.as_mut()?;
+            let written = match self.stream.next().await {
+                Some(Ok(batch)) => writer.write(&batch),
+                Some(Err(e)) => return Some(Err(self.fail(e))),
+                None => {
+                    let writer = self.writer.take().expect("writer");
+                    let written = writer.finish().map(|_| ());
+                    if written.is_ok() {
+                        self.token.set_success();
+                    }
+                    written
+                }
+            };
+            if let Err(e) = written {
+                return Some(Err(self.fail(DataFusionError::External(Box::new(e)))));
+            }
+
+            let bytes = self.buffer.take();
+            if !bytes.is_empty() {
+                return Some(Ok(bytes));
+            }
+        }
+    }
+
+    /// Stop writing results, as the query failed with `e`, and return `e`.
+    ///
+    /// The response is aborted, as its status has already been sent.
+    fn fail(&mut self, e: DataFusionError) -> DataFusionError {
+        info!(%e, "error executing SQL /api/v3/query_sql request");
+        self.token.set_error(&e);
+        self.writer = None;
+        e
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use arrow::ipc::reader::FileReader;
+    use async_trait::async_trait;
+    use authz::Permission;
+    use iox_query::test::TestChunk;
+    use ioxd_common::http::error::HttpApiErrorCode;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use service_common::test_util::TestDatabaseStore;
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct MockAuthorizer {}
+
+    #[async_trait]
+    impl Authorizer for MockAuthorizer {
+        async fn permissions(
+            &self,
+            token: Option<Vec<u8>>,
+            perms: &[Permission],
+        ) -> Result<Vec<Permission>, authz::Error> {
+            match token.as_deref() {
+                Some(b"GOOD") => Ok(perms.to_vec()),
+                Some(b"BAD") => Err(authz::Error::Forbidden),
+                // Grants access to the "cpu" table only.
+                Some(b"CPU") => {
+                    let granted = perms
+                        .iter()
+                        .filter(|perm| {
+                            matches!(
+                                perm,
+                                Permission::ResourceAction(authz::Resource::Table { table, .. }, _)
+                                    if table == "cpu"
+                            )
+                        })
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    if granted.is_empty() {
+                        return Err(authz::Error::Forbidden);
+                    }
+                    Ok(granted)
+                }
+                Some(_) => Err(authz::Error::InvalidToken),
+                None => Err(authz::Error::NoToken),
+            }
+        }
+    }
+
+    async fn test_store(store: TestDatabaseStore) -> Arc<TestDatabaseStore> {
+        store.db_or_create("bananas").await.add_chunk(
+            "partition",
+            Arc::new(
+                TestChunk::new("cpu")
+                    .with_time_column()
+                    .with_tag_column("host")
+                    .with_f64_field_column("usage")
+                    .with_one_row_of_data(),
+            ),
+        );
+        Arc::new(store)
+    }
+
+    fn post(body: &str) -> Request<Body> {
+        Request::post("/api/v3/query_sql")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    fn select(format: &str) -> Request<Body> {
+        post(&format!(
+            r#"{{"db":"bananas","query":"SELECT host, usage, time FROM cpu","format":"{format}"}}"#
+        ))
+    }
+
+    async fn request(
+        store: &Arc<TestDatabaseStore>,
+        authz: Option<Arc<dyn Authorizer>>,
+        req: Request<Body>,
+    ) -> Result<(String, Vec<u8>), Error> {
+        let response = query_sql(Arc::clone(store), authz, req).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let content_type = response.headers()[CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        Ok((content_type, body.to_vec()))
+    }
+
+    const EXPECTED_JSON: &str =
+        r#"[{"host":"MA","usage":99.5,"time":"1970-01-01T00:00:00.000001"}]"#;
+
+    #[tokio::test]
+    async fn test_text_formats() {
+        let store = test_store(TestDatabaseStore::default()).await;
+
+        let (content_type, body) = request(
+            &store,
+            None,
+            post(r#"{"db":"bananas","query":"SELECT host, usage, time FROM cpu"}"#),
+        )
+        .await
+        .unwrap();
+        assert_eq!(content_type, "application/json");
+        assert_eq!(String::from_utf8(body).unwrap(), EXPECTED_JSON);
+
+        let (content_type, body) = request(&store, None, select("ndjson")).await.unwrap();
+        assert_eq!(content_type, "application/x-ndjson");
+        assert_eq!(
+            String::from_utf8(body).unwrap(),
+            "{\"host\":\"MA\",\"usage\":99.5,\"time\":\"1970-01-01T00:00:00.000001\"}\n"
+        );
+
+        let (content_type, body) = request(&store, None, select("csv")).await.unwrap();
+        assert_eq!(content_type, "text/csv");
+        let body = String::from_utf8(body).unwrap();
+        assert!(
+            body.starts_with("host,usage,time\nMA,99.5,1970-01-01T00:00:00.000001"),
+            "{body}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_binary_formats() {
+        let store = test_store(TestDatabaseStore::default()).await;
+
+        let (content_type, body) = request(&store, None, select("parquet")).await.unwrap();
+        assert_eq!(content_type, "application/vnd.apache.parquet");
+        let batches = ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(body))
+            .unwrap()
+            .build()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            QueryOutputFormat::Json.format(&batches).unwrap(),
+            EXPECTED_JSON
+        );
+
+        let (content_type, body) = request(&store, None, select("arrow")).await.unwrap();
+        assert_eq!(content_type, "application/vnd.apache.arrow.file");
+        let batches = FileReader::try_new(std::io::Cursor::new(body), None)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            QueryOutputFormat::Json.format(&batches).unwrap(),
+            EXPECTED_JSON
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_errors() {
+        let store = test_store(TestDatabaseStore::default()).await;
+
+        async fn code(store: &Arc<TestDatabaseStore>, req: Request<Body>) -> HttpApiErrorCode {
+            request(store, None, req).await.unwrap_err().status_code()
+        }
+
+        assert_eq!(
+            code(&store, post("SELECT 1")).await,
+            HttpApiErrorCode::Invalid
+        );
+        assert_eq!(
+            code(&store, post(r#"{"db":"bananas"}"#)).await,
+            HttpApiErrorCode::Invalid
+        );
+        assert_eq!(code(&store, select("xml")).await, HttpApiErrorCode::Invalid);
+        assert_eq!(
+            code(
+                &store,
+                post(r#"{"db":"bananas","query":"SELECT * FROM nope"}"#)
+            )
+            .await,
+            HttpApiErrorCode::Invalid
+        );
+        assert_eq!(
+            code(&store, post(r#"{"db":"nope","query":"SELECT 1"}"#)).await,
+            HttpApiErrorCode::NotFound
+        );
+    }
+
+    #[tokio::test]
+    async fn test_authz() {
+        let store = test_store(TestDatabaseStore::default()).await;
+        let authz = Some(Arc::new(MockAuthorizer {}) as Arc<dyn Authorizer>);
+
+        let with_query_token = |mut req: Request<Body>, token: &str| {
+            req.extensions_mut()
+                .insert(AuthorizationHeaderExtension::new(Some(
+                    format!("Token {token}").parse().unwrap(),
+                )));
+            req
+        };
+        let with_token = |token: &str| with_query_token(select("json"), token);
+
+        let err = request(&store, authz.as_ref().map(Arc::clone), select("json"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code(), HttpApiErrorCode::Unauthorized);
+        let err = request(&store, authz.as_ref().map(Arc::clone), with_token("BAD"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code(), HttpApiErrorCode::Forbidden);
+        request(&store, authz.as_ref().map(Arc::clone), with_token("GOOD"))
+            .await
+            .unwrap();
+
+        // A token granted access to the queried tables only.
+        request(&store, authz.as_ref().map(Arc::clone), with_token("CPU"))
+            .await
+            .unwrap();
+        let err = request(
+            &store,
+            authz.as_ref().map(Arc::clone),
+            with_query_token(
+                post(r#"{"db":"bananas","query":"SELECT * FROM cpu, nope"}"#),
+                "CPU",
+            ),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status_code(), HttpApiErrorCode::Forbidden);
+
+        // A token granted access to some tables only is not told whether
+        // the namespace exists.
+        let missing = || post(r#"{"db":"nope","query":"SELECT 1"}"#);
+        let err = request(
+            &store,
+            authz.as_ref().map(Arc::clone),
+            with_query_token(missing(), "GOOD"),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status_code(), HttpApiErrorCode::NotFound);
+        let err = request(&store, authz, with_query_token(missing(), "CPU"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code(), HttpApiErrorCode::Forbidden);
+    }
+
+    #[tokio::test]
+    async fn test_max_concurrent_queries() {
+        let store = test_store(TestDatabaseStore::new_with_semaphore_size(1)).await;
+
+        // The permit of a query is held until its response body is dropped,
+        // whether or not it has been read to the end.
+        let response = query_sql(Arc::clone(&store), None, select("json"))
+            .await
+            .unwrap();
+        let blocked = tokio::time::timeout(
+            Duration::from_millis(100),
+            query_sql(Arc::clone(&store), None, select("json")),
+        )
+        .await;
+        assert!(blocked.is_err());
+
+        drop(response);
+        request(&store, None, select("json")).await.unwrap();
+    }
+}
@@ -23,7 +23,7 @@ use service_grpc_schema::SchemaService;
 use workspace_hack as _;
 
 use async_trait::async_trait;
-use authz::{Authorizer, IoxAuthorizer};
+use authz::{Authorizer, CatalogAuthorizer, IoxAuthorizer, DEFAULT_CACHE_TTL};
 use clap_blocks::querier::QuerierConfig;
 use datafusion_util::config::register_iox_object_store;
 use hyper::{Body, Request, Response};
@@ -32,7 +32,7 @@ use iox_query::exec::{Executor, ExecutorType};
 use iox_time::TimeProvider;
 use ioxd_common::{
     add_service,
-    http::error::{HttpApiError, HttpApiErrorCode, HttpApiErrorSource},
+    http::error::HttpApiErrorSource,
     rpc::RpcBuilderInput,
     serve_builder,
     server_type::{CommonServerState, RpcError, ServerType},
@@ -40,16 +40,17 @@ use ioxd_common::{
 };
 use metric::Registry;
 use object_store::{DynObjectStore, ObjectStore};
-use querier::{create_ingester_connections, QuerierCatalogCache, QuerierDatabase, QuerierServer};
-use std::{
-    fmt::{Debug, Display},
-    sync::Arc,
+use querier::{
+    create_ingester_connections, GrpcRouterConnection, QuerierCatalogCache, QuerierDatabase,
+    QuerierDiskCacheConfig, QuerierServer, QueryLogNamespaceWriter, QueryLogSink,
 };
+use std::{fmt::Debug, sync::Arc};
 use thiserror::Error;
 use tokio::runtime::Handle;
 use tokio_util::sync::CancellationToken;
 use trace::TraceCollector;
 
+mod http;
 mod rpc;
 
 pub struct QuerierServerType {
@@ -60,6 +61,7 @@ pub struct QuerierServerType {
     object_store: Arc<dyn ObjectStore>,
     trace_collector: Option<Arc<dyn TraceCollector>>,
     authz: Option<Arc<dyn Authorizer>>,
+    http: http::HttpApi<QuerierDatabase>,
 }
 
 impl std::fmt::Debug for QuerierServerType {
@@ -85,12 +87,15 @@ impl ServerType for QuerierServerType {
         self.trace_collector.as_ref().map(Arc::clone)
     }
 
-    /// Just return "not found".
+    /// Dispatches `req` to the querier HTTP API.
     async fn route_http_request(
         &self,
-        _req: Request<Body>,
+        req: Request<Body>,
     ) -> Result<Response<Body>, Box<dyn HttpApiErrorSource>> {
-        Err(Box::new(IoxHttpError::NotFound))
+        self.http
+            .route(req)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn HttpApiErrorSource>)
     }
 
     /// Configure the gRPC services.
@@ -142,34 +147,6 @@ impl ServerType for QuerierServerType {
     }
 }
 
-/// Simple error struct, we're not really providing an HTTP interface for the compactor.
-#[derive(Debug)]
-pub enum IoxHttpError {
-    NotFound,
-}
-
-impl IoxHttpError {
-    fn status_code(&self) -> HttpApiErrorCode {
-        match self {
-            Self::NotFound => HttpApiErrorCode::NotFound,
-        }
-    }
-}
-
-impl Display for IoxHttpError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{self:?}")
-    }
-}
-
-impl std::error::Error for IoxHttpError {}
-
-impl HttpApiErrorSource for IoxHttpError {
-    fn to_http_api_error(&self) -> HttpApiError {
-        HttpApiError::new(self.status_code(), self.to_string())
-    }
-}
-
 /// Arguments required to create a [`ServerType`] for the querier.
 #[derive(Debug)]
 pub struct QuerierServerTypeArgs<'a> {
@@ -193,6 +170,18 @@ pub enum Error {
         source: Box<dyn std::error::Error>,
         addr: String,
     },
+
+    #[error("cannot connect to query log sink router '{addr}': {source}")]
+    QueryLogSinkConnection {
+        source: client_util::connection::Error,
+        addr: String,
+    },
+
+    #[error("invalid router address '{addr}': {source}")]
+    RouterConnection {
+        source: client_util::connection::Error,
+        addr: String,
+    },
 }
 
 /// Instantiate a querier server
@@ -206,6 +195,13 @@ pub async fn create_querier_server_type(
         Arc::clone(&args.object_store),
         args.querier_config.ram_pool_metadata_bytes.bytes(),
         args.querier_config.ram_pool_data_bytes.bytes(),
+        args.querier_config
+            .disk_cache_directory
+            .clone()
+            .map(|directory| QuerierDiskCacheConfig {
+                directory,
+                size_bytes: args.querier_config.disk_cache_bytes,
+            }),
         &Handle::current(),
     ));
 
@@ -235,6 +231,10 @@ pub async fn create_querier_server_type(
 
             Some(authz)
         }
+        None if args.querier_config.authz_catalog => Some(Arc::new(CatalogAuthorizer::new(
+            Arc::clone(&args.catalog),
+            DEFAULT_CACHE_TTL,
+        )) as Arc<dyn Authorizer>),
         None => None,
     };
 
@@ -256,21 +256,59 @@ pub async fn create_querier_server_type(
         ))
     };
 
-    let database = Arc::new(
-        QuerierDatabase::new(
-            catalog_cache,
-            Arc::clone(&args.metric_registry),
-            args.exec,
-            ingester_connections,
-            args.querier_config.max_concurrent_queries,
-            Arc::new(args.querier_config.datafusion_config),
-        )
-        .await?,
-    );
+    let query_log_sink = match (
+        &args.querier_config.query_log_sink_router_address,
+        &args.querier_config.query_log_sink_namespace,
+    ) {
+        (Some(addr), Some(namespace)) => {
+            let connection = client_util::connection::Builder::new()
+                .build(addr.as_str())
+                .await
+                .map_err(|source| Error::QueryLogSinkConnection {
+                    source,
+                    addr: addr.clone(),
+                })?;
+            let writer = QueryLogNamespaceWriter::new(connection, namespace.clone());
+            Some(QueryLogSink::new(Arc::new(writer), &args.metric_registry))
+        }
+        _ => None,
+    };
+
+    let router_connection = match &args.querier_config.router_grpc_address {
+        Some(addr) => {
+            // The router may not be up yet, e.g. in all-in-one mode.
+            let connection = client_util::connection::Builder::new()
+                .build_lazy(addr.as_str())
+                .map_err(|source| Error::RouterConnection {
+                    source,
+                    addr: addr.clone(),
+                })?;
+            Some(GrpcRouterConnection::new(connection))
+        }
+        None => None,
+    };
+
+    let mut database = QuerierDatabase::new(
+        catalog_cache,
+        Arc::clone(&args.metric_registry),
+        args.exec,
+        ingester_connections,
+        args.querier_config.max_concurrent_queries,
+        Arc::new(args.querier_config.datafusion_config),
+    )
+    .await?;
+    if let Some(sink) = query_log_sink {
+        database = database.with_query_log_sink(sink);
+    }
+    if let Some(router_connection) = router_connection {
+        database = database.with_router_connection(Arc::new(router_connection));
+    }
+    let database = Arc::new(database);
 
     let server = QuerierServer::new(Arc::clone(&database));
     Ok(Arc::new(QuerierServerType {
         catalog: args.catalog,
+        http: http::HttpApi::new(Arc::clone(&database), authz.as_ref().map(Arc::clone)),
         database,
         server,
         metric_registry: args.metric_registry,
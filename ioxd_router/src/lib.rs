@@ -25,7 +25,9 @@ use std::{
 };
 
 use async_trait::async_trait;
-use authz::{Authorizer, AuthorizerInstrumentation, IoxAuthorizer};
+use authz::{
+    Authorizer, AuthorizerInstrumentation, CatalogAuthorizer, IoxAuthorizer, DEFAULT_CACHE_TTL,
+};
 use clap_blocks::{gossip::GossipConfig, router::RouterConfig};
 use data_types::NamespaceName;
 use hashbrown::HashMap;
@@ -37,6 +39,7 @@ use ioxd_common::{
     reexport::{
         generated_types::influxdata::iox::{
             catalog::v1::catalog_service_server,
+            delete::v1::delete_service_server,
             gossip::{v1::anti_entropy_service_server, Topic},
             namespace::v1::namespace_service_server,
             object_store::v1::object_store_service_server,
@@ -203,6 +206,10 @@ where
             builder,
             table_service_server::TableServiceServer::new(self.server.grpc().table_service())
         );
+        add_service!(
+            builder,
+            delete_service_server::DeleteServiceServer::new(self.server.grpc().delete_service())
+        );
         add_service!(
             builder,
             anti_entropy_service_server::AntiEntropyServiceServer::new(
@@ -244,6 +251,13 @@ impl HttpApiErrorSource for IoxHttpErrorAdaptor {
     fn to_http_api_error(&self) -> HttpApiError {
         HttpApiError::new(self.0.as_status_code(), self.to_string())
             .with_line(self.0.get_parse_error_line_index())
+            .with_rejected_lines(
+                self.0
+                    .rejected_lines()
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|r| (r.line, r.reason.clone())),
+            )
     }
 }
 
@@ -413,8 +427,9 @@ pub async fn create_router_server_type(
     let write_request_unifier: Result<Box<dyn WriteRequestUnifier>> = match (
         router_config.single_tenant_deployment,
         &router_config.authz_address,
+        router_config.authz_catalog,
     ) {
-        (true, Some(addr)) => {
+        (true, Some(addr), false) => {
             let authz = IoxAuthorizer::connect_lazy(addr.clone())
                 .map(|c| {
                     Arc::new(AuthorizerInstrumentation::new(&metrics, c)) as Arc<dyn Authorizer>
@@ -427,18 +442,30 @@ pub async fn create_router_server_type(
 
             Ok(Box::new(SingleTenantRequestUnifier::new(authz)))
         }
-        (true, None) => {
+        (true, None, true) => {
+            let authz = Arc::new(AuthorizerInstrumentation::new(
+                &metrics,
+                CatalogAuthorizer::new(Arc::clone(&catalog), DEFAULT_CACHE_TTL),
+            )) as Arc<dyn Authorizer>;
+
+            Ok(Box::new(SingleTenantRequestUnifier::new(authz)))
+        }
+        (true, None, false) => {
             // Single tenancy was requested, but no auth was provided - the
             // router's clap flag parse configuration should not allow this
             // combination to be accepted and therefore execution should
             // never reach here.
             unreachable!("INFLUXDB_IOX_SINGLE_TENANCY is set, but could not create an authz service. Check the INFLUXDB_IOX_AUTHZ_ADDR")
         }
-        (false, None) => Ok(Box::<MultiTenantRequestUnifier>::default()),
-        (false, Some(_)) => {
+        (true, Some(_), true) => {
+            // As above, the two authorizers are mutually exclusive flags.
+            unreachable!("both INFLUXDB_IOX_AUTHZ_ADDR and INFLUXDB_IOX_AUTHZ_CATALOG are set")
+        }
+        (false, None, false) => Ok(Box::<MultiTenantRequestUnifier>::default()),
+        (false, _, _) => {
             // As above, this combination should be prevented by the
             // router's clap flag parse configuration.
-            unreachable!("INFLUXDB_IOX_AUTHZ_ADDR is set, but authz only exists for single_tenancy. Check the INFLUXDB_IOX_SINGLE_TENANCY")
+            unreachable!("INFLUXDB_IOX_AUTHZ_ADDR or INFLUXDB_IOX_AUTHZ_CATALOG is set, but authz only exists for single_tenancy. Check the INFLUXDB_IOX_SINGLE_TENANCY")
         }
     };
     let http = HttpDelegate::new(
@@ -448,7 +475,8 @@ pub async fn create_router_server_type(
         handler_stack,
         &metrics,
         write_request_unifier?,
-    );
+    )
+    .with_delete_catalog(Arc::clone(&catalog));
 
     // Initialize the gRPC API delegate that creates the services relevant to the RPC
     // write router path and use it to create the relevant `RpcWriteRouterServer` and
@@ -10,9 +10,10 @@ mod strftime;
 use std::{borrow::Cow, ops::Range};
 
 use data_types::partition_template::{
-    TablePartitionTemplateOverride, TemplatePart, ENCODED_PARTITION_KEY_CHARS,
-    MAXIMUM_NUMBER_OF_TEMPLATE_PARTS, PARTITION_KEY_DELIMITER, PARTITION_KEY_MAX_PART_LEN,
-    PARTITION_KEY_PART_TRUNCATED, PARTITION_KEY_VALUE_EMPTY_STR, PARTITION_KEY_VALUE_NULL_STR,
+    bucket_for_tag_value, TablePartitionTemplateOverride, TemplatePart,
+    ENCODED_PARTITION_KEY_CHARS, MAXIMUM_NUMBER_OF_TEMPLATE_PARTS, PARTITION_KEY_DELIMITER,
+    PARTITION_KEY_MAX_PART_LEN, PARTITION_KEY_PART_TRUNCATED, PARTITION_KEY_VALUE_EMPTY_STR,
+    PARTITION_KEY_VALUE_NULL_STR,
 };
 use percent_encoding::utf8_percent_encode;
 use schema::{InfluxColumnType, TIME_COLUMN_NAME};
@@ -40,6 +41,11 @@ pub enum PartitionKeyError {
     #[error("tag value partitioner does not accept input columns of type {0:?}")]
     TagValueNotTag(InfluxColumnType),
 
+    /// The partition template defines a [`TagFormat::Bucket`] part with zero
+    /// buckets.
+    #[error("invalid number of buckets in partition template")]
+    InvalidNumberOfBuckets,
+
     /// A "catch all" error for when a formatter returns [`std::fmt::Error`],
     /// which contains no context.
     #[error("partition key generation error")]
@@ -75,13 +81,27 @@ pub fn partition_batch<'a>(
 #[derive(Debug)]
 #[allow(clippy::large_enum_variant)]
 enum Template<'a> {
-    TagValue(&'a Column, Option<i32>),
+    TagValue(&'a Column, Option<i32>, TagFormat),
     TimeFormat(&'a [i64], StrftimeFormatter<'a>),
 
     /// This batch is missing a partitioning tag column.
     MissingTag,
 }
 
+/// The rendering of the value of a tag column by a [`Template::TagValue`].
+#[derive(Debug, Clone, Copy)]
+enum TagFormat {
+    /// The full tag value.
+    Value,
+
+    /// At most the specified number of leading graphemes of the tag value.
+    Prefix(usize),
+
+    /// The bucket the tag value hashes into, out of the specified number of
+    /// buckets.
+    Bucket(u32),
+}
+
 impl<'a> Template<'a> {
     /// Renders this template to `out` for the row `idx`.
     fn fmt_row<W: std::fmt::Write>(
@@ -90,7 +110,7 @@ impl<'a> Template<'a> {
         idx: usize,
     ) -> Result<(), PartitionKeyError> {
         match self {
-            Template::TagValue(col, last_key) if col.valid.get(idx) => match &col.data {
+            Template::TagValue(col, last_key, format) if col.valid.get(idx) => match &col.data {
                 ColumnData::Tag(col_data, dictionary, _) => {
                     let this_key = col_data[idx];
 
@@ -98,16 +118,26 @@ impl<'a> Template<'a> {
                     // potentially different key.
                     *last_key = Some(this_key);
 
-                    out.write_str(
-                        encode_key_part(dictionary.lookup_id(this_key).unwrap()).as_ref(),
-                    )?
+                    let value = dictionary.lookup_id(this_key).unwrap();
+                    match *format {
+                        TagFormat::Value => out.write_str(encode_key_part(value).as_ref())?,
+                        TagFormat::Prefix(len) => {
+                            out.write_str(encode_key_prefix(value, len).as_ref())?
+                        }
+                        TagFormat::Bucket(0) => {
+                            return Err(PartitionKeyError::InvalidNumberOfBuckets)
+                        }
+                        TagFormat::Bucket(num_buckets) => {
+                            write!(out, "{}", bucket_for_tag_value(value, num_buckets))?
+                        }
+                    }
                 }
                 _ => return Err(PartitionKeyError::TagValueNotTag(col.influx_type())),
             },
             Template::TimeFormat(t, fmt) => fmt.render(t[idx], out)?,
             // Either a tag that has no value for this given row index, or the
             // batch does not contain this tag at all.
-            Template::TagValue(_, last_key) => {
+            Template::TagValue(_, last_key, _) => {
                 // This row doesn't have a tag value, which should be carried
                 // forwards to be checked against the next row.
                 *last_key = None;
@@ -123,7 +153,7 @@ impl<'a> Template<'a> {
     /// identical to the last generated key.
     fn is_identical(&self, idx: usize) -> bool {
         match self {
-            Template::TagValue(col, last_key) if col.valid.get(idx) => match &col.data {
+            Template::TagValue(col, last_key, _) if col.valid.get(idx) => match &col.data {
                 ColumnData::Tag(col_data, _, _) => {
                     let this_key = col_data[idx];
                     // Check if the dictionary key matches the last dictionary
@@ -141,10 +171,10 @@ impl<'a> Template<'a> {
                 fmt.equals_last(t[idx])
             }
             // The last row did not contain this key, and neither does this.
-            Template::TagValue(_, None) => true,
+            Template::TagValue(_, None, _) => true,
             // The last row did contain a key, but this one does not (therefore
             // it differs).
-            Template::TagValue(_, Some(_)) => false,
+            Template::TagValue(_, Some(_), _) => false,
 
             // The batch does not contain this tag at all - it always matches
             // with the previous row.
@@ -160,42 +190,66 @@ fn encode_key_part(s: &str) -> Cow<'_, str> {
     match as_str.len() {
         0 => Cow::Borrowed(PARTITION_KEY_VALUE_EMPTY_STR),
         1..=PARTITION_KEY_MAX_PART_LEN => as_str,
-        _ => {
-            // This string exceeds the maximum byte length limit and must be
-            // truncated.
-            //
-            // Truncation of unicode strings can be tricky - this implementation
-            // avoids splitting unicode code-points nor graphemes. See the
-            // partition_template module docs in data_types before altering
-            // this.
+        // This string exceeds the maximum byte length limit and must be
+        // truncated.
+        _ => Cow::Owned(truncate_key_part(s)),
+    }
+}
 
-            // Preallocate the string to hold the long partition key part.
-            let mut buf = String::with_capacity(PARTITION_KEY_MAX_PART_LEN);
+/// Encode at most the first `max_graphemes` graphemes of `s`, appending the
+/// truncation marker if `s` was shortened.
+fn encode_key_prefix(s: &str, max_graphemes: usize) -> Cow<'_, str> {
+    let prefix = match s.grapheme_indices(true).nth(max_graphemes) {
+        Some((end, _)) => &s[..end],
+        // The value is no longer than the prefix, and is rendered in full.
+        None => return encode_key_part(s),
+    };
 
-            // This is a slow path, re-encoding the original input string -
-            // fortunately this is an uncommon path.
-            //
-            // Walk the string, encoding each grapheme (which includes spaces)
-            // individually, tracking the total length of the encoded string.
-            // Once it hits 199 bytes, stop and append a #.
+    let encoded: Cow<'_, str> = utf8_percent_encode(prefix, &ENCODED_PARTITION_KEY_CHARS).into();
+    if encoded.len() >= PARTITION_KEY_MAX_PART_LEN {
+        // There is no room for the truncation marker - the prefix is
+        // truncated further to fit within the maximum byte length limit.
+        return Cow::Owned(truncate_key_part(prefix));
+    }
 
-            let mut bytes = 0;
-            s.graphemes(true)
-                .map(|v| Cow::from(utf8_percent_encode(v, &ENCODED_PARTITION_KEY_CHARS)))
-                .take_while(|v| {
-                    bytes += v.len(); // Byte length of encoded grapheme
-                    bytes < PARTITION_KEY_MAX_PART_LEN
-                })
-                .for_each(|v| buf.push_str(v.as_ref()));
+    let mut buf = encoded.into_owned();
+    buf.push(PARTITION_KEY_PART_TRUNCATED);
+    Cow::Owned(buf)
+}
 
-            // Append the truncation marker.
-            buf.push(PARTITION_KEY_PART_TRUNCATED);
+/// Encode `s`, truncating the result to fit within
+/// [`PARTITION_KEY_MAX_PART_LEN`] including the appended truncation marker.
+fn truncate_key_part(s: &str) -> String {
+    // Truncation of unicode strings can be tricky - this implementation
+    // avoids splitting unicode code-points nor graphemes. See the
+    // partition_template module docs in data_types before altering
+    // this.
 
-            assert!(buf.len() <= PARTITION_KEY_MAX_PART_LEN);
+    // Preallocate the string to hold the long partition key part.
+    let mut buf = String::with_capacity(PARTITION_KEY_MAX_PART_LEN);
 
-            Cow::Owned(buf)
-        }
-    }
+    // This is a slow path, re-encoding the original input string -
+    // fortunately this is an uncommon path.
+    //
+    // Walk the string, encoding each grapheme (which includes spaces)
+    // individually, tracking the total length of the encoded string.
+    // Once it hits 199 bytes, stop and append a #.
+
+    let mut bytes = 0;
+    s.graphemes(true)
+        .map(|v| Cow::from(utf8_percent_encode(v, &ENCODED_PARTITION_KEY_CHARS)))
+        .take_while(|v| {
+            bytes += v.len(); // Byte length of encoded grapheme
+            bytes < PARTITION_KEY_MAX_PART_LEN
+        })
+        .for_each(|v| buf.push_str(v.as_ref()));
+
+    // Append the truncation marker.
+    buf.push(PARTITION_KEY_PART_TRUNCATED);
+
+    assert!(buf.len() <= PARTITION_KEY_MAX_PART_LEN);
+
+    buf
 }
 
 /// Returns an iterator of partition keys for the given table batch.
@@ -216,13 +270,24 @@ fn partition_keys<'a>(
 
     // Convert TemplatePart into an ordered array of Template
     let mut template = template_parts
-        .map(|v| match v {
-            TemplatePart::TagValue(col_name) => batch
-                .column(col_name)
-                .map_or_else(|_| Template::MissingTag, |v| Template::TagValue(v, None)),
-            TemplatePart::TimeFormat(fmt) => {
-                Template::TimeFormat(time, StrftimeFormatter::new(fmt))
-            }
+        .map(|v| {
+            let (col_name, format) = match v {
+                TemplatePart::TimeFormat(fmt) => {
+                    return Template::TimeFormat(time, StrftimeFormatter::new(fmt))
+                }
+                TemplatePart::TagValue(col_name) => (col_name, TagFormat::Value),
+                TemplatePart::TagPrefix(col_name, len) => {
+                    (col_name, TagFormat::Prefix(len as usize))
+                }
+                TemplatePart::Bucket(col_name, num_buckets) => {
+                    (col_name, TagFormat::Bucket(num_buckets))
+                }
+            };
+
+            batch.column(col_name).map_or_else(
+                |_| Template::MissingTag,
+                |v| Template::TagValue(v, None, format),
+            )
         })
         .collect::<Vec<_>>();
 
@@ -624,6 +689,13 @@ mod tests {
         ColumnValue::Prefix(s.into())
     }
 
+    fn bucket(bucket: u32, num_buckets: u32) -> ColumnValue<'static> {
+        ColumnValue::Bucket {
+            bucket,
+            num_buckets,
+        }
+    }
+
     fn year(y: i32) -> ColumnValue<'static> {
         ColumnValue::Datetime {
             begin: Utc.with_ymd_and_hms(y, 1, 1, 0, 0, 0).unwrap(),
@@ -1011,6 +1083,59 @@ mod tests {
         want_reversed_tags = [("a", identity(format!("{}நி", "A".repeat(182))))]
     );
 
+    test_partition_key!(
+        bucket,
+        template = [
+            TemplatePart::TimeFormat("%Y"),
+            TemplatePart::Bucket("a", 10),
+            TemplatePart::Bucket("b", 100),
+            TemplatePart::Bucket("c", 10),
+        ],
+        tags = [("a", "bananas"), ("b", "cpu-02")],
+        want_key = "2023|1|33|!",
+        want_reversed_tags = [
+            (TIME_COLUMN_NAME, year(2023)),
+            ("a", bucket(1, 10)),
+            ("b", bucket(33, 100)),
+        ]
+    );
+
+    test_partition_key!(
+        tag_prefix,
+        template = [
+            TemplatePart::TagPrefix("a", 3),
+            TemplatePart::TagPrefix("b", 3),
+            TemplatePart::TagPrefix("c", 3),
+            TemplatePart::TagPrefix("d", 3),
+        ],
+        tags = [("a", "bananas"), ("b", "ban"), ("c", "")],
+        want_key = "ban#|ban|^|!",
+        want_reversed_tags = [
+            ("a", prefix("ban")),
+            ("b", identity("ban")),
+            ("c", identity("")),
+        ]
+    );
+
+    // Prefix lengths are measured in graphemes, not bytes.
+    test_partition_key!(
+        tag_prefix_grapheme,
+        template = [TemplatePart::TagPrefix("a", 2)],
+        tags = [("a", "நிநிநி")],
+        want_key = "%E0%AE%A8%E0%AE%BF%E0%AE%A8%E0%AE%BF#",
+        want_reversed_tags = [("a", prefix("நிநி"))]
+    );
+
+    // A prefix that is exactly the maximum part length once encoded leaves no
+    // room for the truncation marker.
+    test_partition_key!(
+        tag_prefix_max_part_len,
+        template = [TemplatePart::TagPrefix("a", 200)],
+        tags = [("a", "A".repeat(201))],
+        want_key = format!("{}#", "A".repeat(199)),
+        want_reversed_tags = [("a", prefix("A".repeat(199)))]
+    );
+
     /// A test using an invalid strftime format string.
     #[test]
     fn test_invalid_strftime() {
@@ -1071,6 +1196,10 @@ mod tests {
         TemplatePart::TagValue("my_tag"),
         TemplatePart::TagValue("my|tag"),
         TemplatePart::TagValue("%%%%|!!!!|"),
+        TemplatePart::TagPrefix("A", 1),
+        TemplatePart::TagPrefix("B", 5),
+        TemplatePart::Bucket("C", 1),
+        TemplatePart::Bucket("D", 42),
     ];
 
     prop_compose! {
@@ -1181,7 +1310,9 @@ mod tests {
             // NULL tags (preserving empty string values).
             let ts = Utc.timestamp_nanos(ts);
             let want_reversed: Vec<(&str, StringOrTSRange)> = template.parts().filter_map(|v| match v {
-                TemplatePart::TagValue(col_name) if tag_values.contains_key(col_name) => {
+                TemplatePart::TagValue(col_name)
+                | TemplatePart::TagPrefix(col_name, _)
+                | TemplatePart::Bucket(col_name, _) if tag_values.contains_key(col_name) => {
                     // This tag had a (potentially empty) value wrote and should
                     // appear in the reversed output.
                     Some((col_name, StringOrTSRange::String(tag_values.get(col_name).unwrap().to_string())))
@@ -1221,6 +1352,15 @@ mod tests {
                             want_val,
                         );
                     },
+                    ColumnValue::Bucket{bucket, num_buckets} => {
+                        let want_val = want_val.expect_string();
+                        assert_eq!(
+                            *bucket,
+                            bucket_for_tag_value(want_val, *num_buckets),
+                            "bucket mismatch for {:?}",
+                            want_val,
+                        );
+                    },
                     ColumnValue::Datetime{..} => {
                         let (got_begin, got_end) = want_val.expect_ts_range();
                         match got_val {
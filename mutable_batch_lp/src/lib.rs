@@ -21,7 +21,11 @@ use criterion as _;
 use workspace_hack as _;
 
 use hashbrown::{hash_map::Entry, HashMap, HashSet};
-use influxdb_line_protocol::{parse_lines, FieldValue, ParsedLine};
+use influxdb_line_protocol::{
+    parse_lines,
+    stream::{LineParseError, LineSplitter},
+    FieldValue, ParsedLine,
+};
 use mutable_batch::writer::Writer;
 use mutable_batch::MutableBatch;
 use snafu::{ResultExt, Snafu};
@@ -52,6 +56,9 @@ pub enum LineError {
         line: usize,
     },
 
+    #[snafu(display("{}", source))]
+    LocatedLineProtocol { source: LineParseError },
+
     #[snafu(display("error writing line {} (1-based): {}", line, source))]
     Write { source: LineWriteError, line: usize },
 
@@ -138,6 +145,43 @@ impl LinesConverter {
         Ok(())
     }
 
+    /// Write all the complete lines currently buffered in `splitter`.
+    ///
+    /// This allows line protocol to be converted as it arrives - call this
+    /// after each chunk is pushed into `splitter`, and once more after
+    /// [`LineSplitter::finish()`] to write the final line.
+    ///
+    /// Unlike [`LinesConverter::write_lp()`], the line numbers in returned
+    /// errors are physical line numbers within the stream (including empty
+    /// and comment lines), and parse errors are
+    /// [`LineError::LocatedLineProtocol`] errors identifying the offending
+    /// column and field.
+    pub fn write_lp_stream(&mut self, splitter: &mut LineSplitter) -> Result<()> {
+        let mut errors = vec![];
+        while errors.len() < MAXIMUM_RETURNED_ERRORS {
+            let Some(line) = splitter.next_line() else {
+                break;
+            };
+            let Some(parsed) = line.parse() else {
+                continue;
+            };
+
+            let line_idx = line.number() - 1;
+            let res = parsed
+                .context(LocatedLineProtocolSnafu)
+                .and_then(|parsed| self.rebase_timestamp(parsed, line_idx))
+                .and_then(|parsed| self.add_line_to_batch(parsed, line_idx));
+            if let Err(e) = res {
+                errors.push(e);
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(Error::PerLine { lines: errors });
+        }
+        Ok(())
+    }
+
     fn rebase_timestamp<'a>(
         &self,
         mut line: ParsedLine<'a>,
@@ -472,6 +516,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_write_lp_stream() {
+        let lp = "cpu,tag1=v1 val=2i 0\n\n# comment\nmem,tag1=v2 ival=3i 0\ncpu,tag1=v4 val=3i 1";
+        let want = lines_to_batches(lp, 5).unwrap();
+
+        let mut converter = LinesConverter::new(5);
+        let mut splitter = LineSplitter::default();
+        for chunk in lp.as_bytes().chunks(3) {
+            splitter.push(chunk);
+            converter.write_lp_stream(&mut splitter).unwrap();
+        }
+        splitter.finish();
+        converter.write_lp_stream(&mut splitter).unwrap();
+        let (batches, stats) = converter.finish().unwrap();
+
+        assert_eq!(stats.num_lines, 3);
+        assert_eq!(batches.len(), 2);
+        for (table, batch) in &batches {
+            assert_eq!(
+                batch.to_arrow(Projection::All).unwrap(),
+                want[table].to_arrow(Projection::All).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_write_lp_stream_errors() {
+        let lp = "cpu val=2i 0\n\ncpu val=bad 1\ncpu val=3.0 2\ncpu val=4i 3\n";
+
+        let mut converter = LinesConverter::new(5);
+        let mut splitter = LineSplitter::default();
+        splitter.push(lp.as_bytes());
+        splitter.finish();
+
+        let result = converter.write_lp_stream(&mut splitter);
+        let lines = assert_matches!(result, Err(Error::PerLine { lines }) => lines);
+        assert_matches!(
+            &lines[..],
+            [
+                LineError::LocatedLineProtocol { source },
+                LineError::Write { line: 4, .. },
+            ] => {
+                assert_eq!(source.line(), 3);
+                assert_eq!(source.column(), 9);
+                assert_eq!(source.offset(), 22);
+                assert_eq!(source.field(), Some("val"));
+            }
+        );
+
+        // The valid lines are written
+        let (batches, stats) = converter.finish().unwrap();
+        assert_eq!(stats.num_lines, 3);
+        assert_eq!(batches["cpu"].rows(), 2);
+    }
+
     #[test]
     fn test_nulls_string_and_float() {
         let lp = r#"m f0="cat" 1639612800000000000
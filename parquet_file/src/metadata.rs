@@ -422,6 +422,10 @@ impl IoxMetadata {
 
     /// Create a corresponding iox catalog's ParquetFile
     ///
+    /// The [`data_written_at`](ParquetFileParams::data_written_at) of the result is the
+    /// creation time of the file. Callers writing data that may predate tombstones not applied to
+    /// it have to set an earlier time.
+    ///
     /// # Panics
     ///
     /// This method panics if the [`IoxParquetMetaData`] structure does not
@@ -496,6 +500,7 @@ impl IoxMetadata {
             compaction_level: self.compaction_level,
             row_count: row_count.try_into().expect("row count overflows i64"),
             created_at: Timestamp::from(self.creation_timestamp),
+            data_written_at: Timestamp::from(self.creation_timestamp),
             column_set: ColumnSet::new(columns),
             max_l0_created_at: Timestamp::from(self.max_l0_created_at),
         }
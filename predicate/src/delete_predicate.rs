@@ -1,16 +1,22 @@
 use crate::delete_expr::{df_to_expr, expr_to_df};
+use arrow::record_batch::RecordBatch;
 use chrono::DateTime;
-use data_types::{DeleteExpr, DeletePredicate, TimestampRange};
+use data_types::{DeleteExpr, DeletePredicate, TimestampRange, Tombstone};
 use datafusion::{
+    error::DataFusionError,
+    execution::context::ExecutionProps,
     logical_expr::Operator,
     prelude::{binary_expr, lit, Column, Expr},
 };
+use datafusion_util::{batch_filter, create_physical_expr_from_schema, make_range_expr};
+use schema::TIME_COLUMN_NAME;
 use snafu::Snafu;
 use sqlparser::{
     ast::{BinaryOperator, Expr as SqlParserExpr, Ident, Statement, Value},
     dialect::GenericDialect,
     parser::Parser,
 };
+use std::sync::Arc;
 
 /// Parse Delete Predicates
 /// Parse Error
@@ -69,6 +75,76 @@ pub fn parse_delete_predicate(
     })
 }
 
+/// Reconstruct the [`DeletePredicate`] recorded in the catalog as `tombstone`.
+pub fn parse_tombstone(tombstone: &Tombstone) -> Result<DeletePredicate> {
+    if tombstone.min_time > tombstone.max_time {
+        return Err(Error::InvalidTimeRange {
+            start: tombstone.min_time.get().to_string(),
+            stop: tombstone.max_time.get().to_string(),
+        });
+    }
+
+    Ok(DeletePredicate {
+        range: tombstone.time_range(),
+        exprs: parse_predicate(&tombstone.serialized_predicate)?,
+    })
+}
+
+/// Build an expression that is true for every row NOT removed by any of the
+/// `predicates`, or [`None`] if no predicate can remove any rows of a table
+/// with the given columns.
+///
+/// A row is deleted if it falls into the time range of a predicate and all
+/// expressions of that predicate evaluate to true; a `NULL` result (e.g. a
+/// tag that is not set for the row) never deletes a row. Predicates that
+/// refer to a column not in `column_names` cannot match any row and are
+/// ignored.
+pub fn delete_predicates_keep_expr<'a>(
+    predicates: impl IntoIterator<Item = &'a DeletePredicate>,
+    column_names: &[&str],
+) -> Option<Expr> {
+    predicates
+        .into_iter()
+        .filter(|p| {
+            p.exprs
+                .iter()
+                .all(|e| column_names.contains(&e.column.as_str()))
+        })
+        .map(|p| {
+            let range = make_range_expr(p.range.start(), p.range.end(), TIME_COLUMN_NAME);
+            let deleted = p
+                .exprs
+                .iter()
+                .cloned()
+                .map(expr_to_df)
+                .fold(range, |acc, e| acc.and(e));
+            deleted.is_not_true()
+        })
+        .reduce(|acc, e| acc.and(e))
+}
+
+/// Remove all rows from `batch` that are deleted by any of the `predicates`.
+pub fn filter_batch_by_delete_predicates(
+    batch: RecordBatch,
+    predicates: &[Arc<DeletePredicate>],
+) -> Result<RecordBatch, DataFusionError> {
+    let schema = batch.schema();
+    let column_names = schema
+        .fields()
+        .iter()
+        .map(|f| f.name().as_str())
+        .collect::<Vec<_>>();
+
+    let Some(keep) =
+        delete_predicates_keep_expr(predicates.iter().map(|p| p.as_ref()), &column_names)
+    else {
+        return Ok(batch);
+    };
+
+    let keep = create_physical_expr_from_schema(&ExecutionProps::new(), &keep, &schema)?;
+    batch_filter(&batch, &keep)
+}
+
 /// Parse the predicate and convert it into datafusion expression
 /// A delete predicate is a conjunctive expression of many
 /// binary expressions of 'colum = constant' or 'column != constant'
@@ -439,4 +515,70 @@ mod tests {
         let result = parse_delete_predicate(start, stop, pred);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_parse_tombstone() {
+        let pred =
+            parse_delete_predicate("100", "200", r#"region = 'west' and cost != 1.5"#).unwrap();
+        let tombstone = Tombstone {
+            id: data_types::TombstoneId::new(1),
+            table_id: data_types::TableId::new(1),
+            min_time: data_types::Timestamp::new(pred.range.start()),
+            max_time: data_types::Timestamp::new(pred.range.end()),
+            serialized_predicate: pred.expr_sql_string(),
+            created_at: data_types::Timestamp::new(42),
+        };
+
+        assert_eq!(parse_tombstone(&tombstone).unwrap(), pred);
+    }
+
+    #[test]
+    fn test_filter_batch_by_delete_predicates() {
+        use arrow::array::{Array, Int64Array, StringArray, TimestampNanosecondArray};
+
+        let batch = RecordBatch::try_from_iter(vec![
+            (
+                "region",
+                Arc::new(StringArray::from(vec![
+                    Some("west"),
+                    Some("east"),
+                    None,
+                    Some("west"),
+                ])) as _,
+            ),
+            ("cost", Arc::new(Int64Array::from(vec![1, 2, 3, 4])) as _),
+            (
+                TIME_COLUMN_NAME,
+                Arc::new(TimestampNanosecondArray::from(vec![10, 20, 30, 40])) as _,
+            ),
+        ])
+        .unwrap();
+
+        // the end of the time range is exclusive, and a NULL tag never matches
+        let preds = vec![
+            Arc::new(parse_delete_predicate("0", "40", "region = 'west'").unwrap()),
+            Arc::new(parse_delete_predicate("0", "100", "region != 'west'").unwrap()),
+            // unknown columns cannot match
+            Arc::new(parse_delete_predicate("0", "100", "host = 'a'").unwrap()),
+        ];
+
+        let got = filter_batch_by_delete_predicates(batch.clone(), &preds).unwrap();
+        let cost = got
+            .column_by_name("cost")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(cost.len(), 2);
+        assert_eq!(cost.values(), &[3, 4]);
+
+        // no predicates leave the batch untouched
+        let got = filter_batch_by_delete_predicates(batch.clone(), &[]).unwrap();
+        assert_eq!(got, batch);
+
+        // a predicate without expressions deletes everything in its range
+        let preds = vec![Arc::new(parse_delete_predicate("0", "100", "").unwrap())];
+        let got = filter_batch_by_delete_predicates(batch, &preds).unwrap();
+        assert_eq!(got.num_rows(), 0);
+    }
 }
@@ -0,0 +1,469 @@
+//! Local disk tier of the [object store cache](super::object_store::ObjectStoreCache).
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    ops::{Add, Sub},
+    path::{Path as StdPath, PathBuf},
+    sync::Arc,
+};
+
+use bytes::Bytes;
+use cache_system::{
+    backend::{
+        policy::{
+            lru::{LruPolicy, ResourcePool},
+            ChangeRequest, PolicyBackend, Subscriber,
+        },
+        CacheBackend,
+    },
+    resource_consumption::{FunctionEstimator, Resource},
+};
+use chrono::{TimeZone, Utc};
+use iox_time::{Time, TimeProvider};
+use object_store::{path::Path, ObjectMeta};
+use observability_deps::tracing::{debug, info, warn};
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+use tokio::runtime::Handle;
+use uuid::Uuid;
+
+const CACHE_ID: &str = "object_store_disk";
+
+/// Marks (and versions) the format of the files written by the [`DiskTier`].
+const MAGIC: &[u8; 8] = b"IOXOSC01";
+
+/// Length of the SHA-256 checksum stored after the [`MAGIC`].
+const CHECKSUM_LEN: usize = 32;
+
+/// Suffix of files that are still being written.
+const TMP_SUFFIX: &str = "tmp";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DiskSize(pub usize);
+
+impl Resource for DiskSize {
+    fn zero() -> Self {
+        Self(0)
+    }
+
+    fn unit() -> &'static str {
+        "bytes"
+    }
+}
+
+impl From<DiskSize> for u64 {
+    fn from(s: DiskSize) -> Self {
+        s.0 as Self
+    }
+}
+
+impl Add for DiskSize {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0.checked_add(rhs.0).expect("overflow"))
+    }
+}
+
+impl Sub for DiskSize {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0.checked_sub(rhs.0).expect("underflow"))
+    }
+}
+
+/// Configuration of the local disk tier of the object store cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiskCacheConfig {
+    /// Directory that holds the cached objects.
+    ///
+    /// It is created if it does not exist. Objects found in this directory on startup are used to
+    /// populate the cache, so it should NOT be shared with any other process.
+    pub directory: PathBuf,
+
+    /// Maximum number of bytes stored in [`directory`](Self::directory).
+    pub size_bytes: usize,
+}
+
+/// Size of an object stored on disk, including the header.
+#[derive(Debug, Clone, Copy)]
+struct DiskEntry {
+    size: usize,
+}
+
+/// Second tier of the object store cache that keeps objects on a local disk (usually an SSD).
+///
+/// Every object is stored in a separate file that is named after the hash of its location:
+///
+/// ```text
+/// magic (8 bytes) | SHA-256 of the remainder (32 bytes) | header | data
+/// ```
+///
+/// The header holds the [`ObjectMeta`] of the object. The checksum is verified on every read and
+/// corrupted files are dropped, so the tier never returns data that differs from what was read
+/// from the object store.
+///
+/// The stored bytes are bounded by an [`LruPolicy`] with its own [`ResourcePool`]. Files are
+/// deleted once their entry is evicted. Since the files are written atomically (via a rename),
+/// the tier can be re-populated from the directory on startup. The "last used" order is NOT
+/// persisted though, so all objects found on startup are considered to be equally old.
+#[derive(Debug)]
+pub(crate) struct DiskTier {
+    directory: PathBuf,
+    backend: Mutex<PolicyBackend<Path, DiskEntry>>,
+    pool: Arc<ResourcePool<DiskSize>>,
+}
+
+impl DiskTier {
+    /// Open disk tier, re-using all objects that are already stored in the configured directory.
+    pub(crate) fn new(
+        config: DiskCacheConfig,
+        time_provider: Arc<dyn TimeProvider>,
+        metric_registry: Arc<metric::Registry>,
+        handle: &Handle,
+    ) -> std::io::Result<Self> {
+        let DiskCacheConfig {
+            directory,
+            size_bytes,
+        } = config;
+        std::fs::create_dir_all(&directory)?;
+
+        let pool = Arc::new(ResourcePool::new(
+            "disk_data",
+            DiskSize(size_bytes),
+            metric_registry,
+            handle,
+        ));
+
+        let mut backend = PolicyBackend::hashmap_backed(time_provider);
+        backend.add_policy(LruPolicy::new(
+            Arc::clone(&pool),
+            CACHE_ID,
+            Arc::new(FunctionEstimator::new(|_k: &Path, v: &DiskEntry| {
+                DiskSize(v.size)
+            })),
+        ));
+        let directory_captured = directory.clone();
+        backend.add_policy(move |_callback_handle| RemoveFilePolicy {
+            directory: directory_captured,
+        });
+
+        let mut n_files = 0;
+        for entry in std::fs::read_dir(&directory)? {
+            let file = entry?.path();
+
+            match read_location(&file) {
+                Ok(location) if file == file_path(&directory, &location) => {
+                    let size = std::fs::metadata(&file)?.len() as usize;
+                    backend.set(location, DiskEntry { size });
+                    n_files += 1;
+                }
+                res => {
+                    // partial writes, foreign or broken files
+                    debug!(file=%file.display(), ok=res.is_ok(), "removing unknown file from disk cache");
+                    remove_file(&file);
+                }
+            }
+        }
+        info!(directory=%directory.display(), n_files, "opened object store disk cache");
+
+        Ok(Self {
+            directory,
+            backend: Mutex::new(backend),
+            pool,
+        })
+    }
+
+    /// Resource pool that bounds the stored bytes.
+    pub(crate) fn pool(&self) -> &Arc<ResourcePool<DiskSize>> {
+        &self.pool
+    }
+
+    /// Get object from disk, if it is cached and intact.
+    pub(crate) async fn get(&self, location: &Path) -> Option<(ObjectMeta, Bytes)> {
+        self.backend.lock().get(location)?;
+
+        let file = file_path(&self.directory, location);
+        let res = match tokio::fs::read(&file).await {
+            Ok(data) => decode(data.into()),
+            Err(e) => Err(e.to_string()),
+        };
+
+        match res {
+            Ok((meta, _)) if meta.location != *location => {
+                warn!(%location, file=%file.display(), other=%meta.location, "disk cache file belongs to another object");
+            }
+            Ok(res) => return Some(res),
+            Err(e) => {
+                warn!(%location, file=%file.display(), %e, "dropping broken disk cache file");
+            }
+        }
+
+        // also removes the file
+        self.backend.lock().remove(location);
+        None
+    }
+
+    /// Store object on disk.
+    ///
+    /// Errors are logged and otherwise ignored, since the object can always be fetched from the
+    /// object store again.
+    pub(crate) async fn put(&self, meta: &ObjectMeta, data: &Bytes) {
+        let encoded = encode(meta, data);
+        let size = encoded.len();
+        if size > self.pool.limit().0 {
+            debug!(location=%meta.location, size, "object too large for disk cache");
+            return;
+        }
+
+        let file = file_path(&self.directory, &meta.location);
+        let tmp_file = file.with_extension(format!("{}.{TMP_SUFFIX}", Uuid::new_v4()));
+        let res = async {
+            tokio::fs::write(&tmp_file, encoded).await?;
+            tokio::fs::rename(&tmp_file, &file).await
+        }
+        .await;
+
+        match res {
+            Ok(()) => {
+                self.backend
+                    .lock()
+                    .set(meta.location.clone(), DiskEntry { size });
+            }
+            Err(e) => {
+                warn!(location=%meta.location, file=%file.display(), %e, "cannot write disk cache file");
+                remove_file(&tmp_file);
+            }
+        }
+    }
+}
+
+/// Policy that removes the file of every entry that is removed from the backend, e.g. because the
+/// [`LruPolicy`] evicted it.
+#[derive(Debug)]
+struct RemoveFilePolicy {
+    directory: PathBuf,
+}
+
+impl Subscriber for RemoveFilePolicy {
+    type K = Path;
+    type V = DiskEntry;
+
+    fn remove(&mut self, k: &Self::K, _now: Time) -> Vec<ChangeRequest<'static, Self::K, Self::V>> {
+        remove_file(&file_path(&self.directory, k));
+        vec![]
+    }
+}
+
+/// Remove file, ignoring files that are already gone.
+fn remove_file(file: &StdPath) {
+    match std::fs::remove_file(file) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => warn!(file=%file.display(), %e, "cannot remove disk cache file"),
+    }
+}
+
+/// File that stores the given object.
+fn file_path(directory: &StdPath, location: &Path) -> PathBuf {
+    let hash = Sha256::digest(location.as_ref().as_bytes());
+    directory.join(format!("{hash:x}"))
+}
+
+/// Encode object into the file format described at [`DiskTier`].
+fn encode(meta: &ObjectMeta, data: &Bytes) -> Vec<u8> {
+    let location = meta.location.as_ref().as_bytes();
+    let e_tag = meta.e_tag.as_deref().unwrap_or_default().as_bytes();
+
+    let mut payload = Vec::with_capacity(8 + 4 + location.len() + 1 + 4 + e_tag.len() + data.len());
+    payload.extend_from_slice(
+        &meta
+            .last_modified
+            .timestamp_nanos_opt()
+            .unwrap_or_default()
+            .to_le_bytes(),
+    );
+    payload.extend_from_slice(&(location.len() as u32).to_le_bytes());
+    payload.extend_from_slice(location);
+    payload.push(meta.e_tag.is_some() as u8);
+    payload.extend_from_slice(&(e_tag.len() as u32).to_le_bytes());
+    payload.extend_from_slice(e_tag);
+    payload.extend_from_slice(data);
+
+    let mut encoded = Vec::with_capacity(MAGIC.len() + CHECKSUM_LEN + payload.len());
+    encoded.extend_from_slice(MAGIC);
+    encoded.extend_from_slice(&Sha256::digest(&payload));
+    encoded.extend_from_slice(&payload);
+    encoded
+}
+
+/// Decode and verify file content, see [`DiskTier`] for the format.
+fn decode(encoded: Bytes) -> Result<(ObjectMeta, Bytes), String> {
+    let header_len = MAGIC.len() + CHECKSUM_LEN;
+    if encoded.len() < header_len || &encoded[..MAGIC.len()] != MAGIC {
+        return Err("invalid magic".to_owned());
+    }
+    let payload = encoded.slice(header_len..);
+    if Sha256::digest(&payload).as_slice() != &encoded[MAGIC.len()..header_len] {
+        return Err("checksum mismatch".to_owned());
+    }
+
+    let mut reader = payload.as_ref();
+    let last_modified = i64::from_le_bytes(read_array(&mut reader)?);
+    let location = read_string(&mut reader)?;
+    let has_e_tag = read_array::<1>(&mut reader)?[0] != 0;
+    let e_tag = read_string(&mut reader)?;
+
+    let data = payload.slice(payload.len() - reader.len()..);
+    let meta = ObjectMeta {
+        location: Path::from(location),
+        last_modified: Utc.timestamp_nanos(last_modified),
+        size: data.len(),
+        e_tag: has_e_tag.then_some(e_tag),
+    };
+
+    Ok((meta, data))
+}
+
+/// Read only the location of the object stored in `file`, without verifying the checksum.
+fn read_location(file: &StdPath) -> Result<Path, String> {
+    let mut reader = BufReader::new(File::open(file).map_err(|e| e.to_string())?);
+
+    let mut header = [0u8; MAGIC.len() + CHECKSUM_LEN + 8];
+    reader.read_exact(&mut header).map_err(|e| e.to_string())?;
+    if &header[..MAGIC.len()] != MAGIC {
+        return Err("invalid magic".to_owned());
+    }
+
+    let mut len = [0u8; 4];
+    reader.read_exact(&mut len).map_err(|e| e.to_string())?;
+    let mut location = vec![0u8; u32::from_le_bytes(len) as usize];
+    reader
+        .read_exact(&mut location)
+        .map_err(|e| e.to_string())?;
+    let location = String::from_utf8(location).map_err(|e| e.to_string())?;
+
+    Ok(Path::from(location))
+}
+
+fn read_array<const N: usize>(reader: &mut &[u8]) -> Result<[u8; N], String> {
+    let mut buf = [0u8; N];
+    reader.read_exact(&mut buf).map_err(|e| e.to_string())?;
+    Ok(buf)
+}
+
+fn read_string(reader: &mut &[u8]) -> Result<String, String> {
+    let len = u32::from_le_bytes(read_array(reader)?) as usize;
+    if reader.len() < len {
+        return Err("truncated header".to_owned());
+    }
+    let (s, rest) = reader.split_at(len);
+    *reader = rest;
+    String::from_utf8(s.to_vec()).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use iox_time::SystemProvider;
+
+    use super::*;
+
+    #[test]
+    fn test_encode_decode() {
+        let meta = ObjectMeta {
+            location: Path::from("foo/bar.parquet"),
+            last_modified: Utc.timestamp_nanos(1_000_000_123),
+            size: 4,
+            e_tag: Some("etag".to_owned()),
+        };
+        let data = Bytes::from_static(b"data");
+
+        let encoded = encode(&meta, &data);
+        assert_eq!(
+            decode(encoded.clone().into()).unwrap(),
+            (meta.clone(), data)
+        );
+
+        let mut meta_no_tag = meta;
+        meta_no_tag.e_tag = None;
+        let (decoded, _) = decode(encode(&meta_no_tag, &Bytes::new()).into()).unwrap();
+        assert_eq!(decoded.e_tag, None);
+        assert_eq!(decoded.size, 0);
+
+        // flipping a single bit is detected
+        let mut corrupted = encoded;
+        *corrupted.last_mut().unwrap() ^= 1;
+        assert_eq!(decode(corrupted.into()).unwrap_err(), "checksum mismatch");
+
+        assert_eq!(
+            decode(Bytes::from_static(b"IOX")).unwrap_err(),
+            "invalid magic"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_survives_restart() {
+        let dir = test_helpers::tmp_dir().unwrap();
+        let meta = ObjectMeta {
+            location: Path::from("foo"),
+            last_modified: Utc.timestamp_nanos(1),
+            size: 8,
+            e_tag: None,
+        };
+        let data = Bytes::from_static(b"data_foo");
+
+        let tier = open(dir.path());
+        assert_matches!(tier.get(&meta.location).await, None);
+        tier.put(&meta, &data).await;
+        assert_eq!(
+            tier.get(&meta.location).await,
+            Some((meta.clone(), data.clone()))
+        );
+        drop(tier);
+
+        // leftovers of interrupted writes are cleaned up
+        let tmp_file = dir.path().join(format!("foo.{TMP_SUFFIX}"));
+        std::fs::write(&tmp_file, b"partial").unwrap();
+
+        let tier = open(dir.path());
+        assert!(!tmp_file.exists());
+        assert_eq!(tier.get(&meta.location).await, Some((meta, data)));
+    }
+
+    #[tokio::test]
+    async fn test_corrupted_file_is_dropped() {
+        let dir = test_helpers::tmp_dir().unwrap();
+        let meta = ObjectMeta {
+            location: Path::from("foo"),
+            last_modified: Utc.timestamp_nanos(1),
+            size: 8,
+            e_tag: None,
+        };
+
+        let tier = open(dir.path());
+        tier.put(&meta, &Bytes::from_static(b"data_foo")).await;
+
+        let file = file_path(dir.path(), &meta.location);
+        let mut content = std::fs::read(&file).unwrap();
+        *content.last_mut().unwrap() ^= 1;
+        std::fs::write(&file, content).unwrap();
+
+        assert_matches!(tier.get(&meta.location).await, None);
+        assert!(!file.exists());
+    }
+
+    fn open(directory: &StdPath) -> DiskTier {
+        DiskTier::new(
+            DiskCacheConfig {
+                directory: directory.to_owned(),
+                size_bytes: usize::MAX,
+            },
+            Arc::new(SystemProvider::new()),
+            Arc::new(metric::Registry::new()),
+            &Handle::current(),
+        )
+        .unwrap()
+    }
+}
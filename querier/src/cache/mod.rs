@@ -2,23 +2,28 @@
 use ::object_store::ObjectStore;
 use ::parquet_file::storage::{ParquetStorage, StorageId};
 use backoff::BackoffConfig;
-use cache_system::backend::policy::lru::ResourcePool;
+use cache_system::{backend::policy::lru::ResourcePool, resource_consumption::Resource};
 use iox_catalog::interface::Catalog;
 use iox_time::TimeProvider;
 use std::sync::Arc;
 use tokio::runtime::Handle;
 
 use self::{
-    namespace::NamespaceCache, object_store::ObjectStoreCache, parquet_file::ParquetFileCache,
-    partition::PartitionCache, projected_schema::ProjectedSchemaCache, ram::RamSize,
+    disk::DiskTier, namespace::NamespaceCache, object_store::ObjectStoreCache,
+    parquet_file::ParquetFileCache, partition::PartitionCache,
+    projected_schema::ProjectedSchemaCache, ram::RamSize, tombstone::TombstoneCache,
 };
 
+mod disk;
 pub mod namespace;
 pub mod object_store;
 pub mod parquet_file;
 pub mod partition;
 pub mod projected_schema;
 mod ram;
+pub mod tombstone;
+
+pub use self::disk::DiskCacheConfig;
 
 #[cfg(test)]
 pub(crate) mod test_util;
@@ -38,12 +43,18 @@ pub struct CatalogCache {
     /// Parquet file cache
     parquet_file_cache: ParquetFileCache,
 
+    /// Tombstone cache.
+    tombstone_cache: TombstoneCache,
+
     /// Projected schema cache.
     projected_schema_cache: ProjectedSchemaCache,
 
     /// Object store cache.
     object_store_cache: ObjectStoreCache,
 
+    /// Resource pools shared by the caches.
+    pools: Vec<PoolUsageSource>,
+
     /// Metric registry
     metric_registry: Arc<metric::Registry>,
 
@@ -53,6 +64,13 @@ pub struct CatalogCache {
 
 impl CatalogCache {
     /// Create empty cache.
+    ///
+    /// If `disk_cache` is provided, object store data is also cached on local disk. Objects that are already stored in
+    /// the configured directory are re-used.
+    ///
+    /// # Panic
+    /// Panics if the disk cache directory cannot be opened.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         catalog: Arc<dyn Catalog>,
         time_provider: Arc<dyn TimeProvider>,
@@ -60,6 +78,7 @@ impl CatalogCache {
         object_store: Arc<dyn ObjectStore>,
         ram_pool_metadata_bytes: usize,
         ram_pool_data_bytes: usize,
+        disk_cache: Option<DiskCacheConfig>,
         handle: &Handle,
     ) -> Self {
         Self::new_internal(
@@ -69,6 +88,7 @@ impl CatalogCache {
             object_store,
             ram_pool_metadata_bytes,
             ram_pool_data_bytes,
+            disk_cache,
             handle,
             false,
         )
@@ -91,6 +111,7 @@ impl CatalogCache {
             object_store,
             usize::MAX,
             usize::MAX,
+            None,
             handle,
             true,
         )
@@ -104,6 +125,7 @@ impl CatalogCache {
         object_store: Arc<dyn ObjectStore>,
         ram_pool_metadata_bytes: usize,
         ram_pool_data_bytes: usize,
+        disk_cache: Option<DiskCacheConfig>,
         handle: &Handle,
         testing: bool,
     ) -> Self {
@@ -147,18 +169,55 @@ impl CatalogCache {
             Arc::clone(&ram_pool_metadata),
             testing,
         );
+        let tombstone_cache = TombstoneCache::new(
+            Arc::clone(&catalog),
+            backoff_config.clone(),
+            Arc::clone(&time_provider),
+            &metric_registry,
+            Arc::clone(&ram_pool_metadata),
+            testing,
+        );
         let projected_schema_cache = ProjectedSchemaCache::new(
             Arc::clone(&time_provider),
             &metric_registry,
             Arc::clone(&ram_pool_metadata),
             testing,
         );
+        let disk_tier = disk_cache.map(|config| {
+            let directory = config.directory.clone();
+            let disk_tier = DiskTier::new(
+                config,
+                Arc::clone(&time_provider),
+                Arc::clone(&metric_registry),
+                handle,
+            )
+            .unwrap_or_else(|e| {
+                panic!(
+                    "cannot open object store disk cache at {}: {e}",
+                    directory.display()
+                )
+            });
+            Arc::new(disk_tier)
+        });
+
+        let mut pools = vec![
+            PoolUsageSource::new("ram_metadata", Arc::clone(&ram_pool_metadata)),
+            PoolUsageSource::new("ram_data", Arc::clone(&ram_pool_data)),
+        ];
+        if let Some(disk_tier) = &disk_tier {
+            pools.push(PoolUsageSource::new(
+                "disk_data",
+                Arc::clone(disk_tier.pool()),
+            ));
+        }
+
         let object_store_cache = ObjectStoreCache::new(
             backoff_config,
             object_store,
             Arc::clone(&time_provider),
             &metric_registry,
             Arc::clone(&ram_pool_data),
+            disk_tier,
             handle,
             testing,
         );
@@ -168,8 +227,10 @@ impl CatalogCache {
             partition_cache,
             namespace_cache,
             parquet_file_cache,
+            tombstone_cache,
             projected_schema_cache,
             object_store_cache,
+            pools,
             metric_registry,
             time_provider,
         }
@@ -205,11 +266,26 @@ impl CatalogCache {
         &self.parquet_file_cache
     }
 
+    /// Tombstone cache.
+    pub(crate) fn tombstone(&self) -> &TombstoneCache {
+        &self.tombstone_cache
+    }
+
     /// Projected schema cache.
     pub(crate) fn projected_schema(&self) -> &ProjectedSchemaCache {
         &self.projected_schema_cache
     }
 
+    /// Object store cache.
+    pub(crate) fn object_store(&self) -> &ObjectStoreCache {
+        &self.object_store_cache
+    }
+
+    /// Current usage of all resource pools.
+    pub(crate) fn pool_usage(&self) -> Vec<PoolUsage> {
+        self.pools.iter().map(|pool| pool.usage()).collect()
+    }
+
     /// Parquet store that points to the cached object store.
     pub fn parquet_store(&self) -> ParquetStorage {
         ParquetStorage::new(
@@ -218,3 +294,58 @@ impl CatalogCache {
         )
     }
 }
+
+/// Usage of a resource pool shared by the caches, see [`CatalogCache::pool_usage`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PoolUsage {
+    /// Name of the pool.
+    pub(crate) pool: &'static str,
+
+    /// Unit of [`limit`](Self::limit) and [`used`](Self::used).
+    pub(crate) unit: &'static str,
+
+    /// Pool limit.
+    pub(crate) limit: u64,
+
+    /// Current usage.
+    pub(crate) used: u64,
+}
+
+/// Type-erased access to the usage of a [`ResourcePool`].
+struct PoolUsageSource {
+    pool: &'static str,
+    usage: Box<dyn Fn() -> (u64, u64) + Send + Sync>,
+    unit: &'static str,
+}
+
+impl PoolUsageSource {
+    fn new<S>(pool: &'static str, resource_pool: Arc<ResourcePool<S>>) -> Self
+    where
+        S: Resource,
+    {
+        Self {
+            pool,
+            usage: Box::new(move || (resource_pool.limit().into(), resource_pool.current().into())),
+            unit: S::unit(),
+        }
+    }
+
+    fn usage(&self) -> PoolUsage {
+        let (limit, used) = (self.usage)();
+        PoolUsage {
+            pool: self.pool,
+            unit: self.unit,
+            limit,
+            used,
+        }
+    }
+}
+
+impl std::fmt::Debug for PoolUsageSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PoolUsageSource")
+            .field("pool", &self.pool)
+            .field("unit", &self.unit)
+            .finish_non_exhaustive()
+    }
+}
@@ -9,12 +9,14 @@ use cache_system::{
         lru::{LruPolicy, ResourcePool},
         PolicyBackend,
     },
-    cache::{driver::CacheDriver, metrics::CacheWithMetrics, Cache},
+    cache::{driver::CacheDriver, metrics::CacheWithMetrics, Cache, CacheGetStatus},
     loader::{metrics::MetricsLoader, FunctionLoader},
     resource_consumption::FunctionEstimator,
 };
 use futures::{stream::BoxStream, StreamExt};
+use iox_query::exec::query_stats::QueryStats;
 use iox_time::TimeProvider;
+use metric::U64Counter;
 use object_store::{
     path::Path, Error as ObjectStoreError, GetOptions, GetResult, GetResultPayload, ListResult,
     MultipartId, ObjectMeta, ObjectStore,
@@ -23,14 +25,46 @@ use observability_deps::tracing::warn;
 use tokio::{io::AsyncWrite, runtime::Handle, sync::oneshot::channel, task::JoinSet};
 use trace::span::Span;
 
-use super::ram::RamSize;
+use super::{disk::DiskTier, ram::RamSize};
 
 const CACHE_ID: &str = "object_store";
 
+/// GET requests per cache tier, split into hits and misses.
+#[derive(Debug)]
+struct TierMetrics {
+    hit: U64Counter,
+    miss: U64Counter,
+}
+
+impl TierMetrics {
+    fn new(metric_registry: &metric::Registry, tier: &'static str) -> Self {
+        let metric = metric_registry.register_metric::<U64Counter>(
+            "iox_cache_object_store_tier_get",
+            "GET requests to the different tiers of the object store cache",
+        );
+
+        Self {
+            hit: metric.recorder(&[("tier", tier), ("status", "hit")]),
+            miss: metric.recorder(&[("tier", tier), ("status", "miss")]),
+        }
+    }
+
+    fn record(&self, hit: bool) {
+        if hit {
+            self.hit.inc(1);
+        } else {
+            self.miss.inc(1);
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 struct CachedRead {
     bytes: Bytes,
     meta: ObjectMeta,
+
+    /// The object was loaded from the disk tier instead of the underlying object store.
+    from_disk: bool,
 }
 
 impl CachedRead {
@@ -48,7 +82,7 @@ impl CachedRead {
 
     /// Convert this CachedRead into a GetResult
     fn into_result(self) -> GetResult {
-        let Self { bytes, meta } = self;
+        let Self { bytes, meta, .. } = self;
         let stream = futures::stream::once(async move { Ok(bytes) }).boxed();
         let range = 0..meta.size - 1;
         GetResult {
@@ -75,7 +109,11 @@ async fn read_from_store(
         Err(e) => return Err(e),
     };
 
-    Ok(Some(CachedRead { bytes, meta }))
+    Ok(Some(CachedRead {
+        bytes,
+        meta,
+        from_disk: false,
+    }))
 }
 
 type CacheT = Arc<
@@ -94,10 +132,17 @@ type CacheT = Arc<
 ///
 /// ["Not found"](ObjectStoreError::NotFound) results are cached forever, so make sure to only retrieve objects that
 /// shall exist.
+///
+/// Objects are kept in RAM. If a [`DiskTier`] is provided, it is consulted before reading from the object store and
+/// keeps a copy of every object that was read from the store, so that RAM evictions and restarts do not require to
+/// go back to the object store. "Not found" results are NOT stored on disk.
 #[derive(Debug)]
 pub struct ObjectStoreCache {
     // this is the virtual object store
     object_store: Arc<dyn ObjectStore>,
+
+    // the virtual object store, without query statistics
+    store: CachedObjectStore,
 }
 
 impl ObjectStoreCache {
@@ -108,16 +153,32 @@ impl ObjectStoreCache {
         time_provider: Arc<dyn TimeProvider>,
         metric_registry: &metric::Registry,
         ram_pool: Arc<ResourcePool<RamSize>>,
+        disk_tier: Option<Arc<DiskTier>>,
         handle: &Handle,
         testing: bool,
     ) -> Self {
+        let disk_metrics = Arc::new(TierMetrics::new(metric_registry, "disk"));
         let object_store_captured = Arc::clone(&object_store);
         let loader = FunctionLoader::new(move |key: Path, _extra: ()| {
             let backoff_config = backoff_config.clone();
             let object_store = Arc::clone(&object_store_captured);
+            let disk_tier = disk_tier.clone();
+            let disk_metrics = Arc::clone(&disk_metrics);
 
             async move {
-                Backoff::new(&backoff_config)
+                if let Some(disk_tier) = &disk_tier {
+                    let res = disk_tier.get(&key).await;
+                    disk_metrics.record(res.is_some());
+                    if let Some((meta, bytes)) = res {
+                        return Some(CachedRead {
+                            bytes,
+                            meta,
+                            from_disk: true,
+                        });
+                    }
+                }
+
+                let data = Backoff::new(&backoff_config)
                     .retry_all_errors::<_, _, _, ObjectStoreError>(
                         "get object from object store",
                         || async {
@@ -127,7 +188,13 @@ impl ObjectStoreCache {
                         },
                     )
                     .await
-                    .expect("retry forever")
+                    .expect("retry forever");
+
+                if let (Some(disk_tier), Some(data)) = (&disk_tier, &data) {
+                    disk_tier.put(&data.meta, &data.bytes).await;
+                }
+
+                data
             }
         });
         let loader = Arc::new(MetricsLoader::new(
@@ -163,24 +230,45 @@ impl ObjectStoreCache {
             metric_registry,
         ));
 
-        let object_store = Arc::new(CachedObjectStore {
+        let store = CachedObjectStore {
             cache,
             handle: handle.clone(),
-        });
+            ram_metrics: Arc::new(TierMetrics::new(metric_registry, "ram")),
+            query_stats: None,
+        };
 
-        Self { object_store }
+        Self {
+            object_store: Arc::new(store.clone()),
+            store,
+        }
     }
 
     /// Get object store.
     pub fn object_store(&self) -> &Arc<dyn ObjectStore> {
         &self.object_store
     }
+
+    /// Get object store for a single query, recording the bytes the query reads into `query_stats`.
+    ///
+    /// Bytes served from RAM count as read from the cache. Loading an object counts its full size as read from the
+    /// cache if it was found on disk, and as read from the object store otherwise.
+    pub(crate) fn object_store_for_query(
+        &self,
+        query_stats: Arc<QueryStats>,
+    ) -> Arc<dyn ObjectStore> {
+        Arc::new(CachedObjectStore {
+            query_stats: Some(query_stats),
+            ..self.store.clone()
+        })
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct CachedObjectStore {
     cache: CacheT,
     handle: Handle,
+    ram_metrics: Arc<TierMetrics>,
+    query_stats: Option<Arc<QueryStats>>,
 }
 
 impl CachedObjectStore {
@@ -188,8 +276,15 @@ impl CachedObjectStore {
     ///
     /// Ensures that the caller tokio runtime (usually the CPU-bound DataFusion runtime) is decoupled from the cache
     /// runtime (usually our main runtime for async IO that also needs to keep connections alive).
-    async fn get_data(&self, location: &Path) -> Result<CachedRead, ObjectStoreError> {
+    ///
+    /// `served` is the number of bytes of the object that the caller passes on.
+    async fn get_data(
+        &self,
+        location: &Path,
+        served: impl FnOnce(&CachedRead) -> usize,
+    ) -> Result<CachedRead, ObjectStoreError> {
         let cache = Arc::clone(&self.cache);
+        let ram_metrics = Arc::clone(&self.ram_metrics);
         let location = location.clone();
         let (tx, rx) = channel();
 
@@ -197,13 +292,15 @@ impl CachedObjectStore {
         let mut join_set = JoinSet::new();
         join_set.spawn_on(
             async move {
-                let res = cache
-                    .get(location.clone(), ((), None))
-                    .await
-                    .ok_or_else(|| ObjectStoreError::NotFound {
-                        path: location.to_string(),
-                        source: String::from("not found").into(),
-                    });
+                let (res, status) = cache.get_with_status(location.clone(), ((), None)).await;
+                ram_metrics.record(status == CacheGetStatus::Hit);
+
+                let res =
+                    res.map(|data| (data, status))
+                        .ok_or_else(|| ObjectStoreError::NotFound {
+                            path: location.to_string(),
+                            source: String::from("not found").into(),
+                        });
 
                 // it's OK when the receiver is gone
                 tx.send(res).ok();
@@ -211,10 +308,26 @@ impl CachedObjectStore {
             &self.handle,
         );
 
-        rx.await.map_err(|e| ObjectStoreError::Generic {
+        let (data, status) = rx.await.map_err(|e| ObjectStoreError::Generic {
             store: "CachedObjectStore",
             source: Box::new(e),
-        })?
+        })??;
+
+        if let Some(query_stats) = &self.query_stats {
+            match status {
+                CacheGetStatus::Hit => query_stats.record_bytes(served(&data) as u64, 0),
+                CacheGetStatus::Miss | CacheGetStatus::MissAlreadyLoading => {
+                    let loaded = data.bytes.len() as u64;
+                    if data.from_disk {
+                        query_stats.record_bytes(loaded, 0);
+                    } else {
+                        query_stats.record_bytes(0, loaded);
+                    }
+                }
+            }
+        }
+
+        Ok(data)
     }
 }
 
@@ -293,7 +406,7 @@ impl ObjectStore for CachedObjectStore {
             return Err(ObjectStoreError::NotImplemented);
         }
 
-        let cached_read = self.get_data(location).await?;
+        let cached_read = self.get_data(location, |data| data.bytes.len()).await?;
 
         Ok(cached_read.into_result())
     }
@@ -303,7 +416,11 @@ impl ObjectStore for CachedObjectStore {
         location: &Path,
         range: Range<usize>,
     ) -> Result<Bytes, ObjectStoreError> {
-        let cached_read = self.get_data(location).await?;
+        let cached_read = self
+            .get_data(location, |data| {
+                range.end.min(data.bytes.len()).saturating_sub(range.start)
+            })
+            .await?;
         let data = cached_read.bytes;
 
         if range.end > data.len() {
@@ -323,7 +440,7 @@ impl ObjectStore for CachedObjectStore {
     }
 
     async fn head(&self, location: &Path) -> Result<ObjectMeta, ObjectStoreError> {
-        let cached_read = self.get_data(location).await?;
+        let cached_read = self.get_data(location, |_| 0).await?;
         Ok(cached_read.meta)
     }
 
@@ -362,7 +479,7 @@ mod tests {
     use object_store::memory::InMemory;
     use object_store_metrics::ObjectStoreMetrics;
 
-    use crate::cache::ram::test_util::test_ram_pool;
+    use crate::cache::{disk::DiskCacheConfig, ram::test_util::test_ram_pool};
 
     use super::*;
 
@@ -400,6 +517,7 @@ mod tests {
             time_provider,
             &metric_registry,
             test_ram_pool(),
+            None,
             &Handle::current(),
             true,
         );
@@ -464,6 +582,190 @@ mod tests {
         assert_eq!(get_count_miss(&metric_registry), 1);
     }
 
+    #[tokio::test]
+    async fn test_disk_tier() {
+        let inner = Arc::new(InMemory::new());
+
+        let path_1 = Path::from("foo");
+        let bytes_1 = Bytes::from(b"data_foo" as &'static [u8]);
+        inner.put(&path_1, bytes_1.clone()).await.unwrap();
+
+        let path_2 = Path::from("bar");
+
+        let dir = test_helpers::tmp_dir().unwrap();
+        let metric_registry = Arc::new(metric::Registry::new());
+        let time_provider = Arc::new(SystemProvider::new());
+        let instrumented_store: Arc<dyn ObjectStore> = Arc::new(ObjectStoreMetrics::new(
+            Arc::clone(&inner) as _,
+            Arc::clone(&time_provider) as _,
+            &metric_registry,
+        ));
+        let new_cache = || {
+            let disk_tier = DiskTier::new(
+                DiskCacheConfig {
+                    directory: dir.path().to_owned(),
+                    size_bytes: usize::MAX,
+                },
+                Arc::clone(&time_provider) as _,
+                Arc::clone(&metric_registry),
+                &Handle::current(),
+            )
+            .unwrap();
+
+            ObjectStoreCache::new(
+                BackoffConfig::default(),
+                Arc::clone(&instrumented_store),
+                Arc::clone(&time_provider) as _,
+                &metric_registry,
+                test_ram_pool(),
+                Some(Arc::new(disk_tier)),
+                &Handle::current(),
+                true,
+            )
+        };
+
+        // first read goes to the object store and populates both tiers
+        let cache = new_cache();
+        assert_eq!(
+            cache.object_store().get_range(&path_1, 0..8).await.unwrap(),
+            bytes_1,
+        );
+        assert_eq!(
+            cache.object_store().get_range(&path_1, 0..8).await.unwrap(),
+            bytes_1,
+        );
+        assert_eq!(get_count_hit(&metric_registry), 1);
+        assert_eq!(get_count_tier(&metric_registry, "ram", "hit"), 1);
+        assert_eq!(get_count_tier(&metric_registry, "ram", "miss"), 1);
+        assert_eq!(get_count_tier(&metric_registry, "disk", "hit"), 0);
+        assert_eq!(get_count_tier(&metric_registry, "disk", "miss"), 1);
+
+        // "not found" is not persisted
+        assert_matches!(
+            cache.object_store().head(&path_2).await.unwrap_err(),
+            ObjectStoreError::NotFound { .. }
+        );
+        assert_eq!(get_count_miss(&metric_registry), 1);
+        drop(cache);
+
+        // a "restarted" cache is served from disk, even though the object is gone from the store
+        inner.delete(&path_1).await.unwrap();
+        let cache = new_cache();
+        assert_eq!(
+            cache.object_store().get_range(&path_1, 0..8).await.unwrap(),
+            bytes_1,
+        );
+        assert_eq!(
+            cache.object_store().head(&path_1).await.unwrap().size,
+            bytes_1.len(),
+        );
+        assert_eq!(get_count_hit(&metric_registry), 1);
+        assert_eq!(get_count_tier(&metric_registry, "disk", "hit"), 1);
+        assert_eq!(get_count_tier(&metric_registry, "disk", "miss"), 2);
+
+        assert_matches!(
+            cache.object_store().head(&path_2).await.unwrap_err(),
+            ObjectStoreError::NotFound { .. }
+        );
+        assert_eq!(get_count_miss(&metric_registry), 2);
+        assert_eq!(get_count_tier(&metric_registry, "disk", "miss"), 3);
+    }
+
+    #[tokio::test]
+    async fn test_query_stats() {
+        let inner = Arc::new(InMemory::new());
+
+        let path_1 = Path::from("foo");
+        let bytes_1 = Bytes::from(b"data_foo" as &'static [u8]);
+        inner.put(&path_1, bytes_1.clone()).await.unwrap();
+
+        let path_2 = Path::from("bar");
+
+        let dir = test_helpers::tmp_dir().unwrap();
+        let metric_registry = Arc::new(metric::Registry::new());
+        let time_provider = Arc::new(SystemProvider::new());
+        let new_cache = || {
+            let disk_tier = DiskTier::new(
+                DiskCacheConfig {
+                    directory: dir.path().to_owned(),
+                    size_bytes: usize::MAX,
+                },
+                Arc::clone(&time_provider) as _,
+                Arc::clone(&metric_registry),
+                &Handle::current(),
+            )
+            .unwrap();
+
+            ObjectStoreCache::new(
+                BackoffConfig::default(),
+                Arc::clone(&inner) as _,
+                Arc::clone(&time_provider) as _,
+                &metric_registry,
+                test_ram_pool(),
+                Some(Arc::new(disk_tier)),
+                &Handle::current(),
+                true,
+            )
+        };
+
+        let cache = new_cache();
+        let query_stats = Arc::new(QueryStats::default());
+        let store = cache.object_store_for_query(Arc::clone(&query_stats));
+
+        // loading the object counts the entire object as read from the object store
+        assert_eq!(store.get_range(&path_1, 0..2).await.unwrap(), "da");
+        assert_eq!(query_stats.bytes_from_cache(), 0);
+        assert_eq!(query_stats.bytes_from_object_store(), 8);
+
+        // RAM hits count the bytes served
+        assert_eq!(store.get_range(&path_1, 2..5).await.unwrap(), "ta_");
+        store.head(&path_1).await.unwrap();
+        assert_eq!(query_stats.bytes_from_cache(), 3);
+        assert_eq!(query_stats.bytes_from_object_store(), 8);
+
+        // "not found" is not recorded
+        assert_matches!(
+            store.get(&path_2).await.unwrap_err(),
+            ObjectStoreError::NotFound { .. }
+        );
+        assert_eq!(query_stats.bytes_from_cache(), 3);
+        assert_eq!(query_stats.bytes_from_object_store(), 8);
+
+        // the shared store and other queries do not record into these stats
+        cache.object_store().get(&path_1).await.unwrap();
+        cache
+            .object_store_for_query(Arc::default())
+            .get(&path_1)
+            .await
+            .unwrap();
+        assert_eq!(query_stats.bytes_from_cache(), 3);
+        drop(cache);
+
+        // loading the object from disk counts it as read from the cache
+        let cache = new_cache();
+        let query_stats = Arc::new(QueryStats::default());
+        let store = cache.object_store_for_query(Arc::clone(&query_stats));
+        assert_eq!(
+            store.get(&path_1).await.unwrap().bytes().await.unwrap(),
+            bytes_1
+        );
+        assert_eq!(query_stats.bytes_from_cache(), 8);
+        assert_eq!(query_stats.bytes_from_object_store(), 0);
+    }
+
+    fn get_count_tier(
+        metric_registry: &metric::Registry,
+        tier: &'static str,
+        status: &'static str,
+    ) -> u64 {
+        metric_registry
+            .get_instrument::<Metric<U64Counter>>("iox_cache_object_store_tier_get")
+            .unwrap()
+            .get_observer(&Attributes::from(&[("tier", tier), ("status", status)]))
+            .unwrap()
+            .fetch()
+    }
+
     fn get_count_hit(metric_registry: &metric::Registry) -> u64 {
         metric_registry
             .get_instrument::<Metric<DurationHistogram>>("object_store_op_duration")
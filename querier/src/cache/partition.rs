@@ -250,6 +250,22 @@ pub struct CachedPartition {
     pub id: TransitionPartitionId,
     pub sort_key: Option<Arc<PartitionSortKey>>,
     pub column_ranges: ColumnRanges,
+
+    /// Buckets the tag values of this partition were hashed into, for columns
+    /// partitioned by a bucket template part.
+    ///
+    /// A column may appear more than once if the template contains multiple
+    /// bucket parts for it.
+    pub column_buckets: Box<[(Arc<str>, ColumnBucket)]>,
+}
+
+/// The bucket all values of a column within a partition hash into.
+///
+/// See [`bucket_for_tag_value`](data_types::partition_template::bucket_for_tag_value).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColumnBucket {
+    pub bucket: u32,
+    pub num_buckets: u32,
 }
 
 impl CachedPartition {
@@ -269,6 +285,7 @@ impl CachedPartition {
         );
 
         let mut column_ranges = HashMap::new();
+        let mut column_buckets = vec![];
         let mut ignore = HashSet::new();
         for (col, val) in
             build_column_values(&table.partition_template, partition.partition_key.inner())
@@ -333,6 +350,20 @@ impl CachedPartition {
                         max_value,
                     }
                 }
+                ColumnValue::Bucket {
+                    bucket,
+                    num_buckets,
+                } => {
+                    // buckets cannot be expressed as a range, but are used for pruning separately
+                    column_buckets.push((
+                        col,
+                        ColumnBucket {
+                            bucket,
+                            num_buckets,
+                        },
+                    ));
+                    continue;
+                }
                 ColumnValue::Datetime { begin, end } => ColumnRange {
                     min_value: Arc::new(ScalarValue::TimestampNanosecond(
                         Some(
@@ -375,6 +406,7 @@ impl CachedPartition {
             id: partition.transition_partition_id(),
             sort_key,
             column_ranges: Arc::new(column_ranges),
+            column_buckets: column_buckets.into_boxed_slice(),
         }
     }
 
@@ -398,7 +430,14 @@ impl CachedPartition {
                 .map(|(col, range)| col.len() + range.min_value.size() + range.max_value.size())
                 .sum::<usize>();
 
-        std::mem::size_of_val(self) + id + sort_key + column_ranges
+        // Box content
+        let column_buckets = self
+            .column_buckets
+            .iter()
+            .map(|(col, _bucket)| size_of::<(Arc<str>, ColumnBucket)>() + col.len())
+            .sum::<usize>();
+
+        std::mem::size_of_val(self) + id + sort_key + column_ranges + column_buckets
     }
 }
 
@@ -467,7 +506,7 @@ mod tests {
     };
     use futures::StreamExt;
     use generated_types::influxdata::iox::partition_template::v1::{
-        template_part::Part, PartitionTemplate, TemplatePart,
+        template_part::Part, PartitionTemplate, TagBucket, TemplatePart,
     };
     use iox_tests::{TestCatalog, TestNamespace};
     use schema::{Schema, SchemaBuilder, TIME_COLUMN_NAME};
@@ -938,6 +977,109 @@ mod tests {
         assert_eq!(ranges.as_ref(), &HashMap::new(),);
     }
 
+    #[tokio::test]
+    async fn test_column_buckets() {
+        let catalog = TestCatalog::new();
+
+        let ns = catalog.create_namespace_1hr_retention("ns").await;
+        let t = ns
+            .create_table_with_partition_template(
+                "table",
+                Some(PartitionTemplate {
+                    parts: vec![
+                        TemplatePart {
+                            part: Some(Part::TimeFormat(String::from("%Y"))),
+                        },
+                        TemplatePart {
+                            part: Some(Part::TagBucket(TagBucket {
+                                tag_name: String::from("tag1"),
+                                num_buckets: 10,
+                            })),
+                        },
+                        TemplatePart {
+                            part: Some(Part::TagValue(String::from("tag1"))),
+                        },
+                    ],
+                }),
+            )
+            .await;
+        let c1 = t.create_column("tag1", ColumnType::Tag).await;
+        let c2 = t.create_column(TIME_COLUMN_NAME, ColumnType::Time).await;
+
+        // bucket 3, value "v1"
+        let p1 = t.create_partition("2023|3|v1").await.partition.clone();
+        // tag is NULL
+        let p2 = t.create_partition("2023|!|!").await.partition.clone();
+        let cached_table = Arc::new(CachedTable {
+            id: t.table.id,
+            schema: schema(),
+            column_id_map: HashMap::from([
+                (c1.column.id, Arc::from(c1.column.name.clone())),
+                (c2.column.id, Arc::from(c2.column.name.clone())),
+            ]),
+            column_id_map_rev: HashMap::from([
+                (Arc::from(c1.column.name.clone()), c1.column.id),
+                (Arc::from(c2.column.name.clone()), c2.column.id),
+            ]),
+            primary_key_column_ids: [c1.column.id, c2.column.id].into(),
+            partition_template: t.table.partition_template.clone(),
+        });
+
+        let cache = PartitionCache::new(
+            catalog.catalog(),
+            BackoffConfig::default(),
+            catalog.time_provider(),
+            &catalog.metric_registry(),
+            test_ram_pool(),
+            true,
+        );
+
+        let cached_p1 = cache
+            .get_one(
+                Arc::clone(&cached_table),
+                &p1.transition_partition_id(),
+                &[],
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            cached_p1.column_buckets.as_ref(),
+            &[(
+                Arc::from("tag1"),
+                ColumnBucket {
+                    bucket: 3,
+                    num_buckets: 10
+                }
+            )],
+        );
+        // the bucket does not interfere with the range of the same column
+        assert_eq!(
+            cached_p1.column_ranges.as_ref(),
+            &HashMap::from([
+                (Arc::from(TIME_COLUMN_NAME), year_column_range(2023)),
+                (
+                    Arc::from("tag1"),
+                    ColumnRange {
+                        min_value: Arc::new(ScalarValue::from("v1")),
+                        max_value: Arc::new(ScalarValue::from("v1"))
+                    }
+                ),
+            ]),
+        );
+
+        let cached_p2 = cache
+            .get_one(
+                Arc::clone(&cached_table),
+                &p2.transition_partition_id(),
+                &[],
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(cached_p2.column_buckets.is_empty());
+    }
+
     #[tokio::test]
     async fn test_column_ranges_time_edges() {
         let catalog = TestCatalog::new();
@@ -0,0 +1,248 @@
+//! Tombstone cache
+
+use backoff::{Backoff, BackoffConfig};
+use cache_system::{
+    backend::policy::{
+        lru::{LruPolicy, ResourcePool},
+        ttl::{ConstantValueTtlProvider, TtlPolicy},
+        PolicyBackend,
+    },
+    cache::{driver::CacheDriver, metrics::CacheWithMetrics, Cache},
+    loader::{metrics::MetricsLoader, FunctionLoader},
+    resource_consumption::FunctionEstimator,
+};
+use data_types::{DeletePredicate, ParquetFile, TableId, Tombstone};
+use iox_catalog::interface::Catalog;
+use iox_time::TimeProvider;
+use observability_deps::tracing::warn;
+use predicate::delete_predicate::parse_tombstone;
+use snafu::{ResultExt, Snafu};
+use std::{mem, sync::Arc, time::Duration};
+use trace::span::Span;
+
+use super::ram::RamSize;
+
+/// Duration to keep cached view.
+///
+/// Deletes are not announced by the ingesters, so this is kept short to
+/// bound the time until a delete becomes visible to queries.
+pub const TTL: Duration = Duration::from_secs(10);
+
+const CACHE_ID: &str = "tombstone";
+
+#[derive(Debug, Snafu)]
+#[allow(missing_copy_implementations, missing_docs)]
+pub enum Error {
+    #[snafu(display("CatalogError refreshing tombstone cache: {}", source))]
+    Catalog {
+        source: iox_catalog::interface::Error,
+    },
+}
+
+/// Tombstones of a single table, together with their parsed predicates.
+#[derive(Debug)]
+pub struct CachedTombstones {
+    tombstones: Box<[(Tombstone, Arc<DeletePredicate>)]>,
+}
+
+impl CachedTombstones {
+    fn new(tombstones: Vec<Tombstone>) -> Self {
+        let tombstones = tombstones
+            .into_iter()
+            .filter_map(|t| match parse_tombstone(&t) {
+                Ok(predicate) => Some((t, Arc::new(predicate))),
+                Err(e) => {
+                    warn!(
+                        tombstone_id=%t.id,
+                        table_id=%t.table_id,
+                        %e,
+                        "ignoring tombstone with invalid predicate",
+                    );
+                    None
+                }
+            })
+            .collect();
+
+        Self { tombstones }
+    }
+
+    /// Return the delete predicates that have to be applied to the rows of
+    /// the given parquet file.
+    pub fn predicates_for(&self, file: &ParquetFile) -> Vec<Arc<DeletePredicate>> {
+        self.tombstones
+            .iter()
+            .filter(|(t, _)| t.applies_to(file))
+            .map(|(_, p)| Arc::clone(p))
+            .collect()
+    }
+
+    /// Estimate the memory consumption of this object and its contents
+    fn size(&self) -> usize {
+        mem::size_of_val(self)
+            + self
+                .tombstones
+                .iter()
+                .map(|(t, p)| t.size() + p.size())
+                .sum::<usize>()
+    }
+}
+
+type CacheT = Box<
+    dyn Cache<
+        K = TableId,
+        V = Arc<CachedTombstones>,
+        GetExtra = ((), Option<Span>),
+        PeekExtra = ((), Option<Span>),
+    >,
+>;
+
+/// Cache for the tombstones of a table.
+#[derive(Debug)]
+pub struct TombstoneCache {
+    cache: CacheT,
+}
+
+impl TombstoneCache {
+    /// Create new empty cache.
+    pub fn new(
+        catalog: Arc<dyn Catalog>,
+        backoff_config: BackoffConfig,
+        time_provider: Arc<dyn TimeProvider>,
+        metric_registry: &metric::Registry,
+        ram_pool: Arc<ResourcePool<RamSize>>,
+        testing: bool,
+    ) -> Self {
+        let loader = FunctionLoader::new(move |table_id: TableId, _extra: ()| {
+            let catalog = Arc::clone(&catalog);
+            let backoff_config = backoff_config.clone();
+
+            async move {
+                Backoff::new(&backoff_config)
+                    .retry_all_errors("get tombstones", || async {
+                        let tombstones = catalog
+                            .repositories()
+                            .await
+                            .tombstones()
+                            .list_by_table_id(table_id)
+                            .await
+                            .context(CatalogSnafu)?;
+
+                        Ok(Arc::new(CachedTombstones::new(tombstones)))
+                            as std::result::Result<_, Error>
+                    })
+                    .await
+                    .expect("retry forever")
+            }
+        });
+        let loader = Arc::new(MetricsLoader::new(
+            loader,
+            CACHE_ID,
+            Arc::clone(&time_provider),
+            metric_registry,
+            testing,
+        ));
+
+        let mut backend = PolicyBackend::hashmap_backed(Arc::clone(&time_provider));
+        backend.add_policy(LruPolicy::new(
+            Arc::clone(&ram_pool),
+            CACHE_ID,
+            Arc::new(FunctionEstimator::new(
+                |k: &TableId, v: &Arc<CachedTombstones>| {
+                    RamSize(mem::size_of_val(k) + mem::size_of_val(v) + v.size())
+                },
+            )),
+        ));
+        backend.add_policy(TtlPolicy::new(
+            Arc::new(ConstantValueTtlProvider::new(Some(TTL))),
+            CACHE_ID,
+            metric_registry,
+        ));
+
+        let cache = CacheDriver::new(loader, backend);
+        let cache = Box::new(CacheWithMetrics::new(
+            cache,
+            CACHE_ID,
+            time_provider,
+            metric_registry,
+        ));
+
+        Self { cache }
+    }
+
+    /// Get the tombstones of the given table.
+    pub async fn get(&self, table_id: TableId, span: Option<Span>) -> Arc<CachedTombstones> {
+        self.cache.get(table_id, ((), span)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::{
+        ram::test_util::test_ram_pool, test_util::assert_catalog_access_metric_count,
+    };
+    use data_types::{ColumnType, DeleteExpr, Op, Scalar, TimestampRange};
+    use iox_tests::{TestCatalog, TestParquetFileBuilder};
+
+    const METRIC_NAME: &str = "tombstone_list_by_table_id";
+
+    #[tokio::test]
+    async fn test_tombstones() {
+        let catalog = TestCatalog::new();
+        let ns = catalog.create_namespace_1hr_retention("ns").await;
+        let table = ns.create_table("table").await;
+        table.create_column("foo", ColumnType::F64).await;
+        table.create_column("time", ColumnType::Time).await;
+        let partition = table.create_partition("k").await;
+
+        let builder = TestParquetFileBuilder::default().with_line_protocol("table foo=1 11");
+        let file = partition.create_parquet_file(builder).await.parquet_file;
+
+        let cache = make_cache(&catalog);
+        let cached = cache.get(table.table.id, None).await;
+        assert!(cached.predicates_for(&file).is_empty());
+        assert_catalog_access_metric_count(&catalog.metric_registry, METRIC_NAME, 1);
+
+        // a tombstone created after the file applies to it
+        let predicate = DeletePredicate {
+            range: TimestampRange::new(0, 100),
+            exprs: vec![DeleteExpr::new(
+                "foo".to_string(),
+                Op::Eq,
+                Scalar::F64(1.0.into()),
+            )],
+        };
+        catalog
+            .catalog
+            .repositories()
+            .await
+            .tombstones()
+            .create(table.table.id, &predicate)
+            .await
+            .unwrap();
+
+        // still cached
+        let cached = cache.get(table.table.id, None).await;
+        assert!(cached.predicates_for(&file).is_empty());
+        assert_catalog_access_metric_count(&catalog.metric_registry, METRIC_NAME, 1);
+
+        // expired
+        catalog.mock_time_provider().inc(TTL);
+        let cached = cache.get(table.table.id, None).await;
+        let predicates = cached.predicates_for(&file);
+        assert_eq!(predicates.len(), 1);
+        assert_eq!(predicates[0].as_ref(), &predicate);
+        assert_catalog_access_metric_count(&catalog.metric_registry, METRIC_NAME, 2);
+    }
+
+    fn make_cache(catalog: &TestCatalog) -> TombstoneCache {
+        TombstoneCache::new(
+            catalog.catalog(),
+            BackoffConfig::default(),
+            catalog.time_provider(),
+            &catalog.metric_registry(),
+            test_ram_pool(),
+            true,
+        )
+    }
+}
@@ -5,15 +5,18 @@ use crate::{
     ingester::IngesterConnection,
     namespace::{QuerierNamespace, QuerierNamespaceArgs},
     parquet::ChunkAdapter,
-    query_log::QueryLog,
+    query_log::{QueryLog, QueryLogSink},
+    router::{RouterConnection, RouterError},
     table::PruneMetrics,
     QueryLogEntry,
 };
 use async_trait::async_trait;
 use backoff::{Backoff, BackoffConfig};
-use data_types::Namespace;
+use data_types::{DeletePredicate, Namespace, NamespaceName};
+use datafusion::error::DataFusionError;
 use iox_catalog::interface::SoftDeletedRows;
 use iox_query::exec::Executor;
+use observability_deps::tracing::info;
 use service_common::QueryNamespaceProvider;
 use snafu::Snafu;
 use std::{
@@ -37,6 +40,9 @@ pub enum Error {
     Catalog {
         source: iox_catalog::interface::Error,
     },
+
+    #[snafu(display("Router error: {source}"))]
+    Router { source: RouterError },
 }
 
 /// Database for the querier.
@@ -74,6 +80,9 @@ pub struct QuerierDatabase {
 
     /// DataFusion config.
     datafusion_config: Arc<HashMap<String, String>>,
+
+    /// Connection to the router that performs schema changes.
+    router_connection: Option<Arc<dyn RouterConnection>>,
 }
 
 #[async_trait]
@@ -95,6 +104,59 @@ impl QueryNamespaceProvider for QuerierDatabase {
             .await
             .expect("Semaphore should not be closed by anyone")
     }
+
+    async fn namespace_names(&self) -> Result<Vec<String>, DataFusionError> {
+        Ok(self
+            .namespaces()
+            .await
+            .into_iter()
+            .map(|ns| ns.name)
+            .collect())
+    }
+
+    async fn create_namespace(
+        &self,
+        name: &str,
+        retention_period_ns: Option<i64>,
+    ) -> Result<(), DataFusionError> {
+        let name = NamespaceName::new(name).map_err(|e| DataFusionError::Plan(e.to_string()))?;
+
+        self.router_connection()?
+            .create_namespace(&name, retention_period_ns)
+            .await
+            .map_err(|source| DataFusionError::External(Box::new(Error::Router { source })))?;
+
+        info!(namespace_name=%name, "created namespace");
+        Ok(())
+    }
+
+    async fn delete(
+        &self,
+        name: &str,
+        table_name: Option<&str>,
+        predicate: &DeletePredicate,
+    ) -> Result<(), DataFusionError> {
+        let router_connection = self.router_connection()?;
+
+        let namespace = self
+            .catalog_cache
+            .catalog()
+            .repositories()
+            .await
+            .namespaces()
+            .get_by_name(name, SoftDeletedRows::ExcludeDeleted)
+            .await
+            .map_err(|source| DataFusionError::External(Box::new(Error::Catalog { source })))?
+            .ok_or_else(|| DataFusionError::Plan(format!("database not found: {name}")))?;
+
+        router_connection
+            .delete(namespace.id, table_name, predicate)
+            .await
+            .map_err(|source| DataFusionError::External(Box::new(Error::Router { source })))?;
+
+        info!(namespace_name=%name, ?table_name, "requested delete");
+        Ok(())
+    }
 }
 
 impl QuerierDatabase {
@@ -146,9 +208,40 @@ impl QuerierDatabase {
             query_execution_semaphore,
             prune_metrics,
             datafusion_config,
+            router_connection: None,
+        })
+    }
+
+    /// Forward schema changes, like creating namespaces or deleting data, to the given router.
+    ///
+    /// Without a router connection these requests are rejected.
+    pub fn with_router_connection(self, router_connection: Arc<dyn RouterConnection>) -> Self {
+        Self {
+            router_connection: Some(router_connection),
+            ..self
+        }
+    }
+
+    fn router_connection(&self) -> Result<&dyn RouterConnection, DataFusionError> {
+        self.router_connection.as_deref().ok_or_else(|| {
+            DataFusionError::NotImplemented(
+                "schema changes require the querier to be configured with a router address"
+                    .to_owned(),
+            )
         })
     }
 
+    /// Pass completed queries to the given sink in addition to the in-memory query log.
+    ///
+    /// This must be called before the first namespace is requested, otherwise queries on that namespace are not
+    /// passed to the sink.
+    pub fn with_query_log_sink(self, sink: QueryLogSink) -> Self {
+        let query_log = Arc::new(
+            QueryLog::new(QUERY_LOG_SIZE, self.catalog_cache.time_provider()).with_sink(sink),
+        );
+        Self { query_log, ..self }
+    }
+
     /// Get namespace if it exists.
     ///
     /// This will await the internal namespace semaphore. Existence of namespaces is checked AFTER
@@ -214,7 +307,11 @@ impl QuerierDatabase {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::create_ingester_connection_for_testing;
+    use crate::{
+        create_ingester_connection_for_testing,
+        router::mock::{MockRouterConnection, MockRouterRequest},
+    };
+    use assert_matches::assert_matches;
     use iox_tests::TestCatalog;
     use tokio::runtime::Handle;
 
@@ -270,6 +367,63 @@ mod tests {
         assert_eq!(namespaces[1].name, "ns2");
     }
 
+    #[tokio::test]
+    async fn test_namespace_lifecycle() {
+        let catalog = TestCatalog::new();
+        let predicate = DeletePredicate {
+            range: data_types::TimestampRange::new(0, 100),
+            exprs: vec![],
+        };
+
+        // without a router, schema changes are rejected
+        let db = new_db(&catalog).await;
+        let ns = catalog.create_namespace_1hr_retention("ns1").await;
+        assert_matches!(
+            db.create_namespace("ns2", None).await,
+            Err(DataFusionError::NotImplemented(_))
+        );
+        assert_matches!(
+            db.delete("ns1", None, &predicate).await,
+            Err(DataFusionError::NotImplemented(_))
+        );
+
+        let router = Arc::new(MockRouterConnection::default());
+        let db = new_db(&catalog)
+            .await
+            .with_router_connection(Arc::clone(&router) as _);
+
+        db.create_namespace("ns2", Some(3_600_000_000_000))
+            .await
+            .unwrap();
+        db.create_namespace("bad name!", None).await.unwrap_err();
+        db.delete("ns1", Some("cpu"), &predicate).await.unwrap();
+        db.delete("ns1", None, &predicate).await.unwrap();
+        db.delete("ns3", None, &predicate).await.unwrap_err();
+
+        assert_eq!(
+            router.requests(),
+            vec![
+                MockRouterRequest::CreateNamespace {
+                    name: "ns2".to_owned(),
+                    retention_period_ns: Some(3_600_000_000_000),
+                },
+                MockRouterRequest::Delete {
+                    namespace_id: ns.namespace.id,
+                    table_name: Some("cpu".to_owned()),
+                    predicate: predicate.clone(),
+                },
+                MockRouterRequest::Delete {
+                    namespace_id: ns.namespace.id,
+                    table_name: None,
+                    predicate,
+                },
+            ]
+        );
+
+        // the querier itself does not write to the catalog
+        assert_eq!(db.namespace_names().await.unwrap(), vec!["ns1".to_owned()]);
+    }
+
     async fn new_db(catalog: &Arc<TestCatalog>) -> QuerierDatabase {
         let catalog_cache = Arc::new(CatalogCache::new_testing(
             catalog.catalog(),
@@ -22,6 +22,7 @@ mod ingester;
 mod namespace;
 mod parquet;
 mod query_log;
+mod router;
 mod server;
 mod system_tables;
 mod table;
@@ -31,9 +32,13 @@ mod table;
 /// This is mostly to fetch per-partition data concurrently.
 const CONCURRENT_CHUNK_CREATION_JOBS: usize = 100;
 
-pub use cache::CatalogCache as QuerierCatalogCache;
+pub use cache::{CatalogCache as QuerierCatalogCache, DiskCacheConfig as QuerierDiskCacheConfig};
 pub use database::{Error as QuerierDatabaseError, QuerierDatabase};
 pub use ingester::{create_ingester_connection_for_testing, create_ingester_connections};
 pub use namespace::QuerierNamespace;
-pub use query_log::QueryLogEntry;
+pub use query_log::{
+    NamespaceWriter as QueryLogNamespaceWriter, QueryLogEntry, QueryLogSink, QueryLogStats,
+    QueryLogWriter,
+};
+pub use router::{GrpcRouterConnection, RouterConnection, RouterError};
 pub use server::QuerierServer;
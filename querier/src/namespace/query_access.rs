@@ -1,6 +1,7 @@
 //! This module contains implementations of [`iox_query`] interfaces for [QuerierNamespace].
 
 use crate::{
+    cache::CatalogCache,
     namespace::QuerierNamespace,
     query_log::QueryLog,
     system_tables::{SystemSchemaProvider, SYSTEM_SCHEMA},
@@ -48,6 +49,7 @@ impl QueryNamespace for QuerierNamespace {
                 filters,
                 ctx.child_span("QuerierNamespace chunks"),
                 projection,
+                Some(&ctx.query_stats()),
             )
             .await?;
 
@@ -71,7 +73,7 @@ impl QueryNamespace for QuerierNamespace {
         let query_log = Arc::clone(&self.query_log);
         let trace_id = span_ctx.map(|ctx| ctx.trace_id);
         let entry = query_log.push(self.id, query_type, query_text, trace_id);
-        QueryCompletedToken::new(move |success| query_log.set_completed(entry, success))
+        QueryCompletedToken::new(move |completion| query_log.set_completed(entry, completion))
     }
 
     fn new_query_context(&self, span_ctx: Option<SpanContext>) -> IOxSessionContext {
@@ -81,6 +83,13 @@ impl QueryNamespace for QuerierNamespace {
             .with_default_catalog(Arc::new(QuerierCatalogProvider::from_namespace(self)) as _)
             .with_span_context(span_ctx);
 
+        // record what the query reads through the cached object store
+        let object_store = self
+            .catalog_cache
+            .object_store()
+            .object_store_for_query(cfg.query_stats());
+        cfg = cfg.with_object_store(self.catalog_cache.parquet_store().id(), object_store);
+
         for (k, v) in self.datafusion_config.as_ref() {
             cfg = cfg.with_config_option(k, v);
         }
@@ -93,12 +102,18 @@ pub struct QuerierCatalogProvider {
     /// Namespace ID.
     namespace_id: NamespaceId,
 
+    /// Namespace name.
+    namespace_name: Arc<str>,
+
     /// A snapshot of all tables.
     tables: Arc<HashMap<Arc<str>, Arc<QuerierTable>>>,
 
     /// Query log.
     query_log: Arc<QueryLog>,
 
+    /// Catalog cache.
+    catalog_cache: Arc<CatalogCache>,
+
     /// Include debug info tables.
     include_debug_info_tables: bool,
 }
@@ -107,8 +122,10 @@ impl QuerierCatalogProvider {
     fn from_namespace(namespace: &QuerierNamespace) -> Self {
         Self {
             namespace_id: namespace.id,
+            namespace_name: Arc::clone(&namespace.name),
             tables: Arc::clone(&namespace.tables),
             query_log: Arc::clone(&namespace.query_log),
+            catalog_cache: Arc::clone(&namespace.catalog_cache),
             include_debug_info_tables: namespace.include_debug_info_tables,
         }
     }
@@ -130,7 +147,9 @@ impl CatalogProvider for QuerierCatalogProvider {
             })),
             SYSTEM_SCHEMA => Some(Arc::new(SystemSchemaProvider::new(
                 Arc::clone(&self.query_log),
+                Arc::clone(&self.catalog_cache),
                 self.namespace_id,
+                Arc::clone(&self.namespace_name),
                 self.include_debug_info_tables,
             ))),
             _ => None,
@@ -171,7 +190,7 @@ mod tests {
     use crate::namespace::test_util::{clear_parquet_cache, querier_namespace};
     use arrow::record_batch::RecordBatch;
     use arrow_util::test_util::{batches_to_sorted_lines, Normalizer};
-    use data_types::ColumnType;
+    use data_types::{ColumnType, CompactionLevel};
     use datafusion::common::DataFusionError;
     use iox_query::frontend::sql::SqlQueryPlanner;
     use iox_tests::{TestCatalog, TestParquetFileBuilder};
@@ -529,6 +548,152 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_system_tables() {
+        test_helpers::maybe_start_logging();
+
+        let catalog = TestCatalog::new();
+        let ns = catalog.create_namespace_with_retention("ns", None).await;
+
+        let table_cpu = ns.create_table("cpu").await;
+        let table_mem = ns.create_table("mem").await;
+        table_cpu.create_column("host", ColumnType::Tag).await;
+        table_cpu.create_column("time", ColumnType::Time).await;
+        table_cpu.create_column("load", ColumnType::F64).await;
+        table_mem.create_column("time", ColumnType::Time).await;
+
+        let partition_cpu_a = table_cpu.create_partition("a").await;
+        let builder = TestParquetFileBuilder::default()
+            .with_line_protocol("cpu,host=a load=1 11\ncpu,host=b load=2 12")
+            .with_min_time(11)
+            .with_max_time(12);
+        partition_cpu_a.create_parquet_file(builder).await;
+        let builder = TestParquetFileBuilder::default()
+            .with_line_protocol("cpu,host=a load=3 33")
+            .with_min_time(33)
+            .with_max_time(33)
+            .with_compaction_level(CompactionLevel::FileNonOverlapped);
+        partition_cpu_a.create_parquet_file(builder).await;
+
+        catalog
+            .catalog
+            .repositories()
+            .await
+            .partitions()
+            .record_skipped_compaction(
+                partition_cpu_a.partition.id,
+                "over memory budget",
+                2,
+                1,
+                1,
+                1000,
+                100,
+            )
+            .await
+            .unwrap();
+
+        // skipped compactions of other namespaces are not shown
+        let other_ns = catalog.create_namespace_with_retention("other", None).await;
+        let other_partition = other_ns
+            .create_table("cpu")
+            .await
+            .create_partition("b")
+            .await;
+        catalog
+            .catalog
+            .repositories()
+            .await
+            .partitions()
+            .record_skipped_compaction(
+                other_partition.partition.id,
+                "too many files",
+                3,
+                1,
+                1,
+                10,
+                1,
+            )
+            .await
+            .unwrap();
+
+        let querier_namespace = Arc::new(querier_namespace(&ns).await);
+
+        insta::assert_yaml_snapshot!(
+            format_query(
+                &querier_namespace,
+                "SELECT table_name, column_count, parquet_file_count, row_count FROM system.tables",
+            ).await,
+            @r###"
+        ---
+        - +------------+--------------+--------------------+-----------+
+        - "| table_name | column_count | parquet_file_count | row_count |"
+        - +------------+--------------+--------------------+-----------+
+        - "| cpu        | 3            | 2                  | 3         |"
+        - "| mem        | 1            | 0                  | 0         |"
+        - +------------+--------------+--------------------+-----------+
+        "###
+        );
+
+        insta::assert_yaml_snapshot!(
+            format_query(
+                &querier_namespace,
+                "SELECT table_name, parquet_file_count, row_count, min_time, max_time, sort_key FROM system.partitions",
+            ).await,
+            @r###"
+        ---
+        - +------------+--------------------+-----------+--------------------------------+--------------------------------+----------+
+        - "| table_name | parquet_file_count | row_count | min_time                       | max_time                       | sort_key |"
+        - +------------+--------------------+-----------+--------------------------------+--------------------------------+----------+
+        - "| cpu        | 2                  | 3         | 1970-01-01T00:00:00.000000011Z | 1970-01-01T00:00:00.000000033Z |          |"
+        - +------------+--------------------+-----------+--------------------------------+--------------------------------+----------+
+        "###
+        );
+
+        insta::assert_yaml_snapshot!(
+            format_query(
+                &querier_namespace,
+                "SELECT table_name, compaction_level, row_count, min_time, max_time FROM system.parquet_files",
+            ).await,
+            @r###"
+        ---
+        - +------------+------------------+-----------+--------------------------------+--------------------------------+
+        - "| table_name | compaction_level | row_count | min_time                       | max_time                       |"
+        - +------------+------------------+-----------+--------------------------------+--------------------------------+
+        - "| cpu        | 0                | 2         | 1970-01-01T00:00:00.000000011Z | 1970-01-01T00:00:00.000000012Z |"
+        - "| cpu        | 1                | 1         | 1970-01-01T00:00:00.000000033Z | 1970-01-01T00:00:00.000000033Z |"
+        - +------------+------------------+-----------+--------------------------------+--------------------------------+
+        "###
+        );
+
+        insta::assert_yaml_snapshot!(
+            format_query(
+                &querier_namespace,
+                "SELECT table_name, partition_key, reason, num_files, limit_num_files, estimated_bytes, limit_bytes FROM system.compactor_skipped",
+            ).await,
+            @r###"
+        ---
+        - +------------+---------------+--------------------+-----------+-----------------+-----------------+-------------+
+        - "| table_name | partition_key | reason             | num_files | limit_num_files | estimated_bytes | limit_bytes |"
+        - +------------+---------------+--------------------+-----------+-----------------+-----------------+-------------+
+        - "| cpu        | a             | over memory budget | 2         | 1               | 1000            | 100         |"
+        - +------------+---------------+--------------------+-----------+-----------------+-----------------+-------------+
+        "###
+        );
+
+        insta::assert_yaml_snapshot!(
+            format_query(&querier_namespace, "SELECT pool, unit FROM system.caches").await,
+            @r###"
+        ---
+        - +--------------+-------+
+        - "| pool         | unit  |"
+        - +--------------+-------+
+        - "| ram_data     | bytes |"
+        - "| ram_metadata | bytes |"
+        - +--------------+-------+
+        "###
+        );
+    }
+
     async fn format_query(querier_namespace: &Arc<QuerierNamespace>, sql: &str) -> Vec<String> {
         format_query_with_span_ctx(querier_namespace, sql, None).await
     }
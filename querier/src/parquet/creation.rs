@@ -1,6 +1,6 @@
 use std::{collections::HashMap, sync::Arc};
 
-use data_types::{ChunkId, ChunkOrder, ColumnId, ParquetFile, TimestampMinMax};
+use data_types::{ChunkId, ChunkOrder, ColumnId, DeletePredicate, ParquetFile, TimestampMinMax};
 use datafusion::{physical_plan::Statistics, prelude::Expr};
 use futures::StreamExt;
 use hashbrown::HashSet;
@@ -130,6 +130,15 @@ impl ChunkAdapter {
             span_recorder.child_span("prune chunks"),
         );
 
+        let tombstones = self
+            .catalog_cache
+            .tombstone()
+            .get(
+                cached_table.id,
+                span_recorder.child_span("cache GET tombstones"),
+            )
+            .await;
+
         {
             let _span_recorder = span_recorder.child("finalize chunks");
 
@@ -137,7 +146,8 @@ impl ChunkAdapter {
                 .into_iter()
                 .map(|file| {
                     let cached_table = Arc::clone(&cached_table);
-                    self.new_chunk(cached_table, file)
+                    let delete_predicates = tombstones.predicates_for(&file.file);
+                    self.new_chunk(cached_table, file, delete_predicates)
                 })
                 .collect()
         }
@@ -197,6 +207,7 @@ impl ChunkAdapter {
         &self,
         cached_table: Arc<CachedTable>,
         parquet_file: PreparedParquetFileWithStats,
+        delete_predicates: Vec<Arc<DeletePredicate>>,
     ) -> QuerierParquetChunk {
         let PreparedParquetFileWithStats {
             file,
@@ -256,6 +267,7 @@ impl ChunkAdapter {
         ));
 
         QuerierParquetChunk::new(parquet_chunk, meta, stats)
+            .with_delete_predicates(delete_predicates)
     }
 }
 
@@ -1,6 +1,6 @@
 //! Querier Chunks
 
-use data_types::{ChunkId, ChunkOrder, TransitionPartitionId};
+use data_types::{ChunkId, ChunkOrder, DeletePredicate, ParquetFile, TransitionPartitionId};
 use datafusion::physical_plan::Statistics;
 use parquet_file::chunk::ParquetChunk;
 use schema::sort::SortKey;
@@ -54,6 +54,9 @@ pub struct QuerierParquetChunk {
 
     /// Stats
     stats: Arc<Statistics>,
+
+    /// Delete predicates of tombstones that were created after this file.
+    delete_predicates: Vec<Arc<DeletePredicate>>,
 }
 
 impl QuerierParquetChunk {
@@ -67,6 +70,16 @@ impl QuerierParquetChunk {
             meta,
             parquet_chunk,
             stats,
+            delete_predicates: vec![],
+        }
+    }
+
+    /// Set the delete predicates that have to be applied to the rows of this
+    /// chunk.
+    pub fn with_delete_predicates(self, delete_predicates: Vec<Arc<DeletePredicate>>) -> Self {
+        Self {
+            delete_predicates,
+            ..self
         }
     }
 
@@ -74,6 +87,11 @@ impl QuerierParquetChunk {
     pub fn meta(&self) -> &QuerierParquetChunkMeta {
         self.meta.as_ref()
     }
+
+    /// Catalog entry of the parquet file.
+    pub fn parquet_file(&self) -> &Arc<ParquetFile> {
+        self.parquet_chunk.parquet_file()
+    }
 }
 
 #[cfg(test)]
@@ -90,7 +108,7 @@ pub mod tests {
     use super::*;
     use arrow::{datatypes::DataType, record_batch::RecordBatch};
     use arrow_util::assert_batches_eq;
-    use data_types::{ColumnType, ParquetFile, SortedColumnSet};
+    use data_types::{ColumnType, SortedColumnSet};
     use datafusion_util::config::register_iox_object_store;
     use iox_query::{
         exec::{ExecutorType, IOxSessionContext},
@@ -1,7 +1,11 @@
 //! Ring buffer of queries that have been run with some brief information
 
 use data_types::NamespaceId;
-use iox_query::QueryText;
+use datafusion::physical_plan::ExecutionPlan;
+use iox_query::{
+    exec::query_stats::{rows_scanned, QueryStats},
+    QueryCompletion, QueryText,
+};
 use iox_time::{Time, TimeProvider};
 use observability_deps::tracing::warn;
 use parking_lot::Mutex;
@@ -12,6 +16,10 @@ use std::{
 };
 use trace::ctx::TraceId;
 
+mod sink;
+
+pub use sink::{NamespaceWriter, QueryLogSink, QueryLogWriter};
+
 /// The query duration used for queries still running.
 const UNCOMPLETED_DURATION: i64 = -1;
 
@@ -38,6 +46,64 @@ pub struct QueryLogEntry {
 
     /// If the query completed successfully
     pub success: atomic::AtomicBool,
+
+    /// Statistics, available once a planned query completed.
+    pub(crate) stats: Mutex<Option<QueryLogStats>>,
+
+    /// The error that made the query fail, if known.
+    pub(crate) error: Mutex<Option<String>>,
+}
+
+/// Statistics about a single completed query.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueryLogStats {
+    /// Rows read from parquet files and ingester data.
+    pub rows_scanned: u64,
+
+    /// Bytes of parquet files that had to be read from the object store.
+    pub bytes_from_object_store: u64,
+
+    /// Bytes of parquet files that were served by the RAM or disk cache.
+    pub bytes_from_cache: u64,
+
+    /// Partitions that were scanned.
+    pub partitions_scanned: u64,
+
+    /// Partitions that were pruned during planning.
+    pub partitions_pruned: u64,
+
+    /// Parquet files that were scanned.
+    pub parquet_files_scanned: u64,
+
+    /// Parquet files that were pruned during planning.
+    pub parquet_files_pruned: u64,
+
+    /// Latency of the slowest ingester request, if the ingesters were queried.
+    pub ingester_latency: Option<Duration>,
+
+    /// Peak memory reserved by the query in the memory pool, in bytes.
+    pub max_memory: u64,
+}
+
+impl QueryLogStats {
+    /// Gather statistics from an executed `plan` and the `stats` recorded while it was planned and executed.
+    fn new(plan: &dyn ExecutionPlan, stats: Option<&QueryStats>) -> Self {
+        let mut res = Self {
+            rows_scanned: rows_scanned(plan),
+            ..Default::default()
+        };
+        if let Some(stats) = stats {
+            res.bytes_from_object_store = stats.bytes_from_object_store();
+            res.bytes_from_cache = stats.bytes_from_cache();
+            res.partitions_scanned = stats.partitions_scanned();
+            res.partitions_pruned = stats.partitions_pruned();
+            res.parquet_files_scanned = stats.parquet_files_scanned();
+            res.parquet_files_pruned = stats.parquet_files_pruned();
+            res.ingester_latency = stats.ingester_latency();
+            res.max_memory = stats.peak_memory();
+        }
+        res
+    }
 }
 
 impl std::fmt::Debug for QueryLogEntry {
@@ -48,6 +114,8 @@ impl std::fmt::Debug for QueryLogEntry {
             .field("issue_time", &self.issue_time)
             .field("query_completed_duration", &self.query_completed_duration)
             .field("success", &self.success)
+            .field("stats", &self.stats)
+            .field("error", &self.error)
             .finish()
     }
 }
@@ -69,6 +137,8 @@ impl QueryLogEntry {
             issue_time,
             query_completed_duration: UNCOMPLETED_DURATION.into(),
             success: atomic::AtomicBool::new(false),
+            stats: Mutex::new(None),
+            error: Mutex::new(None),
         }
     }
 
@@ -89,6 +159,16 @@ impl QueryLogEntry {
         self.success.load(atomic::Ordering::SeqCst)
     }
 
+    /// Statistics of the query, if it was planned and has completed.
+    pub fn stats(&self) -> Option<QueryLogStats> {
+        *self.stats.lock()
+    }
+
+    /// The error that made the query fail, if known.
+    pub fn error(&self) -> Option<String> {
+        self.error.lock().clone()
+    }
+
     /// Mark this entry complete as of `now`. `success` records if the
     /// entry is successful or not.
     pub fn set_completed(&self, now: Time, success: bool) {
@@ -107,11 +187,14 @@ impl QueryLogEntry {
 
 /// Stores a fixed number `QueryExecutions` -- handles locking
 /// internally so can be shared across multiple
+///
+/// Completed entries are additionally passed to the [`QueryLogSink`], if any.
 #[derive(Debug)]
 pub struct QueryLog {
     log: Mutex<VecDeque<Arc<QueryLogEntry>>>,
     max_size: usize,
     time_provider: Arc<dyn TimeProvider>,
+    sink: Option<QueryLogSink>,
 }
 
 impl QueryLog {
@@ -122,6 +205,15 @@ impl QueryLog {
             log: Mutex::new(VecDeque::with_capacity(max_size)),
             max_size,
             time_provider,
+            sink: None,
+        }
+    }
+
+    /// Pass completed entries to `sink`.
+    pub fn with_sink(self, sink: QueryLogSink) -> Self {
+        Self {
+            sink: Some(sink),
+            ..self
         }
     }
 
@@ -161,14 +253,34 @@ impl QueryLog {
     }
 
     /// Marks the provided query entry as completed using the current time.
-    /// `success` specifies the query ran successfully
-    pub fn set_completed(&self, entry: Arc<QueryLogEntry>, success: bool) {
-        entry.set_completed(self.time_provider.now(), success)
+    ///
+    /// Statistics are gathered from the plan of the `completion`, if the query was planned.
+    pub fn set_completed(&self, entry: Arc<QueryLogEntry>, completion: QueryCompletion) {
+        let QueryCompletion {
+            success,
+            error,
+            plan,
+            stats,
+        } = completion;
+
+        if let Some(plan) = plan {
+            *entry.stats.lock() = Some(QueryLogStats::new(plan.as_ref(), stats.as_deref()));
+        }
+        if let Some(error) = error {
+            *entry.error.lock() = Some(error);
+        }
+        entry.set_completed(self.time_provider.now(), success);
+
+        if let Some(sink) = &self.sink {
+            sink.push(entry);
+        }
     }
 }
 
 #[cfg(test)]
 mod test_super {
+    use arrow::datatypes::Schema;
+    use datafusion::physical_plan::empty::EmptyExec;
     use iox_time::MockProvider;
 
     use super::*;
@@ -205,4 +317,60 @@ mod test_super {
         );
         assert!(!entry.success());
     }
+
+    #[test]
+    fn test_query_log_set_completed() {
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_millis(100).unwrap()));
+        let query_log = QueryLog::new(10, Arc::clone(&time_provider) as _);
+
+        // not planned
+        let entry = query_log.push(NamespaceId::new(1), "sql", Box::new("SELEC 1"), None);
+        query_log.set_completed(
+            Arc::clone(&entry),
+            QueryCompletion {
+                error: Some(String::from("syntax error")),
+                ..Default::default()
+            },
+        );
+        assert!(!entry.success());
+        assert_eq!(entry.error().as_deref(), Some("syntax error"));
+        assert_eq!(entry.stats(), None);
+
+        // planned
+        let entry = query_log.push(NamespaceId::new(1), "sql", Box::new("SELECT 1"), None);
+        let stats = Arc::new(QueryStats::default());
+        stats.record_partitions(1, 2);
+        stats.record_parquet_files(3, 4);
+        stats.record_bytes(5, 6);
+        time_provider.inc(Duration::from_millis(5));
+        query_log.set_completed(
+            Arc::clone(&entry),
+            QueryCompletion {
+                success: true,
+                error: None,
+                plan: Some(Arc::new(EmptyExec::new(false, Arc::new(Schema::empty())))),
+                stats: Some(stats),
+            },
+        );
+        assert!(entry.success());
+        assert_eq!(
+            entry.query_completed_duration(),
+            Some(Duration::from_millis(5))
+        );
+        assert_eq!(entry.error(), None);
+        assert_eq!(
+            entry.stats(),
+            Some(QueryLogStats {
+                rows_scanned: 0,
+                bytes_from_object_store: 6,
+                bytes_from_cache: 5,
+                partitions_scanned: 1,
+                partitions_pruned: 2,
+                parquet_files_scanned: 3,
+                parquet_files_pruned: 4,
+                ingester_latency: None,
+                max_memory: 0,
+            })
+        );
+    }
 }
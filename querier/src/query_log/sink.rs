@@ -0,0 +1,294 @@
+//! Writes completed queries as line protocol into an IOx namespace, so they survive restarts and can be analyzed with
+//! regular queries.
+
+use super::QueryLogEntry;
+use async_trait::async_trait;
+use client_util::connection::Connection;
+use influxdb_iox_client::write::Client;
+use influxdb_line_protocol::builder::LineProtocolBuilder;
+use metric::U64Counter;
+use observability_deps::tracing::warn;
+use std::{sync::Arc, time::Duration};
+use tokio::{sync::mpsc, time::MissedTickBehavior};
+
+/// Measurement that completed queries are written to.
+const MEASUREMENT: &str = "queries";
+
+/// Number of buffered entries that triggers a write.
+const MAX_BATCH_SIZE: usize = 1_000;
+
+/// Interval at which buffered entries are written.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Number of entries that may wait for the background task before new entries are dropped.
+const CHANNEL_SIZE: usize = 10_000;
+
+/// Destination of the line protocol produced by a [`QueryLogSink`].
+#[async_trait]
+pub trait QueryLogWriter: std::fmt::Debug + Send + Sync + 'static {
+    /// Write line protocol.
+    async fn write_lp(&self, lp: String) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+}
+
+/// [`QueryLogWriter`] that writes to an IOx namespace via the HTTP write API of a router.
+#[derive(Debug)]
+pub struct NamespaceWriter {
+    client: Client,
+    namespace: String,
+}
+
+impl NamespaceWriter {
+    /// Create new writer.
+    ///
+    /// The `namespace` uses the `<org>_<bucket>` form of the write API.
+    pub fn new(connection: Connection, namespace: impl Into<String>) -> Self {
+        Self {
+            client: Client::new(connection),
+            namespace: namespace.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl QueryLogWriter for NamespaceWriter {
+    async fn write_lp(&self, lp: String) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.client.clone().write_lp(&self.namespace, lp).await?;
+        Ok(())
+    }
+}
+
+/// Writes completed [`QueryLogEntry`]s in batches to a [`QueryLogWriter`].
+///
+/// Writes happen in a background task so they never delay queries. If the writer cannot keep up, new entries are
+/// dropped. Failed writes are NOT retried.
+#[derive(Debug)]
+pub struct QueryLogSink {
+    tx: mpsc::Sender<Arc<QueryLogEntry>>,
+    dropped: U64Counter,
+}
+
+impl QueryLogSink {
+    /// Create new sink and start its background task.
+    ///
+    /// The background task writes the remaining entries and exits once the sink is dropped.
+    pub fn new(writer: Arc<dyn QueryLogWriter>, metric_registry: &metric::Registry) -> Self {
+        let metric = metric_registry.register_metric::<U64Counter>(
+            "query_log_sink_entries",
+            "Number of query log entries handled by the query log sink",
+        );
+        let written = metric.recorder(&[("result", "written")]);
+        let failed = metric.recorder(&[("result", "failed")]);
+        let dropped = metric.recorder(&[("result", "dropped")]);
+
+        let (tx, rx) = mpsc::channel(CHANNEL_SIZE);
+        tokio::spawn(run(rx, writer, written, failed));
+
+        Self { tx, dropped }
+    }
+
+    /// Queue completed entry for writing.
+    pub(crate) fn push(&self, entry: Arc<QueryLogEntry>) {
+        if self.tx.try_send(entry).is_err() {
+            self.dropped.inc(1);
+        }
+    }
+}
+
+async fn run(
+    mut rx: mpsc::Receiver<Arc<QueryLogEntry>>,
+    writer: Arc<dyn QueryLogWriter>,
+    written: U64Counter,
+    failed: U64Counter,
+) {
+    let mut buffer = Vec::with_capacity(MAX_BATCH_SIZE);
+    let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        let done = tokio::select! {
+            entry = rx.recv() => match entry {
+                Some(entry) => {
+                    buffer.push(entry);
+                    if buffer.len() < MAX_BATCH_SIZE {
+                        continue;
+                    }
+                    false
+                }
+                None => true,
+            },
+            _ = interval.tick() => false,
+        };
+
+        if !buffer.is_empty() {
+            let n = buffer.len() as u64;
+            match writer.write_lp(to_line_protocol(&buffer)).await {
+                Ok(()) => written.inc(n),
+                Err(e) => {
+                    warn!(%e, n, "cannot write query log");
+                    failed.inc(n);
+                }
+            }
+            buffer.clear();
+        }
+
+        if done {
+            return;
+        }
+    }
+}
+
+/// Convert entries to line protocol, one line per entry.
+fn to_line_protocol(entries: &[Arc<QueryLogEntry>]) -> String {
+    let mut builder = LineProtocolBuilder::new();
+
+    for entry in entries {
+        let mut line = builder
+            .measurement(MEASUREMENT)
+            .tag("namespace_id", &entry.namespace_id.get().to_string())
+            .tag("query_type", entry.query_type)
+            .field("query_text", entry.query_text.to_string().as_str())
+            .field("success", entry.success());
+
+        if let Some(duration) = entry.query_completed_duration() {
+            line = line.field("completed_duration_ns", duration.as_nanos() as u64);
+        }
+        if let Some(trace_id) = entry.trace_id {
+            line = line.field("trace_id", format!("{:x}", trace_id.0).as_str());
+        }
+        if let Some(error) = entry.error() {
+            line = line.field("error", error.as_str());
+        }
+        if let Some(stats) = entry.stats() {
+            line = line
+                .field("rows_scanned", stats.rows_scanned)
+                .field("bytes_from_object_store", stats.bytes_from_object_store)
+                .field("bytes_from_cache", stats.bytes_from_cache)
+                .field("partitions_scanned", stats.partitions_scanned)
+                .field("partitions_pruned", stats.partitions_pruned)
+                .field("parquet_files_scanned", stats.parquet_files_scanned)
+                .field("parquet_files_pruned", stats.parquet_files_pruned)
+                .field("max_memory", stats.max_memory);
+            if let Some(latency) = stats.ingester_latency {
+                line = line.field("ingester_latency_ns", latency.as_nanos() as u64);
+            }
+        }
+
+        builder = line
+            .timestamp(entry.issue_time.timestamp_nanos())
+            .close_line();
+    }
+
+    String::from_utf8(builder.build()).expect("line protocol is UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query_log::{QueryLog, QueryLogStats};
+    use data_types::NamespaceId;
+    use iox_time::{MockProvider, Time};
+    use metric::{Attributes, Metric};
+    use parking_lot::Mutex;
+    use trace::ctx::TraceId;
+
+    #[test]
+    fn test_to_line_protocol() {
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_nanos(1_000)));
+        let query_log = QueryLog::new(10, Arc::clone(&time_provider) as _);
+
+        let running = query_log.push(NamespaceId::new(1), "sql", Box::new("SELECT 1"), None);
+        let completed = query_log.push(
+            NamespaceId::new(2),
+            "influxql",
+            Box::new("SELECT \"f\" FROM m"),
+            Some(TraceId::new(0x45fe).unwrap()),
+        );
+        *completed.stats.lock() = Some(QueryLogStats {
+            rows_scanned: 10,
+            bytes_from_object_store: 100,
+            bytes_from_cache: 200,
+            partitions_scanned: 1,
+            partitions_pruned: 2,
+            parquet_files_scanned: 3,
+            parquet_files_pruned: 4,
+            ingester_latency: Some(Duration::from_nanos(5)),
+            max_memory: 1024,
+        });
+        *completed.error.lock() = Some(String::from("boom"));
+        completed.set_completed(Time::from_timestamp_nanos(3_000), false);
+
+        let lp = to_line_protocol(&[running, completed]);
+        assert_eq!(
+            lp.lines().collect::<Vec<_>>(),
+            vec![
+                r#"queries,namespace_id=1,query_type=sql query_text="SELECT 1",success=false 1000"#,
+                concat!(
+                    r#"queries,namespace_id=2,query_type=influxql "#,
+                    r#"query_text="SELECT \"f\" FROM m",success=false,completed_duration_ns=2000u,"#,
+                    r#"trace_id="45fe",error="boom",rows_scanned=10u,bytes_from_object_store=100u,"#,
+                    r#"bytes_from_cache=200u,partitions_scanned=1u,partitions_pruned=2u,"#,
+                    r#"parquet_files_scanned=3u,parquet_files_pruned=4u,max_memory=1024u,"#,
+                    r#"ingester_latency_ns=5u 1000"#,
+                ),
+            ],
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sink_writes_on_drop() {
+        let metric_registry = metric::Registry::new();
+        let writer = Arc::new(MockWriter::default());
+        let sink = QueryLogSink::new(Arc::clone(&writer) as _, &metric_registry);
+
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_nanos(0)));
+        let query_log = QueryLog::new(10, Arc::clone(&time_provider) as _).with_sink(sink);
+        let entry = query_log.push(NamespaceId::new(1), "sql", Box::new("SELECT 1"), None);
+        time_provider.inc(Duration::from_nanos(10));
+        query_log.set_completed(entry, Default::default());
+
+        // dropping the sink flushes the buffer
+        drop(query_log);
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while entry_count(&metric_registry, "written") == 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            writer.lp.lock().as_slice(),
+            [String::from(
+                "queries,namespace_id=1,query_type=sql query_text=\"SELECT 1\",success=false,completed_duration_ns=10u 0\n"
+            )],
+        );
+        assert_eq!(entry_count(&metric_registry, "written"), 1);
+        assert_eq!(entry_count(&metric_registry, "failed"), 0);
+        assert_eq!(entry_count(&metric_registry, "dropped"), 0);
+    }
+
+    #[derive(Debug, Default)]
+    struct MockWriter {
+        lp: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl QueryLogWriter for MockWriter {
+        async fn write_lp(
+            &self,
+            lp: String,
+        ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            self.lp.lock().push(lp);
+            Ok(())
+        }
+    }
+
+    fn entry_count(metric_registry: &metric::Registry, result: &'static str) -> u64 {
+        metric_registry
+            .get_instrument::<Metric<U64Counter>>("query_log_sink_entries")
+            .unwrap()
+            .get_observer(&Attributes::from(&[("result", result)]))
+            .unwrap()
+            .fetch()
+    }
+}
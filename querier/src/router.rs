@@ -0,0 +1,150 @@
+//! Forwards schema changes requested through the query APIs to a router, so the querier itself never modifies the
+//! catalog.
+
+use async_trait::async_trait;
+use client_util::connection::Connection;
+use data_types::{DeletePredicate, NamespaceId};
+use influxdb_iox_client::{delete, error::Error as ClientError, namespace};
+
+/// Error returned by a [`RouterConnection`].
+pub type RouterError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Connection to the service that performs schema changes on behalf of the querier.
+#[async_trait]
+pub trait RouterConnection: std::fmt::Debug + Send + Sync + 'static {
+    /// Create namespace.
+    ///
+    /// Creating a namespace that already exists is NOT an error.
+    async fn create_namespace(
+        &self,
+        name: &str,
+        retention_period_ns: Option<i64>,
+    ) -> Result<(), RouterError>;
+
+    /// Delete data matching `predicate` from the table, or from all tables of the namespace if `table_name` is
+    /// `None`.
+    async fn delete(
+        &self,
+        namespace_id: NamespaceId,
+        table_name: Option<&str>,
+        predicate: &DeletePredicate,
+    ) -> Result<(), RouterError>;
+}
+
+/// [`RouterConnection`] that uses the gRPC namespace and delete APIs of a router.
+#[derive(Debug)]
+pub struct GrpcRouterConnection {
+    namespace_client: namespace::Client,
+    delete_client: delete::Client,
+}
+
+impl GrpcRouterConnection {
+    /// Create new connection.
+    pub fn new(connection: Connection) -> Self {
+        Self {
+            namespace_client: namespace::Client::new(connection.clone()),
+            delete_client: delete::Client::new(connection),
+        }
+    }
+}
+
+#[async_trait]
+impl RouterConnection for GrpcRouterConnection {
+    async fn create_namespace(
+        &self,
+        name: &str,
+        retention_period_ns: Option<i64>,
+    ) -> Result<(), RouterError> {
+        match self
+            .namespace_client
+            .clone()
+            .create_namespace(name, retention_period_ns, None, None)
+            .await
+        {
+            Ok(_) | Err(ClientError::AlreadyExists(_)) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn delete(
+        &self,
+        namespace_id: NamespaceId,
+        table_name: Option<&str>,
+        predicate: &DeletePredicate,
+    ) -> Result<(), RouterError> {
+        // An empty table name deletes from all tables in the namespace.
+        self.delete_client
+            .clone()
+            .delete(
+                namespace_id.get(),
+                table_name.unwrap_or_default(),
+                predicate.clone().into(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod mock {
+    use super::*;
+    use parking_lot::Mutex;
+
+    /// Request received by a [`MockRouterConnection`].
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub(crate) enum MockRouterRequest {
+        CreateNamespace {
+            name: String,
+            retention_period_ns: Option<i64>,
+        },
+        Delete {
+            namespace_id: NamespaceId,
+            table_name: Option<String>,
+            predicate: DeletePredicate,
+        },
+    }
+
+    /// [`RouterConnection`] that records all requests.
+    #[derive(Debug, Default)]
+    pub(crate) struct MockRouterConnection {
+        requests: Mutex<Vec<MockRouterRequest>>,
+    }
+
+    impl MockRouterConnection {
+        /// Requests received so far.
+        pub(crate) fn requests(&self) -> Vec<MockRouterRequest> {
+            self.requests.lock().clone()
+        }
+    }
+
+    #[async_trait]
+    impl RouterConnection for MockRouterConnection {
+        async fn create_namespace(
+            &self,
+            name: &str,
+            retention_period_ns: Option<i64>,
+        ) -> Result<(), RouterError> {
+            self.requests
+                .lock()
+                .push(MockRouterRequest::CreateNamespace {
+                    name: name.to_owned(),
+                    retention_period_ns,
+                });
+            Ok(())
+        }
+
+        async fn delete(
+            &self,
+            namespace_id: NamespaceId,
+            table_name: Option<&str>,
+            predicate: &DeletePredicate,
+        ) -> Result<(), RouterError> {
+            self.requests.lock().push(MockRouterRequest::Delete {
+                namespace_id,
+                table_name: table_name.map(ToOwned::to_owned),
+                predicate: predicate.clone(),
+            });
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use crate::{
+    cache::CatalogCache,
+    system_tables::{split_batch, BatchIterator, IoxSystemTable},
+};
+use arrow::{
+    array::{ArrayRef, StringArray, UInt64Array},
+    datatypes::{DataType, Field, Schema, SchemaRef},
+    error::Result,
+    record_batch::RecordBatch,
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Implementation of system.caches table
+///
+/// Lists the resource pools that bound the querier caches. The pools are shared by all namespaces.
+#[derive(Debug)]
+pub(super) struct CachesTable {
+    schema: SchemaRef,
+    catalog_cache: Arc<CatalogCache>,
+}
+
+impl CachesTable {
+    pub(super) fn new(catalog_cache: Arc<CatalogCache>) -> Self {
+        Self {
+            schema: caches_schema(),
+            catalog_cache,
+        }
+    }
+}
+
+#[async_trait]
+impl IoxSystemTable for CachesTable {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    async fn scan(&self, batch_size: usize) -> Result<BatchIterator> {
+        let pools = self.catalog_cache.pool_usage();
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(pools.iter().map(|p| Some(p.pool)).collect::<StringArray>()),
+            Arc::new(pools.iter().map(|p| Some(p.unit)).collect::<StringArray>()),
+            Arc::new(pools.iter().map(|p| Some(p.limit)).collect::<UInt64Array>()),
+            Arc::new(pools.iter().map(|p| Some(p.used)).collect::<UInt64Array>()),
+        ];
+        let batch = RecordBatch::try_new(self.schema(), columns)?;
+
+        Ok(split_batch(batch, batch_size))
+    }
+}
+
+fn caches_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("pool", DataType::Utf8, false),
+        Field::new("unit", DataType::Utf8, false),
+        Field::new("limit", DataType::UInt64, false),
+        Field::new("used", DataType::UInt64, false),
+    ]))
+}
@@ -0,0 +1,155 @@
+use crate::system_tables::{snapshot::NamespaceSource, split_batch, BatchIterator, IoxSystemTable};
+use arrow::{
+    array::{ArrayRef, Int64Array, StringArray, TimestampNanosecondArray},
+    datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit},
+    error::{ArrowError, Result},
+    record_batch::RecordBatch,
+};
+use async_trait::async_trait;
+use data_types::{Partition, SkippedCompaction};
+use std::{collections::HashMap, sync::Arc};
+
+/// Implementation of system.compactor_skipped table
+///
+/// Skipped compactions are not cached by the querier, so every scan of this table queries the catalog for the skipped
+/// compactions of the namespace.
+#[derive(Debug)]
+pub(super) struct CompactorSkippedTable {
+    schema: SchemaRef,
+    source: NamespaceSource,
+}
+
+impl CompactorSkippedTable {
+    pub(super) fn new(source: NamespaceSource) -> Self {
+        Self {
+            schema: compactor_skipped_schema(),
+            source,
+        }
+    }
+
+    /// Skipped compactions of the namespace, together with their partition and table name.
+    async fn skipped_compactions(&self) -> Result<Vec<(Arc<str>, Partition, SkippedCompaction)>> {
+        let Some(namespace) = self.source.namespace().await else {
+            return Ok(vec![]);
+        };
+        let table_names = namespace
+            .tables
+            .iter()
+            .map(|(name, table)| (table.id, Arc::clone(name)))
+            .collect::<HashMap<_, _>>();
+
+        let catalog = self.source.catalog_cache().catalog();
+        let mut repos = catalog.repositories().await;
+        let skipped = repos
+            .partitions()
+            .list_skipped_compactions_by_namespace_id(namespace.id)
+            .await
+            .map_err(|e| ArrowError::ExternalError(Box::new(e)))?;
+        let mut partitions = repos
+            .partitions()
+            .get_by_id_batch(skipped.iter().map(|s| s.partition_id).collect())
+            .await
+            .map_err(|e| ArrowError::ExternalError(Box::new(e)))?
+            .into_iter()
+            .map(|p| (p.id, p))
+            .collect::<HashMap<_, _>>();
+
+        let mut rows = skipped
+            .into_iter()
+            .filter_map(|s| {
+                let partition = partitions.remove(&s.partition_id)?;
+                let table_name = table_names.get(&partition.table_id)?;
+                Some((Arc::clone(table_name), partition, s))
+            })
+            .collect::<Vec<_>>();
+        rows.sort_by(|(t1, p1, _), (t2, p2, _)| (t1, p1.id).cmp(&(t2, p2.id)));
+
+        Ok(rows)
+    }
+}
+
+#[async_trait]
+impl IoxSystemTable for CompactorSkippedTable {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    async fn scan(&self, batch_size: usize) -> Result<BatchIterator> {
+        let rows = self.skipped_compactions().await?;
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(
+                rows.iter()
+                    .map(|(table_name, _, _)| Some(table_name.as_ref()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|(_, p, _)| Some(p.id.get()))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|(_, p, _)| Some(p.partition_key.inner()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|(_, _, s)| Some(s.reason.as_str()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|(_, _, s)| Some(s.skipped_at.get()))
+                    .collect::<TimestampNanosecondArray>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|(_, _, s)| Some(s.estimated_bytes))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|(_, _, s)| Some(s.limit_bytes))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|(_, _, s)| Some(s.num_files))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|(_, _, s)| Some(s.limit_num_files))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|(_, _, s)| Some(s.limit_num_files_first_in_partition))
+                    .collect::<Int64Array>(),
+            ),
+        ];
+        let batch = RecordBatch::try_new(self.schema(), columns)?;
+
+        Ok(split_batch(batch, batch_size))
+    }
+}
+
+fn compactor_skipped_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("table_name", DataType::Utf8, false),
+        Field::new("partition_id", DataType::Int64, false),
+        Field::new("partition_key", DataType::Utf8, false),
+        Field::new("reason", DataType::Utf8, false),
+        Field::new(
+            "skipped_at",
+            DataType::Timestamp(TimeUnit::Nanosecond, None),
+            false,
+        ),
+        Field::new("estimated_bytes", DataType::Int64, false),
+        Field::new("limit_bytes", DataType::Int64, false),
+        Field::new("num_files", DataType::Int64, false),
+        Field::new("limit_num_files", DataType::Int64, false),
+        Field::new("limit_num_files_first_in_partition", DataType::Int64, false),
+    ]))
+}
@@ -1,9 +1,8 @@
-use crate::query_log::QueryLog;
+use crate::{cache::CatalogCache, query_log::QueryLog};
 use arrow::{datatypes::SchemaRef, error::Result as ArrowResult, record_batch::RecordBatch};
 use async_trait::async_trait;
 use data_types::NamespaceId;
-use datafusion::error::DataFusionError;
-use datafusion::physical_plan::{DisplayAs, DisplayFormatType};
+use datafusion::physical_plan::{stream::RecordBatchStreamAdapter, DisplayAs, DisplayFormatType};
 use datafusion::{
     catalog::schema::SchemaProvider,
     datasource::TableProvider,
@@ -11,24 +10,31 @@ use datafusion::{
     execution::context::{SessionState, TaskContext},
     logical_expr::TableType,
     physical_plan::{
-        expressions::PhysicalSortExpr, ExecutionPlan, Partitioning, RecordBatchStream,
-        SendableRecordBatchStream, Statistics,
+        expressions::PhysicalSortExpr, ExecutionPlan, Partitioning, SendableRecordBatchStream,
+        Statistics,
     },
     prelude::Expr,
 };
+use futures::{StreamExt, TryStreamExt};
 use std::collections::HashMap;
-use std::{
-    any::Any,
-    pin::Pin,
-    sync::Arc,
-    task::{Context, Poll},
-};
+use std::{any::Any, sync::Arc};
 
+mod caches;
+mod compactor_skipped;
+mod parquet_files;
+mod partitions;
 mod queries;
+mod snapshot;
+mod tables;
 
 pub const SYSTEM_SCHEMA: &str = "system";
 
 const QUERIES_TABLE: &str = "queries";
+const TABLES_TABLE: &str = "tables";
+const PARTITIONS_TABLE: &str = "partitions";
+const PARQUET_FILES_TABLE: &str = "parquet_files";
+const COMPACTOR_SKIPPED_TABLE: &str = "compactor_skipped";
+const CACHES_TABLE: &str = "caches";
 
 pub struct SystemSchemaProvider {
     tables: HashMap<&'static str, Arc<dyn TableProvider>>,
@@ -37,7 +43,9 @@ pub struct SystemSchemaProvider {
 impl SystemSchemaProvider {
     pub fn new(
         query_log: Arc<QueryLog>,
+        catalog_cache: Arc<CatalogCache>,
         namespace_id: NamespaceId,
+        namespace_name: Arc<str>,
         include_debug_info: bool,
     ) -> Self {
         let mut tables: HashMap<&'static str, Arc<dyn TableProvider>> = HashMap::new();
@@ -47,6 +55,38 @@ impl SystemSchemaProvider {
                 table: Arc::new(queries::QueriesTable::new(query_log, Some(namespace_id))),
             });
             tables.insert(QUERIES_TABLE, queries);
+
+            let source = snapshot::NamespaceSource::new(Arc::clone(&catalog_cache), namespace_name);
+            tables.insert(
+                TABLES_TABLE,
+                Arc::new(SystemTableProvider {
+                    table: Arc::new(tables::TablesTable::new(source.clone())),
+                }),
+            );
+            tables.insert(
+                PARTITIONS_TABLE,
+                Arc::new(SystemTableProvider {
+                    table: Arc::new(partitions::PartitionsTable::new(source.clone())),
+                }),
+            );
+            tables.insert(
+                PARQUET_FILES_TABLE,
+                Arc::new(SystemTableProvider {
+                    table: Arc::new(parquet_files::ParquetFilesTable::new(source.clone())),
+                }),
+            );
+            tables.insert(
+                COMPACTOR_SKIPPED_TABLE,
+                Arc::new(SystemTableProvider {
+                    table: Arc::new(compactor_skipped::CompactorSkippedTable::new(source)),
+                }),
+            );
+            tables.insert(
+                CACHES_TABLE,
+                Arc::new(SystemTableProvider {
+                    table: Arc::new(caches::CachesTable::new(catalog_cache)),
+                }),
+            );
         }
 
         Self { tables }
@@ -81,12 +121,32 @@ impl SchemaProvider for SystemSchemaProvider {
 type BatchIterator = Box<dyn Iterator<Item = ArrowResult<RecordBatch>> + Send + Sync>;
 
 /// The minimal thing that a system table needs to implement
+#[async_trait]
 trait IoxSystemTable: Send + Sync {
     /// Produce the schema from this system table
     fn schema(&self) -> SchemaRef;
 
     /// Get the contents of the system table
-    fn scan(&self, batch_size: usize) -> ArrowResult<BatchIterator>;
+    ///
+    /// This is called when the table is scanned during query execution, so the content reflects the state at that
+    /// point in time.
+    async fn scan(&self, batch_size: usize) -> ArrowResult<BatchIterator>;
+}
+
+/// Split `batch` into batches of at most `batch_size` rows.
+fn split_batch(batch: RecordBatch, batch_size: usize) -> BatchIterator {
+    let batch_size = batch_size.max(1);
+    let mut offset = 0;
+    Box::new(std::iter::from_fn(move || {
+        if offset >= batch.num_rows() {
+            return None;
+        }
+
+        let len = batch_size.min(batch.num_rows() - offset);
+        let slice = batch.slice(offset, len);
+        offset += len;
+        Some(Ok(slice))
+    }))
 }
 
 /// Adapter that makes any `IoxSystemTable` a DataFusion `TableProvider`
@@ -178,12 +238,24 @@ impl<T: IoxSystemTable + 'static> ExecutionPlan for SystemTableExecutionPlan<T>
         context: Arc<TaskContext>,
     ) -> DataFusionResult<SendableRecordBatchStream> {
         let batch_size = context.session_config().batch_size();
+        let table = Arc::clone(&self.table);
+        let projection = self.projection.clone();
+
+        let stream = futures::stream::once(async move { table.scan(batch_size).await })
+            .map_ok(futures::stream::iter)
+            .try_flatten()
+            .map(move |maybe_batch| -> DataFusionResult<RecordBatch> {
+                let batch = maybe_batch?;
+                match &projection {
+                    Some(projection) => Ok(batch.project(projection)?),
+                    None => Ok(batch),
+                }
+            });
 
-        Ok(Box::pin(SystemTableStream {
-            projected_schema: Arc::clone(&self.projected_schema),
-            batches: self.table.scan(batch_size)?,
-            projection: self.projection.clone(),
-        }))
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            Arc::clone(&self.projected_schema),
+            stream,
+        )))
     }
 
     fn statistics(&self) -> Statistics {
@@ -201,29 +273,3 @@ impl<T> DisplayAs for SystemTableExecutionPlan<T> {
         }
     }
 }
-
-struct SystemTableStream {
-    projected_schema: SchemaRef,
-    projection: Option<Vec<usize>>,
-    batches: BatchIterator,
-}
-
-impl RecordBatchStream for SystemTableStream {
-    fn schema(&self) -> SchemaRef {
-        Arc::clone(&self.projected_schema)
-    }
-}
-
-impl futures::Stream for SystemTableStream {
-    type Item = Result<RecordBatch, DataFusionError>;
-
-    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        Poll::Ready(self.batches.next().map(|maybe_batch| {
-            let batch = maybe_batch?;
-            match &self.projection {
-                Some(projection) => Ok(batch.project(projection)?),
-                None => Ok(batch),
-            }
-        }))
-    }
-}
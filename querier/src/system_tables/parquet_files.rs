@@ -0,0 +1,121 @@
+use crate::system_tables::{snapshot::NamespaceSource, split_batch, BatchIterator, IoxSystemTable};
+use arrow::{
+    array::{ArrayRef, Int16Array, Int64Array, StringArray, TimestampNanosecondArray},
+    datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit},
+    error::Result,
+    record_batch::RecordBatch,
+};
+use async_trait::async_trait;
+use data_types::ParquetFile;
+use std::sync::Arc;
+
+/// Implementation of system.parquet_files table
+#[derive(Debug)]
+pub(super) struct ParquetFilesTable {
+    schema: SchemaRef,
+    source: NamespaceSource,
+}
+
+impl ParquetFilesTable {
+    pub(super) fn new(source: NamespaceSource) -> Self {
+        Self {
+            schema: parquet_files_schema(),
+            source,
+        }
+    }
+}
+
+#[async_trait]
+impl IoxSystemTable for ParquetFilesTable {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    async fn scan(&self, batch_size: usize) -> Result<BatchIterator> {
+        let mut rows: Vec<(Arc<str>, Arc<ParquetFile>)> = vec![];
+        for table in self.source.tables().await {
+            let mut files = table.files.files.to_vec();
+            files.sort_by(|a, b| {
+                (&a.partition_id, a.min_time, a.id).cmp(&(&b.partition_id, b.min_time, b.id))
+            });
+            rows.extend(files.into_iter().map(|f| (Arc::clone(&table.name), f)));
+        }
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(
+                rows.iter()
+                    .map(|(table_name, _)| Some(table_name.as_ref()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|(_, f)| Some(f.partition_id.to_string()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|(_, f)| Some(f.object_store_id.to_string()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|(_, f)| Some(f.compaction_level as i16))
+                    .collect::<Int16Array>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|(_, f)| Some(f.row_count))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|(_, f)| Some(f.file_size_bytes))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|(_, f)| Some(f.min_time.get()))
+                    .collect::<TimestampNanosecondArray>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|(_, f)| Some(f.max_time.get()))
+                    .collect::<TimestampNanosecondArray>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|(_, f)| Some(f.created_at.get()))
+                    .collect::<TimestampNanosecondArray>(),
+            ),
+        ];
+        let batch = RecordBatch::try_new(self.schema(), columns)?;
+
+        Ok(split_batch(batch, batch_size))
+    }
+}
+
+fn parquet_files_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("table_name", DataType::Utf8, false),
+        Field::new("partition_id", DataType::Utf8, false),
+        Field::new("object_store_id", DataType::Utf8, false),
+        Field::new("compaction_level", DataType::Int16, false),
+        Field::new("row_count", DataType::Int64, false),
+        Field::new("size_bytes", DataType::Int64, false),
+        Field::new(
+            "min_time",
+            DataType::Timestamp(TimeUnit::Nanosecond, None),
+            false,
+        ),
+        Field::new(
+            "max_time",
+            DataType::Timestamp(TimeUnit::Nanosecond, None),
+            false,
+        ),
+        Field::new(
+            "created_at",
+            DataType::Timestamp(TimeUnit::Nanosecond, None),
+            false,
+        ),
+    ]))
+}
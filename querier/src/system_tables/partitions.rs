@@ -0,0 +1,150 @@
+use crate::system_tables::{snapshot::NamespaceSource, split_batch, BatchIterator, IoxSystemTable};
+use arrow::{
+    array::{ArrayRef, Int64Array, StringArray, TimestampNanosecondArray},
+    datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit},
+    error::Result,
+    record_batch::RecordBatch,
+};
+use async_trait::async_trait;
+use data_types::TransitionPartitionId;
+use std::{collections::BTreeMap, sync::Arc};
+
+/// Implementation of system.partitions table
+///
+/// Only partitions that have parquet files are listed.
+#[derive(Debug)]
+pub(super) struct PartitionsTable {
+    schema: SchemaRef,
+    source: NamespaceSource,
+}
+
+impl PartitionsTable {
+    pub(super) fn new(source: NamespaceSource) -> Self {
+        Self {
+            schema: partitions_schema(),
+            source,
+        }
+    }
+}
+
+/// Aggregated parquet file information of a single partition.
+#[derive(Debug)]
+struct PartitionRow {
+    table_name: Arc<str>,
+    partition_id: TransitionPartitionId,
+    parquet_file_count: i64,
+    row_count: i64,
+    size_bytes: i64,
+    min_time: i64,
+    max_time: i64,
+    sort_key: Option<String>,
+}
+
+#[async_trait]
+impl IoxSystemTable for PartitionsTable {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    async fn scan(&self, batch_size: usize) -> Result<BatchIterator> {
+        let mut rows = vec![];
+        for table in self.source.tables().await {
+            let partitions = self.source.partitions(&table).await;
+
+            let mut table_rows = BTreeMap::new();
+            for file in table.files.files.iter() {
+                let row = table_rows
+                    .entry(file.partition_id.clone())
+                    .or_insert_with(|| PartitionRow {
+                        table_name: Arc::clone(&table.name),
+                        partition_id: file.partition_id.clone(),
+                        parquet_file_count: 0,
+                        row_count: 0,
+                        size_bytes: 0,
+                        min_time: i64::MAX,
+                        max_time: i64::MIN,
+                        sort_key: partitions
+                            .get(&file.partition_id)
+                            .and_then(|p| p.sort_key.as_ref())
+                            .map(|sort_key| {
+                                sort_key.sort_key.to_columns().collect::<Vec<_>>().join(",")
+                            }),
+                    });
+
+                row.parquet_file_count += 1;
+                row.row_count += file.row_count;
+                row.size_bytes += file.file_size_bytes;
+                row.min_time = row.min_time.min(file.min_time.get());
+                row.max_time = row.max_time.max(file.max_time.get());
+            }
+            rows.extend(table_rows.into_values());
+        }
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(
+                rows.iter()
+                    .map(|r| Some(r.table_name.as_ref()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|r| Some(r.partition_id.to_string()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|r| Some(r.parquet_file_count))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|r| Some(r.row_count))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|r| Some(r.size_bytes))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|r| Some(r.min_time))
+                    .collect::<TimestampNanosecondArray>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|r| Some(r.max_time))
+                    .collect::<TimestampNanosecondArray>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|r| r.sort_key.as_deref())
+                    .collect::<StringArray>(),
+            ),
+        ];
+        let batch = RecordBatch::try_new(self.schema(), columns)?;
+
+        Ok(split_batch(batch, batch_size))
+    }
+}
+
+fn partitions_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("table_name", DataType::Utf8, false),
+        Field::new("partition_id", DataType::Utf8, false),
+        Field::new("parquet_file_count", DataType::Int64, false),
+        Field::new("row_count", DataType::Int64, false),
+        Field::new("size_bytes", DataType::Int64, false),
+        Field::new(
+            "min_time",
+            DataType::Timestamp(TimeUnit::Nanosecond, None),
+            false,
+        ),
+        Field::new(
+            "max_time",
+            DataType::Timestamp(TimeUnit::Nanosecond, None),
+            false,
+        ),
+        Field::new("sort_key", DataType::Utf8, true),
+    ]))
+}
@@ -1,16 +1,17 @@
 use crate::{
-    query_log::{QueryLog, QueryLogEntry},
+    query_log::{QueryLog, QueryLogEntry, QueryLogStats},
     system_tables::{BatchIterator, IoxSystemTable},
 };
 use arrow::{
     array::{
         ArrayRef, BooleanArray, DurationNanosecondArray, Int64Array, StringArray,
-        TimestampNanosecondArray,
+        TimestampNanosecondArray, UInt64Array,
     },
     datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit},
     error::Result,
     record_batch::RecordBatch,
 };
+use async_trait::async_trait;
 use data_types::NamespaceId;
 use observability_deps::tracing::error;
 use std::{collections::VecDeque, sync::Arc};
@@ -33,12 +34,13 @@ impl QueriesTable {
     }
 }
 
+#[async_trait]
 impl IoxSystemTable for QueriesTable {
     fn schema(&self) -> SchemaRef {
         Arc::clone(&self.schema)
     }
 
-    fn scan(&self, batch_size: usize) -> Result<BatchIterator> {
+    async fn scan(&self, batch_size: usize) -> Result<BatchIterator> {
         let schema = self.schema();
 
         let mut entries = self.query_log.entries();
@@ -94,6 +96,20 @@ fn queries_schema(include_namespace_id: bool) -> SchemaRef {
         ),
         Field::new("success", DataType::Boolean, false),
         Field::new("trace_id", DataType::Utf8, true),
+        Field::new("error", DataType::Utf8, true),
+        Field::new("rows_scanned", DataType::UInt64, true),
+        Field::new("bytes_from_object_store", DataType::UInt64, true),
+        Field::new("bytes_from_cache", DataType::UInt64, true),
+        Field::new("partitions_scanned", DataType::UInt64, true),
+        Field::new("partitions_pruned", DataType::UInt64, true),
+        Field::new("parquet_files_scanned", DataType::UInt64, true),
+        Field::new("parquet_files_pruned", DataType::UInt64, true),
+        Field::new(
+            "ingester_latency",
+            DataType::Duration(TimeUnit::Nanosecond),
+            true,
+        ),
+        Field::new("max_memory", DataType::UInt64, true),
     ]);
 
     Arc::new(Schema::new(columns))
@@ -174,6 +190,48 @@ fn from_query_log_entries(
             .collect::<StringArray>(),
     ));
 
+    columns.push(Arc::new(
+        entries
+            .iter()
+            .skip(offset)
+            .take(len)
+            .map(|e| e.error())
+            .collect::<StringArray>(),
+    ));
+
+    let stats = entries
+        .iter()
+        .skip(offset)
+        .take(len)
+        .map(|e| e.stats())
+        .collect::<Vec<_>>();
+    let stats_column = |f: fn(&QueryLogStats) -> u64| -> ArrayRef {
+        Arc::new(
+            stats
+                .iter()
+                .map(|s| s.as_ref().map(f))
+                .collect::<UInt64Array>(),
+        )
+    };
+
+    columns.push(stats_column(|s| s.rows_scanned));
+    columns.push(stats_column(|s| s.bytes_from_object_store));
+    columns.push(stats_column(|s| s.bytes_from_cache));
+    columns.push(stats_column(|s| s.partitions_scanned));
+    columns.push(stats_column(|s| s.partitions_pruned));
+    columns.push(stats_column(|s| s.parquet_files_scanned));
+    columns.push(stats_column(|s| s.parquet_files_pruned));
+    columns.push(Arc::new(
+        stats
+            .iter()
+            .map(|s| {
+                s.and_then(|s| s.ingester_latency)
+                    .map(|d| d.as_nanos() as i64)
+            })
+            .collect::<DurationNanosecondArray>(),
+    ));
+    columns.push(stats_column(|s| s.max_memory));
+
     RecordBatch::try_new(schema, columns)
 }
 
@@ -184,8 +242,8 @@ mod tests {
     use iox_time::{Time, TimeProvider};
     use trace::ctx::TraceId;
 
-    #[test]
-    fn test_from_query_log() {
+    #[tokio::test]
+    async fn test_from_query_log() {
         let now = Time::from_rfc3339("1996-12-19T16:39:57+00:00").unwrap();
         let time_provider = Arc::new(iox_time::MockProvider::new(now));
 
@@ -209,16 +267,21 @@ mod tests {
         let table = QueriesTable::new(Arc::clone(&query_log), None);
 
         let expected = vec![
-            "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+",
-            "| namespace_id | issue_time           | query_type  | query_text        | completed_duration | success | trace_id |",
-            "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+",
-            "| 1            | 1996-12-19T16:39:57Z | sql         | select * from foo |                    | false   |          |",
-            "| 1            | 1996-12-20T16:39:57Z | sql         | select * from bar |                    | false   |          |",
-            "| 2            | 1996-12-20T16:39:57Z | read_filter | json goop         |                    | false   | 45fe     |",
-            "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+",
+            "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+-------+--------------+-------------------------+------------------+--------------------+-------------------+-----------------------+----------------------+------------------+------------+",
+            "| namespace_id | issue_time           | query_type  | query_text        | completed_duration | success | trace_id | error | rows_scanned | bytes_from_object_store | bytes_from_cache | partitions_scanned | partitions_pruned | parquet_files_scanned | parquet_files_pruned | ingester_latency | max_memory |",
+            "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+-------+--------------+-------------------------+------------------+--------------------+-------------------+-----------------------+----------------------+------------------+------------+",
+            "| 1            | 1996-12-19T16:39:57Z | sql         | select * from foo |                    | false   |          |       |              |                         |                  |                    |                   |                       |                      |                  |            |",
+            "| 1            | 1996-12-20T16:39:57Z | sql         | select * from bar |                    | false   |          |       |              |                         |                  |                    |                   |                       |                      |                  |            |",
+            "| 2            | 1996-12-20T16:39:57Z | read_filter | json goop         |                    | false   | 45fe     |       |              |                         |                  |                    |                   |                       |                      |                  |            |",
+            "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+-------+--------------+-------------------------+------------------+--------------------+-------------------+-----------------------+----------------------+------------------+------------+",
         ];
 
-        let entries = table.scan(3).unwrap().collect::<Result<Vec<_>>>().unwrap();
+        let entries = table
+            .scan(3)
+            .await
+            .unwrap()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
         assert_eq!(entries.len(), 1);
         assert_batches_eq!(&expected, &entries);
 
@@ -229,17 +292,35 @@ mod tests {
         // mark the read_filter query completed after 4s successfuly
         read_filter_entry.set_completed(now, true);
 
+        *sql2_entry.error.lock() = Some(String::from("boom"));
+        *read_filter_entry.stats.lock() = Some(QueryLogStats {
+            rows_scanned: 10,
+            bytes_from_object_store: 100,
+            bytes_from_cache: 200,
+            partitions_scanned: 1,
+            partitions_pruned: 2,
+            parquet_files_scanned: 3,
+            parquet_files_pruned: 4,
+            ingester_latency: Some(std::time::Duration::from_millis(5)),
+            max_memory: 1024,
+        });
+
         let expected = vec![
-            "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+",
-            "| namespace_id | issue_time           | query_type  | query_text        | completed_duration | success | trace_id |",
-            "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+",
-            "| 1            | 1996-12-19T16:39:57Z | sql         | select * from foo |                    | false   |          |",
-            "| 1            | 1996-12-20T16:39:57Z | sql         | select * from bar | 4s                 | false   |          |",
-            "| 2            | 1996-12-20T16:39:57Z | read_filter | json goop         | 4s                 | true    | 45fe     |",
-            "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+",
+            "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+-------+--------------+-------------------------+------------------+--------------------+-------------------+-----------------------+----------------------+------------------+------------+",
+            "| namespace_id | issue_time           | query_type  | query_text        | completed_duration | success | trace_id | error | rows_scanned | bytes_from_object_store | bytes_from_cache | partitions_scanned | partitions_pruned | parquet_files_scanned | parquet_files_pruned | ingester_latency | max_memory |",
+            "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+-------+--------------+-------------------------+------------------+--------------------+-------------------+-----------------------+----------------------+------------------+------------+",
+            "| 1            | 1996-12-19T16:39:57Z | sql         | select * from foo |                    | false   |          |       |              |                         |                  |                    |                   |                       |                      |                  |            |",
+            "| 1            | 1996-12-20T16:39:57Z | sql         | select * from bar | 4s                 | false   |          | boom  |              |                         |                  |                    |                   |                       |                      |                  |            |",
+            "| 2            | 1996-12-20T16:39:57Z | read_filter | json goop         | 4s                 | true    | 45fe     |       | 10           | 100                     | 200              | 1                  | 2                 | 3                     | 4                    | 5ms              | 1024       |",
+            "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+-------+--------------+-------------------------+------------------+--------------------+-------------------+-----------------------+----------------------+------------------+------------+",
         ];
 
-        let entries = table.scan(2).unwrap().collect::<Result<Vec<_>>>().unwrap();
+        let entries = table
+            .scan(2)
+            .await
+            .unwrap()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
         assert_eq!(entries.len(), 2);
         assert_batches_eq!(&expected, &entries);
 
@@ -247,15 +328,20 @@ mod tests {
         let table = QueriesTable::new(Arc::clone(&query_log), Some(id1));
 
         let expected = vec![
-            "+----------------------+------------+-------------------+--------------------+---------+----------+",
-            "| issue_time           | query_type | query_text        | completed_duration | success | trace_id |",
-            "+----------------------+------------+-------------------+--------------------+---------+----------+",
-            "| 1996-12-19T16:39:57Z | sql        | select * from foo |                    | false   |          |",
-            "| 1996-12-20T16:39:57Z | sql        | select * from bar | 4s                 | false   |          |",
-            "+----------------------+------------+-------------------+--------------------+---------+----------+",
+            "+----------------------+------------+-------------------+--------------------+---------+----------+-------+--------------+-------------------------+------------------+--------------------+-------------------+-----------------------+----------------------+------------------+------------+",
+            "| issue_time           | query_type | query_text        | completed_duration | success | trace_id | error | rows_scanned | bytes_from_object_store | bytes_from_cache | partitions_scanned | partitions_pruned | parquet_files_scanned | parquet_files_pruned | ingester_latency | max_memory |",
+            "+----------------------+------------+-------------------+--------------------+---------+----------+-------+--------------+-------------------------+------------------+--------------------+-------------------+-----------------------+----------------------+------------------+------------+",
+            "| 1996-12-19T16:39:57Z | sql        | select * from foo |                    | false   |          |       |              |                         |                  |                    |                   |                       |                      |                  |            |",
+            "| 1996-12-20T16:39:57Z | sql        | select * from bar | 4s                 | false   |          | boom  |              |                         |                  |                    |                   |                       |                      |                  |            |",
+            "+----------------------+------------+-------------------+--------------------+---------+----------+-------+--------------+-------------------------+------------------+--------------------+-------------------+-----------------------+----------------------+------------------+------------+",
         ];
 
-        let entries = table.scan(3).unwrap().collect::<Result<Vec<_>>>().unwrap();
+        let entries = table
+            .scan(3)
+            .await
+            .unwrap()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
         assert_eq!(entries.len(), 1);
         assert_batches_eq!(&expected, &entries);
     }
@@ -0,0 +1,106 @@
+use crate::cache::{
+    namespace::{CachedNamespace, CachedTable},
+    parquet_file::CachedParquetFiles,
+    partition::{CachedPartition, PartitionRequest},
+    CatalogCache,
+};
+use data_types::TransitionPartitionId;
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::Arc,
+};
+
+/// Source of the namespace metadata shown by the catalog-based system tables.
+///
+/// All data is read through the [`CatalogCache`], so scanning these tables neither bypasses nor refreshes the caches
+/// that are used for query planning.
+#[derive(Debug, Clone)]
+pub(super) struct NamespaceSource {
+    catalog_cache: Arc<CatalogCache>,
+    namespace_name: Arc<str>,
+}
+
+impl NamespaceSource {
+    pub(super) fn new(catalog_cache: Arc<CatalogCache>, namespace_name: Arc<str>) -> Self {
+        Self {
+            catalog_cache,
+            namespace_name,
+        }
+    }
+
+    pub(super) fn catalog_cache(&self) -> &Arc<CatalogCache> {
+        &self.catalog_cache
+    }
+
+    /// Load the namespace, without the files of its tables.
+    pub(super) async fn namespace(&self) -> Option<Arc<CachedNamespace>> {
+        self.catalog_cache
+            .namespace()
+            .get(Arc::clone(&self.namespace_name), &[], None)
+            .await
+    }
+
+    /// Load all tables of the namespace, ordered by name.
+    pub(super) async fn tables(&self) -> Vec<TableSnapshot> {
+        let Some(namespace) = self.namespace().await else {
+            return vec![];
+        };
+
+        let mut tables = namespace
+            .tables
+            .iter()
+            .map(|(name, table)| (Arc::clone(name), Arc::clone(table)))
+            .collect::<Vec<_>>();
+        tables.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut out = Vec::with_capacity(tables.len());
+        for (name, table) in tables {
+            let files = self
+                .catalog_cache
+                .parquet_file()
+                .get(table.id, None, None)
+                .await;
+            out.push(TableSnapshot { name, table, files });
+        }
+        out
+    }
+
+    /// Load the partitions of all files of the given table.
+    pub(super) async fn partitions(
+        &self,
+        table: &TableSnapshot,
+    ) -> HashMap<TransitionPartitionId, Arc<CachedPartition>> {
+        let partition_ids = table
+            .files
+            .files
+            .iter()
+            .map(|f| f.partition_id.clone())
+            .collect::<BTreeSet<_>>();
+
+        self.catalog_cache
+            .partition()
+            .get(
+                Arc::clone(&table.table),
+                partition_ids
+                    .into_iter()
+                    .map(|partition_id| PartitionRequest {
+                        partition_id,
+                        sort_key_should_cover: vec![],
+                    })
+                    .collect(),
+                None,
+            )
+            .await
+            .into_iter()
+            .map(|p| (p.id.clone(), p))
+            .collect()
+    }
+}
+
+/// Cached state of a single table.
+#[derive(Debug)]
+pub(super) struct TableSnapshot {
+    pub(super) name: Arc<str>,
+    pub(super) table: Arc<CachedTable>,
+    pub(super) files: Arc<CachedParquetFiles>,
+}
@@ -0,0 +1,75 @@
+use crate::system_tables::{snapshot::NamespaceSource, split_batch, BatchIterator, IoxSystemTable};
+use arrow::{
+    array::{ArrayRef, Int64Array, StringArray},
+    datatypes::{DataType, Field, Schema, SchemaRef},
+    error::Result,
+    record_batch::RecordBatch,
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Implementation of system.tables table
+#[derive(Debug)]
+pub(super) struct TablesTable {
+    schema: SchemaRef,
+    source: NamespaceSource,
+}
+
+impl TablesTable {
+    pub(super) fn new(source: NamespaceSource) -> Self {
+        Self {
+            schema: tables_schema(),
+            source,
+        }
+    }
+}
+
+#[async_trait]
+impl IoxSystemTable for TablesTable {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    async fn scan(&self, batch_size: usize) -> Result<BatchIterator> {
+        let tables = self.source.tables().await;
+
+        let mut table_id = Int64Array::builder(tables.len());
+        let mut table_name = Vec::with_capacity(tables.len());
+        let mut column_count = Int64Array::builder(tables.len());
+        let mut parquet_file_count = Int64Array::builder(tables.len());
+        let mut row_count = Int64Array::builder(tables.len());
+        let mut size_bytes = Int64Array::builder(tables.len());
+
+        for table in &tables {
+            table_id.append_value(table.table.id.get());
+            table_name.push(Some(table.name.as_ref()));
+            column_count.append_value(table.table.column_id_map.len() as i64);
+            parquet_file_count.append_value(table.files.files.len() as i64);
+            row_count.append_value(table.files.files.iter().map(|f| f.row_count).sum());
+            size_bytes.append_value(table.files.files.iter().map(|f| f.file_size_bytes).sum());
+        }
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(table_id.finish()),
+            Arc::new(StringArray::from(table_name)),
+            Arc::new(column_count.finish()),
+            Arc::new(parquet_file_count.finish()),
+            Arc::new(row_count.finish()),
+            Arc::new(size_bytes.finish()),
+        ];
+        let batch = RecordBatch::try_new(self.schema(), columns)?;
+
+        Ok(split_batch(batch, batch_size))
+    }
+}
+
+fn tables_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("table_id", DataType::Int64, false),
+        Field::new("table_name", DataType::Utf8, false),
+        Field::new("column_count", DataType::Int64, false),
+        Field::new("parquet_file_count", DataType::Int64, false),
+        Field::new("row_count", DataType::Int64, false),
+        Field::new("size_bytes", DataType::Int64, false),
+    ]))
+}
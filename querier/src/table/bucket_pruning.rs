@@ -0,0 +1,210 @@
+//! Pruning of partitions using the buckets of bucket partition template parts.
+
+use data_types::partition_template::bucket_for_tag_value;
+use datafusion::{
+    logical_expr::{BinaryExpr, Operator},
+    optimizer::utils::split_conjunction,
+    prelude::Expr,
+    scalar::ScalarValue,
+};
+
+use crate::cache::partition::CachedPartition;
+
+/// Returns false iff it can be proven from the column buckets of `partition`
+/// that none of its rows match `filters`.
+///
+/// A partition is pruned if a filter restricts a bucketed column to a set of
+/// string literals (via `=`, `IN` or an `OR` of these), and none of those
+/// literals hash into the bucket of the partition.
+pub(super) fn keep_after_bucket_pruning(partition: &CachedPartition, filters: &[Expr]) -> bool {
+    partition.column_buckets.iter().all(|(col, bucket)| {
+        filters
+            .iter()
+            .flat_map(split_conjunction)
+            .filter_map(|expr| literal_values(expr, col))
+            .all(|values| {
+                values
+                    .iter()
+                    .any(|v| bucket_for_tag_value(v, bucket.num_buckets) == bucket.bucket)
+            })
+    })
+}
+
+/// Return the set of values `expr` restricts `column` to, or [`None`] if `expr`
+/// does not restrict the column to a set of string literals.
+fn literal_values<'a>(expr: &'a Expr, column: &str) -> Option<Vec<&'a str>> {
+    match expr {
+        Expr::BinaryExpr(BinaryExpr {
+            left,
+            op: Operator::Eq,
+            right,
+        }) => match (left.as_ref(), right.as_ref()) {
+            (Expr::Column(c), Expr::Literal(v)) | (Expr::Literal(v), Expr::Column(c))
+                if c.name == column =>
+            {
+                Some(vec![string_literal(v)?])
+            }
+            _ => None,
+        },
+        Expr::BinaryExpr(BinaryExpr {
+            left,
+            op: Operator::Or,
+            right,
+        }) => {
+            let mut values = literal_values(left, column)?;
+            values.extend(literal_values(right, column)?);
+            Some(values)
+        }
+        Expr::InList(in_list) if !in_list.negated => match in_list.expr.as_ref() {
+            Expr::Column(c) if c.name == column => in_list
+                .list
+                .iter()
+                .map(|v| match v {
+                    Expr::Literal(v) => string_literal(v),
+                    _ => None,
+                })
+                .collect(),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn string_literal(v: &ScalarValue) -> Option<&str> {
+    match v {
+        ScalarValue::Utf8(Some(s)) | ScalarValue::LargeUtf8(Some(s)) => Some(s),
+        ScalarValue::Dictionary(_, v) => string_literal(v),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use data_types::{PartitionId, TransitionPartitionId};
+    use datafusion::prelude::{col, lit};
+
+    use crate::cache::partition::ColumnBucket;
+
+    use super::*;
+
+    fn partition(buckets: &[(&str, u32, u32)]) -> CachedPartition {
+        CachedPartition {
+            id: TransitionPartitionId::Deprecated(PartitionId::new(1)),
+            sort_key: None,
+            column_ranges: Arc::new(HashMap::new()),
+            column_buckets: buckets
+                .iter()
+                .map(|(col, bucket, num_buckets)| {
+                    (
+                        Arc::from(*col),
+                        ColumnBucket {
+                            bucket: *bucket,
+                            num_buckets: *num_buckets,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_keep_after_bucket_pruning() {
+        let b = |v: &str| bucket_for_tag_value(v, 10);
+        let p = partition(&[("tag", b("bananas"), 10)]);
+
+        // no filters
+        assert!(keep_after_bucket_pruning(&p, &[]));
+
+        // equality
+        assert!(keep_after_bucket_pruning(
+            &p,
+            &[col("tag").eq(lit("bananas"))]
+        ));
+        assert!(keep_after_bucket_pruning(
+            &p,
+            &[lit("bananas").eq(col("tag"))]
+        ));
+        assert!(!keep_after_bucket_pruning(
+            &p,
+            &[col("tag").eq(lit("cpu-02"))]
+        ));
+        assert!(!keep_after_bucket_pruning(
+            &p,
+            &[lit("cpu-02").eq(col("tag"))]
+        ));
+
+        // other columns and operators are ignored
+        assert!(keep_after_bucket_pruning(
+            &p,
+            &[col("other").eq(lit("cpu-02"))]
+        ));
+        assert!(keep_after_bucket_pruning(
+            &p,
+            &[col("tag").not_eq(lit("cpu-02"))]
+        ));
+        assert!(keep_after_bucket_pruning(
+            &p,
+            &[col("tag").gt(lit("cpu-02"))]
+        ));
+
+        // conjunctions
+        assert!(!keep_after_bucket_pruning(
+            &p,
+            &[col("other").eq(lit("x")).and(col("tag").eq(lit("cpu-02")))]
+        ));
+        assert!(!keep_after_bucket_pruning(
+            &p,
+            &[col("other").eq(lit("x")), col("tag").eq(lit("cpu-02"))]
+        ));
+
+        // disjunctions
+        assert!(keep_after_bucket_pruning(
+            &p,
+            &[col("tag")
+                .eq(lit("cpu-02"))
+                .or(col("tag").eq(lit("bananas")))]
+        ));
+        assert!(!keep_after_bucket_pruning(
+            &p,
+            &[col("tag")
+                .eq(lit("cpu-02"))
+                .or(col("tag").eq(lit("cpu-01")))]
+        ));
+        assert!(keep_after_bucket_pruning(
+            &p,
+            &[col("tag").eq(lit("cpu-02")).or(col("other").eq(lit("x")))]
+        ));
+
+        // IN lists
+        assert!(keep_after_bucket_pruning(
+            &p,
+            &[col("tag").in_list(vec![lit("cpu-02"), lit("bananas")], false)]
+        ));
+        assert!(!keep_after_bucket_pruning(
+            &p,
+            &[col("tag").in_list(vec![lit("cpu-02"), lit("cpu-01")], false)]
+        ));
+        assert!(keep_after_bucket_pruning(
+            &p,
+            &[col("tag").in_list(vec![lit("cpu-02"), lit("cpu-01")], true)]
+        ));
+
+        // dictionary literals
+        let dict = |v: &str| {
+            lit(ScalarValue::Dictionary(
+                Box::new(arrow::datatypes::DataType::Int32),
+                Box::new(ScalarValue::from(v)),
+            ))
+        };
+        assert!(keep_after_bucket_pruning(
+            &p,
+            &[col("tag").eq(dict("bananas"))]
+        ));
+        assert!(!keep_after_bucket_pruning(
+            &p,
+            &[col("tag").eq(dict("cpu-02"))]
+        ));
+    }
+}
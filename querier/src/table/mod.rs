@@ -14,7 +14,8 @@ use data_types::{
 use datafusion::{error::DataFusionError, prelude::Expr};
 use futures::{join, StreamExt};
 use iox_query::{
-    chunk_statistics::create_chunk_statistics, provider, pruning::prune_summaries, QueryChunk,
+    chunk_statistics::create_chunk_statistics, exec::query_stats::QueryStats, provider,
+    pruning::prune_summaries, QueryChunk,
 };
 use observability_deps::tracing::debug;
 use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
@@ -31,6 +32,7 @@ use uuid::Uuid;
 
 pub use self::metrics::PruneMetrics;
 
+mod bucket_pruning;
 mod metrics;
 mod query_access;
 
@@ -143,14 +145,20 @@ impl QuerierTable {
     }
 
     /// Query all chunks within this table.
+    ///
+    /// What was scanned and pruned is recorded in `query_stats`, if provided.
     pub async fn chunks(
         &self,
         filters: &[Expr],
         span: Option<Span>,
         projection: Option<&Vec<usize>>,
+        query_stats: Option<&QueryStats>,
     ) -> Result<Vec<Arc<dyn QueryChunk>>> {
         let mut span_recorder = SpanRecorder::new(span);
-        match self.chunks_inner(filters, &span_recorder, projection).await {
+        match self
+            .chunks_inner(filters, &span_recorder, projection, query_stats)
+            .await
+        {
             Ok(chunks) => {
                 span_recorder.ok("got chunks");
                 Ok(chunks)
@@ -167,6 +175,7 @@ impl QuerierTable {
         filters: &[Expr],
         span_recorder: &SpanRecorder,
         projection: Option<&Vec<usize>>,
+        query_stats: Option<&QueryStats>,
     ) -> Result<Vec<Arc<dyn QueryChunk>>> {
         debug!(
             ?filters,
@@ -186,6 +195,7 @@ impl QuerierTable {
         let ingester_ready = CancellationToken::new();
         let (partitions, _) = join!(
             async {
+                let start = catalog_cache.time_provider().now();
                 let partitions = self
                     .ingester_partitions(
                         filters,
@@ -194,6 +204,15 @@ impl QuerierTable {
                     )
                     .await;
                 ingester_ready.cancel();
+                if let (Some(query_stats), Some(_)) = (query_stats, &self.ingester_connection) {
+                    if let Some(latency) = catalog_cache
+                        .time_provider()
+                        .now()
+                        .checked_duration_since(start)
+                    {
+                        query_stats.record_ingester_latency(latency);
+                    }
+                }
                 partitions
             },
             async {
@@ -276,6 +295,7 @@ impl QuerierTable {
             .await;
 
         // prune partitons
+        let num_cached_partitions = cached_partitions.len();
         let cached_partitions = self
             .prune_partitions(
                 cached_partitions,
@@ -311,6 +331,17 @@ impl QuerierTable {
             .await;
         let num_final_parquet_file_chunks = parquet_files.len();
 
+        if let Some(query_stats) = query_stats {
+            query_stats.record_partitions(
+                cached_partitions.len() as u64,
+                (num_cached_partitions - cached_partitions.len()) as u64,
+            );
+            query_stats.record_parquet_files(
+                num_final_parquet_file_chunks as u64,
+                (num_initial_parquet_file_chunks - num_final_parquet_file_chunks) as u64,
+            );
+        }
+
         // build final chunk list from ingester chunks + pruned parquet file chunks
         let chunks: Vec<_> = partitions
             .into_iter()
@@ -412,6 +443,13 @@ impl QuerierTable {
     ) -> HashMap<TransitionPartitionId, Arc<CachedPartition>> {
         let span_recorder = SpanRecorder::new(span);
 
+        // Partitions with buckets that cannot contain any value matched by the filters are removed
+        // up front, as bucket membership cannot be expressed as column statistics.
+        let partitions = partitions
+            .into_iter()
+            .filter(|p| bucket_pruning::keep_after_bucket_pruning(p, filters))
+            .collect::<Vec<_>>();
+
         let projections = partitions
             .iter()
             .map(|p| {
@@ -1243,7 +1281,9 @@ mod tests {
                 .next_response(Ok(self.ingester_partitions.clone()));
 
             let span = Some(Span::root("root", Arc::clone(&self.traces) as _));
-            self.querier_table.chunks(filters, span, projection).await
+            self.querier_table
+                .chunks(filters, span, projection, None)
+                .await
         }
     }
 }
@@ -361,6 +361,63 @@ mod tests {
         pretty_assertions::assert_eq!(expected, got);
     }
 
+    #[tokio::test]
+    async fn test_write_table_partition_template_bucket_and_prefix() {
+        let partitioner = Partitioner::default();
+        let ns = NamespaceName::new("bananas").expect("valid db name");
+
+        let namespace_schema = Arc::new(new_empty_namespace_schema(42));
+
+        let table_template = test_table_partition_override(vec![
+            TemplatePart::Bucket("tag1", 4),
+            TemplatePart::TagPrefix("tag2", 3),
+        ]);
+
+        let lp = "
+            bananas,tag1=A,tag2=cpu-01 val=42i 1\n\
+            bananas,tag1=B,tag2=cpu-02 val=42i 1\n\
+            bananas,tag1=A,tag2=mem-01 val=42i 1\n\
+            bananas,tag1=A,tag2=cpu-03 val=42i 1\n\
+            bananas,tag2=cpu val=42i 1\n\
+        ";
+
+        let (writes, _) = mutable_batch_lp::lines_to_batches_stats(lp, 42)
+            .expect("failed to build test writes from LP");
+
+        let writes = writes
+            .into_iter()
+            .enumerate()
+            .map(|(i, (name, data))| (TableId::new(i as _), (name, table_template.clone(), data)))
+            .collect();
+
+        let handler_ret = partitioner.write(&ns, namespace_schema, writes, None).await;
+
+        // Check the partition -> row count mapping.
+        let got = handler_ret
+            .unwrap_or_default()
+            .into_iter()
+            .map(|partition| {
+                let rows = partition
+                    .payload
+                    .values()
+                    .map(|v| v.1.rows())
+                    .sum::<usize>();
+
+                (partition.key, rows)
+            })
+            .collect::<HashMap<_, _>>();
+
+        // "A" hashes into bucket 2 of 4, "B" into bucket 1.
+        let expected = HashMap::from([
+            (PartitionKey::from("2|cpu#"), 2),
+            (PartitionKey::from("1|cpu#"), 1),
+            (PartitionKey::from("2|mem#"), 1),
+            (PartitionKey::from("!|cpu"), 1),
+        ]);
+
+        pretty_assertions::assert_eq!(expected, got);
+    }
+
     prop_compose! {
         /// Yield a Vec containing an identical timestamp run of random length,
         /// up to `max_run_len`,
@@ -1,12 +1,13 @@
 //! gRPC service implementations for `router`.
 
 use generated_types::influxdata::iox::{
-    catalog::v1::*, gossip::v1::anti_entropy_service_server, namespace::v1::*, object_store::v1::*,
-    table::v1::*,
+    catalog::v1::*, delete::v1::*, gossip::v1::anti_entropy_service_server, namespace::v1::*,
+    object_store::v1::*, table::v1::*,
 };
 use iox_catalog::interface::Catalog;
 use object_store::DynObjectStore;
 use service_grpc_catalog::CatalogService;
+use service_grpc_delete::DeleteService;
 use service_grpc_namespace::NamespaceService;
 use service_grpc_object_store::ObjectStoreService;
 use service_grpc_schema::SchemaService;
@@ -75,6 +76,13 @@ impl<T> RpcWriteGrpcDelegate<T> {
         TableService::new(Arc::clone(&self.catalog))
     }
 
+    /// Acquire a [`DeleteService`] gRPC service implementation.
+    ///
+    /// [`DeleteService`]: generated_types::influxdata::iox::delete::v1::delete_service_server::DeleteService
+    pub fn delete_service(&self) -> impl delete_service_server::DeleteService {
+        DeleteService::new(Arc::clone(&self.catalog))
+    }
+
     /// Acquire a [`AntiEntropyService`] gRPC service implementation.
     ///
     /// This method returns the server exactly once, if provided at
@@ -1,13 +1,17 @@
 //! HTTP service implementations for `router`.
 
+pub mod delete;
+pub mod prom;
 pub mod write;
 
-use std::{str::Utf8Error, time::Instant};
+use std::{str::Utf8Error, sync::Arc, time::Instant};
 
 use bytes::{Bytes, BytesMut};
 use futures::StreamExt;
 use hashbrown::HashMap;
 use hyper::{header::CONTENT_ENCODING, Body, Method, Request, Response, StatusCode};
+use influxdb_line_protocol::stream::LineSplitter;
+use iox_catalog::interface::{delete_by_predicate, Catalog, SoftDeletedRows};
 use iox_time::{SystemProvider, TimeProvider};
 use metric::{DurationHistogram, U64Counter};
 use mutable_batch::MutableBatch;
@@ -17,6 +21,8 @@ use thiserror::Error;
 use tokio::sync::{Semaphore, TryAcquireError};
 use trace::ctx::SpanContext;
 
+use self::delete::{parse_delete_request, DeleteRequestError};
+use self::prom::{parse_prom_write_request, PromWriteError};
 use self::write::{
     multi_tenant::MultiTenantExtractError, single_tenant::SingleTenantExtractError, WriteParams,
     WriteRequestUnifier,
@@ -30,6 +36,9 @@ use crate::{
     schema_validator::SchemaError,
 };
 
+/// The maximum number of [`RejectedLine`] returned for a partial write.
+const MAX_REJECTED_LINES: usize = 100;
+
 /// Errors returned by the `router` HTTP request handler.
 #[derive(Debug, Error)]
 pub enum Error {
@@ -41,6 +50,14 @@ pub enum Error {
     #[error("deletes are not supported")]
     DeletesUnsupported,
 
+    /// The delete request is invalid.
+    #[error(transparent)]
+    DeleteRequest(#[from] DeleteRequestError),
+
+    /// Recording a delete in the catalog failed.
+    #[error("failed to record delete: {0}")]
+    DeleteCatalog(iox_catalog::interface::Error),
+
     /// An error parsing a single-tenant HTTP request.
     #[error(transparent)]
     SingleTenantError(#[from] SingleTenantExtractError),
@@ -77,6 +94,15 @@ pub enum Error {
     #[error("failed to parse line protocol: {0}")]
     ParseLineProtocol(mutable_batch_lp::Error),
 
+    /// Some lines of a request with partial writes enabled were invalid, and
+    /// were rejected. All valid lines were written.
+    #[error("partial write: {} line(s) rejected", .0.total)]
+    PartialWrite(RejectedLines),
+
+    /// The Prometheus remote write request is invalid.
+    #[error(transparent)]
+    PromWriteRequest(#[from] PromWriteError),
+
     /// An error returned from the [`DmlHandler`].
     #[error("dml handler error: {0}")]
     DmlHandler(#[from] DmlError),
@@ -109,12 +135,22 @@ impl Error {
         match self {
             Error::NoHandler => StatusCode::NOT_FOUND,
             Error::DeletesUnsupported => StatusCode::NOT_IMPLEMENTED,
+            Error::DeleteRequest(_) => StatusCode::BAD_REQUEST,
+            Error::DeleteCatalog(iox_catalog::interface::Error::NamespaceNotFoundByName {
+                ..
+            }) => StatusCode::NOT_FOUND,
+            Error::DeleteCatalog(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Error::ClientHangup(_) => StatusCode::BAD_REQUEST,
             Error::InvalidGzip(_) => StatusCode::BAD_REQUEST,
             Error::NonUtf8ContentHeader(_) => StatusCode::BAD_REQUEST,
             Error::NonUtf8Body(_) => StatusCode::BAD_REQUEST,
             Error::ParseLineProtocol(_) => StatusCode::BAD_REQUEST,
+            Error::PartialWrite(_) => StatusCode::BAD_REQUEST,
             Error::RequestSizeExceeded(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            Error::PromWriteRequest(PromWriteError::RequestSizeExceeded(_)) => {
+                StatusCode::PAYLOAD_TOO_LARGE
+            }
+            Error::PromWriteRequest(_) => StatusCode::BAD_REQUEST,
             Error::InvalidContentEncoding(_) => {
                 // https://www.rfc-editor.org/rfc/rfc7231#section-6.5.13
                 StatusCode::UNSUPPORTED_MEDIA_TYPE
@@ -140,21 +176,82 @@ impl Error {
     pub fn get_parse_error_line_index(&self) -> Option<usize> {
         match self {
             Self::ParseLineProtocol(mutable_batch_lp::Error::PerLine { lines }) => {
-                let line = match lines
+                let line = lines
                     .get(0)
-                    .expect("PerLine error must have at least one line")
-                {
-                    LineError::LineProtocol { source: _, line }
-                    | LineError::TimestampOverflow { line }
-                    | LineError::Write { source: _, line } => *line,
-                };
-                Some(line)
+                    .expect("PerLine error must have at least one line");
+                Some(line_number(line))
             }
+            Self::PartialWrite(rejected) => rejected.lines.get(0).map(|r| r.line),
+            _ => None,
+        }
+    }
+
+    /// Return the lines rejected by a partial write, if the error is a
+    /// [`Error::PartialWrite`].
+    pub fn rejected_lines(&self) -> Option<&[RejectedLine]> {
+        match self {
+            Self::PartialWrite(rejected) => Some(&rejected.lines),
             _ => None,
         }
     }
 }
 
+/// The invalid lines rejected by a partial write.
+#[derive(Debug, Default)]
+pub struct RejectedLines {
+    /// The first [`MAX_REJECTED_LINES`] rejected lines.
+    lines: Vec<RejectedLine>,
+    /// The total number of rejected lines.
+    total: usize,
+}
+
+impl RejectedLines {
+    fn push(&mut self, errors: &[LineError]) {
+        let remaining = MAX_REJECTED_LINES - self.lines.len();
+        self.lines
+            .extend(errors.iter().take(remaining).map(RejectedLine::from));
+        self.total += errors.len();
+    }
+
+    /// The first [`MAX_REJECTED_LINES`] rejected lines.
+    pub fn lines(&self) -> &[RejectedLine] {
+        &self.lines
+    }
+
+    /// The total number of rejected lines.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+}
+
+/// A line of a partial write that was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RejectedLine {
+    /// The (1-based) line number within the request body.
+    pub line: usize,
+    /// Why the line was rejected.
+    pub reason: String,
+}
+
+impl From<&LineError> for RejectedLine {
+    fn from(e: &LineError) -> Self {
+        Self {
+            line: line_number(e),
+            reason: e.to_string(),
+        }
+    }
+}
+
+/// Return the (1-based) line number of the line that caused `e`.
+fn line_number(e: &LineError) -> usize {
+    match e {
+        LineError::LineProtocol { source: _, line }
+        | LineError::TimestampOverflow { line }
+        | LineError::Write { source: _, line } => *line,
+        LineError::LocatedLineProtocol { source } => source.line(),
+    }
+}
+
 impl From<&DmlError> for StatusCode {
     fn from(e: &DmlError) -> Self {
         match e {
@@ -211,6 +308,9 @@ pub struct HttpDelegate<D, N, T = SystemProvider> {
     dml_handler: D,
     write_request_mode_handler: Box<dyn WriteRequestUnifier>,
 
+    // The catalog deletes are recorded in, if deletes are enabled.
+    delete_catalog: Option<Arc<dyn Catalog>>,
+
     // A request limiter to restrict the number of simultaneous requests this
     // router services.
     //
@@ -225,6 +325,8 @@ pub struct HttpDelegate<D, N, T = SystemProvider> {
     write_metric_fields: U64Counter,
     write_metric_tables: U64Counter,
     write_metric_body_size: U64Counter,
+    write_metric_rejected_lines: U64Counter,
+    write_metric_prom_samples: U64Counter,
     request_limit_rejected: U64Counter,
 }
 
@@ -266,6 +368,18 @@ impl<D, N> HttpDelegate<D, N, SystemProvider> {
                 "cumulative byte size of successfully routed (decompressed) line protocol write requests",
             )
             .recorder(&[]);
+        let write_metric_rejected_lines = metrics
+            .register_metric::<U64Counter>(
+                "http_write_rejected_lines",
+                "cumulative number of invalid line protocol lines rejected by partial writes",
+            )
+            .recorder(&[]);
+        let write_metric_prom_samples = metrics
+            .register_metric::<U64Counter>(
+                "http_write_prom_samples",
+                "cumulative number of prometheus remote write samples successfully routed",
+            )
+            .recorder(&[]);
         let request_limit_rejected = metrics
             .register_metric::<U64Counter>(
                 "http_request_limit_rejected",
@@ -285,17 +399,33 @@ impl<D, N> HttpDelegate<D, N, SystemProvider> {
             namespace_resolver,
             write_request_mode_handler,
             dml_handler,
+            delete_catalog: None,
             request_sem: Semaphore::new(max_requests),
             write_metric_lines,
             http_line_protocol_parse_duration,
             write_metric_fields,
             write_metric_tables,
             write_metric_body_size,
+            write_metric_rejected_lines,
+            write_metric_prom_samples,
             request_limit_rejected,
         }
     }
 }
 
+impl<D, N, T> HttpDelegate<D, N, T> {
+    /// Accept delete requests, recording them in `catalog`.
+    ///
+    /// Without a catalog, delete requests are rejected with
+    /// [`Error::DeletesUnsupported`].
+    pub fn with_delete_catalog(self, catalog: Arc<dyn Catalog>) -> Self {
+        Self {
+            delete_catalog: Some(catalog),
+            ..self
+        }
+    }
+}
+
 impl<D, N, T> HttpDelegate<D, N, T>
 where
     D: DmlHandler<WriteInput = HashMap<String, MutableBatch>, WriteOutput = ()>,
@@ -332,7 +462,14 @@ where
                 let dml_info = self.write_request_mode_handler.parse_v2(&req).await?;
                 self.write_handler(req, dml_info).await
             }
-            (&Method::POST, "/api/v2/delete") => return Err(Error::DeletesUnsupported),
+            (&Method::POST, "/api/v1/prom/write") => {
+                let dml_info = self.write_request_mode_handler.parse_v2(&req).await?;
+                self.prom_write_handler(req, dml_info).await
+            }
+            (&Method::POST, "/api/v2/delete") => {
+                let dml_info = self.write_request_mode_handler.parse_v2(&req).await?;
+                self.delete_handler(req, dml_info).await
+            }
             _ => return Err(Error::NoHandler),
         }
         .map(|_summary| {
@@ -355,10 +492,6 @@ where
             "processing write request"
         );
 
-        // Read the HTTP body and convert it to a str.
-        let body = self.read_body(req).await?;
-        let body = std::str::from_utf8(&body).map_err(Error::NonUtf8Body)?;
-
         // The time, in nanoseconds since the epoch, to assign to any points that don't
         // contain a timestamp
         let default_time = self.time_provider.now().timestamp_nanos();
@@ -366,12 +499,46 @@ where
 
         let mut converter = LinesConverter::new(default_time);
         converter.set_timestamp_base(write_info.precision.timestamp_base());
-        let (batches, stats) = match converter.write_lp(body).and_then(|_| converter.finish()) {
+
+        // Convert the body as it is received, writing every valid line and
+        // either collecting the invalid lines (which may include lines that
+        // are not valid utf8) or failing the request at the first of them.
+        let mut rejected = RejectedLines::default();
+        let body_size = self
+            .read_line_protocol(
+                req,
+                &mut converter,
+                write_info.accept_partial.then_some(&mut rejected),
+            )
+            .await?;
+        if write_info.accept_partial {
+            self.write_metric_rejected_lines.inc(rejected.total as _);
+        }
+
+        let converted = converter.finish();
+
+        // A request not authorized to write to the whole namespace must be
+        // authorized to write to every table it contains.
+        if let Some(table_authz) = &write_info.table_authz {
+            let tables = match &converted {
+                Ok((batches, _)) => batches.keys().map(String::as_str).collect(),
+                Err(_) => vec![],
+            };
+            table_authz
+                .authorize(&write_info.namespace, &tables)
+                .await
+                .map_err(SingleTenantExtractError::Authorizer)?;
+        }
+
+        let (batches, stats) = match converted {
             Ok(v) => v,
-            Err(mutable_batch_lp::Error::EmptyPayload) => {
+            Err(mutable_batch_lp::Error::EmptyPayload) if rejected.total == 0 => {
                 debug!("nothing to write");
                 return Ok(());
             }
+            Err(mutable_batch_lp::Error::EmptyPayload) => {
+                return Err(Error::PartialWrite(rejected))
+            }
             Err(line_errors) => return Err(Error::ParseLineProtocol(line_errors)),
         };
 
@@ -383,7 +550,7 @@ where
             num_fields=stats.num_fields,
             num_tables,
             precision=?write_info.precision,
-            body_size=body.len(),
+            body_size,
             namespace=%write_info.namespace,
             duration=?duration,
             "routing write",
@@ -403,14 +570,166 @@ where
         self.write_metric_lines.inc(stats.num_lines as _);
         self.write_metric_fields.inc(stats.num_fields as _);
         self.write_metric_tables.inc(num_tables as _);
+        self.write_metric_body_size.inc(body_size as _);
+
+        if rejected.total > 0 {
+            debug!(
+                num_rejected=rejected.total,
+                namespace=%write_info.namespace,
+                "partial write rejected invalid lines",
+            );
+            return Err(Error::PartialWrite(rejected));
+        }
+
+        Ok(())
+    }
+
+    /// Write the samples of a Prometheus remote write request, see
+    /// [`parse_prom_write_request`].
+    async fn prom_write_handler(
+        &self,
+        req: Request<Body>,
+        write_info: WriteParams,
+    ) -> Result<(), Error> {
+        let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
+
+        trace!(
+            namespace=%write_info.namespace,
+            "processing prometheus remote write request"
+        );
+
+        // The body of a remote write request is always snappy-compressed.
+        let encoding = req
+            .headers()
+            .get(&CONTENT_ENCODING)
+            .map(|v| v.to_str().map_err(Error::NonUtf8ContentHeader))
+            .transpose()?;
+        match encoding {
+            None | Some("snappy") => {}
+            Some(v) => return Err(Error::InvalidContentEncoding(v.to_string())),
+        }
+        let body = self.read_payload(req.into_body()).await?;
+
+        let write = parse_prom_write_request(&body, self.max_request_bytes)?;
+
+        // A request not authorized to write to the whole namespace must be
+        // authorized to write to every metric it contains.
+        if let Some(table_authz) = &write_info.table_authz {
+            let tables = write.batches.keys().map(String::as_str).collect::<Vec<_>>();
+            table_authz
+                .authorize(&write_info.namespace, &tables)
+                .await
+                .map_err(SingleTenantExtractError::Authorizer)?;
+        }
+
+        if write.batches.is_empty() {
+            debug!("nothing to write");
+            return Ok(());
+        }
+
+        let num_tables = write.batches.len();
+        debug!(
+            num_series=write.num_series,
+            num_samples=write.num_samples,
+            num_tables,
+            body_size=body.len(),
+            namespace=%write_info.namespace,
+            "routing prometheus remote write",
+        );
+
+        let namespace_schema = self
+            .namespace_resolver
+            .get_namespace_schema(&write_info.namespace)
+            .await?;
+
+        self.dml_handler
+            .write(
+                &write_info.namespace,
+                namespace_schema,
+                write.batches,
+                span_ctx,
+            )
+            .await
+            .map_err(Into::into)?;
+
+        self.write_metric_prom_samples.inc(write.num_samples as _);
+        self.write_metric_tables.inc(num_tables as _);
         self.write_metric_body_size.inc(body.len() as _);
 
         Ok(())
     }
 
-    /// Parse the request's body into raw bytes, applying the configured size
-    /// limits and decoding any content encoding.
-    async fn read_body(&self, req: hyper::Request<Body>) -> Result<Bytes, Error> {
+    async fn delete_handler(
+        &self,
+        req: Request<Body>,
+        write_info: WriteParams,
+    ) -> Result<(), Error> {
+        let Some(catalog) = &self.delete_catalog else {
+            return Err(Error::DeletesUnsupported);
+        };
+
+        let body = self.read_body(req).await?;
+        let delete = parse_delete_request(&body)?;
+
+        // A delete without a table name deletes from every table, so it is
+        // only authorized by access to the whole namespace.
+        if let Some(table_authz) = &write_info.table_authz {
+            let tables: Vec<_> = delete.table_name.as_deref().into_iter().collect();
+            table_authz
+                .authorize(&write_info.namespace, &tables)
+                .await
+                .map_err(SingleTenantExtractError::Authorizer)?;
+        }
+
+        debug!(
+            namespace=%write_info.namespace,
+            table_name=?delete.table_name,
+            predicate=?delete.predicate,
+            "recording delete",
+        );
+
+        let mut repos = catalog.repositories().await;
+        let namespace = repos
+            .namespaces()
+            .get_by_name(
+                write_info.namespace.as_str(),
+                SoftDeletedRows::ExcludeDeleted,
+            )
+            .await
+            .map_err(Error::DeleteCatalog)?
+            .ok_or_else(|| {
+                Error::DeleteCatalog(iox_catalog::interface::Error::NamespaceNotFoundByName {
+                    name: write_info.namespace.to_string(),
+                })
+            })?;
+
+        delete_by_predicate(
+            namespace.id,
+            delete.table_name.as_deref(),
+            &delete.predicate,
+            repos.as_mut(),
+        )
+        .await
+        .map_err(Error::DeleteCatalog)?;
+
+        Ok(())
+    }
+
+    /// Read the line protocol body of `req`, decoding any content encoding,
+    /// and convert each line with `converter` as soon as it has been received.
+    ///
+    /// The configured size limit is applied to every chunk of the body, both
+    /// before and after decoding. If `rejected` is provided, invalid lines are
+    /// collected into it, otherwise the request fails at the first chunk
+    /// containing an invalid line, without reading the rest of the body.
+    ///
+    /// Returns the size of the decoded body.
+    async fn read_line_protocol(
+        &self,
+        req: hyper::Request<Body>,
+        converter: &mut LinesConverter,
+        mut rejected: Option<&mut RejectedLines>,
+    ) -> Result<usize, Error> {
         let encoding = req
             .headers()
             .get(&CONTENT_ENCODING)
@@ -422,18 +741,77 @@ where
             Some(v) => return Err(Error::InvalidContentEncoding(v.to_string())),
         };
 
+        // Decode at most max_request_bytes bytes to prevent a decompression
+        // bomb based DoS.
+        let mut decoder =
+            ungzip.then(|| flate2::write::GzDecoder::new(LimitedBuf::new(self.max_request_bytes)));
+        let mut splitter = LineSplitter::default();
         let mut payload = req.into_body();
+        let mut payload_size = 0;
+        let mut body_size = 0;
 
-        let mut body = BytesMut::new();
         while let Some(chunk) = payload.next().await {
             let chunk = chunk.map_err(Error::ClientHangup)?;
-            // limit max size of in-memory payload
-            if (body.len() + chunk.len()) > self.max_request_bytes {
+            // limit max size of the (encoded) payload
+            payload_size += chunk.len();
+            if payload_size > self.max_request_bytes {
                 return Err(Error::RequestSizeExceeded(self.max_request_bytes));
             }
-            body.extend_from_slice(&chunk);
+
+            let chunk = match &mut decoder {
+                Some(decoder) => {
+                    use std::io::Write;
+                    decoder
+                        .write_all(&chunk)
+                        .map_err(|e| self.gzip_error(decoder.get_ref(), e))?;
+                    decoder.get_mut().take()
+                }
+                None => chunk,
+            };
+
+            body_size += chunk.len();
+            splitter.push(chunk);
+            convert_lines(converter, &mut splitter, rejected.as_deref_mut())?;
+        }
+
+        if let Some(mut decoder) = decoder {
+            decoder
+                .try_finish()
+                .map_err(|e| self.gzip_error(decoder.get_ref(), e))?;
+            let chunk = decoder.get_mut().take();
+            body_size += chunk.len();
+            splitter.push(chunk);
         }
-        let body = body.freeze();
+
+        splitter.finish();
+        convert_lines(converter, &mut splitter, rejected)?;
+
+        Ok(body_size)
+    }
+
+    /// Map an error writing to a gzip decoder over `buf` to an [`Error`].
+    fn gzip_error(&self, buf: &LimitedBuf, e: std::io::Error) -> Error {
+        if buf.exceeded {
+            return Error::RequestSizeExceeded(self.max_request_bytes);
+        }
+        Error::InvalidGzip(e)
+    }
+
+    /// Parse the request's body into raw bytes, applying the configured size
+    /// limits and decoding any content encoding.
+    async fn read_body(&self, req: hyper::Request<Body>) -> Result<Bytes, Error> {
+        let encoding = req
+            .headers()
+            .get(&CONTENT_ENCODING)
+            .map(|v| v.to_str().map_err(Error::NonUtf8ContentHeader))
+            .transpose()?;
+        let ungzip = match encoding {
+            None | Some("identity") => false,
+            Some("gzip") => true,
+            Some(v) => return Err(Error::InvalidContentEncoding(v.to_string())),
+        };
+
+        let body = self.read_payload(req.into_body()).await?;
 
         // If the body is not compressed, return early.
         if !ungzip {
@@ -464,6 +842,96 @@ where
 
         Ok(decoded_data.into())
     }
+
+    /// Read the raw bytes of `payload`, applying the configured size limits.
+    async fn read_payload(&self, mut payload: Body) -> Result<Bytes, Error> {
+        let mut body = BytesMut::new();
+        while let Some(chunk) = payload.next().await {
+            let chunk = chunk.map_err(Error::ClientHangup)?;
+            // limit max size of in-memory payload
+            if (body.len() + chunk.len()) > self.max_request_bytes {
+                return Err(Error::RequestSizeExceeded(self.max_request_bytes));
+            }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body.freeze())
+    }
+}
+
+/// Convert the complete lines buffered in `splitter` with `converter`.
+///
+/// If `rejected` is provided, invalid lines are collected into it, otherwise
+/// the first invalid lines fail the conversion.
+fn convert_lines(
+    converter: &mut LinesConverter,
+    splitter: &mut LineSplitter,
+    mut rejected: Option<&mut RejectedLines>,
+) -> Result<(), Error> {
+    loop {
+        match (converter.write_lp_stream(splitter), rejected.as_deref_mut()) {
+            (Ok(()), _) => return Ok(()),
+            (Err(mutable_batch_lp::Error::PerLine { lines }), Some(rejected)) => {
+                rejected.push(&lines)
+            }
+            (Err(mutable_batch_lp::Error::PerLine { lines }), None) => {
+                // Preserve the error of a body that is not valid utf8.
+                if let [LineError::LocatedLineProtocol { source }] = &lines[..] {
+                    if let influxdb_line_protocol::Error::InvalidUtf8 { source } = source.error() {
+                        return Err(Error::NonUtf8Body(*source));
+                    }
+                }
+                return Err(Error::ParseLineProtocol(mutable_batch_lp::Error::PerLine {
+                    lines,
+                }));
+            }
+            (Err(e), _) => return Err(Error::ParseLineProtocol(e)),
+        }
+    }
+}
+
+/// A buffer for decoded data, failing any write that would grow it beyond
+/// the limit it was created with.
+#[derive(Debug)]
+struct LimitedBuf {
+    buf: Vec<u8>,
+    /// The number of bytes that may still be written.
+    remaining: usize,
+    /// Whether a write was rejected for exceeding the limit.
+    exceeded: bool,
+}
+
+impl LimitedBuf {
+    fn new(limit: usize) -> Self {
+        Self {
+            buf: Vec::new(),
+            remaining: limit,
+            exceeded: false,
+        }
+    }
+
+    /// Take the data written since the last call.
+    fn take(&mut self) -> Bytes {
+        std::mem::take(&mut self.buf).into()
+    }
+}
+
+impl std::io::Write for LimitedBuf {
+    fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+        if data.len() > self.remaining {
+            self.exceeded = true;
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "decoded body exceeds the maximum request size",
+            ));
+        }
+        self.remaining -= data.len();
+        self.buf.extend_from_slice(data);
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -476,6 +944,10 @@ mod tests {
         OrgBucketMappingError, TableId,
     };
     use flate2::{write::GzEncoder, Compression};
+    use generated_types::{
+        prometheus::{Label, Sample, TimeSeries, WriteRequest},
+        prost::Message,
+    };
     use hyper::header::HeaderValue;
     use metric::{Attributes, Metric};
     use mutable_batch::column::ColumnData;
@@ -849,6 +1321,58 @@ mod tests {
         want_dml_calls = [] // None
     );
 
+    test_write_handler!(
+        partial_write,
+        query_string = "?org=bananas&bucket=test&accept_partial=true",
+        body = "platanos,tag1=A val=42i 1\nnot line protocol\nplatanos,tag1=B val=4.2 2\nplatanos,tag1=C val=43i 3".as_bytes(),
+        dml_handler = [Ok(())],
+        want_result = [
+            Err(Error::PartialWrite(rejected))
+                if rejected.total() == 2 && rejected.lines().iter().map(|r| r.line).eq([2, 3])
+        ],
+        want_dml_calls = [
+            MockDmlHandlerCall::Write { namespace, write_input, .. }
+        ] => {
+            assert_eq!(namespace, NAMESPACE_NAME);
+            let table = write_input.get("platanos").expect("table not found");
+            assert_eq!(table.rows(), 2);
+        }
+    );
+
+    test_write_handler!(
+        partial_write_all_rejected,
+        query_string = "?org=bananas&bucket=test&accept_partial=true",
+        body = "not line protocol".as_bytes(),
+        dml_handler = [Ok(())],
+        want_result = [Err(Error::PartialWrite(rejected)) if rejected.total() == 1],
+        want_dml_calls = [] // None
+    );
+
+    test_write_handler!(
+        partial_write_non_utf8_line,
+        query_string = "?org=bananas&bucket=test&accept_partial=true",
+        body = b"platanos val=42i 1\nplatanos val=\"\xc3\x28\" 2".to_vec(),
+        dml_handler = [Ok(())],
+        want_result = [
+            Err(Error::PartialWrite(rejected))
+                if rejected.lines().iter().map(|r| r.line).eq([2])
+        ],
+        want_dml_calls = [MockDmlHandlerCall::Write { namespace, .. }] => {
+            assert_eq!(namespace, NAMESPACE_NAME);
+        }
+    );
+
+    test_write_handler!(
+        partial_write_ok,
+        query_string = "?org=bananas&bucket=test&accept_partial=true",
+        body = "platanos,tag1=A,tag2=B val=42i 123456".as_bytes(),
+        dml_handler = [Ok(())],
+        want_result = [Ok(_)],
+        want_dml_calls = [MockDmlHandlerCall::Write { namespace, .. }] => {
+            assert_eq!(namespace, NAMESPACE_NAME);
+        }
+    );
+
     test_write_handler!(
         non_utf8_body,
         query_string = "?org=bananas&bucket=test",
@@ -1106,6 +1630,8 @@ mod tests {
                     Ok(WriteParams {
                         namespace: NamespaceName::new(NAMESPACE_NAME).unwrap(),
                         precision: Precision::default(),
+                        accept_partial: false,
+                        table_authz: None,
                     })
                 })),
             ),
@@ -1218,6 +1744,128 @@ mod tests {
         assert_metric_hit(&metrics, "http_request_limit_rejected", Some(1));
     }
 
+    /// Build a delegate for requests with a streamed body, returning it
+    /// together with the sender for the body chunks and the request.
+    fn streamed_write_request() -> (
+        Arc<
+            HttpDelegate<Arc<MockDmlHandler<HashMap<String, MutableBatch>>>, MockNamespaceResolver>,
+        >,
+        Arc<MockDmlHandler<HashMap<String, MutableBatch>>>,
+        tokio::sync::mpsc::Sender<Result<String, MockError>>,
+        Request<Body>,
+    ) {
+        let mock_namespace_resolver =
+            MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, NAMESPACE_ID);
+
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = Arc::new(HttpDelegate::new(
+            MAX_BYTES,
+            1,
+            mock_namespace_resolver,
+            Arc::clone(&dml_handler),
+            &metrics,
+            Box::new(
+                MockWriteRequestUnifier::default().with_ret(iter::repeat_with(|| {
+                    Ok(WriteParams {
+                        namespace: NamespaceName::new(NAMESPACE_NAME).unwrap(),
+                        precision: Precision::default(),
+                        accept_partial: false,
+                        table_authz: None,
+                    })
+                })),
+            ),
+        ));
+
+        let (tx, rx) = tokio::sync::mpsc::channel(1);
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test")
+            .method("POST")
+            .body(Body::wrap_stream(ReceiverStream::new(rx)))
+            .unwrap();
+
+        (delegate, dml_handler, tx, request)
+    }
+
+    // Lines split across body chunks are reassembled.
+    #[tokio::test]
+    async fn test_write_streamed_body() {
+        let (delegate, dml_handler, tx, request) = streamed_write_request();
+
+        let handler = tokio::spawn(async move { delegate.route(request).await });
+        for chunk in [
+            "platanos val=42i 1\nplat",
+            "anos val=4",
+            "3i 2\nplatanos val=44i 3",
+        ] {
+            tx.send(Ok(chunk.to_string()))
+                .await
+                .expect("handler closed channel");
+        }
+        drop(tx);
+
+        handler
+            .with_timeout_panic(Duration::from_secs(1))
+            .await
+            .expect("handler should not panic")
+            .expect("write should succeed");
+
+        assert_matches!(
+            &dml_handler.calls()[..],
+            [MockDmlHandlerCall::Write { write_input, .. }] => {
+                let table = write_input.get("platanos").expect("table not in write");
+                assert_eq!(table.rows(), 3);
+            }
+        );
+    }
+
+    // An invalid line fails the request as soon as it is received, without
+    // waiting for the rest of the body.
+    #[tokio::test]
+    async fn test_write_streamed_body_invalid_line() {
+        let (delegate, dml_handler, tx, request) = streamed_write_request();
+
+        let handler = tokio::spawn(async move { delegate.route(request).await });
+        tx.send(Ok("platanos val=42i 1\nnot line protocol\n".to_string()))
+            .await
+            .expect("handler closed channel");
+
+        // The body stream is held open by tx.
+        let err = handler
+            .with_timeout_panic(Duration::from_secs(1))
+            .await
+            .expect("handler should not panic")
+            .expect_err("write should fail");
+        assert_matches!(
+            err,
+            Error::ParseLineProtocol(mutable_batch_lp::Error::PerLine { lines }) => {
+                assert_matches!(&lines[..], [LineError::LocatedLineProtocol { source }] => {
+                    assert_eq!(source.line(), 2);
+                });
+            }
+        );
+        assert!(dml_handler.calls().is_empty());
+    }
+
+    // The size limit is enforced as the body is received, without waiting for
+    // the rest of the body.
+    #[tokio::test]
+    async fn test_write_streamed_body_size_exceeded() {
+        let (delegate, dml_handler, tx, request) = streamed_write_request();
+
+        let handler = tokio::spawn(async move { delegate.route(request).await });
+        let chunk = "platanos val=42i 1\n".repeat(MAX_BYTES / 19 + 1);
+        tx.send(Ok(chunk)).await.expect("handler closed channel");
+
+        let err = handler
+            .with_timeout_panic(Duration::from_secs(1))
+            .await
+            .expect("handler should not panic")
+            .expect_err("write should fail");
+        assert_matches!(err, Error::RequestSizeExceeded(MAX_BYTES));
+        assert!(dml_handler.calls().is_empty());
+    }
+
     /// Assert the router rejects writes to the V1 endpoint when in
     /// "multi-tenant" mode.
     #[tokio::test]
@@ -1246,6 +1894,104 @@ mod tests {
         assert_matches!(got, Err(Error::NoHandler));
     }
 
+    /// Assert delete requests are rejected when no catalog is configured.
+    #[tokio::test]
+    async fn test_delete_unsupported() {
+        let mock_namespace_resolver =
+            MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, NamespaceId::new(42));
+
+        let dml_handler = Arc::new(MockDmlHandler::default());
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = HttpDelegate::new(
+            MAX_BYTES,
+            1,
+            mock_namespace_resolver,
+            Arc::clone(&dml_handler),
+            &metrics,
+            Box::<MultiTenantRequestUnifier>::default(),
+        );
+
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/delete?org=bananas&bucket=test")
+            .method("POST")
+            .body(Body::from(r#"{"start": "1", "stop": "2"}"#))
+            .unwrap();
+
+        let got = delegate.route(request).await;
+        assert_matches!(got, Err(Error::DeletesUnsupported));
+        assert_matches!(dml_handler.calls().as_slice(), []);
+    }
+
+    /// Assert delete requests are recorded as tombstones in the catalog.
+    #[tokio::test]
+    async fn test_delete_records_tombstones() {
+        let metrics = Arc::new(metric::Registry::default());
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(iox_catalog::mem::MemCatalog::new(Arc::clone(&metrics)));
+        let (namespace, table) = {
+            let mut repos = catalog.repositories().await;
+            let namespace =
+                iox_catalog::test_helpers::arbitrary_namespace(&mut *repos, "bananas_test").await;
+            let table =
+                iox_catalog::test_helpers::arbitrary_table(&mut *repos, "platanos", &namespace)
+                    .await;
+            iox_catalog::test_helpers::arbitrary_table(&mut *repos, "other", &namespace).await;
+            (namespace, table)
+        };
+
+        let mock_namespace_resolver =
+            MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, namespace.id);
+        let dml_handler = Arc::new(MockDmlHandler::default());
+        let delegate = HttpDelegate::new(
+            MAX_BYTES,
+            1,
+            mock_namespace_resolver,
+            Arc::clone(&dml_handler),
+            &metrics,
+            Box::<MultiTenantRequestUnifier>::default(),
+        )
+        .with_delete_catalog(Arc::clone(&catalog));
+
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/delete?org=bananas&bucket=test")
+            .method("POST")
+            .body(Body::from(
+                r#"{"start": "1", "stop": "2", "predicate": "_measurement=platanos and tag1='A'"}"#,
+            ))
+            .unwrap();
+        let got = delegate
+            .route(request)
+            .await
+            .expect("delete should succeed");
+        assert_eq!(got.status(), StatusCode::NO_CONTENT);
+
+        let tombstones = catalog
+            .repositories()
+            .await
+            .tombstones()
+            .list_by_namespace_id(namespace.id)
+            .await
+            .unwrap();
+        assert_matches!(tombstones.as_slice(), [t] => {
+            assert_eq!(t.table_id, table.id);
+            assert_eq!(t.serialized_predicate, r#""tag1"='A'"#);
+        });
+
+        // A predicate that cannot be parsed is rejected
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/delete?org=bananas&bucket=test")
+            .method("POST")
+            .body(Body::from(
+                r#"{"start": "1", "stop": "2", "predicate": "tag1 > 'A'"}"#,
+            ))
+            .unwrap();
+        let got = delegate.route(request).await;
+        assert_matches!(got, Err(Error::DeleteRequest(_)));
+
+        // Deletes never hit the DML handlers
+        assert_matches!(dml_handler.calls().as_slice(), []);
+    }
+
     /// Assert the router delegates request parsing to the
     /// [`WriteRequestUnifier`] implementation.
     ///
@@ -1262,6 +2008,8 @@ mod tests {
                 Ok(WriteParams {
                     namespace: NamespaceName::new(NAMESPACE_NAME).unwrap(),
                     precision: Precision::default(),
+                    accept_partial: false,
+                    table_authz: None,
                 })
             }),
         ));
@@ -1318,6 +2066,144 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_prom_write() {
+        let mock_namespace_resolver =
+            MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, NAMESPACE_ID);
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = HttpDelegate::new(
+            MAX_BYTES,
+            1,
+            mock_namespace_resolver,
+            Arc::clone(&dml_handler),
+            &metrics,
+            Box::<MultiTenantRequestUnifier>::default(),
+        );
+
+        let write_request = WriteRequest {
+            timeseries: vec![TimeSeries {
+                labels: vec![
+                    Label {
+                        name: "__name__".to_string(),
+                        value: "up".to_string(),
+                    },
+                    Label {
+                        name: "job".to_string(),
+                        value: "node".to_string(),
+                    },
+                ],
+                samples: vec![
+                    Sample {
+                        value: 1.0,
+                        timestamp: 1000,
+                    },
+                    Sample {
+                        value: 0.0,
+                        timestamp: 2000,
+                    },
+                ],
+            }],
+        };
+        let body = snap::raw::Encoder::new()
+            .compress_vec(&write_request.encode_to_vec())
+            .unwrap();
+
+        let request = |encoding| {
+            Request::builder()
+                .uri("https://bananas.example/api/v1/prom/write?org=bananas&bucket=test")
+                .method("POST")
+                .header(CONTENT_ENCODING, encoding)
+                .body(Body::from(body.clone()))
+                .unwrap()
+        };
+
+        // Only snappy-compressed bodies are accepted.
+        let err = delegate
+            .route(request("gzip"))
+            .await
+            .expect_err("gzip body should be rejected");
+        assert_matches!(err, Error::InvalidContentEncoding(_));
+
+        let got = delegate
+            .route(request("snappy"))
+            .await
+            .expect("remote write should succeed");
+        assert_eq!(got.status(), StatusCode::NO_CONTENT);
+
+        assert_matches!(
+            dml_handler.calls().as_slice(),
+            [MockDmlHandlerCall::Write { namespace, write_input, .. }] => {
+                assert_eq!(namespace, NAMESPACE_NAME);
+                let up = write_input.get("up").expect("table not found");
+                assert_eq!(up.rows(), 2);
+                assert_matches!(up.column("value").unwrap().data(), ColumnData::F64(data, _) => {
+                    assert_eq!(data.as_slice(), [1.0, 0.0]);
+                });
+            }
+        );
+
+        assert_metric_hit(&metrics, "http_write_prom_samples", Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_partial_write_rejected_lines() {
+        let mock_namespace_resolver =
+            MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, NAMESPACE_ID);
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = HttpDelegate::new(
+            MAX_BYTES,
+            1,
+            mock_namespace_resolver,
+            Arc::clone(&dml_handler),
+            &metrics,
+            Box::<MultiTenantRequestUnifier>::default(),
+        );
+
+        // 150 invalid lines, followed by a valid line.
+        let body = iter::repeat("bananas\n")
+            .take(150)
+            .chain(iter::once("platanos val=42i 1"))
+            .collect::<String>();
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test&accept_partial=true")
+            .method("POST")
+            .body(Body::from(body))
+            .unwrap();
+
+        let err = delegate
+            .route(request)
+            .await
+            .expect_err("partial write should return an error");
+        assert_eq!(err.as_status_code(), StatusCode::BAD_REQUEST);
+        assert_eq!(err.to_string(), "partial write: 150 line(s) rejected");
+        assert_eq!(err.get_parse_error_line_index(), Some(1));
+
+        // Only the first MAX_REJECTED_LINES are returned.
+        let rejected = err.rejected_lines().expect("must have rejected lines");
+        assert_eq!(rejected.len(), MAX_REJECTED_LINES);
+        assert!(rejected.iter().enumerate().all(|(i, r)| r.line == i + 1));
+        assert!(
+            rejected[0]
+                .reason
+                .starts_with("error parsing line 1, column 8 (byte offset 7)"),
+            "{}",
+            rejected[0].reason
+        );
+
+        // The valid line was written.
+        assert_matches!(
+            dml_handler.calls().as_slice(),
+            [MockDmlHandlerCall::Write { write_input, .. }] => {
+                assert_eq!(write_input.get("platanos").expect("table not found").rows(), 1);
+            }
+        );
+
+        assert_metric_hit(&metrics, "http_write_rejected_lines", Some(150));
+        assert_metric_hit(&metrics, "http_write_lines", Some(1));
+    }
+
     // The display text of Error gets passed through `ioxd_router::IoxHttpErrorAdaptor` then
     // `ioxd_common::http::error::HttpApiError` as the JSON "message" value in error response
     // bodies. These are fixture tests to document error messages that users might see when
@@ -1491,6 +2377,22 @@ mod tests {
             \nerror writing line 44 (1-based): the field 'bananas' is specified more than once with conflicting types",
         ),
 
+        (
+            PartialWrite(RejectedLines {
+                lines: vec![RejectedLine {
+                    line: 42,
+                    reason: "timestamp overflows i64 on line 42 (1-based)".into(),
+                }],
+                total: 24,
+            }),
+            "partial write: 24 line(s) rejected",
+        ),
+
+        (
+            PromWriteRequest(PromWriteError::NoMetricName),
+            "time series has no metric name (`__name__` label)",
+        ),
+
         (
             DmlHandler(DmlError::NamespaceNotFound("[namespace name]".into())),
             "dml handler error: namespace [namespace name] does not exist",
@@ -0,0 +1,173 @@
+//! Request parsing for the InfluxDB v2 compatible `/api/v2/delete` endpoint.
+
+use data_types::{DeletePredicate, Op, Scalar};
+use predicate::delete_predicate::parse_delete_predicate;
+use serde::Deserialize;
+use thiserror::Error;
+
+/// The pseudo-column used in a delete predicate to select the measurement
+/// (table) to delete from.
+const MEASUREMENT_COLUMN_NAME: &str = "_measurement";
+
+/// Errors returned when parsing a delete request.
+#[derive(Debug, Error)]
+pub enum DeleteRequestError {
+    /// The request body is not a valid delete request.
+    #[error("invalid delete request body: {0}")]
+    InvalidBody(serde_json::Error),
+
+    /// The time range or predicate of the request is invalid.
+    #[error("invalid delete predicate: {0}")]
+    InvalidPredicate(#[from] predicate::delete_predicate::Error),
+
+    /// The predicate selects the measurement with something other than a
+    /// single `_measurement = <name>` expression.
+    #[error("delete predicate must select at most one measurement using `_measurement = <name>`")]
+    InvalidMeasurement,
+}
+
+/// The JSON body of a delete request.
+#[derive(Debug, Deserialize)]
+struct DeleteBody {
+    start: String,
+    stop: String,
+    #[serde(default)]
+    predicate: String,
+}
+
+/// A parsed delete request.
+#[derive(Debug, PartialEq)]
+pub struct DeleteRequest {
+    /// The table to delete from, or [`None`] to delete from all tables.
+    pub table_name: Option<String>,
+
+    /// The rows to delete.
+    pub predicate: DeletePredicate,
+}
+
+/// Parse the JSON `body` of a delete request of the form:
+///
+/// ```json
+/// {
+///     "start": "1970-01-01T00:00:00Z",
+///     "stop": "2070-01-02T00:00:00Z",
+///     "predicate": "_measurement=\"cpu\" and host=\"a\""
+/// }
+/// ```
+///
+/// The optional `_measurement` expression is extracted from the predicate and
+/// returned as the [`DeleteRequest::table_name`].
+pub fn parse_delete_request(body: &[u8]) -> Result<DeleteRequest, DeleteRequestError> {
+    let body: DeleteBody = serde_json::from_slice(body).map_err(DeleteRequestError::InvalidBody)?;
+
+    let mut predicate = parse_delete_predicate(&body.start, &body.stop, &body.predicate)?;
+
+    let (measurement, exprs) = predicate
+        .exprs
+        .into_iter()
+        .partition::<Vec<_>, _>(|e| e.column() == MEASUREMENT_COLUMN_NAME);
+    predicate.exprs = exprs;
+
+    let table_name = match measurement.as_slice() {
+        [] => None,
+        [expr] => match (expr.op(), expr.scalar()) {
+            (Op::Eq, Scalar::String(name)) => Some(name.clone()),
+            _ => return Err(DeleteRequestError::InvalidMeasurement),
+        },
+        _ => return Err(DeleteRequestError::InvalidMeasurement),
+    };
+
+    Ok(DeleteRequest {
+        table_name,
+        predicate,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use data_types::{DeleteExpr, TimestampRange};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_with_measurement() {
+        let body = br#"{
+            "start": "1970-01-01T00:00:00Z",
+            "stop": "100",
+            "predicate": "_measurement=\"cpu\" and host=\"a\""
+        }"#;
+
+        let got = parse_delete_request(body).unwrap();
+        assert_eq!(
+            got,
+            DeleteRequest {
+                table_name: Some("cpu".to_string()),
+                predicate: DeletePredicate {
+                    range: TimestampRange::new(0, 100),
+                    exprs: vec![DeleteExpr::new(
+                        "host".to_string(),
+                        Op::Eq,
+                        Scalar::String("a".to_string())
+                    )],
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_all_tables() {
+        let body = br#"{"start": "10", "stop": "100"}"#;
+
+        let got = parse_delete_request(body).unwrap();
+        assert_eq!(
+            got,
+            DeleteRequest {
+                table_name: None,
+                predicate: DeletePredicate {
+                    range: TimestampRange::new(10, 100),
+                    exprs: vec![],
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_measurement() {
+        let body = br#"{"start": "10", "stop": "100", "predicate": "_measurement != 'cpu'"}"#;
+        assert_matches!(
+            parse_delete_request(body),
+            Err(DeleteRequestError::InvalidMeasurement)
+        );
+
+        let body = br#"{
+            "start": "10",
+            "stop": "100",
+            "predicate": "_measurement = 'cpu' and _measurement = 'mem'"
+        }"#;
+        assert_matches!(
+            parse_delete_request(body),
+            Err(DeleteRequestError::InvalidMeasurement)
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert_matches!(
+            parse_delete_request(b"not json"),
+            Err(DeleteRequestError::InvalidBody(_))
+        );
+
+        let body = br#"{"start": "100", "stop": "10"}"#;
+        assert_matches!(
+            parse_delete_request(body),
+            Err(DeleteRequestError::InvalidPredicate(_))
+        );
+
+        let body = br#"{"start": "10", "stop": "100", "predicate": "host > 'a'"}"#;
+        assert_matches!(
+            parse_delete_request(body),
+            Err(DeleteRequestError::InvalidPredicate(_))
+        );
+    }
+}
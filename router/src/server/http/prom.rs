@@ -0,0 +1,303 @@
+//! Request parsing for the Prometheus remote write `/api/v1/prom/write`
+//! endpoint.
+
+use generated_types::{
+    prometheus::{Label, TimeSeries, WriteRequest},
+    prost::Message,
+};
+use hashbrown::HashMap;
+use mutable_batch::{writer::Writer, MutableBatch};
+use thiserror::Error;
+
+/// The label holding the metric name of a time series, which is the name of
+/// the table its samples are written to.
+pub const METRIC_NAME_LABEL: &str = "__name__";
+
+/// The field column the values of the samples are written to.
+pub const VALUE_COLUMN_NAME: &str = "value";
+
+/// The timestamp column the times of the samples are written to.
+pub const TIME_COLUMN_NAME: &str = "time";
+
+/// Errors returned when parsing a remote write request.
+#[derive(Debug, Error)]
+pub enum PromWriteError {
+    /// The body is not valid snappy-compressed data.
+    #[error("error decoding snappy-compressed body: {0}")]
+    Snappy(snap::Error),
+
+    /// The decompressed body exceeds the maximum request size.
+    #[error("max request size ({0} bytes) exceeded")]
+    RequestSizeExceeded(usize),
+
+    /// The decompressed body is not a protobuf `WriteRequest`.
+    #[error("invalid remote write request: {0}")]
+    Decode(generated_types::prost::DecodeError),
+
+    /// A time series has no metric name label.
+    #[error("time series has no metric name (`{METRIC_NAME_LABEL}` label)")]
+    NoMetricName,
+
+    /// A label of a time series has the name of a column the samples are
+    /// written to.
+    #[error("label name {0:?} is reserved")]
+    ReservedLabel(String),
+
+    /// A time series has several labels with the same name.
+    #[error("duplicate label {0:?}")]
+    DuplicateLabel(String),
+
+    /// The timestamp of a sample, in milliseconds, cannot be represented in
+    /// nanoseconds.
+    #[error("sample timestamp {0}ms is out of range")]
+    TimestampOverflow(i64),
+
+    /// The samples of a time series could not be added to the batch of its
+    /// table.
+    #[error("error writing samples of metric {metric:?}: {source}")]
+    Write {
+        /// The metric name of the time series.
+        metric: String,
+        /// The underlying error.
+        source: mutable_batch::writer::Error,
+    },
+}
+
+/// A parsed remote write request.
+#[derive(Debug)]
+pub struct PromWrite {
+    /// The batch of samples to write to each table.
+    pub batches: HashMap<String, MutableBatch>,
+
+    /// The number of time series in the request.
+    pub num_series: usize,
+
+    /// The number of samples in the request.
+    pub num_samples: usize,
+}
+
+/// Parse the snappy-compressed protobuf `WriteRequest` in `body`, which must
+/// not be larger than `max_bytes` once decompressed.
+///
+/// The samples of each time series are written to the table named after its
+/// metric name, with its other labels as tags, their values as the
+/// [`VALUE_COLUMN_NAME`] float field and their times as the
+/// [`TIME_COLUMN_NAME`] timestamp. A label with an empty value is the same
+/// as no label in Prometheus, and is not written.
+pub fn parse_prom_write_request(
+    body: &[u8],
+    max_bytes: usize,
+) -> Result<PromWrite, PromWriteError> {
+    // Check the size before decompressing, to prevent a decompression bomb
+    // based DoS.
+    let len = snap::raw::decompress_len(body).map_err(PromWriteError::Snappy)?;
+    if len > max_bytes {
+        return Err(PromWriteError::RequestSizeExceeded(max_bytes));
+    }
+    let body = snap::raw::Decoder::new()
+        .decompress_vec(body)
+        .map_err(PromWriteError::Snappy)?;
+
+    let request = WriteRequest::decode(body.as_slice()).map_err(PromWriteError::Decode)?;
+
+    let mut write = PromWrite {
+        batches: HashMap::new(),
+        num_series: request.timeseries.len(),
+        num_samples: 0,
+    };
+    for series in &request.timeseries {
+        write.num_samples += series.samples.len();
+        write_series(series, &mut write.batches)?;
+    }
+
+    Ok(write)
+}
+
+/// Write the samples of `series` to the batch of its table in `batches`.
+fn write_series(
+    series: &TimeSeries,
+    batches: &mut HashMap<String, MutableBatch>,
+) -> Result<(), PromWriteError> {
+    let mut metric = None;
+    let mut tags = Vec::with_capacity(series.labels.len());
+    for Label { name, value } in &series.labels {
+        match name.as_str() {
+            METRIC_NAME_LABEL => metric = Some(value.as_str()),
+            VALUE_COLUMN_NAME | TIME_COLUMN_NAME => {
+                return Err(PromWriteError::ReservedLabel(name.clone()))
+            }
+            _ if value.is_empty() => {}
+            _ => tags.push((name.as_str(), value.as_str())),
+        }
+    }
+    let metric = metric
+        .filter(|m| !m.is_empty())
+        .ok_or(PromWriteError::NoMetricName)?;
+
+    tags.sort_unstable_by_key(|(name, _)| *name);
+    if let Some(w) = tags.windows(2).find(|w| w[0].0 == w[1].0) {
+        return Err(PromWriteError::DuplicateLabel(w[0].0.to_string()));
+    }
+
+    if series.samples.is_empty() {
+        return Ok(());
+    }
+    let timestamps = series
+        .samples
+        .iter()
+        .map(|s| {
+            s.timestamp
+                .checked_mul(1_000_000)
+                .ok_or(PromWriteError::TimestampOverflow(s.timestamp))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let batch = batches.entry(metric.to_string()).or_default();
+    let mut writer = Writer::new(batch, series.samples.len());
+    write_samples(&mut writer, &tags, series, timestamps).map_err(|source| {
+        PromWriteError::Write {
+            metric: metric.to_string(),
+            source,
+        }
+    })?;
+    writer.commit();
+
+    Ok(())
+}
+
+/// Write the `tags` and samples of `series` with `writer`.
+fn write_samples(
+    writer: &mut Writer<'_>,
+    tags: &[(&str, &str)],
+    series: &TimeSeries,
+    timestamps: Vec<i64>,
+) -> Result<(), mutable_batch::writer::Error> {
+    let to_insert = series.samples.len();
+    for (name, value) in tags {
+        writer.write_tag(name, None, std::iter::repeat(*value).take(to_insert))?;
+    }
+    writer.write_f64(
+        VALUE_COLUMN_NAME,
+        None,
+        series.samples.iter().map(|s| s.value),
+    )?;
+    writer.write_time(TIME_COLUMN_NAME, timestamps.into_iter())
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use generated_types::prometheus::Sample;
+    use mutable_batch::column::ColumnData;
+
+    use super::*;
+
+    fn label(name: &str, value: &str) -> Label {
+        Label {
+            name: name.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    fn series(labels: &[(&str, &str)], samples: &[(f64, i64)]) -> TimeSeries {
+        TimeSeries {
+            labels: labels.iter().map(|(n, v)| label(n, v)).collect(),
+            samples: samples
+                .iter()
+                .map(|(value, timestamp)| Sample {
+                    value: *value,
+                    timestamp: *timestamp,
+                })
+                .collect(),
+        }
+    }
+
+    fn encode(timeseries: Vec<TimeSeries>) -> Vec<u8> {
+        let request = WriteRequest { timeseries };
+        snap::raw::Encoder::new()
+            .compress_vec(&request.encode_to_vec())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let body = encode(vec![
+            series(
+                &[("__name__", "up"), ("job", "node"), ("instance", "a:9100")],
+                &[(1.0, 1000), (0.0, 2000)],
+            ),
+            series(
+                &[("job", "node"), ("__name__", "up"), ("env", "")],
+                &[(1.0, 1000)],
+            ),
+            series(&[("__name__", "go_goroutines")], &[(42.0, 1500)]),
+            series(&[("__name__", "empty")], &[]),
+        ]);
+
+        let write = parse_prom_write_request(&body, 1024).unwrap();
+        assert_eq!(write.num_series, 4);
+        assert_eq!(write.num_samples, 4);
+
+        let mut tables = write.batches.keys().cloned().collect::<Vec<_>>();
+        tables.sort();
+        assert_eq!(tables, ["go_goroutines", "up"]);
+
+        let up = &write.batches["up"];
+        assert_eq!(up.rows(), 3);
+        assert_eq!(
+            up.column_names().into_iter().collect::<Vec<_>>(),
+            ["instance", "job", "time", "value"]
+        );
+        assert_matches!(up.column("value").unwrap().data(), ColumnData::F64(data, _) => {
+            assert_eq!(data.as_slice(), [1.0, 0.0, 1.0]);
+        });
+        assert_matches!(up.column("time").unwrap().data(), ColumnData::I64(data, _) => {
+            assert_eq!(data.as_slice(), [1_000_000_000, 2_000_000_000, 1_000_000_000]);
+        });
+        // The second series of the metric has no instance label.
+        let instance = up.column("instance").unwrap().valid_mask();
+        assert!(instance.get(0) && instance.get(1) && !instance.get(2));
+
+        let goroutines = &write.batches["go_goroutines"];
+        assert_eq!(
+            goroutines.column_names().into_iter().collect::<Vec<_>>(),
+            ["time", "value"]
+        );
+        assert_matches!(goroutines.column("time").unwrap().data(), ColumnData::I64(data, _) => {
+            assert_eq!(data.as_slice(), [1_500_000_000]);
+        });
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let parse = |timeseries| parse_prom_write_request(&encode(timeseries), 1024);
+
+        assert_matches!(
+            parse(vec![series(&[("job", "node")], &[(1.0, 1000)])]),
+            Err(PromWriteError::NoMetricName)
+        );
+        assert_matches!(
+            parse(vec![series(&[("__name__", "up"), ("value", "x")], &[])]),
+            Err(PromWriteError::ReservedLabel(name)) => assert_eq!(name, "value")
+        );
+        assert_matches!(
+            parse(vec![series(
+                &[("__name__", "up"), ("job", "a"), ("job", "b")],
+                &[]
+            )]),
+            Err(PromWriteError::DuplicateLabel(name)) => assert_eq!(name, "job")
+        );
+        assert_matches!(
+            parse(vec![series(&[("__name__", "up")], &[(1.0, i64::MAX)])]),
+            Err(PromWriteError::TimestampOverflow(i64::MAX))
+        );
+        assert_matches!(
+            parse_prom_write_request(b"not snappy", 1024),
+            Err(PromWriteError::Snappy(_))
+        );
+        assert_matches!(
+            parse_prom_write_request(&encode(vec![series(&[("__name__", "up")], &[])]), 1),
+            Err(PromWriteError::RequestSizeExceeded(1))
+        );
+    }
+}
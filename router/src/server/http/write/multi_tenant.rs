@@ -72,6 +72,8 @@ fn parse_v2(req: &Request<Body>) -> Result<WriteParams, MultiTenantExtractError>
     Ok(WriteParams {
         namespace,
         precision: write_params.precision,
+        accept_partial: write_params.accept_partial,
+        table_authz: None,
     })
 }
 
@@ -225,10 +227,31 @@ mod tests {
         query_string = "?org=banana&bucket=cool&precision=ms",
         want = Ok(WriteParams {
             namespace,
-            precision
+            precision,
+            ..
         }) => {
             assert_eq!(namespace.as_str(), "banana_cool");
             assert_matches!(precision, Precision::Milliseconds);
         }
     );
+
+    test_parse_v2!(
+        with_accept_partial,
+        query_string = "?org=banana&bucket=cool&accept_partial=true",
+        want = Ok(WriteParams {
+            namespace,
+            accept_partial: true,
+            ..
+        }) => {
+            assert_eq!(namespace.as_str(), "banana_cool");
+        }
+    );
+
+    test_parse_v2!(
+        invalid_accept_partial,
+        query_string = "?org=banana&bucket=cool&accept_partial=maybe",
+        want = Err(Error::MultiTenantError(
+            MultiTenantExtractError::ParseV2Request(V2WriteParseError::DecodeFail(_))
+        ))
+    );
 }
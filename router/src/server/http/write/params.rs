@@ -5,6 +5,7 @@ use data_types::NamespaceName;
 use hyper::{Body, Request};
 use serde::Deserialize;
 
+use super::single_tenant::auth::TableAuthorization;
 use crate::server::http::Error;
 
 #[derive(Clone, Debug, Deserialize)]
@@ -42,6 +43,12 @@ impl Precision {
 pub struct WriteParams {
     pub(crate) namespace: NamespaceName<'static>,
     pub(crate) precision: Precision,
+    /// Write the valid lines of a request containing invalid lines, instead
+    /// of rejecting the entire request.
+    pub(crate) accept_partial: bool,
+    /// The authorization of the tables written to, if the request is not
+    /// authorized to write to the whole namespace.
+    pub(crate) table_authz: Option<TableAuthorization>,
 }
 
 /// A [`WriteRequestUnifier`] abstraction returns a unified [`WriteParams`]
@@ -1,6 +1,6 @@
 //! Authorization of HTTP requests using the authz service client.
 
-use std::sync::Arc;
+use std::{fmt::Debug, sync::Arc};
 
 use authz::{
     self, extract_token, http::AuthorizationHeaderExtension, Action, Authorizer, Error, Permission,
@@ -15,12 +15,7 @@ pub(crate) async fn authorize(
     namespace: &NamespaceName<'_>,
     query_param_token: Option<String>,
 ) -> Result<(), Error> {
-    let token = extract_token(
-        req.extensions()
-            .get::<AuthorizationHeaderExtension>()
-            .and_then(|v| v.as_ref()),
-    )
-    .or_else(|| query_param_token.map(|t| t.into_bytes()));
+    let token = request_token(req, query_param_token);
 
     let perms = [Permission::ResourceAction(
         Resource::Database(namespace.to_string()),
@@ -31,6 +26,74 @@ pub(crate) async fn authorize(
     Ok(())
 }
 
+/// Authorize a write request to `namespace`, returning the
+/// [`TableAuthorization`] to check the tables written to against if the
+/// request is not authorized to write to the whole namespace.
+pub(crate) async fn authorize_write(
+    authz: &Arc<dyn Authorizer>,
+    req: &Request<Body>,
+    namespace: &NamespaceName<'_>,
+    query_param_token: Option<String>,
+) -> Result<Option<TableAuthorization>, Error> {
+    match authorize(authz, req, namespace, query_param_token.clone()).await {
+        Ok(()) => Ok(None),
+        // The token may still be granted writes to some of the tables of
+        // the namespace, which are only known once the body is parsed.
+        Err(Error::Forbidden) => Ok(Some(TableAuthorization {
+            authz: Arc::clone(authz),
+            token: request_token(req, query_param_token),
+        })),
+        Err(e) => Err(e),
+    }
+}
+
+/// Extract the authorization token of `req`, from its headers or, failing
+/// that, from the `query_param_token`.
+fn request_token(req: &Request<Body>, query_param_token: Option<String>) -> Option<Vec<u8>> {
+    extract_token(
+        req.extensions()
+            .get::<AuthorizationHeaderExtension>()
+            .and_then(|v| v.as_ref()),
+    )
+    .or_else(|| query_param_token.map(|t| t.into_bytes()))
+}
+
+/// The authorization of a write request that is not authorized to write to
+/// the whole namespace, so that every table it writes to must be authorized
+/// individually.
+pub(crate) struct TableAuthorization {
+    authz: Arc<dyn Authorizer>,
+    token: Option<Vec<u8>>,
+}
+
+impl TableAuthorization {
+    /// Check the request is authorized to write to each of the `tables` of
+    /// `namespace`.
+    pub(crate) async fn authorize(
+        &self,
+        namespace: &NamespaceName<'_>,
+        tables: &[&str],
+    ) -> Result<(), Error> {
+        self.authz
+            .authorize_tables(
+                self.token.clone(),
+                namespace.as_str(),
+                tables,
+                Action::Write,
+            )
+            .await
+    }
+}
+
+impl Debug for TableAuthorization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The token is deliberately not included.
+        f.debug_struct("TableAuthorization")
+            .field("authz", &self.authz)
+            .finish_non_exhaustive()
+    }
+}
+
 #[cfg(test)]
 pub mod mock {
     use async_trait::async_trait;
@@ -40,6 +103,10 @@ pub mod mock {
     pub const MOCK_AUTH_VALID_TOKEN: &str = "GOOD";
     pub const MOCK_AUTH_INVALID_TOKEN: &str = "UGLY";
     pub const MOCK_AUTH_NO_PERMS_TOKEN: &str = "BAD";
+    pub const MOCK_AUTH_TABLE_TOKEN: &str = "TABLE";
+
+    /// The table [`MOCK_AUTH_TABLE_TOKEN`] grants permissions on.
+    pub const MOCK_AUTH_TABLE_NAME: &str = "platanos";
 
     #[derive(Debug, Default)]
     pub struct MockAuthorizer {}
@@ -55,6 +122,24 @@ pub mod mock {
                 Some(token) => match (&token as &dyn AsRef<[u8]>).as_ref() {
                     b"GOOD" => Ok(perms.to_vec()),
                     b"BAD" => Err(authz::Error::Forbidden),
+                    b"TABLE" => {
+                        let granted = perms
+                            .iter()
+                            .filter(|p| {
+                                matches!(
+                                    p,
+                                    Permission::ResourceAction(Resource::Table { table, .. }, _)
+                                        if table == MOCK_AUTH_TABLE_NAME
+                                )
+                            })
+                            .cloned()
+                            .collect::<Vec<_>>();
+                        if granted.is_empty() {
+                            Err(authz::Error::Forbidden)
+                        } else {
+                            Ok(granted)
+                        }
+                    }
                     b"UGLY" => Err(authz::Error::verification("test", "test error")),
                     _ => panic!("unexpected token"),
                 },
@@ -171,6 +256,71 @@ mod tests {
         })
     }
 
+    #[tokio::test]
+    async fn test_authz_table_permissions() {
+        static NAMESPACE_NAME: &str = "test";
+        let mock_namespace_resolver =
+            MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, NamespaceId::new(42));
+
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+        let metrics = Arc::new(metric::Registry::default());
+        let authz = Arc::new(MockAuthorizer::default());
+        let delegate = HttpDelegate::new(
+            MAX_BYTES,
+            1,
+            mock_namespace_resolver,
+            Arc::clone(&dml_handler),
+            &metrics,
+            Box::new(SingleTenantRequestUnifier::new(authz)),
+        );
+
+        let table_request = |body: &'static str| {
+            Request::builder()
+                .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test")
+                .method("POST")
+                .extension(AuthorizationHeaderExtension::new(Some(
+                    HeaderValue::from_str(format!("Token {MOCK_AUTH_TABLE_TOKEN}").as_str())
+                        .unwrap(),
+                )))
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        // A write to the granted table only is accepted.
+        let got = delegate
+            .route(table_request("platanos,tag1=A val=42i 123456"))
+            .await;
+        assert!(got.is_ok());
+
+        // A write to any other table is rejected entirely.
+        let got = delegate
+            .route(table_request(
+                "platanos,tag1=A val=42i 123456\nbananas,tag1=A val=42i 123456",
+            ))
+            .await;
+        assert_matches!(
+            got,
+            Err(http::Error::SingleTenantError(
+                SingleTenantExtractError::Authorizer(authz::Error::Forbidden)
+            ))
+        );
+
+        // An empty write is not authorized by table permissions.
+        let got = delegate.route(table_request("")).await;
+        assert_matches!(
+            got,
+            Err(http::Error::SingleTenantError(
+                SingleTenantExtractError::Authorizer(authz::Error::Forbidden)
+            ))
+        );
+
+        let calls = dml_handler.calls();
+        assert_matches!(calls.as_slice(), [MockDmlHandlerCall::Write{namespace, write_input, ..}] => {
+            assert_eq!(namespace, NAMESPACE_NAME);
+            assert!(write_input.contains_key(MOCK_AUTH_TABLE_NAME));
+        })
+    }
+
     #[tokio::test]
     async fn test_authz_metric() {
         static NAMESPACE_NAME: &str = "test";
@@ -12,7 +12,7 @@ pub mod auth;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use auth::authorize;
+use auth::authorize_write;
 use authz::{self, Authorizer};
 use data_types::{NamespaceName, NamespaceNameError};
 use hyper::{Body, Request};
@@ -134,13 +134,15 @@ async fn parse_v1(
             )
         }
     })?;
-    authorize(authz, req, &namespace, write_params.password)
+    let table_authz = authorize_write(authz, req, &namespace, write_params.password)
         .await
         .map_err(SingleTenantExtractError::Authorizer)?;
 
     Ok(WriteParams {
         namespace,
         precision: write_params.precision,
+        accept_partial: write_params.accept_partial,
+        table_authz,
     })
 }
 
@@ -161,13 +163,15 @@ async fn parse_v2(
         return Err(SingleTenantExtractError::NoBucketSpecified);
     }
     let namespace = NamespaceName::new(write_params.bucket)?;
-    authorize(authz, req, &namespace, None)
+    let table_authz = authorize_write(authz, req, &namespace, None)
         .await
         .map_err(SingleTenantExtractError::Authorizer)?;
 
     Ok(WriteParams {
         namespace,
         precision: write_params.precision,
+        accept_partial: write_params.accept_partial,
+        table_authz,
     })
 }
 
@@ -304,7 +308,7 @@ mod tests {
     test_parse_v1!(
         no_rp,
         query_string = "?db=bananas",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas");
             assert_matches!(precision, Precision::Nanoseconds);
         }
@@ -314,7 +318,7 @@ mod tests {
     test_parse_v1!(
         no_rp_db_with_rp_separator,
         query_string = "?db=bananas/are/great",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas/are/great");
             assert_matches!(precision, Precision::Nanoseconds);
         }
@@ -324,7 +328,7 @@ mod tests {
     test_parse_v1!(
         rp_with_rp_separator,
         query_string = "?db=bananas&rp=are/great",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas/are/great");
             assert_matches!(precision, Precision::Nanoseconds);
         }
@@ -334,7 +338,7 @@ mod tests {
     test_parse_v1!(
         db_with_rp_separator_and_rp,
         query_string = "?db=foo/bar&rp=my_rp",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "foo/bar/my_rp");
             assert_matches!(precision, Precision::Nanoseconds);
         }
@@ -345,7 +349,7 @@ mod tests {
     test_parse_v1!(
         db_with_rp_separator_and_duplicate_rp,
         query_string = "?db=foo/my_rp&rp=my_rp",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "foo/my_rp/my_rp");
             assert_matches!(precision, Precision::Nanoseconds);
         }
@@ -355,7 +359,7 @@ mod tests {
     test_parse_v1!(
         db_with_rp_separator_and_rp_autogen,
         query_string = "?db=foo/bar&rp=autogen",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "foo/bar");
             assert_matches!(precision, Precision::Nanoseconds);
         }
@@ -365,7 +369,7 @@ mod tests {
     test_parse_v1!(
         db_with_rp_separator_and_rp_default,
         query_string = "?db=foo/bar&rp=default",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "foo/bar");
             assert_matches!(precision, Precision::Nanoseconds);
         }
@@ -374,7 +378,7 @@ mod tests {
     test_parse_v1!(
         rp_empty,
         query_string = "?db=bananas&rp=",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas");
             assert_matches!(precision, Precision::Nanoseconds);
         }
@@ -383,7 +387,7 @@ mod tests {
     test_parse_v1!(
         rp_empty_quotes,
         query_string = "?db=bananas&rp=''",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas");
             assert_matches!(precision, Precision::Nanoseconds);
         }
@@ -392,7 +396,7 @@ mod tests {
     test_parse_v1!(
         rp_autogen,
         query_string = "?db=bananas&rp=autogen",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas");
             assert_matches!(precision, Precision::Nanoseconds);
         }
@@ -401,7 +405,7 @@ mod tests {
     test_parse_v1!(
         rp_specified,
         query_string = "?db=bananas&rp=ageless",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas/ageless");
             assert_matches!(precision, Precision::Nanoseconds);
         }
@@ -442,7 +446,7 @@ mod tests {
     test_parse_v1!(
         with_precision,
         query_string = "?db=bananas&rp=ageless&precision=ms",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas/ageless");
             assert_matches!(precision, Precision::Milliseconds);
         }
@@ -489,7 +493,7 @@ mod tests {
     test_parse_v2!(
         bucket_only,
         query_string = "?bucket=bananas",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas");
             assert_matches!(precision, Precision::Nanoseconds);
         }
@@ -543,7 +547,8 @@ mod tests {
         query_string = "?org=wat&bucket=bananas",
         want = Ok(WriteParams {
             namespace,
-            precision
+            precision,
+            ..
         }) => {
             assert_eq!(namespace.as_str(), "bananas");
             assert_matches!(precision, Precision::Nanoseconds);
@@ -596,7 +601,8 @@ mod tests {
         query_string = "?bucket=bananas&precision=ms",
         want = Ok(WriteParams {
             namespace,
-            precision
+            precision,
+            ..
         }) => {
             assert_eq!(namespace.as_str(), "bananas");
             assert_matches!(precision, Precision::Milliseconds);
@@ -32,6 +32,8 @@ pub(crate) struct WriteParamsV2 {
 
     #[serde(default)]
     pub(crate) precision: Precision,
+    #[serde(default)]
+    pub(crate) accept_partial: bool,
 }
 
 impl<T> TryFrom<&Request<T>> for WriteParamsV2 {
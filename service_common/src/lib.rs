@@ -19,11 +19,14 @@ use workspace_hack as _;
 
 mod error;
 pub mod planner;
+pub mod table_access;
 pub mod test_util;
 
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use data_types::DeletePredicate;
+use datafusion::error::DataFusionError;
 use iox_query::QueryNamespace;
 use trace::span::Span;
 use tracker::InstrumentedAsyncOwnedSemaphorePermit;
@@ -49,6 +52,28 @@ pub trait QueryNamespaceProvider: std::fmt::Debug + Send + Sync + 'static {
 
     /// Acquire concurrency-limiting sempahore
     async fn acquire_semaphore(&self, span: Option<Span>) -> InstrumentedAsyncOwnedSemaphorePermit;
+
+    /// List the names of all namespaces.
+    async fn namespace_names(&self) -> Result<Vec<String>, DataFusionError>;
+
+    /// Create the namespace `name`, with the optional retention period.
+    ///
+    /// Creating a namespace that already exists is not an error.
+    async fn create_namespace(
+        &self,
+        name: &str,
+        retention_period_ns: Option<i64>,
+    ) -> Result<(), DataFusionError>;
+
+    /// Delete the rows matching `predicate` from the table `table_name` of
+    /// the namespace `name`, or from all its tables if `table_name` is
+    /// `None`.
+    async fn delete(
+        &self,
+        name: &str,
+        table_name: Option<&str>,
+        predicate: &DeletePredicate,
+    ) -> Result<(), DataFusionError>;
 }
 
 pub use error::datafusion_error_to_tonic_code;
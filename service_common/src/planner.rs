@@ -9,13 +9,18 @@ use flightsql::{FlightSQLCommand, FlightSQLPlanner};
 use iox_query::{
     exec::IOxSessionContext,
     frontend::sql::SqlQueryPlanner,
-    plan::{fieldlist::FieldListPlan, seriesset::SeriesSetPlans, stringset::StringSetPlan},
+    plan::{
+        cardinality::{SeriesCardinalityMode, SeriesCardinalityPlan},
+        fieldlist::FieldListPlan,
+        seriesset::SeriesSetPlans,
+        stringset::StringSetPlan,
+    },
     Aggregate, QueryNamespace, WindowDuration,
 };
 use iox_query_influxrpc::InfluxRpcPlanner;
 
 pub use datafusion::error::{DataFusionError as Error, Result};
-use iox_query_influxql::frontend::planner::InfluxQLQueryPlanner;
+use iox_query_influxql::frontend::{params::StatementParams, planner::InfluxQLQueryPlanner};
 use predicate::rpc_predicate::InfluxRpcPredicate;
 
 /// Query planner that plans queries on a separate threadpool.
@@ -50,17 +55,19 @@ impl Planner {
     }
 
     /// Plan an InfluxQL query against the data in `database`, and return a
-    /// DataFusion physical execution plan.
+    /// DataFusion physical execution plan. Bind parameters referenced by the
+    /// query are replaced with the values of `params`.
     pub async fn influxql(
         &self,
         query: impl Into<String> + Send,
+        params: StatementParams,
     ) -> Result<Arc<dyn ExecutionPlan>> {
         let planner = InfluxQLQueryPlanner::new();
         let query = query.into();
         let ctx = self.ctx.child_ctx("planner influxql");
 
         self.ctx
-            .run(async move { planner.query(&query, &ctx).await })
+            .run(async move { planner.query(&query, &params, &ctx).await })
             .await
     }
 
@@ -291,4 +298,27 @@ impl Planner {
             })
             .await
     }
+
+    /// Creates a plan as described on
+    /// [`InfluxRpcPlanner::series_cardinality`], on a separate threadpool
+    pub async fn series_cardinality<N>(
+        &self,
+        namespace: Arc<N>,
+        predicate: InfluxRpcPredicate,
+        mode: SeriesCardinalityMode,
+    ) -> Result<SeriesCardinalityPlan>
+    where
+        N: QueryNamespace + 'static,
+    {
+        let planner = InfluxRpcPlanner::new(self.ctx.child_ctx("planner series_cardinality")).await;
+
+        self.ctx
+            .run(async move {
+                planner
+                    .series_cardinality(namespace, predicate, mode)
+                    .await
+                    .map_err(|e| e.to_df_error("series_cardinality"))
+            })
+            .await
+    }
 }
@@ -0,0 +1,184 @@
+//! Restriction of the tables a query may access, for requests whose
+//! permissions are granted per table rather than per namespace.
+use std::{any::Any, collections::BTreeSet, sync::Arc};
+
+use async_trait::async_trait;
+use authz::Authorizer;
+use datafusion::{
+    catalog::{schema::SchemaProvider, CatalogProvider},
+    datasource::TableProvider,
+};
+use datafusion_util::config::DEFAULT_SCHEMA;
+use iox_query::exec::IOxSessionContext;
+use parking_lot::Mutex;
+
+/// The user tables a query planned with an [`IOxSessionContext`] is allowed
+/// to access, and those it was denied access to.
+///
+/// The tables are checked as the planner resolves them, see
+/// [`TableAccess::restrict`].
+#[derive(Debug, Clone)]
+pub struct TableAccess {
+    granted: Arc<BTreeSet<String>>,
+    denied: Arc<Mutex<BTreeSet<String>>>,
+}
+
+impl TableAccess {
+    /// The names of the user tables of the default catalog of `ctx`.
+    pub fn table_names(ctx: &IOxSessionContext) -> Vec<String> {
+        default_catalog(ctx)
+            .and_then(|(_, catalog)| catalog.schema(DEFAULT_SCHEMA))
+            .map(|schema| schema.table_names())
+            .unwrap_or_default()
+    }
+
+    /// Replace the default catalog of `ctx` with one restricted to the
+    /// `granted` user tables.
+    ///
+    /// The other tables are not listed by the catalog, and resolving any of
+    /// them while planning a query records it as denied, see
+    /// [`TableAccess::denied_tables`]. The other schemas, such as the system
+    /// tables, are not covered by table permissions and are hidden.
+    pub fn restrict(ctx: &IOxSessionContext, granted: impl IntoIterator<Item = String>) -> Self {
+        let access = Self {
+            granted: Arc::new(granted.into_iter().collect()),
+            denied: Default::default(),
+        };
+
+        if let Some((name, inner)) = default_catalog(ctx) {
+            ctx.inner().register_catalog(
+                name,
+                Arc::new(RestrictedCatalogProvider {
+                    inner,
+                    access: access.clone(),
+                }),
+            );
+        }
+
+        access
+    }
+
+    /// The names of the tables resolved so far which were not granted, in
+    /// lexicographic order.
+    pub fn denied_tables(&self) -> Vec<String> {
+        self.denied.lock().iter().cloned().collect()
+    }
+}
+
+/// The user tables of the namespace `namespace_name` queried with `ctx` which
+/// `authz_token` is granted read access to, for a token which is not granted
+/// read access to the whole namespace.
+///
+/// Returns [`authz::Error::Forbidden`] if no table is granted, as the token
+/// is then not authorized to query the namespace at all.
+pub async fn granted_tables(
+    authz: &dyn Authorizer,
+    ctx: &IOxSessionContext,
+    namespace_name: &str,
+    authz_token: Option<Vec<u8>>,
+) -> Result<Vec<String>, authz::Error> {
+    let perms = TableAccess::table_names(ctx)
+        .into_iter()
+        .map(|table| {
+            authz::Permission::ResourceAction(
+                authz::Resource::Table {
+                    database: namespace_name.to_string(),
+                    table,
+                },
+                authz::Action::Read,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let tables = authz
+        .permissions(authz_token, &perms)
+        .await?
+        .into_iter()
+        .filter_map(|perm| match perm {
+            authz::Permission::ResourceAction(authz::Resource::Table { table, .. }, _) => {
+                Some(table)
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if tables.is_empty() {
+        return Err(authz::Error::Forbidden);
+    }
+
+    Ok(tables)
+}
+
+/// The name and provider of the default catalog of `ctx`.
+fn default_catalog(ctx: &IOxSessionContext) -> Option<(String, Arc<dyn CatalogProvider>)> {
+    let name = ctx
+        .inner()
+        .copied_config()
+        .options()
+        .catalog
+        .default_catalog
+        .clone();
+    let catalog = ctx.inner().catalog(&name)?;
+    Some((name, catalog))
+}
+
+/// A [`CatalogProvider`] exposing the [`DEFAULT_SCHEMA`] of `inner` only,
+/// restricted by a [`TableAccess`].
+#[derive(Debug)]
+struct RestrictedCatalogProvider {
+    inner: Arc<dyn CatalogProvider>,
+    access: TableAccess,
+}
+
+impl CatalogProvider for RestrictedCatalogProvider {
+    fn as_any(&self) -> &dyn Any {
+        self as &dyn Any
+    }
+
+    fn schema_names(&self) -> Vec<String> {
+        vec![DEFAULT_SCHEMA.to_string()]
+    }
+
+    fn schema(&self, name: &str) -> Option<Arc<dyn SchemaProvider>> {
+        match name {
+            DEFAULT_SCHEMA => Some(Arc::new(RestrictedSchemaProvider {
+                inner: self.inner.schema(name)?,
+                access: self.access.clone(),
+            })),
+            _ => None,
+        }
+    }
+}
+
+/// A [`SchemaProvider`] listing the granted tables of `inner` only, and
+/// recording the other tables resolved in it as denied.
+struct RestrictedSchemaProvider {
+    inner: Arc<dyn SchemaProvider>,
+    access: TableAccess,
+}
+
+#[async_trait]
+impl SchemaProvider for RestrictedSchemaProvider {
+    fn as_any(&self) -> &dyn Any {
+        self as &dyn Any
+    }
+
+    fn table_names(&self) -> Vec<String> {
+        self.inner
+            .table_names()
+            .into_iter()
+            .filter(|name| self.access.granted.contains(name))
+            .collect()
+    }
+
+    async fn table(&self, name: &str) -> Option<Arc<dyn TableProvider>> {
+        let table = self.inner.table(name).await?;
+        if !self.access.granted.contains(name) {
+            self.access.denied.lock().insert(name.to_string());
+        }
+        Some(table)
+    }
+
+    fn table_exist(&self, name: &str) -> bool {
+        self.inner.table_exist(name)
+    }
+}
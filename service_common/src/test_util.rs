@@ -1,6 +1,8 @@
 use std::{collections::BTreeMap, sync::Arc};
 
 use async_trait::async_trait;
+use data_types::DeletePredicate;
+use datafusion::error::DataFusionError;
 use iox_query::{exec::Executor, test::TestDatabase};
 use parking_lot::Mutex;
 use trace::span::Span;
@@ -78,4 +80,32 @@ impl QueryNamespaceProvider for TestDatabaseStore {
             .await
             .unwrap()
     }
+
+    async fn namespace_names(&self) -> Result<Vec<String>, DataFusionError> {
+        Ok(self.databases.lock().keys().cloned().collect())
+    }
+
+    async fn create_namespace(
+        &self,
+        name: &str,
+        _retention_period_ns: Option<i64>,
+    ) -> Result<(), DataFusionError> {
+        self.db_or_create(name).await;
+        Ok(())
+    }
+
+    async fn delete(
+        &self,
+        name: &str,
+        _table_name: Option<&str>,
+        _predicate: &DeletePredicate,
+    ) -> Result<(), DataFusionError> {
+        // Test databases do not support deletes, so only the existence of
+        // the namespace is checked.
+        self.databases
+            .lock()
+            .contains_key(name)
+            .then_some(())
+            .ok_or_else(|| DataFusionError::Plan(format!("database not found: {name}")))
+    }
 }
@@ -0,0 +1,225 @@
+//! Implementation of the delete gRPC service
+
+#![deny(rustdoc::broken_intra_doc_links, rust_2018_idioms)]
+#![warn(
+    clippy::clone_on_ref_ptr,
+    clippy::dbg_macro,
+    clippy::explicit_iter_loop,
+    // See https://github.com/influxdata/influxdb_iox/pull/1671
+    clippy::future_not_send,
+    clippy::todo,
+    clippy::use_self,
+    missing_debug_implementations,
+    unused_crate_dependencies
+)]
+
+// Workaround for "unused crate" lint false positives.
+use workspace_hack as _;
+
+use std::sync::Arc;
+
+use data_types::{DeletePredicate, NamespaceId};
+use generated_types::influxdata::iox::delete::v1::*;
+use iox_catalog::interface::{delete_by_predicate, Catalog, SoftDeletedRows};
+use observability_deps::tracing::{debug, error, info};
+use tonic::{Request, Response, Status};
+
+/// Implementation of the delete gRPC service, recording deletes as tombstones
+/// in the catalog.
+#[derive(Debug)]
+pub struct DeleteService {
+    /// Catalog.
+    catalog: Arc<dyn Catalog>,
+}
+
+impl DeleteService {
+    /// Create a new `DeleteService` instance
+    pub fn new(catalog: Arc<dyn Catalog>) -> Self {
+        Self { catalog }
+    }
+}
+
+#[tonic::async_trait]
+impl delete_service_server::DeleteService for DeleteService {
+    async fn delete(
+        &self,
+        request: Request<DeleteRequest>,
+    ) -> Result<Response<DeleteResponse>, Status> {
+        let DeletePayload {
+            database_id,
+            table_name,
+            predicate,
+        } = request
+            .into_inner()
+            .payload
+            .ok_or_else(|| Status::invalid_argument("no delete payload specified"))?;
+
+        let predicate =
+            predicate.ok_or_else(|| Status::invalid_argument("no delete predicate specified"))?;
+        let predicate = DeletePredicate::try_from(predicate)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        // An empty table name deletes from all tables in the namespace.
+        let table_name = Some(table_name.as_str()).filter(|v| !v.is_empty());
+        let namespace_id = NamespaceId::new(database_id);
+
+        debug!(%namespace_id, ?table_name, ?predicate, "recording delete");
+
+        let mut repos = self.catalog.repositories().await;
+
+        repos
+            .namespaces()
+            .get_by_id(namespace_id, SoftDeletedRows::ExcludeDeleted)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .ok_or_else(|| {
+                Status::not_found(format!("Could not find a namespace with id {namespace_id}"))
+            })?;
+
+        let tombstones = delete_by_predicate(namespace_id, table_name, &predicate, &mut *repos)
+            .await
+            .map_err(|e| {
+                error!(error=%e, %namespace_id, ?table_name, "failed to record delete");
+                Status::internal(e.to_string())
+            })?;
+
+        info!(
+            %namespace_id,
+            ?table_name,
+            tombstones = tombstones.len(),
+            "recorded delete"
+        );
+
+        Ok(Response::new(DeleteResponse {}))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use generated_types::influxdata::iox::{
+        delete::v1::delete_service_server::DeleteService as _, predicate::v1 as proto,
+    };
+    use iox_catalog::{
+        mem::MemCatalog,
+        test_helpers::{arbitrary_namespace, arbitrary_table},
+    };
+    use tonic::Code;
+
+    use super::*;
+
+    fn predicate() -> proto::Predicate {
+        proto::Predicate {
+            range: Some(proto::TimestampRange { start: 1, end: 10 }),
+            exprs: vec![proto::Expr {
+                column: "region".to_string(),
+                op: proto::Op::Eq.into(),
+                scalar: Some(proto::Scalar {
+                    value: Some(proto::scalar::Value::ValueString("west".to_string())),
+                }),
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delete() {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+        let handler = DeleteService::new(Arc::clone(&catalog));
+
+        let (namespace, table_1, table_2) = {
+            let mut repos = catalog.repositories().await;
+            let namespace = arbitrary_namespace(&mut *repos, "bananas").await;
+            let table_1 = arbitrary_table(&mut *repos, "platanos", &namespace).await;
+            let table_2 = arbitrary_table(&mut *repos, "cavendish", &namespace).await;
+            (namespace, table_1, table_2)
+        };
+
+        // Delete from a single table
+        handler
+            .delete(Request::new(DeleteRequest {
+                payload: Some(DeletePayload {
+                    database_id: namespace.id.get(),
+                    table_name: table_1.name.clone(),
+                    predicate: Some(predicate()),
+                }),
+            }))
+            .await
+            .expect("delete should succeed");
+
+        let tombstones = catalog
+            .repositories()
+            .await
+            .tombstones()
+            .list_by_namespace_id(namespace.id)
+            .await
+            .unwrap();
+        assert_eq!(tombstones.len(), 1);
+        assert_eq!(tombstones[0].table_id, table_1.id);
+        assert_eq!(tombstones[0].serialized_predicate, r#""region"='west'"#);
+
+        // Delete from all tables
+        handler
+            .delete(Request::new(DeleteRequest {
+                payload: Some(DeletePayload {
+                    database_id: namespace.id.get(),
+                    table_name: String::new(),
+                    predicate: Some(predicate()),
+                }),
+            }))
+            .await
+            .expect("delete should succeed");
+
+        let tombstones = catalog
+            .repositories()
+            .await
+            .tombstones()
+            .list_by_table_id(table_2.id)
+            .await
+            .unwrap();
+        assert_eq!(tombstones.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_delete_invalid() {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+        let handler = DeleteService::new(Arc::clone(&catalog));
+        let namespace = arbitrary_namespace(&mut *catalog.repositories().await, "bananas").await;
+
+        // No payload
+        let err = handler
+            .delete(Request::new(DeleteRequest { payload: None }))
+            .await
+            .expect_err("delete should fail");
+        assert_eq!(err.code(), Code::InvalidArgument);
+
+        // No time range
+        let err = handler
+            .delete(Request::new(DeleteRequest {
+                payload: Some(DeletePayload {
+                    database_id: namespace.id.get(),
+                    table_name: String::new(),
+                    predicate: Some(proto::Predicate {
+                        range: None,
+                        ..predicate()
+                    }),
+                }),
+            }))
+            .await
+            .expect_err("delete should fail");
+        assert_eq!(err.code(), Code::InvalidArgument);
+
+        // Unknown namespace
+        let err = handler
+            .delete(Request::new(DeleteRequest {
+                payload: Some(DeletePayload {
+                    database_id: 4242,
+                    table_name: String::new(),
+                    predicate: Some(predicate()),
+                }),
+            }))
+            .await
+            .expect_err("delete should fail");
+        assert_eq!(err.code(), Code::NotFound);
+    }
+}
@@ -23,7 +23,7 @@ use workspace_hack as _;
 mod keep_alive;
 mod request;
 
-use arrow::error::ArrowError;
+use arrow::{error::ArrowError, record_batch::RecordBatch};
 use arrow_flight::{
     encode::FlightDataEncoderBuilder,
     flight_descriptor::DescriptorType,
@@ -38,10 +38,19 @@ use flightsql::FlightSQLCommand;
 use futures::{ready, Stream, StreamExt, TryStreamExt};
 use generated_types::influxdata::iox::querier::v1 as proto;
 use iox_query::{exec::IOxSessionContext, QueryCompletedToken, QueryNamespace};
+use iox_query_influxql::frontend::{
+    catalog::{empty_record_batch, show_databases_record_batch, CatalogStatement},
+    planner::InfluxQLQueryPlanner,
+};
 use observability_deps::tracing::{debug, info, warn};
 use prost::Message;
 use request::{IoxGetRequest, RunQuery};
-use service_common::{datafusion_error_to_tonic_code, planner::Planner, QueryNamespaceProvider};
+use service_common::{
+    datafusion_error_to_tonic_code,
+    planner::Planner,
+    table_access::{granted_tables, TableAccess},
+    QueryNamespaceProvider,
+};
 use snafu::{OptionExt, ResultExt, Snafu};
 use std::{
     fmt::Debug,
@@ -99,6 +108,13 @@ pub enum Error {
         source: DataFusionError,
     },
 
+    #[snafu(display("Error while executing statement: {}", source))]
+    CatalogStatement {
+        namespace_name: String,
+        query: String,
+        source: DataFusionError,
+    },
+
     #[snafu(display(
         "More than one headers are found in request: {:?}. \
     Please include only one of them",
@@ -166,7 +182,8 @@ impl From<Error> for tonic::Status {
             | Error::Unauthenticated { .. }
             | Error::PermissionDenied { .. }
             | Error::InvalidDatabaseName { .. }
-            | Error::Query { .. } => info!(e=%err, %namespace, %query, msg),
+            | Error::Query { .. }
+            | Error::CatalogStatement { .. } => info!(e=%err, %namespace, %query, msg),
             Error::Optimize { .. }
             | Error::EncodeSchema { .. }
             | Error::TooManyFlightSQLDatabases { .. }
@@ -200,9 +217,9 @@ impl Error {
             | Self::NoFlightSQLDatabase
             | Self::InvalidDatabaseHeader { .. }
             | Self::InvalidDatabaseName { .. } => tonic::Code::InvalidArgument,
-            Self::Planning { source, .. } | Self::Query { source, .. } => {
-                datafusion_error_to_tonic_code(&source)
-            }
+            Self::Planning { source, .. }
+            | Self::Query { source, .. }
+            | Self::CatalogStatement { source, .. } => datafusion_error_to_tonic_code(&source),
             Self::UnsupportedMessageType { .. } => tonic::Code::Unimplemented,
             Self::FlightSQL { source } => match source {
                 flightsql::Error::InvalidHandle { .. }
@@ -248,6 +265,7 @@ impl Error {
             | Error::Authz { .. } => "<unknown>",
             Error::DatabaseNotFound { namespace_name } => namespace_name,
             Error::Query { namespace_name, .. } => namespace_name,
+            Error::CatalogStatement { namespace_name, .. } => namespace_name,
             Error::Planning { namespace_name, .. } => namespace_name,
         }
     }
@@ -272,6 +290,7 @@ impl Error {
             | Error::Authz { .. }
             | Error::DatabaseNotFound { .. } => "NONE",
             Error::Query { query, .. } => query,
+            Error::CatalogStatement { query, .. } => query,
             Error::Planning { query, .. } => query,
         }
     }
@@ -487,6 +506,14 @@ where
     S: QueryNamespaceProvider,
 {
     /// Implementation of the `DoGet` method
+    ///
+    /// `influxql_namespace` is the namespace referenced by an InfluxQL
+    /// query, if it is qualified with one.
+    ///
+    /// If `restrict_tables` is set, the `authz_token` is not authorized to
+    /// read the whole namespace, and the query may only read the tables it
+    /// is granted access to.
+    #[allow(clippy::too_many_arguments)]
     async fn run_do_get(
         &self,
         span_ctx: Option<SpanContext>,
@@ -494,7 +521,10 @@ where
         permit: InstrumentedAsyncOwnedSemaphorePermit,
         query: RunQuery,
         namespace_name: String,
+        influxql_namespace: Option<String>,
         is_debug: bool,
+        authz_token: Option<Vec<u8>>,
+        restrict_tables: bool,
     ) -> Result<Response<TonicStream<FlightData>>, tonic::Status> {
         let db = self
             .server
@@ -503,41 +533,45 @@ where
                 span_ctx.child_span("get namespace"),
                 is_debug,
             )
-            .await
-            .context(DatabaseNotFoundSnafu {
-                namespace_name: &namespace_name,
-            })?;
+            .await;
+        // A token restricted to some tables is not told whether the
+        // namespace exists.
+        if db.is_none() && restrict_tables {
+            return Err(Error::PermissionDenied.into());
+        }
+        let db = db.context(DatabaseNotFoundSnafu {
+            namespace_name: &namespace_name,
+        })?;
 
         let ctx = db.new_query_context(span_ctx);
-        let (query_completed_token, physical_plan) = match &query {
+        let table_access = if restrict_tables {
+            Some(
+                self.restrict_tables(&ctx, &namespace_name, authz_token)
+                    .await?,
+            )
+        } else {
+            None
+        };
+        let (mut query_completed_token, physical_plan) = match &query {
             RunQuery::Sql(sql_query) => {
                 let token = db.record_query(
                     external_span_ctx.as_ref().map(RequestLogContext::ctx),
                     "sql",
                     Box::new(sql_query.clone()),
                 );
-                let plan = Planner::new(&ctx)
-                    .sql(sql_query)
-                    .await
-                    .context(PlanningSnafu {
-                        namespace_name: &namespace_name,
-                        query: query.to_string(),
-                    })?;
+                let plan = Planner::new(&ctx).sql(sql_query).await;
                 (token, plan)
             }
-            RunQuery::InfluxQL(sql_query) => {
+            RunQuery::InfluxQL(sql_query, params) => {
                 let token = db.record_query(
                     external_span_ctx.as_ref().map(RequestLogContext::ctx),
                     "influxql",
                     Box::new(sql_query.clone()),
                 );
-                let plan = Planner::new(&ctx)
-                    .influxql(sql_query)
-                    .await
-                    .context(PlanningSnafu {
-                        namespace_name: &namespace_name,
-                        query: query.to_string(),
-                    })?;
+                if let Some(other) = &influxql_namespace {
+                    register_namespace(self.server.as_ref(), &ctx, other, is_debug).await?;
+                }
+                let plan = Planner::new(&ctx).influxql(sql_query, params.clone()).await;
                 (token, plan)
             }
             RunQuery::FlightSQL(msg) => {
@@ -548,14 +582,36 @@ where
                 );
                 let plan = Planner::new(&ctx)
                     .flight_sql_do_get(&namespace_name, db, msg.clone())
-                    .await
+                    .await;
+                (token, plan)
+            }
+        };
+        // The tables are checked before any planning error is returned, as
+        // it may describe the tables which are not granted.
+        if let Some(table_access) = &table_access {
+            let denied = table_access.denied_tables();
+            if !denied.is_empty() {
+                debug!(
+                    %namespace_name,
+                    ?denied,
+                    "query reads tables it is not granted access to",
+                );
+                return Err(Error::PermissionDenied.into());
+            }
+        }
+        let physical_plan = match physical_plan {
+            Ok(plan) => plan,
+            Err(e) => {
+                query_completed_token.set_error(&e);
+                return Err(e)
                     .context(PlanningSnafu {
-                        namespace_name: &namespace_name,
+                        namespace_name,
                         query: query.to_string(),
-                    })?;
-                (token, plan)
+                    })
+                    .map_err(Into::into);
             }
         };
+        query_completed_token.set_plan(&ctx, Arc::clone(&physical_plan));
 
         let output = GetStream::new(
             ctx,
@@ -584,6 +640,48 @@ where
 
         Ok(Response::new(Box::pin(output) as TonicStream<FlightData>))
     }
+
+    /// Restrict the tables of the namespace `namespace_name` which queries
+    /// planned with `ctx` may read to those `authz_token` is granted read
+    /// access to.
+    async fn restrict_tables(
+        &self,
+        ctx: &IOxSessionContext,
+        namespace_name: &str,
+        authz_token: Option<Vec<u8>>,
+    ) -> Result<TableAccess> {
+        let tables = granted_tables(&self.authz, ctx, namespace_name, authz_token).await?;
+        Ok(TableAccess::restrict(ctx, tables))
+    }
+
+    /// Execute an InfluxQL statement which operates on the catalog, and
+    /// return its result as a single record batch.
+    async fn run_catalog_statement(
+        &self,
+        authz_token: Option<Vec<u8>>,
+        statement: CatalogStatement,
+        query: &RunQuery,
+        namespace_name: String,
+    ) -> Result<Response<TonicStream<FlightData>>, tonic::Status> {
+        let batch = execute_catalog_statement(
+            self.server.as_ref(),
+            &self.authz,
+            authz_token,
+            &namespace_name,
+            &query.to_string(),
+            statement,
+        )
+        .await?;
+
+        let app_metadata = proto::AppMetadata {};
+        let output = FlightDataEncoderBuilder::new()
+            .with_schema(batch.schema())
+            .with_metadata(app_metadata.encode_to_vec().into())
+            .build(futures::stream::iter([Ok(batch)]))
+            .map_err(tonic::Status::from);
+
+        Ok(Response::new(Box::pin(output) as TonicStream<FlightData>))
+    }
 }
 
 #[tonic::async_trait]
@@ -630,17 +728,80 @@ where
         let query = request.query();
         is_debug |= request.is_debug();
 
-        let perms = match query {
-            RunQuery::FlightSQL(cmd) => flightsql_permissions(namespace_name, cmd),
-            RunQuery::Sql(_) | RunQuery::InfluxQL(_) => vec![authz::Permission::ResourceAction(
-                authz::Resource::Database(namespace_name.to_string()),
-                authz::Action::Read,
-            )],
+        // InfluxQL statements which operate on the catalog are executed
+        // directly, rather than planned as queries.
+        let catalog_statement = match query {
+            RunQuery::InfluxQL(influxql_query, params) => {
+                InfluxQLQueryPlanner::new().catalog_statement(influxql_query, params)
+            }
+            RunQuery::Sql(_) | RunQuery::FlightSQL(_) => Ok(None),
         };
-        self.authz
-            .permissions(authz_token, &perms)
-            .await
-            .map_err(Error::from)?;
+
+        // InfluxQL queries may reference another namespace, through an ON
+        // clause or qualified measurement names.
+        let influxql_namespace = match (query, &catalog_statement) {
+            (RunQuery::InfluxQL(influxql_query, _), Ok(None)) => {
+                InfluxQLQueryPlanner::new().namespace(influxql_query)
+            }
+            _ => Ok(None),
+        };
+
+        let perms = match (query, &catalog_statement) {
+            (_, Ok(Some(statement))) => catalog_statement_permissions(namespace_name, statement),
+            (RunQuery::FlightSQL(cmd), _) => flightsql_permissions(namespace_name, cmd),
+            (RunQuery::Sql(_) | RunQuery::InfluxQL(_, _), _) => {
+                let mut perms = vec![authz::Permission::ResourceAction(
+                    authz::Resource::Database(namespace_name.to_string()),
+                    authz::Action::Read,
+                )];
+                if let Ok(Some(other)) = &influxql_namespace {
+                    if other != namespace_name {
+                        perms.push(authz::Permission::ResourceAction(
+                            authz::Resource::Database(other.clone()),
+                            authz::Action::Read,
+                        ));
+                    }
+                }
+                perms
+            }
+        };
+        // A query of a single namespace which is not authorized to read the
+        // whole namespace may still be granted access to the tables it
+        // reads, which are only known once it is planned.
+        let table_scoped = matches!(
+            (query, &catalog_statement, &influxql_namespace),
+            (
+                RunQuery::Sql(_) | RunQuery::InfluxQL(_, _),
+                Ok(None),
+                Ok(None)
+            )
+        );
+        let mut restrict_tables = false;
+        // The permissions for SHOW DATABASES are checked per namespace when
+        // the statement is executed.
+        if !perms.is_empty() {
+            let granted = match self.authz.permissions(authz_token.clone(), &perms).await {
+                Ok(granted) => granted,
+                Err(authz::Error::Forbidden) if table_scoped => vec![],
+                Err(e) => return Err(Error::from(e).into()),
+            };
+            // A query referencing several namespaces needs every permission.
+            if perms.iter().any(|perm| !granted.contains(perm)) {
+                if !table_scoped {
+                    return Err(Error::PermissionDenied.into());
+                }
+                restrict_tables = true;
+            }
+        }
+
+        let catalog_statement = catalog_statement.context(PlanningSnafu {
+            namespace_name,
+            query: query.to_string(),
+        })?;
+        let influxql_namespace = influxql_namespace.context(PlanningSnafu {
+            namespace_name,
+            query: query.to_string(),
+        })?;
 
         let permit = self
             .server
@@ -657,16 +818,31 @@ where
             "DoGet request",
         );
 
-        let response = self
-            .run_do_get(
-                span_ctx,
-                external_span_ctx.clone(),
-                permit,
-                query.clone(),
-                namespace_name.to_string(),
-                is_debug,
-            )
-            .await;
+        let response = match catalog_statement {
+            Some(statement) => {
+                self.run_catalog_statement(
+                    authz_token,
+                    statement,
+                    query,
+                    namespace_name.to_string(),
+                )
+                .await
+            }
+            None => {
+                self.run_do_get(
+                    span_ctx,
+                    external_span_ctx.clone(),
+                    permit,
+                    query.clone(),
+                    namespace_name.to_string(),
+                    influxql_namespace,
+                    is_debug,
+                    authz_token,
+                    restrict_tables,
+                )
+                .await
+            }
+        };
 
         if let Err(e) = &response {
             info!(
@@ -972,6 +1148,168 @@ fn flightsql_permissions(namespace_name: &str, cmd: &FlightSQLCommand) -> Vec<au
     vec![authz::Permission::ResourceAction(resource, action)]
 }
 
+/// Returns the permissions required to execute `statement` against the
+/// namespace `namespace_name`.
+///
+/// `SHOW DATABASES` requires no permissions up front, as only the
+/// namespaces the caller may read the schema of are listed.
+pub fn catalog_statement_permissions(
+    namespace_name: &str,
+    statement: &CatalogStatement,
+) -> Vec<authz::Permission> {
+    let (name, action) = match statement {
+        CatalogStatement::ShowDatabases => return vec![],
+        CatalogStatement::CreateDatabase { name, .. } => (name.as_str(), authz::Action::Create),
+        CatalogStatement::Delete { .. } | CatalogStatement::DropMeasurement { .. } => {
+            (namespace_name, authz::Action::Delete)
+        }
+    };
+    vec![authz::Permission::ResourceAction(
+        authz::Resource::Database(name.to_string()),
+        action,
+    )]
+}
+
+/// Register the catalog of the namespace `namespace_name` with `ctx`, under
+/// the name of the namespace, so that InfluxQL queries planned with `ctx` may
+/// reference it.
+pub async fn register_namespace<S>(
+    server: &S,
+    ctx: &IOxSessionContext,
+    namespace_name: &str,
+    is_debug: bool,
+) -> Result<()>
+where
+    S: QueryNamespaceProvider,
+{
+    let db = server
+        .db(
+            namespace_name,
+            ctx.child_span("get referenced namespace"),
+            is_debug,
+        )
+        .await
+        .context(DatabaseNotFoundSnafu { namespace_name })?;
+
+    let db_ctx = db.new_query_context(None);
+    let default_catalog = db_ctx
+        .inner()
+        .copied_config()
+        .options()
+        .catalog
+        .default_catalog
+        .clone();
+    let catalog = db_ctx
+        .inner()
+        .catalog(&default_catalog)
+        .context(DatabaseNotFoundSnafu { namespace_name })?;
+    ctx.inner().register_catalog(namespace_name, catalog);
+    Ok(())
+}
+
+/// Execute the InfluxQL statement `statement` (the text of which is
+/// `query`), which operates on the catalog, against the namespace
+/// `namespace_name`, and return its result as a single record batch.
+///
+/// The permissions of `authz_token` must have been checked, see
+/// [`catalog_statement_permissions`], except for `SHOW DATABASES`, which
+/// only lists the namespaces the token may read the schema of.
+pub async fn execute_catalog_statement<S>(
+    server: &S,
+    authz: &dyn Authorizer,
+    authz_token: Option<Vec<u8>>,
+    namespace_name: &str,
+    query: &str,
+    statement: CatalogStatement,
+) -> Result<RecordBatch>
+where
+    S: QueryNamespaceProvider,
+{
+    let context = CatalogStatementSnafu {
+        namespace_name,
+        query,
+    };
+
+    match statement {
+        CatalogStatement::ShowDatabases => {
+            let names = server.namespace_names().await.context(context.clone())?;
+            let names = readable_namespaces(authz, authz_token, names).await?;
+            show_databases_record_batch(names)
+        }
+        CatalogStatement::CreateDatabase {
+            name,
+            retention_period_ns,
+        } => {
+            server
+                .create_namespace(&name, retention_period_ns)
+                .await
+                .context(context.clone())?;
+            empty_record_batch()
+        }
+        CatalogStatement::Delete {
+            table_names: Some(table_names),
+            predicate,
+        } => {
+            for table_name in &table_names {
+                server
+                    .delete(namespace_name, Some(table_name), &predicate)
+                    .await
+                    .context(context.clone())?;
+            }
+            empty_record_batch()
+        }
+        CatalogStatement::Delete {
+            table_names: None,
+            predicate,
+        } => {
+            server
+                .delete(namespace_name, None, &predicate)
+                .await
+                .context(context.clone())?;
+            empty_record_batch()
+        }
+        CatalogStatement::DropMeasurement { table_name } => {
+            // The catalog does not support removing a table, so all
+            // of its rows are deleted instead.
+            server
+                .delete(
+                    namespace_name,
+                    Some(&table_name),
+                    &CatalogStatement::drop_measurement_predicate(),
+                )
+                .await
+                .context(context.clone())?;
+            empty_record_batch()
+        }
+    }
+    .context(context)
+}
+
+/// Returns the subset of `names` the `authz_token` may read the schema of.
+async fn readable_namespaces(
+    authz: &dyn Authorizer,
+    authz_token: Option<Vec<u8>>,
+    mut names: Vec<String>,
+) -> Result<Vec<String>> {
+    if names.is_empty() {
+        return Ok(names);
+    }
+
+    let read_schema = |name: &str| {
+        authz::Permission::ResourceAction(
+            authz::Resource::Database(name.to_string()),
+            authz::Action::ReadSchema,
+        )
+    };
+    let perms = names
+        .iter()
+        .map(|name| read_schema(name))
+        .collect::<Vec<_>>();
+    let granted = authz.permissions(authz_token, &perms).await?;
+    names.retain(|name| granted.contains(&read_schema(name)));
+    Ok(names)
+}
+
 /// Check if request has IOx debug header set.
 fn has_debug_header(metadata: &MetadataMap) -> bool {
     metadata
@@ -998,24 +1336,29 @@ impl GetStream {
         physical_plan: Arc<dyn ExecutionPlan>,
         namespace_name: String,
         query: &RunQuery,
-        query_completed_token: QueryCompletedToken,
+        mut query_completed_token: QueryCompletedToken,
         permit: InstrumentedAsyncOwnedSemaphorePermit,
     ) -> Result<Self, tonic::Status> {
         let app_metadata = proto::AppMetadata {};
 
         let schema = physical_plan.schema();
 
-        let query_results = ctx
-            .execute_stream(Arc::clone(&physical_plan))
-            .await
-            .context(QuerySnafu {
-                namespace_name: namespace_name.clone(),
-                query: query.to_string(),
-            })?
-            .map_err(|e| {
-                let code = datafusion_error_to_tonic_code(&e);
-                tonic::Status::new(code, e.to_string()).into()
-            });
+        let query_results = match ctx.execute_stream(Arc::clone(&physical_plan)).await {
+            Ok(query_results) => query_results,
+            Err(e) => {
+                query_completed_token.set_error(&e);
+                return Err(e)
+                    .context(QuerySnafu {
+                        namespace_name,
+                        query: query.to_string(),
+                    })
+                    .map_err(Into::into);
+            }
+        };
+        let query_results = query_results.map_err(|e| {
+            let code = datafusion_error_to_tonic_code(&e);
+            tonic::Status::new(code, e.to_string()).into()
+        });
 
         // setup inner stream
         let inner = FlightDataEncoderBuilder::new()
@@ -1059,6 +1402,7 @@ impl Stream for GetStream {
                 }
                 Some(Err(e)) => {
                     self.done = true;
+                    self.query_completed_token.set_error(&e);
                     return Poll::Ready(Some(Err(e.into())));
                 }
             }
@@ -1071,6 +1415,7 @@ mod tests {
     use async_trait::async_trait;
     use authz::Permission;
     use futures::Future;
+    use iox_query::test::TestChunk;
     use metric::{Attributes, Metric, U64Gauge};
     use service_common::test_util::TestDatabaseStore;
     use tokio::pin;
@@ -1306,7 +1651,14 @@ mod tests {
 
         fn influxql_request(authorization: &'static str) -> tonic::Request<arrow_flight::Ticket> {
             request(
-                RunQuery::InfluxQL("SHOW DATABASES".to_string()),
+                RunQuery::InfluxQL("SHOW DATABASES".to_string(), Default::default()),
+                authorization,
+            )
+        }
+
+        fn create_request(authorization: &'static str) -> tonic::Request<arrow_flight::Ticket> {
+            request(
+                RunQuery::InfluxQL("CREATE DATABASE apples".to_string(), Default::default()),
                 authorization,
             )
         }
@@ -1338,19 +1690,23 @@ mod tests {
 
         assert_code(&svc, tonic::Code::Unauthenticated, influxql_request("")).await;
 
+        assert_code(&svc, tonic::Code::Ok, influxql_request("Bearer GOOD")).await;
         assert_code(
             &svc,
-            tonic::Code::InvalidArgument, // SHOW DATABASE has not been implemented yet.
-            influxql_request("Bearer GOOD"),
+            tonic::Code::PermissionDenied,
+            influxql_request("Bearer BAD"),
         )
         .await;
+        assert_code(&svc, tonic::Code::Internal, influxql_request("Bearer UGLY")).await;
+
+        assert_code(&svc, tonic::Code::Unauthenticated, create_request("")).await;
+        assert_code(&svc, tonic::Code::Ok, create_request("Bearer GOOD")).await;
         assert_code(
             &svc,
             tonic::Code::PermissionDenied,
-            influxql_request("Bearer BAD"),
+            create_request("Bearer BAD"),
         )
         .await;
-        assert_code(&svc, tonic::Code::Internal, influxql_request("Bearer UGLY")).await;
 
         assert_code(&svc, tonic::Code::Unauthenticated, flightsql_request("")).await;
         assert_code(&svc, tonic::Code::Ok, flightsql_request("Bearer GOOD")).await;
@@ -1412,4 +1768,223 @@ mod tests {
         assert_code(&svc, tonic::Code::PermissionDenied, request("Bearer BAD")).await;
         assert_code(&svc, tonic::Code::Internal, request("Bearer UGLY")).await;
     }
+
+    /// Grants permissions on the "bananas" namespace only.
+    #[derive(Debug)]
+    struct BananasAuthorizer {}
+
+    #[async_trait]
+    impl Authorizer for BananasAuthorizer {
+        async fn permissions(
+            &self,
+            _token: Option<Vec<u8>>,
+            perms: &[Permission],
+        ) -> Result<Vec<Permission>, authz::Error> {
+            Ok(perms
+                .iter()
+                .filter(|perm| {
+                    matches!(
+                        perm,
+                        Permission::ResourceAction(authz::Resource::Database(name), _)
+                            if name == "bananas"
+                    )
+                })
+                .cloned()
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn do_get_show_databases_authz() {
+        let test_storage = Arc::new(TestDatabaseStore::default());
+        test_storage.db_or_create("apples").await;
+        test_storage.db_or_create("bananas").await;
+
+        let svc = FlightService {
+            server: Arc::clone(&test_storage),
+            authz: Some(Arc::new(BananasAuthorizer {})),
+        };
+
+        let ticket = IoxGetRequest::new(
+            "bananas".to_string(),
+            RunQuery::InfluxQL("SHOW DATABASES".to_string(), Default::default()),
+            false,
+        )
+        .try_encode()
+        .unwrap();
+        let stream = svc
+            .do_get(tonic::Request::new(ticket))
+            .await
+            .unwrap()
+            .into_inner()
+            .map_err(arrow_flight::error::FlightError::from);
+        let batches = arrow_flight::decode::FlightRecordBatchStream::new_from_flight_data(stream)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        // only the namespaces the caller may read the schema of are listed
+        let names = batches
+            .iter()
+            .flat_map(|batch| {
+                batch
+                    .column_by_name("name")
+                    .unwrap()
+                    .as_any()
+                    .downcast_ref::<arrow::array::StringArray>()
+                    .unwrap()
+                    .iter()
+                    .map(|name| name.unwrap().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["bananas"]);
+    }
+
+    #[tokio::test]
+    async fn do_get_influxql_on_database_authz() {
+        let test_storage = Arc::new(TestDatabaseStore::default());
+        test_storage.db_or_create("apples").await;
+        test_storage.db_or_create("bananas").await;
+
+        let svc = FlightService {
+            server: Arc::clone(&test_storage),
+            authz: Some(Arc::new(BananasAuthorizer {})),
+        };
+
+        async fn code(svc: &FlightService<TestDatabaseStore>, query: &str) -> tonic::Code {
+            let ticket = IoxGetRequest::new(
+                "bananas".to_string(),
+                RunQuery::InfluxQL(query.to_string(), Default::default()),
+                false,
+            )
+            .try_encode()
+            .unwrap();
+            match svc.do_get(tonic::Request::new(ticket)).await {
+                Ok(_) => tonic::Code::Ok,
+                Err(e) => e.code(),
+            }
+        }
+
+        assert_eq!(
+            code(&svc, "SHOW MEASUREMENTS ON bananas").await,
+            tonic::Code::Ok
+        );
+        assert_eq!(
+            code(&svc, "SELECT * FROM bananas..cpu").await,
+            tonic::Code::Ok
+        );
+        assert_eq!(
+            code(&svc, "SHOW MEASUREMENTS ON apples").await,
+            tonic::Code::PermissionDenied
+        );
+        assert_eq!(
+            code(&svc, "SELECT * FROM apples.autogen.cpu").await,
+            tonic::Code::PermissionDenied
+        );
+        assert_eq!(
+            code(&svc, "SELECT * FROM apples..cpu, bananas..cpu").await,
+            tonic::Code::InvalidArgument
+        );
+    }
+
+    /// Grants read access to the "cpu" table of the "bananas" namespace only.
+    #[derive(Debug)]
+    struct CpuTableAuthorizer {}
+
+    #[async_trait]
+    impl Authorizer for CpuTableAuthorizer {
+        async fn permissions(
+            &self,
+            _token: Option<Vec<u8>>,
+            perms: &[Permission],
+        ) -> Result<Vec<Permission>, authz::Error> {
+            let granted = perms
+                .iter()
+                .filter(|perm| {
+                    matches!(
+                        perm,
+                        Permission::ResourceAction(
+                            authz::Resource::Table { database, table },
+                            authz::Action::Read,
+                        ) if database == "bananas" && table == "cpu"
+                    )
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+            if granted.is_empty() {
+                return Err(authz::Error::Forbidden);
+            }
+            Ok(granted)
+        }
+    }
+
+    #[tokio::test]
+    async fn do_get_table_authz() {
+        let test_storage = Arc::new(TestDatabaseStore::default());
+        let chunk = |table_name: &str, id: u128| {
+            Arc::new(
+                TestChunk::new(table_name)
+                    .with_id(id)
+                    .with_tag_column("host")
+                    .with_i64_field_column("usage")
+                    .with_time_column(),
+            )
+        };
+        test_storage
+            .db_or_create("bananas")
+            .await
+            .add_chunk("p1", chunk("cpu", 1))
+            .add_chunk("p1", chunk("mem", 2));
+        test_storage
+            .db_or_create("apples")
+            .await
+            .add_chunk("p1", chunk("cpu", 1));
+
+        let svc = FlightService {
+            server: Arc::clone(&test_storage),
+            authz: Some(Arc::new(CpuTableAuthorizer {})),
+        };
+
+        async fn code(
+            svc: &FlightService<TestDatabaseStore>,
+            namespace: &str,
+            query: RunQuery,
+        ) -> tonic::Code {
+            let ticket = IoxGetRequest::new(namespace.to_string(), query, false)
+                .try_encode()
+                .unwrap();
+            match svc.do_get(tonic::Request::new(ticket)).await {
+                Ok(_) => tonic::Code::Ok,
+                Err(e) => e.code(),
+            }
+        }
+        let sql = |query: &str| RunQuery::Sql(query.to_string());
+        let influxql = |query: &str| RunQuery::InfluxQL(query.to_string(), Default::default());
+
+        assert_eq!(
+            code(&svc, "bananas", sql("SELECT * FROM cpu")).await,
+            tonic::Code::Ok
+        );
+        assert_eq!(
+            code(&svc, "bananas", influxql("SELECT * FROM cpu")).await,
+            tonic::Code::Ok
+        );
+        assert_eq!(
+            code(&svc, "bananas", sql("SELECT * FROM mem")).await,
+            tonic::Code::PermissionDenied
+        );
+        assert_eq!(
+            code(&svc, "bananas", sql("SELECT * FROM cpu, mem")).await,
+            tonic::Code::PermissionDenied
+        );
+        assert_eq!(
+            code(&svc, "bananas", influxql("SELECT * FROM mem")).await,
+            tonic::Code::PermissionDenied
+        );
+        assert_eq!(
+            code(&svc, "apples", sql("SELECT * FROM cpu")).await,
+            tonic::Code::PermissionDenied
+        );
+    }
 }
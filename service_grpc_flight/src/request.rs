@@ -5,11 +5,14 @@ use bytes::Bytes;
 use flightsql::FlightSQLCommand;
 use generated_types::google::protobuf::Any;
 use generated_types::influxdata::iox::querier::v1 as proto;
+use generated_types::influxdata::iox::querier::v1::query_param_value::Value as ParamValue;
 use generated_types::influxdata::iox::querier::v1::read_info::QueryType;
+use iox_query_influxql::frontend::params::{StatementParam, StatementParams};
 use observability_deps::tracing::trace;
 use prost::Message;
 use serde::Deserialize;
 use snafu::{ResultExt, Snafu};
+use std::collections::HashMap;
 use std::fmt::{Debug, Display, Formatter};
 
 #[derive(Debug, Snafu)]
@@ -90,6 +93,18 @@ enum AnyError {
 ///   "query_type": "influxql"
 /// }
 /// ```
+///
+/// InfluxQL queries may reference bind parameters, the values of which are
+/// provided as `params`
+///
+/// ```json
+/// {
+///   "database": "my_db",
+///   "sql_query": "SELECT usage FROM cpu WHERE host = $host;"
+///   "query_type": "influxql",
+///   "params": {"host": "server01"}
+/// }
+/// ```
 #[derive(Debug, PartialEq, Clone)]
 pub struct IoxGetRequest {
     database: String,
@@ -101,8 +116,8 @@ pub struct IoxGetRequest {
 pub enum RunQuery {
     /// Unparameterized SQL query
     Sql(String),
-    /// InfluxQL
-    InfluxQL(String),
+    /// InfluxQL, with the values of any bind parameters it references
+    InfluxQL(String, StatementParams),
     /// Execute a FlightSQL command. The payload is an encoded
     /// FlightSQL Command*. message that was received at the
     /// get_flight_info endpoint
@@ -113,7 +128,7 @@ impl RunQuery {
     pub fn variant(&self) -> &'static str {
         match self {
             Self::Sql(_) => "sql",
-            Self::InfluxQL(_) => "influxql",
+            Self::InfluxQL(_, _) => "influxql",
             Self::FlightSQL(_) => "flightsql",
         }
     }
@@ -123,7 +138,7 @@ impl Display for RunQuery {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Sql(s) => Display::fmt(s, f),
-            Self::InfluxQL(s) => Display::fmt(s, f),
+            Self::InfluxQL(s, _) => Display::fmt(s, f),
             Self::FlightSQL(s) => Display::fmt(s, f),
         }
     }
@@ -183,14 +198,19 @@ impl IoxGetRequest {
                 query_type: QueryType::Sql.into(),
                 flightsql_command: vec![],
                 is_debug,
+                params: HashMap::new(),
             },
-            RunQuery::InfluxQL(influxql) => proto::ReadInfo {
+            RunQuery::InfluxQL(influxql, params) => proto::ReadInfo {
                 database,
                 // field name is misleading
                 sql_query: influxql,
                 query_type: QueryType::InfluxQl.into(),
                 flightsql_command: vec![],
                 is_debug,
+                params: params
+                    .into_iter()
+                    .map(|(name, value)| (name, param_to_proto(value)))
+                    .collect(),
             },
             RunQuery::FlightSQL(flightsql_command) => proto::ReadInfo {
                 database,
@@ -201,6 +221,7 @@ impl IoxGetRequest {
                     .context(FlightSQLSnafu)?
                     .into(),
                 is_debug,
+                params: HashMap::new(),
             },
         };
 
@@ -229,6 +250,9 @@ impl IoxGetRequest {
             query_type: Option<String>,
             #[serde(default = "Default::default")]
             is_debug: bool,
+            // Bind parameters, only valid for InfluxQL
+            #[serde(default = "Default::default")]
+            params: HashMap<String, serde_json::Value>,
         }
 
         let ReadInfoJson {
@@ -236,21 +260,35 @@ impl IoxGetRequest {
             sql_query,
             query_type,
             is_debug,
+            params,
         } = serde_json::from_str(&json_str).map_err(|e| format!("JSON parse error: {e}"))?;
 
         let query = if let Some(query_type) = query_type {
             match query_type.as_str() {
-                "sql" => RunQuery::Sql(sql_query),
-                "influxql" => RunQuery::InfluxQL(sql_query),
+                "sql" if params.is_empty() => RunQuery::Sql(sql_query),
+                "sql" => return Err("params are only supported for InfluxQL queries".to_string()),
+                "influxql" => {
+                    let params = params
+                        .into_iter()
+                        .map(|(name, value)| {
+                            StatementParam::try_from(value)
+                                .map(|value| (name, value))
+                                .map_err(|e| e.to_string())
+                        })
+                        .collect::<Result<_, _>>()?;
+                    RunQuery::InfluxQL(sql_query, params)
+                }
                 _ => {
                     return Err(format!(
                         "unknown query type. Expected 'sql' or 'influxql', got {query_type}'"
                     ))
                 }
             }
-        } else {
+        } else if params.is_empty() {
             // default to SQL
             RunQuery::Sql(sql_query)
+        } else {
+            return Err("params are only supported for InfluxQL queries".to_string());
         };
 
         Ok(Self {
@@ -284,6 +322,7 @@ impl IoxGetRequest {
             query_type: _,
             flightsql_command,
             is_debug,
+            params,
         } = read_info;
 
         Ok(Self {
@@ -296,6 +335,12 @@ impl IoxGetRequest {
                         }
                         .fail();
                     }
+                    if !params.is_empty() {
+                        return InvalidContentSnafu {
+                            msg: "QueryType::Sql contained non empty params",
+                        }
+                        .fail();
+                    }
                     RunQuery::Sql(sql_query)
                 }
                 QueryType::InfluxQl => {
@@ -305,7 +350,14 @@ impl IoxGetRequest {
                         }
                         .fail();
                     }
-                    RunQuery::InfluxQL(sql_query)
+                    let params = params
+                        .into_iter()
+                        .map(|(name, value)| {
+                            let value = param_from_proto(&name, value)?;
+                            Ok((name, value))
+                        })
+                        .collect::<Result<_>>()?;
+                    RunQuery::InfluxQL(sql_query, params)
                 }
                 QueryType::FlightSqlMessage => {
                     if !sql_query.is_empty() {
@@ -314,6 +366,12 @@ impl IoxGetRequest {
                         }
                         .fail();
                     }
+                    if !params.is_empty() {
+                        return InvalidContentSnafu {
+                            msg: "QueryType::FlightSqlMessage contained non empty params",
+                        }
+                        .fail();
+                    }
                     let cmd = FlightSQLCommand::try_decode(flightsql_command.into())
                         .context(FlightSQLSnafu)?;
                     RunQuery::FlightSQL(cmd)
@@ -336,6 +394,33 @@ impl IoxGetRequest {
     }
 }
 
+fn param_to_proto(value: StatementParam) -> proto::QueryParamValue {
+    let value = match value {
+        StatementParam::Boolean(v) => ParamValue::BoolValue(v),
+        StatementParam::Integer(v) => ParamValue::Int64Value(v),
+        StatementParam::Unsigned(v) => ParamValue::Uint64Value(v),
+        StatementParam::Float(v) => ParamValue::DoubleValue(v),
+        StatementParam::String(v) => ParamValue::StringValue(v),
+    };
+    proto::QueryParamValue { value: Some(value) }
+}
+
+fn param_from_proto(name: &str, value: proto::QueryParamValue) -> Result<StatementParam> {
+    Ok(match value.value {
+        Some(ParamValue::BoolValue(v)) => StatementParam::Boolean(v),
+        Some(ParamValue::Int64Value(v)) => StatementParam::Integer(v),
+        Some(ParamValue::Uint64Value(v)) => StatementParam::Unsigned(v),
+        Some(ParamValue::DoubleValue(v)) => StatementParam::Float(v),
+        Some(ParamValue::StringValue(v)) => StatementParam::String(v),
+        None => {
+            return InvalidContentSnafu {
+                msg: format!("parameter {name} has no value"),
+            }
+            .fail()
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use arrow_flight::sql::CommandStatementQuery;
@@ -383,7 +468,7 @@ mod tests {
                     json,
                     expected: IoxGetRequest {
                         database: String::from(expected_database),
-                        query: RunQuery::InfluxQL(String::from(query)),
+                        query: RunQuery::InfluxQL(String::from(query), StatementParams::new()),
                         is_debug: false,
                     },
                 }
@@ -556,6 +641,37 @@ mod tests {
         assert_matches!(e, Error::Invalid);
     }
 
+    #[test]
+    fn json_ticket_decoding_params() {
+        let ticket = make_json_ticket(
+            r#"{"database": "my_db", "sql_query": "SELECT a FROM cpu WHERE host = $host AND a > $a", "query_type": "influxql", "params": {"host": "west", "a": 1}}"#,
+        );
+        let ri = IoxGetRequest::try_decode(ticket).unwrap();
+        assert_matches!(ri.query, RunQuery::InfluxQL(_, params) => {
+            assert_eq!(
+                params,
+                StatementParams::from([
+                    ("host".to_string(), StatementParam::String("west".into())),
+                    ("a".to_string(), StatementParam::Integer(1)),
+                ])
+            );
+        });
+
+        // unsupported parameter value
+        let ticket = make_json_ticket(
+            r#"{"database": "my_db", "sql_query": "SELECT a FROM cpu WHERE host = $host", "query_type": "influxql", "params": {"host": null}}"#,
+        );
+        let e = IoxGetRequest::try_decode(ticket).unwrap_err();
+        assert_matches!(e, Error::Invalid);
+
+        // params are only supported by InfluxQL
+        let ticket = make_json_ticket(
+            r#"{"database": "my_db", "sql_query": "SELECT 1", "params": {"a": 1}}"#,
+        );
+        let e = IoxGetRequest::try_decode(ticket).unwrap_err();
+        assert_matches!(e, Error::Invalid);
+    }
+
     #[test]
     fn proto_ticket_decoding_unspecified() {
         let ticket = make_proto_ticket(&proto::ReadInfo {
@@ -564,6 +680,7 @@ mod tests {
             query_type: QueryType::Unspecified.into(),
             flightsql_command: vec![],
             is_debug: false,
+            params: HashMap::new(),
         });
 
         // Reverts to default (unspecified) for invalid query_type enumeration, and thus SQL
@@ -580,6 +697,7 @@ mod tests {
             query_type: QueryType::Sql.into(),
             flightsql_command: vec![],
             is_debug: false,
+            params: HashMap::new(),
         });
 
         let ri = IoxGetRequest::try_decode(ticket).unwrap();
@@ -595,11 +713,15 @@ mod tests {
             query_type: QueryType::InfluxQl.into(),
             flightsql_command: vec![],
             is_debug: false,
+            params: HashMap::new(),
         });
 
         let ri = IoxGetRequest::try_decode(ticket).unwrap();
         assert_eq!(ri.database, "<foo>_<bar>");
-        assert_matches!(ri.query, RunQuery::InfluxQL(query) => assert_eq!(query, "SELECT 1"));
+        assert_matches!(ri.query, RunQuery::InfluxQL(query, params) => {
+            assert_eq!(query, "SELECT 1");
+            assert!(params.is_empty());
+        });
     }
 
     #[test]
@@ -610,6 +732,7 @@ mod tests {
             query_type: 42, // not a known query type
             flightsql_command: vec![],
             is_debug: false,
+            params: HashMap::new(),
         });
 
         // Reverts to default (unspecified) for invalid query_type enumeration, and thus SQL
@@ -627,6 +750,7 @@ mod tests {
             // can't have both sql_query and flightsql
             flightsql_command: vec![1, 2, 3],
             is_debug: false,
+            params: HashMap::new(),
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -642,6 +766,7 @@ mod tests {
             // can't have both sql_query and flightsql
             flightsql_command: vec![1, 2, 3],
             is_debug: false,
+            params: HashMap::new(),
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -657,6 +782,73 @@ mod tests {
             // can't have both sql_query and flightsql
             flightsql_command: vec![1, 2, 3],
             is_debug: false,
+            params: HashMap::new(),
+        });
+
+        let e = IoxGetRequest::try_decode(ticket).unwrap_err();
+        assert_matches!(e, Error::Invalid);
+    }
+
+    #[test]
+    fn proto_ticket_decoding_influxql_params() {
+        let ticket = make_proto_ticket(&proto::ReadInfo {
+            database: "<foo>_<bar>".to_string(),
+            sql_query: "SELECT 1 FROM cpu WHERE host = $host".to_string(),
+            query_type: QueryType::InfluxQl.into(),
+            flightsql_command: vec![],
+            is_debug: false,
+            params: HashMap::from([(
+                "host".to_string(),
+                proto::QueryParamValue {
+                    value: Some(ParamValue::StringValue("west".into())),
+                },
+            )]),
+        });
+
+        let ri = IoxGetRequest::try_decode(ticket).unwrap();
+        assert_matches!(ri.query, RunQuery::InfluxQL(_, params) => {
+            assert_eq!(
+                params,
+                StatementParams::from([(
+                    "host".to_string(),
+                    StatementParam::String("west".into())
+                )])
+            );
+        });
+    }
+
+    #[test]
+    fn proto_ticket_decoding_influxql_param_without_value() {
+        let read_info = proto::ReadInfo {
+            database: "<foo>_<bar>".to_string(),
+            sql_query: "SELECT 1 FROM cpu WHERE host = $host".to_string(),
+            query_type: QueryType::InfluxQl.into(),
+            flightsql_command: vec![],
+            is_debug: false,
+            params: HashMap::from([("host".to_string(), proto::QueryParamValue { value: None })]),
+        };
+
+        let e = IoxGetRequest::decode_protobuf(read_info.encode_to_vec().into()).unwrap_err();
+        assert_matches!(e, Error::InvalidContent { msg } => {
+            assert_eq!(msg, "parameter host has no value");
+        });
+    }
+
+    #[test]
+    fn proto_ticket_decoding_sql_params() {
+        let ticket = make_proto_ticket(&proto::ReadInfo {
+            database: "<foo>_<bar>".to_string(),
+            sql_query: "SELECT 1".to_string(),
+            query_type: QueryType::Sql.into(),
+            flightsql_command: vec![],
+            is_debug: false,
+            // params are only supported by InfluxQL
+            params: HashMap::from([(
+                "a".to_string(),
+                proto::QueryParamValue {
+                    value: Some(ParamValue::BoolValue(true)),
+                },
+            )]),
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -682,6 +874,7 @@ mod tests {
             query_type: QueryType::Unspecified.into(),
             flightsql_command: vec![],
             is_debug: false,
+            params: HashMap::new(),
         });
 
         // Reverts to default (unspecified) for invalid query_type enumeration, and thus SQL
@@ -698,6 +891,7 @@ mod tests {
             query_type: QueryType::Sql.into(),
             flightsql_command: vec![],
             is_debug: false,
+            params: HashMap::new(),
         });
 
         let ri = IoxGetRequest::try_decode(ticket).unwrap();
@@ -713,11 +907,15 @@ mod tests {
             query_type: QueryType::InfluxQl.into(),
             flightsql_command: vec![],
             is_debug: false,
+            params: HashMap::new(),
         });
 
         let ri = IoxGetRequest::try_decode(ticket).unwrap();
         assert_eq!(ri.database, "<foo>_<bar>");
-        assert_matches!(ri.query, RunQuery::InfluxQL(query) => assert_eq!(query, "SELECT 1"));
+        assert_matches!(ri.query, RunQuery::InfluxQL(query, params) => {
+            assert_eq!(query, "SELECT 1");
+            assert!(params.is_empty());
+        });
     }
 
     #[test]
@@ -728,6 +926,7 @@ mod tests {
             query_type: 42, // not a known query type
             flightsql_command: vec![],
             is_debug: false,
+            params: HashMap::new(),
         });
 
         // Reverts to default (unspecified) for invalid query_type enumeration, and thus SQL
@@ -745,6 +944,7 @@ mod tests {
             // can't have both sql_query and flightsql
             flightsql_command: vec![1, 2, 3],
             is_debug: false,
+            params: HashMap::new(),
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -760,6 +960,7 @@ mod tests {
             // can't have both sql_query and flightsql
             flightsql_command: vec![1, 2, 3],
             is_debug: false,
+            params: HashMap::new(),
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -775,6 +976,7 @@ mod tests {
             // can't have both sql_query and flightsql
             flightsql_command: vec![1, 2, 3],
             is_debug: false,
+            params: HashMap::new(),
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -825,7 +1027,31 @@ mod tests {
     fn round_trip_influxql() {
         let request = IoxGetRequest {
             database: "foo_blarg".into(),
-            query: RunQuery::InfluxQL("select * from bar".into()),
+            query: RunQuery::InfluxQL("select * from bar".into(), StatementParams::new()),
+            is_debug: false,
+        };
+
+        let ticket = request.clone().try_encode().expect("encoding failed");
+
+        let roundtripped = IoxGetRequest::try_decode(ticket).expect("decode failed");
+
+        assert_eq!(request, roundtripped)
+    }
+
+    #[test]
+    fn round_trip_influxql_params() {
+        let request = IoxGetRequest {
+            database: "foo_blarg".into(),
+            query: RunQuery::InfluxQL(
+                "select * from bar where a = $a and b = $b".into(),
+                StatementParams::from([
+                    ("a".to_string(), StatementParam::Boolean(true)),
+                    ("b".to_string(), StatementParam::Integer(-1)),
+                    ("c".to_string(), StatementParam::Unsigned(u64::MAX)),
+                    ("d".to_string(), StatementParam::Float(1.5)),
+                    ("e".to_string(), StatementParam::String("foo".into())),
+                ]),
+            ),
             is_debug: false,
         };
 
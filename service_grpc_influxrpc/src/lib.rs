@@ -16,6 +16,7 @@
 )]
 
 // Workaround for "unused crate" lint false positives.
+use tokio_stream as _;
 use workspace_hack as _;
 
 // rustc doesn't seem to understand test-only requirements
@@ -22,6 +22,7 @@ use generated_types::{
     literal_or_regex::Value as RegexOrLiteralValue,
     offsets_response::PartitionOffsetResponse,
     read_response::Frame,
+    read_series_cardinality_request,
     storage_server::Storage,
     tag_key_predicate, CapabilitiesResponse, Capability, Int64ValuesResponse, LiteralOrRegex,
     MeasurementFieldsRequest, MeasurementFieldsResponse, MeasurementNamesRequest,
@@ -35,6 +36,7 @@ use iox_query::{
     exec::{
         fieldlist::FieldList, seriesset::converter::Error as SeriesSetError, IOxSessionContext,
     },
+    plan::cardinality::SeriesCardinalityMode,
     QueryCompletedToken, QueryNamespace, QueryText,
 };
 use observability_deps::tracing::{error, info, trace};
@@ -46,7 +48,6 @@ use std::{
     fmt::{Display, Formatter, Result as FmtResult},
     sync::Arc,
 };
-use tokio_stream::wrappers::ReceiverStream;
 use tonic::{metadata::MetadataMap, Response, Status};
 use trace::{ctx::SpanContext, span::SpanExt};
 use trace_http::ctx::{RequestLogContext, RequestLogContextExt};
@@ -109,6 +110,15 @@ pub enum Error {
         source: DataFusionError,
     },
 
+    #[snafu(display("Error counting series in namespace '{}': {}", db_name, source))]
+    CountingSeries {
+        db_name: String,
+        source: DataFusionError,
+    },
+
+    #[snafu(display("Unknown series cardinality mode: {}", mode))]
+    UnknownCardinalityMode { mode: i32 },
+
     #[snafu(display(
         "Can not retrieve tag values for '{}' in namespace '{}': {}",
         tag_name,
@@ -218,6 +228,7 @@ impl Error {
             | Self::PlanningGroupSeries { source, .. }
             | Self::FilteringSeries { source, .. }
             | Self::GroupingSeries { source, .. }
+            | Self::CountingSeries { source, .. }
             | Self::ListingTagValues { source, .. } => datafusion_error_to_tonic_code(&source),
             Self::ConvertingPredicate { source, .. }
             | Self::ConvertingReadGroupType { source, .. }
@@ -242,7 +253,8 @@ impl Error {
             | Self::SettingPredicateTable { .. }
             | Self::MeasurementLiteralOrRegex { .. }
             | Self::MissingTagKeyPredicate {}
-            | Self::InvalidTagKeyRegex { .. } => tonic::Code::InvalidArgument,
+            | Self::InvalidTagKeyRegex { .. }
+            | Self::UnknownCardinalityMode { .. } => tonic::Code::InvalidArgument,
             Self::SendingResults { .. } | Self::InternalHintsFieldNotSupported { .. } => {
                 tonic::Code::Internal
             }
@@ -804,13 +816,66 @@ where
         )
     }
 
-    type ReadSeriesCardinalityStream = ReceiverStream<Result<Int64ValuesResponse, Status>>;
+    type ReadSeriesCardinalityStream = StreamWithPermit<
+        QueryCompletedTokenStream<
+            BoxStream<'static, Result<Int64ValuesResponse, Status>>,
+            Int64ValuesResponse,
+            Status,
+        >,
+    >;
 
     async fn read_series_cardinality(
         &self,
-        _req: tonic::Request<ReadSeriesCardinalityRequest>,
+        req: tonic::Request<ReadSeriesCardinalityRequest>,
     ) -> Result<Response<Self::ReadSeriesCardinalityStream>, Status> {
-        unimplemented!("read_series_cardinality not yet implemented. https://github.com/influxdata/influxdb_iox/issues/447");
+        let external_span_ctx: Option<RequestLogContext> = req.extensions().get().cloned();
+        let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
+
+        let req = req.into_inner();
+        let permit = self
+            .db_store
+            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .await;
+
+        let db_name = get_namespace_name(&req)?;
+        info!(
+            %db_name,
+            ?req.range,
+            predicate=%req.predicate.loggable(),
+            mode=req.mode,
+            trace=%external_span_ctx.format_jaeger(),
+            "read_series_cardinality",
+        );
+
+        let db = self
+            .db_store
+            .db(&db_name, span_ctx.child_span("get namespace"), false)
+            .await
+            .context(NamespaceNotFoundSnafu { db_name: &db_name })?;
+
+        let ctx = db.new_query_context(span_ctx);
+        let query_completed_token = db.record_query(
+            external_span_ctx.as_ref().map(RequestLogContext::ctx),
+            "read_series_cardinality",
+            defer_json(&req),
+        );
+
+        let ReadSeriesCardinalityRequest {
+            read_series_cardinality_source: _source,
+            range,
+            predicate,
+            mode,
+        } = req;
+
+        let response = series_cardinality_impl(db, db_name, range, predicate, mode, &ctx)
+            .await
+            .map_err(|e| e.into_status());
+
+        make_response(
+            futures::stream::once(async move { response }).boxed(),
+            query_completed_token,
+            permit,
+        )
     }
 
     async fn capabilities(
@@ -1245,6 +1310,54 @@ where
     Ok(StringValuesResponse { values })
 }
 
+/// Return the number of series with optional timestamp and arbitrary
+/// predicates
+async fn series_cardinality_impl<N>(
+    db: Arc<N>,
+    db_name: NamespaceName<'static>,
+    range: Option<TimestampRange>,
+    rpc_predicate: Option<Predicate>,
+    mode: i32,
+    ctx: &IOxSessionContext,
+) -> Result<Int64ValuesResponse>
+where
+    N: QueryNamespace + 'static,
+{
+    let rpc_predicate_string = format!("{rpc_predicate:?}");
+    let db_name = db_name.as_str();
+
+    let mode = match read_series_cardinality_request::Mode::try_from(mode) {
+        Ok(read_series_cardinality_request::Mode::Exact) => SeriesCardinalityMode::Exact,
+        Ok(read_series_cardinality_request::Mode::Approximate) => {
+            SeriesCardinalityMode::Approximate
+        }
+        Err(_) => return UnknownCardinalityModeSnafu { mode }.fail(),
+    };
+
+    let predicate = InfluxRpcPredicateBuilder::default()
+        .set_range(range)
+        .rpc_predicate(rpc_predicate)
+        .context(ConvertingPredicateSnafu {
+            rpc_predicate_string,
+        })?
+        .build();
+
+    let plan = Planner::new(ctx)
+        .series_cardinality(db, predicate, mode)
+        .await
+        .context(CountingSeriesSnafu { db_name })?;
+
+    let cardinality = ctx
+        .to_series_cardinality(plan)
+        .await
+        .context(CountingSeriesSnafu { db_name })?;
+
+    trace!(cardinality, "Series cardinality response");
+    Ok(Int64ValuesResponse {
+        values: vec![cardinality as i64],
+    })
+}
+
 /// Return tag values for tag_name, with optional measurement, timestamp and
 /// arbitratry predicates
 async fn tag_values_impl<N>(
@@ -2686,6 +2799,90 @@ mod tests {
         grpc_request_metric_has_count(&fixture, "ReadFilter", "ok", 1);
     }
 
+    #[tokio::test]
+    async fn test_read_series_cardinality() {
+        test_helpers::maybe_start_logging();
+        // Start a test gRPC server on a randomally allocated port
+        let mut fixture = Fixture::new().await.expect("Connecting to test server");
+
+        let db_info = org_and_bucket();
+
+        // 3 tag sets with one field
+        let chunk0 = TestChunk::new("m1")
+            .with_id(0)
+            .with_tag_column("tag1")
+            .with_i64_field_column("field_int")
+            .with_time_column()
+            .with_three_rows_of_data();
+
+        // 3 tag sets with two fields
+        let chunk1 = TestChunk::new("m2")
+            .with_id(1)
+            .with_tag_column("state")
+            .with_i64_field_column("field_int")
+            .with_u64_column("field_uint")
+            .with_time_column()
+            .with_three_rows_of_data();
+
+        fixture
+            .test_storage
+            .db_or_create(db_info.db_name())
+            .await
+            .add_chunk("my_partition_key", Arc::new(chunk0))
+            .add_chunk("my_partition_key", Arc::new(chunk1));
+
+        let source = Some(StorageClient::read_source(&db_info, 1));
+
+        let request = ReadSeriesCardinalityRequest {
+            read_series_cardinality_source: source.clone(),
+            range: Some(make_timestamp_range(0, 100_000)),
+            predicate: None,
+            mode: read_series_cardinality_request::Mode::Exact as i32,
+        };
+
+        let actual = fixture
+            .storage_client
+            .read_series_cardinality(request.clone())
+            .await
+            .unwrap();
+        assert_eq!(actual, vec![9]);
+
+        // only the series with rows in the time range are counted
+        let actual = fixture
+            .storage_client
+            .read_series_cardinality(ReadSeriesCardinalityRequest {
+                range: Some(make_timestamp_range(0, 9000)),
+                ..request.clone()
+            })
+            .await
+            .unwrap();
+        assert_eq!(actual, vec![3]);
+
+        let actual = fixture
+            .storage_client
+            .read_series_cardinality(ReadSeriesCardinalityRequest {
+                mode: read_series_cardinality_request::Mode::Approximate as i32,
+                ..request.clone()
+            })
+            .await
+            .unwrap();
+        assert_eq!(actual.len(), 1);
+        assert!((actual[0] - 9).abs() <= 1, "unexpected estimate {actual:?}");
+
+        let status = fixture
+            .storage_client
+            .read_series_cardinality(ReadSeriesCardinalityRequest {
+                mode: 42,
+                ..request
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+        grpc_request_metric_has_count(&fixture, "ReadSeriesCardinality", "ok", 3);
+        grpc_request_metric_has_count(&fixture, "ReadSeriesCardinality", "client_error", 1);
+    }
+
     #[tokio::test]
     async fn test_read_filter_empty_string() {
         test_helpers::maybe_start_logging();
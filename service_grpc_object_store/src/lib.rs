@@ -136,6 +136,7 @@ mod tests {
                 row_count: 29,
                 compaction_level: CompactionLevel::Initial,
                 created_at: Timestamp::new(2343),
+                data_written_at: Timestamp::new(2343),
                 column_set: ColumnSet::new([ColumnId::new(1), ColumnId::new(2)]),
                 max_l0_created_at: Timestamp::new(2343),
             };
@@ -177,6 +177,19 @@ impl TestConfig {
         )
     }
 
+    /// Forward schema changes requested through the querier to the given router.
+    pub fn with_querier_router(self, router_config: &Self) -> Self {
+        assert_eq!(router_config.server_type(), ServerType::Router);
+        self.with_env(
+            "INFLUXDB_IOX_QUERIER_ROUTER_GRPC_ADDRESS",
+            router_config
+                .addrs()
+                .router_grpc_api()
+                .client_base()
+                .to_string(),
+        )
+    }
+
     /// Configure tracing capture
     pub fn with_tracing(self, udp_capture: &UdpCapture) -> Self {
         self.with_env("TRACES_EXPORTER", "jaeger")
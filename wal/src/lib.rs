@@ -203,7 +203,7 @@ pub(crate) fn build_segment_path(dir: impl Into<PathBuf>, id: SegmentId) -> Path
 type FileTypeIdentifier = [u8; 8];
 const FILE_TYPE_IDENTIFIER: &FileTypeIdentifier = b"INFLUXV3";
 /// File extension for segment files.
-const SEGMENT_FILE_EXTENSION: &str = "dat";
+pub const SEGMENT_FILE_EXTENSION: &str = "dat";
 
 /// The main type representing one WAL for one ingester instance.
 ///
@@ -236,6 +236,18 @@ impl Wal {
     /// Similarly, editing or deleting files within a `Wal`'s root directory via some other
     /// mechanism is not supported.
     pub async fn new(root: impl Into<PathBuf>) -> Result<Arc<Self>> {
+        Self::new_with_min_segment_id(root, SegmentId::new(0)).await
+    }
+
+    /// Creates a `Wal` instance like [`Wal::new()`], that never assigns an ID lower than
+    /// `min_segment_id` to a new segment file.
+    ///
+    /// This keeps segment IDs unique when copies of segment files that are no longer in the root
+    /// directory are kept elsewhere.
+    pub async fn new_with_min_segment_id(
+        root: impl Into<PathBuf>,
+        min_segment_id: SegmentId,
+    ) -> Result<Arc<Self>> {
         let root = root.into();
         info!(wal_dir=?root, "Initalizing Write Ahead Log (WAL)");
         tokio::fs::create_dir_all(&root)
@@ -289,7 +301,8 @@ impl Wal {
             .last()
             .copied()
             .map(|id| id.get() + 1)
-            .unwrap_or(0);
+            .unwrap_or(0)
+            .max(min_segment_id.get());
         let next_id_source = Arc::new(AtomicU64::new(next_id));
         let open_segment =
             OpenSegmentFileWriter::new_in_directory(&root, Arc::clone(&next_id_source))
@@ -671,6 +684,11 @@ impl ClosedSegment {
     pub fn size(&self) -> u64 {
         self.size
     }
+
+    /// The path of the segment file on disk.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
 }
 
 #[cfg(test)]
@@ -809,6 +827,25 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn min_segment_id() {
+        let dir = test_helpers::tmp_dir().unwrap();
+
+        let wal = Wal::new_with_min_segment_id(dir.path(), SegmentId::new(42))
+            .await
+            .unwrap();
+        let (closed, _) = wal.rotate().unwrap();
+        assert_eq!(closed.id(), SegmentId::new(42));
+        drop(wal);
+
+        // Existing segment files with higher IDs take precedence.
+        let wal = Wal::new_with_min_segment_id(dir.path(), SegmentId::new(1))
+            .await
+            .unwrap();
+        let (closed, _) = wal.rotate().unwrap();
+        assert!(closed.id() > SegmentId::new(42));
+    }
+
     #[tokio::test]
     async fn decode_write_op_entries() {
         let dir = test_helpers::tmp_dir().unwrap();